@@ -44,7 +44,17 @@ pub fn format_cc_ident(ident: &str) -> Result<TokenStream> {
 
 /// Makes an 'Ident' to be used in the Rust source code. Escapes Rust keywords.
 /// Panics if `ident` is empty or is otherwise an invalid identifier.
+///
+/// `crate`, `self`, `super` and `Self` can't be escaped as raw identifiers (see
+/// https://doc.rust-lang.org/reference/identifiers.html#raw-identifiers), so
+/// they are renamed by appending an underscore (e.g. `self` becomes `self_`).
+/// The new name may collide with another identifier in the same scope (e.g. a
+/// C++ struct with both a `self` and a `self_` field), which callers have to
+/// check for.
 pub fn make_rs_ident(ident: &str) -> Ident {
+    if ["crate", "self", "super", "Self"].contains(&ident) {
+        return format_ident!("{}_", ident);
+    }
     // TODO(https://github.com/dtolnay/syn/pull/1098): Remove the hardcoded list once syn recognizes
     // 2018 and 2021 keywords.
     if ["async", "await", "try", "dyn"].contains(&ident) {
@@ -102,6 +112,30 @@ pub fn escape_non_identifier_chars(symbol: &str) -> String {
     result
 }
 
+/// Converts a `CamelCase` or `camelCase` identifier to `snake_case`.
+///
+/// Runs of uppercase letters are treated as a single word (e.g. `HTTPServer`
+/// becomes `http_server`). Identifiers which are already `snake_case` are
+/// returned unchanged.
+pub fn to_snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect_vec();
+    let mut result = String::with_capacity(ident.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lowercase)
+            {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
 /// Representation of `foo::bar::baz` where each component is either the name
 /// of a C++ namespace, or the name of a Rust module.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
@@ -232,6 +266,18 @@ impl CcInclude {
         Self::SystemHeader("cstdint")
     }
 
+    /// Creates a `CcInclude` that represents `#include <cerrno>` and provides
+    /// the `errno` macro.  See https://en.cppreference.com/w/cpp/header/cerrno
+    pub fn cerrno() -> Self {
+        Self::SystemHeader("cerrno")
+    }
+
+    /// Creates a `CcInclude` that represents `#include <exception>` and provides
+    /// `std::exception_ptr`.  See https://en.cppreference.com/w/cpp/header/exception
+    pub fn exception() -> Self {
+        Self::SystemHeader("exception")
+    }
+
     /// Creates a `CcInclude` that represents `#include <memory>`.
     /// See https://en.cppreference.com/w/cpp/header/memory
     pub fn memory() -> Self {
@@ -252,6 +298,13 @@ impl CcInclude {
         Self::SystemHeader("type_traits")
     }
 
+    /// Creates a `CcInclude` that represents `#include <sstream>` and provides
+    /// C++ types like `std::ostringstream`.
+    /// See https://en.cppreference.com/w/cpp/header/sstream
+    pub fn sstream() -> Self {
+        Self::SystemHeader("sstream")
+    }
+
     /// Creates a user include: `#include "some/path/to/header.h"`.
     pub fn user_header(path: Rc<str>) -> Self {
         Self::UserHeader(path)
@@ -516,6 +569,14 @@ pub mod tests {
         assert_rs_matches!(quote! { #id }, quote! { r#impl });
     }
 
+    #[test]
+    fn test_make_rs_ident_unescapable_rust_keyword() {
+        let id = make_rs_ident("self");
+        assert_rs_matches!(quote! { #id }, quote! { self_ });
+        let id = make_rs_ident("Self");
+        assert_rs_matches!(quote! { #id }, quote! { Self_ });
+    }
+
     #[test]
     #[should_panic]
     fn test_make_rs_ident_unfinished_group() {
@@ -763,4 +824,23 @@ pub mod tests {
         let empty_vec: Vec<&'static str> = vec![];
         assert_eq!(empty_vec, duplicate_expectations);
     }
+
+    #[test]
+    fn test_to_snake_case() {
+        let tests = vec![
+            ("", ""),
+            ("foo", "foo"),
+            ("foo_bar", "foo_bar"),
+            ("FooBar", "foo_bar"),
+            ("fooBar", "foo_bar"),
+            ("GetX", "get_x"),
+            ("HTTPServer", "http_server"),
+            ("ParseHTTP", "parse_http"),
+            ("Utf8Decode", "utf8_decode"),
+            ("Foo_Bar", "foo_bar"),
+        ];
+        for (input, expected_output) in tests {
+            assert_eq!(to_snake_case(input), expected_output, "input: {input}");
+        }
+    }
 }
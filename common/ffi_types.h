@@ -46,6 +46,47 @@ enum SourceLocationDocComment {
   Enabled,
 };
 
+// The symbol visibility of the C++ thunks emitted in the generated bindings.
+//
+// Exporting the thunks under a linker version script is out of scope: the
+// bindings don't come with one.
+enum class ThunkVisibility : int {
+  // The default visibility: the thunks are exported from shared libraries.
+  Default,
+  // `__attribute__((visibility("hidden")))`.
+  Hidden,
+  // `__attribute__((visibility("internal")))`: like `Hidden`, and the thunks
+  // are never called from another module, not even through function pointers.
+  Internal,
+};
+
+// Which functions taking raw pointers are bound as `unsafe fn`.
+enum class PointerUnsafety : int {
+  // Any raw pointer in the parameter types (e.g. also `int*&` or `int**`).
+  AnyPointer,
+  // Only parameters that are themselves raw pointers.
+  TopLevelPointer,
+};
+
+// What happens when a C++ function called through a thunk throws an exception.
+enum class ExceptionPolicy : int {
+  // Nothing: the exception unwinds into Rust, which is undefined behavior.
+  // Only suitable for code compiled with `-fno-exceptions`.
+  Unchecked,
+  // The thunks are `noexcept`, so the program terminates.
+  Abort,
+  // The exception is caught and returned to Rust as a `CxxException` error.
+  Catch,
+};
+
+// How the parameters with default arguments of C++ functions are bound.
+enum class DefaultArguments : int {
+  // As additional `<name>_without_<param>` functions without them.
+  Overloads,
+  // As `Option<T>` parameters, where `None` uses the default argument.
+  Option,
+};
+
 }  // namespace crubit
 
 #endif  // CRUBIT_COMMON_FFI_TYPES_H_
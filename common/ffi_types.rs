@@ -100,6 +100,56 @@ pub enum SourceLocationDocComment {
     Enabled,
 }
 
+/// The symbol visibility of the C++ thunks emitted in the generated bindings.
+///
+/// Exporting the thunks under a linker version script is out of scope: the
+/// bindings don't come with one.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ThunkVisibility {
+    /// The default visibility: the thunks are exported from shared libraries.
+    Default,
+    /// `__attribute__((visibility("hidden")))`.
+    Hidden,
+    /// `__attribute__((visibility("internal")))`: like `Hidden`, and the thunks
+    /// are never called from another module, not even through function
+    /// pointers.
+    Internal,
+}
+
+/// Which functions taking raw pointers are bound as `unsafe fn`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PointerUnsafety {
+    /// Any raw pointer in the parameter types (e.g. also `int*&` or `int**`).
+    AnyPointer,
+    /// Only parameters that are themselves raw pointers.
+    TopLevelPointer,
+}
+
+/// What happens when a C++ function called through a thunk throws an exception.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExceptionPolicy {
+    /// Nothing: the exception unwinds into Rust, which is undefined behavior.
+    /// Only suitable for code compiled with `-fno-exceptions`.
+    Unchecked,
+    /// The thunks are `noexcept`, so the program terminates.
+    Abort,
+    /// The exception is caught and returned to Rust as a `CxxException` error.
+    Catch,
+}
+
+/// How the parameters with default arguments of C++ functions are bound.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DefaultArguments {
+    /// As additional `<name>_without_<param>` functions without them.
+    Overloads,
+    /// As `Option<T>` parameters, where `None` uses the default argument.
+    Option,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
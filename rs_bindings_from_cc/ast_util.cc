@@ -4,9 +4,22 @@
 
 #include "rs_bindings_from_cc/ast_util.h"
 
+#include <optional>
+#include <string>
+
+#include "absl/status/status.h"
+#include "absl/status/statusor.h"
+#include "absl/strings/str_cat.h"
+#include "absl/strings/string_view.h"
+#include "clang/AST/ASTContext.h"
+#include "clang/AST/Attr.h"
+#include "clang/AST/Decl.h"
 #include "clang/AST/DeclBase.h"
 #include "clang/AST/DeclTemplate.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/Type.h"
 #include "clang/Basic/LLVM.h"
+#include "llvm/ADT/StringRef.h"
 
 namespace crubit {
 
@@ -26,4 +39,103 @@ bool IsFullClassTemplateSpecializationOrChild(const clang::Decl* decl) {
   return false;
 }
 
+bool HasAnnotation(const clang::Decl* decl, absl::string_view annotation) {
+  for (const clang::AnnotateAttr* attr :
+       decl->specific_attrs<clang::AnnotateAttr>()) {
+    if (attr->getAnnotation() == llvm::StringRef(annotation)) return true;
+  }
+  return false;
+}
+
+absl::StatusOr<const clang::AnnotateAttr*> GetAnnotateAttr(
+    const clang::Decl* decl, absl::string_view attribute) {
+  const clang::AnnotateAttr* found_attr = nullptr;
+  for (clang::AnnotateAttr* attr :
+       decl->specific_attrs<clang::AnnotateAttr>()) {
+    if (attr->getAnnotation() != llvm::StringRef(attribute)) continue;
+
+    if (found_attr != nullptr)
+      return absl::InvalidArgumentError(
+          absl::StrCat("Only one `", attribute,
+                       "` attribute may be placed on a declaration."));
+    found_attr = attr;
+  }
+  return found_attr;
+}
+
+std::optional<std::string> GetDeprecationMessage(const clang::Decl* decl) {
+  const auto* attr = decl->getAttr<clang::DeprecatedAttr>();
+  if (attr == nullptr) return std::nullopt;
+  return attr->getMessage().str();
+}
+
+bool IsSupportedConstantType(clang::QualType type) {
+  type = type.getCanonicalType();
+  return type->isIntegralOrEnumerationType() ||
+         type->isSpecificBuiltinType(clang::BuiltinType::Float) ||
+         type->isSpecificBuiltinType(clang::BuiltinType::Double);
+}
+
+// Copied from lifetime_annotations/type_lifetimes.cc, which is expected to move
+// into ClangTidy. See:
+// https://discourse.llvm.org/t/rfc-lifetime-annotations-for-c/61377
+absl::StatusOr<absl::string_view> EvaluateAsStringLiteral(
+    const clang::Expr& expr, const clang::ASTContext& ast_context) {
+  auto error = []() {
+    return absl::InvalidArgumentError(
+        "cannot evaluate argument as a string literal");
+  };
+
+  clang::Expr::EvalResult eval_result;
+  if (!expr.EvaluateAsConstantExpr(eval_result, ast_context) ||
+      !eval_result.Val.isLValue()) {
+    return error();
+  }
+
+  const auto* eval_result_expr =
+      eval_result.Val.getLValueBase().dyn_cast<const clang::Expr*>();
+  if (!eval_result_expr) {
+    return error();
+  }
+
+  const auto* string_literal =
+      clang::dyn_cast<clang::StringLiteral>(eval_result_expr);
+  if (!string_literal) {
+    return error();
+  }
+
+  return {string_literal->getString()};
+}
+
+const clang::FieldDecl* GetFieldOfAnonNestedRecord(
+    const clang::RecordDecl* record_decl) {
+  if (!record_decl->getName().empty() ||
+      record_decl->getTypedefNameForAnonDecl() != nullptr) {
+    return nullptr;
+  }
+  const auto* parent =
+      clang::dyn_cast<clang::RecordDecl>(record_decl->getDeclContext());
+  if (parent == nullptr) return nullptr;
+  for (const clang::FieldDecl* field_decl : parent->fields()) {
+    const clang::RecordDecl* field_record =
+        field_decl->getType()->getAsRecordDecl();
+    if (field_record != nullptr && field_record->getCanonicalDecl() ==
+                                       record_decl->getCanonicalDecl()) {
+      return field_decl;
+    }
+  }
+  return nullptr;
+}
+
+clang::TagDecl* GetTagDeclNamedByTypedef(
+    const clang::TypedefNameDecl* typedef_decl) {
+  clang::TagDecl* tag_decl = typedef_decl->getUnderlyingType()->getAsTagDecl();
+  if (tag_decl == nullptr ||
+      tag_decl->getDeclContext() != typedef_decl->getDeclContext() ||
+      tag_decl->getName() != typedef_decl->getName()) {
+    return nullptr;
+  }
+  return tag_decl;
+}
+
 }  // namespace crubit
@@ -5,7 +5,17 @@
 #ifndef CRUBIT_RS_BINDINGS_FROM_CC_AST_UTIL_H_
 #define CRUBIT_RS_BINDINGS_FROM_CC_AST_UTIL_H_
 
+#include <optional>
+#include <string>
+
+#include "absl/status/statusor.h"
+#include "absl/strings/string_view.h"
+#include "clang/AST/ASTContext.h"
+#include "clang/AST/Attr.h"
+#include "clang/AST/Decl.h"
 #include "clang/AST/DeclBase.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/Type.h"
 
 namespace crubit {
 
@@ -14,6 +24,42 @@ namespace crubit {
 // function decl) nested inside a ClassTemplateSpecializationDecl.
 bool IsFullClassTemplateSpecializationOrChild(const clang::Decl* decl);
 
+// Returns true if `decl` has a `[[clang::annotate(annotation)]]` attribute.
+bool HasAnnotation(const clang::Decl* decl, absl::string_view annotation);
+
+// Gets the `[[clang::annotate(attribute, ...)]]` attribute of `decl`, or
+// nullptr if there is none. Returns an error if there is more than one.
+// `decl` must not be null.
+absl::StatusOr<const clang::AnnotateAttr*> GetAnnotateAttr(
+    const clang::Decl* decl, absl::string_view attribute);
+
+// Returns the message of the `[[deprecated]]` attribute of `decl` (which is
+// empty if the attribute has no message), or nullopt if `decl` isn't
+// deprecated.
+std::optional<std::string> GetDeprecationMessage(const clang::Decl* decl);
+
+// Returns true if constants of type `type` can be bound as Rust constants.
+bool IsSupportedConstantType(clang::QualType type);
+
+// Evaluates `expr` (e.g. an argument of an annotation) as a string literal.
+absl::StatusOr<absl::string_view> EvaluateAsStringLiteral(
+    const clang::Expr& expr, const clang::ASTContext& ast_context);
+
+// Returns the field whose type is `record_decl`, if `record_decl` is an
+// anonymous record (without a typedef name) nested in another record. For
+// example, returns `s` for the type of `s` in
+// `struct Outer { struct { int x; } s; };`, and the unnamed field for an
+// anonymous struct or union member. Returns nullptr otherwise.
+const clang::FieldDecl* GetFieldOfAnonNestedRecord(
+    const clang::RecordDecl* record_decl);
+
+// Returns the tag declaration that `typedef_decl` only gives a C-style name
+// to, as in `typedef struct Foo Foo;`. That is, returns the tag declaration
+// aliased by `typedef_decl` if it has the same name and is declared in the
+// same context. Returns nullptr otherwise.
+clang::TagDecl* GetTagDeclNamedByTypedef(
+    const clang::TypedefNameDecl* typedef_decl);
+
 }  // namespace crubit
 
 #endif  // CRUBIT_RS_BINDINGS_FROM_CC_AST_UTIL_H_
@@ -0,0 +1,136 @@
+# Part of the Crubit project, under the Apache License v2.0 with LLVM
+# Exceptions. See /LICENSE for license information.
+# SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+"""The aspect hint, to be attached to a `cc_library`, configures how its Rust bindings map
+exceptions, `long double`, plain `char` and default arguments (the `--exception_policy`,
+`--long_double`, `--char_mapping` and `--default_arguments` flags of `rs_bindings_from_cc`)."""
+
+# buildifier: disable=bzl-visibility
+load(
+    "@rules_rust//rust/private:providers.bzl",
+    "CrateInfo",
+    "DepInfo",
+    "DepVariantInfo",
+)
+
+# The feature is OK for general use.
+visibility([
+    "//rs_bindings_from_cc/...",
+])
+
+_CodegenOptionsProviderInfo = provider(
+    doc = """
+The provider that specifies the `rs_bindings_from_cc` options of this C++ target, and the support
+crates that the bindings need for them.
+""",
+    fields = {
+        "options": "A dict from the flag name (e.g. `long_double`) to its value.",
+        "deps_for_rs_file": "The `DepVariantInfo`s of the support crates required by the options.",
+    },
+)
+
+# The valid values of each option. The default value of the `rs_bindings_from_cc` flag comes first.
+_OPTION_VALUES = {
+    "char_mapping": ["native", "u8", "i8", "c_char"],
+    "default_arguments": ["overloads", "option"],
+    "exception_policy": ["unchecked", "abort", "catch"],
+    "long_double": ["skip", "opaque", "f64"],
+}
+
+def _dep_variant_info(dep):
+    return DepVariantInfo(
+        crate_info = dep[CrateInfo] if CrateInfo in dep else None,
+        dep_info = dep[DepInfo] if DepInfo in dep else None,
+        cc_info = dep[CcInfo] if CcInfo in dep else None,
+        build_info = None,
+    )
+
+def _codegen_options_for_crubit_bindings_impl(ctx):
+    options = {}
+    for name, values in _OPTION_VALUES.items():
+        value = getattr(ctx.attr, name)
+        if not value:
+            continue
+        if value not in values:
+            fail("Unknown value `%s` for `%s`, expected one of: %s" % (
+                value,
+                name,
+                ", ".join(values),
+            ))
+        options[name] = value
+
+    deps_for_rs_file = []
+    if options.get("exception_policy") == "catch":
+        deps_for_rs_file.append(_dep_variant_info(ctx.attr._cxx_exception))
+    if options.get("long_double") == "opaque":
+        deps_for_rs_file.append(_dep_variant_info(ctx.attr._long_double))
+    return [_CodegenOptionsProviderInfo(
+        options = options,
+        deps_for_rs_file = deps_for_rs_file,
+    )]
+
+codegen_options_for_crubit_bindings = rule(
+    attrs = {
+        "char_mapping": attr.string(
+            doc = """How plain `char` is mapped: `native` (`i8` or `u8`, depending on the signedness
+of `char` on the target), `u8`, `i8` or `c_char`. Unset means `native`.""",
+        ),
+        "default_arguments": attr.string(
+            doc = """How parameters with default arguments are bound: `overloads` (additional
+`<name>_without_<param>` functions) or `option` (`Option<T>` parameters). Unset means
+`overloads`.""",
+        ),
+        "exception_policy": attr.string(
+            doc = """What happens to C++ exceptions thrown through the bindings: `unchecked`, `abort`
+or `catch`. Unset means `unchecked`.""",
+        ),
+        "long_double": attr.string(
+            doc = """How `long double` is mapped: `skip`, `opaque` (the `long_double` support crate)
+or `f64`. Unset means `skip`.""",
+        ),
+        "_cxx_exception": attr.label(
+            default = "//support:cxx_exception",
+        ),
+        "_long_double": attr.label(
+            default = "//support:long_double",
+        ),
+    },
+    implementation = _codegen_options_for_crubit_bindings_impl,
+    doc = """
+Defines an aspect hint that is used to pass the `--exception_policy`, `--long_double`,
+`--char_mapping` and `--default_arguments` CLI arguments to the `rs_bindings_from_cc` tool, and to
+add the support crates that these options require to the dependencies of the generated bindings.
+""",
+)
+
+def get_codegen_options(target, aspect_ctx):
+    """Returns the `rs_bindings_from_cc` options configured for the `target`.
+
+    Args:
+        target: The target, as seen in aspect_hint.
+        aspect_ctx: The ctx from an aspect_hint.
+
+    Returns:
+        A struct with the `cli_flags` for `rs_bindings_from_cc`, and the `deps_for_rs_file` that the
+        generated bindings need.
+    """
+    options = {}
+    deps_for_rs_file = []
+    for hint in aspect_ctx.rule.attr.aspect_hints:
+        if _CodegenOptionsProviderInfo not in hint:
+            continue
+        for name, value in hint[_CodegenOptionsProviderInfo].options.items():
+            if name in options and options[name] != value:
+                fail("Conflicting values `%s` and `%s` for `%s` in the aspect hints of %s" % (
+                    options[name],
+                    value,
+                    name,
+                    target.label,
+                ))
+            options[name] = value
+        deps_for_rs_file.extend(hint[_CodegenOptionsProviderInfo].deps_for_rs_file)
+    return struct(
+        cli_flags = ["--%s=%s" % (name, value) for name, value in sorted(options.items())],
+        deps_for_rs_file = deps_for_rs_file,
+    )
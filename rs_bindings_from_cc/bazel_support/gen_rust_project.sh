@@ -0,0 +1,50 @@
+#!/bin/bash
+# Part of the Crubit project, under the Apache License v2.0 with LLVM
+# Exceptions. See /LICENSE for license information.
+# SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+# Builds the Rust bindings of a C++ library and writes a rust-project.json for
+# them, so that rust-analyzer can be used on the generated bindings.
+#
+# Usage: gen_rust_project.sh //some/cc/library:target [rust-project.json]
+
+set -euo pipefail
+
+if [[ "${#}" -lt 1 || "${#}" -gt 2 ]]; then
+  echo "Usage: ${0} <cc_library label> [output file]"
+  exit 1
+fi
+
+if [[ -n "${BUILD_WORKSPACE_DIRECTORY:-}" ]]; then
+  cd "${BUILD_WORKSPACE_DIRECTORY}"
+fi
+
+readonly LABEL="${1}"
+readonly OUTPUT="${2:-rust-project.json}"
+
+if [[ "${LABEL}" != //*:* ]]; then
+  echo "Expected a label of the form //package:name, got: ${LABEL}"
+  exit 1
+fi
+PACKAGE="${LABEL#//}"
+PACKAGE="${PACKAGE%%:*}"
+NAME="${LABEL##*:}"
+
+bazel build \
+  --aspects //rs_bindings_from_cc/bazel_support:rust_bindings_from_cc_aspect.bzl%rust_bindings_from_cc_aspect \
+  --output_groups=rust_analyzer \
+  "${LABEL}"
+
+readonly EXEC_ROOT="$(bazel info execution_root)"
+readonly RUST_PROJECT="$(bazel info bazel-bin)/${PACKAGE}/${NAME}_rust_project.json"
+
+# rust-analyzer needs the sources of the standard library, which the
+# rust-project.json generated at analysis time doesn't know about.
+SED_ARGS=(-e "s|__EXEC_ROOT__|${EXEC_ROOT}|g")
+if command -v rustc > /dev/null; then
+  SYSROOT_SRC="$(rustc --print sysroot)/lib/rustlib/src/rust/library"
+  SED_ARGS+=(-e "1s|^{|{\"sysroot_src\": \"${SYSROOT_SRC}\",|")
+fi
+
+sed "${SED_ARGS[@]}" "${RUST_PROJECT}" > "${OUTPUT}"
+echo "Wrote ${OUTPUT}"
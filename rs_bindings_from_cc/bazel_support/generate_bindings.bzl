@@ -28,7 +28,8 @@ def generate_bindings(
         action_inputs,
         target_args,
         extra_rs_srcs,
-        extra_rs_bindings_from_cc_cli_flags):
+        extra_rs_bindings_from_cc_cli_flags,
+        instantiations_manifests = []):
     """Runs the bindings generator.
 
     Args:
@@ -44,13 +45,17 @@ def generate_bindings(
                         its per-target arguments (headers, features) in json format.
       extra_rs_srcs: A list of extra source files to add.
       extra_rs_bindings_from_cc_cli_flags: CLI flags to be passed to `rs_bindings_from_cc`.
+      instantiations_manifests: A list of `.instantiations` manifests listing the class template
+                                instantiations to generate bindings for.
 
     Returns:
-      tuple(cc_output, rs_output, namespaces_output, error_report_output): The generated source files.
+      tuple(cc_output, rs_output, namespaces_output, error_report_output, source_map_output): The
+      generated source files.
     """
     cc_output = ctx.actions.declare_file(ctx.label.name + "_rust_api_impl.cc")
     rs_output = ctx.actions.declare_file(ctx.label.name + "_rust_api.rs")
     namespaces_output = ctx.actions.declare_file(ctx.label.name + "_namespaces.json")
+    source_map_output = ctx.actions.declare_file(ctx.label.name + "_rust_api_source_map.json")
     error_report_output = None
 
     rs_bindings_from_cc_flags = [
@@ -62,6 +67,8 @@ def generate_bindings(
         cc_output.path,
         "--namespaces_out",
         namespaces_output.path,
+        "--source_map_out",
+        source_map_output.path,
         "--crubit_support_path",
         "support",
         "--clang_format_exe_path",
@@ -77,6 +84,10 @@ def generate_bindings(
             "--error_report_out",
             error_report_output.path,
         ]
+    if instantiations_manifests:
+        rs_bindings_from_cc_flags.append(
+            "--instantiations_manifests=" + ",".join([x.path for x in instantiations_manifests]),
+        )
 
     variables = cc_common.create_compile_variables(
         feature_configuration = feature_configuration,
@@ -122,10 +133,10 @@ def generate_bindings(
                 ctx.executable._clang_format,
                 ctx.executable._rustfmt,
                 ctx.executable._generator,
-            ] + ctx.files._rustfmt_cfg + extra_rs_srcs,
+            ] + ctx.files._rustfmt_cfg + extra_rs_srcs + instantiations_manifests,
             transitive = [action_inputs],
         ),
-        additional_outputs = [x for x in [rs_output, namespaces_output, error_report_output] if x != None],
+        additional_outputs = [x for x in [rs_output, namespaces_output, error_report_output, source_map_output] if x != None],
         variables = variables,
     )
-    return (cc_output, rs_output, namespaces_output, error_report_output)
+    return (cc_output, rs_output, namespaces_output, error_report_output, source_map_output)
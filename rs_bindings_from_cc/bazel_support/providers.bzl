@@ -31,6 +31,10 @@ GeneratedBindingsInfo = provider(
         "cc_file": "The generated C++ source file.",
         "rust_file": "The generated Rust source file.",
         "namespaces_file": "The generated namespace hierarchy in JSON format.",
+        "rust_project_file": ("A rust-project.json describing the crate compiled from the " +
+                              "generated Rust source file, for rust-analyzer."),
+        "source_map_file": ("A JSON map from the generated Rust items and C++ thunks to the C++ " +
+                            "declarations they were generated from."),
     },
 )
 
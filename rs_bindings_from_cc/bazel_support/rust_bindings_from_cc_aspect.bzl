@@ -22,6 +22,10 @@ load(
     "bindings_attrs",
     "generate_and_compile_bindings",
 )
+load(
+    "//rs_bindings_from_cc/bazel_support:codegen_options_for_crubit_bindings_aspect_hint.bzl",
+    "get_codegen_options",
+)
 load(
     "//rs_bindings_from_cc/bazel_support:crubit_feature_hint.bzl",
     "find_crubit_features",
@@ -34,6 +38,14 @@ load(
     "//rs_bindings_from_cc/bazel_support:additional_rust_srcs_for_crubit_bindings_aspect_hint.bzl",
     "get_additional_rust_srcs",
 )
+load(
+    "//rs_bindings_from_cc/bazel_support:template_instantiations_for_crubit_bindings_aspect_hint.bzl",
+    "get_instantiations_manifests",
+)
+load(
+    "//rs_bindings_from_cc/bazel_support:support_type_maps_for_crubit_bindings_aspect_hint.bzl",
+    "get_support_type_maps",
+)
 
 # <internal link>/127#naming-header-files-h-and-inc recommends declaring textual headers either in the
 # `textual_hdrs` attribute of the Bazel C++ rules, or using the `.inc` file extension. Therefore
@@ -181,6 +193,14 @@ def _rust_bindings_from_cc_aspect_impl(target, ctx):
         header_includes.append("-include")
         header_includes.append(hdr.short_path)
 
+    support_type_maps = get_support_type_maps(target, ctx)
+    codegen_options = get_codegen_options(target, ctx)
+    cli_flags = (
+        collect_rust_bindings_from_cc_cli_flags(target, ctx) +
+        support_type_maps.cli_flags +
+        codegen_options.cli_flags
+    )
+
     return generate_and_compile_bindings(
         ctx,
         ctx.rule.attr,
@@ -205,9 +225,12 @@ def _rust_bindings_from_cc_aspect_impl(target, ctx):
             dep[RustBindingsFromCcInfo].dep_variant_info
             for dep in all_deps
             if RustBindingsFromCcInfo in dep
-        ] + ctx.attr._deps_for_bindings[DepsForBindingsInfo].deps_for_rs_file,
+        ] + ctx.attr._deps_for_bindings[DepsForBindingsInfo].deps_for_rs_file +
+                           support_type_maps.deps_for_rs_file +
+                           codegen_options.deps_for_rs_file,
         extra_cc_compilation_action_inputs = extra_cc_compilation_action_inputs,
-        extra_rs_bindings_from_cc_cli_flags = collect_rust_bindings_from_cc_cli_flags(target, ctx),
+        extra_rs_bindings_from_cc_cli_flags = cli_flags,
+        instantiations_manifests = get_instantiations_manifests(target, ctx),
     )
 
 rust_bindings_from_cc_aspect = aspect(
@@ -38,11 +38,11 @@ should only be used by Crubit developers.
 """,
 )
 
-def collect_rust_bindings_from_cc_cli_flags(_target, aspect_ctx):
+def collect_rust_bindings_from_cc_cli_flags(target, aspect_ctx):
     """Returns the command line flags and values for `rs_bindings_from_cc`.
 
     Args:
-        _target: The target, as seen in aspect_hint.
+        target: The target, as seen in aspect_hint.
         aspect_ctx: The ctx from an aspect_hint.
 
     Returns:
@@ -52,5 +52,10 @@ def collect_rust_bindings_from_cc_cli_flags(_target, aspect_ctx):
     flags = []
     for hint in aspect_ctx.rule.attr.aspect_hints:
         if _RustBindingsFromCcCliFlagInfo in hint:
-            flags.append(hint[_RustBindingsFromCcCliFlagInfo].flags)
+            hint_flags = hint[_RustBindingsFromCcCliFlagInfo].flags
+            if "--split_namespace" in hint_flags:
+                # The aspect generates a single crate per target.
+                fail("--split_namespaces is not supported by the aspect, in the aspect hints of %s" %
+                     target.label)
+            flags.append(hint_flags)
     return flags
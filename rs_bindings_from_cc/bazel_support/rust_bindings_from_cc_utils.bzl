@@ -17,6 +17,7 @@ load(
     "GeneratedBindingsInfo",
     "RustBindingsFromCcInfo",
 )
+load("//rs_bindings_from_cc/bazel_support:rust_project.bzl", "write_rust_project")
 
 def generate_and_compile_bindings(
         ctx,
@@ -30,7 +31,8 @@ def generate_and_compile_bindings(
         deps_for_cc_file,
         deps_for_rs_file,
         extra_cc_compilation_action_inputs = [],
-        extra_rs_bindings_from_cc_cli_flags = []):
+        extra_rs_bindings_from_cc_cli_flags = [],
+        instantiations_manifests = []):
     """Runs the bindings generator.
 
     Args:
@@ -48,6 +50,8 @@ def generate_and_compile_bindings(
       extra_cc_compilation_action_inputs: A list of input files for the C++ compilation action.
       extra_rs_bindings_from_cc_cli_flags: CLI flags to pass to `rs_bindings_from_cc`, in addition
                                            to the flags that are passed by the build rule.
+      instantiations_manifests: list[file]: `.instantiations` manifests listing the class template
+                                instantiations to generate bindings for.
     Returns:
       A RustBindingsFromCcInfo containing the result of the compilation of the generated source
       files, as well a GeneratedBindingsInfo provider containing the generated source files.
@@ -61,7 +65,7 @@ def generate_and_compile_bindings(
         unsupported_features = ctx.disabled_features + ["module_maps"],
     )
 
-    cc_output, rs_output, namespaces_output, error_report_output = generate_bindings(
+    cc_output, rs_output, namespaces_output, error_report_output, source_map_output = generate_bindings(
         ctx = ctx,
         attr = attr,
         cc_toolchain = cc_toolchain,
@@ -73,6 +77,7 @@ def generate_and_compile_bindings(
         target_args = target_args,
         extra_rs_srcs = extra_rs_srcs,
         extra_rs_bindings_from_cc_cli_flags = extra_rs_bindings_from_cc_cli_flags,
+        instantiations_manifests = instantiations_manifests,
     )
 
     # Relocate the rs files so that they can be read by rustc using relative paths.
@@ -102,6 +107,9 @@ def generate_and_compile_bindings(
         deps_for_rs_file,
     )
 
+    # Describe the compiled crate for rust-analyzer.
+    rust_project_output = write_rust_project(ctx, dep_variant_info)
+
     return [
         RustBindingsFromCcInfo(
             cc_info = cc_info,
@@ -113,8 +121,13 @@ def generate_and_compile_bindings(
             cc_file = cc_output,
             rust_file = rs_output,
             namespaces_file = namespaces_output,
+            rust_project_file = rust_project_output,
+            source_map_file = source_map_output,
+        ),
+        OutputGroupInfo(
+            out = depset([x for x in [cc_output, rs_output, namespaces_output, error_report_output, source_map_output] if x != None]),
+            rust_analyzer = depset([rust_project_output, rs_output]),
         ),
-        OutputGroupInfo(out = depset([x for x in [cc_output, rs_output, namespaces_output, error_report_output] if x != None])),
     ]
 
 bindings_attrs = {
@@ -0,0 +1,71 @@
+# Part of the Crubit project, under the Apache License v2.0 with LLVM
+# Exceptions. See /LICENSE for license information.
+# SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+"""Generates rust-analyzer project metadata for the generated bindings crates.
+
+Disclaimer: This project is experimental, under heavy development, and should
+not be used yet.
+"""
+
+# Placeholder for the Bazel execution root, which isn't known at analysis time.
+# `gen_rust_project.sh` replaces it with the actual path.
+EXEC_ROOT_PLACEHOLDER = "__EXEC_ROOT__"
+
+def _crate_spec(crate_info, is_workspace_member):
+    deps = [
+        dep.crate_info
+        for dep in crate_info.deps.to_list()
+        if dep.crate_info
+    ]
+    return {
+        "display_name": crate_info.name,
+        "root_module": EXEC_ROOT_PLACEHOLDER + "/" + crate_info.root.path,
+        "edition": crate_info.edition,
+        "is_workspace_member": is_workspace_member,
+        # Crates are identified by their root module here, and by their index
+        # in the final `crates` list below.
+        "deps": [
+            {"name": dep.name, "root_module": EXEC_ROOT_PLACEHOLDER + "/" + dep.root.path}
+            for dep in deps
+        ],
+        "cfg": [],
+    }
+
+def write_rust_project(ctx, dep_variant_info):
+    """Writes a `rust-project.json` describing the generated bindings crate.
+
+    The file describes the crate compiled from the generated `.rs` file and all
+    of its transitive dependencies, so that rust-analyzer can provide IDE
+    features (completion, go-to-definition, etc.) for the bindings.
+
+    Args:
+      ctx: The rule context.
+      dep_variant_info: The DepVariantInfo of the compiled bindings crate.
+
+    Returns:
+      The generated `rust-project.json` file. Paths in it are relative to
+      `EXEC_ROOT_PLACEHOLDER`.
+    """
+    crate_info = dep_variant_info.crate_info
+    crates = [_crate_spec(crate_info, True)] + [
+        _crate_spec(dep, False)
+        for dep in dep_variant_info.dep_info.transitive_crates.to_list()
+        if dep.root != crate_info.root
+    ]
+
+    # rust-project.json refers to dependencies by their index in `crates`.
+    index_by_root = {crate["root_module"]: i for i, crate in enumerate(crates)}
+    for crate in crates:
+        crate["deps"] = [
+            {"crate": index_by_root[dep["root_module"]], "name": dep["name"]}
+            for dep in crate["deps"]
+            if dep["root_module"] in index_by_root
+        ]
+
+    rust_project_output = ctx.actions.declare_file(ctx.label.name + "_rust_project.json")
+    ctx.actions.write(
+        output = rust_project_output,
+        content = json.encode_indent({"crates": crates}),
+    )
+    return rust_project_output
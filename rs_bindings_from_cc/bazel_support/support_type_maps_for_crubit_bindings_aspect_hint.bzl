@@ -0,0 +1,106 @@
+# Part of the Crubit project, under the Apache License v2.0 with LLVM
+# Exceptions. See /LICENSE for license information.
+# SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+"""The aspect hint, to be attached to a `cc_library`, enables built-in type mappings into Crubit
+support crates that are not part of the default dependencies of the bindings (e.g. `std::string`
+into `::cxx_string::CxxString`)."""
+
+# buildifier: disable=bzl-visibility
+load(
+    "@rules_rust//rust/private:providers.bzl",
+    "CrateInfo",
+    "DepInfo",
+    "DepVariantInfo",
+)
+
+# The feature is OK for general use.
+visibility([
+    "//rs_bindings_from_cc/...",
+])
+
+_SupportTypeMapsProviderInfo = provider(
+    doc = """
+The provider that specifies the built-in type mappings enabled in the bindings of this C++ target,
+and the support crates that the mapped types live in.
+""",
+    fields = {
+        "type_maps": "The names of the enabled type mappings, e.g. `cxx_string`.",
+        "deps_for_rs_file": "The `DepVariantInfo`s of the support crates of the type mappings.",
+    },
+)
+
+def _support_type_maps_for_crubit_bindings_impl(ctx):
+    support_crates = {
+        "absl_status": ctx.attr._absl_status,
+        "cxx_string": ctx.attr._cxx_string,
+    }
+    deps_for_rs_file = []
+    for type_map in ctx.attr.type_maps:
+        if type_map not in support_crates:
+            fail("Unknown type map `%s`, expected one of: %s" % (
+                type_map,
+                ", ".join(support_crates.keys()),
+            ))
+        dep = support_crates[type_map]
+        deps_for_rs_file.append(DepVariantInfo(
+            crate_info = dep[CrateInfo] if CrateInfo in dep else None,
+            dep_info = dep[DepInfo] if DepInfo in dep else None,
+            cc_info = dep[CcInfo] if CcInfo in dep else None,
+            build_info = None,
+        ))
+    return [_SupportTypeMapsProviderInfo(
+        type_maps = ctx.attr.type_maps,
+        deps_for_rs_file = deps_for_rs_file,
+    )]
+
+support_type_maps_for_crubit_bindings = rule(
+    attrs = {
+        "type_maps": attr.string_list(
+            doc = """The built-in type mappings to enable, named after the support crate they map
+into: `absl_status` (`absl::Status` and `absl::StatusOr<T>`) and `cxx_string` (`std::string` and
+string views). `cxx_string` requires a standard library whose `std::string` is trivially
+relocatable, e.g. libc++.""",
+            mandatory = True,
+        ),
+        "_absl_status": attr.label(
+            default = "//support:absl_status",
+        ),
+        "_cxx_string": attr.label(
+            default = "//support:cxx_string",
+        ),
+    },
+    implementation = _support_type_maps_for_crubit_bindings_impl,
+    doc = """
+Defines an aspect hint that is used to pass the `--support_type_maps` CLI argument to the
+`rs_bindings_from_cc` tool, and to add the support crates of the type mappings to the dependencies
+of the generated bindings.
+""",
+)
+
+def get_support_type_maps(_target, aspect_ctx):
+    """Returns the built-in type mappings enabled for the `_target`.
+
+    Args:
+        _target: The target, as seen in aspect_hint.
+        aspect_ctx: The ctx from an aspect_hint.
+
+    Returns:
+        A struct with the `cli_flags` for `rs_bindings_from_cc`, and the `deps_for_rs_file` that the
+        generated bindings need.
+    """
+    type_maps = []
+    deps_for_rs_file = []
+    for hint in aspect_ctx.rule.attr.aspect_hints:
+        if _SupportTypeMapsProviderInfo in hint:
+            for type_map, dep in zip(
+                hint[_SupportTypeMapsProviderInfo].type_maps,
+                hint[_SupportTypeMapsProviderInfo].deps_for_rs_file,
+            ):
+                if type_map not in type_maps:
+                    type_maps.append(type_map)
+                    deps_for_rs_file.append(dep)
+    return struct(
+        cli_flags = ["--support_type_maps=" + ",".join(type_maps)] if type_maps else [],
+        deps_for_rs_file = deps_for_rs_file,
+    )
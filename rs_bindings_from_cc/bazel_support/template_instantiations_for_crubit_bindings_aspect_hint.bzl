@@ -0,0 +1,73 @@
+# Part of the Crubit project, under the Apache License v2.0 with LLVM
+# Exceptions. See /LICENSE for license information.
+# SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+"""The aspect hint, to be attached to a `cc_library`, specifies class and function template
+instantiations that Rust code needs bindings for."""
+
+load("@bazel_skylib//lib:collections.bzl", "collections")
+
+# The feature is OK for general use.
+visibility([
+    "//rs_bindings_from_cc/...",
+])
+
+_TemplateInstantiationsProviderInfo = provider(
+    doc = """
+The provider that specifies the `.instantiations` manifests listing the class template
+instantiations to be generated in the bindings of this C++ target.
+""",
+    fields = {
+        "manifests": "The `.instantiations` manifest files, one C++ template instantiation per line.",
+    },
+)
+
+def _template_instantiations_for_crubit_bindings_impl(ctx):
+    manifests = list(ctx.files.manifests)
+    if ctx.attr.instantiations:
+        manifest = ctx.actions.declare_file(ctx.label.name + ".instantiations")
+        ctx.actions.write(
+            output = manifest,
+            content = "\n".join(ctx.attr.instantiations) + "\n",
+        )
+        manifests.append(manifest)
+    return [_TemplateInstantiationsProviderInfo(
+        manifests = manifests,
+    )]
+
+template_instantiations_for_crubit_bindings = rule(
+    attrs = {
+        "instantiations": attr.string_list(
+            doc = """C++ template instantiations to generate bindings for, in the `.instantiations`
+manifest syntax: a class template instantiation (e.g. `MyTemplate<int>`), or a function template
+instantiation prefixed with `function ` (e.g. `function absl::StrCat<int>`). Instantiations of
+variadic templates list the whole argument pack (e.g. `function Log<int, float>`), and each
+requested pack gets its own Rust function (e.g. `Log_int_float`).""",
+        ),
+        "manifests": attr.label_list(
+            doc = "`.instantiations` manifests, listing one C++ template instantiation per line.",
+            allow_files = [".instantiations"],
+        ),
+    },
+    implementation = _template_instantiations_for_crubit_bindings_impl,
+    doc = """
+Defines an aspect hint that is used to pass class template instantiations needed by Rust code to
+`rs_bindings_from_cc` tool's `instantiations_manifests` CLI argument.
+""",
+)
+
+def get_instantiations_manifests(_target, aspect_ctx):
+    """Returns the `.instantiations` manifests associated with the `_target`.
+
+    Args:
+        _target: The target, as seen in aspect_hint.
+        aspect_ctx: The ctx from an aspect_hint.
+
+    Returns:
+        A list of `File`s listing the class template instantiations requested for the `_target`.
+    """
+    manifests = []
+    for hint in aspect_ctx.rule.attr.aspect_hints:
+        if _TemplateInstantiationsProviderInfo in hint:
+            manifests.extend(hint[_TemplateInstantiationsProviderInfo].manifests)
+    return collections.uniq(manifests)
@@ -14,6 +14,7 @@
 #include "absl/log/log.h"
 #include "absl/status/status.h"
 #include "absl/strings/str_cat.h"
+#include "absl/strings/str_split.h"
 #include "absl/strings/substitute.h"
 #include "common/ffi_types.h"
 #include "common/status_macros.h"
@@ -80,6 +81,179 @@ ABSL_FLAG(std::string, error_report_out, "",
 ABSL_FLAG(bool, generate_source_location_in_doc_comment, true,
           "add the source code location from which the binding originates in"
           "the doc comment of the binding");
+ABSL_FLAG(bool, generate_miri_compatible_bindings, false,
+          "avoid patterns that Miri rejects (integer-pointer casts, and "
+          "references to uninitialized out parameters) in the Rust parts of "
+          "the generated bindings, so that they can be tested under Miri");
+ABSL_FLAG(std::string, fuzz_harness_out, "",
+          "(optional) output path for a cargo-fuzz / libFuzzer harness "
+          "exercising the generated bindings of the target. Only free "
+          "functions that take primitive types are called, and it is an "
+          "error if the target has none");
+ABSL_FLAG(std::string, benchmark_out, "",
+          "(optional) output path for a Criterion benchmark comparing the "
+          "call overhead of the generated bindings with direct extern "
+          "calls of the C++ functions, where possible (e.g. not for inline "
+          "functions). With --generate_raw_module, the `extern \"C\"` "
+          "declarations in `mod raw` are benchmarked too");
+ABSL_FLAG(std::string, thunk_visibility, "default",
+          "ELF symbol visibility of the generated `__rust_thunk__*` "
+          "functions: `default` (exported from shared libraries), `hidden` "
+          "(`visibility(\"hidden\")`: not exported from the shared library "
+          "or executable that embeds the bindings), or `internal` "
+          "(`visibility(\"internal\")`: like `hidden`, and additionally "
+          "never called from outside of that shared library or executable, "
+          "not even through a function pointer). Exporting the thunks under "
+          "a linker version script is out of scope: no version script is "
+          "generated, and the thunks can only be matched by a hand-written "
+          "one, e.g. with the `__rust_thunk__*` glob");
+ABSL_FLAG(bool, generate_header_only_thunks, false,
+          "emit the C++ thunks as `inline` functions that can be included "
+          "into an existing translation unit of the bound library (instead "
+          "of having to compile the --cc_out file as a separate translation "
+          "unit)");
+ABSL_FLAG(std::vector<std::string>, split_namespaces,
+          std::vector<std::string>(),
+          "(optional) top-level namespaces of the target whose bindings should "
+          "be generated into separate crates, in the `namespace=crate_name` "
+          "format. The bindings of the target re-export the bindings of these "
+          "namespaces from the given crates. Items in a split namespace may "
+          "only refer to items of the same namespace or of other targets; "
+          "items that refer to other items of the target don't get bindings. "
+          "The Bazel aspect doesn't support this flag yet: the crates of the "
+          "split namespaces must be generated (see "
+          "--split_namespace_to_generate) and built separately, and the crate "
+          "of the target must depend on them.");
+ABSL_FLAG(std::string, split_namespace_to_generate, "",
+          "(optional) one of the namespaces from --split_namespaces. If set, "
+          "only the bindings of this namespace are generated (i.e. the "
+          "bindings for the crate of this namespace).");
+ABSL_FLAG(std::vector<std::string>, extern_rust_types,
+          std::vector<std::string>(),
+          "(optional) C++ types that already have Rust bindings defined "
+          "elsewhere (e.g. in a hand-written crate), in the "
+          "`cc_type=rust_type` format, where `cc_type` is the fully qualified "
+          "name of the C++ type, and `rust_type` is the fully qualified path "
+          "of the Rust type (e.g. `ns::Foo=::legacy_ffi::Foo`). Instead of "
+          "generating bindings for these C++ types, the generated bindings "
+          "refer to the given Rust types, as if the C++ type was annotated "
+          "with `crubit_internal_rust_type`.");
+ABSL_FLAG(bool, snake_case_function_names, false,
+          "convert `CamelCase` names of functions and methods to `snake_case` "
+          "in the generated Rust API. The original names remain available as "
+          "`#[doc(hidden)]` aliases, so that existing callers keep compiling.");
+ABSL_FLAG(int, constructor_builder_min_params, 0,
+          "(optional) if positive, constructors of `Unpin` types that take at "
+          "least this many parameters are bound as a builder type (with a "
+          "setter method per parameter) instead of as a function. So are "
+          "constructors that take a single options struct (an aggregate with "
+          "public fields), with a setter method per field. At most one "
+          "constructor per type can be bound as a builder. 0 disables "
+          "builders.");
+ABSL_FLAG(bool, generate_raw_module, false,
+          "generate a `pub mod raw` with an `unsafe fn` per bound function "
+          "that calls its `extern \"C\"` declaration, with the faithful C++ "
+          "signature. The rest of the generated crate is the safe layer "
+          "that wraps these declarations.");
+ABSL_FLAG(std::string, pointer_unsafety, "any_pointer",
+          "which functions taking raw pointers are bound as `unsafe fn`: "
+          "`any_pointer` (a raw pointer anywhere in a parameter type, e.g. "
+          "also `int*&` or `int**`) or `top_level_pointer` (only parameters "
+          "that are themselves raw pointers). Pointers with lifetime "
+          "annotations are bound as references and don't make a function "
+          "unsafe.");
+ABSL_FLAG(std::vector<std::string>, forced_includes, std::vector<std::string>(),
+          "(optional) headers to include before the --public_headers, e.g. "
+          "to define macros that normalize a macro-heavy framework enough "
+          "for its headers to be imported. Declarations from these headers "
+          "are only bound if they belong to the current target. The "
+          "generated C++ thunks include them as well.");
+ABSL_FLAG(std::vector<std::string>, macro_definitions,
+          std::vector<std::string>(),
+          "(optional) macros to predefine when importing the --public_headers, "
+          "in the form `NAME` or `NAME=VALUE` (like the `-D` option of "
+          "Clang). For example, `Q_OBJECT=` makes Qt's `Q_OBJECT` expand to "
+          "nothing. The generated C++ thunks define them as well.");
+ABSL_FLAG(bool, cuda_host_only, false,
+          "only bind functions that run exclusively on the host when "
+          "importing CUDA headers. `__device__` functions and `__global__` "
+          "kernels are never bound; with this flag, `__host__ __device__` "
+          "functions are skipped as well.");
+ABSL_FLAG(std::vector<std::string>, errno_functions,
+          std::vector<std::string>(),
+          "(optional) fully qualified names of C functions that report errors "
+          "POSIX-style, by returning -1 (or `NULL`) and setting `errno`. In "
+          "addition to the regular bindings, such functions get a `try_` "
+          "prefixed wrapper that returns `Result<T, c_int>`, with the `errno` "
+          "as the error. Only free functions that return a signed integer or a "
+          "pointer are supported; other matching declarations are errors.");
+ABSL_FLAG(bool, errno_std_io_error, false,
+          "whether the `try_` wrappers of `--errno_functions` return a "
+          "`std::io::Error` instead of the raw `errno`. The generated "
+          "bindings are `no_std`, so this makes them depend on `std`.");
+ABSL_FLAG(std::string, source_map_out, "",
+          "(optional) output path for a JSON source map from the generated "
+          "Rust items (and C++ thunks) to the C++ declarations they were "
+          "generated from, for IDEs and debuggers.");
+ABSL_FLAG(std::string, stats_out, "",
+          "(optional) output path for JSON statistics about the run: the "
+          "number of items imported and bound, the reasons for skipping "
+          "items, the number of C++ thunks, and the time spent in Clang and "
+          "in code generation.");
+ABSL_FLAG(std::vector<std::string>, instantiations_manifests,
+          std::vector<std::string>(),
+          "(optional) comma-separated paths to `.instantiations` manifest "
+          "files. Each line of a manifest is a C++ class template "
+          "instantiation (e.g. `std::vector<int>`) that should be "
+          "instantiated and bound as part of the current target. Lines "
+          "prefixed with `function ` are C++ function template instantiations "
+          "(e.g. `function absl::StrCat<int>`) instead. Empty lines and lines "
+          "starting with `#` are ignored.");
+ABSL_FLAG(bool, watch, false,
+          "keep running after generating the bindings, and regenerate them "
+          "from scratch whenever one of the files that they were generated "
+          "from (the headers of the target and of its dependencies, and the "
+          "non-system headers that they include) changes. The files are "
+          "polled for changes, and nothing is cached between runs: each run "
+          "parses the headers again. Meant for iterating on headers locally, "
+          "outside of the build system.");
+ABSL_FLAG(std::string, exception_policy, "unchecked",
+          "what happens when a C++ function that is called from Rust throws "
+          "an exception: `unchecked` (nothing, the exception unwinds into "
+          "Rust, which is undefined behavior; only suitable for code built "
+          "with `-fno-exceptions`), `abort` (the thunks are `noexcept`, so "
+          "the program terminates) or `catch` (functions and methods return "
+          "a `Result` with a `CxxException` error).");
+ABSL_FLAG(std::string, default_arguments, "overloads",
+          "how the parameters with default arguments of functions and "
+          "methods are bound: `overloads` (additional `<name>_without_<p>` "
+          "functions without the parameter `p` and the parameters after it) "
+          "or `option` (as `Option<T>` parameters, where `None` passes no "
+          "argument, so that C++ evaluates the default argument). "
+          "Constructors are always bound with `overloads`.");
+ABSL_FLAG(std::string, long_double, "skip",
+          "how `long double` is bound: `skip` (declarations that use it "
+          "don't get bindings), `opaque` (as the opaque `LongDouble` type of "
+          "the `long_double` support crate, which has the same size and "
+          "alignment) or `f64` (parameters and return values are bound as "
+          "`f64`, and converted from and to `double` by the C++ thunks, "
+          "losing precision; other uses are not supported).");
+ABSL_FLAG(std::string, char_mapping, "native",
+          "how plain `char` is bound: `native` (`i8` on targets where `char` "
+          "is signed and `u8` on targets where it is unsigned), `u8`, `i8` or "
+          "`c_char` (`::core::ffi::c_char`).");
+ABSL_FLAG(bool, lifetime_elision, false,
+          "whether Rust's lifetime elision rules are applied to the functions "
+          "of the current target that are not annotated with lifetimes, as "
+          "if all of its headers specified `#pragma clang lifetime_elision`. "
+          "Functions annotated with `CRUBIT_NO_LIFETIME_ELISION` are exempt.");
+ABSL_FLAG(std::vector<std::string>, support_type_maps,
+          std::vector<std::string>(),
+          "(optional) comma-separated built-in type mappings to enable, named "
+          "after the support crate they map into: `absl_status` (binds "
+          "`absl::Status` and `absl::StatusOr<T>`) and `cxx_string` (binds "
+          "`std::string` and string views). The bindings of the current "
+          "target must depend on the named support crates.");
 
 namespace crubit {
 
@@ -99,9 +273,113 @@ bool fromJSON(const llvm::json::Value& json, TargetArgs& out,
          mapper.mapOptional("f", out.features);
 }
 
+// Parses `key=value` pairs given to the `--flag_name` cmdline flag.
+absl::StatusOr<absl::flat_hash_map<std::string, std::string>>
+ParseKeyValuePairs(absl::string_view flag_name, absl::string_view format,
+                   const std::vector<std::string>& key_value_pairs) {
+  absl::flat_hash_map<std::string, std::string> result;
+  for (const std::string& key_value_pair : key_value_pairs) {
+    std::pair<std::string, std::string> key_and_value =
+        absl::StrSplit(key_value_pair, absl::MaxSplits('=', 1));
+    if (key_and_value.first.empty() || key_and_value.second.empty()) {
+      return absl::InvalidArgumentError(
+          absl::Substitute("Expected `$0` in --$1, but got `$2`", format,
+                           flag_name, key_value_pair));
+    }
+    if (!result.insert(std::move(key_and_value)).second) {
+      return absl::InvalidArgumentError(
+          absl::Substitute("Duplicate key in --$0=$1", flag_name,
+                           key_value_pair));
+    }
+  }
+  return result;
+}
+
 }  // namespace
 
 absl::StatusOr<Cmdline> Cmdline::Create() {
+  ThunkVisibility thunk_visibility;
+  std::string thunk_visibility_str = absl::GetFlag(FLAGS_thunk_visibility);
+  if (thunk_visibility_str == "default") {
+    thunk_visibility = ThunkVisibility::Default;
+  } else if (thunk_visibility_str == "hidden") {
+    thunk_visibility = ThunkVisibility::Hidden;
+  } else if (thunk_visibility_str == "internal") {
+    thunk_visibility = ThunkVisibility::Internal;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--thunk_visibility must be one of `default`, `hidden`, or "
+        "`internal`, but got `$0`",
+        thunk_visibility_str));
+  }
+  PointerUnsafety pointer_unsafety;
+  std::string pointer_unsafety_str = absl::GetFlag(FLAGS_pointer_unsafety);
+  if (pointer_unsafety_str == "any_pointer") {
+    pointer_unsafety = PointerUnsafety::AnyPointer;
+  } else if (pointer_unsafety_str == "top_level_pointer") {
+    pointer_unsafety = PointerUnsafety::TopLevelPointer;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--pointer_unsafety must be one of `any_pointer` or "
+        "`top_level_pointer`, but got `$0`",
+        pointer_unsafety_str));
+  }
+  ExceptionPolicy exception_policy;
+  std::string exception_policy_str = absl::GetFlag(FLAGS_exception_policy);
+  if (exception_policy_str == "unchecked") {
+    exception_policy = ExceptionPolicy::Unchecked;
+  } else if (exception_policy_str == "abort") {
+    exception_policy = ExceptionPolicy::Abort;
+  } else if (exception_policy_str == "catch") {
+    exception_policy = ExceptionPolicy::Catch;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--exception_policy must be one of `unchecked`, `abort`, or `catch`, "
+        "but got `$0`",
+        exception_policy_str));
+  }
+  DefaultArguments default_arguments;
+  std::string default_arguments_str = absl::GetFlag(FLAGS_default_arguments);
+  if (default_arguments_str == "overloads") {
+    default_arguments = DefaultArguments::Overloads;
+  } else if (default_arguments_str == "option") {
+    default_arguments = DefaultArguments::Option;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--default_arguments must be one of `overloads` or `option`, but got "
+        "`$0`",
+        default_arguments_str));
+  }
+  LongDoublePolicy long_double_policy;
+  std::string long_double_policy_str = absl::GetFlag(FLAGS_long_double);
+  if (long_double_policy_str == "skip") {
+    long_double_policy = LongDoublePolicy::kSkip;
+  } else if (long_double_policy_str == "opaque") {
+    long_double_policy = LongDoublePolicy::kOpaque;
+  } else if (long_double_policy_str == "f64") {
+    long_double_policy = LongDoublePolicy::kF64;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--long_double must be one of `skip`, `opaque`, or `f64`, but got "
+        "`$0`",
+        long_double_policy_str));
+  }
+  CharMapping char_mapping;
+  std::string char_mapping_str = absl::GetFlag(FLAGS_char_mapping);
+  if (char_mapping_str == "native") {
+    char_mapping = CharMapping::kNative;
+  } else if (char_mapping_str == "u8") {
+    char_mapping = CharMapping::kU8;
+  } else if (char_mapping_str == "i8") {
+    char_mapping = CharMapping::kI8;
+  } else if (char_mapping_str == "c_char") {
+    char_mapping = CharMapping::kCChar;
+  } else {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--char_mapping must be one of `native`, `u8`, `i8`, or `c_char`, "
+        "but got `$0`",
+        char_mapping_str));
+  }
   return CreateFromArgs(
       absl::GetFlag(FLAGS_target), absl::GetFlag(FLAGS_cc_out),
       absl::GetFlag(FLAGS_rs_out), absl::GetFlag(FLAGS_ir_out),
@@ -117,7 +395,42 @@ absl::StatusOr<Cmdline> Cmdline::Create() {
       absl::GetFlag(FLAGS_error_report_out),
       absl::GetFlag(FLAGS_generate_source_location_in_doc_comment)
           ? SourceLocationDocComment::Enabled
-          : SourceLocationDocComment::Disabled);
+          : SourceLocationDocComment::Disabled,
+      CmdlineOptions{
+          .generate_miri_compatible_bindings =
+              absl::GetFlag(FLAGS_generate_miri_compatible_bindings),
+          .fuzz_harness_out = absl::GetFlag(FLAGS_fuzz_harness_out),
+          .benchmark_out = absl::GetFlag(FLAGS_benchmark_out),
+          .thunk_visibility = thunk_visibility,
+          .generate_header_only_thunks =
+              absl::GetFlag(FLAGS_generate_header_only_thunks),
+          .split_namespaces = absl::GetFlag(FLAGS_split_namespaces),
+          .split_namespace_to_generate =
+              absl::GetFlag(FLAGS_split_namespace_to_generate),
+          .extern_rust_types = absl::GetFlag(FLAGS_extern_rust_types),
+          .snake_case_function_names =
+              absl::GetFlag(FLAGS_snake_case_function_names),
+          .constructor_builder_min_params =
+              absl::GetFlag(FLAGS_constructor_builder_min_params),
+          .generate_raw_module = absl::GetFlag(FLAGS_generate_raw_module),
+          .pointer_unsafety = pointer_unsafety,
+          .forced_includes = absl::GetFlag(FLAGS_forced_includes),
+          .macro_definitions = absl::GetFlag(FLAGS_macro_definitions),
+          .cuda_host_only = absl::GetFlag(FLAGS_cuda_host_only),
+          .errno_functions = absl::GetFlag(FLAGS_errno_functions),
+          .errno_std_io_error = absl::GetFlag(FLAGS_errno_std_io_error),
+          .watch = absl::GetFlag(FLAGS_watch),
+          .source_map_out = absl::GetFlag(FLAGS_source_map_out),
+          .stats_out = absl::GetFlag(FLAGS_stats_out),
+          .instantiations_manifests =
+              absl::GetFlag(FLAGS_instantiations_manifests),
+          .exception_policy = exception_policy,
+          .long_double_policy = long_double_policy,
+          .char_mapping = char_mapping,
+          .lifetime_elision = absl::GetFlag(FLAGS_lifetime_elision),
+          .support_type_maps = absl::GetFlag(FLAGS_support_type_maps),
+          .default_arguments = default_arguments,
+      });
 }
 
 absl::StatusOr<Cmdline> Cmdline::CreateFromArgs(
@@ -129,7 +442,8 @@ absl::StatusOr<Cmdline> Cmdline::CreateFromArgs(
     std::string target_args_str, std::vector<std::string> extra_rs_srcs,
     std::vector<std::string> srcs_to_scan_for_instantiations,
     std::string instantiations_out, std::string error_report_out,
-    SourceLocationDocComment generate_source_location_in_doc_comment) {
+    SourceLocationDocComment generate_source_location_in_doc_comment,
+    CmdlineOptions options) {
   Cmdline cmdline;
   if (current_target.empty()) {
     return absl::InvalidArgumentError("please specify --target");
@@ -169,6 +483,73 @@ absl::StatusOr<Cmdline> Cmdline::CreateFromArgs(
   cmdline.do_nothing_ = do_nothing;
   cmdline.generate_source_location_in_doc_comment_ =
       generate_source_location_in_doc_comment;
+  cmdline.generate_miri_compatible_bindings_ =
+      options.generate_miri_compatible_bindings;
+  cmdline.fuzz_harness_out_ = std::move(options.fuzz_harness_out);
+  cmdline.benchmark_out_ = std::move(options.benchmark_out);
+  cmdline.thunk_visibility_ = options.thunk_visibility;
+  cmdline.generate_header_only_thunks_ = options.generate_header_only_thunks;
+  CRUBIT_ASSIGN_OR_RETURN(
+      cmdline.split_namespaces_,
+      ParseKeyValuePairs("split_namespaces", "namespace=crate_name",
+                         options.split_namespaces));
+  if (!options.split_namespace_to_generate.empty() &&
+      !cmdline.split_namespaces_.contains(
+          options.split_namespace_to_generate)) {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--split_namespace_to_generate=$0 is not one of --split_namespaces",
+        options.split_namespace_to_generate));
+  }
+  cmdline.split_namespace_to_generate_ =
+      std::move(options.split_namespace_to_generate);
+  CRUBIT_ASSIGN_OR_RETURN(
+      cmdline.extern_rust_types_,
+      ParseKeyValuePairs("extern_rust_types", "cc_type=rust_type",
+                         options.extern_rust_types));
+  cmdline.snake_case_function_names_ = options.snake_case_function_names;
+  if (options.constructor_builder_min_params < 0) {
+    return absl::InvalidArgumentError(absl::Substitute(
+        "--constructor_builder_min_params must not be negative, but got $0",
+        options.constructor_builder_min_params));
+  }
+  cmdline.constructor_builder_min_params_ =
+      options.constructor_builder_min_params;
+  cmdline.generate_raw_module_ = options.generate_raw_module;
+  cmdline.pointer_unsafety_ = options.pointer_unsafety;
+  std::transform(options.forced_includes.begin(), options.forced_includes.end(),
+                 std::back_inserter(cmdline.forced_includes_),
+                 [](const std::string& s) { return HeaderName(s); });
+  for (const std::string& macro_definition : options.macro_definitions) {
+    if (macro_definition.empty() || macro_definition[0] == '=') {
+      return absl::InvalidArgumentError(absl::Substitute(
+          "Expected `--macro_definitions` entries of the form `NAME` or "
+          "`NAME=VALUE`, but got `$0`",
+          macro_definition));
+    }
+  }
+  cmdline.macro_definitions_ = std::move(options.macro_definitions);
+  cmdline.cuda_host_only_ = options.cuda_host_only;
+  cmdline.errno_functions_ = std::move(options.errno_functions);
+  cmdline.errno_std_io_error_ = options.errno_std_io_error;
+  cmdline.watch_ = options.watch;
+  cmdline.source_map_out_ = std::move(options.source_map_out);
+  cmdline.stats_out_ = std::move(options.stats_out);
+  cmdline.instantiations_manifests_ =
+      std::move(options.instantiations_manifests);
+  cmdline.exception_policy_ = options.exception_policy;
+  cmdline.long_double_policy_ = options.long_double_policy;
+  cmdline.char_mapping_ = options.char_mapping;
+  cmdline.lifetime_elision_ = options.lifetime_elision;
+  for (const std::string& type_map : options.support_type_maps) {
+    if (type_map != "absl_status" && type_map != "cxx_string") {
+      return absl::InvalidArgumentError(absl::Substitute(
+          "--support_type_maps entries must be one of `absl_status` or "
+          "`cxx_string`, but got `$0`",
+          type_map));
+    }
+  }
+  cmdline.support_type_maps_ = std::move(options.support_type_maps);
+  cmdline.default_arguments_ = options.default_arguments;
 
   if (public_headers.empty()) {
     return absl::InvalidArgumentError("please specify --public_headers");
@@ -19,6 +19,38 @@
 
 namespace crubit {
 
+// The optional command line arguments. Each field is named after, and defaults
+// to the default of, the corresponding flag.
+struct CmdlineOptions final {
+  bool generate_miri_compatible_bindings = false;
+  std::string fuzz_harness_out = "";
+  std::string benchmark_out = "";
+  ThunkVisibility thunk_visibility = ThunkVisibility::Default;
+  bool generate_header_only_thunks = false;
+  std::vector<std::string> split_namespaces = {};
+  std::string split_namespace_to_generate = "";
+  std::vector<std::string> extern_rust_types = {};
+  bool snake_case_function_names = false;
+  int constructor_builder_min_params = 0;
+  bool generate_raw_module = false;
+  PointerUnsafety pointer_unsafety = PointerUnsafety::AnyPointer;
+  std::vector<std::string> forced_includes = {};
+  std::vector<std::string> macro_definitions = {};
+  bool cuda_host_only = false;
+  std::vector<std::string> errno_functions = {};
+  bool errno_std_io_error = false;
+  bool watch = false;
+  std::string source_map_out = "";
+  std::string stats_out = "";
+  std::vector<std::string> instantiations_manifests = {};
+  ExceptionPolicy exception_policy = ExceptionPolicy::Unchecked;
+  LongDoublePolicy long_double_policy = LongDoublePolicy::kSkip;
+  CharMapping char_mapping = CharMapping::kNative;
+  bool lifetime_elision = false;
+  std::vector<std::string> support_type_maps = {};
+  DefaultArguments default_arguments = DefaultArguments::Overloads;
+};
+
 // Parses and validates command line arguments.
 class Cmdline {
  public:
@@ -36,7 +68,8 @@ class Cmdline {
       std::string target_args_str, std::vector<std::string> extra_rs_srcs,
       std::vector<std::string> srcs_to_scan_for_instantiations,
       std::string instantiations_out, std::string error_report_out,
-      SourceLocationDocComment generate_source_location_in_doc_comment) {
+      SourceLocationDocComment generate_source_location_in_doc_comment,
+      CmdlineOptions options = {}) {
     return CreateFromArgs(
         std::move(current_target), std::move(cc_out), std::move(rs_out),
         std::move(ir_out), std::move(namespaces_out),
@@ -45,7 +78,7 @@ class Cmdline {
         std::move(public_headers), std::move(target_args_str),
         std::move(extra_rs_srcs), std::move(srcs_to_scan_for_instantiations),
         std::move(instantiations_out), std::move(error_report_out),
-        generate_source_location_in_doc_comment);
+        generate_source_location_in_doc_comment, std::move(options));
   }
 
   Cmdline(const Cmdline&) = delete;
@@ -69,6 +102,59 @@ class Cmdline {
   SourceLocationDocComment generate_source_location_in_doc_comment() const {
     return generate_source_location_in_doc_comment_;
   }
+  bool generate_miri_compatible_bindings() const {
+    return generate_miri_compatible_bindings_;
+  }
+  absl::string_view fuzz_harness_out() const { return fuzz_harness_out_; }
+  absl::string_view benchmark_out() const { return benchmark_out_; }
+  ThunkVisibility thunk_visibility() const { return thunk_visibility_; }
+  bool generate_header_only_thunks() const {
+    return generate_header_only_thunks_;
+  }
+  const absl::flat_hash_map<std::string, std::string>& split_namespaces()
+      const {
+    return split_namespaces_;
+  }
+  absl::string_view split_namespace_to_generate() const {
+    return split_namespace_to_generate_;
+  }
+  const absl::flat_hash_map<std::string, std::string>& extern_rust_types()
+      const {
+    return extern_rust_types_;
+  }
+  bool snake_case_function_names() const {
+    return snake_case_function_names_;
+  }
+  int constructor_builder_min_params() const {
+    return constructor_builder_min_params_;
+  }
+  bool generate_raw_module() const { return generate_raw_module_; }
+  PointerUnsafety pointer_unsafety() const { return pointer_unsafety_; }
+  const std::vector<HeaderName>& forced_includes() const {
+    return forced_includes_;
+  }
+  const std::vector<std::string>& macro_definitions() const {
+    return macro_definitions_;
+  }
+  bool cuda_host_only() const { return cuda_host_only_; }
+  const std::vector<std::string>& errno_functions() const {
+    return errno_functions_;
+  }
+  bool errno_std_io_error() const { return errno_std_io_error_; }
+  bool watch() const { return watch_; }
+  absl::string_view source_map_out() const { return source_map_out_; }
+  absl::string_view stats_out() const { return stats_out_; }
+  const std::vector<std::string>& instantiations_manifests() const {
+    return instantiations_manifests_;
+  }
+  ExceptionPolicy exception_policy() const { return exception_policy_; }
+  LongDoublePolicy long_double_policy() const { return long_double_policy_; }
+  CharMapping char_mapping() const { return char_mapping_; }
+  bool lifetime_elision() const { return lifetime_elision_; }
+  const std::vector<std::string>& support_type_maps() const {
+    return support_type_maps_;
+  }
+  DefaultArguments default_arguments() const { return default_arguments_; }
   const std::vector<HeaderName>& public_headers() const {
     return public_headers_;
   }
@@ -105,7 +191,8 @@ class Cmdline {
       std::string target_args_str, std::vector<std::string> extra_rs_srcs,
       std::vector<std::string> srcs_to_scan_for_instantiations,
       std::string instantiations_out, std::string error_report_out,
-      SourceLocationDocComment generate_source_location_in_doc_comment);
+      SourceLocationDocComment generate_source_location_in_doc_comment,
+      CmdlineOptions options);
 
   absl::StatusOr<BazelLabel> FindHeader(const HeaderName& header) const;
 
@@ -120,6 +207,33 @@ class Cmdline {
   bool do_nothing_ = true;
   SourceLocationDocComment generate_source_location_in_doc_comment_ =
       SourceLocationDocComment::Enabled;
+  bool generate_miri_compatible_bindings_ = false;
+  std::string fuzz_harness_out_;
+  std::string benchmark_out_;
+  ThunkVisibility thunk_visibility_ = ThunkVisibility::Default;
+  bool generate_header_only_thunks_ = false;
+  absl::flat_hash_map<std::string, std::string> split_namespaces_;
+  std::string split_namespace_to_generate_;
+  absl::flat_hash_map<std::string, std::string> extern_rust_types_;
+  bool snake_case_function_names_ = false;
+  int constructor_builder_min_params_ = 0;
+  bool generate_raw_module_ = false;
+  PointerUnsafety pointer_unsafety_ = PointerUnsafety::AnyPointer;
+  std::vector<HeaderName> forced_includes_;
+  std::vector<std::string> macro_definitions_;
+  bool cuda_host_only_ = false;
+  std::vector<std::string> errno_functions_;
+  bool errno_std_io_error_ = false;
+  bool watch_ = false;
+  std::string source_map_out_;
+  std::string stats_out_;
+  std::vector<std::string> instantiations_manifests_;
+  ExceptionPolicy exception_policy_ = ExceptionPolicy::Unchecked;
+  LongDoublePolicy long_double_policy_ = LongDoublePolicy::kSkip;
+  CharMapping char_mapping_ = CharMapping::kNative;
+  bool lifetime_elision_ = false;
+  std::vector<std::string> support_type_maps_;
+  DefaultArguments default_arguments_ = DefaultArguments::Overloads;
 
   BazelLabel current_target_;
   std::vector<HeaderName> public_headers_;
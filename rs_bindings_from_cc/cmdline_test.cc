@@ -85,6 +85,7 @@ TEST(CmdlineTest, BasicCorrectInput) {
                            Pair(HeaderName("h2"), BazelLabel("//:t1"))));
   EXPECT_EQ(cmdline.generate_source_location_in_doc_comment(),
             SourceLocationDocComment::Disabled);
+  EXPECT_FALSE(cmdline.generate_miri_compatible_bindings());
 }
 
 TEST(CmdlineTest, TargetArgsEmpty) {
@@ -370,5 +371,115 @@ TEST(CmdlineTest, RustfmtExePathEmpty) {
       StatusIs(absl::StatusCode::kInvalidArgument,
                HasSubstr("please specify --rustfmt_exe_path")));
 }
+
+TEST(CmdlineTest, NegativeConstructorBuilderMinParams) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]}
+  ])";
+  ASSERT_THAT(
+      Cmdline::CreateForTesting(
+          "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+          "crubit_support_path", "clang_format_exe_path", "rustfmt_exe_path",
+          "rustfmt_config_path",
+          /* do_nothing= */ false, {"a.h"}, std::string(kTargetsAndHeaders),
+          /* extra_rs_srcs= */ {},
+          /* srcs_to_scan_for_instantiations= */ {},
+          /* instantiations_out= */ "", "error_report_out",
+          SourceLocationDocComment::Enabled,
+          /* options= */ {.constructor_builder_min_params = -1}),
+      StatusIs(absl::StatusCode::kInvalidArgument,
+               HasSubstr("--constructor_builder_min_params must not be "
+                         "negative, but got -1")));
+}
+
+TEST(CmdlineTest, InvalidMacroDefinition) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]}
+  ])";
+  ASSERT_THAT(
+      Cmdline::CreateForTesting(
+          "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+          "crubit_support_path", "clang_format_exe_path", "rustfmt_exe_path",
+          "rustfmt_config_path",
+          /* do_nothing= */ false, {"a.h"}, std::string(kTargetsAndHeaders),
+          /* extra_rs_srcs= */ {},
+          /* srcs_to_scan_for_instantiations= */ {},
+          /* instantiations_out= */ "", "error_report_out",
+          SourceLocationDocComment::Enabled,
+          /* options= */ {.macro_definitions = {"=1"}}),
+      StatusIs(absl::StatusCode::kInvalidArgument,
+               HasSubstr("Expected `--macro_definitions` entries of the form "
+                         "`NAME` or `NAME=VALUE`, but got `=1`")));
+}
+
+TEST(CmdlineTest, UnknownSupportTypeMap) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]}
+  ])";
+  ASSERT_THAT(
+      Cmdline::CreateForTesting(
+          "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+          "crubit_support_path", "clang_format_exe_path", "rustfmt_exe_path",
+          "rustfmt_config_path",
+          /* do_nothing= */ false, {"a.h"}, std::string(kTargetsAndHeaders),
+          /* extra_rs_srcs= */ {},
+          /* srcs_to_scan_for_instantiations= */ {},
+          /* instantiations_out= */ "", "error_report_out",
+          SourceLocationDocComment::Enabled,
+          /* options= */ {.support_type_maps = {"cxx_string", "std_string"}}),
+      StatusIs(absl::StatusCode::kInvalidArgument,
+               HasSubstr("--support_type_maps entries must be one of "
+                         "`absl_status` or `cxx_string`, but got "
+                         "`std_string`")));
+}
+
+absl::StatusOr<Cmdline> TestCmdlineWithSplitNamespaces(
+    std::vector<std::string> split_namespaces,
+    std::string split_namespace_to_generate) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]}
+  ])";
+  return Cmdline::CreateForTesting(
+      "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+      "crubit_support_path", "clang_format_exe_path", "rustfmt_exe_path",
+      "rustfmt_config_path",
+      /* do_nothing= */ false, {"a.h"}, std::string(kTargetsAndHeaders),
+      /* extra_rs_srcs= */ {},
+      /* srcs_to_scan_for_instantiations= */ {},
+      /* instantiations_out= */ "", "error_report_out",
+      SourceLocationDocComment::Enabled,
+      /* options= */
+      {.split_namespaces = std::move(split_namespaces),
+       .split_namespace_to_generate = std::move(split_namespace_to_generate)});
+}
+
+TEST(CmdlineTest, SplitNamespaces) {
+  ASSERT_OK_AND_ASSIGN(
+      Cmdline cmdline,
+      TestCmdlineWithSplitNamespaces({"ns1=crate1", "ns2=crate2"}, "ns2"));
+  EXPECT_THAT(
+      cmdline.split_namespaces(),
+      UnorderedElementsAre(Pair("ns1", "crate1"), Pair("ns2", "crate2")));
+  EXPECT_EQ(cmdline.split_namespace_to_generate(), "ns2");
+}
+
+TEST(CmdlineTest, SplitNamespacesInvalidFormat) {
+  ASSERT_THAT(TestCmdlineWithSplitNamespaces({"ns1"}, ""),
+              StatusIs(absl::StatusCode::kInvalidArgument,
+                       HasSubstr("Expected `namespace=crate_name`")));
+}
+
+TEST(CmdlineTest, SplitNamespacesDuplicateNamespace) {
+  ASSERT_THAT(
+      TestCmdlineWithSplitNamespaces({"ns1=crate1", "ns1=crate2"}, ""),
+      StatusIs(absl::StatusCode::kInvalidArgument,
+               HasSubstr("Duplicate key in --split_namespaces=ns1=crate2")));
+}
+
+TEST(CmdlineTest, SplitNamespaceToGenerateUnknown) {
+  ASSERT_THAT(TestCmdlineWithSplitNamespaces({"ns1=crate1"}, "ns2"),
+              StatusIs(absl::StatusCode::kInvalidArgument,
+                       HasSubstr("is not one of --split_namespaces")));
+}
 }  // namespace
 }  // namespace crubit
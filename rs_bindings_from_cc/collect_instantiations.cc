@@ -10,8 +10,14 @@
 
 #include "absl/status/status.h"
 #include "absl/status/statusor.h"
+#include "absl/strings/match.h"
+#include "absl/strings/str_split.h"
+#include "absl/strings/string_view.h"
+#include "absl/strings/strip.h"
 #include "absl/types/span.h"
 #include "common/ffi_types.h"
+#include "common/file_io.h"
+#include "common/status_macros.h"
 #include "llvm/Support/Error.h"
 #include "llvm/Support/FormatVariadic.h"
 #include "llvm/Support/JSON.h"
@@ -44,4 +50,23 @@ absl::StatusOr<std::vector<std::string>> CollectInstantiations(
   return absl::InternalError(llvm::toString(root.getError()));
 }
 
+absl::StatusOr<ManifestInstantiations> ReadInstantiationsManifests(
+    absl::Span<const std::string> manifests) {
+  ManifestInstantiations instantiations;
+  for (const std::string& manifest : manifests) {
+    CRUBIT_ASSIGN_OR_RETURN(std::string contents, GetFileContents(manifest));
+    for (absl::string_view line : absl::StrSplit(contents, '\n')) {
+      line = absl::StripAsciiWhitespace(line);
+      if (line.empty() || absl::StartsWith(line, "#")) continue;
+      if (absl::ConsumePrefix(&line, "function ")) {
+        instantiations.function_templates.push_back(
+            std::string(absl::StripLeadingAsciiWhitespace(line)));
+      } else {
+        instantiations.class_templates.push_back(std::string(line));
+      }
+    }
+  }
+  return instantiations;
+}
+
 }  // namespace crubit
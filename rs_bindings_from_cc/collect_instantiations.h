@@ -19,6 +19,24 @@ namespace crubit {
 absl::StatusOr<std::vector<std::string>> CollectInstantiations(
     absl::Span<const std::string> rust_sources);
 
+// C++ template instantiations listed in `.instantiations` manifests.
+struct ManifestInstantiations {
+  // Full class template specializations (e.g. `std::vector<int>`).
+  std::vector<std::string> class_templates;
+  // Function template specializations (e.g. `absl::StrCat<int>`).
+  std::vector<std::string> function_templates;
+};
+
+// Reads `.instantiations` manifest files given their filenames and returns all
+// C++ template instantiations listed in them.
+//
+// Each line of a manifest names one class template instantiation (e.g.
+// `std::vector<int>`), or one function template instantiation when prefixed
+// with `function ` (e.g. `function absl::StrCat<int>`). Leading and trailing
+// whitespace is ignored, as are empty lines and lines starting with `#`.
+absl::StatusOr<ManifestInstantiations> ReadInstantiationsManifests(
+    absl::Span<const std::string> manifests);
+
 }  // namespace crubit
 
 #endif  // CRUBIT_RS_BINDINGS_FROM_CC_COLLECT_INSTANTIATIONS_H_
@@ -9,6 +9,8 @@
 
 #include "gmock/gmock.h"
 #include "gtest/gtest.h"
+#include "absl/status/status.h"
+#include "absl/status/statusor.h"
 #include "common/status_test_matchers.h"
 #include "common/test_utils.h"
 
@@ -32,5 +34,31 @@ TEST(CollectInstantiationsTest, ReturnInstantiationsFromRustTest) {
               IsOkAndHolds(ElementsAre(StrEq("std :: vector < bool >"))));
 }
 
+TEST(CollectInstantiationsTest, ReadInstantiationsManifestsTest) {
+  std::string first = WriteFileForCurrentTest(
+      "first.instantiations",
+      "# Instantiations used by the Rust code.\n"
+      "std::vector<int>\n"
+      "\n"
+      "  std::map<int, float>  \n"
+      "function absl::StrCat<int>\n");
+  std::string second =
+      WriteFileForCurrentTest("second.instantiations", "MyTemplate<bool>");
+  absl::StatusOr<ManifestInstantiations> instantiations =
+      ReadInstantiationsManifests({std::move(first), std::move(second)});
+  ASSERT_OK(instantiations);
+  EXPECT_THAT(instantiations->class_templates,
+              ElementsAre(StrEq("std::vector<int>"),
+                          StrEq("std::map<int, float>"),
+                          StrEq("MyTemplate<bool>")));
+  EXPECT_THAT(instantiations->function_templates,
+              ElementsAre(StrEq("absl::StrCat<int>")));
+}
+
+TEST(CollectInstantiationsTest, ReadInstantiationsManifestsMissingFileTest) {
+  EXPECT_THAT(ReadInstantiationsManifests({"does/not/exist.instantiations"}),
+              StatusIs(absl::StatusCode::kInternal));
+}
+
 }  // namespace
 }  // namespace crubit
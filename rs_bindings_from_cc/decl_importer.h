@@ -12,8 +12,10 @@
 #include <vector>
 
 #include "absl/container/flat_hash_map.h"
+#include "absl/container/flat_hash_set.h"
 #include "absl/log/check.h"
 #include "absl/status/statusor.h"
+#include "absl/strings/string_view.h"
 #include "absl/types/span.h"
 #include "lifetime_annotations/lifetime_annotations.h"
 #include "lifetime_annotations/type_lifetimes.h"
@@ -30,12 +32,27 @@ namespace crubit {
 class Invocation {
  public:
   Invocation(BazelLabel target, absl::Span<const HeaderName> public_headers,
-             const absl::flat_hash_map<HeaderName, BazelLabel>& header_targets)
+             const absl::flat_hash_map<HeaderName, BazelLabel>& header_targets,
+             const absl::flat_hash_map<std::string, std::string>&
+                 extern_rust_types,
+             bool cuda_host_only,
+             absl::Span<const std::string> errno_functions,
+             LongDoublePolicy long_double_policy, CharMapping char_mapping,
+             bool lifetime_elision,
+             absl::Span<const std::string> support_type_maps)
       : target_(target),
         public_headers_(public_headers),
+        cuda_host_only_(cuda_host_only),
+        long_double_policy_(long_double_policy),
+        char_mapping_(char_mapping),
+        lifetime_elision_(lifetime_elision),
         lifetime_context_(std::make_shared<
                           clang::tidy::lifetimes::LifetimeAnnotationContext>()),
-        header_targets_(header_targets) {
+        header_targets_(header_targets),
+        extern_rust_types_(extern_rust_types),
+        errno_functions_(errno_functions.begin(), errno_functions.end()),
+        support_type_maps_(support_type_maps.begin(),
+                           support_type_maps.end()) {
     // Caller should verify that the inputs are non-empty.
     CHECK(!public_headers_.empty());
     CHECK(!header_targets_.empty());
@@ -52,6 +69,28 @@ class Invocation {
                                          : std::nullopt;
   }
 
+  // Returns the Rust type that already binds the C++ type with the given fully
+  // qualified name, if any.
+  std::optional<absl::string_view> extern_rust_type(
+      absl::string_view cc_qualified_name) const {
+    auto it = extern_rust_types_.find(cc_qualified_name);
+    return (it != extern_rust_types_.end())
+               ? std::optional<absl::string_view>(it->second)
+               : std::nullopt;
+  }
+
+  // Returns whether the function with the given fully qualified name reports
+  // errors through `errno` (see `--errno_functions`).
+  bool sets_errno(absl::string_view cc_qualified_name) const {
+    return errno_functions_.contains(cc_qualified_name);
+  }
+
+  // Returns whether the built-in type mapping into the given support crate
+  // (e.g. `cxx_string`) is enabled (see `--support_type_maps`).
+  bool support_type_map_enabled(absl::string_view support_crate) const {
+    return support_type_maps_.contains(support_crate);
+  }
+
   // The main target from which we are importing.
   const BazelLabel target_;
 
@@ -59,6 +98,19 @@ class Invocation {
   // `IR::public_headers` and `HeaderName` for more details.
   const absl::Span<const HeaderName> public_headers_;
 
+  // Whether `__host__ __device__` functions from CUDA headers are skipped.
+  const bool cuda_host_only_;
+
+  // How `long double` is bound (see `--long_double`).
+  const LongDoublePolicy long_double_policy_;
+
+  // How plain `char` is bound (see `--char_mapping`).
+  const CharMapping char_mapping_;
+
+  // Whether lifetime elision is enabled in all headers of the current target
+  // (see `--lifetime_elision`).
+  const bool lifetime_elision_;
+
   const std::shared_ptr<clang::tidy::lifetimes::LifetimeAnnotationContext>
       lifetime_context_;
 
@@ -67,6 +119,9 @@ class Invocation {
 
  private:
   const absl::flat_hash_map<HeaderName, BazelLabel>& header_targets_;
+  const absl::flat_hash_map<std::string, std::string>& extern_rust_types_;
+  const absl::flat_hash_set<std::string> errno_functions_;
+  const absl::flat_hash_set<std::string> support_type_maps_;
 };
 
 // Explicitly defined interface that defines how `DeclImporter`s are allowed to
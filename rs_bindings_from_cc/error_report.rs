@@ -113,6 +113,16 @@ macro_rules! ensure {
     };
 }
 
+/// Returns the category of `error`, which groups similar errors together: the
+/// format string of errors created by the macros above (e.g. `Unsupported type
+/// '{}'`), or the message of other errors.
+pub fn error_category(error: &arc_anyhow::Error) -> Cow<'static, str> {
+    match error.downcast_ref::<AttributedError>() {
+        Some(error) => error.fmt.clone(),
+        None => Cow::Owned(format!("{error}")),
+    }
+}
+
 pub trait ErrorReporting: std::fmt::Debug {
     /// Inserts a new error. Uses interior mutability so that references can be
     /// shared freely.
@@ -5,19 +5,56 @@
 #include "rs_bindings_from_cc/frontend_action.h"
 
 #include <memory>
+#include <string>
+#include <vector>
 
+#include "absl/container/flat_hash_set.h"
 #include "lifetime_annotations/lifetime_annotations.h"
 #include "rs_bindings_from_cc/ast_consumer.h"
 #include "clang/AST/ASTConsumer.h"
+#include "clang/Basic/SourceLocation.h"
+#include "clang/Basic/SourceManager.h"
 #include "clang/Frontend/CompilerInstance.h"
+#include "clang/Lex/PPCallbacks.h"
 #include "llvm/ADT/StringRef.h"
 
 namespace crubit {
 
+namespace {
+
+// Records the non-system files that are entered by the preprocessor, i.e. the
+// headers and the headers that they include, transitively.
+class InputFilesRecorder : public clang::PPCallbacks {
+ public:
+  InputFilesRecorder(const clang::SourceManager& source_manager,
+                     std::vector<std::string>& input_files)
+      : source_manager_(source_manager), input_files_(input_files) {}
+
+  void FileChanged(clang::SourceLocation loc, FileChangeReason reason,
+                   clang::SrcMgr::CharacteristicKind file_type,
+                   clang::FileID) override {
+    if (reason != EnterFile || clang::SrcMgr::isSystem(file_type)) return;
+    std::string path(source_manager_.getFilename(loc));
+    if (!path.empty() && recorded_.insert(path).second) {
+      input_files_.push_back(std::move(path));
+    }
+  }
+
+ private:
+  const clang::SourceManager& source_manager_;
+  std::vector<std::string>& input_files_;
+  absl::flat_hash_set<std::string> recorded_;
+};
+
+}  // namespace
+
 std::unique_ptr<clang::ASTConsumer> FrontendAction::CreateASTConsumer(
     clang::CompilerInstance& instance, llvm::StringRef) {
   AddLifetimeAnnotationHandlers(instance.getPreprocessor(),
                                 invocation_.lifetime_context_);
+  instance.getPreprocessor().addPPCallbacks(
+      std::make_unique<InputFilesRecorder>(instance.getSourceManager(),
+                                           invocation_.ir_.input_files));
   return std::make_unique<AstConsumer>(instance, invocation_);
 }
 
@@ -12,7 +12,12 @@
 #include "absl/container/flat_hash_map.h"
 #include "absl/container/flat_hash_set.h"
 #include "absl/log/check.h"
+#include "absl/status/status.h"
+#include "absl/status/statusor.h"
+#include "absl/strings/str_cat.h"
 #include "absl/strings/string_view.h"
+#include "absl/time/clock.h"
+#include "absl/time/time.h"
 #include "common/status_macros.h"
 #include "rs_bindings_from_cc/cmdline.h"
 #include "rs_bindings_from_cc/collect_instantiations.h"
@@ -20,6 +25,9 @@
 #include "rs_bindings_from_cc/ir.h"
 #include "rs_bindings_from_cc/ir_from_cc.h"
 #include "rs_bindings_from_cc/src_code_gen.h"
+#include "llvm/Support/Error.h"
+#include "llvm/Support/FormatVariadic.h"
+#include "llvm/Support/JSON.h"
 
 namespace crubit {
 
@@ -57,6 +65,25 @@ std::vector<const Record*> FindInstantiationsInNamespace(const IR& ir,
   return result;
 }
 
+// Adds the time spent in Clang (i.e. building the IR) and in code generation to
+// the JSON `stats` produced by the code generator.
+absl::StatusOr<std::string> AddTimingsToStats(absl::string_view stats,
+                                              absl::Duration clang_time,
+                                              absl::Duration codegen_time) {
+  llvm::Expected<llvm::json::Value> json = llvm::json::parse(stats);
+  if (!json) {
+    return absl::InternalError(
+        absl::StrCat("Invalid stats JSON: ", llvm::toString(json.takeError())));
+  }
+  llvm::json::Object* obj = json->getAsObject();
+  if (obj == nullptr) {
+    return absl::InternalError("Expected the stats JSON to be an object");
+  }
+  (*obj)["clang_ms"] = absl::ToInt64Milliseconds(clang_time);
+  (*obj)["codegen_ms"] = absl::ToInt64Milliseconds(codegen_time);
+  return std::string(llvm::formatv("{0:2}", *json));
+}
+
 absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
     Cmdline& cmdline, std::vector<std::string> clang_args,
     absl::flat_hash_map<const HeaderName, const std::string>
@@ -68,7 +95,15 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
   CRUBIT_ASSIGN_OR_RETURN(
       std::vector<std::string> requested_instantiations,
       CollectInstantiations(cmdline.srcs_to_scan_for_instantiations()));
-
+  CRUBIT_ASSIGN_OR_RETURN(
+      ManifestInstantiations manifest_instantiations,
+      ReadInstantiationsManifests(cmdline.instantiations_manifests()));
+  requested_instantiations.insert(
+      requested_instantiations.end(),
+      manifest_instantiations.class_templates.begin(),
+      manifest_instantiations.class_templates.end());
+
+  absl::Time clang_start = absl::Now();
   CRUBIT_ASSIGN_OR_RETURN(
       IR ir, IrFromCc({.current_target = cmdline.current_target(),
                        .public_headers = cmdline.public_headers(),
@@ -78,20 +113,62 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
                        .extra_rs_srcs = cmdline.extra_rs_srcs(),
                        .clang_args = clang_args_view,
                        .extra_instantiations = requested_instantiations,
-                       .crubit_features = cmdline.target_to_features()}));
+                       .extra_function_template_instantiations =
+                           manifest_instantiations.function_templates,
+                       .crubit_features = cmdline.target_to_features(),
+                       .extern_rust_types = cmdline.extern_rust_types(),
+                       .forced_includes = cmdline.forced_includes(),
+                       .macro_definitions = cmdline.macro_definitions(),
+                       .cuda_host_only = cmdline.cuda_host_only(),
+                       .errno_functions = cmdline.errno_functions(),
+                       .long_double_policy = cmdline.long_double_policy(),
+                       .char_mapping = cmdline.char_mapping(),
+                       .lifetime_elision = cmdline.lifetime_elision(),
+                       .support_type_maps = cmdline.support_type_maps()}));
+  absl::Duration clang_time = absl::Now() - clang_start;
 
   if (!cmdline.instantiations_out().empty()) {
     ir.crate_root_path = "__cc_template_instantiations_rs_api";
   }
-
-  bool generate_error_report = !cmdline.error_report_out().empty();
+  ir.split_namespaces = cmdline.split_namespaces();
+  ir.split_namespace_to_generate =
+      std::string(cmdline.split_namespace_to_generate());
+
+  CodegenOptions codegen_options{
+      .generate_error_report = !cmdline.error_report_out().empty(),
+      .generate_source_location_in_doc_comment =
+          cmdline.generate_source_location_in_doc_comment(),
+      .generate_miri_compatible_bindings =
+          cmdline.generate_miri_compatible_bindings(),
+      .generate_fuzz_harness = !cmdline.fuzz_harness_out().empty(),
+      .generate_benchmark = !cmdline.benchmark_out().empty(),
+      .generate_source_map = !cmdline.source_map_out().empty(),
+      .generate_stats = !cmdline.stats_out().empty(),
+      .thunk_visibility = cmdline.thunk_visibility(),
+      .generate_header_only_thunks = cmdline.generate_header_only_thunks(),
+      .snake_case_function_names = cmdline.snake_case_function_names(),
+      .constructor_builder_min_params =
+          cmdline.constructor_builder_min_params(),
+      .generate_raw_module = cmdline.generate_raw_module(),
+      .pointer_unsafety = cmdline.pointer_unsafety(),
+      .exception_policy = cmdline.exception_policy(),
+      .errno_std_io_error = cmdline.errno_std_io_error(),
+      .default_arguments = cmdline.default_arguments(),
+  };
+  absl::Time codegen_start = absl::Now();
   CRUBIT_ASSIGN_OR_RETURN(
       Bindings bindings,
       GenerateBindings(ir, cmdline.crubit_support_path(),
                        cmdline.clang_format_exe_path(),
                        cmdline.rustfmt_exe_path(),
-                       cmdline.rustfmt_config_path(), generate_error_report,
-                       cmdline.generate_source_location_in_doc_comment()));
+                       cmdline.rustfmt_config_path(), codegen_options));
+  absl::Duration codegen_time = absl::Now() - codegen_start;
+
+  std::string stats;
+  if (codegen_options.generate_stats) {
+    CRUBIT_ASSIGN_OR_RETURN(stats, AddTimingsToStats(bindings.stats, clang_time,
+                                                     codegen_time));
+  }
 
   absl::flat_hash_map<std::string, std::string> instantiations;
   std::optional<const Namespace*> ns =
@@ -113,6 +190,10 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
       .namespaces = std::move(top_level_namespaces),
       .instantiations = std::move(instantiations),
       .error_report = bindings.error_report,
+      .fuzz_harness = bindings.fuzz_harness,
+      .benchmark = bindings.benchmark,
+      .source_map = bindings.source_map,
+      .stats = std::move(stats),
   };
 }
 
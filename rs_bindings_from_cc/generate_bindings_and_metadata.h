@@ -31,6 +31,15 @@ struct BindingsAndMetadata {
   absl::flat_hash_map<std::string, std::string> instantiations;
   // A JSON error report, if requested.
   std::string error_report;
+  // A cargo-fuzz / libFuzzer harness for the generated bindings, if requested.
+  std::string fuzz_harness;
+  // A Criterion benchmark for the generated bindings, if requested.
+  std::string benchmark;
+  // A JSON source map from the generated items to C++ declarations, if
+  // requested.
+  std::string source_map;
+  // JSON statistics about the run, if requested.
+  std::string stats;
 };
 
 // Returns `BindingsAndMetadata` as requested by the user on the command line.
@@ -23,6 +23,7 @@ namespace crubit {
 namespace {
 
 using ::testing::ElementsAre;
+using ::testing::HasSubstr;
 using ::testing::IsEmpty;
 using ::testing::Pair;
 using ::testing::StrEq;
@@ -92,6 +93,84 @@ TEST(GenerateBindingsAndMetadataTest, InstantiationsAreEmptyInNormalMode) {
   ASSERT_THAT(result.instantiations, IsEmpty());
 }
 
+TEST(GenerateBindingsAndMetadataTest, InstantiationsFromManifests) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]}
+  ])";
+  std::string manifest = WriteFileForCurrentTest(
+      "target1.instantiations",
+      "# Used from Rust.\nMyTemplate<int>\nfunction Add<int>\n");
+  ASSERT_OK_AND_ASSIGN(
+      Cmdline cmdline,
+      Cmdline::CreateForTesting(
+          "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+          "crubit_support_path", std::string(kDefaultClangFormatExePath),
+          std::string(kDefaultRustfmtExePath), "nowhere/rustfmt.toml",
+          /* do_nothing= */ false,
+          /* public_headers= */ {"a.h"}, std::string(kTargetsAndHeaders),
+          /* extra_rs_srcs= */ {},
+          /* srcs_to_scan_for_instantiations= */ {},
+          /* instantiations_out= */ "",
+          /* error_report_out= */ "", SourceLocationDocComment::Enabled,
+          /* options= */ {.instantiations_manifests = {manifest}}));
+
+  ASSERT_OK_AND_ASSIGN(
+      BindingsAndMetadata result,
+      GenerateBindingsAndMetadata(
+          cmdline, DefaultClangArgs(),
+          /*virtual_headers_contents_for_testing=*/
+          {{HeaderName("a.h"),
+            "template <typename T> struct MyTemplate { T value; };\n"
+            "template <typename T> T Add(T a, T b) { return a + b; }"}}));
+
+  EXPECT_THAT(result.instantiations,
+              ElementsAre(Pair("MyTemplate<int>",
+                               "__CcTemplateInst10MyTemplateIiE")));
+  EXPECT_THAT(result.rs_api,
+              HasSubstr("pub struct __CcTemplateInst10MyTemplateIiE"));
+  EXPECT_THAT(result.rs_api, HasSubstr("pub fn Add_int("));
+  EXPECT_THAT(result.rs_api_impl, HasSubstr("return Add<int>(a, b);"));
+}
+
+TEST(GenerateBindingsAndMetadataTest, ForcedIncludesAndMacroDefinitions) {
+  constexpr absl::string_view kTargetsAndHeaders = R"([
+    {"t": "//:target1", "h": ["a.h"]},
+    {"t": "//:shims", "h": ["shims.h"]}
+  ])";
+
+  ASSERT_OK_AND_ASSIGN(
+      Cmdline cmdline,
+      Cmdline::CreateForTesting(
+          "//:target1", "cc_out", "rs_out", "ir_out", "namespaces_out",
+          "crubit_support_path", std::string(kDefaultClangFormatExePath),
+          std::string(kDefaultRustfmtExePath), "nowhere/rustfmt.toml",
+          /* do_nothing= */ false,
+          /* public_headers= */ {"a.h"}, std::string(kTargetsAndHeaders),
+          /* extra_rs_srcs= */ {},
+          /* srcs_to_scan_for_instantiations= */ {},
+          /* instantiations_out= */ "",
+          /* error_report_out= */ "", SourceLocationDocComment::Enabled,
+          /* options= */ {.forced_includes = {"shims.h"},
+                          .macro_definitions = {"MY_EXPORT="}}));
+
+  // Without the forced include and the macro definition, `a.h` doesn't
+  // compile.
+  ASSERT_OK_AND_ASSIGN(
+      BindingsAndMetadata result,
+      GenerateBindingsAndMetadata(
+          cmdline, DefaultClangArgs(),
+          /*virtual_headers_contents_for_testing=*/
+          {{HeaderName("shims.h"), "#define MY_OBJECT static int kind();"},
+           {HeaderName("a.h"),
+            "struct MY_EXPORT Widget { MY_OBJECT int x; };"}}));
+
+  ASSERT_THAT(result.rs_api, HasSubstr("pub struct Widget"));
+  ASSERT_THAT(result.rs_api, HasSubstr("pub fn kind()"));
+  // The C++ thunks see the same declarations.
+  EXPECT_THAT(result.rs_api_impl, HasSubstr("#define MY_EXPORT \n"));
+  EXPECT_THAT(result.rs_api_impl, HasSubstr("#include \"shims.h\"\n"));
+}
+
 absl::StatusOr<absl::flat_hash_map<std::string, std::string>>
 GetInstantiationsFor(absl::string_view header_content,
                      absl::string_view rust_source) {
@@ -43,8 +43,11 @@
 #include "clang/AST/DeclBase.h"
 #include "clang/AST/DeclCXX.h"
 #include "clang/AST/DeclFriend.h"
+#include "clang/AST/DeclTemplate.h"
 #include "clang/AST/Mangle.h"
+#include "clang/AST/PrettyPrinter.h"
 #include "clang/AST/RawCommentList.h"
+#include "clang/AST/TemplateBase.h"
 #include "clang/AST/Type.h"
 #include "clang/Basic/FileManager.h"
 #include "clang/Basic/LLVM.h"
@@ -52,8 +55,12 @@
 #include "clang/Basic/SourceLocation.h"
 #include "clang/Basic/SourceManager.h"
 #include "clang/Basic/Specifiers.h"
+#include "clang/Basic/TargetInfo.h"
+#include "clang/Lex/MacroInfo.h"
+#include "clang/Lex/Preprocessor.h"
 #include "clang/Sema/Sema.h"
 #include "llvm/ADT/STLExtras.h"
+#include "llvm/ADT/Triple.h"
 #include "llvm/Support/Casting.h"
 #include "llvm/Support/FormatVariadic.h"
 #include "llvm/Support/Regex.h"
@@ -242,6 +249,12 @@ Importer::SourceOrderKey Importer::GetSourceOrderKey(
   return SourceOrderKey(comment->getSourceRange());
 }
 
+Importer::SourceOrderKey Importer::GetSourceOrderKey(
+    const clang::MacroInfo* macro_info) const {
+  return SourceOrderKey(clang::SourceRange(macro_info->getDefinitionLoc(),
+                                           macro_info->getDefinitionEndLoc()));
+}
+
 class Importer::SourceLocationComparator {
  public:
   bool operator()(const clang::SourceLocation& a,
@@ -361,6 +374,13 @@ std::vector<ItemId> Importer::GetItemIdsInSourceOrder(
   for (auto& [_, comment] : ordered_comments) {
     items.push_back({GetSourceOrderKey(comment), GenerateItemId(comment)});
   }
+  // Macros don't belong to namespaces or records.
+  if (decl_context->isTranslationUnit()) {
+    for (const auto& [macro_info, _] : macro_items_) {
+      items.push_back(
+          {GetSourceOrderKey(macro_info), GenerateItemId(macro_info)});
+    }
+  }
   llvm::sort(items, compare_locations);
 
   std::vector<ItemId> ordered_item_ids;
@@ -374,10 +394,14 @@ std::vector<ItemId> Importer::GetItemIdsInSourceOrder(
 std::vector<ItemId> Importer::GetOrderedItemIdsOfTemplateInstantiations()
     const {
   std::vector<SourceLocationComparator::OrderedItemId> items;
-  items.reserve(class_template_instantiations_.size());
+  items.reserve(class_template_instantiations_.size() +
+                function_template_instantiations_.size());
   for (const auto* decl : class_template_instantiations_) {
     items.push_back({GetSourceOrderKey(decl), GenerateItemId(decl)});
   }
+  for (const auto* decl : function_template_instantiations_) {
+    items.push_back({GetSourceOrderKey(decl), GenerateItemId(decl)});
+  }
 
   clang::SourceManager& sm = ctx_.getSourceManager();
   auto compare_locations = SourceLocationComparator(sm);
@@ -406,6 +430,23 @@ void Importer::ImportFreeComments() {
   llvm::sort(comments_, SourceLocationComparator(sm));
 }
 
+void Importer::ImportMacros() {
+  MacroImporter macro_importer(*this);
+  clang::Preprocessor& preprocessor = sema_.getPreprocessor();
+  for (const auto& [identifier, _] : preprocessor.macros()) {
+    const clang::MacroInfo* macro_info = preprocessor.getMacroInfo(identifier);
+    if (macro_info == nullptr ||
+        GetOwningTargetOfLocation(macro_info->getDefinitionLoc()) !=
+            invocation_.target_) {
+      continue;
+    }
+    if (std::optional<IR::Item> item =
+            macro_importer.Import(*identifier, *macro_info)) {
+      macro_items_.push_back({macro_info, *std::move(item)});
+    }
+  }
+}
+
 void Importer::Import(clang::TranslationUnitDecl* translation_unit_decl) {
   ImportFreeComments();
   clang::SourceManager& sm = ctx_.getSourceManager();
@@ -420,6 +461,14 @@ void Importer::Import(clang::TranslationUnitDecl* translation_unit_decl) {
   }
 
   ImportDeclsFromDeclContext(translation_unit_decl);
+  ImportExplicitClassTemplateInstantiations(translation_unit_decl);
+  ImportFunctionTemplateInstantiations(translation_unit_decl);
+  // Macros are imported last, since the types that they use may need to be
+  // imported.
+  ImportMacros();
+  for (const auto& [macro_info, item] : macro_items_) {
+    ordered_items.push_back({GetSourceOrderKey(macro_info), item});
+  }
   for (const auto& [decl, item] : import_cache_) {
     if (item.has_value()) {
       if (std::holds_alternative<UnsupportedItem>(*item) &&
@@ -452,6 +501,76 @@ void Importer::ImportDeclsFromDeclContext(
   }
 }
 
+void Importer::ImportExplicitClassTemplateInstantiations(
+    const clang::DeclContext* decl_context) {
+  for (clang::Decl* decl : decl_context->decls()) {
+    if (clang::isa<clang::LinkageSpecDecl>(decl) ||
+        clang::isa<clang::NamespaceDecl>(decl)) {
+      ImportExplicitClassTemplateInstantiations(
+          clang::cast<clang::DeclContext>(decl));
+      continue;
+    }
+    // Explicit instantiations are never found by name lookup, so Clang adds
+    // the specialization to the decl context where the instantiation is
+    // spelled.  Implicit instantiations and explicit specializations are
+    // only imported when used (see `ConvertTemplateSpecializationType`).
+    //
+    // TODO(b/245467707): Consider also handling explicit instantiation
+    // declarations (`extern template class MyTemplate<int>;`).
+    auto* specialization_decl =
+        clang::dyn_cast<clang::ClassTemplateSpecializationDecl>(decl);
+    if (specialization_decl == nullptr ||
+        specialization_decl->getSpecializationKind() !=
+            clang::TSK_ExplicitInstantiationDefinition) {
+      continue;
+    }
+    // `GetOwningTarget` attributes all specializations to the current target,
+    // so look at where the explicit instantiation is spelled instead.
+    if (GetOwningTargetOfLocation(specialization_decl->getLocation()) !=
+        invocation_.target_) {
+      continue;
+    }
+    GetDeclItem(specialization_decl);
+  }
+}
+
+void Importer::ImportFunctionTemplateInstantiations(
+    clang::TranslationUnitDecl* translation_unit_decl) {
+  clang::SourceManager& sm = ctx_.getSourceManager();
+  for (clang::Decl* decl : translation_unit_decl->decls()) {
+    auto* namespace_decl = clang::dyn_cast<clang::NamespaceDecl>(decl);
+    if (namespace_decl == nullptr ||
+        !sm.isInMainFile(namespace_decl->getLocation())) {
+      continue;
+    }
+    for (clang::Decl* child : namespace_decl->decls()) {
+      // `IrFromCc` spells each requested instantiation as
+      // `inline constexpr auto* __cc_function_template_instantiation_N = &f<T>;`
+      auto* var_decl = clang::dyn_cast<clang::VarDecl>(child);
+      if (var_decl == nullptr || !var_decl->hasInit()) continue;
+      clang::Expr* init = var_decl->getInit()->IgnoreParenImpCasts();
+      if (auto* address_of = clang::dyn_cast<clang::UnaryOperator>(init);
+          address_of != nullptr &&
+          address_of->getOpcode() == clang::UO_AddrOf) {
+        init = address_of->getSubExpr()->IgnoreParenImpCasts();
+      }
+      auto* decl_ref = clang::dyn_cast<clang::DeclRefExpr>(init);
+      if (decl_ref == nullptr) continue;
+      auto* function_decl =
+          clang::dyn_cast<clang::FunctionDecl>(decl_ref->getDecl());
+      if (function_decl == nullptr ||
+          !function_decl->isFunctionTemplateSpecialization()) {
+        continue;
+      }
+      if (GetDeclItem(function_decl).has_value()) {
+        // Store `function_decl`s so that they will get included in
+        // IR::top_level_item_ids.
+        function_template_instantiations_.insert(function_decl);
+      }
+    }
+  }
+}
+
 std::optional<IR::Item> Importer::GetDeclItem(clang::Decl* decl) {
   // TODO(jeanpierreda): Move `decl->getCanonicalDecl()` from callers into here.
   if (auto it = import_cache_.find(decl); it != import_cache_.end()) {
@@ -552,8 +671,20 @@ bool IsTransitivelyInPrivate(clang::Decl* decl_to_check) {
   }
 }
 
+/// Returns true if a decl, or any decl enclosing it, is annotated with
+/// `CRUBIT_DO_NOT_BIND`.
+bool IsTransitivelyDoNotBind(const clang::Decl* decl_to_check) {
+  while (decl_to_check != nullptr) {
+    if (HasAnnotation(decl_to_check, "crubit_do_not_bind")) return true;
+    decl_to_check =
+        llvm::dyn_cast<clang::Decl>(decl_to_check->getDeclContext());
+  }
+  return false;
+}
+
 std::optional<IR::Item> Importer::ImportDecl(clang::Decl* decl) {
   if (IsTransitivelyInPrivate(decl)) return std::nullopt;
+  if (IsTransitivelyDoNotBind(decl)) return std::nullopt;
   for (auto& importer : decl_importers_) {
     std::optional<IR::Item> result = importer->ImportDecl(decl);
     if (result.has_value()) {
@@ -577,9 +708,19 @@ BazelLabel Importer::GetOwningTarget(const clang::Decl* decl) const {
   if (IsFullClassTemplateSpecializationOrChild(decl)) {
     return invocation_.target_;
   }
+  if (const auto* function_decl = clang::dyn_cast<clang::FunctionDecl>(decl);
+      function_decl != nullptr &&
+      function_decl->getTemplateSpecializationKind() ==
+          clang::TSK_ImplicitInstantiation) {
+    return invocation_.target_;
+  }
 
+  return GetOwningTargetOfLocation(decl->getLocation());
+}
+
+BazelLabel Importer::GetOwningTargetOfLocation(
+    clang::SourceLocation source_location) const {
   clang::SourceManager& source_manager = ctx_.getSourceManager();
-  auto source_location = decl->getLocation();
 
   // If the header this decl comes from is not associated with a target we
   // consider it a textual header. In that case we go up the include stack
@@ -748,6 +889,20 @@ absl::StatusOr<MappedType> Importer::ConvertTemplateSpecializationType(
 }
 
 absl::StatusOr<MappedType> Importer::ConvertTypeDecl(clang::NamedDecl* decl) {
+  // `typedef struct { ... } X;` and `typedef enum { ... } X;` don't get a
+  // separate TypeAlias item: the anonymous tag declaration is imported under
+  // the typedef name instead, so refer to it directly. Similarly, the C idiom
+  // `typedef struct X X;` doesn't get a TypeAlias item, since it would clash
+  // with the struct itself.
+  if (auto* typedef_decl = clang::dyn_cast<clang::TypedefNameDecl>(decl)) {
+    if (clang::TagDecl* anon_decl =
+            typedef_decl->getAnonDeclWithTypedefName()) {
+      decl = anon_decl;
+    } else if (clang::TagDecl* tag_decl =
+                   GetTagDeclNamedByTypedef(typedef_decl)) {
+      decl = tag_decl;
+    }
+  }
   if (!EnsureSuccessfullyImported(decl)) {
     return absl::NotFoundError(absl::Substitute(
         "No generated bindings found for '$0'", decl->getNameAsString()));
@@ -772,6 +927,384 @@ static bool IsSameCanonicalUnqualifiedType(clang::QualType type1,
   return type1 == type2;
 }
 
+// Returns the element type `T` of a `gsl::span<T>` with dynamic extent, or
+// `std::nullopt` if `type` is not such a span.
+static std::optional<clang::QualType> GetGslSpanElementType(
+    const clang::Type* type) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "gsl::span") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 2 || args[0].getKind() != clang::TemplateArgument::Type ||
+      args[1].getKind() != clang::TemplateArgument::Integral ||
+      !args[1].getAsIntegral().isMaxValue()) {
+    // Spans with a static extent don't store their size, and so have a
+    // different layout.
+    return std::nullopt;
+  }
+  return args[0].getAsType();
+}
+
+// Returns the element type `T` of an `absl::Span<T>`, or `std::nullopt` if
+// `type` is not an `absl::Span`.
+static std::optional<clang::QualType> GetAbslSpanElementType(
+    const clang::Type* type) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "absl::Span") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  return args[0].getAsType();
+}
+
+// Returns the name of the `core::arch` type that has the same size and
+// alignment as the given vector type (e.g. `__m128` or `float32x4_t`).
+static absl::StatusOr<std::string> GetCoreArchVectorTypeName(
+    const clang::VectorType* vector_type, const clang::ASTContext& ctx) {
+  const llvm::Triple& triple = ctx.getTargetInfo().getTriple();
+  clang::QualType element_type = vector_type->getElementType();
+  uint64_t size_in_bits = ctx.getTypeSize(vector_type);
+  bool is_integer =
+      element_type->isIntegerType() && !element_type->isBooleanType();
+  switch (triple.getArch()) {
+    case llvm::Triple::x86:
+    case llvm::Triple::x86_64: {
+      // The x86 types are named after the vector size and the kind of the
+      // elements, e.g. `__m256d` for any 256-bit vector of `double`s.
+      if (size_in_bits != 128 && size_in_bits != 256 && size_in_bits != 512) {
+        break;
+      }
+      absl::string_view suffix;
+      if (element_type->isSpecificBuiltinType(clang::BuiltinType::Float)) {
+        suffix = "";
+      } else if (element_type->isSpecificBuiltinType(
+                     clang::BuiltinType::Double)) {
+        suffix = "d";
+      } else if (is_integer) {
+        suffix = "i";
+      } else {
+        break;
+      }
+      absl::string_view module =
+          triple.getArch() == llvm::Triple::x86 ? "x86" : "x86_64";
+      return absl::StrCat("::core::arch::", module, "::__m", size_in_bits,
+                          suffix);
+    }
+    case llvm::Triple::aarch64: {
+      // The NEON types are named after the element type and count, e.g.
+      // `uint8x16_t`.
+      if (size_in_bits != 64 && size_in_bits != 128) break;
+      absl::string_view element_kind;
+      if (vector_type->getVectorKind() == clang::VectorType::NeonPolyVector) {
+        element_kind = "poly";
+      } else if (element_type->isSpecificBuiltinType(
+                     clang::BuiltinType::Float) ||
+                 element_type->isSpecificBuiltinType(
+                     clang::BuiltinType::Double)) {
+        element_kind = "float";
+      } else if (is_integer) {
+        element_kind = element_type->isSignedIntegerType() ? "int" : "uint";
+      } else {
+        break;
+      }
+      return absl::StrCat("::core::arch::aarch64::", element_kind,
+                          ctx.getTypeSize(element_type), "x",
+                          vector_type->getNumElements(), "_t");
+    }
+    default:
+      break;
+  }
+  return absl::UnimplementedError(absl::StrCat(
+      "Vector type '", clang::QualType(vector_type, 0).getAsString(),
+      "' has no `core::arch` counterpart on ", triple.getArchName().str()));
+}
+
+// Returns the element type `T` of a `_Complex T` or `std::complex<T>`, or
+// `std::nullopt` if `type` is not a complex number type. `std::complex` is
+// only recognized for `float` and `double`.
+static std::optional<clang::QualType> GetComplexElementType(
+    const clang::Type* type) {
+  if (const auto* complex_type = type->getAs<clang::ComplexType>()) {
+    return complex_type->getElementType();
+  }
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::complex") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  clang::QualType element_type = args[0].getAsType();
+  if (!element_type->isSpecificBuiltinType(clang::BuiltinType::Float) &&
+      !element_type->isSpecificBuiltinType(clang::BuiltinType::Double)) {
+    return std::nullopt;
+  }
+  return element_type;
+}
+
+// Returns whether `arg` is the type `char`.
+static bool IsCharTypeArgument(const clang::TemplateArgument& arg,
+                               clang::ASTContext& ctx) {
+  return arg.getKind() == clang::TemplateArgument::Type &&
+         ctx.hasSameType(arg.getAsType(), ctx.CharTy);
+}
+
+// Returns whether `arg` is the type `name<char>` (e.g. `std::allocator<char>`).
+static bool IsTemplateOfCharArgument(const clang::TemplateArgument& arg,
+                                     absl::string_view name,
+                                     clang::ASTContext& ctx) {
+  if (arg.getKind() != clang::TemplateArgument::Type) return false;
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          arg.getAsType()->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != name) {
+    return false;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  return args.size() == 1 && IsCharTypeArgument(args[0], ctx);
+}
+
+// Returns whether `type` is `std::string`, i.e. `std::basic_string` of `char`
+// with the default character traits and allocator.
+static bool IsStdString(const clang::Type* type, clang::ASTContext& ctx) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::basic_string") {
+    return false;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  return args.size() == 3 && IsCharTypeArgument(args[0], ctx) &&
+         IsTemplateOfCharArgument(args[1], "std::char_traits", ctx) &&
+         IsTemplateOfCharArgument(args[2], "std::allocator", ctx);
+}
+
+// Returns the C++ spelling of `type` if it is `std::string_view` (i.e.
+// `std::basic_string_view` of `char` with the default character traits) or a
+// non-alias `absl::string_view`, or `std::nullopt` otherwise.
+static std::optional<absl::string_view> GetStringViewName(
+    const clang::Type* type, clang::ASTContext& ctx) {
+  const clang::CXXRecordDecl* record_decl = type->getAsCXXRecordDecl();
+  if (record_decl == nullptr) return std::nullopt;
+  std::string name = record_decl->getQualifiedNameAsString();
+  if (name == "absl::string_view") return "absl::string_view";
+  const auto* specialization_decl =
+      clang::dyn_cast<clang::ClassTemplateSpecializationDecl>(record_decl);
+  if (specialization_decl == nullptr || name != "std::basic_string_view") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 2 || !IsCharTypeArgument(args[0], ctx) ||
+      !IsTemplateOfCharArgument(args[1], "std::char_traits", ctx)) {
+    return std::nullopt;
+  }
+  return "std::string_view";
+}
+
+// Returns the element type `T` of a `std::vector<T>` with the default
+// allocator, or `std::nullopt` if `type` is not such a vector.
+// `std::vector<bool>` is not recognized, because it doesn't store `bool`s.
+static std::optional<clang::QualType> GetStdVectorElementType(
+    const clang::Type* type, clang::ASTContext& ctx) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::vector") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 2 || args[0].getKind() != clang::TemplateArgument::Type ||
+      args[1].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  clang::QualType element_type = args[0].getAsType();
+  if (element_type->isBooleanType()) return std::nullopt;
+  const auto* allocator_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          args[1].getAsType()->getAsCXXRecordDecl());
+  if (allocator_decl == nullptr ||
+      allocator_decl->getQualifiedNameAsString() != "std::allocator" ||
+      allocator_decl->getTemplateArgs().size() != 1 ||
+      allocator_decl->getTemplateArgs()[0].getKind() !=
+          clang::TemplateArgument::Type ||
+      !ctx.hasSameType(allocator_decl->getTemplateArgs()[0].getAsType(),
+                       element_type)) {
+    return std::nullopt;
+  }
+  return element_type;
+}
+
+// Returns the value type `T` of a `std::optional<T>`, or `std::nullopt` if
+// `type` is not a `std::optional`.
+static std::optional<clang::QualType> GetStdOptionalValueType(
+    const clang::Type* type) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::optional") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  return args[0].getAsType();
+}
+
+// Returns the pointee type `T` of a `std::unique_ptr<T>` with the default
+// deleter, or `std::nullopt` if `type` is not such a pointer. Pointers to
+// arrays and to const objects are not recognized.
+static std::optional<clang::QualType> GetStdUniquePtrPointeeType(
+    const clang::Type* type, clang::ASTContext& ctx) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::unique_ptr") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 2 || args[0].getKind() != clang::TemplateArgument::Type ||
+      args[1].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  clang::QualType pointee_type = args[0].getAsType();
+  if (pointee_type->isArrayType() || pointee_type.isConstQualified()) {
+    return std::nullopt;
+  }
+  const auto* deleter_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          args[1].getAsType()->getAsCXXRecordDecl());
+  if (deleter_decl == nullptr ||
+      deleter_decl->getQualifiedNameAsString() != "std::default_delete" ||
+      deleter_decl->getTemplateArgs().size() != 1 ||
+      deleter_decl->getTemplateArgs()[0].getKind() !=
+          clang::TemplateArgument::Type ||
+      !ctx.hasSameType(deleter_decl->getTemplateArgs()[0].getAsType(),
+                       pointee_type)) {
+    return std::nullopt;
+  }
+  return pointee_type;
+}
+
+// Returns the pointee type `T` of a `std::shared_ptr<T>`, or `std::nullopt` if
+// `type` is not a `std::shared_ptr`. Pointers to arrays and to const objects
+// are not recognized.
+static std::optional<clang::QualType> GetStdSharedPtrPointeeType(
+    const clang::Type* type) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "std::shared_ptr") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  clang::QualType pointee_type = args[0].getAsType();
+  if (pointee_type->isArrayType() || pointee_type.isConstQualified()) {
+    return std::nullopt;
+  }
+  return pointee_type;
+}
+
+// Returns whether `type` is `absl::Status`.
+static bool IsAbslStatus(const clang::Type* type) {
+  const clang::CXXRecordDecl* record_decl = type->getAsCXXRecordDecl();
+  return record_decl != nullptr &&
+         record_decl->getQualifiedNameAsString() == "absl::Status";
+}
+
+// Returns the value type `T` of an `absl::StatusOr<T>`, or `std::nullopt` if
+// `type` is not an `absl::StatusOr`.
+static std::optional<clang::QualType> GetAbslStatusOrValueType(
+    const clang::Type* type) {
+  const auto* specialization_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "absl::StatusOr") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type) {
+    return std::nullopt;
+  }
+  return args[0].getAsType();
+}
+
+// Converts `long double` according to `--long_double`. (With
+// `LongDoublePolicy::kF64`, parameters and return values are converted by
+// `FunctionDeclImporter` instead.)
+static absl::StatusOr<MappedType> ConvertLongDouble(
+    LongDoublePolicy policy, const clang::ASTContext& ctx) {
+  switch (policy) {
+    case LongDoublePolicy::kSkip:
+      return absl::UnimplementedError(
+          "`long double` is not supported (see `--long_double`)");
+    case LongDoublePolicy::kOpaque:
+      // `LongDouble` is a 16-byte blob.
+      if (ctx.getTypeSize(ctx.LongDoubleTy) != 128 ||
+          ctx.getTypeAlign(ctx.LongDoubleTy) != 128) {
+        return absl::UnimplementedError(
+            "`long double` is only supported as an opaque type on targets "
+            "where its size and alignment are 16 bytes");
+      }
+      return MappedType::Simple("::long_double::LongDouble", "long double");
+    case LongDoublePolicy::kF64:
+      return absl::UnimplementedError(
+          "`long double` is only supported as the type of a parameter or of "
+          "a return value with `--long_double=f64`");
+  }
+  LOG(FATAL) << "The `switch` above should handle all cases";
+}
+
+// Converts plain `char` according to `--char_mapping`.
+static MappedType ConvertChar(CharMapping mapping, bool is_signed) {
+  switch (mapping) {
+    case CharMapping::kNative:
+      return MappedType::Simple(is_signed ? "i8" : "u8", "char");
+    case CharMapping::kU8:
+      return MappedType::Simple("u8", "char");
+    case CharMapping::kI8:
+      return MappedType::Simple("i8", "char");
+    case CharMapping::kCChar:
+      return MappedType::Simple("::core::ffi::c_char", "char");
+  }
+  LOG(FATAL) << "The `switch` above should handle all cases";
+}
+
 absl::StatusOr<MappedType> Importer::ConvertType(
     const clang::Type* type,
     const clang::tidy::lifetimes::ValueLifetimes* lifetimes,
@@ -785,6 +1318,185 @@ absl::StatusOr<MappedType> Importer::ConvertType(
   if (auto override_type = GetTypeMapOverride(*type);
       override_type.has_value()) {
     return *std::move(override_type);
+  } else if (std::optional<clang::QualType> element_type =
+                 GetGslSpanElementType(type);
+             element_type.has_value()) {
+    // `gsl::span<const T>` and `gsl::span<T>` are bound as the `Span<T>` and
+    // `SpanMut<T>` types from the `gsl` support crate, which have the same
+    // layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_element_type,
+        ConvertQualType(*element_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    policy.AlwaysIncludeTypeForTemplateArgument = true;
+    MappedType span_type = MappedType::Simple(
+        element_type->isConstQualified() ? "::gsl::Span" : "::gsl::SpanMut",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    span_type.rs_type.type_args.push_back(
+        std::move(mapped_element_type.rs_type));
+    return span_type;
+  } else if (std::optional<clang::QualType> element_type =
+                 GetAbslSpanElementType(type);
+             element_type.has_value()) {
+    // `absl::Span<const T>` and `absl::Span<T>` are bound as the `Span<T>` and
+    // `SpanMut<T>` types from the `absl_span` support crate, which have the
+    // same layout. Like for string views, the lifetime of the span is recorded
+    // if the span type has a lifetime parameter.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_element_type,
+        ConvertQualType(*element_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType span_type = MappedType::Simple(
+        element_type->isConstQualified() ? "::absl_span::Span"
+                                         : "::absl_span::SpanMut",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    span_type.rs_type.type_args.push_back(
+        std::move(mapped_element_type.rs_type));
+    llvm::SmallVector<std::string> lifetime_params =
+        clang::tidy::lifetimes::GetLifetimeParameters(
+            clang::QualType(type, 0));
+    if (lifetimes && lifetime_params.size() == 1) {
+      span_type.rs_type.lifetime_args.push_back(LifetimeId(
+          lifetimes->GetLifetimeParameter(lifetime_params[0]).Id()));
+    }
+    return span_type;
+  } else if (invocation_.support_type_map_enabled("absl_status") &&
+             IsAbslStatus(type)) {
+    // `absl::Status` is bound as `Status` from the `absl_status` support
+    // crate, which has the same layout.
+    return MappedType::Simple("::absl_status::Status", "absl::Status");
+  } else if (std::optional<clang::QualType> value_type =
+                 invocation_.support_type_map_enabled("absl_status")
+                     ? GetAbslStatusOrValueType(type)
+                     : std::nullopt;
+             value_type.has_value()) {
+    // `absl::StatusOr<T>` is bound as `StatusOr<T>` from the `absl_status`
+    // support crate, which has the same layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_value_type,
+        ConvertQualType(*value_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType status_or_type = MappedType::Simple(
+        "::absl_status::StatusOr",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    status_or_type.rs_type.type_args.push_back(
+        std::move(mapped_value_type.rs_type));
+    return status_or_type;
+  } else if (std::optional<clang::QualType> element_type =
+                 GetComplexElementType(type);
+             element_type.has_value()) {
+    // Complex numbers are bound as `Complex<T>` from the `complex` support
+    // crate, which has the same layout as both `_Complex T` and
+    // `std::complex<T>`.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_element_type,
+        ConvertQualType(*element_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType complex_type = MappedType::Simple(
+        "::complex::Complex",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    complex_type.rs_type.type_args.push_back(
+        std::move(mapped_element_type.rs_type));
+    return complex_type;
+  } else if (invocation_.support_type_map_enabled("cxx_string") &&
+             IsStdString(type, ctx_)) {
+    // `std::string` is bound as `CxxString` from the `cxx_string` support
+    // crate, which has the same size and alignment.
+    return MappedType::Simple("::cxx_string::CxxString", "std::string");
+  } else if (std::optional<absl::string_view> string_view_name =
+                 invocation_.support_type_map_enabled("cxx_string")
+                     ? GetStringViewName(type, ctx_)
+                     : std::nullopt;
+             string_view_name.has_value()) {
+    // String views are bound as `CxxStringView` from the `cxx_string` support
+    // crate, which has the same layout. If the string view type has a lifetime
+    // parameter (e.g. `LIFETIME_PARAM(s)`), its lifetime is recorded, so that
+    // returned string views can be bound as lifetime-bound slices.
+    MappedType string_view_type = MappedType::Simple(
+        "::cxx_string::CxxStringView", std::string(*string_view_name));
+    llvm::SmallVector<std::string> lifetime_params =
+        clang::tidy::lifetimes::GetLifetimeParameters(
+            clang::QualType(type, 0));
+    if (lifetimes && lifetime_params.size() == 1) {
+      string_view_type.rs_type.lifetime_args.push_back(LifetimeId(
+          lifetimes->GetLifetimeParameter(lifetime_params[0]).Id()));
+    }
+    return string_view_type;
+  } else if (std::optional<clang::QualType> element_type =
+                 GetStdVectorElementType(type, ctx_);
+             element_type.has_value()) {
+    // `std::vector<T>` is bound as `CxxVector<T>` from the `cxx_vector`
+    // support crate, which has the same layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_element_type,
+        ConvertQualType(*element_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType vector_type = MappedType::Simple(
+        "::cxx_vector::CxxVector",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    vector_type.rs_type.type_args.push_back(
+        std::move(mapped_element_type.rs_type));
+    return vector_type;
+  } else if (std::optional<clang::QualType> value_type =
+                 GetStdOptionalValueType(type);
+             value_type.has_value()) {
+    // `std::optional<T>` is bound as `CxxOptional<T>` from the `cxx_optional`
+    // support crate, which has the same layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_value_type,
+        ConvertQualType(*value_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType optional_type = MappedType::Simple(
+        "::cxx_optional::CxxOptional",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    optional_type.rs_type.type_args.push_back(
+        std::move(mapped_value_type.rs_type));
+    return optional_type;
+  } else if (std::optional<clang::QualType> pointee_type =
+                 GetStdUniquePtrPointeeType(type, ctx_);
+             pointee_type.has_value()) {
+    // `std::unique_ptr<T>` is bound as `UniquePtr<T>` from the
+    // `cxx_unique_ptr` support crate, which has the same layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_pointee_type,
+        ConvertQualType(*pointee_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType unique_ptr_type = MappedType::Simple(
+        "::cxx_unique_ptr::UniquePtr",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    unique_ptr_type.rs_type.type_args.push_back(
+        std::move(mapped_pointee_type.rs_type));
+    return unique_ptr_type;
+  } else if (std::optional<clang::QualType> pointee_type =
+                 GetStdSharedPtrPointeeType(type);
+             pointee_type.has_value()) {
+    // `std::shared_ptr<T>` is bound as `SharedPtr<T>` from the
+    // `cxx_shared_ptr` support crate, which has the same layout.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_pointee_type,
+        ConvertQualType(*pointee_type, /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    MappedType shared_ptr_type = MappedType::Simple(
+        "::cxx_shared_ptr::SharedPtr",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy));
+    shared_ptr_type.rs_type.type_args.push_back(
+        std::move(mapped_pointee_type.rs_type));
+    return shared_ptr_type;
+  } else if (const auto* vector_type = type->getAs<clang::VectorType>()) {
+    // SIMD types (e.g. `__m128` or `float32x4_t`) are bound as the
+    // `core::arch` type with the same layout, so that structs containing them
+    // keep their layout.
+    CRUBIT_ASSIGN_OR_RETURN(std::string rs_name,
+                            GetCoreArchVectorTypeName(vector_type, ctx_));
+    return MappedType::Simple(std::move(rs_name), type_string);
   } else if (type->isPointerType() || type->isLValueReferenceType() ||
              type->isRValueReferenceType()) {
     clang::QualType pointee_type = type->getPointeeType();
@@ -802,6 +1514,10 @@ absl::StatusOr<MappedType> Importer::ConvertType(
       CHECK(!lifetime.has_value() ||
             (lifetime->value() ==
              clang::tidy::lifetimes::Lifetime::Static().Id()));
+      if (func_type->isVariadic()) {
+        return absl::UnimplementedError(
+            "Pointers and references to variadic functions are not supported");
+      }
 
       clang::StringRef cc_call_conv =
           clang::FunctionType::getNameForCallConv(func_type->getCallConv());
@@ -844,9 +1560,22 @@ absl::StatusOr<MappedType> Importer::ConvertType(
       }
     }
 
+    // Rust references can't refer to `volatile` objects, so pointers and
+    // references to them are bound as raw pointers (to be accessed with
+    // `read_volatile` and `write_volatile`), even if they have a lifetime.
+    bool is_volatile = pointee_type.isLocalVolatileQualified();
+    if (is_volatile) {
+      if (type->isRValueReferenceType()) {
+        return absl::UnimplementedError(
+            "Rvalue references to `volatile` types are not supported");
+      }
+      pointee_type.removeLocalVolatile();
+      lifetime = std::nullopt;
+    }
     CRUBIT_ASSIGN_OR_RETURN(
         MappedType mapped_pointee_type,
         ConvertQualType(pointee_type, pointee_lifetimes, ref_qualifier_kind));
+    mapped_pointee_type.cc_type.is_volatile = is_volatile;
     if (type->isPointerType()) {
       return MappedType::PointerTo(std::move(mapped_pointee_type), lifetime,
                                    ref_qualifier_kind, nullable);
@@ -862,6 +1591,59 @@ absl::StatusOr<MappedType> Importer::ConvertType(
       return MappedType::RValueReferenceTo(std::move(mapped_pointee_type),
                                            *lifetime);
     }
+  } else if (const auto* array_type =
+                 type->getAsAdjusted<clang::ConstantArrayType>()) {
+    // Function parameters of array type have already been adjusted to
+    // pointers, so arrays are mostly seen as the types of fields.
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_element_type,
+        ConvertQualType(array_type->getElementType(), /*lifetimes=*/nullptr,
+                        ref_qualifier_kind));
+    return MappedType::ArrayOf(std::move(mapped_element_type),
+                               array_type->getSize().getZExtValue());
+  } else if (const auto* member_pointer_type =
+                 type->getAsAdjusted<clang::MemberPointerType>()) {
+    // Pointers to members are bound as the opaque types from the
+    // `member_pointer` support crate, which have the layout prescribed by the
+    // Itanium C++ ABI.
+    if (!ctx_.getTargetInfo().getCXXABI().isItaniumFamily()) {
+      return absl::UnimplementedError(
+          "Pointers to members are only supported for the Itanium C++ ABI");
+    }
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_class_type,
+        ConvertQualType(clang::QualType(member_pointer_type->getClass(), 0),
+                        /*lifetimes=*/nullptr, std::nullopt));
+    clang::PrintingPolicy policy(ctx_.getLangOpts());
+    policy.PrintCanonicalTypes = true;
+    std::string cc_name = absl::StrCat(
+        "crubit::type_identity_t<",
+        clang::QualType(type, 0).getCanonicalType().getAsString(policy), ">");
+    if (member_pointer_type->isMemberFunctionPointer()) {
+      MappedType mapped_type = MappedType::Simple(
+          "::member_pointer::MemberFunctionPointer", std::move(cc_name));
+      mapped_type.rs_type.type_args.push_back(
+          std::move(mapped_class_type.rs_type));
+      // Pointers to member functions that can't be called from Rust are bound
+      // with the `()` signature.
+      absl::StatusOr<MappedType> mapped_signature =
+          ConvertMemberFunctionSignature(member_pointer_type);
+      mapped_type.rs_type.type_args.push_back(
+          mapped_signature.ok() ? std::move(mapped_signature->rs_type)
+                                : MappedType::Void().rs_type);
+      return mapped_type;
+    }
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_member_type,
+        ConvertQualType(member_pointer_type->getPointeeType(),
+                        /*lifetimes=*/nullptr, std::nullopt));
+    MappedType mapped_type = MappedType::Simple(
+        "::member_pointer::DataMemberPointer", std::move(cc_name));
+    mapped_type.rs_type.type_args.push_back(
+        std::move(mapped_class_type.rs_type));
+    mapped_type.rs_type.type_args.push_back(
+        std::move(mapped_member_type.rs_type));
+    return mapped_type;
   } else if (const auto* builtin_type =
                  // Use getAsAdjusted instead of getAs so we don't desugar
                  // typedefs.
@@ -880,19 +1662,40 @@ absl::StatusOr<MappedType> Importer::ConvertType(
         return MappedType::Simple("f32", "float");
       case clang::BuiltinType::Double:
         return MappedType::Simple("f64", "double");
+      case clang::BuiltinType::LongDouble:
+        return ConvertLongDouble(invocation_.long_double_policy_, ctx_);
+
+      // Half-precision floating-point numbers are bound as the types from the
+      // `half_float` support crate (until `f16` is stable in Rust).
+      case clang::BuiltinType::Float16:
+        return MappedType::Simple("::half_float::F16", "_Float16");
+      case clang::BuiltinType::Half:
+        return MappedType::Simple("::half_float::F16", "__fp16");
+      case clang::BuiltinType::BFloat16:
+        return MappedType::Simple("::half_float::Bf16", "__bf16");
 
       // `char`
       case clang::BuiltinType::Char_S:  // 'char' in targets where it's signed
-        // TODO(b/276790180, b/276931370): use `::core::ffi::c_char` instead.
-        return MappedType::Simple("i8", "char");
       case clang::BuiltinType::Char_U:  // 'char' in targets where it's unsigned
-        // TODO(b/276790180, b/276931370): use `::core::ffi::c_char` instead.
-        return MappedType::Simple("u8", "char");
+        return ConvertChar(
+            invocation_.char_mapping_,
+            builtin_type->getKind() == clang::BuiltinType::Char_S);
       case clang::BuiltinType::SChar:  // 'signed char', explicitly qualified
         return MappedType::Simple("::core::ffi::c_schar", "signed char");
       case clang::BuiltinType::UChar:  // 'unsigned char', explicitly qualified
         return MappedType::Simple("::core::ffi::c_uchar", "unsigned char");
 
+      // `wchar_t` is bound as the integer type with the same size and
+      // signedness on the target (e.g. `u16` on Windows, `i32` on x86-64
+      // Linux), which is also the `c_wchar` alias of the `wchar` support crate.
+      case clang::BuiltinType::WChar_S:
+      case clang::BuiltinType::WChar_U: {
+        bool is_signed = builtin_type->getKind() == clang::BuiltinType::WChar_S;
+        return MappedType::Simple(
+            absl::StrCat(is_signed ? "i" : "u", ctx_.getTypeSize(builtin_type)),
+            "wchar_t");
+      }
+
       // Signed integers
       case clang::BuiltinType::Short:
         return MappedType::Simple("::core::ffi::c_short", "short");
@@ -917,6 +1720,11 @@ absl::StatusOr<MappedType> Importer::ConvertType(
         return absl::UnimplementedError("Unsupported builtin type");
     }
   } else if (const auto* tag_type = type->getAsAdjusted<clang::TagType>()) {
+    // The types of the CUDA builtin variables (`threadIdx` etc.) only have
+    // device-side members.
+    if (tag_type->getDecl()->getName().startswith("__cuda_builtin_")) {
+      return absl::UnimplementedError("CUDA builtin types are not supported");
+    }
     return ConvertTypeDecl(tag_type->getDecl());
   } else if (const auto* typedef_type =
                  type->getAsAdjusted<clang::TypedefType>()) {
@@ -936,12 +1744,69 @@ absl::StatusOr<MappedType> Importer::ConvertType(
     CHECK(deduced_type->isDeduced());
     return ConvertQualType(deduced_type->getDeducedType(), lifetimes,
                            ref_qualifier_kind);
+  } else if (type->isObjCObjectPointerType() || type->isObjCObjectType()) {
+    return absl::UnimplementedError("Objective-C types are not supported");
+  } else if (type->isBlockPointerType()) {
+    return absl::UnimplementedError("Block pointers are not supported");
   }
 
   return absl::UnimplementedError(absl::StrCat(
       "Unsupported clang::Type class '", type->getTypeClassName(), "'"));
 }
 
+absl::StatusOr<MappedType> Importer::ConvertMemberFunctionSignature(
+    const clang::MemberPointerType* type) {
+  const auto* func_type =
+      type->getPointeeType()->getAs<clang::FunctionProtoType>();
+  CHECK(func_type != nullptr);
+  // The call thunks pass the object as an lvalue, and the arguments as the
+  // lvalues that are the parameters of the thunk.
+  if (func_type->isVariadic() ||
+      func_type->getRefQualifier() == clang::RQ_RValue ||
+      func_type->getMethodQuals().hasVolatile()) {
+    return absl::UnimplementedError(
+        "Calling pointers to variadic, `&&`-qualified or `volatile` member "
+        "functions is not supported");
+  }
+  clang::QualType object_type(type->getClass(), 0);
+  if (func_type->getMethodQuals().hasConst()) {
+    object_type.addConst();
+  }
+  // The canonical types are converted, so that pointers to member functions
+  // of the same type get the same signature, regardless of how their
+  // parameter types are spelled (e.g. `int` or a typedef of `int`).
+  std::vector<MappedType> mapped_param_types;
+  CRUBIT_ASSIGN_OR_RETURN(
+      MappedType mapped_object_type,
+      ConvertQualType(ctx_.getPointerType(object_type), /*lifetimes=*/nullptr,
+                      std::nullopt));
+  mapped_param_types.push_back(std::move(mapped_object_type));
+  for (clang::QualType param_type : func_type->getParamTypes()) {
+    if (param_type->isRValueReferenceType()) {
+      return absl::UnimplementedError(
+          "Calling pointers to member functions with rvalue reference "
+          "parameters is not supported");
+    }
+    CRUBIT_ASSIGN_OR_RETURN(
+        MappedType mapped_param_type,
+        ConvertQualType(param_type.getCanonicalType(), /*lifetimes=*/nullptr,
+                        std::nullopt));
+    mapped_param_types.push_back(std::move(mapped_param_type));
+  }
+  CRUBIT_ASSIGN_OR_RETURN(
+      MappedType mapped_return_type,
+      ConvertQualType(func_type->getReturnType().getCanonicalType(),
+                      /*lifetimes=*/nullptr, std::nullopt));
+  clang::StringRef cc_call_conv =
+      clang::FunctionType::getNameForCallConv(func_type->getCallConv());
+  CRUBIT_ASSIGN_OR_RETURN(
+      absl::string_view rs_abi,
+      ConvertCcCallConvIntoRsAbi(func_type->getCallConv()));
+  return MappedType::FuncRef(cc_call_conv, rs_abi, /*lifetime=*/std::nullopt,
+                             std::move(mapped_return_type),
+                             std::move(mapped_param_types));
+}
+
 // Returns a QualType with leading ElaboratedType nodes removed.
 //
 // This is analogous to getDesugaredType but *only* removes ElaboratedType
@@ -1086,21 +1951,14 @@ absl::StatusOr<UnqualifiedIdentifier> Importer::GetTranslatedName(
         return absl::InvalidArgumentError("Missing identifier");
       }
 
-      // `r#foo` syntax in Rust can't be used to escape `crate`, `self`,
-      // `super`, not `Self` identifiers - see
-      // https://doc.rust-lang.org/reference/identifiers.html#identifiers
-      if (name == "crate" || name == "self" || name == "super" ||
-          name == "Self") {
-        return absl::InvalidArgumentError(
-            absl::StrCat("Unescapable identifier: ", name));
-      }
-
       return {Identifier(std::move(name))};
     }
     case clang::DeclarationName::CXXConstructorName:
       return {SpecialName::kConstructor};
     case clang::DeclarationName::CXXDestructorName:
       return {SpecialName::kDestructor};
+    case clang::DeclarationName::CXXConversionFunctionName:
+      return {SpecialName::kConversion};
     case clang::DeclarationName::CXXOperatorName:
       switch (named_decl->getDeclName().getCXXOverloadedOperator()) {
         case clang::OO_None:
@@ -1118,7 +1976,6 @@ absl::StatusOr<UnqualifiedIdentifier> Importer::GetTranslatedName(
       }
       LOG(FATAL) << "The `switch` above should handle all cases";
     default:
-      // To be implemented later: CXXConversionFunctionName.
       // There are also e.g. literal operators, deduction guides, etc., but
       // we might not need to implement them at all. Full list at:
       // https://clang.llvm.org/doxygen/classclang_1_1DeclarationName.html#a9ab322d434446b43379d39e41af5cbe3
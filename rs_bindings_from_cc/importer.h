@@ -21,12 +21,16 @@
 #include "rs_bindings_from_cc/importers/friend.h"
 #include "rs_bindings_from_cc/importers/function.h"
 #include "rs_bindings_from_cc/importers/function_template.h"
+#include "rs_bindings_from_cc/importers/macro.h"
 #include "rs_bindings_from_cc/importers/namespace.h"
+#include "rs_bindings_from_cc/importers/objc.h"
 #include "rs_bindings_from_cc/importers/type_alias.h"
 #include "rs_bindings_from_cc/importers/type_map_override.h"
+#include "rs_bindings_from_cc/importers/var.h"
 #include "rs_bindings_from_cc/ir.h"
 #include "clang/AST/Mangle.h"
 #include "clang/AST/RawCommentList.h"
+#include "clang/Lex/MacroInfo.h"
 
 namespace crubit {
 
@@ -49,7 +53,10 @@ class Importer final : public ImportContext {
     decl_importers_.push_back(
         std::make_unique<FunctionTemplateDeclImporter>(*this));
     decl_importers_.push_back(std::make_unique<NamespaceDeclImporter>(*this));
+    decl_importers_.push_back(
+        std::make_unique<ObjCContainerDeclImporter>(*this));
     decl_importers_.push_back(std::make_unique<TypeAliasImporter>(*this));
+    decl_importers_.push_back(std::make_unique<VarDeclImporter>(*this));
   }
 
   // Import all visible declarations from a translation unit.
@@ -107,6 +114,9 @@ class Importer final : public ImportContext {
   // Returns a SourceOrderKey for the given `comment` that should be used for
   // ordering Items.
   SourceOrderKey GetSourceOrderKey(const clang::RawComment* comment) const;
+  // Returns a SourceOrderKey for the given `macro_info` that should be used for
+  // ordering Items.
+  SourceOrderKey GetSourceOrderKey(const clang::MacroInfo* macro_info) const;
 
   // Returns a name for `decl` that should be used for ordering declarations.
   std::string GetNameForSourceOrder(const clang::Decl* decl) const;
@@ -119,6 +129,22 @@ class Importer final : public ImportContext {
   std::optional<IR::Item> GetDeclItem(clang::Decl* decl) override;
   // Stores the comments of this target in source order.
   void ImportFreeComments();
+  // Imports the macros of this target that are constants or that behave like
+  // functions (see `MacroImporter`).
+  void ImportMacros();
+  // Imports the explicit class template instantiation definitions (e.g.
+  // `template class MyTemplate<int>;`) that are spelled in the headers of the
+  // current target, looking into nested namespaces and `extern "C"` blocks.
+  void ImportExplicitClassTemplateInstantiations(
+      const clang::DeclContext* decl_context);
+  // Imports the function template specializations requested through
+  // `IrFromCcOptions::extra_function_template_instantiations`, whose address is
+  // taken in a namespace of the main (virtual input) file.
+  void ImportFunctionTemplateInstantiations(
+      clang::TranslationUnitDecl* translation_unit_decl);
+  // Returns the target owning the header that contains `source_location`.
+  BazelLabel GetOwningTargetOfLocation(
+      clang::SourceLocation source_location) const;
 
   // Converts a type to a MappedType.
   //
@@ -132,6 +158,13 @@ class Importer final : public ImportContext {
       std::optional<clang::RefQualifierKind> ref_qualifier_kind, bool nullable);
   absl::StatusOr<MappedType> ConvertTypeDecl(clang::NamedDecl* decl);
 
+  // Converts the signature of the member functions that `type` points to into
+  // a function pointer type that takes the object pointer (`this`) as its
+  // first parameter. Returns an error if the member functions can't be called
+  // through such a function pointer.
+  absl::StatusOr<MappedType> ConvertMemberFunctionSignature(
+      const clang::MemberPointerType* type);
+
   // Converts `type` into a MappedType, after first importing the Record behind
   // the template instantiation.
   absl::StatusOr<MappedType> ConvertTemplateSpecializationType(
@@ -145,7 +178,10 @@ class Importer final : public ImportContext {
       import_cache_;
   absl::flat_hash_set<const clang::ClassTemplateSpecializationDecl*>
       class_template_instantiations_;
+  absl::flat_hash_set<const clang::FunctionDecl*>
+      function_template_instantiations_;
   std::vector<const clang::RawComment*> comments_;
+  std::vector<std::pair<const clang::MacroInfo*, IR::Item>> macro_items_;
 
   // Set of decls that have been successfully imported (i.e. that will be
   // present in the IR output / that will not produce dangling ItemIds in the IR
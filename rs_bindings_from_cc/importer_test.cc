@@ -4,6 +4,7 @@
 
 #include <optional>
 #include <string>
+#include <utility>
 #include <variant>
 #include <vector>
 
@@ -24,8 +25,11 @@ using ::testing::AnyOf;
 using ::testing::Contains;
 using ::testing::Each;
 using ::testing::ElementsAre;
+using ::testing::Field;
+using ::testing::HasSubstr;
 using ::testing::IsEmpty;
 using ::testing::Not;
+using ::testing::Optional;
 using ::testing::Pointee;
 using ::testing::SizeIs;
 using ::testing::UnorderedElementsAre;
@@ -403,6 +407,17 @@ TEST(ImporterTest, TestImportReferenceFunc) {
                   ReturnType(IsIntRef()), ParamsAre(ParamType(IsIntRef()))))));
 }
 
+TEST(ImporterTest, TestImportRvalueReferenceReturnType) {
+  ASSERT_OK_AND_ASSIGN(IR ir, IrFromCc({"int&& Foo();"}));
+
+  EXPECT_THAT(ItemsWithoutBuiltins(ir),
+              UnorderedElementsAre(VariantWith<Func>(AllOf(
+                  IdentifierIs("Foo"),
+                  ReturnType(AllOf(CcTypeIs(AllOf(NameIs("&&"),
+                                                  CcTypeParamsAre(IsCcInt()))),
+                                   RsTypeIs(RsPointsTo(IsRsInt()))))))));
+}
+
 TEST(ImporterTest, TrivialCopyConstructor) {
   absl::string_view file = R"cc(
     struct Implicit {};
@@ -859,6 +874,321 @@ TEST(ImporterTest, RecordItemIds) {
                     Contains(VariantWith<Func>(IdentifierIs("baz")))));
 }
 
+TEST(ImporterTest, ExternRustTypes) {
+  absl::string_view file = R"cc(
+    namespace ns {
+    struct Bound final {
+      int x;
+    };
+    }  // namespace ns
+    struct NotBound final {
+      int x;
+    };
+  )cc";
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .extern_rust_types = {{"ns::Bound", "::legacy::Bound"}}}));
+  EXPECT_THAT(ItemsWithoutBuiltins(ir),
+              AllOf(Contains(VariantWith<TypeMapOverride>(
+                        RsNameIs("::legacy::Bound"))),
+                    Contains(VariantWith<Record>(RsNameIs("NotBound"))),
+                    Not(Contains(VariantWith<Record>(RsNameIs("Bound"))))));
+}
+
+TEST(ImporterTest, ObjectiveCDeclsAreUnsupported) {
+  absl::string_view file = R"cc(
+    struct Portable final {
+      int x;
+    };
+    void PortableFunc(Portable p);
+#ifdef __OBJC__
+    @interface ObjCClass
+    @end
+    void TakesObjCClass(ObjCClass* c);
+#endif
+  )cc";
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .clang_args = {"-x", "objective-c++"}}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(Contains(VariantWith<Record>(RsNameIs("Portable"))),
+            Contains(VariantWith<Func>(IdentifierIs("PortableFunc"))),
+            Contains(VariantWith<UnsupportedItem>(AllOf(
+                NameIs("ObjCClass"),
+                Field(&UnsupportedItem::message,
+                      HasSubstr("Objective-C declarations are not "
+                                "supported"))))),
+            Contains(VariantWith<UnsupportedItem>(
+                AllOf(NameIs("TakesObjCClass"),
+                      Field(&UnsupportedItem::message,
+                            HasSubstr("Objective-C types are not "
+                                      "supported")))))));
+}
+
+TEST(ImporterTest, CudaDeviceFunctionsAreUnsupported) {
+  absl::string_view file = R"cc(
+#define __host__ __attribute__((host))
+#define __device__ __attribute__((device))
+#define __global__ __attribute__((global))
+    void HostFunc();
+    __host__ void ExplicitHostFunc();
+    __host__ __device__ void HostDeviceFunc();
+    __device__ void DeviceFunc();
+    __global__ void Kernel();
+  )cc";
+  std::vector<absl::string_view> cuda_args = {
+      "-x", "cuda", "--cuda-host-only", "-nocudainc", "-nocudalib"};
+  ASSERT_OK_AND_ASSIGN(IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                                        .clang_args = cuda_args}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(Contains(VariantWith<Func>(IdentifierIs("HostFunc"))),
+            Contains(VariantWith<Func>(IdentifierIs("ExplicitHostFunc"))),
+            Contains(VariantWith<Func>(IdentifierIs("HostDeviceFunc"))),
+            Contains(VariantWith<UnsupportedItem>(AllOf(
+                NameIs("DeviceFunc"),
+                Field(&UnsupportedItem::message,
+                      HasSubstr("CUDA `__device__` functions are not "
+                                "supported"))))),
+            Contains(VariantWith<UnsupportedItem>(AllOf(
+                NameIs("Kernel"),
+                Field(&UnsupportedItem::message,
+                      HasSubstr("CUDA kernels (`__global__` functions) are "
+                                "not supported")))))));
+
+  ASSERT_OK_AND_ASSIGN(ir, IrFromCc({.extra_source_code_for_testing = file,
+                                     .clang_args = cuda_args,
+                                     .cuda_host_only = true}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(Contains(VariantWith<Func>(IdentifierIs("HostFunc"))),
+            Contains(VariantWith<Func>(IdentifierIs("ExplicitHostFunc"))),
+            Contains(VariantWith<UnsupportedItem>(
+                NameIs("HostDeviceFunc")))));
+}
+
+TEST(ImporterTest, ErrnoFunctions) {
+  absl::string_view file = R"cc(
+    namespace posix {
+    int close(int fd);
+    void* mmap(void* addr, unsigned long length);
+    void sync();
+    int getpid();
+    }  // namespace posix
+  )cc";
+  std::vector<std::string> errno_functions = {"posix::close", "posix::mmap",
+                                              "posix::sync"};
+  ASSERT_OK_AND_ASSIGN(IR ir,
+                       IrFromCc({.extra_source_code_for_testing = file,
+                                 .errno_functions = errno_functions}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(Contains(VariantWith<Func>(AllOf(
+                IdentifierIs("close"), Field(&Func::sets_errno, true)))),
+            Contains(VariantWith<Func>(
+                AllOf(IdentifierIs("mmap"), Field(&Func::sets_errno, true)))),
+            Contains(VariantWith<Func>(AllOf(
+                IdentifierIs("getpid"), Field(&Func::sets_errno, false)))),
+            Contains(VariantWith<UnsupportedItem>(AllOf(
+                NameIs("posix::sync"),
+                Field(&UnsupportedItem::message,
+                      HasSubstr("`--errno_functions` only supports functions "
+                                "that return a signed integer or a "
+                                "pointer")))))));
+}
+
+TEST(ImporterTest, LongDoubleIsSkippedByDefault) {
+  absl::string_view file = R"cc(
+    double Scale(double x);
+    long double Scale(long double x);
+  )cc";
+  ASSERT_OK_AND_ASSIGN(IR ir,
+                       IrFromCc({.extra_source_code_for_testing = file}));
+  // Only the overload that uses `long double` is skipped.
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(Contains(VariantWith<Func>(
+                AllOf(IdentifierIs("Scale"),
+                      ReturnType(RsTypeIs(NameIs("f64")))))),
+            Contains(VariantWith<UnsupportedItem>(AllOf(
+                NameIs("Scale"),
+                Field(&UnsupportedItem::message,
+                      HasSubstr("`long double` is not supported")))))));
+}
+
+TEST(ImporterTest, LongDoubleAsOpaqueType) {
+  absl::string_view file = R"cc(
+    long double Scale(long double x);
+  )cc";
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .long_double_policy = LongDoublePolicy::kOpaque}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      Contains(VariantWith<Func>(AllOf(
+          IdentifierIs("Scale"),
+          ReturnType(AllOf(RsTypeIs(NameIs("::long_double::LongDouble")),
+                           CcTypeIs(NameIs("long double")))),
+          Field(&Func::converts_long_double, false)))));
+}
+
+TEST(ImporterTest, LongDoubleConvertedToF64) {
+  absl::string_view file = R"cc(
+    long double Scale(long double x, int factor);
+    void Store(long double* out);
+  )cc";
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .long_double_policy = LongDoublePolicy::kF64}));
+  EXPECT_THAT(
+      ItemsWithoutBuiltins(ir),
+      AllOf(
+          Contains(VariantWith<Func>(AllOf(
+              IdentifierIs("Scale"),
+              ReturnType(AllOf(RsTypeIs(NameIs("f64")),
+                               CcTypeIs(NameIs("double")))),
+              ParamsAre(ParamType(RsTypeIs(NameIs("f64")),
+                                  CcTypeIs(NameIs("double"))),
+                        ParamType(RsTypeIs(IsRsInt()), CcTypeIs(IsCcInt()))),
+              Field(&Func::converts_long_double, true)))),
+          Contains(VariantWith<UnsupportedItem>(AllOf(
+              NameIs("Store"),
+              Field(&UnsupportedItem::message,
+                    HasSubstr("`long double` is only supported as the type "
+                              "of a parameter or of a return value")))))));
+}
+
+TEST(ImporterTest, CharMapping) {
+  absl::string_view file = R"cc(
+    char Get(const char* s, signed char i);
+  )cc";
+  for (auto [char_mapping, rs_name] :
+       std::vector<std::pair<CharMapping, std::string>>{
+           {CharMapping::kU8, "u8"},
+           {CharMapping::kI8, "i8"},
+           {CharMapping::kCChar, "::core::ffi::c_char"}}) {
+    ASSERT_OK_AND_ASSIGN(IR ir,
+                         IrFromCc({.extra_source_code_for_testing = file,
+                                   .char_mapping = char_mapping}));
+    // `signed char` and `unsigned char` are not affected.
+    EXPECT_THAT(
+        ItemsWithoutBuiltins(ir),
+        Contains(VariantWith<Func>(AllOf(
+            IdentifierIs("Get"),
+            ReturnType(AllOf(RsTypeIs(NameIs(rs_name)),
+                             CcTypeIs(NameIs("char")))),
+            ParamsAre(
+                ParamType(RsTypeIs(RsConstPointsTo(NameIs(rs_name)))),
+                ParamType(RsTypeIs(NameIs("::core::ffi::c_schar"))))))));
+  }
+}
+
+TEST(ImporterTest, WCharHasTheSizeOfTheTarget) {
+  absl::string_view file = R"cc(
+    wchar_t ToUpper(wchar_t c);
+  )cc";
+  for (auto [target, rs_name] :
+       std::vector<std::pair<absl::string_view, std::string>>{
+           {"x86_64-unknown-linux-gnu", "i32"},
+           {"aarch64-unknown-linux-gnu", "u32"},
+           {"x86_64-pc-windows-msvc", "u16"}}) {
+    std::vector<absl::string_view> target_args = {"-target", target};
+    ASSERT_OK_AND_ASSIGN(IR ir,
+                         IrFromCc({.extra_source_code_for_testing = file,
+                                   .clang_args = target_args}));
+    EXPECT_THAT(ItemsWithoutBuiltins(ir),
+                Contains(VariantWith<Func>(AllOf(
+                    IdentifierIs("ToUpper"),
+                    ReturnType(AllOf(RsTypeIs(NameIs(rs_name)),
+                                     CcTypeIs(NameIs("wchar_t")))),
+                    ParamsAre(ParamType(RsTypeIs(NameIs(rs_name))))))))
+        << "target: " << target;
+  }
+}
+
+TEST(ImporterTest, LifetimeElisionFlag) {
+  absl::string_view file = R"cc(
+    int& Identity(int& x);
+    [[clang::annotate("crubit_no_lifetime_elision")]] int& Register(int& x);
+  )cc";
+  for (bool lifetime_elision : {false, true}) {
+    ASSERT_OK_AND_ASSIGN(IR ir,
+                         IrFromCc({.extra_source_code_for_testing = file,
+                                   .lifetime_elision = lifetime_elision}));
+    absl::string_view identity_type = lifetime_elision ? "&mut" : "*mut";
+    EXPECT_THAT(ItemsWithoutBuiltins(ir),
+                AllOf(Contains(VariantWith<Func>(AllOf(
+                          IdentifierIs("Identity"),
+                          ReturnType(RsTypeIs(NameIs(identity_type)))))),
+                      Contains(VariantWith<Func>(
+                          AllOf(IdentifierIs("Register"),
+                                ReturnType(RsTypeIs(NameIs("*mut"))))))))
+        << "lifetime_elision: " << lifetime_elision;
+  }
+}
+
+TEST(ImporterTest, FunctionTemplateInstantiations) {
+  absl::string_view file = R"cc(
+    namespace ns {
+    template <typename T>
+    T Add(T a, T b) {
+      return a + b;
+    }
+    }  // namespace ns
+  )cc";
+  std::vector<std::string> instantiations = {"ns::Add<int>", "ns::Add<float>"};
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .extra_function_template_instantiations =
+                           instantiations}));
+  std::vector<const Func*> funcs = ir.get_items_if<Func>();
+  EXPECT_THAT(
+      funcs,
+      UnorderedElementsAre(
+          Pointee(AllOf(IdentifierIs("Add"),
+                        Field(&Func::template_args,
+                              Optional(std::string("<int>"))))),
+          Pointee(AllOf(IdentifierIs("Add"),
+                        Field(&Func::template_args,
+                              Optional(std::string("<float>")))))));
+  for (const Func* func : funcs) {
+    EXPECT_EQ(func->owning_target, BazelLabel("//test:testing_target"));
+    EXPECT_THAT(ir.top_level_item_ids, Contains(func->id));
+  }
+}
+
+TEST(ImporterTest, VariadicFunctionTemplateInstantiations) {
+  absl::string_view file = R"cc(
+    namespace ns {
+    template <typename... Args>
+    int Count(int first, const Args&... args) {
+      return 1 + sizeof...(args);
+    }
+    }  // namespace ns
+  )cc";
+  std::vector<std::string> instantiations = {"ns::Count<>",
+                                             "ns::Count<int, float>"};
+  ASSERT_OK_AND_ASSIGN(
+      IR ir, IrFromCc({.extra_source_code_for_testing = file,
+                       .extra_function_template_instantiations =
+                           instantiations}));
+  EXPECT_THAT(
+      ir.get_items_if<Func>(),
+      UnorderedElementsAre(
+          Pointee(AllOf(
+              IdentifierIs("Count"),
+              Field(&Func::template_args, Optional(std::string("<>"))),
+              ParamsAre(IdentifierIs("first")))),
+          Pointee(AllOf(
+              IdentifierIs("Count"),
+              Field(&Func::template_args,
+                    Optional(std::string("<int, float>"))),
+              // The parameters expanded from the pack get distinct names.
+              ParamsAre(IdentifierIs("first"), IdentifierIs("__args_1"),
+                        IdentifierIs("__args_2"))))));
+}
+
 TEST(ImporterTest, CrashRepro_FunctionTypeAlias) {
   absl::string_view file = R"cc(
     using Callback = void(const int&);
@@ -18,16 +18,21 @@
 #include "absl/log/die_if_null.h"
 #include "absl/log/log.h"
 #include "absl/status/status.h"
+#include "absl/strings/ascii.h"
 #include "absl/strings/str_cat.h"
 #include "absl/strings/string_view.h"
 #include "lifetime_annotations/type_lifetimes.h"
 #include "rs_bindings_from_cc/ast_convert.h"
+#include "rs_bindings_from_cc/ast_util.h"
 #include "rs_bindings_from_cc/bazel_types.h"
 #include "rs_bindings_from_cc/ir.h"
 #include "clang/AST/ASTContext.h"
 #include "clang/AST/CXXInheritance.h"
 #include "clang/AST/Decl.h"
 #include "clang/AST/DeclCXX.h"
+#include "clang/AST/DeclFriend.h"
+#include "clang/AST/DeclTemplate.h"
+#include "clang/AST/DeclarationName.h"
 #include "clang/AST/PrettyPrinter.h"
 #include "clang/AST/RecordLayout.h"
 #include "clang/AST/Type.h"
@@ -118,6 +123,115 @@ absl::StatusOr<RecordType> TranslateRecordType(
   llvm::report_fatal_error("Unrecognized clang::TagKind");
 }
 
+// Returns whether `record_decl` opts into (`true`) or out of (`false`) the
+// `Send` or `Sync` auto trait, or `std::nullopt` if it is not annotated.
+//
+// `trait` is the lowercase trait name, e.g. `send`.
+absl::StatusOr<std::optional<bool>> GetAutoTraitOverride(
+    const clang::CXXRecordDecl& record_decl, absl::string_view trait) {
+  bool opt_in = HasAnnotation(&record_decl, absl::StrCat("crubit_", trait));
+  bool opt_out =
+      HasAnnotation(&record_decl, absl::StrCat("crubit_not_", trait));
+  if (opt_in && opt_out) {
+    std::string macro = absl::AsciiStrToUpper(trait);
+    return absl::InvalidArgumentError(
+        absl::StrCat("Records can't be annotated with both `CRUBIT_", macro,
+                     "` and `CRUBIT_NOT_", macro, "`"));
+  }
+  if (!opt_in && !opt_out) return std::nullopt;
+  return opt_in;
+}
+
+// Returns whether `type` is `std::ostream&`.
+bool IsOstreamReference(clang::QualType type) {
+  if (!type->isLValueReferenceType()) return false;
+  clang::QualType pointee = type->getPointeeType();
+  if (pointee.isConstQualified()) return false;
+  const auto* specialization =
+      llvm::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          pointee->getAsCXXRecordDecl());
+  if (specialization == nullptr || !specialization->isInStdNamespace() ||
+      specialization->getName() != "basic_ostream") {
+    return false;
+  }
+  const clang::TemplateArgumentList& args = specialization->getTemplateArgs();
+  return args.size() > 0 &&
+         args[0].getKind() == clang::TemplateArgument::Type &&
+         args[0].getAsType()->isCharType();
+}
+
+// Returns whether `decl` is `operator<<(std::ostream&, const T&)` (or
+// `operator<<(std::ostream&, T)`), where `T` is `record_type`.
+bool IsStreamInsertionOperator(clang::ASTContext& ctx,
+                               clang::QualType record_type,
+                               const clang::NamedDecl* decl) {
+  const auto* func = llvm::dyn_cast_or_null<clang::FunctionDecl>(decl);
+  if (func == nullptr || func->getNumParams() != 2 ||
+      !IsOstreamReference(func->getParamDecl(0)->getType())) {
+    return false;
+  }
+  clang::QualType value_type = func->getParamDecl(1)->getType();
+  if (value_type->isLValueReferenceType()) {
+    value_type = value_type->getPointeeType();
+    if (!value_type.isConstQualified()) return false;
+  } else if (value_type->isReferenceType()) {
+    return false;
+  }
+  return ctx.hasSameUnqualifiedType(value_type, record_type);
+}
+
+// Returns whether `operator<<(std::ostream&, const T&)` is declared for the
+// record `T`, either in its enclosing namespace or as a (hidden) friend.
+bool HasStreamInsertionOperator(clang::ASTContext& ctx,
+                                const clang::CXXRecordDecl& record_decl) {
+  clang::QualType record_type = ctx.getRecordType(&record_decl);
+  clang::DeclarationName name =
+      ctx.DeclarationNames.getCXXOperatorName(clang::OO_LessLess);
+  for (const clang::NamedDecl* decl :
+       record_decl.getDeclContext()->getRedeclContext()->lookup(name)) {
+    if (IsStreamInsertionOperator(ctx, record_type, decl)) return true;
+  }
+  for (const clang::FriendDecl* friend_decl : record_decl.friends()) {
+    if (IsStreamInsertionOperator(ctx, record_type,
+                                  friend_decl->getFriendDecl())) {
+      return true;
+    }
+  }
+  return false;
+}
+
+// Returns whether `record_decl` has a pure virtual member function that is not
+// public, either declared in it or inherited and not overridden.
+bool HasNonPublicPureVirtual(const clang::CXXRecordDecl& record_decl) {
+  if (!record_decl.isAbstract()) return false;
+  clang::CXXFinalOverriderMap final_overriders;
+  record_decl.getFinalOverriders(final_overriders);
+  for (const auto& [method, overriding_methods] : final_overriders) {
+    for (const auto& [subobject, overriders] : overriding_methods) {
+      for (const clang::UniqueVirtualMethod& overrider : overriders) {
+        if (overrider.Method->isPureVirtual() &&
+            overrider.Method->getAccess() != clang::AS_public) {
+          return true;
+        }
+      }
+    }
+  }
+  return false;
+}
+
+// Returns the name of an anonymous record nested in another record, which is
+// synthesized from the field of its type (see `GetFieldOfAnonNestedRecord`):
+// the type of `s` in `struct Outer { struct { int x; } s; };` is named `_s`,
+// and the type of an anonymous struct or union member at field index 1 is
+// named `_unnamed_field1`. Together with the name of the enclosing record,
+// this gives `Outer__s` and `Outer__unnamed_field1`.
+std::string GetAnonNestedRecordName(const clang::FieldDecl& field_decl) {
+  if (field_decl.getName().empty()) {
+    return absl::StrCat("_unnamed_field", field_decl.getFieldIndex());
+  }
+  return absl::StrCat("_", field_decl.getName().str());
+}
+
 }  // namespace
 
 std::optional<Identifier> CXXRecordDeclImporter::GetTranslatedFieldName(
@@ -162,10 +276,6 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
   if (record_decl->isImplicit()) {
     return std::nullopt;
   }
-  if (decl_context->isRecord()) {
-    return ictx_.ImportUnsupportedItem(record_decl,
-                                       "Nested classes are not supported yet");
-  }
   if (clang::isa<clang::ClassTemplatePartialSpecializationDecl>(record_decl)) {
     return ictx_.ImportUnsupportedItem(
         record_decl, "Partially-specialized class templates are not supported");
@@ -182,6 +292,20 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
   if (record_decl->isInvalidDecl()) {
     return std::nullopt;
   }
+  if (auto* enclosing_record = clang::dyn_cast<clang::CXXRecordDecl>(
+          record_decl->getDeclContext())) {
+    if (clang::isa<clang::ClassTemplateSpecializationDecl>(enclosing_record)) {
+      return ictx_.ImportUnsupportedItem(
+          record_decl,
+          "Nested classes in class template specializations are not supported "
+          "yet");
+    }
+    if (!ictx_.EnsureSuccessfullyImported(enclosing_record)) {
+      return ictx_.ImportUnsupportedItem(
+          record_decl,
+          "Nested classes of unsupported classes are not supported");
+    }
+  }
   if (record_decl->isInStdNamespace() &&
       record_decl->hasAttr<clang::VisibilityAttr>()) {
     auto visibility = record_decl->getAttr<clang::VisibilityAttr>();
@@ -253,23 +377,54 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
     if (record_decl->getName().empty()) {
       if (auto* typedef_decl = record_decl->getTypedefNameForAnonDecl()) {
         named_decl = typedef_decl;
+      } else if (const clang::FieldDecl* field_decl =
+                     GetFieldOfAnonNestedRecord(record_decl)) {
+        rs_name = cc_name = GetAnonNestedRecordName(*field_decl);
+        named_decl = nullptr;
       } else {
-        // Skip anonymous structs that don't get a name via typedecl.
+        // Skip anonymous structs that don't get a name via typedecl and
+        // aren't nested in another record.
         return std::nullopt;
       }
     }
-    CHECK(!named_decl->getName().empty());
 
-    absl::StatusOr<Identifier> record_name =
-        ictx_.GetTranslatedIdentifier(named_decl);
-    if (record_name.ok()) {
+    if (named_decl != nullptr) {
+      CHECK(!named_decl->getName().empty());
+      absl::StatusOr<Identifier> record_name =
+          ictx_.GetTranslatedIdentifier(named_decl);
+      if (!record_name.ok()) {
+        return ictx_.ImportUnsupportedItem(
+            record_decl, absl::StrCat("Record name is not supported: ",
+                                      record_name.status().message()));
+      }
       rs_name = cc_name = record_name->Ident();
-      doc_comment = ictx_.GetComment(record_decl);
-      source_loc = record_decl->getBeginLoc();
-    } else {
-      return ictx_.ImportUnsupportedItem(
-          record_decl, absl::StrCat("Record name is not supported: ",
-                                    record_name.status().message()));
+    }
+    doc_comment = ictx_.GetComment(record_decl);
+    source_loc = record_decl->getBeginLoc();
+
+    // Nested records are named after their enclosing records: `Outer::Inner`
+    // is bound as `Outer_Inner`.
+    for (const clang::DeclContext* context = decl_context; context->isRecord();
+         context = context->getParent()) {
+      const auto* enclosing_record = clang::cast<clang::CXXRecordDecl>(context);
+      const clang::NamedDecl* enclosing_named_decl = enclosing_record;
+      if (enclosing_record->getName().empty()) {
+        enclosing_named_decl = enclosing_record->getTypedefNameForAnonDecl();
+      }
+      // The enclosing records have been imported successfully, so they have
+      // a supported name.
+      std::string enclosing_name;
+      if (enclosing_named_decl != nullptr) {
+        enclosing_name =
+            ictx_.GetTranslatedIdentifier(enclosing_named_decl)->Ident();
+      } else {
+        const clang::FieldDecl* enclosing_field_decl =
+            GetFieldOfAnonNestedRecord(enclosing_record);
+        CHECK(enclosing_field_decl != nullptr);
+        enclosing_name = GetAnonNestedRecordName(*enclosing_field_decl);
+      }
+      rs_name = absl::StrCat(enclosing_name, "_", rs_name);
+      cc_name = absl::StrCat(enclosing_name, "::", cc_name);
     }
   }
 
@@ -287,6 +442,19 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
         .enclosing_namespace_id = GetEnclosingNamespaceId(record_decl)};
   }
 
+  absl::StatusOr<std::optional<bool>> is_send =
+      GetAutoTraitOverride(*record_decl, "send");
+  if (!is_send.ok()) {
+    return ictx_.ImportUnsupportedItem(record_decl,
+                                       std::string(is_send.status().message()));
+  }
+  absl::StatusOr<std::optional<bool>> is_sync =
+      GetAutoTraitOverride(*record_decl, "sync");
+  if (!is_sync.ok()) {
+    return ictx_.ImportUnsupportedItem(record_decl,
+                                       std::string(is_sync.status().message()));
+  }
+
   // At this point we know that the import of `record_decl` will succeed /
   // cannot fail.
   ictx_.MarkAsSuccessfullyImported(record_decl);
@@ -314,6 +482,7 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
       .owning_target = ictx_.GetOwningTarget(record_decl),
       .defining_target = std::move(defining_target),
       .doc_comment = std::move(doc_comment),
+      .deprecated = GetDeprecationMessage(record_decl),
       .source_loc = ictx_.ConvertSourceLocation(source_loc),
       .unambiguous_public_bases = GetUnambiguousPublicBases(*record_decl),
       .fields = ImportFields(record_decl),
@@ -328,11 +497,23 @@ std::optional<IR::Item> CXXRecordDeclImporter::Import(
       .move_constructor = GetMoveCtorSpecialMemberFunc(*record_decl),
       .destructor = GetDestructorSpecialMemberFunc(*record_decl),
       .is_trivial_abi = record_decl->canPassInRegisters(),
+      // Virtual base class subobjects are located through the vtable, so Rust
+      // must not move such objects with `memcpy` even if they're annotated.
+      .is_rust_movable = HasAnnotation(record_decl, "crubit_rust_movable") &&
+                         record_decl->getNumVBases() == 0,
+      .is_send = *is_send,
+      .is_sync = *is_sync,
+      .is_extensible = HasAnnotation(record_decl, "crubit_extensible"),
+      .has_stream_insertion_operator =
+          HasStreamInsertionOperator(ictx_.ctx_, *record_decl),
       .is_inheritable = !is_effectively_final,
       .is_abstract = record_decl->isAbstract(),
+      .has_non_public_pure_virtual = HasNonPublicPureVirtual(*record_decl),
       .record_type = *record_type,
       .is_aggregate = record_decl->isAggregate(),
       .is_anon_record_with_typedef = anon_typedef != nullptr,
+      .is_anon_nested_record =
+          GetFieldOfAnonNestedRecord(record_decl) != nullptr,
       .is_explicit_class_template_instantiation_definition =
           is_explicit_class_template_instantiation_definition,
       .child_item_ids = std::move(item_ids),
@@ -386,6 +567,11 @@ std::vector<Field> CXXRecordDeclImporter::ImportFields(
     if (access == clang::AS_none) {
       access = default_access;
     }
+    // `CRUBIT_DO_NOT_BIND` fields still occupy space in the record, so they
+    // are emitted as opaque blobs of bytes, like private fields.
+    if (HasAnnotation(field_decl, "crubit_do_not_bind")) {
+      access = clang::AS_private;
+    }
 
     const clang::tidy::lifetimes::ValueLifetimes* no_lifetimes = nullptr;
     absl::StatusOr<MappedType> type;
@@ -425,6 +611,7 @@ std::vector<Field> CXXRecordDeclImporter::ImportFields(
     fields.push_back(
         {.identifier = GetTranslatedFieldName(field_decl),
          .doc_comment = ictx_.GetComment(field_decl),
+         .deprecated = GetDeprecationMessage(field_decl),
          .type = std::move(type),
          .access = TranslateAccessSpecifier(access),
          .offset = layout.getFieldOffset(field_decl->getFieldIndex()),
@@ -12,6 +12,7 @@
 #include "absl/status/statusor.h"
 #include "absl/strings/str_cat.h"
 #include "lifetime_annotations/type_lifetimes.h"
+#include "rs_bindings_from_cc/ast_util.h"
 #include "rs_bindings_from_cc/ir.h"
 #include "clang/AST/Decl.h"
 #include "clang/AST/Type.h"
@@ -19,7 +20,13 @@
 namespace crubit {
 
 std::optional<IR::Item> EnumDeclImporter::Import(clang::EnumDecl* enum_decl) {
+  const clang::NamedDecl* named_decl = enum_decl;
   if (enum_decl->getName().empty()) {
+    // C headers commonly declare enums as `typedef enum { ... } Name;`. Such
+    // an enum is bound under its typedef name (just like anonymous structs).
+    named_decl = enum_decl->getTypedefNameForAnonDecl();
+  }
+  if (named_decl == nullptr) {
     // TODO(b/208945197): This corresponds to an unnamed enum declaration like
     // `enum { kFoo = 1 }`, which only exists to provide constants into the
     // surrounding scope and doesn't actually introduce an enum namespace. It
@@ -28,7 +35,7 @@ std::optional<IR::Item> EnumDeclImporter::Import(clang::EnumDecl* enum_decl) {
                                        "Unnamed enums are not supported yet");
   }
   absl::StatusOr<Identifier> enum_name =
-      ictx_.GetTranslatedIdentifier(enum_decl);
+      ictx_.GetTranslatedIdentifier(named_decl);
   if (!enum_name.ok()) {
     return ictx_.ImportUnsupportedItem(
         enum_decl, absl::StrCat("Enum name is not supported: ",
@@ -67,6 +74,7 @@ std::optional<IR::Item> EnumDeclImporter::Import(clang::EnumDecl* enum_decl) {
 
     enumerators.push_back(Enumerator{
         .identifier = *enumerator_name,
+        .doc_comment = ictx_.GetComment(enumerator),
         .value = IntegerConstant(enumerator->getInitVal()),
     });
   }
@@ -75,6 +83,8 @@ std::optional<IR::Item> EnumDeclImporter::Import(clang::EnumDecl* enum_decl) {
       .identifier = *enum_name,
       .id = GenerateItemId(enum_decl),
       .owning_target = ictx_.GetOwningTarget(enum_decl),
+      .doc_comment = ictx_.GetComment(enum_decl),
+      .deprecated = GetDeprecationMessage(enum_decl),
       .source_loc = ictx_.ConvertSourceLocation(enum_decl->getBeginLoc()),
       .underlying_type = *std::move(type),
       .enumerators = enumerators,
@@ -12,9 +12,12 @@
 #include <vector>
 
 #include "absl/log/check.h"
+#include "absl/status/status.h"
 #include "absl/status/statusor.h"
 #include "absl/strings/str_cat.h"
+#include "absl/strings/string_view.h"
 #include "absl/strings/substitute.h"
+#include "common/status_macros.h"
 #include "lifetime_annotations/lifetime.h"
 #include "lifetime_annotations/lifetime_annotations.h"
 #include "lifetime_annotations/lifetime_error.h"
@@ -23,7 +26,13 @@
 #include "rs_bindings_from_cc/ast_util.h"
 #include "rs_bindings_from_cc/bazel_types.h"
 #include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/ASTContext.h"
+#include "clang/AST/Attr.h"
+#include "clang/AST/DeclTemplate.h"
 #include "clang/AST/DeclarationName.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/PrettyPrinter.h"
+#include "clang/AST/TemplateBase.h"
 #include "clang/AST/Type.h"
 #include "clang/Basic/LLVM.h"
 #include "clang/Basic/Specifiers.h"
@@ -31,6 +40,7 @@
 #include "llvm/ADT/STLExtras.h"
 #include "llvm/ADT/StringRef.h"
 #include "llvm/Support/Error.h"
+#include "llvm/Support/raw_ostream.h"
 
 namespace crubit {
 
@@ -45,6 +55,186 @@ static bool IsInStdNamespace(const clang::FunctionDecl* decl) {
   return false;
 }
 
+// Returns the `T*` in `gsl::not_null<T*>`, or `std::nullopt` if `type` is not
+// a `gsl::not_null` of a pointer.
+static std::optional<clang::QualType> GetGslNotNullPointerType(
+    clang::QualType type) {
+  const auto* specialization_decl =
+      llvm::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          type.getCanonicalType()->getAsCXXRecordDecl());
+  if (specialization_decl == nullptr ||
+      specialization_decl->getQualifiedNameAsString() != "gsl::not_null") {
+    return std::nullopt;
+  }
+  const clang::TemplateArgumentList& args =
+      specialization_decl->getTemplateArgs();
+  if (args.size() != 1 || args[0].getKind() != clang::TemplateArgument::Type ||
+      !args[0].getAsType()->isPointerType()) {
+    return std::nullopt;
+  }
+  return args[0].getAsType();
+}
+
+// Returns the nullability that `type` is annotated with: either with a
+// `_Nonnull`, `_Nullable` or `_Null_unspecified` attribute, or with an alias
+// template that is annotated with `[[clang::annotate("Nonnull")]]` etc. (like
+// `absl::Nonnull<T*>`).
+static std::optional<clang::NullabilityKind> GetNullabilityAnnotation(
+    clang::QualType type) {
+  const clang::Type* sugared_type = type.getTypePtr();
+  while (true) {
+    if (const auto* attributed_type =
+            llvm::dyn_cast<clang::AttributedType>(sugared_type)) {
+      if (std::optional<clang::NullabilityKind> nullability =
+              attributed_type->getImmediateNullability()) {
+        return nullability;
+      }
+    } else if (const auto* alias_type =
+                   llvm::dyn_cast<clang::TemplateSpecializationType>(
+                       sugared_type);
+               alias_type != nullptr && alias_type->isTypeAlias()) {
+      const clang::TemplateDecl* template_decl =
+          alias_type->getTemplateName().getAsTemplateDecl();
+      if (template_decl != nullptr &&
+          template_decl->getTemplatedDecl() != nullptr) {
+        if (const auto* annotation =
+                template_decl->getTemplatedDecl()
+                    ->getAttr<clang::AnnotateAttr>()) {
+          if (annotation->getAnnotation() == "Nonnull") {
+            return clang::NullabilityKind::NonNull;
+          }
+          if (annotation->getAnnotation() == "Nullable") {
+            return clang::NullabilityKind::Nullable;
+          }
+          if (annotation->getAnnotation() == "Nullability_Unspecified") {
+            return clang::NullabilityKind::Unspecified;
+          }
+        }
+      }
+    }
+    clang::QualType desugared_type =
+        sugared_type->getLocallyUnqualifiedSingleStepDesugaredType();
+    if (desugared_type.getTypePtr() == sugared_type) {
+      return std::nullopt;
+    }
+    sugared_type = desugared_type.getTypePtr();
+  }
+}
+
+// Returns the nullability of a pointer parameter:
+// - `T* p = nullptr` is nullable, even if `p` is annotated as `_Nonnull`.
+// - Otherwise, the nullability is the one that the type is annotated with (see
+//   `GetNullabilityAnnotation`), if any.
+static std::optional<clang::NullabilityKind> GetParamNullability(
+    const clang::ParmVarDecl* param, clang::ASTContext& ctx) {
+  if (param->hasDefaultArg() && !param->hasUnparsedDefaultArg() &&
+      !param->hasUninstantiatedDefaultArg() &&
+      param->getDefaultArg()->isNullPointerConstant(
+          ctx, clang::Expr::NPC_ValueDependentIsNotNull)) {
+    return clang::NullabilityKind::Nullable;
+  }
+  return GetNullabilityAnnotation(param->getType());
+}
+
+// Binds a pointer that has no lifetime (and so is bound as a raw pointer) as
+// `NonNull<T>` if it is annotated as non-null, or as `Option<NonNull<T>>` if
+// it is annotated as nullable. (Pointers that have a lifetime are bound as
+// `&T` or `Option<&T>` instead, see `MappedType::PointerTo`.)
+static void ApplyNullabilityToRawPointer(
+    MappedType& type, std::optional<clang::NullabilityKind> nullability) {
+  if (type.rs_type.name != internal::kRustPtrMut &&
+      type.rs_type.name != internal::kRustPtrConst) {
+    return;
+  }
+  if (nullability != clang::NullabilityKind::NonNull &&
+      nullability != clang::NullabilityKind::Nullable &&
+      nullability != clang::NullabilityKind::NullableResult) {
+    return;
+  }
+  RsType non_null_type{.name = "::core::ptr::NonNull",
+                       .type_args = std::move(type.rs_type.type_args)};
+  if (nullability == clang::NullabilityKind::NonNull) {
+    type.rs_type = std::move(non_null_type);
+  } else {
+    type.rs_type =
+        RsType{.name = "Option", .type_args = {std::move(non_null_type)}};
+  }
+}
+
+// Returns whether `param_decl` is one of the parameters that a function
+// parameter pack (e.g. `const Args&... args`) was expanded into.
+static bool IsExpandedFromParameterPack(const clang::ParmVarDecl* param_decl) {
+  if (const auto* sttpt =
+          param_decl->getType()->getAs<clang::SubstTemplateTypeParmType>();
+      sttpt != nullptr && sttpt->getReplacedParameter()->isParameterPack()) {
+    return true;
+  }
+  // Otherwise, find the parameter of the template pattern that `param_decl`
+  // was instantiated from. This requires the pattern to have a single
+  // parameter pack (a pack that isn't the last parameter can't be deduced, so
+  // functions with several packs are rare).
+  const auto* function_decl =
+      clang::dyn_cast<clang::FunctionDecl>(param_decl->getDeclContext());
+  if (function_decl == nullptr) return false;
+  const clang::FunctionDecl* pattern =
+      function_decl->getTemplateInstantiationPattern();
+  if (pattern == nullptr) return false;
+  auto is_pack = [](const clang::ParmVarDecl* param) {
+    return param->isParameterPack();
+  };
+  if (llvm::count_if(pattern->parameters(), is_pack) != 1) return false;
+  unsigned pack_size =
+      function_decl->getNumParams() + 1 - pattern->getNumParams();
+  unsigned index = param_decl->getFunctionScopeIndex();
+  for (const clang::ParmVarDecl* pattern_param : pattern->parameters()) {
+    unsigned num_params = pattern_param->isParameterPack() ? pack_size : 1;
+    if (index < num_params) return pattern_param->isParameterPack();
+    index -= num_params;
+  }
+  return false;
+}
+
+// Gets the Rust name given by the `crubit_rust_name` attribute of
+// `function_decl`, if any.
+static absl::StatusOr<std::optional<std::string>> GetRustNameAttribute(
+    const clang::FunctionDecl* function_decl) {
+  CRUBIT_ASSIGN_OR_RETURN(const clang::AnnotateAttr* attr,
+                          GetAnnotateAttr(function_decl, "crubit_rust_name"));
+  if (attr == nullptr) return std::nullopt;
+  if (attr->args_size() != 1) {
+    return absl::InvalidArgumentError(
+        "The `crubit_rust_name` attribute requires a single string literal "
+        "argument, the Rust name.");
+  }
+  CRUBIT_ASSIGN_OR_RETURN(
+      absl::string_view rust_name,
+      EvaluateAsStringLiteral(**attr->args_begin(),
+                              function_decl->getASTContext()));
+  return std::string(rust_name);
+}
+
+// Returns why a CUDA function cannot be bound, if it can't: only functions
+// that can be called from host code are bound. Functions that are implicitly
+// `__host__ __device__` (e.g. `constexpr` functions) count as host functions.
+static std::optional<absl::string_view> GetCudaUnsupportedReason(
+    const clang::FunctionDecl* function_decl, bool cuda_host_only) {
+  if (function_decl->hasAttr<clang::CUDAGlobalAttr>()) {
+    return "CUDA kernels (`__global__` functions) are not supported";
+  }
+  const auto* device_attr = function_decl->getAttr<clang::CUDADeviceAttr>();
+  if (device_attr == nullptr || device_attr->isImplicit()) {
+    return std::nullopt;
+  }
+  if (!function_decl->hasAttr<clang::CUDAHostAttr>()) {
+    return "CUDA `__device__` functions are not supported";
+  }
+  if (cuda_host_only) {
+    return "CUDA `__host__ __device__` functions are not bound with "
+           "`--cuda_host_only`";
+  }
+  return std::nullopt;
+}
+
 Identifier FunctionDeclImporter::GetTranslatedParamName(
     const clang::ParmVarDecl* param_decl) {
   int param_pos = param_decl->getFunctionScopeIndex();
@@ -52,9 +242,7 @@ Identifier FunctionDeclImporter::GetTranslatedParamName(
   if (!name.ok()) {
     return {Identifier(absl::StrCat("__param_", param_pos))};
   }
-  if (auto* sttpt =
-          param_decl->getType()->getAs<clang::SubstTemplateTypeParmType>();
-      sttpt && sttpt->getReplacedParameter()->isParameterPack()) {
+  if (IsExpandedFromParameterPack(param_decl)) {
     // Avoid giving the same name to all parameters expanded from a pack.
     return {Identifier(absl::StrCat("__", name->Ident(), "_", param_pos))};
   }
@@ -65,6 +253,10 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
     clang::FunctionDecl* function_decl) {
   if (!ictx_.IsFromCurrentTarget(function_decl)) return std::nullopt;
   if (function_decl->isDeleted()) return std::nullopt;
+  if (std::optional<absl::string_view> cuda_error = GetCudaUnsupportedReason(
+          function_decl, ictx_.invocation_.cuda_host_only_)) {
+    return ictx_.ImportUnsupportedItem(function_decl, std::string(*cuda_error));
+  }
 
   if (IsInStdNamespace(function_decl)) {
     if (clang::IdentifierInfo* id = function_decl->getIdentifier();
@@ -102,14 +294,34 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
         // TODO(lukasza): Revisit this for protected methods.
         return std::nullopt;
     }
+    // The type of an anonymous nested record can't be spelled in C++, so
+    // there is no way to call its member functions from a thunk.
+    if (GetFieldOfAnonNestedRecord(method_decl->getParent()) != nullptr) {
+      return std::nullopt;
+    }
+  }
+
+  // Lifetime elision is enabled in files that specify the `lifetime_elision`
+  // pragma, and with `--lifetime_elision`, in all headers of the current
+  // target. Functions annotated with `CRUBIT_NO_LIFETIME_ELISION` are exempt.
+  const clang::tidy::lifetimes::LifetimeAnnotationContext* lifetime_context =
+      ictx_.invocation_.lifetime_context_.get();
+  clang::tidy::lifetimes::LifetimeAnnotationContext elision_override;
+  if (HasAnnotation(function_decl, "crubit_no_lifetime_elision")) {
+    lifetime_context = &elision_override;
+  } else if (ictx_.invocation_.lifetime_elision_ &&
+             ictx_.IsFromCurrentTarget(function_decl)) {
+    elision_override.lifetime_elision_files.insert(
+        ictx_.ctx_.getSourceManager().getFileID(
+            function_decl->getSourceRange().getBegin()));
+    lifetime_context = &elision_override;
   }
 
   clang::tidy::lifetimes::LifetimeSymbolTable lifetime_symbol_table;
   std::optional<clang::tidy::lifetimes::FunctionLifetimes> lifetimes;
   llvm::Expected<clang::tidy::lifetimes::FunctionLifetimes> lifetimes_or_err =
       clang::tidy::lifetimes::GetLifetimeAnnotations(
-          function_decl, *ictx_.invocation_.lifetime_context_,
-          &lifetime_symbol_table);
+          function_decl, *lifetime_context, &lifetime_symbol_table);
   if (lifetimes_or_err) {
     lifetimes = std::move(*lifetimes_or_err);
   } else {
@@ -182,14 +394,57 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
     CHECK(lifetimes->IsValidForDecl(function_decl));
   }
 
+  // With `--long_double=f64`, parameters and return values of type
+  // `long double` are bound as `double`. The thunk converts them implicitly
+  // when calling the function.
+  bool converts_long_double = false;
+  auto convert_long_double_to_double =
+      [&](clang::QualType type) -> std::optional<MappedType> {
+    if (ictx_.invocation_.long_double_policy_ != LongDoublePolicy::kF64 ||
+        !type->isSpecificBuiltinType(clang::BuiltinType::LongDouble)) {
+      return std::nullopt;
+    }
+    converts_long_double = true;
+    return MappedType::Simple("f64", "double");
+  };
+
   for (unsigned i = 0; i < function_decl->getNumParams(); ++i) {
     const clang::ParmVarDecl* param = function_decl->getParamDecl(i);
     const clang::tidy::lifetimes::ValueLifetimes* param_lifetimes = nullptr;
     if (lifetimes) {
       param_lifetimes = &lifetimes->GetParamLifetimes(i);
     }
-    auto param_type =
-        ictx_.ConvertQualType(param->getType(), param_lifetimes, std::nullopt);
+    clang::QualType param_qual_type = param->getType();
+    std::optional<clang::NullabilityKind> nullability =
+        GetParamNullability(param, ictx_.ctx_);
+    // `gsl::not_null<T*>` is bound as if it was a non-nullable `T*`. The
+    // thunk converts the `T*` back into a `gsl::not_null<T*>`, and when the
+    // C++ function is called directly, it is passed the same way as `T*`
+    // (`gsl::not_null<T*>` is a trivially copyable wrapper of a `T*`).
+    if (std::optional<clang::QualType> pointer_type =
+            GetGslNotNullPointerType(param_qual_type)) {
+      param_qual_type = *pointer_type;
+      nullability = clang::NullabilityKind::NonNull;
+      if (param_lifetimes) {
+        const std::optional<clang::tidy::lifetimes::ValueLifetimes>&
+            pointer_lifetimes = param_lifetimes->GetTemplateArgumentLifetimes(
+                /*depth=*/0, /*index=*/0);
+        param_lifetimes =
+            pointer_lifetimes.has_value() ? &*pointer_lifetimes : nullptr;
+      }
+    }
+    absl::StatusOr<MappedType> param_type;
+    if (std::optional<MappedType> converted =
+            convert_long_double_to_double(param_qual_type)) {
+      param_type = *std::move(converted);
+    } else {
+      param_type = ictx_.ConvertQualType(
+          param_qual_type, param_lifetimes, std::nullopt,
+          /*nullable=*/nullability != clang::NullabilityKind::NonNull);
+      if (param_type.ok()) {
+        ApplyNullabilityToRawPointer(*param_type, nullability);
+      }
+    }
     if (!param_type.ok()) {
       add_error(absl::Substitute("Parameter #$0 is not supported: $1", i,
                                  param_type.status().message()));
@@ -198,7 +453,8 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
 
     std::optional<Identifier> param_name = GetTranslatedParamName(param);
     CHECK(param_name.has_value());  // No known failure cases.
-    params.push_back({*param_type, *std::move(param_name)});
+    params.push_back({*param_type, *std::move(param_name),
+                      /*has_default_value=*/param->hasDefaultArg()});
   }
 
   if (function_decl->getReturnType()->isUndeducedType()) {
@@ -214,8 +470,35 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
     return_lifetimes = &lifetimes->GetReturnLifetimes();
   }
 
-  auto return_type = ictx_.ConvertQualType(function_decl->getReturnType(),
-                                           return_lifetimes, std::nullopt);
+  absl::StatusOr<MappedType> return_type;
+  if (std::optional<MappedType> converted =
+          convert_long_double_to_double(function_decl->getReturnType())) {
+    return_type = *std::move(converted);
+  } else if (return_lifetimes == nullptr &&
+             function_decl->getReturnType()->isRValueReferenceType() &&
+             !function_decl->getReturnType()
+                  ->getPointeeType()
+                  .isLocalVolatileQualified()) {
+    // Without lifetimes, a returned `T&&` can't be bound as an
+    // `RvalueReference`. Like a returned `T&`, it is bound as a raw pointer
+    // instead (the thunk still returns the address of the referenced object).
+    return_type = ictx_.ConvertQualType(
+        ictx_.ctx_.getLValueReferenceType(
+            function_decl->getReturnType()->getPointeeType()),
+        /*lifetimes=*/nullptr, std::nullopt);
+    if (return_type.ok()) {
+      return_type->cc_type.name = std::string(internal::kCcRValueRef);
+    }
+  } else {
+    std::optional<clang::NullabilityKind> nullability =
+        GetNullabilityAnnotation(function_decl->getReturnType());
+    return_type = ictx_.ConvertQualType(
+        function_decl->getReturnType(), return_lifetimes, std::nullopt,
+        /*nullable=*/nullability != clang::NullabilityKind::NonNull);
+    if (return_type.ok()) {
+      ApplyNullabilityToRawPointer(*return_type, nullability);
+    }
+  }
   if (!return_type.ok()) {
     add_error(absl::StrCat("Return type is not supported: ",
                            return_type.status().message()));
@@ -260,6 +543,7 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
           .reference = reference,
           .is_const = method_decl->isConst(),
           .is_virtual = method_decl->isVirtual(),
+          .is_pure_virtual = method_decl->isPureVirtual(),
       };
     }
 
@@ -268,6 +552,27 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
         .instance_method_metadata = instance_metadata};
   }
 
+  bool sets_errno = ictx_.invocation_.sets_errno(
+      function_decl->getQualifiedNameAsString());
+  if (sets_errno) {
+    clang::QualType cc_return_type = function_decl->getReturnType();
+    if (member_func_metadata.has_value()) {
+      add_error("`--errno_functions` only supports free functions");
+    } else if (!cc_return_type->isPointerType() &&
+               !cc_return_type->isSignedIntegerType()) {
+      add_error(
+          "`--errno_functions` only supports functions that return a signed "
+          "integer or a pointer");
+    }
+  }
+
+  absl::StatusOr<std::optional<std::string>> rust_name =
+      GetRustNameAttribute(function_decl);
+  if (!rust_name.ok()) {
+    add_error(absl::StrCat("Invalid `crubit_rust_name` attribute: ",
+                           rust_name.status().message()));
+  }
+
   if (!errors.empty()) {
     return ictx_.ImportUnsupportedItem(function_decl, errors);
   }
@@ -278,8 +583,24 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
   bool is_member_or_descendant_of_class_template =
       IsFullClassTemplateSpecializationOrChild(function_decl);
 
+  std::optional<std::string> template_args;
+  if (const clang::TemplateArgumentList* args =
+          function_decl->getTemplateSpecializationArgs()) {
+    clang::PrintingPolicy policy(ictx_.ctx_.getLangOpts());
+    // Canonicalize types so that their namespaces are written down, as in
+    // `GetClassTemplateSpecializationCcName`.
+    policy.PrintCanonicalTypes = true;
+    policy.AlwaysIncludeTypeForTemplateArgument = true;
+    std::string template_args_str;
+    llvm::raw_string_ostream os(template_args_str);
+    clang::printTemplateArgumentList(os, args->asArray(), policy);
+    template_args = std::move(os.str());
+  }
+
   std::optional<std::string> doc_comment = ictx_.GetComment(function_decl);
-  if (!doc_comment.has_value() && is_member_or_descendant_of_class_template) {
+  if (!doc_comment.has_value() &&
+      (is_member_or_descendant_of_class_template ||
+       template_args.has_value())) {
     // Despite `is_member_or_descendant_of_class_template` check above, we are
     // not guaranteed that a `func_pattern` exists below.  For example, it may
     // be missing when `function_decl` is an implicitly defined constructor of a
@@ -291,27 +612,37 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
   }
 
   std::string mangled_name = ictx_.GetMangledName(function_decl);
-  if (is_member_or_descendant_of_class_template) {
+  if (is_member_or_descendant_of_class_template || template_args.has_value()) {
     // `thunks_for_class_template_member_functions.md` explains in more detail
     // why the `mangled_name` has to include the target name when working with
-    // members or descendants of a class template.
+    // members or descendants of a class template.  The same applies to
+    // function template specializations.
     mangled_name += '_';
     mangled_name += ConvertToCcIdentifier(ictx_.GetOwningTarget(function_decl));
   }
 
+  bool is_noexcept = function_decl->getType()
+                         ->castAs<clang::FunctionProtoType>()
+                         ->isNothrow();
+
   // Silence ClangTidy, checked above: calling `add_error` if
   // `!return_type.ok()` and returning early if `!errors.empty()`.
   CHECK_OK(return_type);
+  CHECK_OK(rust_name);
 
   return Func{
       .name = *translated_name,
       .owning_target = ictx_.GetOwningTarget(function_decl),
       .doc_comment = std::move(doc_comment),
+      .deprecated = GetDeprecationMessage(function_decl),
       .mangled_name = std::move(mangled_name),
       .return_type = *return_type,
       .params = std::move(params),
       .lifetime_params = std::move(lifetime_params),
       .is_inline = function_decl->isInlined(),
+      .is_noexcept = is_noexcept,
+      .may_throw = !is_noexcept && ictx_.ctx_.getLangOpts().CXXExceptions,
+      .is_variadic = function_decl->isVariadic(),
       .member_func_metadata = std::move(member_func_metadata),
       .has_c_calling_convention = has_c_calling_convention,
       .is_member_or_descendant_of_class_template =
@@ -319,6 +650,10 @@ std::optional<IR::Item> FunctionDeclImporter::Import(
       .source_loc = ictx_.ConvertSourceLocation(function_decl->getBeginLoc()),
       .id = GenerateItemId(function_decl),
       .enclosing_namespace_id = GetEnclosingNamespaceId(function_decl),
+      .sets_errno = sets_errno,
+      .converts_long_double = converts_long_double,
+      .template_args = std::move(template_args),
+      .rust_name = *std::move(rust_name),
   };
 }
 
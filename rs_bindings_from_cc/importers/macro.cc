@@ -0,0 +1,522 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "rs_bindings_from_cc/importers/macro.h"
+
+#include <optional>
+#include <string>
+#include <utility>
+#include <variant>
+#include <vector>
+
+#include "absl/container/flat_hash_set.h"
+#include "absl/status/statusor.h"
+#include "absl/strings/str_cat.h"
+#include "absl/types/span.h"
+#include "rs_bindings_from_cc/ast_util.h"
+#include "rs_bindings_from_cc/bazel_types.h"
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/APValue.h"
+#include "clang/AST/ASTContext.h"
+#include "clang/AST/Decl.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/Type.h"
+#include "clang/Basic/Diagnostic.h"
+#include "clang/Basic/IdentifierTable.h"
+#include "clang/Basic/OperatorPrecedence.h"
+#include "clang/Basic/TokenKinds.h"
+#include "clang/Lex/MacroInfo.h"
+#include "clang/Lex/Preprocessor.h"
+#include "clang/Lex/Token.h"
+#include "clang/Sema/Ownership.h"
+#include "clang/Sema/Sema.h"
+#include "llvm/ADT/ArrayRef.h"
+
+namespace crubit {
+
+// Returns the value of a constant, or `std::nullopt` if it can't be represented
+// in the IR.
+static std::optional<std::variant<IntegerConstant, FloatConstant>>
+ConvertConstantValue(const clang::APValue& value) {
+  if (value.isInt() && value.getInt().getSignificantBits() <= 64) {
+    return IntegerConstant(value.getInt());
+  }
+  if (value.isFloat() && value.getFloat().isFinite()) {
+    return FloatConstant(value.getFloat());
+  }
+  return std::nullopt;
+}
+
+// Returns the position of the `)` that matches the `(` at `l_paren_pos`, or
+// `tokens.size()` if there is none.
+static size_t FindMatchingParen(absl::Span<const clang::Token> tokens,
+                                size_t l_paren_pos) {
+  int depth = 0;
+  for (size_t pos = l_paren_pos; pos < tokens.size(); ++pos) {
+    if (tokens[pos].is(clang::tok::l_paren)) {
+      ++depth;
+    } else if (tokens[pos].is(clang::tok::r_paren) && --depth == 0) {
+      return pos;
+    }
+  }
+  return tokens.size();
+}
+
+// Removes the parentheses that enclose all of `tokens` (e.g. `((x))` -> `x`).
+static absl::Span<const clang::Token> StripParens(
+    absl::Span<const clang::Token> tokens) {
+  while (tokens.size() >= 2 && tokens.front().is(clang::tok::l_paren) &&
+         FindMatchingParen(tokens, 0) == tokens.size() - 1) {
+    tokens = tokens.subspan(1, tokens.size() - 2);
+  }
+  return tokens;
+}
+
+// Returns the index of the parameter of `macro_info` that `tokens` consist of
+// (possibly in parentheses), or nullopt if they are anything else.
+static std::optional<int> GetParamIndex(const clang::MacroInfo& macro_info,
+                                        absl::Span<const clang::Token> tokens) {
+  tokens = StripParens(tokens);
+  if (tokens.size() != 1 || tokens.front().isNot(clang::tok::identifier)) {
+    return std::nullopt;
+  }
+  int index = macro_info.getParameterNum(tokens.front().getIdentifierInfo());
+  if (index < 0) return std::nullopt;
+  return index;
+}
+
+std::optional<IR::Item> MacroImporter::Import(
+    const clang::IdentifierInfo& name, const clang::MacroInfo& macro_info) {
+  if (macro_info.isBuiltinMacro()) return std::nullopt;
+  if (macro_info.isFunctionLike()) {
+    return ImportFunctionLikeMacro(name, macro_info);
+  }
+  if (std::optional<Constant> constant =
+          ImportObjectLikeMacro(name, macro_info)) {
+    return *std::move(constant);
+  }
+  return std::nullopt;
+}
+
+std::optional<Constant> MacroImporter::ImportObjectLikeMacro(
+    const clang::IdentifierInfo& name, const clang::MacroInfo& macro_info) {
+  if (macro_info.tokens_empty()) return std::nullopt;
+  absl::flat_hash_set<const clang::IdentifierInfo*> visited = {&name};
+  tokens_.clear();
+  pos_ = 0;
+  if (!ExpandTokens(macro_info, visited, tokens_)) return std::nullopt;
+
+  // Sema diagnoses the expressions that it can't build (e.g. `1.5 % 2`), but
+  // such macros are simply not imported.
+  clang::DiagnosticsEngine& diagnostics = ictx_.ctx_.getDiagnostics();
+  bool suppress_all_diagnostics = diagnostics.getSuppressAllDiagnostics();
+  diagnostics.setSuppressAllDiagnostics(true);
+  clang::Expr* expr = ParseExpression(clang::prec::LogicalOr);
+  diagnostics.setSuppressAllDiagnostics(suppress_all_diagnostics);
+  if (expr == nullptr || pos_ != tokens_.size() || expr->isValueDependent()) {
+    return std::nullopt;
+  }
+
+  clang::QualType type = expr->getType();
+  if (!IsSupportedConstantType(type)) return std::nullopt;
+  clang::Expr::EvalResult result;
+  if (!expr->EvaluateAsRValue(result, ictx_.ctx_) || result.HasSideEffects ||
+      result.HasUndefinedBehavior) {
+    return std::nullopt;
+  }
+  std::optional<std::variant<IntegerConstant, FloatConstant>> value =
+      ConvertConstantValue(result.Val);
+  if (!value.has_value()) return std::nullopt;
+
+  const clang::tidy::lifetimes::ValueLifetimes* no_lifetimes = nullptr;
+  absl::StatusOr<MappedType> mapped_type =
+      ictx_.ConvertQualType(type, no_lifetimes, std::nullopt);
+  if (!mapped_type.ok()) return std::nullopt;
+
+  return Constant{
+      .identifier = Identifier(std::string(name.getName())),
+      .id = GenerateItemId(&macro_info),
+      .owning_target = ictx_.invocation_.target_,
+      .doc_comment = std::nullopt,
+      .type = *std::move(mapped_type),
+      .value = *std::move(value),
+      .source_loc = ictx_.ConvertSourceLocation(macro_info.getDefinitionLoc()),
+      .enclosing_record_id = std::nullopt,
+      .enclosing_namespace_id = std::nullopt,
+  };
+}
+
+std::optional<IR::Item> MacroImporter::ImportFunctionLikeMacro(
+    const clang::IdentifierInfo& name, const clang::MacroInfo& macro_info) {
+  std::string source_loc =
+      ictx_.ConvertSourceLocation(macro_info.getDefinitionLoc());
+  auto unsupported = [&](std::string message) -> IR::Item {
+    return UnsupportedItem{
+        .name = std::string(name.getName()),
+        .message = std::move(message),
+        .source_loc = source_loc,
+        .id = GenerateItemId(&macro_info),
+    };
+  };
+  // Macros that don't look like a call or a cast (e.g. `MIN(a, b)`) are not
+  // candidates for bindings, and are not reported.
+  if (macro_info.isVariadic()) return std::nullopt;
+
+  // The macro parameters, in order, and the types of their Rust parameters.
+  llvm::ArrayRef<const clang::IdentifierInfo*> macro_params =
+      macro_info.params();
+  std::vector<std::optional<clang::QualType>> param_types(macro_params.size());
+  clang::QualType return_type;
+  bool is_noexcept = true;
+
+  absl::Span<const clang::Token> body = StripParens(macro_info.tokens());
+  if (body.size() >= 3 && body[0].is(clang::tok::identifier) &&
+      body[1].is(clang::tok::l_paren) &&
+      FindMatchingParen(body, 1) == body.size() - 1) {
+    // A call, e.g. `__builtin_bswap16(x)`.
+    if (body[0].getIdentifierInfo() == &name) return std::nullopt;
+    const clang::FunctionDecl* callee =
+        LookupFunction(body[0].getIdentifierInfo(), body[0].getLocation());
+    if (callee == nullptr) {
+      return unsupported(absl::StrCat(
+          "The macro calls `", body[0].getIdentifierInfo()->getName().str(),
+          "`, which is not a unique, non-overloaded function at namespace "
+          "scope"));
+    }
+    if (callee->isVariadic()) {
+      return unsupported("The macro calls a variadic function");
+    }
+    std::vector<absl::Span<const clang::Token>> args;
+    absl::Span<const clang::Token> args_tokens =
+        body.subspan(2, body.size() - 3);
+    if (!args_tokens.empty()) {
+      size_t arg_begin = 0;
+      for (size_t pos = 0; pos <= args_tokens.size(); ++pos) {
+        if (pos == args_tokens.size() ||
+            args_tokens[pos].is(clang::tok::comma)) {
+          args.push_back(args_tokens.subspan(arg_begin, pos - arg_begin));
+          arg_begin = pos + 1;
+        } else if (args_tokens[pos].is(clang::tok::l_paren)) {
+          pos = FindMatchingParen(args_tokens, pos);
+          if (pos == args_tokens.size()) break;
+        }
+      }
+    }
+    if (args.size() != callee->getNumParams() ||
+        args.size() != macro_params.size()) {
+      return unsupported(
+          "The arguments of the call must be the parameters of the macro, in "
+          "any order");
+    }
+    for (size_t i = 0; i < args.size(); ++i) {
+      std::optional<int> param_index = GetParamIndex(macro_info, args[i]);
+      if (!param_index.has_value() || param_types[*param_index].has_value()) {
+        return unsupported(
+            "The arguments of the call must be the parameters of the macro, in "
+            "any order");
+      }
+      param_types[*param_index] = callee->getParamDecl(i)->getType();
+    }
+    return_type = callee->getReturnType();
+    is_noexcept = callee->getType()
+                      ->castAs<clang::FunctionProtoType>()
+                      ->isNothrow();
+  } else if (!body.empty() && body[0].is(clang::tok::l_paren) &&
+             macro_params.size() == 1) {
+    // A cast, e.g. `(unsigned char)(x)`.
+    tokens_.assign(body.begin(), body.end());
+    pos_ = 1;
+    std::optional<clang::QualType> type = ParseCastType();
+    if (!type.has_value()) return std::nullopt;
+    if (GetParamIndex(macro_info, absl::MakeConstSpan(tokens_).subspan(pos_)) !=
+        0) {
+      return unsupported(
+          "The macro must cast its parameter to a builtin arithmetic type or "
+          "to a typedef");
+    }
+    param_types[0] = *type;
+    return_type = *type;
+  } else {
+    return std::nullopt;
+  }
+
+  const clang::tidy::lifetimes::ValueLifetimes* no_lifetimes = nullptr;
+  std::vector<FuncParam> params;
+  for (size_t i = 0; i < macro_params.size(); ++i) {
+    absl::StatusOr<MappedType> type =
+        ictx_.ConvertQualType(*param_types[i], no_lifetimes, std::nullopt);
+    if (!type.ok()) {
+      return unsupported(absl::StrCat("Parameter #", i, " is not supported: ",
+                                      type.status().message()));
+    }
+    params.push_back(FuncParam{
+        .type = *std::move(type),
+        .identifier = Identifier(macro_params[i]->getName().str()),
+    });
+  }
+  absl::StatusOr<MappedType> mapped_return_type =
+      ictx_.ConvertQualType(return_type, no_lifetimes, std::nullopt);
+  if (!mapped_return_type.ok()) {
+    return unsupported(absl::StrCat("Return type is not supported: ",
+                                    mapped_return_type.status().message()));
+  }
+
+  std::string name_str = name.getName().str();
+  return Func{
+      .name = Identifier(name_str),
+      .owning_target = ictx_.invocation_.target_,
+      .doc_comment = std::nullopt,
+      .deprecated = std::nullopt,
+      // There is no symbol to link to: the (always generated) thunk expands
+      // the macro, and its name is derived from the mangled name.
+      .mangled_name =
+          absl::StrCat("__crubit_macro_", name_str, "_",
+                       ConvertToCcIdentifier(ictx_.invocation_.target_)),
+      .return_type = *std::move(mapped_return_type),
+      .params = std::move(params),
+      .lifetime_params = {},
+      .is_inline = true,
+      .is_noexcept = is_noexcept,
+      .may_throw = !is_noexcept && ictx_.ctx_.getLangOpts().CXXExceptions,
+      .source_loc = std::move(source_loc),
+      .id = GenerateItemId(&macro_info),
+  };
+}
+
+const clang::FunctionDecl* MacroImporter::LookupFunction(
+    clang::IdentifierInfo* identifier, clang::SourceLocation loc) const {
+  const clang::FunctionDecl* function_decl = nullptr;
+  for (clang::NamedDecl* decl :
+       ictx_.ctx_.getTranslationUnitDecl()->lookup(identifier)) {
+    auto* candidate = clang::dyn_cast<clang::FunctionDecl>(decl);
+    if (candidate == nullptr) return nullptr;
+    if (function_decl != nullptr && function_decl->getCanonicalDecl() !=
+                                        candidate->getCanonicalDecl()) {
+      return nullptr;
+    }
+    function_decl = candidate;
+  }
+  unsigned builtin_id = identifier->getBuiltinID();
+  if (function_decl == nullptr && builtin_id != 0 &&
+      !ictx_.ctx_.BuiltinInfo.hasCustomTypechecking(builtin_id)) {
+    // Builtins are declared lazily, when they are first used.
+    function_decl = clang::dyn_cast_or_null<clang::FunctionDecl>(
+        ictx_.sema_.LazilyCreateBuiltin(identifier, builtin_id,
+                                        /*S=*/nullptr,
+                                        /*ForRedeclaration=*/false, loc));
+  }
+  if (function_decl == nullptr ||
+      !function_decl->getType()->isFunctionProtoType()) {
+    return nullptr;
+  }
+  return function_decl;
+}
+
+bool MacroImporter::ExpandTokens(
+    const clang::MacroInfo& macro_info,
+    absl::flat_hash_set<const clang::IdentifierInfo*>& visited,
+    std::vector<clang::Token>& tokens) const {
+  clang::Preprocessor& preprocessor = ictx_.sema_.getPreprocessor();
+  for (const clang::Token& token : macro_info.tokens()) {
+    const clang::IdentifierInfo* identifier = token.getIdentifierInfo();
+    const clang::MacroInfo* nested_macro_info =
+        token.is(clang::tok::identifier)
+            ? preprocessor.getMacroInfo(identifier)
+            : nullptr;
+    if (nested_macro_info == nullptr) {
+      tokens.push_back(token);
+      continue;
+    }
+    if (nested_macro_info->isFunctionLike() ||
+        !visited.insert(identifier).second) {
+      return false;
+    }
+    if (!ExpandTokens(*nested_macro_info, visited, tokens)) return false;
+    visited.erase(identifier);
+  }
+  return true;
+}
+
+clang::Expr* MacroImporter::ParseExpression(int min_precedence) {
+  clang::Expr* lhs = ParseUnaryExpression();
+  while (lhs != nullptr && pos_ < tokens_.size()) {
+    const clang::Token& op = tokens_[pos_];
+    clang::prec::Level precedence =
+        clang::getBinOpPrecedence(op.getKind(), /*GreaterThanIsOperator=*/true,
+                                  /*CPlusPlus11=*/true);
+    // The conditional, assignment and comma operators have lower precedence
+    // than `||`, and aren't supported.
+    if (precedence < min_precedence ||
+        precedence > clang::prec::Multiplicative) {
+      break;
+    }
+    ++pos_;
+    clang::Expr* rhs = ParseExpression(precedence + 1);
+    if (rhs == nullptr) return nullptr;
+    clang::ExprResult result = ictx_.sema_.ActOnBinOp(
+        /*S=*/nullptr, op.getLocation(), op.getKind(), lhs, rhs);
+    lhs = result.isUsable() ? result.get() : nullptr;
+  }
+  return lhs;
+}
+
+clang::Expr* MacroImporter::ParseUnaryExpression() {
+  if (pos_ >= tokens_.size()) return nullptr;
+  const clang::Token& token = tokens_[pos_];
+  switch (token.getKind()) {
+    case clang::tok::plus:
+    case clang::tok::minus:
+    case clang::tok::tilde:
+    case clang::tok::exclaim: {
+      ++pos_;
+      clang::Expr* operand = ParseUnaryExpression();
+      if (operand == nullptr) return nullptr;
+      clang::ExprResult result = ictx_.sema_.ActOnUnaryOp(
+          /*S=*/nullptr, token.getLocation(), token.getKind(), operand);
+      return result.isUsable() ? result.get() : nullptr;
+    }
+    case clang::tok::l_paren: {
+      size_t l_paren_pos = pos_++;
+      if (std::optional<clang::QualType> type = ParseCastType()) {
+        clang::SourceLocation r_paren_loc = tokens_[pos_ - 1].getLocation();
+        clang::Expr* operand = ParseUnaryExpression();
+        if (operand == nullptr) return nullptr;
+        clang::ExprResult result = ictx_.sema_.BuildCStyleCastExpr(
+            token.getLocation(),
+            ictx_.ctx_.getTrivialTypeSourceInfo(*type, token.getLocation()),
+            r_paren_loc, operand);
+        return result.isUsable() ? result.get() : nullptr;
+      }
+      pos_ = l_paren_pos;
+      break;
+    }
+    default:
+      break;
+  }
+  return ParsePrimaryExpression();
+}
+
+clang::Expr* MacroImporter::ParsePrimaryExpression() {
+  if (pos_ >= tokens_.size()) return nullptr;
+  const clang::Token& token = tokens_[pos_++];
+  clang::ExprResult result;
+  switch (token.getKind()) {
+    case clang::tok::numeric_constant:
+      result = ictx_.sema_.ActOnNumericConstant(token);
+      break;
+    case clang::tok::char_constant:
+      result = ictx_.sema_.ActOnCharacterConstant(token);
+      break;
+    case clang::tok::kw_true:
+    case clang::tok::kw_false:
+      result = ictx_.sema_.ActOnCXXBoolLiteral(token.getLocation(),
+                                               token.getKind());
+      break;
+    case clang::tok::l_paren: {
+      clang::Expr* inner = ParseExpression(clang::prec::LogicalOr);
+      if (inner == nullptr || pos_ >= tokens_.size() ||
+          tokens_[pos_].isNot(clang::tok::r_paren)) {
+        return nullptr;
+      }
+      result = ictx_.sema_.ActOnParenExpr(
+          token.getLocation(), tokens_[pos_++].getLocation(), inner);
+      break;
+    }
+    default:
+      return nullptr;
+  }
+  return result.isUsable() ? result.get() : nullptr;
+}
+
+std::optional<clang::QualType> MacroImporter::ParseCastType() {
+  clang::ASTContext& ctx = ictx_.ctx_;
+  size_t start_pos = pos_;
+  std::optional<clang::QualType> type;
+  if (pos_ < tokens_.size() && tokens_[pos_].is(clang::tok::identifier)) {
+    // A typedef name at namespace scope, e.g. `uint32_t`.
+    for (clang::NamedDecl* decl : ctx.getTranslationUnitDecl()->lookup(
+             tokens_[pos_].getIdentifierInfo())) {
+      if (auto* typedef_decl = clang::dyn_cast<clang::TypedefNameDecl>(decl)) {
+        type = ctx.getTypedefType(typedef_decl);
+        ++pos_;
+        break;
+      }
+    }
+  } else {
+    // A builtin type spelled with keywords, e.g. `unsigned long`.
+    int num_long = 0;
+    bool is_unsigned = false;
+    bool is_signed = false;
+    std::optional<clang::tok::TokenKind> base;
+    for (; pos_ < tokens_.size(); ++pos_) {
+      clang::tok::TokenKind kind = tokens_[pos_].getKind();
+      if (kind == clang::tok::kw_long) {
+        ++num_long;
+      } else if (kind == clang::tok::kw_unsigned) {
+        is_unsigned = true;
+      } else if (kind == clang::tok::kw_signed) {
+        is_signed = true;
+      } else if (!base.has_value() &&
+                 (kind == clang::tok::kw_int || kind == clang::tok::kw_short ||
+                  kind == clang::tok::kw_char || kind == clang::tok::kw_bool ||
+                  kind == clang::tok::kw__Bool ||
+                  kind == clang::tok::kw_float ||
+                  kind == clang::tok::kw_double)) {
+        base = kind;
+      } else {
+        break;
+      }
+    }
+    clang::tok::TokenKind base_kind = base.value_or(clang::tok::kw_int);
+    bool is_integer = base_kind == clang::tok::kw_int ||
+                      base_kind == clang::tok::kw_short ||
+                      base_kind == clang::tok::kw_char;
+    if (pos_ == start_pos || (is_signed && is_unsigned) ||
+        ((is_signed || is_unsigned) && !is_integer) ||
+        (num_long > 0 && base_kind != clang::tok::kw_int &&
+         base_kind != clang::tok::kw_double)) {
+      pos_ = start_pos;
+      return std::nullopt;
+    }
+    switch (base_kind) {
+      case clang::tok::kw_bool:
+      case clang::tok::kw__Bool:
+        type = ctx.BoolTy;
+        break;
+      case clang::tok::kw_float:
+        type = ctx.FloatTy;
+        break;
+      case clang::tok::kw_double:
+        // `long double` isn't a supported constant type.
+        if (num_long == 0) type = ctx.DoubleTy;
+        break;
+      case clang::tok::kw_char:
+        type = is_unsigned ? ctx.UnsignedCharTy
+               : is_signed ? ctx.SignedCharTy
+                           : ctx.CharTy;
+        break;
+      case clang::tok::kw_short:
+        type = is_unsigned ? ctx.UnsignedShortTy : ctx.ShortTy;
+        break;
+      default:
+        if (num_long == 0) {
+          type = is_unsigned ? ctx.UnsignedIntTy : ctx.IntTy;
+        } else if (num_long == 1) {
+          type = is_unsigned ? ctx.UnsignedLongTy : ctx.LongTy;
+        } else if (num_long == 2) {
+          type = is_unsigned ? ctx.UnsignedLongLongTy : ctx.LongLongTy;
+        }
+        break;
+    }
+  }
+  if (!type.has_value() || pos_ >= tokens_.size() ||
+      tokens_[pos_].isNot(clang::tok::r_paren)) {
+    pos_ = start_pos;
+    return std::nullopt;
+  }
+  ++pos_;
+  return type;
+}
+
+}  // namespace crubit
@@ -0,0 +1,95 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_MACRO_H_
+#define CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_MACRO_H_
+
+#include <optional>
+#include <vector>
+
+#include "absl/container/flat_hash_set.h"
+#include "rs_bindings_from_cc/decl_importer.h"
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/Type.h"
+#include "clang/Basic/IdentifierTable.h"
+#include "clang/Lex/MacroInfo.h"
+#include "clang/Lex/Token.h"
+
+namespace crubit {
+
+// Imports macros:
+//
+// * Object-like macros whose replacement list is a constant expression (e.g.
+//   `#define MAX_PATH 4096`) are imported as `Constant`s.
+// * Function-like macros that expand to a call of a function, or to a cast, of
+//   their parameters (e.g. `#define htons(x) __builtin_bswap16(x)`) are
+//   imported as inline `Func`s, whose thunk expands the macro. Such macros
+//   that can't be imported (e.g. because the callee is overloaded) are
+//   imported as `UnsupportedItem`s. Other function-like macros are ignored.
+//
+// Unlike `DeclImporter`s, this doesn't import declarations: the caller picks
+// the macros of the current target.
+class MacroImporter {
+ public:
+  explicit MacroImporter(ImportContext& ictx) : ictx_(ictx) {}
+
+  // Returns the item for the macro `name`, or nullopt if it is not a candidate
+  // for bindings: an object-like macro whose replacement list isn't a
+  // supported constant expression, or a function-like macro that doesn't
+  // expand to a call or a cast.
+  std::optional<IR::Item> Import(const clang::IdentifierInfo& name,
+                                 const clang::MacroInfo& macro_info);
+
+ private:
+  // Returns the `Constant` for the object-like macro `name`, or nullopt if its
+  // replacement list isn't a supported constant expression.
+  //
+  // The replacement list may use integer, floating point, character and `bool`
+  // literals, other such macros, parentheses, casts to arithmetic types (which
+  // also give the constant its type) and the built-in unary and binary
+  // operators.
+  std::optional<Constant> ImportObjectLikeMacro(
+      const clang::IdentifierInfo& name, const clang::MacroInfo& macro_info);
+
+  // Returns the `Func` for the function-like macro `name`, or an
+  // `UnsupportedItem` that explains why a macro that expands to a call or a
+  // cast can't be imported. Returns nullopt if the macro is variadic, if it
+  // doesn't expand to a call or a cast (e.g. `#define SQUARE(x) ((x) * (x))`),
+  // or if it calls the function of the same name (e.g.
+  // `#define abs(x) abs(x)`), which has bindings of its own.
+  std::optional<IR::Item> ImportFunctionLikeMacro(
+      const clang::IdentifierInfo& name, const clang::MacroInfo& macro_info);
+
+  // Returns the function named `identifier` at namespace scope (which may be a
+  // builtin, e.g. `__builtin_bswap16`), or null if there is no such function,
+  // or if the name is overloaded.
+  const clang::FunctionDecl* LookupFunction(clang::IdentifierInfo* identifier,
+                                            clang::SourceLocation loc) const;
+
+  // Appends the replacement list of `macro_info` to `tokens`, expanding the
+  // object-like macros that it refers to. Returns false if the replacement
+  // list refers to a function-like macro, or (recursively) to itself.
+  bool ExpandTokens(const clang::MacroInfo& macro_info,
+                    absl::flat_hash_set<const clang::IdentifierInfo*>& visited,
+                    std::vector<clang::Token>& tokens) const;
+
+  // Parses the expression of `tokens_` starting at `pos_`, whose binary
+  // operators have at least the precedence `min_precedence`. Returns null if
+  // the tokens aren't a supported expression.
+  clang::Expr* ParseExpression(int min_precedence);
+  clang::Expr* ParseUnaryExpression();
+  clang::Expr* ParsePrimaryExpression();
+  // Parses the type of a cast (e.g. `unsigned long` or `uint32_t`), if the
+  // tokens at `pos_` spell an arithmetic type, followed by `)`.
+  std::optional<clang::QualType> ParseCastType();
+
+  ImportContext& ictx_;
+  std::vector<clang::Token> tokens_;
+  size_t pos_ = 0;
+};
+
+}  // namespace crubit
+
+#endif  // CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_MACRO_H_
@@ -0,0 +1,23 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "rs_bindings_from_cc/importers/objc.h"
+
+#include <optional>
+
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/DeclObjC.h"
+
+namespace crubit {
+
+std::optional<IR::Item> ObjCContainerDeclImporter::Import(
+    clang::ObjCContainerDecl* objc_container_decl) {
+  // Objective-C declarations only appear when a header is compiled as
+  // Objective-C++ (typically guarded by `#ifdef __OBJC__`). They are reported
+  // as unsupported so that the portable C++ declarations can still be bound.
+  return ictx_.ImportUnsupportedItem(
+      objc_container_decl, "Objective-C declarations are not supported");
+}
+
+}  // namespace crubit
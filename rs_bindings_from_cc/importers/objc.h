@@ -0,0 +1,27 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_OBJC_H_
+#define CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_OBJC_H_
+
+#include <optional>
+
+#include "rs_bindings_from_cc/decl_importer.h"
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/DeclObjC.h"
+
+namespace crubit {
+
+// A `DeclImporter` for Objective-C `ObjCContainerDecl`s (`@interface`,
+// `@protocol`, `@implementation`, categories).
+class ObjCContainerDeclImporter
+    : public DeclImporterBase<clang::ObjCContainerDecl> {
+ public:
+  explicit ObjCContainerDeclImporter(ImportContext& context)
+      : DeclImporterBase(context) {}
+  std::optional<IR::Item> Import(clang::ObjCContainerDecl*) override;
+};
+
+}  // namespace crubit
+#endif  // CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_OBJC_H_
@@ -10,6 +10,7 @@
 #include "absl/log/check.h"
 #include "absl/strings/str_cat.h"
 #include "lifetime_annotations/type_lifetimes.h"
+#include "rs_bindings_from_cc/ast_util.h"
 #include "rs_bindings_from_cc/ir.h"
 #include "clang/AST/ASTContext.h"
 #include "clang/AST/Decl.h"
@@ -31,11 +32,9 @@ std::optional<IR::Item> crubit::TypeAliasImporter::Import(
       return std::nullopt;
     }
     underlying_qualtype = typedef_name_decl->getUnderlyingType();
-    clang::QualType type =
-        decl->getASTContext().getTypedefType(typedef_name_decl);
-    if (const auto* tag_decl = type->getAsTagDecl();
-        tag_decl && tag_decl->getDeclContext() == decl_context &&
-        tag_decl->getName() == decl->getName()) {
+    if (GetTagDeclNamedByTypedef(typedef_name_decl) != nullptr) {
+      // Uses of the typedef refer to the tag declaration instead (see
+      // `Importer::ConvertTypeDecl`).
       return ictx_.ImportUnsupportedItem(
           decl, "Typedef only used to introduce a name in C. Not importing.");
     }
@@ -91,6 +90,7 @@ std::optional<IR::Item> crubit::TypeAliasImporter::Import(
       .id = GenerateItemId(decl),
       .owning_target = ictx_.GetOwningTarget(decl),
       .doc_comment = ictx_.GetComment(decl),
+      .deprecated = GetDeprecationMessage(decl),
       .underlying_type = *underlying_type,
       .source_loc = ictx_.ConvertSourceLocation(decl->getBeginLoc()),
       .enclosing_record_id = enclosing_record_id,
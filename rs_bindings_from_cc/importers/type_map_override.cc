@@ -13,6 +13,7 @@
 #include "absl/strings/str_cat.h"
 #include "absl/strings/string_view.h"
 #include "common/status_macros.h"
+#include "rs_bindings_from_cc/ast_util.h"
 #include "rs_bindings_from_cc/ir.h"
 #include "clang/AST/ASTContext.h"
 #include "clang/AST/Attr.h"
@@ -26,55 +27,6 @@
 namespace crubit {
 namespace {
 
-// Copied from lifetime_annotations/type_lifetimes.cc, which is expected to move
-// into ClangTidy. See:
-// https://discourse.llvm.org/t/rfc-lifetime-annotations-for-c/61377
-absl::StatusOr<absl::string_view> EvaluateAsStringLiteral(
-    const clang::Expr& expr, const clang::ASTContext& ast_context) {
-  auto error = []() {
-    return absl::InvalidArgumentError(
-        "cannot evaluate argument as a string literal");
-  };
-
-  clang::Expr::EvalResult eval_result;
-  if (!expr.EvaluateAsConstantExpr(eval_result, ast_context) ||
-      !eval_result.Val.isLValue()) {
-    return error();
-  }
-
-  const auto* eval_result_expr =
-      eval_result.Val.getLValueBase().dyn_cast<const clang::Expr*>();
-  if (!eval_result_expr) {
-    return error();
-  }
-
-  const auto* string_literal =
-      clang::dyn_cast<clang::StringLiteral>(eval_result_expr);
-  if (!string_literal) {
-    return error();
-  }
-
-  return {string_literal->getString()};
-}
-
-// Gets the requested attribute for `decl`.
-// `decl` must not be null.
-absl::StatusOr<const clang::AnnotateAttr*> GetAnnotateAttr(
-    const clang::Decl* decl, absl::string_view attribute) {
-  const clang::AnnotateAttr* found_attr = nullptr;
-  for (clang::AnnotateAttr* attr :
-       decl->specific_attrs<clang::AnnotateAttr>()) {
-    if (attr->getAnnotation() != llvm::StringRef(attribute)) continue;
-
-    if (found_attr != nullptr)
-      return absl::InvalidArgumentError(
-          absl::StrCat("Only one `", attribute,
-                       "` attribute may be placed on a declaration."));
-    found_attr = attr;
-  }
-  return found_attr;
-}
-
 // Gets the crubit_internal_rust_type attribute for `decl`.
 // `decl` must not be null.
 absl::StatusOr<std::optional<absl::string_view>> GetRustTypeAttribute(
@@ -115,6 +67,12 @@ std::optional<IR::Item> TypeMapOverrideImporter::Import(
         type_decl, absl::StrCat("Invalid crubit_internal_rust_type attribute: ",
                                 rust_type.status().message()));
   }
+  if (!rust_type->has_value()) {
+    // Types without the attribute may still be bound by an existing Rust type
+    // given on the command line (see `--extern_rust_types`).
+    *rust_type = ictx_.invocation_.extern_rust_type(
+        type_decl->getQualifiedNameAsString());
+  }
   if (!rust_type->has_value()) {
     return std::nullopt;
   }
@@ -0,0 +1,130 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "rs_bindings_from_cc/importers/var.h"
+
+#include <optional>
+#include <string>
+#include <variant>
+
+#include "absl/status/statusor.h"
+#include "absl/strings/str_cat.h"
+#include "lifetime_annotations/type_lifetimes.h"
+#include "rs_bindings_from_cc/ast_util.h"
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/APValue.h"
+#include "clang/AST/Decl.h"
+#include "clang/AST/DeclTemplate.h"
+#include "clang/AST/Expr.h"
+#include "clang/AST/Type.h"
+#include "clang/Basic/LLVM.h"
+
+namespace crubit {
+
+// Returns the value of the initializer of `var_decl` (which may be on another
+// redeclaration), or `std::nullopt` if it can't be evaluated at compile time
+// (or can't be represented in the IR).
+static std::optional<std::variant<IntegerConstant, FloatConstant>>
+EvaluateConstant(const clang::VarDecl* var_decl) {
+  const clang::VarDecl* initializing_decl =
+      var_decl->getInitializingDeclaration();
+  if (initializing_decl == nullptr ||
+      initializing_decl->getInit()->isValueDependent()) {
+    return std::nullopt;
+  }
+  const clang::APValue* value = initializing_decl->evaluateValue();
+  if (value == nullptr) return std::nullopt;
+  if (value->isInt() && value->getInt().getSignificantBits() <= 64) {
+    return IntegerConstant(value->getInt());
+  }
+  if (value->isFloat() && value->getFloat().isFinite()) {
+    return FloatConstant(value->getFloat());
+  }
+  return std::nullopt;
+}
+
+std::optional<IR::Item> VarDeclImporter::Import(clang::VarDecl* var_decl) {
+  auto* record_decl =
+      clang::dyn_cast<clang::CXXRecordDecl>(var_decl->getDeclContext());
+  if (!var_decl->getDeclContext()->isFileContext() && record_decl == nullptr) {
+    return std::nullopt;
+  }
+  if (var_decl->isTemplated() ||
+      clang::isa<clang::VarTemplateSpecializationDecl>(var_decl)) {
+    return std::nullopt;
+  }
+  clang::QualType type = var_decl->getType();
+  bool is_constant = type.isConstQualified() && IsSupportedConstantType(type);
+  // TODO: Support namespace-scope variables that aren't constants.
+  if (record_decl == nullptr && !is_constant) return std::nullopt;
+
+  std::optional<ItemId> enclosing_record_id = std::nullopt;
+  if (record_decl != nullptr) {
+    if (!ictx_.EnsureSuccessfullyImported(record_decl)) {
+      return ictx_.ImportUnsupportedItem(var_decl,
+                                         "Couldn't import the parent");
+    }
+    enclosing_record_id = GenerateItemId(record_decl);
+  }
+
+  absl::StatusOr<Identifier> identifier =
+      ictx_.GetTranslatedIdentifier(var_decl);
+  if (!identifier.ok()) {
+    return ictx_.ImportUnsupportedItem(
+        var_decl, absl::StrCat("Variable name is not supported: ",
+                               identifier.status().message()));
+  }
+
+  const clang::tidy::lifetimes::ValueLifetimes* no_lifetimes = nullptr;
+  absl::StatusOr<MappedType> mapped_type =
+      ictx_.ConvertQualType(type, no_lifetimes, std::nullopt);
+  if (!mapped_type.ok()) {
+    return ictx_.ImportUnsupportedItem(
+        var_decl, std::string(mapped_type.status().message()));
+  }
+
+  std::optional<std::variant<IntegerConstant, FloatConstant>> value =
+      is_constant ? EvaluateConstant(var_decl) : std::nullopt;
+  if (value.has_value()) {
+    return Constant{
+        .identifier = *std::move(identifier),
+        .id = GenerateItemId(var_decl),
+        .owning_target = ictx_.GetOwningTarget(var_decl),
+        .doc_comment = ictx_.GetComment(var_decl),
+        .type = *std::move(mapped_type),
+        .value = *std::move(value),
+        .source_loc = ictx_.ConvertSourceLocation(var_decl->getBeginLoc()),
+        .enclosing_record_id = enclosing_record_id,
+        .enclosing_namespace_id = GetEnclosingNamespaceId(var_decl),
+    };
+  }
+  if (record_decl == nullptr) {
+    return ictx_.ImportUnsupportedItem(
+        var_decl, "The value of the constant is not known at compile time");
+  }
+
+  // Other static data members are accessed through a thunk that returns a
+  // pointer to them.
+  if (IsFullClassTemplateSpecializationOrChild(var_decl)) {
+    // TODO: Support static data members of class template specializations,
+    // whose thunks need to be unique across targets.
+    return ictx_.ImportUnsupportedItem(
+        var_decl,
+        "Static data members of class template specializations are not "
+        "supported yet");
+  }
+  return GlobalVar{
+      .identifier = *std::move(identifier),
+      .id = GenerateItemId(var_decl),
+      .owning_target = ictx_.GetOwningTarget(var_decl),
+      .doc_comment = ictx_.GetComment(var_decl),
+      .mangled_name = ictx_.GetMangledName(var_decl),
+      .type = *std::move(mapped_type),
+      .source_loc = ictx_.ConvertSourceLocation(var_decl->getBeginLoc()),
+      .enclosing_record_id = enclosing_record_id,
+      .enclosing_namespace_id = GetEnclosingNamespaceId(var_decl),
+  };
+}
+
+}  // namespace crubit
@@ -0,0 +1,27 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_VAR_H_
+#define CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_VAR_H_
+
+#include <optional>
+
+#include "rs_bindings_from_cc/decl_importer.h"
+#include "rs_bindings_from_cc/ir.h"
+#include "clang/AST/Decl.h"
+
+namespace crubit {
+
+// A `DeclImporter` for `VarDecl`s: namespace-scope constants (e.g.
+// `inline constexpr int kMaxSize = 42;`) and static data members.
+class VarDeclImporter : public DeclImporterBase<clang::VarDecl> {
+ public:
+  explicit VarDeclImporter(ImportContext& context)
+      : DeclImporterBase(context) {}
+  std::optional<IR::Item> Import(clang::VarDecl* var_decl) override;
+};
+
+}  // namespace crubit
+
+#endif  // CRUBIT_RS_BINDINGS_FROM_CC_IMPORTERS_VAR_H_
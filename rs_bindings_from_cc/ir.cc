@@ -72,6 +72,7 @@ llvm::json::Value CcType::ToJson() const {
       {"name", decl_id.has_value() ? llvm::json::Value(nullptr)
                                    : llvm::json::Value(name)},
       {"is_const", is_const},
+      {"is_volatile", is_volatile},
       {"type_args", type_args},
       {"decl_id", decl_id},
   };
@@ -202,6 +203,15 @@ MappedType MappedType::FuncRef(absl::string_view cc_call_conv,
   };
 }
 
+MappedType MappedType::ArrayOf(MappedType element_type, uint64_t size) {
+  return MappedType{
+      .rs_type = RsType{.name = absl::StrCat(internal::kRustArray, " ", size),
+                        .type_args = {std::move(element_type.rs_type)}},
+      .cc_type = CcType{.name = absl::StrCat(internal::kCcArray, " ", size),
+                        .type_args = {std::move(element_type.cc_type)}},
+  };
+}
+
 llvm::json::Value MappedType::ToJson() const {
   return llvm::json::Object{
       {"rs_type", rs_type},
@@ -222,6 +232,8 @@ llvm::json::Value IntegerConstant::ToJson() const {
   };
 }
 
+llvm::json::Value FloatConstant::ToJson() const { return literal_; }
+
 llvm::json::Value Operator::ToJson() const {
   return llvm::json::Object{
       {"name", name_},
@@ -234,6 +246,8 @@ static std::string SpecialNameToString(SpecialName special_name) {
       return "Destructor";
     case SpecialName::kConstructor:
       return "Constructor";
+    case SpecialName::kConversion:
+      return "Conversion";
   }
 }
 
@@ -258,6 +272,7 @@ llvm::json::Value FuncParam::ToJson() const {
   return llvm::json::Object{
       {"type", type},
       {"identifier", identifier},
+      {"has_default_value", has_default_value},
   };
 }
 
@@ -283,6 +298,7 @@ llvm::json::Value MemberFuncMetadata::InstanceMethodMetadata::ToJson() const {
       {"reference", reference_str},
       {"is_const", is_const},
       {"is_virtual", is_virtual},
+      {"is_pure_virtual", is_pure_virtual},
   };
 }
 
@@ -327,11 +343,15 @@ llvm::json::Value Func::ToJson() const {
       {"name", name},
       {"owning_target", owning_target},
       {"doc_comment", doc_comment},
+      {"deprecated", deprecated},
       {"mangled_name", mangled_name},
       {"return_type", return_type},
       {"params", params},
       {"lifetime_params", lifetime_params},
       {"is_inline", is_inline},
+      {"is_noexcept", is_noexcept},
+      {"may_throw", may_throw},
+      {"is_variadic", is_variadic},
       {"member_func_metadata", member_func_metadata},
       {"has_c_calling_convention", has_c_calling_convention},
       {"is_member_or_descendant_of_class_template",
@@ -340,6 +360,10 @@ llvm::json::Value Func::ToJson() const {
       {"id", id},
       {"enclosing_namespace_id", enclosing_namespace_id},
       {"adl_enclosing_record", adl_enclosing_record},
+      {"sets_errno", sets_errno},
+      {"converts_long_double", converts_long_double},
+      {"template_args", template_args},
+      {"rust_name", rust_name},
   };
 
   return llvm::json::Object{
@@ -366,6 +390,7 @@ llvm::json::Value Field::ToJson() const {
   return llvm::json::Object{
       {"identifier", identifier},
       {"doc_comment", doc_comment},
+      {"deprecated", deprecated},
       {"type", type},
       {"access", AccessToString(access)},
       {"offset", offset},
@@ -448,6 +473,7 @@ llvm::json::Value Record::ToJson() const {
       {"owning_target", owning_target},
       {"defining_target", defining_target},
       {"doc_comment", doc_comment},
+      {"deprecated", deprecated},
       {"source_loc", source_loc},
       {"unambiguous_public_bases", unambiguous_public_bases},
       {"fields", fields},
@@ -459,11 +485,18 @@ llvm::json::Value Record::ToJson() const {
       {"move_constructor", move_constructor},
       {"destructor", destructor},
       {"is_trivial_abi", is_trivial_abi},
+      {"is_rust_movable", is_rust_movable},
+      {"is_send", is_send},
+      {"is_sync", is_sync},
+      {"is_extensible", is_extensible},
+      {"has_stream_insertion_operator", has_stream_insertion_operator},
       {"is_inheritable", is_inheritable},
       {"is_abstract", is_abstract},
+      {"has_non_public_pure_virtual", has_non_public_pure_virtual},
       {"record_type", RecordTypeToString(record_type)},
       {"is_aggregate", is_aggregate},
       {"is_anon_record_with_typedef", is_anon_record_with_typedef},
+      {"is_anon_nested_record", is_anon_nested_record},
       {"child_item_ids", std::move(json_item_ids)},
       {"enclosing_namespace_id", enclosing_namespace_id},
   };
@@ -476,6 +509,7 @@ llvm::json::Value Record::ToJson() const {
 llvm::json::Value Enumerator::ToJson() const {
   return llvm::json::Object{
       {"identifier", identifier},
+      {"doc_comment", doc_comment},
       {"value", value},
   };
 }
@@ -485,6 +519,8 @@ llvm::json::Value Enum::ToJson() const {
       {"identifier", identifier},
       {"id", id},
       {"owning_target", owning_target},
+      {"doc_comment", doc_comment},
+      {"deprecated", deprecated},
       {"source_loc", source_loc},
       {"underlying_type", underlying_type},
       {"enumerators", enumerators},
@@ -502,6 +538,7 @@ llvm::json::Value TypeAlias::ToJson() const {
       {"id", id},
       {"owning_target", owning_target},
       {"doc_comment", doc_comment},
+      {"deprecated", deprecated},
       {"underlying_type", underlying_type},
       {"source_loc", source_loc},
       {"enclosing_record_id", enclosing_record_id},
@@ -513,6 +550,48 @@ llvm::json::Value TypeAlias::ToJson() const {
   };
 }
 
+llvm::json::Value Constant::ToJson() const {
+  llvm::json::Object value_json;
+  if (const auto* integer = std::get_if<IntegerConstant>(&value)) {
+    value_json["Integer"] = *integer;
+  } else {
+    value_json["Float"] = std::get<FloatConstant>(value);
+  }
+  llvm::json::Object constant{
+      {"identifier", identifier},
+      {"id", id},
+      {"owning_target", owning_target},
+      {"doc_comment", doc_comment},
+      {"type", type},
+      {"value", std::move(value_json)},
+      {"source_loc", source_loc},
+      {"enclosing_record_id", enclosing_record_id},
+      {"enclosing_namespace_id", enclosing_namespace_id},
+  };
+
+  return llvm::json::Object{
+      {"Constant", std::move(constant)},
+  };
+}
+
+llvm::json::Value GlobalVar::ToJson() const {
+  llvm::json::Object global_var{
+      {"identifier", identifier},
+      {"id", id},
+      {"owning_target", owning_target},
+      {"doc_comment", doc_comment},
+      {"mangled_name", mangled_name},
+      {"type", type},
+      {"source_loc", source_loc},
+      {"enclosing_record_id", enclosing_record_id},
+      {"enclosing_namespace_id", enclosing_namespace_id},
+  };
+
+  return llvm::json::Object{
+      {"GlobalVar", std::move(global_var)},
+  };
+}
+
 llvm::json::Value UnsupportedItem::ToJson() const {
   llvm::json::Object unsupported{
       {"name", name},
@@ -589,9 +668,25 @@ llvm::json::Value IR::ToJson() const {
       {"top_level_item_ids", std::move(top_level_ids)},
       {"crubit_features", std::move(features_json)},
   };
+  if (!forced_includes.empty()) {
+    result["forced_includes"] = forced_includes;
+  }
+  if (!macro_definitions.empty()) {
+    result["macro_definitions"] = macro_definitions;
+  }
   if (!crate_root_path.empty()) {
     result["crate_root_path"] = crate_root_path;
   }
+  if (!split_namespaces.empty()) {
+    llvm::json::Object split_namespaces_json;
+    for (const auto& [namespace_name, crate_name] : split_namespaces) {
+      split_namespaces_json[namespace_name] = crate_name;
+    }
+    result["split_namespaces"] = std::move(split_namespaces_json);
+  }
+  if (!split_namespace_to_generate.empty()) {
+    result["split_namespace_to_generate"] = split_namespace_to_generate;
+  }
   return std::move(result);
 }
 
@@ -34,7 +34,10 @@
 #include "clang/AST/RawCommentList.h"
 #include "clang/AST/Type.h"
 #include "clang/Basic/LLVM.h"
+#include "clang/Lex/MacroInfo.h"
+#include "llvm/ADT/APFloat.h"
 #include "llvm/ADT/APSInt.h"
+#include "llvm/ADT/SmallString.h"
 #include "llvm/Support/FormatVariadic.h"
 #include "llvm/Support/JSON.h"
 
@@ -55,11 +58,15 @@ inline constexpr absl::string_view kRustRvalueRefConst =
 // Function pointers.
 inline constexpr absl::string_view kRustFuncPtr = "#funcPtr";
 
+// Arrays.
+inline constexpr absl::string_view kRustArray = "#array";
+
 // C++ types therein.
 inline constexpr absl::string_view kCcPtr = "*";
 inline constexpr absl::string_view kCcLValueRef = "&";
 inline constexpr absl::string_view kCcRValueRef = "&&";
 inline constexpr absl::string_view kCcFuncValue = "#funcValue";
+inline constexpr absl::string_view kCcArray = "#array";
 
 inline constexpr int kJsonIndent = 2;
 }  // namespace internal
@@ -110,6 +117,10 @@ inline ItemId GenerateItemId(const clang::RawComment* comment) {
   return ItemId(reinterpret_cast<uintptr_t>(comment));
 }
 
+inline ItemId GenerateItemId(const clang::MacroInfo* macro_info) {
+  return ItemId(reinterpret_cast<uintptr_t>(macro_info));
+}
+
 // Returns the ID of the parent namespace, if such exists, and `std::nullopt`
 // for top level decls. We use this function to assign a parent namespace to all
 // the IR items.
@@ -160,6 +171,7 @@ struct CcType {
   // - "#funcValue <callConv>" (compare with "#funcPtr <abi>" in RsType::name
   //   and note that Rust only supports function pointers; note that <callConv>
   //   in CcType doesn't map 1:1 to <abi> in RsType).
+  // - "#array <size>" (element type stored in `type_args[0]`)
   // - An empty string when `decl_id` is non-empty.
   std::string name;
 
@@ -175,6 +187,11 @@ struct CcType {
   // all the same type in C++.
   bool is_const = false;
 
+  // The C++ volatile-qualification for the type. This is only supported for
+  // the pointee of a pointer or reference, which is then bound as a raw
+  // pointer.
+  bool is_volatile = false;
+
   // Type arguments for a generic type. Examples:
   //   int has no type arguments.
   //   int* has a single type argument, int.
@@ -198,6 +215,7 @@ struct RsType {
   //   `type_args`; param types are stored in other `type_args`; <abi> would be
   //   replaced with "cdecl", "stdcall" or other Abi - see
   //   https://doc.rust-lang.org/reference/types/function-pointer.html);
+  // - "#array <size>" (element type stored in `type_args[0]`)
   // - An empty string when `decl_id` is non-empty.
   std::string name;
 
@@ -273,6 +291,10 @@ struct MappedType {
                             MappedType return_type,
                             std::vector<MappedType> param_types);
 
+  // Creates the mapped type for a C++ array of `size` elements, which is
+  // bound as a Rust array (`[T; size]`).
+  static MappedType ArrayOf(MappedType element_type, uint64_t size);
+
   bool IsVoid() const { return rs_type.name == "()"; }
 
   llvm::json::Value ToJson() const;
@@ -346,6 +368,28 @@ class IntegerConstant {
   uint64_t wrapped_value_;
 };
 
+// A finite floating point value. This is intended to be used to produce
+// floating point literals in Rust code while specifying the type out-of-band.
+class FloatConstant {
+ public:
+  explicit FloatConstant(const llvm::APFloat& value) {
+    CHECK(value.isFinite());
+    // With the default precision, the literal has enough digits to convert
+    // back to the same value.
+    llvm::SmallString<32> literal;
+    value.toString(literal, /*FormatPrecision=*/0, /*FormatMaxPadding=*/0);
+    literal_ = std::string(literal);
+  }
+  FloatConstant(const FloatConstant& other) = default;
+  FloatConstant& operator=(const FloatConstant& other) = default;
+
+  llvm::json::Value ToJson() const;
+
+ private:
+  // A decimal literal, e.g. `4.2E+1`.
+  std::string literal_;
+};
+
 class Operator {
  public:
   explicit Operator(std::string name) : name_(std::move(name)) {
@@ -377,6 +421,8 @@ struct FuncParam {
 
   MappedType type;
   Identifier identifier;
+  // Whether the parameter has a default argument (e.g. `int x = 0`).
+  bool has_default_value = false;
 };
 
 inline std::ostream& operator<<(std::ostream& o, const FuncParam& param) {
@@ -386,6 +432,9 @@ inline std::ostream& operator<<(std::ostream& o, const FuncParam& param) {
 enum SpecialName {
   kDestructor,
   kConstructor,
+  // A conversion function, e.g. `operator int()`. The target type is the
+  // return type of the function.
+  kConversion,
 };
 
 std::ostream& operator<<(std::ostream& o, const SpecialName& special_name);
@@ -415,6 +464,7 @@ struct MemberFuncMetadata {
     ReferenceQualification reference = kUnqualified;
     bool is_const = false;
     bool is_virtual = false;
+    bool is_pure_virtual = false;
   };
 
   llvm::json::Value ToJson() const;
@@ -428,6 +478,32 @@ struct MemberFuncMetadata {
   std::optional<InstanceMethodMetadata> instance_method_metadata;
 };
 
+// How `long double` is bound (see `--long_double`).
+enum class LongDoublePolicy {
+  // Declarations that use `long double` don't get bindings.
+  kSkip,
+  // `long double` is bound as the opaque `LongDouble` type from the
+  // `long_double` support crate, which has the same size and alignment.
+  kOpaque,
+  // Parameters and return values of type `long double` are bound as `f64`,
+  // and converted from and to `double` by the C++ thunk. Other uses of
+  // `long double` (e.g. fields or pointees) are not supported.
+  kF64,
+};
+
+// How plain `char` is bound (see `--char_mapping`).
+enum class CharMapping {
+  // `i8` on targets where `char` is signed, and `u8` where it is unsigned.
+  kNative,
+  // `u8`, regardless of the signedness of `char`.
+  kU8,
+  // `i8`, regardless of the signedness of `char`.
+  kI8,
+  // `::core::ffi::c_char`, which has the signedness of `char` on the target
+  // that the Rust code is compiled for.
+  kCChar,
+};
+
 // A function involved in the bindings.
 struct Func {
   llvm::json::Value ToJson() const;
@@ -435,11 +511,21 @@ struct Func {
   UnqualifiedIdentifier name;
   BazelLabel owning_target;
   std::optional<std::string> doc_comment;
+  // The message of the `[[deprecated]]` attribute (empty if it has none), or
+  // nullopt if the declaration isn't deprecated.
+  std::optional<std::string> deprecated;
   std::string mangled_name;
   MappedType return_type;
   std::vector<FuncParam> params;
   std::vector<LifetimeName> lifetime_params;
   bool is_inline;
+  // Whether the function is declared as non-throwing (e.g. `noexcept`).
+  bool is_noexcept = false;
+  // Whether the function may throw an exception, i.e. it isn't `noexcept` and
+  // the code is compiled with exceptions (no `-fno-exceptions`).
+  bool may_throw = true;
+  // Whether the function is a C variadic function (e.g. `f(int, ...)`).
+  bool is_variadic = false;
   // If null, this is not a member function.
   std::optional<MemberFuncMetadata> member_func_metadata;
   bool has_c_calling_convention = true;
@@ -454,6 +540,19 @@ struct Func {
   // Rust type modeling in src_code_gen makes it much easier to do on the
   // consuming end.
   std::optional<ItemId> adl_enclosing_record;
+  // Whether the function returns -1 (or `NULL`) and sets `errno` on failure
+  // (see `--errno_functions`).
+  bool sets_errno = false;
+  // Whether parameters or the return value of type `long double` are bound as
+  // `double`, and converted by the C++ thunk (see `LongDoublePolicy::kF64`).
+  bool converts_long_double = false;
+  // The template argument list of a function template specialization, as
+  // spelled in C++ (e.g. `<int>`). Absent for functions that are not template
+  // specializations.
+  std::optional<std::string> template_args;
+  // The Rust name given by the `crubit_rust_name` attribute, which overrides
+  // the name that would be derived from `name`.
+  std::optional<std::string> rust_name;
 };
 
 inline std::ostream& operator<<(std::ostream& o, const Func& f) {
@@ -479,6 +578,9 @@ struct Field {
   std::optional<Identifier> identifier;
 
   std::optional<std::string> doc_comment;
+  // The message of the `[[deprecated]]` attribute (empty if it has none), or
+  // nullopt if the declaration isn't deprecated.
+  std::optional<std::string> deprecated;
   absl::StatusOr<MappedType> type;
   AccessSpecifier access;
   uint64_t offset;            // Field offset in bits.
@@ -569,6 +671,9 @@ struct Record {
   BazelLabel owning_target;
   std::optional<BazelLabel> defining_target;
   std::optional<std::string> doc_comment;
+  // The message of the `[[deprecated]]` attribute (empty if it has none), or
+  // nullopt if the declaration isn't deprecated.
+  std::optional<std::string> deprecated;
   std::string source_loc;
   std::vector<BaseClass> unambiguous_public_bases;
   std::vector<Field> fields;
@@ -603,6 +708,27 @@ struct Record {
   //  * https://clang.llvm.org/docs/AttributeReference.html#trivial-abi
   bool is_trivial_abi = false;
 
+  // Whether this type was annotated with `CRUBIT_RUST_MOVABLE`, i.e. its author
+  // guarantees that it can be moved by `memcpy` even if it is not trivially
+  // relocatable.
+  bool is_rust_movable = false;
+
+  // Whether this type was annotated with `CRUBIT_SEND` (true) or
+  // `CRUBIT_NOT_SEND` (false). If unset, Rust's auto trait rules apply.
+  std::optional<bool> is_send;
+
+  // Whether this type was annotated with `CRUBIT_SYNC` (true) or
+  // `CRUBIT_NOT_SYNC` (false). If unset, Rust's auto trait rules apply.
+  std::optional<bool> is_sync;
+
+  // Whether this type was annotated with `CRUBIT_EXTENSIBLE`, i.e. Rust types
+  // may implement its virtual member functions.
+  bool is_extensible = false;
+
+  // Whether `operator<<(std::ostream&, const T&)` is declared for this type,
+  // either in its enclosing namespace or as a friend.
+  bool has_stream_insertion_operator = false;
+
   // Whether this type can be inherited from.
   //
   // A type might not be inheritable if:
@@ -614,6 +740,10 @@ struct Record {
   // Whether this type is abstract.
   bool is_abstract = false;
 
+  // Whether this type has a pure virtual member function that is not public,
+  // either declared in it or inherited and not overridden.
+  bool has_non_public_pure_virtual = false;
+
   // Whether this `Record` corresponds to a C++ `union`, `struct`, or `class`.
   RecordType record_type;
 
@@ -627,6 +757,11 @@ struct Record {
   // It is an anoymous record with a typedef name.
   bool is_anon_record_with_typedef = false;
 
+  // It is an anonymous record (without a typedef name) nested in another
+  // record, e.g. an anonymous struct or union member. Such records get a
+  // synthesized name, and their type can't be spelled in C++.
+  bool is_anon_nested_record = false;
+
   // True when this record is created from an explicit class template
   // instantiation definition (which is also what cc_template!{} macro results
   // in).
@@ -651,6 +786,7 @@ struct Enumerator {
   llvm::json::Value ToJson() const;
 
   Identifier identifier;
+  std::optional<std::string> doc_comment;
   IntegerConstant value;
 };
 
@@ -660,6 +796,10 @@ struct Enum {
   Identifier identifier;
   ItemId id;
   BazelLabel owning_target;
+  std::optional<std::string> doc_comment;
+  // The message of the `[[deprecated]]` attribute (empty if it has none), or
+  // nullopt if the declaration isn't deprecated.
+  std::optional<std::string> deprecated;
   std::string source_loc;
   MappedType underlying_type;
   std::vector<Enumerator> enumerators;
@@ -678,6 +818,9 @@ struct TypeAlias {
   ItemId id;
   BazelLabel owning_target;
   std::optional<std::string> doc_comment;
+  // The message of the `[[deprecated]]` attribute (empty if it has none), or
+  // nullopt if the declaration isn't deprecated.
+  std::optional<std::string> deprecated;
   MappedType underlying_type;
   std::string source_loc;
   std::optional<ItemId> enclosing_record_id;
@@ -688,6 +831,49 @@ inline std::ostream& operator<<(std::ostream& o, const TypeAlias& t) {
   return o << std::string(llvm::formatv("{0:2}", t.ToJson()));
 }
 
+// A constant (e.g. `inline constexpr int kMaxSize = 42;`) of integer, floating
+// point, `bool` or enum type, whose value is known at compile time. Static
+// data members can be constants, too.
+struct Constant {
+  llvm::json::Value ToJson() const;
+
+  Identifier identifier;
+  ItemId id;
+  BazelLabel owning_target;
+  std::optional<std::string> doc_comment;
+  MappedType type;
+  // Integers also represent the values of `bool` and enum constants.
+  std::variant<IntegerConstant, FloatConstant> value;
+  std::string source_loc;
+  std::optional<ItemId> enclosing_record_id;
+  std::optional<ItemId> enclosing_namespace_id;
+};
+
+inline std::ostream& operator<<(std::ostream& o, const Constant& c) {
+  return o << std::string(llvm::formatv("{0:2}", c.ToJson()));
+}
+
+// A variable with static storage duration that isn't a `Constant` (e.g.
+// `static int count;` in a class). It is accessed from Rust through a pointer
+// that a thunk returns.
+struct GlobalVar {
+  llvm::json::Value ToJson() const;
+
+  Identifier identifier;
+  ItemId id;
+  BazelLabel owning_target;
+  std::optional<std::string> doc_comment;
+  std::string mangled_name;
+  MappedType type;
+  std::string source_loc;
+  std::optional<ItemId> enclosing_record_id;
+  std::optional<ItemId> enclosing_namespace_id;
+};
+
+inline std::ostream& operator<<(std::ostream& o, const GlobalVar& v) {
+  return o << std::string(llvm::formatv("{0:2}", v.ToJson()));
+}
+
 // A placeholder for an item that we can't generate bindings for (yet)
 struct UnsupportedItem {
   llvm::json::Value ToJson() const;
@@ -796,11 +982,22 @@ struct IR {
   // preserved.
   std::vector<HeaderName> public_headers;
 
+  // Headers that were included before the `public_headers` (see
+  // `--forced_includes`). The generated C++ code includes them as well.
+  std::vector<HeaderName> forced_includes;
+  // Macros that were predefined, in the form `NAME` or `NAME=VALUE` (see
+  // `--macro_definitions`). The generated C++ code defines them as well.
+  std::vector<std::string> macro_definitions;
+  // Paths of the non-system files that were read to build the AST, i.e. the
+  // headers and the headers that they include, transitively. Only used by
+  // `--watch`, and not serialized.
+  std::vector<std::string> input_files;
+
   BazelLabel current_target;
 
   using Item = std::variant<Func, Record, IncompleteRecord, Enum, TypeAlias,
-                            UnsupportedItem, Comment, Namespace, UseMod,
-                            TypeMapOverride>;
+                            Constant, GlobalVar, UnsupportedItem, Comment,
+                            Namespace, UseMod, TypeMapOverride>;
   std::vector<Item> items;
   std::vector<ItemId> top_level_item_ids;
   // Empty string signals that the bindings should be generated in the crate
@@ -817,6 +1014,16 @@ struct IR {
   // throughout the codebase
   std::string crate_root_path;
 
+  // Top-level namespaces whose bindings are generated into separate crates,
+  // mapped to the names of these crates.
+  absl::flat_hash_map<std::string, std::string> split_namespaces;
+  // Empty string signals that the bindings for the target itself should be
+  // generated (re-exporting the bindings of `split_namespaces`).
+  //
+  // Non-empty value is one of the keys of `split_namespaces`, and signals that
+  // only the bindings of that namespace should be generated.
+  std::string split_namespace_to_generate;
+
   absl::flat_hash_map<BazelLabel, absl::flat_hash_set<std::string>>
       crubit_features;
 };
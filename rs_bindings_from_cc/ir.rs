@@ -66,6 +66,8 @@ where
 {
     make_ir(FlatIR {
         public_headers,
+        forced_includes: vec![],
+        macro_definitions: vec![],
         current_target,
         items,
         top_level_item_ids,
@@ -74,6 +76,8 @@ where
             .into_iter()
             .map(|(label, features)| (label, CrubitFeaturesIR(features.into())))
             .collect(),
+        split_namespaces: HashMap::new(),
+        split_namespace_to_generate: None,
     })
 }
 
@@ -193,6 +197,7 @@ impl RsType {
 pub struct CcType {
     pub name: Option<Rc<str>>,
     pub is_const: bool,
+    pub is_volatile: bool,
     pub type_args: Vec<CcType>,
     pub decl_id: Option<ItemId>,
 }
@@ -319,6 +324,9 @@ pub enum UnqualifiedIdentifier {
     Operator(Operator),
     Constructor,
     Destructor,
+    /// A conversion function, e.g. `operator int()`. The target type is the
+    /// return type of the function.
+    Conversion,
 }
 
 impl UnqualifiedIdentifier {
@@ -337,6 +345,7 @@ impl Debug for UnqualifiedIdentifier {
             UnqualifiedIdentifier::Operator(op) => Debug::fmt(op, f),
             UnqualifiedIdentifier::Constructor => f.write_str("Constructor"),
             UnqualifiedIdentifier::Destructor => f.write_str("Destructor"),
+            UnqualifiedIdentifier::Conversion => f.write_str("Conversion"),
         }
     }
 }
@@ -354,6 +363,7 @@ pub struct InstanceMethodMetadata {
     pub reference: ReferenceQualification,
     pub is_const: bool,
     pub is_virtual: bool,
+    pub is_pure_virtual: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
@@ -369,6 +379,7 @@ pub struct FuncParam {
     #[serde(rename(deserialize = "type"))]
     pub type_: MappedType,
     pub identifier: Identifier,
+    pub has_default_value: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
@@ -378,6 +389,9 @@ pub struct Func {
     pub owning_target: BazelLabel,
     pub mangled_name: Rc<str>,
     pub doc_comment: Option<Rc<str>>,
+    /// The message of the `[[deprecated]]` attribute (empty if it has none), or
+    /// `None` if the declaration isn't deprecated.
+    pub deprecated: Option<Rc<str>>,
     pub return_type: MappedType,
     pub params: Vec<FuncParam>,
     /// For tests and internal use only.
@@ -387,6 +401,12 @@ pub struct Func {
     /// not originally part of the IR.
     pub lifetime_params: Vec<LifetimeName>,
     pub is_inline: bool,
+    pub is_noexcept: bool,
+    /// Whether the function may throw an exception, i.e. it isn't `noexcept`
+    /// and the code is compiled with exceptions (no `-fno-exceptions`).
+    pub may_throw: bool,
+    /// Whether the function is a C variadic function (e.g. `f(int, ...)`).
+    pub is_variadic: bool,
     pub member_func_metadata: Option<MemberFuncMetadata>,
     pub has_c_calling_convention: bool,
     pub is_member_or_descendant_of_class_template: bool,
@@ -394,6 +414,24 @@ pub struct Func {
     pub id: ItemId,
     pub enclosing_namespace_id: Option<ItemId>,
     pub adl_enclosing_record: Option<ItemId>,
+    /// Whether the function returns -1 (or `NULL`) and sets `errno` on failure
+    /// (see `--errno_functions`).
+    pub sets_errno: bool,
+    /// Whether parameters or the return value of type `long double` are bound
+    /// as `f64`, and converted by the C++ thunk (see `--long_double`).
+    pub converts_long_double: bool,
+    /// The template argument list of a function template specialization, as
+    /// spelled in C++ (e.g. `<int>`).
+    pub template_args: Option<Rc<str>>,
+    /// The Rust name given by the `crubit_rust_name` attribute, which
+    /// overrides the name that would be derived from `name`.
+    pub rust_name: Option<Rc<str>>,
+    /// The trailing parameters with default values that were dropped from
+    /// `params` - see `Func::with_omitted_default_args`.
+    ///
+    /// Always empty for the functions in the IR received from C++.
+    #[serde(skip)]
+    pub omitted_default_params: Vec<FuncParam>,
 }
 
 impl GenericItem for Func {
@@ -405,7 +443,9 @@ impl GenericItem for Func {
         let record: Option<&str> = record.as_deref();
 
         let func_name = match &self.name {
-            UnqualifiedIdentifier::Identifier(id) => id.identifier.to_string(),
+            UnqualifiedIdentifier::Identifier(id) => {
+                format!("{}{}", id.identifier, self.template_args.as_deref().unwrap_or(""))
+            }
             UnqualifiedIdentifier::Operator(op) => op.cc_name(),
             UnqualifiedIdentifier::Destructor => {
                 format!("~{}", record.expect("destructor must be associated with a record"))
@@ -413,6 +453,17 @@ impl GenericItem for Func {
             UnqualifiedIdentifier::Constructor => {
                 record.expect("constructor must be associated with a record").to_string()
             }
+            UnqualifiedIdentifier::Conversion => {
+                let cc_type = &self.return_type.cc_type;
+                let target = match (&cc_type.name, cc_type.decl_id) {
+                    (Some(name), _) => name.to_string(),
+                    (None, Some(decl_id)) => {
+                        ir.find_untyped_decl(decl_id).debug_name(ir).to_string()
+                    }
+                    (None, None) => "<unknown type>".to_string(),
+                };
+                format!("operator {}", target)
+            }
         };
 
         if let Some(record_name) = record {
@@ -433,6 +484,31 @@ impl Func {
             .filter(|meta| meta.instance_method_metadata.is_some())
             .is_some()
     }
+
+    /// Returns copies of this function without 1, 2, ... of its trailing
+    /// parameters that have default values, so that C++ evaluates the default
+    /// arguments when the copies are called.
+    ///
+    /// Variadic functions have no such copies, because calling them without
+    /// some of their arguments requires a C++ thunk, which can't forward the
+    /// variadic arguments.
+    pub fn with_omitted_default_args(&self) -> Vec<Func> {
+        if self.is_variadic {
+            return vec![];
+        }
+        let num_default_params =
+            self.params.iter().rev().take_while(|param| param.has_default_value).count();
+        (1..=num_default_params)
+            .map(|n| {
+                let (params, omitted_params) = self.params.split_at(self.params.len() - n);
+                Func {
+                    params: params.to_vec(),
+                    omitted_default_params: omitted_params.to_vec(),
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize)]
@@ -447,6 +523,9 @@ pub enum AccessSpecifier {
 pub struct Field {
     pub identifier: Option<Identifier>,
     pub doc_comment: Option<Rc<str>>,
+    /// The message of the `[[deprecated]]` attribute (empty if it has none), or
+    /// `None` if the declaration isn't deprecated.
+    pub deprecated: Option<Rc<str>>,
     #[serde(rename(deserialize = "type"))]
     pub type_: Result<MappedType, String>,
     pub access: AccessSpecifier,
@@ -534,6 +613,9 @@ pub struct Record {
     /// record type.
     pub defining_target: Option<BazelLabel>,
     pub doc_comment: Option<Rc<str>>,
+    /// The message of the `[[deprecated]]` attribute (empty if it has none), or
+    /// `None` if the declaration isn't deprecated.
+    pub deprecated: Option<Rc<str>>,
     pub source_loc: Rc<str>,
     pub unambiguous_public_bases: Vec<BaseClass>,
     pub fields: Vec<Field>,
@@ -545,11 +627,18 @@ pub struct Record {
     pub move_constructor: SpecialMemberFunc,
     pub destructor: SpecialMemberFunc,
     pub is_trivial_abi: bool,
+    pub is_rust_movable: bool,
+    pub is_send: Option<bool>,
+    pub is_sync: Option<bool>,
+    pub is_extensible: bool,
+    pub has_stream_insertion_operator: bool,
     pub is_inheritable: bool,
     pub is_abstract: bool,
+    pub has_non_public_pure_virtual: bool,
     pub record_type: RecordType,
     pub is_aggregate: bool,
     pub is_anon_record_with_typedef: bool,
+    pub is_anon_nested_record: bool,
     pub child_item_ids: Vec<ItemId>,
     pub enclosing_namespace_id: Option<ItemId>,
 }
@@ -575,9 +664,9 @@ impl Record {
     ///
     /// Conditions:
     ///
-    /// 1. It is trivially relocatable, and thus can be passed by value and have
-    ///    its memory directly mutated by Rust using memcpy-like
-    ///    assignment/swap.
+    /// 1. It is trivially relocatable (or annotated with `CRUBIT_RUST_MOVABLE`),
+    ///    and thus can be passed by value and have its memory directly mutated
+    ///    by Rust using memcpy-like assignment/swap.
     ///
     /// 2. It cannot overlap with any other objects. In particular, it cannot be
     ///    inherited from, as inheritance allows for the tail padding to be
@@ -594,7 +683,9 @@ impl Record {
     ///
     /// Described in more detail at: docs/unpin
     pub fn is_unpin(&self) -> bool {
-        self.is_trivial_abi && !self.is_inheritable && self.fields.iter().all(|f| !f.is_inheritable)
+        (self.is_trivial_abi || self.is_rust_movable)
+            && !self.is_inheritable
+            && self.fields.iter().all(|f| !f.is_inheritable)
     }
 
     pub fn is_union(&self) -> bool {
@@ -611,6 +702,10 @@ pub struct Enum {
     pub identifier: Identifier,
     pub id: ItemId,
     pub owning_target: BazelLabel,
+    pub doc_comment: Option<Rc<str>>,
+    /// The message of the `[[deprecated]]` attribute (empty if it has none), or
+    /// `None` if the declaration isn't deprecated.
+    pub deprecated: Option<Rc<str>>,
     pub source_loc: Rc<str>,
     pub underlying_type: MappedType,
     pub enumerators: Vec<Enumerator>,
@@ -633,6 +728,7 @@ impl GenericItem for Enum {
 #[serde(deny_unknown_fields)]
 pub struct Enumerator {
     pub identifier: Identifier,
+    pub doc_comment: Option<Rc<str>>,
     pub value: IntegerConstant,
 }
 
@@ -643,6 +739,9 @@ pub struct TypeAlias {
     pub id: ItemId,
     pub owning_target: BazelLabel,
     pub doc_comment: Option<Rc<str>>,
+    /// The message of the `[[deprecated]]` attribute (empty if it has none), or
+    /// `None` if the declaration isn't deprecated.
+    pub deprecated: Option<Rc<str>>,
     pub underlying_type: MappedType,
     pub source_loc: Rc<str>,
     pub enclosing_record_id: Option<ItemId>,
@@ -661,6 +760,69 @@ impl GenericItem for TypeAlias {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+pub enum ConstantValue {
+    /// The value of an integer, `bool` or enum constant.
+    Integer(IntegerConstant),
+    /// The value of a floating point constant, as a decimal literal (e.g.
+    /// `4.2E+1`).
+    Float(Rc<str>),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Constant {
+    pub identifier: Identifier,
+    pub id: ItemId,
+    pub owning_target: BazelLabel,
+    pub doc_comment: Option<Rc<str>>,
+    #[serde(rename(deserialize = "type"))]
+    pub type_: MappedType,
+    pub value: ConstantValue,
+    pub source_loc: Rc<str>,
+    pub enclosing_record_id: Option<ItemId>,
+    pub enclosing_namespace_id: Option<ItemId>,
+}
+
+impl GenericItem for Constant {
+    fn id(&self) -> ItemId {
+        self.id
+    }
+    fn debug_name(&self, _: &IR) -> Rc<str> {
+        self.identifier.identifier.clone()
+    }
+    fn source_loc(&self) -> Option<Rc<str>> {
+        Some(self.source_loc.clone())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalVar {
+    pub identifier: Identifier,
+    pub id: ItemId,
+    pub owning_target: BazelLabel,
+    pub doc_comment: Option<Rc<str>>,
+    pub mangled_name: Rc<str>,
+    #[serde(rename(deserialize = "type"))]
+    pub type_: MappedType,
+    pub source_loc: Rc<str>,
+    pub enclosing_record_id: Option<ItemId>,
+    pub enclosing_namespace_id: Option<ItemId>,
+}
+
+impl GenericItem for GlobalVar {
+    fn id(&self) -> ItemId {
+        self.id
+    }
+    fn debug_name(&self, _: &IR) -> Rc<str> {
+        self.identifier.identifier.clone()
+    }
+    fn source_loc(&self) -> Option<Rc<str>> {
+        Some(self.source_loc.clone())
+    }
+}
+
 /// A wrapper type that does not contribute to equality or hashing. All
 /// instances are equal.
 #[derive(Clone, Copy, Default)]
@@ -828,6 +990,8 @@ pub enum Item {
     Record(Rc<Record>),
     Enum(Rc<Enum>),
     TypeAlias(Rc<TypeAlias>),
+    Constant(Rc<Constant>),
+    GlobalVar(Rc<GlobalVar>),
     UnsupportedItem(Rc<UnsupportedItem>),
     Comment(Rc<Comment>),
     Namespace(Rc<Namespace>),
@@ -843,6 +1007,8 @@ macro_rules! forward_item {
             Item::Record($item_name) => $expr,
             Item::Enum($item_name) => $expr,
             Item::TypeAlias($item_name) => $expr,
+            Item::Constant($item_name) => $expr,
+            Item::GlobalVar($item_name) => $expr,
             Item::UnsupportedItem($item_name) => $expr,
             Item::Comment($item_name) => $expr,
             Item::Namespace($item_name) => $expr,
@@ -885,6 +1051,8 @@ impl Item {
             Item::Func(func) => func.enclosing_namespace_id,
             Item::Namespace(namespace) => namespace.enclosing_namespace_id,
             Item::TypeAlias(type_alias) => type_alias.enclosing_namespace_id,
+            Item::Constant(constant) => constant.enclosing_namespace_id,
+            Item::GlobalVar(global_var) => global_var.enclosing_namespace_id,
             Item::Comment(..) => None,
             Item::UnsupportedItem(..) => None,
             Item::UseMod(..) => None,
@@ -909,6 +1077,8 @@ impl Item {
             Item::Record(record) => Some(&record.owning_target),
             Item::Enum(e) => Some(&e.owning_target),
             Item::TypeAlias(type_alias) => Some(&type_alias.owning_target),
+            Item::Constant(constant) => Some(&constant.owning_target),
+            Item::GlobalVar(global_var) => Some(&global_var.owning_target),
             Item::UnsupportedItem(..) => None,
             Item::Comment(..) => None,
             Item::Namespace(..) => None,
@@ -974,6 +1144,13 @@ impl<'a> TryFrom<&'a Item> for &'a Rc<Comment> {
     }
 }
 
+impl<'a> TryFrom<&'a Item> for &'a Rc<Namespace> {
+    type Error = Error;
+    fn try_from(value: &'a Item) -> Result<Self, Self::Error> {
+        if let Item::Namespace(n) = value { Ok(n) } else { bail!("Not a Namespace: {:#?}", value) }
+    }
+}
+
 flagset::flags! {
     pub enum CrubitFeature : u8 {
         Supported,
@@ -1032,6 +1209,10 @@ impl<'de> serde::Deserialize<'de> for CrubitFeaturesIR {
 struct FlatIR {
     #[serde(default)]
     public_headers: Vec<HeaderName>,
+    #[serde(default)]
+    forced_includes: Vec<HeaderName>,
+    #[serde(default)]
+    macro_definitions: Vec<Rc<str>>,
     current_target: BazelLabel,
     #[serde(default)]
     items: Vec<Item>,
@@ -1041,6 +1222,10 @@ struct FlatIR {
     crate_root_path: Option<Rc<str>>,
     #[serde(default)]
     crubit_features: HashMap<BazelLabel, CrubitFeaturesIR>,
+    #[serde(default)]
+    split_namespaces: HashMap<Rc<str>, Rc<str>>,
+    #[serde(default)]
+    split_namespace_to_generate: Option<Rc<str>>,
 }
 
 /// A custom debug impl that wraps the HashMap in rustfmt-friendly notation.
@@ -1062,19 +1247,27 @@ impl Debug for FlatIR {
         // FlatIR.
         let FlatIR {
             public_headers,
+            forced_includes,
+            macro_definitions,
             current_target,
             items,
             top_level_item_ids,
             crate_root_path,
             crubit_features,
+            split_namespaces,
+            split_namespace_to_generate,
         } = self;
         f.debug_struct("FlatIR")
             .field("public_headers", public_headers)
+            .field("forced_includes", forced_includes)
+            .field("macro_definitions", macro_definitions)
             .field("current_target", current_target)
             .field("items", items)
             .field("top_level_item_ids", top_level_item_ids)
             .field("crate_root_path", crate_root_path)
             .field("crubit_features", &DebugHashMap(crubit_features))
+            .field("split_namespaces", &DebugHashMap(split_namespaces))
+            .field("split_namespace_to_generate", split_namespace_to_generate)
             .finish()
     }
 }
@@ -1110,6 +1303,18 @@ impl IR {
         self.flat_ir.public_headers.iter()
     }
 
+    /// Returns the headers that were included before the public headers (see
+    /// `--forced_includes`).
+    pub fn forced_includes(&self) -> impl Iterator<Item = &HeaderName> {
+        self.flat_ir.forced_includes.iter()
+    }
+
+    /// Returns the macros that were predefined, in the form `NAME` or
+    /// `NAME=VALUE` (see `--macro_definitions`).
+    pub fn macro_definitions(&self) -> impl Iterator<Item = &Rc<str>> {
+        self.flat_ir.macro_definitions.iter()
+    }
+
     pub fn functions(&self) -> impl Iterator<Item = &Rc<Func>> {
         self.items().filter_map(|item| match item {
             Item::Func(func) => Some(func),
@@ -1262,6 +1467,38 @@ impl IR {
         self.flat_ir.crate_root_path.clone()
     }
 
+    /// Returns the name of the crate that the bindings of the top-level
+    /// namespace `namespace_name` are split into, if any.
+    pub fn split_namespace_crate(&self, namespace_name: &str) -> Option<&Rc<str>> {
+        self.flat_ir.split_namespaces.get(namespace_name)
+    }
+
+    pub fn split_namespace_to_generate(&self) -> Option<Rc<str>> {
+        self.flat_ir.split_namespace_to_generate.clone()
+    }
+
+    /// Returns a mutable reference to the split namespaces, and the namespace
+    /// to generate bindings for.
+    ///
+    /// Since IR is generally only held immutably, this is only useful for
+    /// testing.
+    #[must_use]
+    pub fn split_namespaces_mut(
+        &mut self,
+    ) -> (&mut HashMap<Rc<str>, Rc<str>>, &mut Option<Rc<str>>) {
+        (&mut self.flat_ir.split_namespaces, &mut self.flat_ir.split_namespace_to_generate)
+    }
+
+    /// Returns a mutable reference to the forced includes and the predefined
+    /// macros.
+    ///
+    /// Since IR is generally only held immutably, this is only useful for
+    /// testing.
+    #[must_use]
+    pub fn preprocessor_options_mut(&mut self) -> (&mut Vec<HeaderName>, &mut Vec<Rc<str>>) {
+        (&mut self.flat_ir.forced_includes, &mut self.flat_ir.macro_definitions)
+    }
+
     pub fn get_functions_by_name(
         &self,
         function_name: &UnqualifiedIdentifier,
@@ -1290,6 +1527,7 @@ mod tests {
         );
         assert_eq!(format!("{:?}", UnqualifiedIdentifier::Constructor), "Constructor");
         assert_eq!(format!("{:?}", UnqualifiedIdentifier::Destructor), "Destructor");
+        assert_eq!(format!("{:?}", UnqualifiedIdentifier::Conversion), "Conversion");
     }
 
     #[test]
@@ -1303,11 +1541,15 @@ mod tests {
         let ir = deserialize_ir(input.as_bytes()).unwrap();
         let expected = FlatIR {
             public_headers: vec![HeaderName { name: "foo/bar.h".into() }],
+            forced_includes: vec![],
+            macro_definitions: vec![],
             current_target: "//foo:bar".into(),
             top_level_item_ids: vec![],
             items: vec![],
             crate_root_path: None,
             crubit_features: Default::default(),
+            split_namespaces: Default::default(),
+            split_namespace_to_generate: None,
         };
         assert_eq!(ir.flat_ir, expected);
     }
@@ -1331,6 +1573,21 @@ mod tests {
         assert_eq!(ir.crate_root_path().as_deref(), Some("__cc_template_instantiations_rs_api"));
     }
 
+    #[test]
+    fn test_split_namespaces() {
+        let input = r#"
+        {
+            "current_target": "//foo:bar",
+            "split_namespaces": { "ns": "bar_ns" },
+            "split_namespace_to_generate": "ns"
+        }
+        "#;
+        let ir = deserialize_ir(input.as_bytes()).unwrap();
+        assert_eq!(ir.split_namespace_crate("ns").map(|c| c.as_ref()), Some("bar_ns"));
+        assert_eq!(ir.split_namespace_crate("other_ns"), None);
+        assert_eq!(ir.split_namespace_to_generate().as_deref(), Some("ns"));
+    }
+
     #[test]
     fn test_bazel_label_target() {
         let label: BazelLabel = "//foo:bar".into();
@@ -0,0 +1,622 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Compares the IR of two versions of a target's headers (as written by
+//! `rs_bindings_from_cc --ir_out`), and reports the changes that affect the
+//! generated Rust bindings: layout changes, removed or renamed functions,
+//! signature changes, and so forth.
+//!
+//! Usage: `ir_diff OLD_IR NEW_IR`
+//!
+//! Every change is reported on its own line, prefixed with:
+//!
+//! * `!` if the change may break Rust code using the generated bindings (e.g.
+//!   a function was removed, or the type of a public field changed),
+//! * `~` if the change doesn't break Rust code at the source level, but
+//!   changes the ABI (e.g. the size of a struct changed),
+//! * `+` if something was added.
+//!
+//! The tool exits with a non-zero status if there are breaking changes, so
+//! that it can be used as a presubmit check.
+//!
+//! Note that only the current target's items are compared, and that the IR
+//! doesn't know which items will fail to generate bindings (e.g. overloaded
+//! functions), so such items are compared as well.
+
+use arc_anyhow::{Context, Result};
+use ir::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: {} OLD_IR NEW_IR", args[0]);
+        std::process::exit(2);
+    }
+    let old_ir = read_ir(&args[1])?;
+    let new_ir = read_ir(&args[2])?;
+    let changes = diff_ir(&old_ir, &new_ir);
+    print!("{}", format_changes(&changes));
+    if changes.iter().any(|change| change.kind == ChangeKind::Breaking) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn read_ir(path: &str) -> Result<IR> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {path}"))?;
+    deserialize_ir(file).with_context(|| format!("Failed to parse the IR in {path}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ChangeKind {
+    /// May break Rust code using the bindings.
+    Breaking,
+    /// Changes the layout or the ABI, but not the Rust API.
+    Abi,
+    /// Adds to the Rust API.
+    Addition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Change {
+    kind: ChangeKind,
+    description: String,
+}
+
+impl Change {
+    fn new(kind: ChangeKind, description: impl Into<String>) -> Self {
+        Self { kind, description: description.into() }
+    }
+}
+
+fn format_changes(changes: &[Change]) -> String {
+    let mut output = String::new();
+    for change in changes {
+        let marker = match change.kind {
+            ChangeKind::Breaking => '!',
+            ChangeKind::Abi => '~',
+            ChangeKind::Addition => '+',
+        };
+        writeln!(output, "{marker} {}", change.description).unwrap();
+    }
+    output
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldApi {
+    name: String,
+    type_: String,
+    offset: usize,
+    is_public: bool,
+}
+
+/// The parts of an item that the generated bindings depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ItemApi {
+    Record {
+        size: usize,
+        alignment: usize,
+        fields: Vec<FieldApi>,
+        /// Whether the bindings use `&mut T` (rather than `Pin<&mut T>`).
+        is_unpin: bool,
+    },
+    Enum {
+        underlying_type: String,
+        enumerators: Vec<(String, IntegerConstant)>,
+    },
+    TypeAlias {
+        underlying_type: String,
+    },
+    Func {
+        /// The qualified C++ name, e.g. `ns::SomeStruct::Method`.
+        name: String,
+        params: Vec<String>,
+        return_type: String,
+    },
+}
+
+impl ItemApi {
+    /// The signature of a function, e.g. `int (int, float)`.
+    fn func_signature(&self) -> Option<String> {
+        match self {
+            ItemApi::Func { params, return_type, .. } => {
+                Some(format!("{return_type} ({})", params.join(", ")))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Maps a key identifying each item (e.g. `struct ns::SomeStruct`, or
+/// `fn ns::Add(int, int)` for functions, so that overloads are distinct) to its
+/// API.
+type Apis = BTreeMap<String, ItemApi>;
+
+fn diff_ir(old_ir: &IR, new_ir: &IR) -> Vec<Change> {
+    // Items that are now unsupported are only present as `UnsupportedItem`s in
+    // the new IR, so their messages explain why they were removed.
+    let unsupported: HashMap<String, String> = new_ir
+        .unsupported_items()
+        .map(|unsupported| (unsupported.name.to_string(), unsupported.message().to_string()))
+        .collect();
+    diff_apis(&collect_apis(old_ir), &collect_apis(new_ir), &unsupported)
+}
+
+/// Returns the qualified C++ name of the item with the given key and API.
+fn item_name<'a>(key: &'a str, api: &'a ItemApi) -> &'a str {
+    match api {
+        ItemApi::Func { name, .. } => name,
+        _ => key.split_once(' ').map_or(key, |(_keyword, name)| name),
+    }
+}
+
+fn diff_apis(old: &Apis, new: &Apis, unsupported: &HashMap<String, String>) -> Vec<Change> {
+    let removed = |key: &str, api: &ItemApi| {
+        let description = match unsupported.get(item_name(key, api)) {
+            None => format!("{key} removed"),
+            Some(message) => format!("{key} removed (now unsupported: {message})"),
+        };
+        Change::new(ChangeKind::Breaking, description)
+    };
+    let mut changes = vec![];
+    let mut removed_funcs = vec![];
+    for (key, old_api) in old {
+        match new.get(key) {
+            Some(new_api) => diff_item(key, old_api, new_api, &mut changes),
+            None if matches!(old_api, ItemApi::Func { .. }) => removed_funcs.push(key),
+            None => changes.push(removed(key, old_api)),
+        }
+    }
+    let mut added_funcs: Vec<&String> = new
+        .iter()
+        .filter(|(key, api)| matches!(api, ItemApi::Func { .. }) && !old.contains_key(*key))
+        .map(|(key, _)| key)
+        .collect();
+
+    // A removed function is matched with an added function with the same name (a
+    // signature change) or, failing that, with the same signature (a rename), as
+    // long as the match is unambiguous.
+    let func_name = |api: &ItemApi| item_name("", api).to_string();
+    for removed_key in removed_funcs {
+        let old_api = &old[removed_key];
+        let find_unique = |added_funcs: &[&String], matches: &dyn Fn(&ItemApi) -> bool| {
+            let mut candidates =
+                added_funcs.iter().enumerate().filter(|(_, key)| matches(&new[**key]));
+            match (candidates.next(), candidates.next()) {
+                (Some((idx, _)), None) => Some(idx),
+                _ => None,
+            }
+        };
+        let same_name = find_unique(&added_funcs, &|api| func_name(api) == func_name(old_api));
+        if let Some(idx) = same_name {
+            let new_api = &new[added_funcs.remove(idx)];
+            changes.push(Change::new(
+                ChangeKind::Breaking,
+                format!(
+                    "fn {}: signature changed from `{}` to `{}`",
+                    func_name(old_api),
+                    old_api.func_signature().unwrap(),
+                    new_api.func_signature().unwrap()
+                ),
+            ));
+            continue;
+        }
+        let same_signature =
+            find_unique(&added_funcs, &|api| api.func_signature() == old_api.func_signature());
+        if let Some(idx) = same_signature {
+            let new_api = &new[added_funcs.remove(idx)];
+            changes.push(Change::new(
+                ChangeKind::Breaking,
+                format!("fn {} renamed to {}", func_name(old_api), func_name(new_api)),
+            ));
+            continue;
+        }
+        changes.push(removed(removed_key, old_api));
+    }
+    for key in new.keys() {
+        if !old.contains_key(key)
+            && (!matches!(new[key], ItemApi::Func { .. }) || added_funcs.contains(&key))
+        {
+            changes.push(Change::new(ChangeKind::Addition, format!("{key} added")));
+        }
+    }
+    changes.sort();
+    changes
+}
+
+fn diff_item(key: &str, old: &ItemApi, new: &ItemApi, changes: &mut Vec<Change>) {
+    let mut change = |kind, description: String| {
+        changes.push(Change::new(kind, format!("{key}: {description}")));
+    };
+    match (old, new) {
+        (
+            ItemApi::Record { size, alignment, fields, is_unpin },
+            ItemApi::Record {
+                size: new_size,
+                alignment: new_alignment,
+                fields: new_fields,
+                is_unpin: new_is_unpin,
+            },
+        ) => {
+            if size != new_size {
+                change(ChangeKind::Abi, format!("size changed from {size} to {new_size}"));
+            }
+            if alignment != new_alignment {
+                change(
+                    ChangeKind::Abi,
+                    format!("alignment changed from {alignment} to {new_alignment}"),
+                );
+            }
+            if is_unpin != new_is_unpin {
+                let (from, to) =
+                    if *is_unpin { ("&mut", "Pin<&mut>") } else { ("Pin<&mut>", "&mut") };
+                change(
+                    ChangeKind::Breaking,
+                    format!("mutable references changed from `{from}` to `{to}`"),
+                );
+            }
+            for field in fields {
+                let kind = if field.is_public { ChangeKind::Breaking } else { ChangeKind::Abi };
+                match new_fields.iter().find(|new_field| new_field.name == field.name) {
+                    None => change(kind, format!("field `{}` removed", field.name)),
+                    Some(new_field) => {
+                        if field.type_ != new_field.type_ {
+                            change(
+                                kind,
+                                format!(
+                                    "type of field `{}` changed from `{}` to `{}`",
+                                    field.name, field.type_, new_field.type_
+                                ),
+                            );
+                        }
+                        if field.is_public && !new_field.is_public {
+                            change(
+                                ChangeKind::Breaking,
+                                format!("field `{}` is no longer public", field.name),
+                            );
+                        }
+                        if field.offset != new_field.offset {
+                            change(
+                                ChangeKind::Abi,
+                                format!(
+                                    "offset of field `{}` changed from {} to {}",
+                                    field.name, field.offset, new_field.offset
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            for new_field in new_fields {
+                if !fields.iter().any(|field| field.name == new_field.name) {
+                    let kind =
+                        if new_field.is_public { ChangeKind::Addition } else { ChangeKind::Abi };
+                    change(kind, format!("field `{}` added", new_field.name));
+                }
+            }
+        }
+        (
+            ItemApi::Enum { underlying_type, enumerators },
+            ItemApi::Enum { underlying_type: new_underlying_type, enumerators: new_enumerators },
+        ) => {
+            if underlying_type != new_underlying_type {
+                change(
+                    ChangeKind::Breaking,
+                    format!(
+                        "underlying type changed from `{underlying_type}` to `{new_underlying_type}`"
+                    ),
+                );
+            }
+            for (name, value) in enumerators {
+                match new_enumerators.iter().find(|(new_name, _)| new_name == name) {
+                    None => change(ChangeKind::Breaking, format!("enumerator `{name}` removed")),
+                    Some((_, new_value)) if new_value != value => change(
+                        ChangeKind::Breaking,
+                        format!(
+                            "value of enumerator `{name}` changed from {} to {}",
+                            format_integer(value),
+                            format_integer(new_value)
+                        ),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            for (new_name, _) in new_enumerators {
+                if !enumerators.iter().any(|(name, _)| name == new_name) {
+                    change(ChangeKind::Addition, format!("enumerator `{new_name}` added"));
+                }
+            }
+        }
+        (
+            ItemApi::TypeAlias { underlying_type },
+            ItemApi::TypeAlias { underlying_type: new_underlying_type },
+        ) => {
+            if underlying_type != new_underlying_type {
+                change(
+                    ChangeKind::Breaking,
+                    format!("changed from `{underlying_type}` to `{new_underlying_type}`"),
+                );
+            }
+        }
+        // Functions are keyed by their signature, and the kind of item is part of
+        // the key, so there is nothing else to compare.
+        _ => {}
+    }
+}
+
+fn format_integer(value: &IntegerConstant) -> String {
+    if value.is_negative {
+        (value.wrapped_value as i64).to_string()
+    } else {
+        value.wrapped_value.to_string()
+    }
+}
+
+fn collect_apis(ir: &IR) -> Apis {
+    let mut apis = Apis::new();
+    for item in ir.items() {
+        if !item.owning_target().map_or(false, |target| ir.is_current_target(target)) {
+            continue;
+        }
+        match item {
+            Item::Record(record) => {
+                let fields = record
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        Some(FieldApi {
+                            name: field.identifier.as_ref()?.identifier.to_string(),
+                            type_: match &field.type_ {
+                                Ok(type_) => format_cc_type(ir, &type_.cc_type),
+                                Err(_) => "<unsupported type>".to_string(),
+                            },
+                            offset: field.offset,
+                            is_public: field.access == AccessSpecifier::Public,
+                        })
+                    })
+                    .collect();
+                apis.insert(
+                    format!("{} {}", record_keyword(record.record_type), qualified_name(ir, item)),
+                    ItemApi::Record {
+                        size: record.size_align.size,
+                        alignment: record.size_align.alignment,
+                        fields,
+                        is_unpin: record.is_unpin(),
+                    },
+                );
+            }
+            Item::Enum(enum_) => {
+                apis.insert(
+                    format!("enum {}", qualified_name(ir, item)),
+                    ItemApi::Enum {
+                        underlying_type: format_cc_type(ir, &enum_.underlying_type.cc_type),
+                        enumerators: enum_
+                            .enumerators
+                            .iter()
+                            .map(|e| (e.identifier.identifier.to_string(), e.value))
+                            .collect(),
+                    },
+                );
+            }
+            Item::TypeAlias(type_alias) => {
+                apis.insert(
+                    format!("using {}", qualified_name(ir, item)),
+                    ItemApi::TypeAlias {
+                        underlying_type: format_cc_type(ir, &type_alias.underlying_type.cc_type),
+                    },
+                );
+            }
+            Item::Func(func) => {
+                let name = qualified_name(ir, item);
+                let params: Vec<String> = func
+                    .params
+                    .iter()
+                    // `__this` is implied by the name of the method.
+                    .filter(|param| &*param.identifier.identifier != "__this")
+                    .map(|param| format_cc_type(ir, &param.type_.cc_type))
+                    .collect();
+                apis.insert(
+                    format!("fn {name}({})", params.join(", ")),
+                    ItemApi::Func {
+                        name,
+                        params,
+                        return_type: format_cc_type(ir, &func.return_type.cc_type),
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+    apis
+}
+
+fn record_keyword(record_type: RecordType) -> &'static str {
+    match record_type {
+        RecordType::Struct => "struct",
+        RecordType::Union => "union",
+        RecordType::Class => "class",
+    }
+}
+
+/// Returns the namespace-qualified C++ name of `item`, e.g. `ns::SomeStruct`.
+fn qualified_name(ir: &IR, item: &Item) -> String {
+    let mut name = item.debug_name(ir).to_string();
+    let mut enclosing_namespace_id = item.enclosing_namespace_id();
+    while let Some(namespace_id) = enclosing_namespace_id {
+        match ir.find_decl(namespace_id) {
+            Ok(Item::Namespace(namespace)) => {
+                name = format!("{}::{name}", namespace.name.identifier);
+                enclosing_namespace_id = namespace.enclosing_namespace_id;
+            }
+            _ => break,
+        }
+    }
+    name
+}
+
+/// Formats `ty` as C++, using qualified names for types declared in the IR.
+fn format_cc_type(ir: &IR, ty: &CcType) -> String {
+    let const_fragment = match (ty.is_const, ty.is_volatile) {
+        (false, false) => "",
+        (true, false) => " const",
+        (false, true) => " volatile",
+        (true, true) => " const volatile",
+    };
+    if let Some(decl_id) = ty.decl_id {
+        let item: &Item = ir.find_decl(decl_id).expect("types refer to items");
+        return format!("{}{const_fragment}", qualified_name(ir, item));
+    }
+    match ty.name.as_deref() {
+        Some(ptr @ ("*" | "&" | "&&")) if ty.type_args.len() == 1 => {
+            format!("{}{ptr}{const_fragment}", format_cc_type(ir, &ty.type_args[0]))
+        }
+        Some(name) if ty.type_args.is_empty() => format!("{name}{const_fragment}"),
+        Some(name) => {
+            let type_args: Vec<String> =
+                ty.type_args.iter().map(|arg| format_cc_type(ir, arg)).collect();
+            format!("{name}<{}>{const_fragment}", type_args.join(", "))
+        }
+        None => "<unknown type>".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ir_from_cc(header: &str) -> Result<IR> {
+        ir_testing::ir_from_cc(multiplatform_testing::test_platform(), header)
+    }
+
+    fn diff(old_header: &str, new_header: &str) -> Result<String> {
+        Ok(format_changes(&diff_ir(&ir_from_cc(old_header)?, &ir_from_cc(new_header)?)))
+    }
+
+    #[test]
+    fn test_no_changes() -> Result<()> {
+        let header = r#"
+            struct SomeStruct final { int field; };
+            int Add(int a, int b);
+        "#;
+        assert_eq!(diff(header, header)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_layout_changes() -> Result<()> {
+        let changes = diff(
+            r#"
+            struct SomeStruct final {
+                int first;
+                int second;
+              private:
+                int private_field;
+            };
+            "#,
+            r#"
+            struct SomeStruct final {
+                long long first;
+                int second;
+                int added;
+              private:
+                char private_field;
+            };
+            "#,
+        )?;
+        assert_eq!(
+            changes,
+            "! struct SomeStruct: type of field `first` changed from `int` to `long long`\n\
+             ~ struct SomeStruct: alignment changed from 4 to 8\n\
+             ~ struct SomeStruct: offset of field `private_field` changed from 8 to 16\n\
+             ~ struct SomeStruct: offset of field `second` changed from 4 to 8\n\
+             ~ struct SomeStruct: size changed from 12 to 24\n\
+             ~ struct SomeStruct: type of field `private_field` changed from `int` to `char`\n\
+             + struct SomeStruct: field `added` added\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_changes() -> Result<()> {
+        let changes = diff(
+            r#"
+            struct SomeStruct final {};
+            namespace ns {
+            int Add(int a, int b);
+            void OldName(SomeStruct* s);
+            void Removed();
+            }
+            "#,
+            r#"
+            struct SomeStruct final {};
+            namespace ns {
+            long Add(long a, long b);
+            void NewName(SomeStruct* s);
+            void Added(int i);
+            }
+            "#,
+        )?;
+        assert_eq!(
+            changes,
+            "! fn ns::Add: signature changed from `int (int, int)` to `long (long, long)`\n\
+             ! fn ns::OldName renamed to ns::NewName\n\
+             ! fn ns::Removed() removed\n\
+             + fn ns::Added(int) added\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_changes() -> Result<()> {
+        let changes = diff(
+            "enum Color { kRed, kGreen, kBlue };",
+            "enum Color : unsigned char { kRed = 1, kBlue, kYellow };",
+        )?;
+        assert_eq!(
+            changes,
+            "! enum Color: enumerator `kGreen` removed\n\
+             ! enum Color: underlying type changed from `unsigned int` to `unsigned char`\n\
+             ! enum Color: value of enumerator `kRed` changed from 0 to 1\n\
+             + enum Color: enumerator `kYellow` added\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_removed_and_unsupported() -> Result<()> {
+        let changes = diff(
+            r#"
+            struct Removed final {};
+            using Alias = int;
+            int* NowUnsupported(int);
+            "#,
+            r#"
+            using Alias = long;
+            volatile int* NowUnsupported(int);
+            "#,
+        )?;
+        assert!(changes.contains("! using Alias: changed from `int` to `long`\n"), "{changes}");
+        assert!(changes.contains("! struct Removed removed\n"), "{changes}");
+        assert!(
+            changes.contains("! fn NowUnsupported(int) removed (now unsupported: "),
+            "{changes}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_changes() {
+        let changes = diff_apis(
+            &Apis::from([(
+                "fn f()".to_string(),
+                ItemApi::Func { name: "f".to_string(), params: vec![], return_type: "void".into() },
+            )]),
+            &Apis::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(format_changes(&changes), "! fn f() removed\n");
+    }
+}
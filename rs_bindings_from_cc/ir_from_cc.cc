@@ -36,7 +36,8 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
   // Caller should verify that the inputs are not empty.
   CHECK(!options.extra_source_code_for_testing.empty() ||
         !options.public_headers.empty() ||
-        !options.extra_instantiations.empty());
+        !options.extra_instantiations.empty() ||
+        !options.extra_function_template_instantiations.empty());
 
   clang::tooling::FileContentMappings file_contents;
 
@@ -60,11 +61,16 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
   }
 
   std::string virtual_input_file_content;
+  for (const HeaderName& header_name : options.forced_includes) {
+    absl::SubstituteAndAppend(&virtual_input_file_content, "#include \"$0\"\n",
+                              header_name.IncludePath());
+  }
   for (const HeaderName& header_name : augmented_public_headers) {
     absl::SubstituteAndAppend(&virtual_input_file_content, "#include \"$0\"\n",
                               header_name.IncludePath());
   }
-  if (!options.extra_instantiations.empty()) {
+  if (!options.extra_instantiations.empty() ||
+      !options.extra_function_template_instantiations.empty()) {
     absl::SubstituteAndAppend(&virtual_input_file_content, "namespace $0 {\n",
                               kInstantiationsNamespaceName);
     int counter = 0;
@@ -74,6 +80,17 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
                                 "using __cc_template_instantiation_$0 = $1;\n",
                                 counter++, extra_instantiation);
     }
+    // Taking the address of a function template specialization odr-uses it,
+    // which makes Clang instantiate its definition.
+    counter = 0;
+    for (const std::string& extra_instantiation :
+         options.extra_function_template_instantiations) {
+      absl::SubstituteAndAppend(
+          &virtual_input_file_content,
+          "inline constexpr auto* __cc_function_template_instantiation_$0 = "
+          "&$1;\n",
+          counter++, extra_instantiation);
+    }
     absl::SubstituteAndAppend(&virtual_input_file_content,
                               "}  // namespace $0\n",
                               kInstantiationsNamespaceName);
@@ -84,9 +101,15 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
       "-fparse-all-comments"};
   args_as_strings.insert(args_as_strings.end(), options.clang_args.begin(),
                          options.clang_args.end());
+  for (const std::string& macro_definition : options.macro_definitions) {
+    args_as_strings.push_back(absl::StrCat("-D", macro_definition));
+  }
 
   Invocation invocation(options.current_target, augmented_public_headers,
-                        options.headers_to_targets);
+                        options.headers_to_targets, options.extern_rust_types,
+                        options.cuda_host_only, options.errno_functions,
+                        options.long_double_policy, options.char_mapping,
+                        options.lifetime_elision, options.support_type_maps);
   if (!clang::tooling::runToolOnCodeWithArgs(
           std::make_unique<FrontendAction>(invocation),
           virtual_input_file_content, args_as_strings, kVirtualInputPath,
@@ -113,6 +136,10 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
     ++i;
   }
   invocation.ir_.crubit_features = std::move(options.crubit_features);
+  invocation.ir_.forced_includes.assign(options.forced_includes.begin(),
+                                        options.forced_includes.end());
+  invocation.ir_.macro_definitions.assign(options.macro_definitions.begin(),
+                                          options.macro_definitions.end());
   return invocation.ir_;
 }
 
@@ -18,8 +18,8 @@
 
 namespace crubit {
 
-// Name of the namespace in which we generate code that triggers class template
-// instantiations.
+// Name of the namespace in which we generate code that triggers class and
+// function template instantiations.
 static constexpr absl::string_view kInstantiationsNamespaceName =
     "__cc_template_instantiations";
 
@@ -38,8 +38,31 @@ struct IrFromCcOptions final {
   absl::Span<const std::string> extra_rs_srcs = {};
   absl::Span<const absl::string_view> clang_args = {};
   absl::Span<const std::string> extra_instantiations = {};
+  absl::Span<const std::string> extra_function_template_instantiations = {};
   absl::flat_hash_map<BazelLabel, absl::flat_hash_set<std::string>>
       crubit_features = {};
+  // C++ types (keyed by their fully qualified name) that are already bound by
+  // the given Rust types.
+  absl::flat_hash_map<std::string, std::string> extern_rust_types = {};
+  // Headers that are included before the `public_headers`.
+  absl::Span<const HeaderName> forced_includes = {};
+  // Macros to predefine, in the form `NAME` or `NAME=VALUE`.
+  absl::Span<const std::string> macro_definitions = {};
+  // Whether only functions that run exclusively on the host are imported from
+  // CUDA headers (i.e. `__host__ __device__` functions are skipped, too).
+  bool cuda_host_only = false;
+  // Fully qualified names of functions that return -1 (or `NULL`) and set
+  // `errno` on failure.
+  absl::Span<const std::string> errno_functions = {};
+  // How `long double` is bound.
+  LongDoublePolicy long_double_policy = LongDoublePolicy::kSkip;
+  // How plain `char` is bound.
+  CharMapping char_mapping = CharMapping::kNative;
+  // Whether lifetime elision is enabled in all headers of the current target.
+  bool lifetime_elision = false;
+  // Built-in type mappings into support crates that are enabled (see
+  // `--support_type_maps`), e.g. `cxx_string`.
+  absl::Span<const std::string> support_type_maps = {};
 
   // Not an argument, just here to prevent the options struct from being
   // copied/moved with nontrivial lifetime implications.
@@ -71,6 +94,9 @@ static_assert(std::is_aggregate_v<IrFromCcOptions>);
 //    the crate. This is done via `#[path="..."] mod <...>; pub use <...>::*;`.
 // * `extra_instantiations`: names of full C++ class template specializations
 //   to instantiate and generate bindings from.
+// * `extra_function_template_instantiations`: names of C++ function template
+//   specializations (e.g. `absl::StrCat<int>`) to instantiate and generate
+//   bindings from.
 // * `crubit_features`: The set of Crubit features to enable for each target.
 //
 absl::StatusOr<IR> IrFromCc(IrFromCcOptions options);
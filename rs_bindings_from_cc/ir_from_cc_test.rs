@@ -32,6 +32,7 @@ fn test_function() {
                 owning_target: BazelLabel("//test:testing_target"),
                 mangled_name: "_Z1fii",
                 doc_comment: None,
+                deprecated: None,
                 return_type: MappedType {
                     rs_type: RsType {
                         name: Some("::core::ffi::c_int"),
@@ -42,6 +43,7 @@ fn test_function() {
                     cc_type: CcType {
                         name: Some("int"),
                         is_const: false,
+                        is_volatile: false,
                         type_args: [],
                         decl_id: None,
                     },
@@ -58,11 +60,13 @@ fn test_function() {
                             cc_type: CcType {
                                 name: Some("int"),
                                 is_const: false,
+                                is_volatile: false,
                                 type_args: [],
                                 decl_id: None,
                             },
                         },
                         identifier: "a",
+                        has_default_value: false,
                     },
                     FuncParam {
                         type_: MappedType {
@@ -75,15 +79,20 @@ fn test_function() {
                             cc_type: CcType {
                                 name: Some("int"),
                                 is_const: false,
+                                is_volatile: false,
                                 type_args: [],
                                 decl_id: None,
                             },
                         },
                         identifier: "b",
+                        has_default_value: false,
                     },
                 ],
                 lifetime_params: [],
                 is_inline: false,
+                is_noexcept: false,
+                may_throw: true,
+                is_variadic: false,
                 member_func_metadata: None,
                 has_c_calling_convention: true,
                 is_member_or_descendant_of_class_template: false,
@@ -91,6 +100,11 @@ fn test_function() {
                 id: ItemId(...),
                 enclosing_namespace_id: None,
                 adl_enclosing_record: None,
+                sets_errno: false,
+                converts_long_double: false,
+                template_args: None,
+                rust_name: None,
+                omitted_default_params: [],
             }
         }
     );
@@ -118,6 +132,49 @@ fn test_function_with_unnamed_parameters() {
     );
 }
 
+#[test]
+fn test_function_with_default_arguments() {
+    let ir = ir_from_cc("int f(int a, int b = 1);").unwrap();
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Func {
+                name: "f", ...
+                params: [
+                    FuncParam {
+                        ... identifier: "a",
+                        has_default_value: false,
+                    },
+                    FuncParam {
+                        ... identifier: "b",
+                        has_default_value: true,
+                    },
+                ], ...
+            }
+        }
+    );
+}
+
+#[test]
+fn test_function_with_rust_name_attribute() {
+    let ir = ir_from_cc(r#"[[clang::annotate("crubit_rust_name", "g")]] void f();"#).unwrap();
+    assert_ir_matches!(ir, quote! { Func { name: "f", ... rust_name: Some("g"), ... } });
+}
+
+#[test]
+fn test_function_with_invalid_rust_name_attribute() {
+    let ir = ir_from_cc(r#"[[clang::annotate("crubit_rust_name")]] void f();"#).unwrap();
+    assert_ir_matches!(
+        ir,
+        quote! {
+            UnsupportedItem {
+                name: "f",
+                message: "Invalid `crubit_rust_name` attribute: The `crubit_rust_name` attribute requires a single string literal argument, the Rust name.", ...
+            }
+        }
+    );
+}
+
 #[test]
 fn test_unescapable_rust_keywords_in_function_parameters() {
     let ir = ir_from_cc("int f(int self, int crate, int super);").unwrap();
@@ -145,53 +202,25 @@ fn test_unescapable_rust_keywords_in_function_parameters() {
 #[test]
 fn test_unescapable_rust_keywords_in_struct_name() {
     let ir = ir_from_cc("struct Self{ int field; };").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "Self", ...
-          message: "Record name is not supported: Unescapable identifier: Self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Record { rs_name: "Self", cc_name: "Self", ... } });
 }
 
 #[test]
 fn test_unescapable_rust_keywords_in_enum_name() {
     let ir = ir_from_cc("enum Self{ kFoo = 1 };").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "Self", ...
-          message: "Enum name is not supported: Unescapable identifier: Self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Enum { identifier: "Self", ... } });
 }
 
 #[test]
 fn test_unescapable_rust_keywords_in_enumerator_name() {
     let ir = ir_from_cc("enum SomeEnum { self = 1 };").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "SomeEnum", ...
-          message: "Enumerator name is not supported: Unescapable identifier: self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Enumerator { identifier: "self", ... } });
 }
 
 #[test]
 fn test_unescapable_rust_keywords_in_anonymous_struct_type_alias() {
     let ir = ir_from_cc("typedef struct { int field; } Self;").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "Self", ...
-          message: "Record name is not supported: Unescapable identifier: Self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Record { rs_name: "Self", cc_name: "Self", ... } });
 }
 
 #[test]
@@ -205,7 +234,7 @@ fn test_unescapable_rust_keywords_in_field_name() {
                cc_name: "SomeStruct",
                ...
                fields: [Field {
-                   identifier: Some("__field_0"), ...
+                   identifier: Some("self"), ...
                }],
                ...
            }
@@ -216,40 +245,19 @@ fn test_unescapable_rust_keywords_in_field_name() {
 #[test]
 fn test_unescapable_rust_keywords_in_namespace_name() {
     let ir = ir_from_cc("namespace self { void foo(); }").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "self", ...
-          message: "Namespace name is not supported: Unescapable identifier: self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Namespace { name: "self", ... } });
 }
 
 #[test]
 fn test_unescapable_rust_keywords_in_function_name() {
     let ir = ir_from_cc("void self();").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "self", ...
-          message: "Function name is not supported: Unescapable identifier: self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { Func { name: "self", ... } });
 }
 
 #[test]
 fn test_unescapable_rust_keywords_in_type_alias_name() {
     let ir = ir_from_cc("using Self = int;").unwrap();
-    assert_ir_matches!(
-        ir,
-        quote! { UnsupportedItem {
-          name: "Self", ...
-          message: "Type alias name is not supported: Unescapable identifier: Self"
-          ...
-        }}
-    );
+    assert_ir_matches!(ir, quote! { TypeAlias { identifier: "Self", ... } });
 }
 
 #[test]
@@ -270,6 +278,22 @@ fn test_function_with_custom_calling_convention() {
     );
 }
 
+#[test]
+fn test_variadic_function() {
+    let ir = ir_from_cc("int Log(const char* fmt, ...); int f(int);").unwrap();
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Func {
+                name: "Log", ...
+                mangled_name: "_Z3LogPKcz", ...
+                is_variadic: true, ...
+            }
+        }
+    );
+    assert_ir_matches!(ir, quote! { Func { name: "f", ... is_variadic: false, ... } });
+}
+
 #[test]
 fn test_functions_from_dependency_are_not_emitted() -> Result<()> {
     let ir = ir_from_cc_dependency("int Add(int a, int b);", "int Multiply(int a, int b);")?;
@@ -303,6 +327,49 @@ fn test_explicit_class_template_instantiation_declaration_not_supported_yet() {
     );
 }
 
+#[test]
+fn test_explicit_class_template_instantiation_definition() {
+    let ir = ir_from_cc(
+        "
+        namespace ns {
+        template <class T> struct MyTemplate{
+          T GetValue() const { return value; }
+          T value;
+        };
+        template struct MyTemplate<int>;
+        }  // namespace ns
+      ",
+    )
+    .unwrap();
+    let record = retrieve_record(&ir, "ns::MyTemplate<int>");
+    assert_eq!(record.rs_name.as_ref(), "__CcTemplateInstN2ns10MyTemplateIiEE");
+    let record_id = record.id;
+    assert_eq!(1, ir.top_level_item_ids().filter(|&&id| id == record_id).count());
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Func {
+                name: "GetValue",
+                owning_target: BazelLabel("//test:testing_target"), ...
+                enclosing_record_id: Some(ItemId(#record_id)) ...
+            }
+        }
+    );
+}
+
+#[test]
+fn test_explicit_class_template_instantiation_definition_from_dependency() {
+    let ir = ir_from_cc_dependency(
+        "using MyAlias = int;",
+        "
+        template <class T> struct MyTemplate{ T value; };
+        template struct MyTemplate<int>;
+      ",
+    )
+    .unwrap();
+    assert_ir_not_matches!(ir, quote! { Record { ... cc_name: "MyTemplate<int>" ... } });
+}
+
 #[test]
 fn test_function_template_not_supported_yet() {
     let ir = ir_from_cc("template<typename SomeParam> void SomeFunctionTemplate() {};").unwrap();
@@ -538,8 +605,6 @@ fn test_struct_with_unnamed_struct_and_union_members() {
     )
     .unwrap();
 
-    // TODO(b/200067824): `type_` should not be `Err(...)` in the expectations below
-    // / we should support nested structs eventually.
     assert_ir_matches!(
         ir,
         quote! {
@@ -549,12 +614,12 @@ fn test_struct_with_unnamed_struct_and_union_members() {
                 fields: [
                     Field {
                         identifier: None, ...
-                        type_ : Err(...), ...
+                        type_ : Ok(...), ...
                         offset: 0, ...
                     } ...
                     Field {
                         identifier: None, ...
-                        type_ : Err(...), ...
+                        type_ : Ok(...), ...
                         offset: 64, ...
                     } ...
                 ], ...
@@ -562,11 +627,47 @@ fn test_struct_with_unnamed_struct_and_union_members() {
                     size: 12,
                     alignment: 4,
                 } ...
+                is_anon_nested_record: false, ...
+            }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "StructWithUnnamedMembers__unnamed_field0" ...
+                record_type: Struct, ...
+                is_anon_nested_record: true, ...
+            }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "StructWithUnnamedMembers__unnamed_field1" ...
+                record_type: Union, ...
+                is_anon_nested_record: true, ...
             }
         }
     );
 }
 
+#[test]
+fn test_anonymous_nested_record_member_functions_not_present() {
+    let ir = ir_from_cc(
+        r#"
+        struct Outer {
+          struct {
+            int method();
+          } field;
+        }; "#,
+    )
+    .unwrap();
+    assert_ir_matches!(ir, quote! { Record { rs_name: "Outer__field" ... } });
+    assert_ir_not_matches!(ir, quote! { Func { name: "method" ... } });
+}
+
 #[test]
 fn test_record_private_member_functions_not_present() {
     let ir = ir_from_cc(
@@ -665,6 +766,164 @@ fn test_record_special_member_definition() {
     );
 }
 
+#[test]
+fn test_record_rust_movable() {
+    let ir = ir_from_cc(
+        r#"
+        struct [[clang::annotate("crubit_rust_movable")]] SomeStruct final {
+          ~SomeStruct();
+        };
+    "#,
+    )
+    .unwrap();
+
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "SomeStruct" ...
+                is_trivial_abi: false,
+                is_rust_movable: true ...
+            }
+        }
+    );
+    let record = ir.records().find(|r| r.rs_name.as_ref() == "SomeStruct").unwrap();
+    assert!(record.is_unpin());
+}
+
+#[test]
+fn test_record_rust_movable_with_virtual_base() {
+    let ir = ir_from_cc(
+        r#"
+        struct Base {};
+        struct [[clang::annotate("crubit_rust_movable")]] SomeStruct final : virtual Base {
+          ~SomeStruct();
+        };
+    "#,
+    )
+    .unwrap();
+
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "SomeStruct" ...
+                is_trivial_abi: false,
+                is_rust_movable: false ...
+            }
+        }
+    );
+    let record = ir.records().find(|r| r.rs_name.as_ref() == "SomeStruct").unwrap();
+    assert!(!record.is_unpin());
+}
+
+#[test]
+fn test_record_send_sync() {
+    let ir = ir_from_cc(
+        r#"
+        struct [[clang::annotate("crubit_send")]] [[clang::annotate("crubit_not_sync")]]
+        SomeStruct {};
+    "#,
+    )
+    .unwrap();
+
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "SomeStruct" ...
+                is_send: Some(true),
+                is_sync: Some(false) ...
+            }
+        }
+    );
+}
+
+#[test]
+fn test_record_stream_insertion_operator() {
+    let ir = ir_from_cc(
+        r#"
+        namespace std {
+          template <typename CharT> class basic_ostream {};
+          using ostream = basic_ostream<char>;
+        }
+        struct Free {};
+        std::ostream& operator<<(std::ostream& os, const Free& value);
+        struct HiddenFriend {
+          friend std::ostream& operator<<(std::ostream& os, const HiddenFriend& value);
+        };
+        struct NonConst {};
+        std::ostream& operator<<(std::ostream& os, NonConst& value);
+    "#,
+    )
+    .unwrap();
+
+    let has_stream_insertion_operator = |name: &str| {
+        ir.records().find(|r| r.rs_name.as_ref() == name).unwrap().has_stream_insertion_operator
+    };
+    assert!(has_stream_insertion_operator("Free"));
+    assert!(has_stream_insertion_operator("HiddenFriend"));
+    assert!(!has_stream_insertion_operator("NonConst"));
+}
+
+#[test]
+fn test_record_send_and_not_send() {
+    let ir = ir_from_cc(
+        r#"
+        struct [[clang::annotate("crubit_send")]] [[clang::annotate("crubit_not_send")]]
+        SomeStruct {};
+    "#,
+    )
+    .unwrap();
+
+    assert_ir_matches!(
+        ir,
+        quote! { UnsupportedItem {
+          name: "SomeStruct", ...
+          message: "Records can't be annotated with both `CRUBIT_SEND` and `CRUBIT_NOT_SEND`"
+          ...
+        }}
+    );
+}
+
+#[test]
+fn test_do_not_bind() {
+    let ir = ir_from_cc(
+        r#"
+        [[clang::annotate("crubit_do_not_bind")]] void HiddenFunction();
+        struct [[clang::annotate("crubit_do_not_bind")]] HiddenStruct {
+          void HiddenMethod();
+        };
+        namespace [[clang::annotate("crubit_do_not_bind")]] hidden_namespace {
+          void FunctionInHiddenNamespace();
+        }
+        struct SomeStruct {
+          [[clang::annotate("crubit_do_not_bind")]] void HiddenMethod();
+          [[clang::annotate("crubit_do_not_bind")]] int hidden_field;
+          int public_field;
+        };
+    "#,
+    )
+    .unwrap();
+
+    assert_ir_not_matches!(ir, quote! { Func { name: "HiddenFunction" ... } });
+    assert_ir_not_matches!(ir, quote! { Func { name: "HiddenMethod" ... } });
+    assert_ir_not_matches!(ir, quote! { Func { name: "FunctionInHiddenNamespace" ... } });
+    assert_ir_not_matches!(ir, quote! { Record { rs_name: "HiddenStruct" ... } });
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Field {
+                identifier: Some("hidden_field") ...
+                type_: Err("Types of non-public C++ fields can be elided away") ...
+                access: Private,
+                offset: 0,
+                size: 32 ...
+            }
+        }
+    );
+}
+
 #[test]
 fn test_pointer_member_variable() {
     let ir = ir_from_cc(
@@ -805,6 +1064,82 @@ fn test_doc_comment_vs_tooling_directives() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_enum_doc_comment() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+        /// Doc comment for Color.
+        enum Color {
+          /// Doc comment for kRed.
+          kRed,
+          kBlue,
+        };
+        "#,
+    )?;
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Enum {
+            identifier: "Color",
+            id: ItemId(...),
+            owning_target: BazelLabel("//test:testing_target"),
+            doc_comment: Some("Doc comment for Color."),
+            ...
+            enumerators: [
+              Enumerator {
+                identifier: "kRed",
+                doc_comment: Some("Doc comment for kRed."),
+                ...
+              },
+              Enumerator {
+                identifier: "kBlue",
+                doc_comment: None,
+                ...
+              },
+            ],
+            ...
+          }
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deprecated() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+        [[deprecated("Use g() instead.")]] void f();
+        void g();
+
+        struct [[deprecated]] S {
+          [[deprecated("Use y.")]] int x;
+          int y;
+        };
+
+        using MyInt [[deprecated("Use int.")]] = int;
+        enum [[deprecated("Use a bool.")]] Color { kRed };
+        "#,
+    )?;
+
+    assert_eq!(retrieve_func(&ir, "f").deprecated.as_deref(), Some("Use g() instead."));
+    assert_eq!(retrieve_func(&ir, "g").deprecated, None);
+
+    let record = retrieve_record(&ir, "S");
+    assert_eq!(record.deprecated.as_deref(), Some(""));
+    assert_eq!(record.fields[0].deprecated.as_deref(), Some("Use y."));
+    assert_eq!(record.fields[1].deprecated, None);
+
+    assert_ir_matches!(
+        ir,
+        quote! { TypeAlias { identifier: "MyInt", ... deprecated: Some("Use int."), ... } }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! { Enum { identifier: "Color", ... deprecated: Some("Use a bool."), ... } }
+    );
+    Ok(())
+}
+
 #[test]
 fn test_type_conversion() -> Result<()> {
     // TODO(mboehme): Add tests for the corresponding versions of the types in
@@ -923,10 +1258,12 @@ fn test_type_conversion() -> Result<()> {
     // because Rust requires that chars are valid UTF scalar values.
     assert_eq!(type_mapping["char32_t"], "u32");
 
-    // TODO(b/283268558): Eventually we may need to add `wchar_t` support, after
-    // figuring out how to represent it accurately on Windows (16-bit) and
-    // elsewhere (32-bit).
-    assert!(!type_mapping.contains_key("wchar_t"));
+    // `wchar_t` has the size and signedness that it has on the target.
+    if multiplatform_testing::test_platform() == multiplatform_testing::Platform::X86Linux {
+        assert_eq!(type_mapping["wchar_t"], "i32");
+    } else {
+        assert_eq!(type_mapping["wchar_t"], "u32");
+    }
 
     assert_eq!(type_mapping["short"], "::core::ffi::c_short");
     assert_eq!(type_mapping["int"], "::core::ffi::c_int");
@@ -996,6 +1333,7 @@ fn test_typedef() -> Result<()> {
         cc_type: CcType {
           name: Some("int"),
           is_const: false,
+          is_volatile: false,
           type_args: [],
           decl_id: None,
         },
@@ -1009,6 +1347,7 @@ fn test_typedef() -> Result<()> {
             id: ItemId(...),
             owning_target: BazelLabel("//test:testing_target"),
             doc_comment: Some("Doc comment for MyTypedefDecl."),
+            deprecated: None,
             underlying_type: #int,
             source_loc: ...
             enclosing_record_id: None,
@@ -1024,6 +1363,7 @@ fn test_typedef() -> Result<()> {
             id: ItemId(...),
             owning_target: BazelLabel("//test:testing_target"),
             doc_comment: Some("Doc comment for MyTypeAliasDecl."),
+            deprecated: None,
             underlying_type: #int,
             source_loc: ...,
             enclosing_record_id: None,
@@ -1035,6 +1375,217 @@ fn test_typedef() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_constant() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+            // Doc comment for kMaxSize.
+            inline constexpr int kMaxSize = 40 + 2;
+            constexpr double kScale = 2.5;
+            int not_const = 1;
+        "#,
+    )?;
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "kMaxSize",
+            id: ItemId(...),
+            owning_target: BazelLabel("//test:testing_target"),
+            doc_comment: Some("Doc comment for kMaxSize."),
+            type_: MappedType {
+              rs_type: RsType { name: Some("::core::ffi::c_int"), ... },
+              cc_type: CcType { name: Some("int"), is_const: true, ... },
+            },
+            value: Integer(IntegerConstant { is_negative: false, wrapped_value: 42 }),
+            source_loc: ...,
+            enclosing_namespace_id: None,
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "kScale", ...
+            type_: MappedType { rs_type: RsType { name: Some("f64"), ... }, ... },
+            value: Float(...), ...
+          }
+        }
+    );
+    assert_ir_not_matches!(ir, quote! { Constant { identifier: "not_const", ... } });
+    Ok(())
+}
+
+#[test]
+fn test_macro_constants() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+            #define MAX_PATH 4096
+            #define MAX_NAME (MAX_PATH / 4)
+            #define SCALE 2.5
+            #define STR "not a number"
+            #define SQUARE(x) ((x) * (x))
+            #define HEADER_GUARD_H_
+        "#,
+    )?;
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "MAX_PATH",
+            id: ItemId(...),
+            owning_target: BazelLabel("//test:testing_target"),
+            doc_comment: None,
+            type_: MappedType {
+              rs_type: RsType { name: Some("::core::ffi::c_int"), ... },
+              cc_type: CcType { name: Some("int"), is_const: false, ... },
+            },
+            value: Integer(IntegerConstant { is_negative: false, wrapped_value: 4096 }),
+            source_loc: ...,
+            enclosing_record_id: None,
+            enclosing_namespace_id: None,
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "MAX_NAME", ...
+            value: Integer(IntegerConstant { is_negative: false, wrapped_value: 1024 }), ...
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "SCALE", ...
+            type_: MappedType { rs_type: RsType { name: Some("f64"), ... }, ... },
+            value: Float(...), ...
+          }
+        }
+    );
+    assert_ir_not_matches!(ir, quote! { Constant { identifier: "STR", ... } });
+    assert_ir_not_matches!(ir, quote! { Constant { identifier: "SQUARE", ... } });
+    assert_ir_not_matches!(ir, quote! { Constant { identifier: "HEADER_GUARD_H_", ... } });
+    Ok(())
+}
+
+#[test]
+fn test_function_like_macros() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+            int Add(int a, long b);
+            #define ADD(x, y) Add((y), x)
+            #define htons(x) __builtin_bswap16(x)
+            #define TO_UCHAR(x) ((unsigned char)(x))
+            #define SQUARE(x) ((x) * (x))
+            #define Twice(x) Add(x, x)
+        "#,
+    )?;
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Func {
+            name: "ADD",
+            owning_target: BazelLabel("//test:testing_target"),
+            mangled_name: "__crubit_macro_ADD__2f_2ftest_3atesting_5ftarget", ...
+            return_type: MappedType { rs_type: RsType { name: Some("::core::ffi::c_int"), ... }, ... },
+            params: [
+              FuncParam {
+                type_: MappedType { rs_type: RsType { name: Some("::core::ffi::c_long"), ... }, ... },
+                identifier: "x", ...
+              },
+              FuncParam {
+                type_: MappedType { rs_type: RsType { name: Some("::core::ffi::c_int"), ... }, ... },
+                identifier: "y", ...
+              },
+            ],
+            lifetime_params: [],
+            is_inline: true, ...
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Func {
+            name: "htons", ...
+            return_type: MappedType { rs_type: RsType { name: Some("::core::ffi::c_ushort"), ... }, ... },
+            params: [
+              FuncParam {
+                type_: MappedType { rs_type: RsType { name: Some("::core::ffi::c_ushort"), ... }, ... },
+                identifier: "x", ...
+              },
+            ], ...
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Func {
+            name: "TO_UCHAR", ...
+            return_type: MappedType { rs_type: RsType { name: Some("::core::ffi::c_uchar"), ... }, ... },
+            params: [
+              FuncParam {
+                type_: MappedType { rs_type: RsType { name: Some("::core::ffi::c_uchar"), ... }, ... },
+                identifier: "x", ...
+              },
+            ], ...
+          }
+        }
+    );
+    // Macros that don't expand to a call or a cast are not candidates for
+    // bindings, and so aren't reported as unsupported.
+    assert_ir_not_matches!(ir, quote! { UnsupportedItem { name: "SQUARE", ... } });
+    assert_ir_matches!(
+        ir,
+        quote! {
+          UnsupportedItem {
+            name: "Twice",
+            message: "The arguments of the call must be the parameters of the macro, in any order", ...
+          }
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_static_data_members() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+            struct S {
+              static constexpr int kMaxSize = 42;
+              static int count;
+            };
+        "#,
+    )?;
+    let record_id = retrieve_record(&ir, "S").id;
+    assert_ir_matches!(
+        ir,
+        quote! {
+          Constant {
+            identifier: "kMaxSize", ...
+            enclosing_record_id: Some(ItemId(#record_id)), ...
+          }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+          GlobalVar {
+            identifier: "count", ...
+            mangled_name: "_ZN1S5countE", ...
+            enclosing_record_id: Some(ItemId(#record_id)), ...
+          }
+        }
+    );
+    Ok(())
+}
+
 #[test]
 fn test_typedef_duplicate() -> Result<()> {
     let ir = ir_from_cc(
@@ -1148,6 +1699,7 @@ fn test_typedef_of_full_template_specialization() -> Result<()> {
                 cc_type: CcType {
                     name: None,
                     is_const: false,
+                    is_volatile: false,
                     type_args: [],
                     decl_id: Some(ItemId(#record_id)),
                 },
@@ -1506,6 +2058,7 @@ fn test_subst_template_type_parm_pack_type() -> Result<()> {
                             cc_type: CcType { name: Some("int"), ...  },
                         },
                         identifier: "__my_args_0",
+                        has_default_value: false,
                     },
                     FuncParam {
                         type_: MappedType {
@@ -1513,6 +2066,7 @@ fn test_subst_template_type_parm_pack_type() -> Result<()> {
                             cc_type: CcType { name: Some("int"), ...  },
                         },
                         identifier: "__my_args_1",
+                        has_default_value: false,
                     },
                 ], ...
             }
@@ -1521,6 +2075,34 @@ fn test_subst_template_type_parm_pack_type() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_subst_template_type_parm_pack_type_behind_reference() -> Result<()> {
+    let ir = ir_from_cc(
+        r#" #pragma clang lifetime_elision
+            template <typename... TArgs>
+            struct MyStruct {
+                static int GetSize(int first, const TArgs&... my_args) {
+                    return 1 + sizeof...(my_args);
+                }
+            };
+            using MyTypeAlias = MyStruct<int, float>; "#,
+    )?;
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Func {
+                name: "GetSize", ...
+                params: [
+                    FuncParam { ... identifier: "first", ... },
+                    FuncParam { ... identifier: "__my_args_1", ... },
+                    FuncParam { ... identifier: "__my_args_2", ... },
+                ], ...
+            }
+        }
+    );
+    Ok(())
+}
+
 #[test]
 fn test_fully_instantiated_template_in_function_return_type() -> Result<()> {
     let ir = ir_from_cc(
@@ -1560,6 +2142,7 @@ fn test_fully_instantiated_template_in_function_return_type() -> Result<()> {
                 cc_type: CcType {
                     name: None,
                     is_const: false,
+                    is_volatile: false,
                     type_args: [],
                     decl_id: Some(ItemId(#record_id)),
                 },
@@ -1620,9 +2203,11 @@ fn test_fully_instantiated_template_in_function_param_type() -> Result<()> {
                     cc_type: CcType {
                         name: Some("&"),
                         is_const: false,
+                        is_volatile: false,
                         type_args: [CcType {
                             name: None,
                             is_const: true,
+                            is_volatile: false,
                             type_args: [],
                             decl_id: Some(ItemId(#record_id)),
                         }],
@@ -1630,6 +2215,7 @@ fn test_fully_instantiated_template_in_function_param_type() -> Result<()> {
                     },
                 },
                 identifier: "my_param",
+                has_default_value: false,
             }], ...
             is_inline: false, ...
             member_func_metadata: None, ...
@@ -1685,6 +2271,7 @@ fn test_fully_instantiated_template_in_public_field() -> Result<()> {
                            cc_type: CcType {
                                name: None,
                                is_const: false,
+                               is_volatile: false,
                                type_args: [],
                                decl_id: Some(ItemId(#record_id)),
                            },
@@ -1802,6 +2389,7 @@ fn test_subst_template_type_parm_type_vs_const_when_non_const_template_param() -
                    cc_type: CcType {
                        name: Some("&"),
                        is_const: false,
+                       is_volatile: false,
                        type_args: [CcType {
                            name: Some("int"),
                            is_const: true, ...
@@ -1824,6 +2412,7 @@ fn test_subst_template_type_parm_type_vs_const_when_non_const_template_param() -
                    cc_type: CcType {
                        name: Some("&"),
                        is_const: false,
+                       is_volatile: false,
                        type_args: [CcType {
                            name: Some("int"),
                            is_const: false, ...
@@ -1871,6 +2460,7 @@ fn test_subst_template_type_parm_type_vs_const_when_const_template_param() -> Re
                    cc_type: CcType {
                        name: Some("&"),
                        is_const: false,
+                       is_volatile: false,
                        type_args: [CcType {
                            name: Some("int"),
                            is_const: true, ...
@@ -1893,6 +2483,7 @@ fn test_subst_template_type_parm_type_vs_const_when_const_template_param() -> Re
                    cc_type: CcType {
                        name: Some("&"),
                        is_const: false,
+                       is_volatile: false,
                        type_args: [CcType {
                            name: Some("int"),
                            is_const: true, ...
@@ -2151,6 +2742,7 @@ fn test_well_known_types_check_namespaces() -> Result<()> {
                  },
                },
                identifier: "i",
+               has_default_value: false,
              }], ...
           }
         }
@@ -2171,6 +2763,14 @@ fn test_dont_import_typedef_for_structs_from_c() {
     assert_ir_not_matches!(ir, quote! { TypeAlias { identifier: "MyStruct" ... } });
 }
 
+#[test]
+fn test_typedef_for_structs_from_c_in_signature() {
+    let ir =
+        ir_from_cc("struct MyStruct {}; typedef struct MyStruct MyStruct; void f(MyStruct* s);")
+            .unwrap();
+    assert_ir_matches!(ir, quote! { Func { name: "f" ... } });
+}
+
 #[test]
 fn test_ignore_typedef_but_import_struct_from_c() {
     let ir = ir_from_cc("typedef struct {} MyStruct;").unwrap();
@@ -2245,6 +2845,24 @@ fn test_typedef_and_import_union_from_c() {
     assert_ir_matches!(ir, quote! { TypeAlias { identifier: "MyTypedef" ... } });
 }
 
+#[test]
+fn test_ignore_typedef_but_import_enum_from_c() {
+    let ir = ir_from_cc("typedef enum { kFoo = 1 } MyEnum; MyEnum Func(MyEnum e);").unwrap();
+    assert_ir_matches!(ir, quote! { Enum { identifier: "MyEnum" ... } });
+    assert_ir_not_matches!(ir, quote! { TypeAlias { identifier: "MyEnum" ... } });
+
+    let enum_id = ir
+        .items()
+        .find_map(|item| match item {
+            Item::Enum(e) if e.identifier.identifier.as_ref() == "MyEnum" => Some(e.id),
+            _ => None,
+        })
+        .unwrap();
+    let func = retrieve_func(&ir, "Func");
+    assert_eq!(func.return_type.rs_type.decl_id, Some(enum_id));
+    assert_eq!(func.params[0].type_.rs_type.decl_id, Some(enum_id));
+}
+
 #[test]
 fn test_import_union_typedef_from_different_decl_context() {
     let ir = ir_from_cc(
@@ -2285,28 +2903,80 @@ fn test_ignore_union_typedef_from_decl_context_redecl_from_multiple_targets() {
 }
 
 #[test]
-fn test_records_nested_in_records_not_supported_yet() {
-    let ir = ir_from_cc("struct SomeStruct { struct NestedStruct {}; };").unwrap();
+fn test_records_nested_in_records() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+        namespace ns {
+        struct SomeStruct {
+          struct NestedStruct {
+            struct DoublyNestedStruct {};
+            void Method();
+          };
+          NestedStruct nested_field;
+        };
+        }  // namespace ns
+    "#,
+    )?;
+    let nested_struct = retrieve_record(&ir, "SomeStruct::NestedStruct");
+    assert_eq!(nested_struct.rs_name.as_ref(), "SomeStruct_NestedStruct");
+    assert!(nested_struct.enclosing_namespace_id.is_some());
+    let doubly_nested_struct = retrieve_record(&ir, "SomeStruct::NestedStruct::DoublyNestedStruct");
+    assert_eq!(doubly_nested_struct.rs_name.as_ref(), "SomeStruct_NestedStruct_DoublyNestedStruct");
+    assert!(nested_struct.child_item_ids.contains(&doubly_nested_struct.id));
+
+    let some_struct = retrieve_record(&ir, "SomeStruct");
+    assert!(some_struct.child_item_ids.contains(&nested_struct.id));
+    let field_type = some_struct.fields[0].type_.as_ref().unwrap();
+    assert_eq!(field_type.cc_type.decl_id, Some(nested_struct.id));
+
+    let method = retrieve_func(&ir, "Method");
+    assert_eq!(method.member_func_metadata.as_ref().unwrap().record_id, nested_struct.id);
+    Ok(())
+}
+
+#[test]
+fn test_records_nested_in_class_template_specializations_not_supported_yet() {
+    let ir = ir_from_cc(
+        r#"
+        template <typename T>
+        struct MyTemplate {
+          struct NestedStruct {};
+        };
+        using MyAlias = MyTemplate<int>;
+    "#,
+    )
+    .unwrap();
+    assert_ir_matches!(
+        ir,
+        quote! { UnsupportedItem {
+          name: "MyTemplate<int>::NestedStruct",
+          message: "Nested classes in class template specializations are not supported yet" ...
+        }}
+    );
+}
+
+#[test]
+fn test_records_nested_in_unsupported_records() {
+    let ir = ir_from_cc("struct __attribute__((packed)) SomeStruct { struct NestedStruct {}; };")
+        .unwrap();
     assert_ir_matches!(
         ir,
         quote! { UnsupportedItem {
           name: "SomeStruct::NestedStruct",
-          message: "Nested classes are not supported yet" ...
+          message: "Nested classes of unsupported classes are not supported" ...
         }}
     );
 }
 
 #[test]
 fn test_record_with_unsupported_field_type() -> Result<()> {
-    // Using a nested struct because it's currently not supported.
+    // Using `volatile` because it's currently not supported.
     // But... any other unsupported type would also work for this test.
     let ir = ir_from_cc(
         r#"
         struct StructWithUnsupportedField {
-          struct NestedStruct {};
-
           // Doc comment for `my_field`.
-          NestedStruct my_field;
+          volatile char my_field;
         };
     "#,
     )?;
@@ -2320,8 +2990,9 @@ fn test_record_with_unsupported_field_type() -> Result<()> {
                fields: [Field {
                    identifier: Some("my_field"),
                    doc_comment: Some("Doc comment for `my_field`."),
+                   deprecated: None,
                    type_: Err(
-                       "Unsupported type 'struct StructWithUnsupportedField::NestedStruct': No generated bindings found for 'NestedStruct'",
+                       "Unsupported `volatile` qualifier: volatile char",
                    ),
                    access: Public,
                    offset: 0,
@@ -2339,14 +3010,48 @@ fn test_record_with_unsupported_field_type() -> Result<()> {
            }
         }
     );
+    Ok(())
+}
+
+#[test]
+fn test_record_with_array_field() -> Result<()> {
+    let ir = ir_from_cc(
+        r#"
+        struct StructWithArray {
+          const int my_field[2][3];
+        };
+    "#,
+    )?;
     assert_ir_matches!(
         ir,
         quote! {
-            UnsupportedItem {
-                name: "StructWithUnsupportedField::NestedStruct",
-                message: "Nested classes are not supported yet",
-                ...
-            }
+           Record {
+               rs_name: "StructWithArray", ...
+               fields: [Field {
+                   identifier: Some("my_field"), ...
+                   type_: Ok(MappedType {
+                       rs_type: RsType {
+                           name: Some("#array 2"), ...
+                           type_args: [RsType {
+                               name: Some("#array 3"), ...
+                               type_args: [RsType { name: Some("::core::ffi::c_int"), ... }], ...
+                           }], ...
+                       },
+                       cc_type: CcType {
+                           name: Some("#array 2"), ...
+                           type_args: [CcType {
+                               name: Some("#array 3"), ...
+                               type_args: [CcType {
+                                   name: Some("int"),
+                                   is_const: true, ...
+                               }], ...
+                           }], ...
+                       },
+                   }), ...
+                   offset: 0,
+                   size: 192, ...
+               }], ...
+           }
         }
     );
     Ok(())
@@ -2355,24 +3060,22 @@ fn test_record_with_unsupported_field_type() -> Result<()> {
 #[test]
 fn test_record_with_unsupported_base() -> Result<()> {
     let ir = ir_from_cc(
-        r#" struct OuterStruct {
-              struct NestedStruct {
-                // Having a field here avoids empty base class optimization
-                // and forces `derived_field` to be at a non-zero offset.
-                // See also: https://en.cppreference.com/w/cpp/language/ebo
-                char nested_field;
-              };
+        r#" struct __attribute__((packed)) PackedStruct {
+              // Having a field here avoids empty base class optimization
+              // and forces `derived_field` to be at a non-zero offset.
+              // See also: https://en.cppreference.com/w/cpp/language/ebo
+              char packed_field;
             };
 
-            // Using a nested struct as a base class because nested structs are
-            // currently unsupported.  But... any other unsupported base class
-            // would also work for this test.
-            struct DerivedClass : public OuterStruct::NestedStruct {
+            // Using a packed struct as a base class because packed structs are
+            // unsupported.  But... any other unsupported base class would also
+            // work for this test.
+            struct DerivedClass : public PackedStruct {
               int derived_field;
             }; "#,
     )?;
     // Verify that `unambiguous_public_bases` are empty (instead of containing a
-    // dangling `ItemId` of the `NestedStruct` (which got imported as
+    // dangling `ItemId` of the `PackedStruct` (which got imported as
     // `UnsupportedItem` rather than as a `Record`).
     assert_ir_matches!(
         ir,
@@ -2385,7 +3088,8 @@ fn test_record_with_unsupported_base() -> Result<()> {
               owning_target: BazelLabel("//test:testing_target"),
               defining_target: None,
               doc_comment: Some(...),
-              source_loc: "Generated from: google3/ir_from_cc_virtual_header.h;l=15",
+              deprecated: None,
+              source_loc: "Generated from: google3/ir_from_cc_virtual_header.h;l=13",
               unambiguous_public_bases: [],
               fields: [Field {
                   identifier: Some("derived_field"), ...
@@ -2401,15 +3105,15 @@ fn test_record_with_unsupported_base() -> Result<()> {
            }
         }
     );
-    // Verify that the NestedStruct is unsupported (this is mostly verification
+    // Verify that the PackedStruct is unsupported (this is mostly verification
     // that the test input correctly sets up the test scenario;  the real
     // verification is above).
     assert_ir_matches!(
         ir,
         quote! {
            UnsupportedItem {
-               name: "OuterStruct::NestedStruct",
-               message: "Nested classes are not supported yet",
+               name: "PackedStruct",
+               message: "Records with packed layout are not supported",
                ...
            }
         }
@@ -2419,40 +3123,36 @@ fn test_record_with_unsupported_base() -> Result<()> {
 
 #[test]
 fn test_do_not_import_static_member_functions_when_record_not_supported_yet() {
-    // only using nested struct as an example of a record we cannot import yet.
+    // only using packed struct as an example of a record we cannot import.
     let ir = ir_from_cc(
         "
-        struct SomeStruct {
-          struct NestedStruct {
-            static void StaticMemberFunction();
-          };
+        struct __attribute__((packed)) SomeStruct {
+          static void StaticMemberFunction();
         };",
     )
     .unwrap();
     assert_ir_matches!(
         ir,
         quote! { UnsupportedItem {
-          name: "SomeStruct::NestedStruct::StaticMemberFunction" ...
+          name: "SomeStruct::StaticMemberFunction" ...
         }}
     );
 }
 
 #[test]
 fn test_do_not_import_nonstatic_member_functions_when_record_not_supported_yet() {
-    // only using nested struct as an example of a record we cannot import yet.
+    // only using packed struct as an example of a record we cannot import.
     let ir = ir_from_cc(
         "
-        struct SomeStruct {
-          struct NestedStruct {
-            void NonStaticMemberFunction();
-          };
+        struct __attribute__((packed)) SomeStruct {
+          void NonStaticMemberFunction();
         };",
     )
     .unwrap();
     assert_ir_matches!(
         ir,
         quote! { UnsupportedItem {
-          name: "SomeStruct::NestedStruct::NonStaticMemberFunction" ...
+          name: "SomeStruct::NonStaticMemberFunction" ...
         }}
     );
 }
@@ -2461,16 +3161,13 @@ fn test_do_not_import_nonstatic_member_functions_when_record_not_supported_yet()
 fn test_dont_import_injected_class_name() {
     let ir = ir_from_cc("struct SomeStruct {};").unwrap();
     let names = ir.records().map(|r| r.rs_name.as_ref()).filter(|n| n.contains("SomeStruct"));
-    // if we do support nested structs, we should not emit record for injected class
-    // name
+    // we should not emit a record for the injected class name
     assert_eq!(names.count(), 1);
-    // if we don't support nested structs, we should not emit unsupported item for
-    // injected class name
+    // nor an unsupported item
     assert_ir_not_matches!(
         ir,
         quote! { UnsupportedItem {
-          name: "SomeStruct::SomeStruct",
-          message: "Nested classes are not supported yet" ...
+          name: "SomeStruct::SomeStruct" ...
         }}
     );
 }
@@ -2763,6 +3460,7 @@ fn test_member_function() {
             reference: ir::ReferenceQualification::Unqualified,
             is_const: false,
             is_virtual: false,
+            is_pure_virtual: false,
         }),
     );
 }
@@ -2776,6 +3474,7 @@ fn test_member_function_const() {
             reference: ir::ReferenceQualification::Unqualified,
             is_const: true,
             is_virtual: false,
+            is_pure_virtual: false,
         }),
     );
 }
@@ -2789,6 +3488,21 @@ fn test_member_function_virtual() {
             reference: ir::ReferenceQualification::Unqualified,
             is_const: false,
             is_virtual: true,
+            is_pure_virtual: false,
+        }),
+    );
+}
+
+#[test]
+fn test_member_function_pure_virtual() {
+    assert_member_function_has_instance_method_metadata(
+        "Function",
+        "virtual void Function() = 0;",
+        &Some(ir::InstanceMethodMetadata {
+            reference: ir::ReferenceQualification::Unqualified,
+            is_const: false,
+            is_virtual: true,
+            is_pure_virtual: true,
         }),
     );
 }
@@ -2802,6 +3516,7 @@ fn test_member_function_lvalue() {
             reference: ir::ReferenceQualification::LValue,
             is_const: false,
             is_virtual: false,
+            is_pure_virtual: false,
         }),
     );
 }
@@ -2815,6 +3530,7 @@ fn test_member_function_rvalue() {
             reference: ir::ReferenceQualification::RValue,
             is_const: false,
             is_virtual: false,
+            is_pure_virtual: false,
         }),
     );
 }
@@ -2870,6 +3586,7 @@ fn test_member_function_explicit_constructor() {
             reference: ir::ReferenceQualification::Unqualified,
             is_const: false,
             is_virtual: false,
+            is_pure_virtual: false,
         }),
     );
 }
@@ -2892,6 +3609,7 @@ fn test_member_function_constructor() {
                 reference: ir::ReferenceQualification::Unqualified,
                 is_const: false,
                 is_virtual: false,
+                is_pure_virtual: false,
             }),
         );
     }
@@ -3142,11 +3860,73 @@ fn test_c_style_struct_with_typedef_and_aligned_attr() {
 
 #[test]
 fn test_volatile_is_unsupported() {
-    let ir = ir_from_cc("volatile int* foo();").unwrap();
+    let ir = ir_from_cc("volatile int foo();").unwrap();
     let f = ir.unsupported_items().find(|i| i.message().contains("volatile")).unwrap();
     assert_eq!("foo", f.name.as_ref());
 }
 
+#[test]
+fn test_pointer_to_volatile() {
+    let ir = ir_from_cc(
+        r#"
+        #pragma clang lifetime_elision
+        volatile int* Register(const volatile int& other);"#,
+    )
+    .unwrap();
+    // Rust references can't point to `volatile` objects, so the pointers are
+    // raw even though `Register` has lifetimes.
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Func {
+                name: "Register", ...
+                return_type: MappedType {
+                    rs_type: RsType {
+                        name: Some("*mut"), ...
+                    },
+                    cc_type: CcType {
+                        name: Some("*"), ...
+                        type_args: [CcType {
+                            name: Some("int"),
+                            is_const: false,
+                            is_volatile: true, ...
+                        }], ...
+                    },
+                }, ...
+                params: [FuncParam {
+                    type_: MappedType {
+                        rs_type: RsType {
+                            name: Some("*const"), ...
+                        },
+                        cc_type: CcType {
+                            name: Some("&"), ...
+                            type_args: [CcType {
+                                name: Some("int"),
+                                is_const: true,
+                                is_volatile: true, ...
+                            }], ...
+                        },
+                    }, ...
+                }], ...
+            }
+        }
+    );
+}
+
+#[test]
+fn test_rvalue_reference_to_volatile_is_unsupported() {
+    let ir = ir_from_cc("void foo(volatile int&& x);").unwrap();
+    assert_ir_matches!(
+        ir,
+        quote! {
+            UnsupportedItem {
+                name: "foo", ...
+                message: "Parameter #0 is not supported: Unsupported type 'volatile int &&': Rvalue references to `volatile` types are not supported" ...
+            }
+        }
+    );
+}
+
 #[test]
 fn test_unnamed_enum_unsupported() {
     let ir = ir_from_cc("enum { kFoo = 1, kBar = 2 };").unwrap();
@@ -3326,11 +4106,7 @@ fn test_record_items() {
               }
             },
             quote! {
-              ... UnsupportedItem {
-                  name: "TopLevelStruct::operator int",
-                  message: "Function name is not supported: Unsupported name: operator int",
-                  ...
-              }
+              ... Func { ... name: Conversion ... }
             },
         ]
     );
@@ -34,7 +34,10 @@ extern "C" FfiU8SliceBox json_from_cc_dependency(
              std::string(StringViewFromFfiU8Slice(dependency_header_source))}},
        .headers_to_targets = {{HeaderName(std::string(kDependencyHeaderName)),
                                BazelLabel{std::string(kDependencyTarget)}}},
-       .clang_args = {"-target", StringViewFromFfiU8Slice(target_triple)}});
+       .clang_args = {"-target", StringViewFromFfiU8Slice(target_triple)},
+       // Rust tests cover the built-in mappings of `absl::Status` and
+       // `std::string`, so they are enabled for all of them.
+       .support_type_maps = {"absl_status", "cxx_string"}});
 
   // TODO(forster): For now it is good enough to just exit: We are just
   // using this from tests, which are ok to just fail. Clang has already
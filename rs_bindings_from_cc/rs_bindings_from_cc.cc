@@ -6,13 +6,17 @@
 // * a Rust source file with bindings for the C++ API
 // * a C++ source file with the implementation of the bindings
 
+#include <chrono>  // NOLINT(build/c++11)
 #include <cstddef>
 #include <string>
+#include <thread>  // NOLINT(build/c++11)
 #include <utility>
 #include <vector>
 
+#include "absl/container/flat_hash_map.h"
 #include "absl/flags/parse.h"
 #include "absl/status/status.h"
+#include "absl/status/statusor.h"
 #include "absl/strings/string_view.h"
 #include "absl/types/span.h"
 #include "common/file_io.h"
@@ -21,6 +25,8 @@
 #include "rs_bindings_from_cc/collect_namespaces.h"
 #include "rs_bindings_from_cc/generate_bindings_and_metadata.h"
 #include "rs_bindings_from_cc/ir.h"
+#include "llvm/Support/Chrono.h"
+#include "llvm/Support/FileSystem.h"
 #include "llvm/Support/FormatVariadic.h"
 #include "llvm/Support/JSON.h"
 #include "llvm/Support/raw_ostream.h"
@@ -36,61 +42,172 @@ std::string InstantiationsAsJson(
   return std::string(llvm::formatv("{0:2}", llvm::json::Value(std::move(obj))));
 }
 
-absl::Status Main(absl::Span<char* const> args) {
-  CRUBIT_ASSIGN_OR_RETURN(Cmdline cmdline, Cmdline::Create());
+// How often the input files are checked for changes in `--watch` mode.
+constexpr std::chrono::milliseconds kWatchPollInterval(200);
 
-  if (cmdline.do_nothing()) {
-    CRUBIT_RETURN_IF_ERROR(SetFileContents(
-        cmdline.rs_out(),
-        "// intentionally left empty because --do_nothing was passed."));
-    CRUBIT_RETURN_IF_ERROR(SetFileContents(
-        cmdline.cc_out(),
-        "// intentionally left empty because --do_nothing was passed."));
-    if (!cmdline.instantiations_out().empty()) {
-      CRUBIT_RETURN_IF_ERROR(
-          SetFileContents(cmdline.instantiations_out(), "[]"));
-    }
-    if (!cmdline.namespaces_out().empty()) {
-      CRUBIT_RETURN_IF_ERROR(SetFileContents(cmdline.namespaces_out(), "[]"));
-    }
+// Writes `contents` to `path`, unless the file already has these contents. In
+// `--watch` mode, this avoids triggering rebuilds of everything that depends on
+// the bindings when a header change doesn't affect them.
+absl::Status UpdateFileContents(absl::string_view path,
+                                absl::string_view contents) {
+  absl::StatusOr<std::string> old_contents = GetFileContents(path);
+  if (old_contents.ok() && *old_contents == contents) {
     return absl::OkStatus();
   }
+  return SetFileContents(path, contents);
+}
 
-  std::vector<std::string> clang_args;
-  clang_args.insert(clang_args.end(), args.begin(), args.end());
-
+// Generates the bindings and writes them to the output files. Returns the
+// paths of the files that they were generated from (see `IR::input_files`).
+absl::StatusOr<std::vector<std::string>> GenerateAndWriteBindings(
+    Cmdline& cmdline, std::vector<std::string> clang_args) {
   CRUBIT_ASSIGN_OR_RETURN(
       BindingsAndMetadata bindings_and_metadata,
       GenerateBindingsAndMetadata(cmdline, std::move(clang_args)));
 
   if (!cmdline.ir_out().empty()) {
-    CRUBIT_RETURN_IF_ERROR(
-        SetFileContents(cmdline.ir_out(), IrToJson(bindings_and_metadata.ir)));
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
+        cmdline.ir_out(), IrToJson(bindings_and_metadata.ir)));
   }
 
   CRUBIT_RETURN_IF_ERROR(
-      SetFileContents(cmdline.rs_out(), bindings_and_metadata.rs_api));
+      UpdateFileContents(cmdline.rs_out(), bindings_and_metadata.rs_api));
   CRUBIT_RETURN_IF_ERROR(
-      SetFileContents(cmdline.cc_out(), bindings_and_metadata.rs_api_impl));
+      UpdateFileContents(cmdline.cc_out(), bindings_and_metadata.rs_api_impl));
 
   if (!cmdline.instantiations_out().empty()) {
     CRUBIT_RETURN_IF_ERROR(
-        SetFileContents(cmdline.instantiations_out(),
-                        InstantiationsAsJson(bindings_and_metadata)));
+        UpdateFileContents(cmdline.instantiations_out(),
+                           InstantiationsAsJson(bindings_and_metadata)));
   }
 
   if (!cmdline.namespaces_out().empty()) {
-    CRUBIT_RETURN_IF_ERROR(SetFileContents(
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
         cmdline.namespaces_out(),
         crubit::NamespacesAsJson(bindings_and_metadata.namespaces)));
   }
 
   if (!cmdline.error_report_out().empty()) {
-    CRUBIT_RETURN_IF_ERROR(SetFileContents(cmdline.error_report_out(),
-                                           bindings_and_metadata.error_report));
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
+        cmdline.error_report_out(), bindings_and_metadata.error_report));
+  }
+
+  if (!cmdline.fuzz_harness_out().empty()) {
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
+        cmdline.fuzz_harness_out(), bindings_and_metadata.fuzz_harness));
+  }
+
+  if (!cmdline.benchmark_out().empty()) {
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
+        cmdline.benchmark_out(), bindings_and_metadata.benchmark));
+  }
+
+  if (!cmdline.source_map_out().empty()) {
+    CRUBIT_RETURN_IF_ERROR(UpdateFileContents(
+        cmdline.source_map_out(), bindings_and_metadata.source_map));
+  }
+
+  if (!cmdline.stats_out().empty()) {
+    CRUBIT_RETURN_IF_ERROR(
+        UpdateFileContents(cmdline.stats_out(), bindings_and_metadata.stats));
+  }
+
+  return std::move(bindings_and_metadata.ir.input_files);
+}
+
+// Returns the modification times of the files at `paths`, keyed by their path.
+// Files that don't exist (e.g. the virtual files of Clang) are skipped.
+absl::flat_hash_map<std::string, llvm::sys::TimePoint<>> GetModificationTimes(
+    const std::vector<std::string>& paths) {
+  absl::flat_hash_map<std::string, llvm::sys::TimePoint<>> result;
+  for (const std::string& path : paths) {
+    llvm::sys::fs::file_status status;
+    if (!llvm::sys::fs::status(path, status)) {
+      result[path] = status.getLastModificationTime();
+    }
+  }
+  return result;
+}
+
+// Regenerates the bindings from scratch (parsing the headers again) whenever
+// one of the files that they were generated from changes, until the process is
+// killed. The files are polled for changes: the headers of the target and of
+// its dependencies, and the non-system headers that they include. Errors (e.g.
+// a header that doesn't compile while it's being edited) are reported, and the
+// files of the last successful run are polled for the next change.
+[[noreturn]] void Watch(Cmdline& cmdline,
+                        const std::vector<std::string>& clang_args) {
+  std::vector<std::string> input_files;
+  for (const HeaderName& header : cmdline.public_headers()) {
+    input_files.push_back(std::string(header.IncludePath()));
+  }
+  for (const auto& [header, target] : cmdline.headers_to_targets()) {
+    input_files.push_back(std::string(header.IncludePath()));
+  }
+  while (true) {
+    absl::StatusOr<std::vector<std::string>> new_input_files =
+        GenerateAndWriteBindings(cmdline, clang_args);
+    if (new_input_files.ok()) {
+      input_files = *std::move(new_input_files);
+      llvm::errs() << "Generated bindings for "
+                   << cmdline.current_target().value() << ".\n";
+    } else {
+      llvm::errs() << new_input_files.status().message() << "\n";
+    }
+    absl::flat_hash_map<std::string, llvm::sys::TimePoint<>>
+        modification_times = GetModificationTimes(input_files);
+    llvm::errs() << "Watching " << modification_times.size()
+                 << " files for changes.\n";
+    while (true) {
+      std::this_thread::sleep_for(kWatchPollInterval);
+      if (GetModificationTimes(input_files) != modification_times) break;
+    }
+  }
+}
+
+absl::Status Main(absl::Span<char* const> args) {
+  CRUBIT_ASSIGN_OR_RETURN(Cmdline cmdline, Cmdline::Create());
+
+  if (cmdline.do_nothing()) {
+    CRUBIT_RETURN_IF_ERROR(SetFileContents(
+        cmdline.rs_out(),
+        "// intentionally left empty because --do_nothing was passed."));
+    CRUBIT_RETURN_IF_ERROR(SetFileContents(
+        cmdline.cc_out(),
+        "// intentionally left empty because --do_nothing was passed."));
+    if (!cmdline.instantiations_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(
+          SetFileContents(cmdline.instantiations_out(), "[]"));
+    }
+    if (!cmdline.namespaces_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(SetFileContents(cmdline.namespaces_out(), "[]"));
+    }
+    if (!cmdline.fuzz_harness_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(SetFileContents(
+          cmdline.fuzz_harness_out(),
+          "// intentionally left empty because --do_nothing was passed."));
+    }
+    if (!cmdline.benchmark_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(SetFileContents(
+          cmdline.benchmark_out(),
+          "// intentionally left empty because --do_nothing was passed."));
+    }
+    if (!cmdline.source_map_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(SetFileContents(cmdline.source_map_out(), "{}"));
+    }
+    if (!cmdline.stats_out().empty()) {
+      CRUBIT_RETURN_IF_ERROR(SetFileContents(cmdline.stats_out(), "{}"));
+    }
+    return absl::OkStatus();
   }
 
-  return absl::OkStatus();
+  std::vector<std::string> clang_args;
+  clang_args.insert(clang_args.end(), args.begin(), args.end());
+
+  if (cmdline.watch()) {
+    Watch(cmdline, clang_args);
+  }
+  return GenerateAndWriteBindings(cmdline, std::move(clang_args)).status();
 }
 
 }  // namespace crubit
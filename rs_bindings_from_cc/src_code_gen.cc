@@ -20,14 +20,19 @@ struct FfiBindings {
   FfiU8SliceBox rs_api;
   FfiU8SliceBox rs_api_impl;
   FfiU8SliceBox error_report;
+  FfiU8SliceBox fuzz_harness;
+  FfiU8SliceBox benchmark;
+  FfiU8SliceBox source_map;
+  FfiU8SliceBox stats;
 };
 
 // This function is implemented in Rust.
-extern "C" FfiBindings GenerateBindingsImpl(
-    FfiU8Slice json, FfiU8Slice crubit_support_path,
-    FfiU8Slice clang_format_exe_path, FfiU8Slice rustfmt_exe_path,
-    FfiU8Slice rustfmt_config_path, bool generate_error_report,
-    SourceLocationDocComment generate_source_location_in_doc_comment);
+extern "C" FfiBindings GenerateBindingsImpl(FfiU8Slice json,
+                                            FfiU8Slice crubit_support_path,
+                                            FfiU8Slice clang_format_exe_path,
+                                            FfiU8Slice rustfmt_exe_path,
+                                            FfiU8Slice rustfmt_config_path,
+                                            CodegenOptions options);
 
 // Creates `Bindings` instance from copied data from `ffi_bindings`.
 static absl::StatusOr<Bindings> MakeBindingsFromFfiBindings(
@@ -37,10 +42,18 @@ static absl::StatusOr<Bindings> MakeBindingsFromFfiBindings(
   const FfiU8SliceBox& rs_api = ffi_bindings.rs_api;
   const FfiU8SliceBox& rs_api_impl = ffi_bindings.rs_api_impl;
   const FfiU8SliceBox& error_report = ffi_bindings.error_report;
+  const FfiU8SliceBox& fuzz_harness = ffi_bindings.fuzz_harness;
+  const FfiU8SliceBox& benchmark = ffi_bindings.benchmark;
+  const FfiU8SliceBox& source_map = ffi_bindings.source_map;
+  const FfiU8SliceBox& stats = ffi_bindings.stats;
 
   bindings.rs_api = std::string(rs_api.ptr, rs_api.size);
   bindings.rs_api_impl = std::string(rs_api_impl.ptr, rs_api_impl.size);
   bindings.error_report = std::string(error_report.ptr, error_report.size);
+  bindings.fuzz_harness = std::string(fuzz_harness.ptr, fuzz_harness.size);
+  bindings.benchmark = std::string(benchmark.ptr, benchmark.size);
+  bindings.source_map = std::string(source_map.ptr, source_map.size);
+  bindings.stats = std::string(stats.ptr, stats.size);
   return bindings;
 }
 
@@ -49,19 +62,21 @@ static void FreeFfiBindings(FfiBindings ffi_bindings) {
   FreeFfiU8SliceBox(ffi_bindings.rs_api);
   FreeFfiU8SliceBox(ffi_bindings.rs_api_impl);
   FreeFfiU8SliceBox(ffi_bindings.error_report);
+  FreeFfiU8SliceBox(ffi_bindings.fuzz_harness);
+  FreeFfiU8SliceBox(ffi_bindings.benchmark);
+  FreeFfiU8SliceBox(ffi_bindings.source_map);
+  FreeFfiU8SliceBox(ffi_bindings.stats);
 }
 
 absl::StatusOr<Bindings> GenerateBindings(
     const IR& ir, absl::string_view crubit_support_path,
     absl::string_view clang_format_exe_path, absl::string_view rustfmt_exe_path,
-    absl::string_view rustfmt_config_path, bool generate_error_report,
-    SourceLocationDocComment generate_source_location_in_doc_comment) {
+    absl::string_view rustfmt_config_path, const CodegenOptions& options) {
   std::string json = llvm::formatv("{0}", ir.ToJson());
   FfiBindings ffi_bindings = GenerateBindingsImpl(
       MakeFfiU8Slice(json), MakeFfiU8Slice(crubit_support_path),
       MakeFfiU8Slice(clang_format_exe_path), MakeFfiU8Slice(rustfmt_exe_path),
-      MakeFfiU8Slice(rustfmt_config_path), generate_error_report,
-      generate_source_location_in_doc_comment);
+      MakeFfiU8Slice(rustfmt_config_path), options);
   CRUBIT_ASSIGN_OR_RETURN(Bindings bindings,
                           MakeBindingsFromFfiBindings(ffi_bindings));
   FreeFfiBindings(ffi_bindings);
@@ -22,14 +22,44 @@ struct Bindings {
   std::string rs_api_impl;
   // Optional JSON error report.
   std::string error_report;
+  // Optional cargo-fuzz / libFuzzer harness (Rust source code).
+  std::string fuzz_harness;
+  // Optional Criterion benchmark (Rust source code).
+  std::string benchmark;
+  // Optional JSON source map from generated items to C++ declarations.
+  std::string source_map;
+  // Optional JSON statistics about the generated bindings.
+  std::string stats;
+};
+
+// Options that control which bindings are generated, and how. Passed by value
+// to the Rust implementation, which declares it as `CodegenOptions` in
+// `src_code_gen.rs`. Keep both in sync.
+struct CodegenOptions {
+  bool generate_error_report = false;
+  SourceLocationDocComment generate_source_location_in_doc_comment =
+      SourceLocationDocComment::Enabled;
+  bool generate_miri_compatible_bindings = false;
+  bool generate_fuzz_harness = false;
+  bool generate_benchmark = false;
+  bool generate_source_map = false;
+  bool generate_stats = false;
+  ThunkVisibility thunk_visibility = ThunkVisibility::Default;
+  bool generate_header_only_thunks = false;
+  bool snake_case_function_names = false;
+  int constructor_builder_min_params = 0;
+  bool generate_raw_module = false;
+  PointerUnsafety pointer_unsafety = PointerUnsafety::AnyPointer;
+  ExceptionPolicy exception_policy = ExceptionPolicy::Unchecked;
+  bool errno_std_io_error = false;
+  DefaultArguments default_arguments = DefaultArguments::Overloads;
 };
 
 // Generates bindings from the given `IR`.
 absl::StatusOr<Bindings> GenerateBindings(
     const IR& ir, absl::string_view crubit_support_path,
     absl::string_view clang_format_exe_path, absl::string_view rustfmt_exe_path,
-    absl::string_view rustfmt_config_path, bool generate_error_report,
-    SourceLocationDocComment generate_source_location_in_doc_comment);
+    absl::string_view rustfmt_config_path, const CodegenOptions& options);
 
 }  // namespace crubit
 
@@ -4,15 +4,19 @@
 #![allow(clippy::collapsible_else_if)]
 
 use arc_anyhow::{Context, Error, Result};
-use code_gen_utils::{format_cc_includes, make_rs_ident, CcInclude, NamespaceQualifier};
-use error_report::{anyhow, bail, ensure, ErrorReport, ErrorReporting, IgnoreErrors};
+use code_gen_utils::{
+    format_cc_includes, make_rs_ident, to_snake_case, CcInclude, NamespaceQualifier,
+};
+use error_report::{
+    anyhow, bail, ensure, error_category, ErrorReport, ErrorReporting, IgnoreErrors,
+};
 use ffi_types::*;
 use ir::*;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
-use proc_macro2::{Ident, Literal, TokenStream};
+use proc_macro2::{Delimiter, Ident, Literal, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::{OsStr, OsString};
 use std::fmt::Write as _;
 use std::iter::{self, Iterator};
@@ -32,6 +36,58 @@ pub struct FfiBindings {
     rs_api: FfiU8SliceBox,
     rs_api_impl: FfiU8SliceBox,
     error_report: FfiU8SliceBox,
+    fuzz_harness: FfiU8SliceBox,
+    benchmark: FfiU8SliceBox,
+    source_map: FfiU8SliceBox,
+    stats: FfiU8SliceBox,
+}
+
+/// Options that control which bindings are generated, and how.
+///
+/// This is passed by value from C++, where it is declared as
+/// `crubit::CodegenOptions` in `src_code_gen.h`. Keep both in sync.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CodegenOptions {
+    pub generate_error_report: bool,
+    pub generate_source_loc_doc_comment: SourceLocationDocComment,
+    pub generate_miri_compatible_bindings: bool,
+    pub generate_fuzz_harness: bool,
+    pub generate_benchmark: bool,
+    pub generate_source_map: bool,
+    pub generate_stats: bool,
+    pub thunk_visibility: ThunkVisibility,
+    pub generate_header_only_thunks: bool,
+    pub snake_case_function_names: bool,
+    pub constructor_builder_min_params: i32,
+    pub generate_raw_module: bool,
+    pub pointer_unsafety: PointerUnsafety,
+    pub exception_policy: ExceptionPolicy,
+    pub errno_std_io_error: bool,
+    pub default_arguments: DefaultArguments,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            generate_error_report: false,
+            generate_source_loc_doc_comment: SourceLocationDocComment::Enabled,
+            generate_miri_compatible_bindings: false,
+            generate_fuzz_harness: false,
+            generate_benchmark: false,
+            generate_source_map: false,
+            generate_stats: false,
+            thunk_visibility: ThunkVisibility::Default,
+            generate_header_only_thunks: false,
+            snake_case_function_names: false,
+            constructor_builder_min_params: 0,
+            generate_raw_module: false,
+            pointer_unsafety: PointerUnsafety::AnyPointer,
+            exception_policy: ExceptionPolicy::Unchecked,
+            errno_std_io_error: false,
+            default_arguments: DefaultArguments::Overloads,
+        }
+    }
 }
 
 /// Deserializes IR from `json` and generates bindings source code.
@@ -64,8 +120,7 @@ pub unsafe extern "C" fn GenerateBindingsImpl(
     clang_format_exe_path: FfiU8Slice,
     rustfmt_exe_path: FfiU8Slice,
     rustfmt_config_path: FfiU8Slice,
-    generate_error_report: bool,
-    generate_source_loc_doc_comment: SourceLocationDocComment,
+    options: CodegenOptions,
 ) -> FfiBindings {
     let json: &[u8] = json.as_slice();
     let crubit_support_path: &str = std::str::from_utf8(crubit_support_path.as_slice()).unwrap();
@@ -77,18 +132,22 @@ pub unsafe extern "C" fn GenerateBindingsImpl(
         std::str::from_utf8(rustfmt_config_path.as_slice()).unwrap().into();
     catch_unwind(|| {
         // It is ok to abort here.
-        let errors: Rc<dyn ErrorReporting> =
-            if generate_error_report { Rc::new(ErrorReport::new()) } else { Rc::new(IgnoreErrors) };
-        let Bindings { rs_api, rs_api_impl } = generate_bindings(
-            json,
-            crubit_support_path,
-            &clang_format_exe_path,
-            &rustfmt_exe_path,
-            &rustfmt_config_path,
-            errors.clone(),
-            generate_source_loc_doc_comment,
-        )
-        .unwrap();
+        let errors: Rc<dyn ErrorReporting> = if options.generate_error_report {
+            Rc::new(ErrorReport::new())
+        } else {
+            Rc::new(IgnoreErrors)
+        };
+        let Bindings { rs_api, rs_api_impl, fuzz_harness, benchmark, source_map, stats } =
+            generate_bindings(
+                json,
+                crubit_support_path,
+                &clang_format_exe_path,
+                &rustfmt_exe_path,
+                &rustfmt_config_path,
+                errors.clone(),
+                options,
+            )
+            .unwrap();
         FfiBindings {
             rs_api: FfiU8SliceBox::from_boxed_slice(rs_api.into_bytes().into_boxed_slice()),
             rs_api_impl: FfiU8SliceBox::from_boxed_slice(
@@ -97,6 +156,12 @@ pub unsafe extern "C" fn GenerateBindingsImpl(
             error_report: FfiU8SliceBox::from_boxed_slice(
                 errors.serialize_to_vec().unwrap().into_boxed_slice(),
             ),
+            fuzz_harness: FfiU8SliceBox::from_boxed_slice(
+                fuzz_harness.into_bytes().into_boxed_slice(),
+            ),
+            benchmark: FfiU8SliceBox::from_boxed_slice(benchmark.into_bytes().into_boxed_slice()),
+            source_map: FfiU8SliceBox::from_boxed_slice(source_map.into_bytes().into_boxed_slice()),
+            stats: FfiU8SliceBox::from_boxed_slice(stats.into_bytes().into_boxed_slice()),
         }
     })
     .unwrap_or_else(|_| process::abort())
@@ -110,6 +175,26 @@ trait BindingsGenerator {
     fn generate_source_loc_doc_comment(&self) -> SourceLocationDocComment;
     #[salsa::input]
     fn errors(&self) -> Rc<dyn ErrorReporting>;
+    #[salsa::input]
+    fn generate_miri_compatible_bindings(&self) -> bool;
+    #[salsa::input]
+    fn thunk_visibility(&self) -> ThunkVisibility;
+    #[salsa::input]
+    fn generate_header_only_thunks(&self) -> bool;
+    #[salsa::input]
+    fn snake_case_function_names(&self) -> bool;
+    #[salsa::input]
+    fn constructor_builder_min_params(&self) -> i32;
+    #[salsa::input]
+    fn generate_raw_module(&self) -> bool;
+    #[salsa::input]
+    fn pointer_unsafety(&self) -> PointerUnsafety;
+    #[salsa::input]
+    fn exception_policy(&self) -> ExceptionPolicy;
+    #[salsa::input]
+    fn errno_std_io_error(&self) -> bool;
+    #[salsa::input]
+    fn default_arguments(&self) -> DefaultArguments;
 
     fn rs_type_kind(&self, rs_type: RsType) -> Result<RsTypeKind>;
 
@@ -117,8 +202,18 @@ trait BindingsGenerator {
 
     fn overloaded_funcs(&self) -> Rc<HashSet<Rc<FunctionId>>>;
 
+    fn disambiguated_func_names(&self) -> Rc<HashMap<ItemId, DisambiguatedFuncName>>;
+
     fn is_record_clonable(&self, record: Rc<Record>) -> bool;
 
+    fn vector_element_records(&self) -> Rc<HashSet<ItemId>>;
+
+    fn unique_ptr_target_records(&self) -> Rc<HashSet<ItemId>>;
+
+    fn shared_ptr_target_records(&self) -> Rc<HashSet<ItemId>>;
+
+    fn member_function_pointer_types(&self) -> Rc<HashMap<ItemId, Vec<MappedType>>>;
+
     fn get_binding(
         &self,
         expected_function_name: UnqualifiedIdentifier,
@@ -140,6 +235,15 @@ struct Bindings {
     rs_api: String,
     // C++ source code.
     rs_api_impl: String,
+    // Rust source code of a cargo-fuzz harness (empty unless requested).
+    fuzz_harness: String,
+    // Rust source code of a Criterion benchmark (empty unless requested).
+    benchmark: String,
+    // JSON source map from the generated items to the C++ declarations (empty
+    // unless requested).
+    source_map: String,
+    // JSON statistics about the generated bindings (empty unless requested).
+    stats: String,
 }
 
 /// Source code for generated bindings, as tokens.
@@ -157,26 +261,22 @@ fn generate_bindings(
     rustfmt_exe_path: &OsStr,
     rustfmt_config_path: &OsStr,
     errors: Rc<dyn ErrorReporting>,
-    generate_source_loc_doc_comment: SourceLocationDocComment,
+    options: CodegenOptions,
 ) -> Result<Bindings> {
     let ir = Rc::new(deserialize_ir(json)?);
 
-    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(
-        ir.clone(),
-        crubit_support_path,
-        errors,
-        generate_source_loc_doc_comment,
-    )?;
-    let rs_api = {
+    let BindingsTokens { rs_api, rs_api_impl } =
+        generate_bindings_tokens(ir.clone(), crubit_support_path, errors.clone(), options)?;
+    let rustfmt_config = {
         let rustfmt_exe_path = Path::new(rustfmt_exe_path);
         let rustfmt_config_path = if rustfmt_config_path.is_empty() {
             None
         } else {
             Some(Path::new(rustfmt_config_path))
         };
-        let rustfmt_config = RustfmtConfig::new(rustfmt_exe_path, rustfmt_config_path);
-        rs_tokens_to_formatted_string(rs_api, &rustfmt_config)?
+        RustfmtConfig::new(rustfmt_exe_path, rustfmt_config_path)
     };
+    let rs_api = rs_tokens_to_formatted_string(rs_api, &rustfmt_config)?;
     let rs_api_impl = cc_tokens_to_formatted_string(rs_api_impl, Path::new(clang_format_exe_path))?;
 
     // Add top-level comments that help identify where the generated bindings came
@@ -223,18 +323,90 @@ fn generate_bindings(
         #![rustfmt::skip]\n\
         {rs_api}"
     );
+    let macro_definitions = format_macro_definitions(&ir);
     let rs_api_impl = format!(
         "{top_level_comment}\n\
+        {macro_definitions}\
         {rs_api_impl}"
     );
+    let db = new_database(ir.clone(), errors, options);
+    let fuzz_harness = if options.generate_fuzz_harness {
+        let fuzz_harness =
+            rs_tokens_to_formatted_string(generate_fuzz_harness_tokens(&db)?, &rustfmt_config)?;
+        format!(
+            "// Automatically @generated fuzz harness for the Rust bindings of the following \
+            C++ target:\n\
+            // {}\n\n\
+            {fuzz_harness}",
+            ir.current_target().0
+        )
+    } else {
+        String::new()
+    };
+    let benchmark = if options.generate_benchmark {
+        let benchmark =
+            rs_tokens_to_formatted_string(generate_benchmark_tokens(&db)?, &rustfmt_config)?;
+        format!(
+            "// Automatically @generated Criterion benchmark for the Rust bindings of the \
+            following C++ target:\n\
+            // {}\n\n\
+            {benchmark}",
+            ir.current_target().0
+        )
+    } else {
+        String::new()
+    };
+
+    let source_map =
+        if options.generate_source_map { generate_source_map_json(&db)? } else { String::new() };
+    let stats = if options.generate_stats { generate_stats_json(&db)? } else { String::new() };
+
+    Ok(Bindings { rs_api, rs_api_impl, fuzz_harness, benchmark, source_map, stats })
+}
 
-    Ok(Bindings { rs_api, rs_api_impl })
+/// Formats the macros that were predefined when importing the headers (see
+/// `--macro_definitions`) as `#define`s, so that the headers expand the same
+/// way when they are included by the generated C++ code.
+///
+/// This is done on the formatted source code rather than on tokens, because
+/// the values of the macros are arbitrary C++ token sequences.
+fn format_macro_definitions(ir: &IR) -> String {
+    let mut result = String::new();
+    for macro_definition in ir.macro_definitions() {
+        if result.is_empty() {
+            result.push_str("// Macros predefined for the C++ library being wrapped.\n");
+        }
+        // Like the `-D` option of Clang, `NAME` is short for `NAME=1`.
+        let (name, value) = macro_definition.split_once('=').unwrap_or((macro_definition, "1"));
+        result.push_str(&format!("#define {name} {value}\n"));
+    }
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
 }
 
 /// If we know the original C++ function is codegenned and already compatible
 /// with `extern "C"` calling convention we skip creating/calling the C++ thunk
 /// since we can call the original C++ directly.
 fn can_skip_cc_thunk(db: &dyn BindingsGenerator, func: &Func) -> bool {
+    // ## Exceptions
+    //
+    // If the function may throw, the thunk is where its exceptions are caught, or
+    // turn into calls to `std::terminate` (see `--exception_policy`). Functions
+    // that can't throw (`noexcept` functions, and all functions when compiling
+    // with `-fno-exceptions`) don't need that.
+    if db.exception_policy() != ExceptionPolicy::Unchecked && func.may_throw {
+        return false;
+    }
+    can_call_cc_function_directly(db, func)
+}
+
+/// Returns whether the C++ function `func` is codegenned and can be called
+/// through an `extern "C"` declaration of its mangled name, with the same
+/// results as through a C++ thunk, except for exceptions (see
+/// `can_skip_cc_thunk`).
+fn can_call_cc_function_directly(db: &dyn BindingsGenerator, func: &Func) -> bool {
     // ## Inline functions
     //
     // Inline functions may not be codegenned in the C++ library since Clang doesn't
@@ -258,6 +430,12 @@ fn can_skip_cc_thunk(db: &dyn BindingsGenerator, func: &Func) -> bool {
     if func.is_member_or_descendant_of_class_template {
         return false;
     }
+    // ## Function template specializations
+    //
+    // Same as above: a thunk is required to force/guarantee template instantiation.
+    if func.template_args.is_some() {
+        return false;
+    }
     // ## Virtual functions
     //
     // When calling virtual `A::Method()`, it's not necessarily the case that we'll
@@ -289,18 +467,33 @@ fn can_skip_cc_thunk(db: &dyn BindingsGenerator, func: &Func) -> bool {
     if !func.has_c_calling_convention {
         return false;
     }
+    // ## `long double` converted to `double`
+    //
+    // With `--long_double=f64`, the thunk is where `long double` parameters and
+    // return values are converted from and to `double`.
+    if func.converts_long_double {
+        return false;
+    }
+    // ## Omitted default arguments
+    //
+    // Default arguments are C++ expressions, so they are evaluated by calling the
+    // C++ function from the thunk without the corresponding arguments.
+    if !func.omitted_default_params.is_empty() {
+        return false;
+    }
 
     // ## Returning structs by value.
     //
-    // Returning a struct by value requires an explicit thunk, because
-    // `rs_bindings_from_cc` may not preserve the ABI of structs (e.g. when
-    // replacing field types with an opaque blob of bytes - see b/270454629).
+    // Returning a struct by value requires an explicit thunk, unless the struct is
+    // C-like, because `rs_bindings_from_cc` may not preserve the ABI of structs
+    // (e.g. when replacing field types with an opaque blob of bytes - see
+    // b/270454629).
     //
     // Note: if the RsTypeKind cannot be parsed / rs_type_kind returns Err, then
     // bindings generation will fail for this function, so it doesn't really matter
     // what we do here.
     if let Ok(return_type) = db.rs_type_kind(func.return_type.rs_type.clone()) {
-        if !return_type.is_c_abi_compatible_by_value() {
+        if !is_c_abi_compatible_by_value_without_thunk(db, &return_type) {
             return false;
         }
     }
@@ -318,9 +511,12 @@ fn can_skip_cc_thunk(db: &dyn BindingsGenerator, func: &Func) -> bool {
     //
     // (As a side effect, this, like return values, means that support is
     // ABI-agnostic.)
+    //
+    // C-like structs are the exception: they are trivially copyable, so the
+    // function can be called directly with a copy.
     for param in &func.params {
         if let Ok(param_type) = db.rs_type_kind(param.type_.rs_type.clone()) {
-            if !param_type.is_c_abi_compatible_by_value() {
+            if !is_c_abi_compatible_by_value_without_thunk(db, &param_type) {
                 return false;
             }
         }
@@ -329,6 +525,129 @@ fn can_skip_cc_thunk(db: &dyn BindingsGenerator, func: &Func) -> bool {
     true
 }
 
+/// Returns whether `type_` can be passed or returned by value when calling a C++
+/// function directly, without a thunk.
+///
+/// This extends `RsTypeKind::is_c_abi_compatible_by_value` to C-like structs (see
+/// `is_record_c_abi_compatible_by_value`).
+fn is_c_abi_compatible_by_value_without_thunk(
+    db: &dyn BindingsGenerator,
+    type_: &RsTypeKind,
+) -> bool {
+    match type_ {
+        RsTypeKind::TypeAlias { underlying_type, .. } => {
+            is_c_abi_compatible_by_value_without_thunk(db, underlying_type)
+        }
+        RsTypeKind::Record { record, .. } => is_record_c_abi_compatible_by_value(db, record),
+        _ => type_.is_c_abi_compatible_by_value(),
+    }
+}
+
+/// Returns whether the Rust struct generated for `record` has the same ABI as the
+/// C++ struct when passed or returned by value.
+///
+/// This is the case for C-like structs: `Unpin` structs that are trivially
+/// copyable and destructible, have no base classes, and whose fields all have
+/// the type that they have in C++ (rather than a blob of bytes).
+fn is_record_c_abi_compatible_by_value(db: &dyn BindingsGenerator, record: &Record) -> bool {
+    fn is_field_type_c_abi_compatible(db: &dyn BindingsGenerator, type_: &RsTypeKind) -> bool {
+        match type_ {
+            RsTypeKind::Array { element_type, .. } => {
+                is_field_type_c_abi_compatible(db, element_type)
+            }
+            _ => is_c_abi_compatible_by_value_without_thunk(db, type_),
+        }
+    }
+    let is_field_c_abi_compatible = |field: &Field| {
+        if field.is_bitfield || field.is_no_unique_address {
+            return false;
+        }
+        match &field.type_ {
+            Ok(type_) => match db.rs_type_kind(type_.rs_type.clone()) {
+                Ok(type_) => is_field_type_c_abi_compatible(db, &type_),
+                Err(_) => false,
+            },
+            Err(_) => false,
+        }
+    };
+    !record.is_union()
+        && record.is_unpin()
+        && record.copy_constructor == SpecialMemberFunc::Trivial
+        && record.move_constructor == SpecialMemberFunc::Trivial
+        && record.destructor == SpecialMemberFunc::Trivial
+        && !record.is_derived_class
+        && !record.override_alignment
+        // Empty C++ structs aren't passed at all, but their Rust structs have a byte.
+        && !record.fields.is_empty()
+        && record.fields.iter().all(is_field_c_abi_compatible)
+}
+
+/// Returns whether the thunk of `func` takes (or returns) `type_` by value rather
+/// than by pointer.
+///
+/// If `func` is called directly, without a C++ thunk, then all of its parameters
+/// and its return value are passed by value (see `can_skip_cc_thunk`).
+fn is_passed_to_thunk_by_value(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    type_: &RsTypeKind,
+) -> bool {
+    type_.is_c_abi_compatible_by_value() || can_skip_cc_thunk(db, func)
+}
+
+/// Returns whether the thunk of `func` returns `return_type` by value, rather
+/// than through a `__return` out-param.
+///
+/// A thunk that catches exceptions has to return a dummy value when the
+/// function throws, so it only returns the types that are known to be
+/// value-initializable by value (e.g. not structs with a type map override,
+/// which may not have a default constructor).
+fn is_returned_from_thunk_by_value(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    return_type: &RsTypeKind,
+    catches_exceptions: bool,
+) -> bool {
+    is_passed_to_thunk_by_value(db, func, return_type)
+        && (!catches_exceptions || is_value_initializable(&db.ir(), &func.return_type.cc_type))
+}
+
+/// Returns whether `cc_type` is known to be value-initializable (e.g. with
+/// `return {};`): builtin types, pointers, references and enums are.
+fn is_value_initializable(ir: &IR, cc_type: &CcType) -> bool {
+    if cc_type.decl_id.is_none() {
+        return true;
+    }
+    match ir.item_for_type(cc_type) {
+        Ok(Item::Enum(_)) => true,
+        Ok(Item::TypeAlias(type_alias)) => {
+            is_value_initializable(ir, &type_alias.underlying_type.cc_type)
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether the bindings of `func` catch the C++ exceptions that it
+/// throws, and return them as `Err(CxxException)` (see `--exception_policy`).
+///
+/// Only functions and methods whose Rust return type is up to Crubit catch
+/// exceptions. Trait implementations, constructors, functions that return
+/// non-`Unpin` types, and functions with an errno wrapper terminate the program
+/// instead, as with `ExceptionPolicy::Abort`.
+fn catches_exceptions(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    impl_kind: &ImplKind,
+    return_type: &RsTypeKind,
+) -> bool {
+    db.exception_policy() == ExceptionPolicy::Catch
+        && func.may_throw
+        && !func.sets_errno
+        && func.name != UnqualifiedIdentifier::Constructor
+        && matches!(impl_kind, ImplKind::None { .. } | ImplKind::Struct { .. })
+        && return_type.is_unpin()
+}
+
 /// Uniquely identifies a generated Rust function.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct FunctionId {
@@ -706,7 +1025,13 @@ fn api_func_shape(
         Some(Err(_)) => return Ok(None),
     };
 
-    let has_pointer_params = param_types.iter().any(|p| matches!(p, RsTypeKind::Pointer { .. }));
+    let has_pointer_params = match db.pointer_unsafety() {
+        PointerUnsafety::AnyPointer => param_types.iter().any(|p| p.contains_raw_pointer()),
+        // Spans don't carry a lifetime either, so they are as unsafe as raw pointers.
+        PointerUnsafety::TopLevelPointer => {
+            param_types.iter().any(|p| p.is_raw_pointer() || p.is_span())
+        }
+    };
     let impl_kind: ImplKind;
     let func_name: syn::Ident;
 
@@ -769,8 +1094,115 @@ fn api_func_shape(
                 /* force_const_reference_params= */ true,
             )?;
         }
+        UnqualifiedIdentifier::Operator(op) if op.name.as_ref() == "!=" => {
+            // `PartialEq::ne` is implemented in terms of `PartialEq::eq`, and C++20 rewrites
+            // `a != b` into `!(a == b)` too, so `operator!=` doesn't need bindings of its own.
+            match get_binding(
+                db,
+                UnqualifiedIdentifier::Operator(Operator { name: Rc::from("==") }),
+                param_types.to_vec(),
+            ) {
+                Some((_, ImplKind::Trait { trait_name: TraitName::PartialEq { .. }, .. })) => {
+                    return Ok(None);
+                }
+                _ => bail!("operator!= where operator== is missing."),
+            }
+        }
         UnqualifiedIdentifier::Operator(op) if op.name.as_ref() == "<=>" => {
-            bail!("Three-way comparison operator not yet supported (b/219827738)");
+            // `PartialOrd` and `Ord` are implemented by `cc_struct_three_way_comparison_impl`,
+            // using a thunk that converts the C++ comparison category into an integer.
+            let record = param_types
+                .get(0)
+                .and_then(comparison_operand_record)
+                .ok_or_else(|| anyhow!("operator<=> where lhs operand is not a record"))?;
+            match find_three_way_comparison(db, record) {
+                Some(three_way_comparison) if three_way_comparison.id == func.id => {}
+                _ => bail!("operator<=> where lhs and rhs are not the same type."),
+            }
+            if get_binding(
+                db,
+                UnqualifiedIdentifier::Operator(Operator { name: Rc::from("==") }),
+                param_types.to_vec(),
+            )
+            .is_none()
+            {
+                bail!("operator<=> where operator== is missing.");
+            }
+            return Ok(None);
+        }
+        UnqualifiedIdentifier::Operator(op) if op.name.as_ref() == "[]" => {
+            let record =
+                maybe_record.ok_or_else(|| anyhow!("operator[] must be a member function."))?;
+            let is_const = func
+                .member_func_metadata
+                .as_ref()
+                .and_then(|meta| meta.instance_method_metadata.as_ref())
+                .map_or(false, |meta| meta.is_const);
+            if subscript_index_trait_is_implementable(db, func, record, param_types) {
+                let (trait_name, method_name) =
+                    if is_const { ("Index", "index") } else { ("IndexMut", "index_mut") };
+                impl_kind = ImplKind::new_trait(
+                    TraitName::Other {
+                        name: Rc::from(format!("::core::ops::{trait_name}")),
+                        params: Rc::from(&param_types[1..]),
+                        is_unsafe_fn: false,
+                    },
+                    record.clone(),
+                    /* format_first_param_as_self= */ true,
+                    /* force_const_reference_params= */ false,
+                )?;
+                func_name = make_rs_ident(method_name);
+            } else {
+                // Otherwise, fall back to an accessor method.
+                let format_first_param_as_self =
+                    param_types.first().map_or(false, |first_param| first_param.is_ref_to(record));
+                impl_kind = ImplKind::Struct {
+                    record: record.clone(),
+                    format_first_param_as_self,
+                    is_unsafe: has_pointer_params,
+                };
+                func_name = make_rs_ident(if is_const { "get" } else { "get_mut" });
+            }
+        }
+        UnqualifiedIdentifier::Operator(op) if op.name.as_ref() == "()" => {
+            let record =
+                maybe_record.ok_or_else(|| anyhow!("operator() must be a member function."))?;
+            let is_const = func
+                .member_func_metadata
+                .as_ref()
+                .and_then(|meta| meta.instance_method_metadata.as_ref())
+                .map_or(false, |meta| meta.is_const);
+            // TODO: Also implement the `Fn*` traits once `unboxed_closures` and
+            // `fn_traits` are stable.
+            let format_first_param_as_self =
+                param_types.first().map_or(false, |first_param| first_param.is_ref_to(record));
+            impl_kind = ImplKind::Struct {
+                record: record.clone(),
+                format_first_param_as_self,
+                is_unsafe: has_pointer_params,
+            };
+            func_name = make_rs_ident(if is_const { "call" } else { "call_mut" });
+        }
+        UnqualifiedIdentifier::Operator(op)
+            if matches!(op.name.as_ref(), "<=" | ">" | ">=") && param_types.len() == 2 =>
+        {
+            // These override the `PartialOrd::le`, `gt` and `ge` methods of the `PartialOrd`
+            // implementation using `operator<` or `operator<=>` (see
+            // `relational_operator_overrides`), so they don't need bindings of their own.
+            let has_three_way_comparison = comparison_operand_record(&param_types[0])
+                .map_or(false, |record| find_three_way_comparison(db, record).is_some());
+            let has_lt = matches!(
+                get_binding(
+                    db,
+                    UnqualifiedIdentifier::Operator(Operator { name: Rc::from("<") }),
+                    param_types.to_vec(),
+                ),
+                Some((_, ImplKind::Trait { trait_name: TraitName::PartialOrd { .. }, .. }))
+            );
+            if has_three_way_comparison || has_lt {
+                return Ok(None);
+            }
+            bail!("operator{} where operator< is missing.", op.name);
         }
         UnqualifiedIdentifier::Operator(op) if op.name.as_ref() == "<" => {
             assert_eq!(
@@ -820,6 +1252,10 @@ fn api_func_shape(
             if lhs_record != rhs_record {
                 bail!("operator< where lhs and rhs are not the same type.");
             }
+            // `operator<=>` takes precedence, since it can also implement `Ord`.
+            if find_three_way_comparison(db, lhs_record).is_some() {
+                return Ok(None);
+            }
             // PartialOrd requires PartialEq, so we need to make sure operator== is
             // implemented for this Record type.
             match get_binding(
@@ -902,6 +1338,25 @@ fn api_func_shape(
                     RsTypeKind::RvalueReference { .. } => {
                         bail!("Not yet supported for rvalue references (b/219826128)")
                     }
+                    // A primitive left-hand side, e.g. `operator*(double, const Vector&)`. The
+                    // trait is implemented for the primitive type, which the orphan rules allow
+                    // because the right-hand side is a local type.
+                    RsTypeKind::Other { .. } | RsTypeKind::Enum { .. } => {
+                        let rhs_record = match param_types.get(1) {
+                            Some(RsTypeKind::Record { record, .. }) => record,
+                            Some(RsTypeKind::Reference { referent, .. }) => match &**referent {
+                                RsTypeKind::Record { record, .. } => record,
+                                _ => bail!("Expected second parameter referent to be a record"),
+                            },
+                            _ => bail!("Expected first or second parameter to be a record"),
+                        };
+                        ensure!(
+                            ir.is_current_target(&rhs_record.owning_target),
+                            "Operators with a primitive left-hand side must be declared in the same \
+                             target as the record on the right-hand side"
+                        );
+                        (rhs_record, ImplFor::RefT)
+                    }
                     _ => bail!("Expected first parameter to be a record or reference"),
                 };
 
@@ -979,7 +1434,27 @@ fn api_func_shape(
             }
         },
         UnqualifiedIdentifier::Identifier(id) => {
-            func_name = make_rs_ident(&id.identifier);
+            let name = match (&func.rust_name, db.disambiguated_func_names().get(&func.id)) {
+                (Some(rust_name), _) => rust_name.to_string(),
+                (None, Some(disambiguated_name)) => {
+                    ensure!(
+                        !disambiguated_name.collides,
+                        "The function would be bound as `{}`, which is the Rust name of another \
+                         function",
+                        disambiguated_name.name
+                    );
+                    disambiguated_name.name.to_string()
+                }
+                (None, None) => rs_func_name(db, &func_base_name(func, &id.identifier)),
+            };
+            // `f(a, b = 1, c = 2)` is also bound as `f_without_b(a)` and
+            // `f_without_c(a, b)`.
+            func_name = match func.omitted_default_params.first() {
+                None => make_rs_ident(&name),
+                Some(param) => {
+                    make_rs_ident(&format!("{name}_without_{}", omitted_param_name(param)))
+                }
+            };
             match maybe_record {
                 None => {
                     impl_kind = ImplKind::None { is_unsafe: has_pointer_params };
@@ -1001,6 +1476,12 @@ fn api_func_shape(
                 }
             };
         }
+        UnqualifiedIdentifier::Conversion => {
+            ensure!(maybe_record.is_some(), "Conversion functions must be member functions.");
+            // `cc_struct_conversion_impls` implements `From` for the target type instead.
+            conversion_target(db, func)?;
+            return Ok(None);
+        }
         UnqualifiedIdentifier::Destructor => {
             // Note: to avoid double-destruction of the fields, they are all wrapped in
             // ManuallyDrop in this case. See `generate_record`.
@@ -1071,6 +1552,15 @@ fn api_func_shape(
                 }
             } else {
                 match func.params.len() {
+                    _ if is_builder_ctor(db, func) => {
+                        // Bound as a builder type, see `generate_ctor_builder`.
+                        impl_kind = ImplKind::Struct {
+                            record: record.clone(),
+                            format_first_param_as_self: false,
+                            is_unsafe: false,
+                        };
+                        func_name = make_rs_ident("builder");
+                    }
                     0 => bail!("Missing `__this` parameter in a constructor: {:?}", func),
                     1 => {
                         impl_kind = ImplKind::new_trait(
@@ -1151,6 +1641,190 @@ fn get_binding(
         })
 }
 
+/// Returns whether a member `operator[]` can be bound as `Index` (if it is
+/// `const`) or `IndexMut` (otherwise).
+///
+/// This requires an `Unpin` record, an index type without lifetimes, and a
+/// reference return type with the same constness as the member function. For
+/// `IndexMut`, the record must also implement `Index` with the same index and
+/// element types.
+fn subscript_index_trait_is_implementable(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    record: &Record,
+    param_types: &[RsTypeKind],
+) -> bool {
+    let index_trait_shape = |func: &Func| -> Option<(bool, RsTypeKind, Rc<RsTypeKind>)> {
+        let is_const =
+            func.member_func_metadata.as_ref()?.instance_method_metadata.as_ref()?.is_const;
+        let mutability = if is_const { Mutability::Const } else { Mutability::Mut };
+        let param_types = func
+            .params
+            .iter()
+            .map(|param| db.rs_type_kind(param.type_.rs_type.clone()))
+            .collect::<Result<Vec<_>>>()
+            .ok()?;
+        match param_types.as_slice() {
+            [this, index] if this.is_ref_to(record) && index.lifetimes().next().is_none() => {}
+            _ => return None,
+        }
+        match db.rs_type_kind(func.return_type.rs_type.clone()).ok()? {
+            RsTypeKind::Reference { referent, mutability: m, .. }
+                if m == mutability && (is_const || referent.is_unpin()) =>
+            {
+                Some((is_const, param_types[1].clone(), referent))
+            }
+            _ => None,
+        }
+    };
+    if !record.is_unpin() || param_types.len() != 2 {
+        return false;
+    }
+    match index_trait_shape(func) {
+        Some((true, ..)) => true,
+        Some((false, index, element)) => db
+            .ir()
+            .get_functions_by_name(&UnqualifiedIdentifier::Operator(Operator {
+                name: Rc::from("[]"),
+            }))
+            .filter(|other| {
+                other.member_func_metadata.as_ref().map(|meta| meta.record_id) == Some(record.id)
+            })
+            .any(|other| index_trait_shape(other) == Some((true, index.clone(), element.clone()))),
+        None => false,
+    }
+}
+
+/// Returns the record compared by a comparison operator operand of type `ty`,
+/// which is either the record itself, or a const reference or pointer to it.
+fn comparison_operand_record(ty: &RsTypeKind) -> Option<&Rc<Record>> {
+    match ty {
+        RsTypeKind::Record { record, .. } => Some(record),
+        RsTypeKind::Reference { referent: pointee, mutability: Mutability::Const, .. }
+        | RsTypeKind::Pointer { pointee, mutability: Mutability::Const } => match &**pointee {
+            RsTypeKind::Record { record, .. } => Some(record),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the `operator<=>` comparing `record` with itself, if any.
+fn find_three_way_comparison(db: &dyn BindingsGenerator, record: &Record) -> Option<Rc<Func>> {
+    find_comparison_operator(db, record, "<=>")
+}
+
+/// Returns the comparison operator named `op` (e.g. `<=`) that compares
+/// `record` with itself, if any.
+fn find_comparison_operator(
+    db: &dyn BindingsGenerator,
+    record: &Record,
+    op: &str,
+) -> Option<Rc<Func>> {
+    db.ir()
+        .get_functions_by_name(&UnqualifiedIdentifier::Operator(Operator { name: Rc::from(op) }))
+        .find(|func| {
+            func.params.len() == 2
+                && func.params.iter().all(|param| {
+                    db.rs_type_kind(param.type_.rs_type.clone()).map_or(false, |ty| {
+                        comparison_operand_record(&ty).map_or(false, |r| r.id == record.id)
+                    })
+                })
+        })
+        .cloned()
+}
+
+/// Returns the `PartialOrd::le`, `gt` and `ge` methods that call the
+/// user-declared `operator<=`, `operator>` and `operator>=` of `record`, so
+/// that they take precedence over the default implementations in terms of
+/// `partial_cmp`. The methods are in `item`, and their thunks in `thunks` and
+/// `thunk_impls`.
+fn relational_operator_overrides(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+) -> Result<GeneratedItem> {
+    let ir = db.ir();
+    let qualified_ident = RsTypeKind::new_record(record.clone(), &ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), &ir)?;
+    let crate_root_path = crate_root_path_tokens(&ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+
+    let mut methods = vec![];
+    let mut thunks = vec![];
+    let mut thunk_impls = vec![];
+    for (op, op_tokens, method) in
+        [("<=", quote! {<=}, "le"), (">", quote! {>}, "gt"), (">=", quote! {>=}, "ge")]
+    {
+        // The thunk is generated in this crate, so it must be able to see the operator.
+        let func = match find_comparison_operator(db, record, op) {
+            Some(func) if ir.is_current_target(&func.owning_target) => func,
+            _ => continue,
+        };
+        let return_type = db.rs_type_kind(func.return_type.rs_type.clone())?;
+        if !matches!(return_type, RsTypeKind::Other { ref name, .. } if &**name == "bool") {
+            continue;
+        }
+        let method = make_rs_ident(method);
+        let thunk_ident = format_ident!("__crubit_{}__{}", method, record.mangled_cc_name.as_ref());
+        methods.push(quote! {
+            #[inline(always)]
+            fn #method(&self, other: &Self) -> bool {
+                unsafe { #crate_root_path::detail::#thunk_ident(self, other) }
+            }
+        });
+        thunks.push(quote! {
+            pub(crate) fn #thunk_ident(lhs: *const #qualified_ident, rhs: *const #qualified_ident) -> bool;
+        });
+        thunk_impls.push(quote! {
+            extern "C" #thunk_specifiers bool #thunk_ident(
+                const #cc_name* lhs, const #cc_name* rhs
+            ) {
+                return *lhs #op_tokens *rhs;
+            }
+        });
+    }
+    Ok(GeneratedItem {
+        item: quote! {#(#methods)*},
+        thunks: quote! {#(#thunks)*},
+        thunk_impls: quote! {#(#thunk_impls)*},
+        ..Default::default()
+    })
+}
+
+/// Returns whether `Eq` is implemented for `record`, i.e. whether it has a
+/// `noexcept` `operator==` comparing it with itself.
+fn implements_eq(db: &dyn BindingsGenerator, record: &Record) -> bool {
+    db.ir()
+        .get_functions_by_name(&UnqualifiedIdentifier::Operator(Operator { name: Rc::from("==") }))
+        .filter(|func| func.is_noexcept)
+        .filter(|func| generate_func(db, (*func).clone()).ok().flatten().is_some())
+        .any(|func| {
+            let mut param_types = match func
+                .params
+                .iter()
+                .map(|param| db.rs_type_kind(param.type_.rs_type.clone()))
+                .collect::<Result<Vec<_>>>()
+            {
+                Ok(param_types) => param_types,
+                Err(_) => return false,
+            };
+            match api_func_shape(db, func, &mut param_types) {
+                Ok(Some((
+                    _,
+                    ImplKind::Trait {
+                        trait_name: TraitName::PartialEq { params },
+                        record: trait_record,
+                        ..
+                    },
+                ))) => {
+                    trait_record.id == record.id
+                        && params.get(0).map_or(false, |param| param.is_record(record))
+                }
+                _ => false,
+            }
+        })
+}
+
 /// Returns whether the given record either implements or derives the Clone
 /// trait.
 fn is_record_clonable(db: &dyn BindingsGenerator, record: Rc<Record>) -> bool {
@@ -1215,6 +1889,52 @@ fn materialize_ctor_in_caller(func: &Func, params: &mut [RsTypeKind]) {
     }
 }
 
+/// Returns the C++ name of `func`, followed by its template arguments if it is a
+/// function template specialization (e.g. `StrCat_int` for `StrCat<int>`).
+fn func_base_name(func: &Func, cc_name: &str) -> String {
+    let template_args = match &func.template_args {
+        Some(template_args) => template_args,
+        None => return cc_name.to_string(),
+    };
+    let mut words = vec![cc_name.to_string()];
+    words.extend(cc_spelling_words(template_args));
+    words.join("_")
+}
+
+/// Splits the C++ spelling of types (e.g. `<const int*, Foo&>`) into words that
+/// can be used in Rust identifiers (e.g. `const`, `int`, `ptr`, `Foo`, `ref`).
+fn cc_spelling_words(spelling: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    for c in spelling.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        match c {
+            '*' => words.push("ptr".to_string()),
+            '&' => words.push("ref".to_string()),
+            _ => {}
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Returns the name of the Rust function generated for a C++ function or method
+/// named `cc_name`, before disambiguation (see `disambiguated_func_names`).
+fn rs_func_name(db: &dyn BindingsGenerator, cc_name: &str) -> String {
+    if !db.snake_case_function_names() {
+        return cc_name.to_string();
+    }
+    to_snake_case(cc_name)
+}
+
 /// Generates Rust source code for a given `Func`.
 ///
 /// Returns:
@@ -1253,9 +1973,48 @@ fn generate_func(
         .rs_type_kind(func.return_type.rs_type.clone())
         .with_context(|| "Failed to format return type")?;
     return_type.check_by_value()?;
+    for param in &func.params {
+        ensure_renamed_keyword_is_unique(&param.identifier.identifier, |rs_name| {
+            func.params.iter().any(|other| &*other.identifier.identifier == rs_name)
+        })?;
+    }
     let param_idents =
         func.params.iter().map(|p| make_rs_ident(&p.identifier.identifier)).collect_vec();
-    let thunk = generate_func_thunk(db, &func, &param_idents, &param_types, &return_type)?;
+    if func.is_variadic {
+        return generate_variadic_func(
+            db,
+            &func,
+            func_name,
+            &impl_kind,
+            &param_idents,
+            &param_types,
+            &return_type,
+        )
+        .map(Some);
+    }
+    let catches_exceptions = catches_exceptions(db, &func, &impl_kind, &return_type);
+    let (thunk, thunk_signature) = generate_func_thunk(
+        db,
+        &func,
+        &param_idents,
+        &param_types,
+        &return_type,
+        catches_exceptions,
+    )?;
+    if func.name == UnqualifiedIdentifier::Constructor {
+        if let ImplKind::Struct { record, .. } = &impl_kind {
+            return generate_ctor_builder(
+                db,
+                &func,
+                record,
+                &param_idents,
+                &param_types,
+                thunk,
+                thunk_signature,
+            )
+            .map(Some);
+        }
+    }
 
     // If the Rust trait require a function to take the params by const reference
     // and the thunk takes some of its params by value then we should add a const
@@ -1281,7 +2040,11 @@ fn generate_func(
                                 func,
                             );
                         }
-                        clone_prefixes.push(quote!{&mut});
+                        if is_passed_to_thunk_by_value(db, &func, &param_type) {
+                            clone_prefixes.push(quote!{});
+                        } else {
+                            clone_prefixes.push(quote!{&mut});
+                        }
                         clone_suffixes.push(quote!{.clone()});
                         Ok(RsTypeKind::Reference {
                             referent: Rc::new(param_type.clone()),
@@ -1304,11 +2067,13 @@ fn generate_func(
 
     let BindingsSignature {
         lifetimes,
-        params: api_params,
+        params: mut api_params,
         return_type_fragment: mut quoted_return_type,
         thunk_prepare,
         thunk_args,
+        return_conversion,
     } = function_signature(
+        db,
         &mut features,
         &func,
         &impl_kind,
@@ -1317,6 +2082,76 @@ fn generate_func(
         &mut return_type,
     )?;
 
+    // With `--default_arguments=option`, the parameters with default arguments
+    // are `Option`s, and a `None` argument calls the thunk of the function without
+    // that parameter and the parameters after it (see `generate_item_impl`).
+    let funcs_without_default_args = if binds_default_args_as_options(db, &func) {
+        func.with_omitted_default_args()
+    } else {
+        vec![]
+    };
+    let optional_param_idents =
+        &param_idents[param_idents.len() - funcs_without_default_args.len()..];
+    for (i, ident) in optional_param_idents.iter().enumerate() {
+        let api_param_index = api_params.len() - optional_param_idents.len() + i;
+        let param_type = &param_types[param_types.len() - optional_param_idents.len() + i];
+        let param_type = match &impl_kind {
+            ImplKind::Struct { record, .. } => {
+                param_type.to_token_stream_replacing_by_self(Some(record))
+            }
+            _ => quote! {#param_type},
+        };
+        api_params[api_param_index] = quote! {#ident: ::core::option::Option<#param_type>};
+    }
+    let cloned_thunk_args = clone_prefixes
+        .iter()
+        .zip(&thunk_args)
+        .zip(&clone_suffixes)
+        .map(|((prefix, arg), suffix)| quote! {#prefix #arg #suffix})
+        .collect_vec();
+    // Calls the thunk with the `leading_args` (e.g. the return value slot),
+    // followed by the `args` of the parameters.
+    let call_thunk = |leading_args: Vec<TokenStream>, args: &[TokenStream]| {
+        if optional_param_idents.is_empty() {
+            let thunk_ident = thunk_ident(&func);
+            let args = leading_args.iter().chain(args);
+            return quote! { #crate_root_path::detail::#thunk_ident( #( #args ),* ) };
+        }
+        let num_optional_params = optional_param_idents.len();
+        let arms = (0..=num_optional_params).rev().map(|num_passed| {
+            let num_omitted = num_optional_params - num_passed;
+            let thunk_ident = match num_omitted {
+                0 => thunk_ident(&func),
+                _ => thunk_ident(&funcs_without_default_args[num_omitted - 1]),
+            };
+            let patterns = optional_param_idents.iter().enumerate().map(|(i, ident)| {
+                if i < num_passed {
+                    quote! { ::core::option::Option::Some(#ident) }
+                } else {
+                    quote! { ::core::option::Option::None }
+                }
+            });
+            let args = leading_args.iter().chain(&args[..args.len() - num_omitted]);
+            quote! {
+                ( #( #patterns, )* ) => #crate_root_path::detail::#thunk_ident( #( #args ),* ),
+            }
+        });
+        // C++ can only omit trailing arguments, so `Some` after `None` is an error.
+        let invalid_arm = if num_optional_params > 1 {
+            quote! {
+                _ => ::core::panic!("Only trailing default arguments can be `None`"),
+            }
+        } else {
+            quote! {}
+        };
+        quote! {
+            match ( #( #optional_param_idents, )* ) {
+                #( #arms )*
+                #invalid_arm
+            }
+        }
+    };
+
     let api_func_def = {
         let thunk_ident = thunk_ident(&func);
         let func_body = match &impl_kind {
@@ -1329,10 +2164,11 @@ fn generate_func(
                 // change once the bindings generator starts supporting
                 // reference fields). TODO(b/213243309): Double-check if
                 // zero-initialization is desirable here.
+                let tmp_arg = format_thunk_out_arg(db, &make_rs_ident("tmp"));
                 quote! {
                     let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
                     unsafe {
-                        #crate_root_path::detail::#thunk_ident( &mut tmp #( , #thunk_args )* );
+                        #crate_root_path::detail::#thunk_ident( #tmp_arg #( , #thunk_args )* );
                         tmp.assume_init()
                     }
                 }
@@ -1343,11 +2179,48 @@ fn generate_func(
                 // not generate the thunk at all, but this would be a bit of extra work.
                 //
                 // TODO(jeanpierreda): separately handle non-Unpin and non-trivial types.
-                let mut body = if return_type.is_c_abi_compatible_by_value() {
-                    quote! {
-                        #crate_root_path::detail::#thunk_ident(
-                            #( #clone_prefixes #thunk_args #clone_suffixes ),*
-                        )
+
+                // When catching exceptions, the thunk stores them into `__exception`,
+                // which is checked before the return value is touched.
+                let (exception_arg, exception_decl) = if catches_exceptions {
+                    (
+                        vec![quote! { &mut __exception }],
+                        quote! { let mut __exception = ::cxx_exception::ExceptionPtr::null(); },
+                    )
+                } else {
+                    (vec![], quote! {})
+                };
+                let convert_return = |value: TokenStream| {
+                    let value = format_nonnull_return_conversion(&func, &return_type, value);
+                    let value = match &return_conversion {
+                        Some(return_conversion) => quote! {#return_conversion(#value)},
+                        None => value,
+                    };
+                    if catches_exceptions {
+                        quote! {
+                            __exception.into_result()?;
+                            ::core::result::Result::Ok(#value)
+                        }
+                    } else {
+                        value
+                    }
+                };
+                let mut body = if is_returned_from_thunk_by_value(
+                    db,
+                    &func,
+                    &return_type,
+                    catches_exceptions,
+                ) {
+                    let call = call_thunk(exception_arg, &cloned_thunk_args);
+                    if catches_exceptions {
+                        let return_value = convert_return(quote! {__return_value});
+                        quote! {
+                            #exception_decl
+                            let __return_value = #call;
+                            #return_value
+                        }
+                    } else {
+                        convert_return(call)
                     }
                 } else {
                     let return_type_or_self = {
@@ -1361,28 +2234,33 @@ fn generate_func(
                         return_type.to_token_stream_replacing_by_self(record)
                     };
                     if return_type.is_unpin() {
+                        let return_value = convert_return(quote! {__return.assume_init()});
+                        let return_arg = format_thunk_out_arg(db, &make_rs_ident("__return"));
+                        let call = call_thunk(
+                            exception_arg.into_iter().chain([return_arg]).collect(),
+                            &cloned_thunk_args,
+                        );
                         quote! {
+                            #exception_decl
                             let mut __return =
                                 ::core::mem::MaybeUninit::<#return_type_or_self>::uninit();
-                            #crate_root_path::detail::#thunk_ident(
-                                &mut __return
-                                #( , #clone_prefixes #thunk_args #clone_suffixes )*
-                            );
-                            __return.assume_init()
+                            #call;
+                            #return_value
                         }
                     } else {
                         // TODO(b/200067242): the Pin-wrapping code doesn't know to wrap &mut
                         // MaybeUninit<T> in Pin if T is !Unpin. It should understand
                         // 'structural pinning', so that we do not need into_inner_unchecked()
                         // here.
+                        let call = call_thunk(
+                            vec![quote! { ::core::pin::Pin::into_inner_unchecked(dest) }],
+                            &thunk_args,
+                        );
                         quote! {
                             ::ctor::FnCtor::new(
                                 move |dest: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<
                                                                         #return_type_or_self>>| {
-                                #crate_root_path::detail::#thunk_ident(
-                                    ::core::pin::Pin::into_inner_unchecked(dest)
-                                    #( , #thunk_args )*
-                                );
+                                #call;
                             })
                         }
                     }
@@ -1405,6 +2283,16 @@ fn generate_func(
                     let _ = return_type; // proof that we don't need to update it.
                     quoted_return_type = quote! {};
                 }
+                if catches_exceptions {
+                    let value_type = if quoted_return_type.is_empty() {
+                        quote! {()}
+                    } else {
+                        quoted_return_type
+                    };
+                    quoted_return_type = quote! {
+                        ::core::result::Result<#value_type, ::cxx_exception::CxxException>
+                    };
+                }
                 // Only need to wrap everything in an `unsafe { ... }` block if
                 // the *whole* api function is safe.
                 if !impl_kind.is_unsafe() {
@@ -1469,25 +2357,109 @@ fn generate_func(
             quote! {}
         };
 
+        // With `--snake_case_function_names`, the original C++ name is kept as a
+        // hidden alias, so that existing callers don't break.
+        let original_name_alias = match (&func.name, &impl_kind) {
+            (
+                UnqualifiedIdentifier::Identifier(id),
+                ImplKind::None { .. } | ImplKind::Struct { .. },
+            ) if make_rs_ident(&rs_func_name(db, &id.identifier))
+                != make_rs_ident(&id.identifier)
+                && func.rust_name.is_none()
+                && func.omitted_default_params.is_empty()
+                && !db
+                    .disambiguated_func_names()
+                    .get(&func.id)
+                    .map_or(false, |name| name.is_overload) =>
+            {
+                let original_name = make_rs_ident(&id.identifier);
+                let callee = match impl_kind {
+                    ImplKind::Struct { .. } => quote! { Self::#func_name },
+                    _ => quote! { #func_name },
+                };
+                let mut args = param_idents.iter().map(|ident| quote! { #ident }).collect_vec();
+                if impl_kind.format_first_param_as_self() {
+                    args[0] = quote! { self };
+                }
+                quote! {
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    #pub_ #unsafe_ fn #original_name #fn_generic_params(
+                            #( #api_params ),* ) #arrow #function_return_type {
+                        #callee(#(#args),*)
+                    }
+                }
+            }
+            _ => quote! {},
+        };
+
         quote! {
             #[inline(always)]
             #pub_ #unsafe_ fn #func_name #fn_generic_params(
                     #( #api_params ),* ) #arrow #function_return_type {
                 #func_body
             }
+            #original_name_alias
         }
     };
 
+    let disambiguation_note = match (&func.name, db.disambiguated_func_names().get(&func.id)) {
+        (UnqualifiedIdentifier::Identifier(id), Some(disambiguated_name))
+            if func.rust_name.is_none() =>
+        {
+            if disambiguated_name.is_overload {
+                Some(format!(
+                    "Bound as `{func_name}`, because the C++ function `{}` is overloaded.",
+                    id.identifier
+                ))
+            } else {
+                Some(format!(
+                    "Bound as `{func_name}`, because the Rust name of the C++ function `{}` \
+                     collides with another function.",
+                    id.identifier
+                ))
+            }
+        }
+        _ => None,
+    };
+    let doc_comment = match (func.doc_comment.as_deref(), disambiguation_note.as_deref()) {
+        (Some(comment), Some(note)) => Some(format!("{comment}\n\n{note}")),
+        (comment, note) => comment.or(note).map(str::to_string),
+    };
     let doc_comment = generate_doc_comment(
-        func.doc_comment.as_deref(),
+        doc_comment.as_deref(),
         Some(&func.source_loc),
         db.generate_source_loc_doc_comment(),
     );
+    let deprecated = generate_deprecated_attr(func.deprecated.as_deref());
+    let mut errno_wrapper = GeneratedItem::default();
+    if func.sets_errno {
+        ensure!(
+            matches!(impl_kind, ImplKind::None { .. }),
+            "`--errno_functions` only supports free functions"
+        );
+        errno_wrapper = generate_errno_wrapper(
+            db,
+            &func,
+            &func_name,
+            &lifetimes,
+            &api_params,
+            &thunk_args,
+            &param_types,
+            &return_type,
+            impl_kind.is_unsafe(),
+        )?;
+    }
+    // The `PartialOrd` methods implemented by `operator<=`, `operator>` and
+    // `operator>=`, if this function implements `PartialOrd::lt`.
+    let mut relational_overrides = GeneratedItem::default();
     let api_func: TokenStream;
     let function_id: FunctionId;
     match impl_kind {
         ImplKind::None { .. } => {
-            api_func = quote! { #doc_comment #api_func_def };
+            let errno_wrapper_item = &errno_wrapper.item;
+            api_func = quote! { #doc_comment #deprecated #api_func_def #errno_wrapper_item };
             function_id = FunctionId {
                 self_type: None,
                 function_path: syn::parse2(quote! { #namespace_qualifier #func_name }).unwrap(),
@@ -1495,7 +2467,7 @@ fn generate_func(
         }
         ImplKind::Struct { record, .. } => {
             let record_name = make_rs_ident(record.rs_name.as_ref());
-            api_func = quote! { impl #record_name { #doc_comment #api_func_def } };
+            api_func = quote! { impl #record_name { #doc_comment #deprecated #api_func_def } };
             function_id = FunctionId {
                 self_type: None,
                 function_path: syn::parse2(quote! {
@@ -1521,12 +2493,27 @@ fn generate_func(
                 quote! {
                     type #name = #quoted_return_type;
                 }
+            } else if matches!(
+                &trait_name,
+                TraitName::Other { name, .. } if &**name == "::core::ops::Index"
+            ) {
+                let output = match &return_type {
+                    RsTypeKind::Reference { referent, .. } => referent,
+                    _ => bail!("Index::index must return a reference"),
+                };
+                quote! {
+                    type Output = #output;
+                }
             } else if let TraitName::PartialOrd { ref params } = trait_name {
                 let param = params.get(0).ok_or_else(|| anyhow!("No parameter to PartialOrd"))?;
                 let quoted_param_or_self = match impl_for {
                     ImplFor::T => param.to_token_stream_replacing_by_self(Some(&trait_record)),
                     ImplFor::RefT => quote! { #param },
                 };
+                if impl_for == ImplFor::T {
+                    relational_overrides = relational_operator_overrides(db, &trait_record)?;
+                }
+                let overrides = &relational_overrides.item;
                 quote! {
                     #[inline(always)]
                     fn partial_cmp(&self, other: & #quoted_param_or_self) -> Option<core::cmp::Ordering> {
@@ -1541,6 +2528,7 @@ fn generate_func(
                         }
                         None
                     }
+                    #overrides
                 }
             } else {
                 quote! {}
@@ -1572,6 +2560,32 @@ fn generate_func(
                         extra_items = quote! {}
                     }
                 }
+                // `Eq` can't be checked, but a non-throwing `operator==` between values of
+                // the same type is assumed to be an equivalence relation, as it is in C++'s
+                // `std::equality_comparable` concept.
+                TraitName::PartialEq { params }
+                    if func.is_noexcept
+                        && impl_for == ImplFor::T
+                        && matches!(
+                            params.get(0),
+                            Some(RsTypeKind::Record { record, .. }) if *record == trait_record
+                        ) =>
+                {
+                    extra_items = quote! {
+                        impl Eq for #record_name {}
+                    };
+                }
+                // Also expose the default constructor as the conventional `new()`.
+                TraitName::UnpinConstructor { name, .. } if &**name == "Default" => {
+                    extra_items = quote! {
+                        impl #record_name {
+                            #[inline(always)]
+                            pub fn new() -> Self {
+                                Default::default()
+                            }
+                        }
+                    };
+                }
                 _ => {
                     extra_items = quote! {};
                 }
@@ -1603,132 +2617,730 @@ fn generate_func(
         }
     }
 
+    let errno_thunk = &errno_wrapper.thunks;
+    let errno_thunk_impl = &errno_wrapper.thunk_impls;
+    let override_thunks = &relational_overrides.thunks;
+    let override_thunk_impls = &relational_overrides.thunk_impls;
+    let thunk_impl = generate_func_thunk_impl(db, &func, catches_exceptions)?;
+    let span_assertions = gsl_span_layout_assertions(&func, &ir)?;
     let generated_item = GeneratedItem {
         item: api_func,
-        thunks: thunk,
+        thunks: quote! { #thunk #errno_thunk #override_thunks },
         features,
-        thunk_impls: generate_func_thunk_impl(db, &func)?,
+        thunk_impls: quote! {
+            #span_assertions #thunk_impl #errno_thunk_impl #override_thunk_impls
+        },
+        extern_crates: errno_wrapper.extern_crates,
+        thunk_signature: Some(Rc::new(thunk_signature)),
         ..Default::default()
     };
     Ok(Some((Rc::new(generated_item), Rc::new(function_id))))
 }
 
-/// The function signature for a function's bindings.
-struct BindingsSignature {
-    /// The lifetime parameters for the Rust function.
-    lifetimes: Vec<Lifetime>,
-
-    /// The parameter list for the Rust function.
-    ///
-    /// For example, `vec![quote!{self}, quote!{x: &i32}]`.
-    params: Vec<TokenStream>,
-
-    /// The return type fragment of the Rust function, as a token stream.
-    ///
-    /// This is the same as the actual return type, except that () is the empty
-    /// tokens, non-Unpin by-value types are `impl Ctor<Output=#return_type> +
-    /// ...`, and wherever the type is the type of `Self`, it gets replaced by
-    /// literal `Self`.
-    return_type_fragment: TokenStream,
-
-    /// Any preparation code to define the arguments in `thunk_args`.
-    thunk_prepare: TokenStream,
-
-    /// The arguments passed to the thunk, expressed in terms of `params`.
-    thunk_args: Vec<TokenStream>,
-}
-
-/// Reformats API parameters and return values to match Rust conventions and the
-/// trait requirements.
+/// Generates the `try_` wrapper of a function that returns -1 (or `NULL`) and
+/// sets `errno` on failure (see `--errno_functions`), e.g. for `int close(int)`:
 ///
-/// For example:
+/// ```ignore
+/// pub fn try_close(fd: c_int) -> Result<c_int, c_int>
+/// ```
 ///
-/// * Use the `self` keyword for the this pointer.
-/// * Use `Self` for the return value of constructor traits.
-/// * For C++ constructors, remove `self` from the Rust side (as it becomes the
-///   return value), retaining it on the C++ side / thunk args.
-/// * serialize a `()` as the empty string.
-fn function_signature(
-    features: &mut BTreeSet<Ident>,
+/// The error is the raw `errno`, unless `--errno_std_io_error` is set, in which
+/// case it is a `std::io::Error` (and the bindings depend on `std`).
+///
+/// `errno` is read by a dedicated C++ thunk, right after the call, so that no
+/// Rust code can clobber it in between.
+///
+/// Only free functions that return a signed integer or a pointer are
+/// supported, anything else is an error rather than a missing wrapper.
+#[allow(clippy::too_many_arguments)]
+fn generate_errno_wrapper(
+    db: &dyn BindingsGenerator,
     func: &Func,
-    impl_kind: &ImplKind,
-    param_idents: &[Ident],
-    param_types: &mut Vec<RsTypeKind>,
-    return_type: &mut RsTypeKind,
-) -> Result<BindingsSignature> {
-    let mut api_params = Vec::with_capacity(func.params.len());
-    let mut thunk_args = Vec::with_capacity(func.params.len());
-    let mut thunk_prepare = quote! {};
-    let impl_kind_record = match impl_kind {
-        ImplKind::Struct { record, .. } | ImplKind::Trait { record, impl_for: ImplFor::T, .. } => {
-            Some(record)
+    func_name: &Ident,
+    lifetimes: &[Lifetime],
+    api_params: &[TokenStream],
+    thunk_args: &[TokenStream],
+    param_types: &[RsTypeKind],
+    return_type: &RsTypeKind,
+    is_unsafe: bool,
+) -> Result<GeneratedItem> {
+    let ir = db.ir();
+    let crate_root_path = crate_root_path_tokens(&ir);
+    for (i, param_type) in param_types.iter().enumerate() {
+        ensure!(
+            param_type.is_c_abi_compatible_by_value(),
+            "`--errno_functions` doesn't support parameter #{i} of type '{}'",
+            quote! {#param_type}
+        );
+    }
+    ensure!(
+        return_type.is_c_abi_compatible_by_value(),
+        "`--errno_functions` doesn't support the return type '{}'",
+        quote! {#return_type}
+    );
+
+    let thunk_ident = format_ident!("{}__errno", thunk_ident(func));
+    let wrapper_name = format_ident!("try_{}", func_name.to_string().trim_start_matches("r#"));
+    let generic_params = format_generic_params(lifetimes, std::iter::empty::<syn::Ident>());
+    let param_idents =
+        func.params.iter().map(|p| make_rs_ident(&p.identifier.identifier)).collect_vec();
+    // A pointer annotated as `_Nonnull` is returned as a raw pointer by the thunk
+    // (see `nonnull_return_pointee`).
+    let nonnull_pointee = nonnull_return_pointee(func, return_type);
+    let failed = if matches!(return_type, RsTypeKind::Other { name, .. } if &**name == "Option") {
+        // A pointer annotated as `_Nullable`, bound as `Option<NonNull<T>>`.
+        quote! { __result.is_none() }
+    } else if nonnull_pointee.is_some() || return_type.is_raw_pointer() {
+        quote! { __result.is_null() }
+    } else if return_type.is_signed_integer() {
+        quote! { __result == -1 }
+    } else {
+        bail!(
+            "`--errno_functions` only supports functions that return a signed integer or a \
+             pointer, but the return type is '{}'",
+            quote! {#return_type}
+        );
+    };
+    let mut extern_crates = BTreeSet::new();
+    let (error_type, error) = if db.errno_std_io_error() {
+        extern_crates.insert(format_ident!("std"));
+        (quote! { ::std::io::Error }, quote! { ::std::io::Error::from_raw_os_error(__errno) })
+    } else {
+        (quote! { ::core::ffi::c_int }, quote! { __errno })
+    };
+    let mut result = format_nonnull_return_conversion(func, return_type, quote! { __result });
+    let mut call = quote! {
+        #crate_root_path::detail::#thunk_ident( #( #thunk_args, )* &mut __errno )
+    };
+    let unsafe_ = if is_unsafe {
+        quote! { unsafe }
+    } else {
+        call = quote! { unsafe { #call } };
+        if nonnull_pointee.is_some() && matches!(return_type, RsTypeKind::Reference { .. }) {
+            // `NonNull::as_ref` and `NonNull::as_mut` are unsafe.
+            result = quote! { unsafe { #result } };
         }
-        _ => None,
+        quote! {}
     };
-    for (i, (ident, type_)) in param_idents.iter().zip(param_types.iter()).enumerate() {
-        type_.check_by_value()?;
-        if !type_.is_unpin() {
-            // `impl Ctor` will fail to compile in a trait.
-            // This will only be hit if there was a bug in api_func_shape.
-            if let ImplKind::Trait { .. } = &impl_kind {
-                panic!(
-                    "non-Unpin types cannot work by value in traits; this should have instead \
-                        become an rvalue reference to force the caller to materialize the Ctor."
-                );
-            }
-            // The generated bindings require a move constructor.
-            if !type_.is_move_constructible() {
-                bail!("Non-movable, non-trivial_abi type '{type}' is not supported by value as parameter #{i}", type=quote!{#type_});
-            }
-            let quoted_type_or_self = if let Some(impl_record) = impl_kind_record {
-                type_.to_token_stream_replacing_by_self(Some(impl_record))
-            } else {
-                quote! {#type_}
-            };
-            features.insert(make_rs_ident("impl_trait_in_assoc_type"));
-            api_params.push(quote! {#ident: impl ::ctor::Ctor<Output=#quoted_type_or_self>});
-            thunk_args
-                .push(quote! {::core::pin::Pin::into_inner_unchecked(::ctor::emplace!(#ident))});
-        } else {
-            let quoted_type_or_self = if let Some(impl_record) = impl_kind_record {
-                type_.to_token_stream_replacing_by_self(Some(impl_record))
-            } else {
-                quote! {#type_}
-            };
-            if type_.is_c_abi_compatible_by_value() {
-                api_params.push(quote! {#ident: #quoted_type_or_self});
-                thunk_args.push(quote! {#ident});
+    let doc_comment = format!(
+        " Calls `{func_name}`, and returns the `errno` that it sets if it returns {}.",
+        if nonnull_pointee.is_some() || return_type.is_raw_pointer() { "`NULL`" } else { "-1" }
+    );
+    let deprecated = generate_deprecated_attr(func.deprecated.as_deref());
+    let item = quote! {
+        #[doc = #doc_comment]
+        #deprecated
+        #[inline(always)]
+        pub #unsafe_ fn #wrapper_name #generic_params( #( #api_params ),* )
+            -> ::core::result::Result<#return_type, #error_type> {
+            let mut __errno: ::core::ffi::c_int = 0;
+            let __result = #call;
+            if #failed {
+                ::core::result::Result::Err(#error)
             } else {
-                api_params.push(quote! {mut #ident: #quoted_type_or_self});
-                thunk_args.push(quote! {&mut #ident});
+                ::core::result::Result::Ok(#result)
             }
         }
-    }
+    };
 
-    let mut lifetimes: Vec<Lifetime> = unique_lifetimes(&*param_types).collect();
+    let thunk_return_type = match nonnull_pointee {
+        Some(pointee) => quote! { *mut #pointee },
+        None => quote! { #return_type },
+    };
+    let thunks = quote! {
+        pub(crate) fn #thunk_ident #generic_params(
+            #( #param_idents: #param_types, )* __errno: &mut ::core::ffi::c_int
+        ) -> #thunk_return_type;
+    };
 
-    let mut quoted_return_type = None;
-    if let ImplKind::Trait {
-        trait_name: trait_name @ (TraitName::UnpinConstructor { .. } | TraitName::CtorNew(..)),
-        ..
-    } = &impl_kind
-    {
-        // For constructors, we move the output parameter to be the return value.
-        // The return value is "really" void.
-        ensure!(
-            func.return_type.rs_type.is_unit_type(),
-            "Unexpectedly non-void return type of a constructor"
-        );
+    let fn_ident = match &func.name {
+        UnqualifiedIdentifier::Identifier(id) => format_cc_ident(&id.identifier),
+        _ => bail!("`--errno_functions` only supports functions with a name"),
+    };
+    let namespace_qualifier = namespace_qualifier_of_item(func.id, &ir)?.format_for_cc()?;
+    let cc_param_idents =
+        func.params.iter().map(|p| format_cc_ident(&p.identifier.identifier)).collect_vec();
+    let cc_param_types = func
+        .params
+        .iter()
+        .map(|p| format_cc_type(&p.type_.cc_type, &ir))
+        .collect::<Result<Vec<_>>>()?;
+    let cc_return_type = format_cc_type(&func.return_type.cc_type, &ir)?;
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+    let noexcept = format_cc_thunk_noexcept(db, func);
+    let thunk_impls = quote! {
+        extern "C" #thunk_specifiers #cc_return_type #thunk_ident(
+            #( #cc_param_types #cc_param_idents, )* int* __errno
+        ) #noexcept {
+            errno = 0;
+            #cc_return_type __result = #namespace_qualifier #fn_ident( #( #cc_param_idents ),* );
+            *__errno = errno;
+            return __result;
+        }
+    };
+    Ok(GeneratedItem { item, thunks, thunk_impls, extern_crates, ..Default::default() })
+}
 
-        //  Presence of element #0 is indirectly verified by a `Constructor`-related
-        // `match` branch a little bit above.
-        *return_type = param_types[0]
-            .referent()
-            .ok_or_else(|| anyhow!("Expected pointer/reference for `__this` parameter"))?
-            .clone();
-        quoted_return_type = Some(quote! {Self});
+/// Returns the options struct that a constructor taking a single parameter of
+/// type `param_type` (by value or by `const` reference) is bound to a builder
+/// for, if `param_type` is such an options struct: an `Unpin` aggregate with a
+/// default constructor and only public, named fields of supported types.
+fn options_struct_of_ctor_param(
+    db: &dyn BindingsGenerator,
+    param_type: &RsTypeKind,
+) -> Option<Rc<Record>> {
+    let record = match param_type {
+        RsTypeKind::Record { record, .. } => record,
+        RsTypeKind::Reference { referent, mutability: Mutability::Const, .. } => {
+            match &**referent {
+                RsTypeKind::Record { record, .. } => record,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let ir = db.ir();
+    let has_default_constructor = record.child_item_ids.iter().any(|id| {
+        matches!(
+            ir.find_decl::<Rc<Func>>(*id),
+            Ok(func) if func.name == UnqualifiedIdentifier::Constructor && func.params.len() == 1
+        )
+    });
+    let has_supported_fields = !record.fields.is_empty()
+        && record.fields.iter().all(|field| {
+            field.access == AccessSpecifier::Public
+                && field.identifier.is_some()
+                && !field.is_bitfield
+                && !field.is_no_unique_address
+                && matches!(
+                    &field.type_,
+                    Ok(type_) if db.rs_type_kind(type_.rs_type.clone())
+                        .is_ok_and(|type_| type_.is_unpin() && type_.lifetimes().next().is_none())
+                )
+        });
+    if record.is_aggregate
+        && record.is_unpin()
+        && !record.is_union()
+        && has_default_constructor
+        && has_supported_fields
+    {
+        Some(record.clone())
+    } else {
+        None
+    }
+}
 
-        // Grab the `__this` lifetime to remove it from the lifetime parameters.
+/// Returns whether the constructor `func` is bound as a builder type (see
+/// `generate_ctor_builder`).
+fn is_builder_ctor(db: &dyn BindingsGenerator, func: &Func) -> bool {
+    let min_params = db.constructor_builder_min_params();
+    if func.name != UnqualifiedIdentifier::Constructor || min_params <= 0 {
+        return false;
+    }
+    match &func.params[..] {
+        // The copy constructor of an aggregate isn't bound as a builder.
+        [_this, param] => db.rs_type_kind(param.type_.rs_type.clone()).is_ok_and(|param_type| {
+            options_struct_of_ctor_param(db, &param_type).is_some_and(|options| {
+                func.member_func_metadata.as_ref().map(|meta| meta.record_id) != Some(options.id)
+            })
+        }),
+        [_this, params @ ..] => params.len() >= min_params as usize,
+        [] => false,
+    }
+}
+
+/// Returns whether an enum, record or type alias of the current target, other
+/// than `except`, is named `rs_name` in Rust and is declared in the namespace
+/// `enclosing_namespace_id`.
+fn is_type_name_taken(
+    ir: &IR,
+    enclosing_namespace_id: Option<ItemId>,
+    rs_name: &str,
+    except: ItemId,
+) -> bool {
+    ir.items().any(|item| {
+        let name: &str = match item {
+            Item::Record(record) => &record.rs_name,
+            Item::IncompleteRecord(record) => &record.rs_name,
+            Item::Enum(enum_) => &enum_.identifier.identifier,
+            Item::TypeAlias(type_alias) => &type_alias.identifier.identifier,
+            _ => return false,
+        };
+        name == rs_name
+            && item.id() != except
+            && item.enclosing_namespace_id() == enclosing_namespace_id
+            && item.owning_target().is_some_and(|target| ir.is_current_target(target))
+    })
+}
+
+/// Returns an error if `record` is nested in another record of the current
+/// target, and the flattened name that it is bound as (e.g. `Outer_Inner` for
+/// `Outer::Inner`) is already the name of a type in the same namespace.
+fn ensure_nested_record_name_is_unique(ir: &IR, record: &Record) -> Result<()> {
+    if !ir.is_current_target(&record.owning_target)
+        || !ir.records().any(|other| other.child_item_ids.contains(&record.id))
+    {
+        return Ok(());
+    }
+    ensure!(
+        !is_type_name_taken(ir, record.enclosing_namespace_id, &record.rs_name, record.id),
+        "The nested record `{}` would be bound as `{}`, which is already the name of another \
+         declaration",
+        record.cc_name,
+        record.rs_name
+    );
+    Ok(())
+}
+
+/// Returns an error if `name` is a Rust keyword that `make_rs_ident` renames
+/// by appending an underscore, because it can't be a raw identifier (e.g.
+/// `self` becomes `self_`), and `is_taken` returns true for the new name.
+///
+/// Functions don't need this check, because `disambiguated_func_names` already
+/// renames them if they collide.
+fn ensure_renamed_keyword_is_unique(name: &str, is_taken: impl FnOnce(&str) -> bool) -> Result<()> {
+    let rs_name = make_rs_ident(name).to_string();
+    ensure!(
+        rs_name != format!("{name}_") || !is_taken(&rs_name),
+        "`{name}` is a Rust keyword, so it would be renamed to `{rs_name}`, which is already \
+         the name of another declaration"
+    );
+    Ok(())
+}
+
+/// Generates a builder type for a constructor of an `Unpin` record with at
+/// least `--constructor_builder_min_params` parameters.
+///
+/// For example, `Foo(int a, double b)` is bound as:
+///
+/// ```ignore
+/// let foo = Foo::builder().a(1).b(2.0).build();
+/// ```
+///
+/// `build` panics if any of the parameters hasn't been set.
+///
+/// A constructor that takes an options struct instead (see
+/// `options_struct_of_ctor_param`), e.g. `Foo(const FooOptions& options)`, gets
+/// a builder with a setter for each field of the options struct. The fields
+/// that aren't set keep their default value.
+///
+/// At most one constructor of a record can be bound as a builder, and the
+/// builder type must not collide with a C++ type.
+fn generate_ctor_builder(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    record: &Rc<Record>,
+    param_idents: &[Ident],
+    param_types: &[RsTypeKind],
+    thunk: TokenStream,
+    thunk_signature: ThunkSignature,
+) -> Result<(Rc<GeneratedItem>, Rc<FunctionId>)> {
+    let ir = db.ir();
+    let crate_root_path = crate_root_path_tokens(&ir);
+    let namespace_qualifier = namespace_qualifier_of_item(func.id, &ir)?.format_for_rs();
+    let record_name = make_rs_ident(record.rs_name.as_ref());
+    let builder_name = make_rs_ident(&format!("{}Builder", record.rs_name));
+    let thunk_ident = thunk_ident(func);
+
+    ensure!(
+        !is_type_name_taken(
+            &ir,
+            record.enclosing_namespace_id,
+            &builder_name.to_string(),
+            record.id
+        ),
+        "The constructor builder `{builder_name}` collides with a C++ type of the same name"
+    );
+    let mut builder_ctors = 0;
+    for id in &record.child_item_ids {
+        let Ok(member) = ir.find_decl::<Rc<Func>>(*id) else { continue };
+        if is_builder_ctor(db, member) {
+            builder_ctors += 1;
+        }
+        if let UnqualifiedIdentifier::Identifier(name) = &member.name {
+            ensure!(
+                &*name.identifier != "builder",
+                "The constructor builder collides with the method `{}::builder`",
+                record.cc_name
+            );
+        }
+    }
+    ensure!(
+        builder_ctors == 1,
+        "{builder_ctors} constructors of `{}` would be bound as `{builder_name}`, only one is \
+         supported",
+        record.cc_name
+    );
+
+    // Skip the `__this` parameter.
+    let param_idents = &param_idents[1..];
+    let param_types = &param_types[1..];
+    let mut generic_params = quote! {};
+    let builder_fields: Vec<TokenStream>;
+    let setters: Vec<TokenStream>;
+    let thunk_args: Vec<TokenStream>;
+    let mut build_prepare = vec![];
+    let doc_comment;
+    if let (Some(options), [param_ident], [param_type]) = (
+        param_types.first().and_then(|param_type| options_struct_of_ctor_param(db, param_type)),
+        param_idents,
+        param_types,
+    ) {
+        let options_type = RsTypeKind::new_record(options.clone(), &ir)?;
+        let mut field_idents = vec![];
+        let mut field_types = vec![];
+        for field in &options.fields {
+            // Checked by `options_struct_of_ctor_param`.
+            let (Some(identifier), Ok(type_)) = (&field.identifier, &field.type_) else {
+                bail!("Unsupported field in the options struct `{}`", options.cc_name);
+            };
+            field_idents.push(make_rs_ident(&identifier.identifier));
+            field_types.push(db.rs_type_kind(type_.rs_type.clone())?);
+        }
+        ensure!(
+            field_idents.iter().all(|ident| ident != "build"),
+            "The field `{}::build` collides with `{builder_name}::build`",
+            options.cc_name
+        );
+        builder_fields = vec![quote! { #param_ident: #options_type }];
+        setters = field_idents
+            .iter()
+            .zip(&field_types)
+            .map(|(ident, type_)| {
+                quote! {
+                    #[inline(always)]
+                    pub fn #ident(mut self, #ident: #type_) -> Self {
+                        self.#param_ident.#ident = #ident;
+                        self
+                    }
+                }
+            })
+            .collect();
+        if matches!(param_type, RsTypeKind::Reference { .. }) {
+            build_prepare.push(quote! { let #param_ident = self.#param_ident; });
+            thunk_args = vec![quote! { &#param_ident }];
+        } else if param_type.is_c_abi_compatible_by_value() {
+            build_prepare.push(quote! { let #param_ident = self.#param_ident; });
+            thunk_args = vec![quote! { #param_ident }];
+        } else {
+            build_prepare.push(quote! { let mut #param_ident = self.#param_ident; });
+            thunk_args = vec![quote! { &mut #param_ident }];
+        }
+        doc_comment = format!(
+            " Builder for the `{}` constructor that takes a `{}`.",
+            record.cc_name, options.cc_name
+        );
+    } else {
+        for (i, param_type) in param_types.iter().enumerate() {
+            param_type.check_by_value()?;
+            ensure!(
+                param_type.is_unpin(),
+                "Non-Unpin type '{}' is not supported by constructor builders as parameter #{i}",
+                quote! {#param_type}
+            );
+        }
+        ensure!(
+            param_idents.iter().all(|ident| ident != "build"),
+            "The parameter `build` collides with `{builder_name}::build`"
+        );
+        let lifetimes = unique_lifetimes(param_types).collect_vec();
+        generic_params = format_generic_params(&lifetimes, std::iter::empty::<syn::Ident>());
+        builder_fields = param_idents
+            .iter()
+            .zip(param_types)
+            .map(|(ident, type_)| quote! { #ident: ::core::option::Option<#type_> })
+            .collect();
+        setters = param_idents
+            .iter()
+            .zip(param_types)
+            .map(|(ident, type_)| {
+                quote! {
+                    #[inline(always)]
+                    pub fn #ident(mut self, #ident: #type_) -> Self {
+                        self.#ident = ::core::option::Option::Some(#ident);
+                        self
+                    }
+                }
+            })
+            .collect();
+        let mut args = vec![];
+        for (ident, param_type) in param_idents.iter().zip(param_types) {
+            let message = format!("`{builder_name}::{ident}` must be set before calling `build`");
+            if param_type.is_c_abi_compatible_by_value() {
+                build_prepare.push(quote! { let #ident = self.#ident.expect(#message); });
+                args.push(quote! {#ident});
+            } else {
+                build_prepare.push(quote! { let mut #ident = self.#ident.expect(#message); });
+                args.push(quote! {&mut #ident});
+            }
+        }
+        thunk_args = args;
+        doc_comment = format!(" Builder for the `{}` constructor.", record.cc_name);
+    }
+
+    let tmp_arg = format_thunk_out_arg(db, &make_rs_ident("tmp"));
+
+    let item = quote! {
+        #[doc = #doc_comment]
+        #[derive(Default)]
+        pub struct #builder_name #generic_params {
+            #( #builder_fields, )*
+        }
+
+        impl #generic_params #builder_name #generic_params {
+            #( #setters )*
+
+            #[inline(always)]
+            pub fn build(self) -> #record_name {
+                #( #build_prepare )*
+                let mut tmp = ::core::mem::MaybeUninit::<#record_name>::zeroed();
+                unsafe {
+                    #crate_root_path::detail::#thunk_ident(#tmp_arg #( , #thunk_args )*);
+                    tmp.assume_init()
+                }
+            }
+        }
+
+        impl #record_name {
+            #[inline(always)]
+            pub fn builder #generic_params () -> #builder_name #generic_params {
+                ::core::default::Default::default()
+            }
+        }
+    };
+    let function_id = FunctionId {
+        self_type: None,
+        function_path: syn::parse2(quote! { #namespace_qualifier #record_name :: builder })
+            .unwrap(),
+    };
+    let generated_item = GeneratedItem {
+        item,
+        thunks: thunk,
+        thunk_impls: generate_func_thunk_impl(db, func, /* catches_exceptions= */ false)?,
+        thunk_signature: Some(Rc::new(thunk_signature)),
+        ..Default::default()
+    };
+    Ok((Rc::new(generated_item), Rc::new(function_id)))
+}
+
+/// The function signature for a function's bindings.
+struct BindingsSignature {
+    /// The lifetime parameters for the Rust function.
+    lifetimes: Vec<Lifetime>,
+
+    /// The parameter list for the Rust function.
+    ///
+    /// For example, `vec![quote!{self}, quote!{x: &i32}]`.
+    params: Vec<TokenStream>,
+
+    /// The return type fragment of the Rust function, as a token stream.
+    ///
+    /// This is the same as the actual return type, except that () is the empty
+    /// tokens, non-Unpin by-value types are `impl Ctor<Output=#return_type> +
+    /// ...`, and wherever the type is the type of `Self`, it gets replaced by
+    /// literal `Self`.
+    return_type_fragment: TokenStream,
+
+    /// Any preparation code to define the arguments in `thunk_args`.
+    thunk_prepare: TokenStream,
+
+    /// The arguments passed to the thunk, expressed in terms of `params`.
+    thunk_args: Vec<TokenStream>,
+
+    /// A function that converts the value returned by the thunk into the
+    /// return value of the Rust function, if they differ.
+    ///
+    /// For example, `quote!{::cxx_string::CxxStringView::as_bytes}`.
+    return_conversion: Option<TokenStream>,
+}
+
+/// Reformats API parameters and return values to match Rust conventions and the
+/// trait requirements.
+///
+/// For example:
+///
+/// * Use the `self` keyword for the this pointer.
+/// * Use `Self` for the return value of constructor traits.
+/// * For C++ constructors, remove `self` from the Rust side (as it becomes the
+///   return value), retaining it on the C++ side / thunk args.
+/// * serialize a `()` as the empty string.
+/// * Accept any `&impl AsRef<[u8]>` for string views, and return string views
+///   with a lifetime as `&[u8]`.
+/// * Accept slices for `absl::Span`s, and return spans with a lifetime as
+///   slices.
+/// * Take and return `std::optional<T>` by value as `Option<T>`.
+/// * Return `absl::Status` and `absl::StatusOr<T>` as `Result`s.
+fn function_signature(
+    db: &dyn BindingsGenerator,
+    features: &mut BTreeSet<Ident>,
+    func: &Func,
+    impl_kind: &ImplKind,
+    param_idents: &[Ident],
+    param_types: &mut Vec<RsTypeKind>,
+    return_type: &mut RsTypeKind,
+) -> Result<BindingsSignature> {
+    let mut api_params = Vec::with_capacity(func.params.len());
+    let mut thunk_args = Vec::with_capacity(func.params.len());
+    let mut thunk_prepare = quote! {};
+    let impl_kind_record = match impl_kind {
+        ImplKind::Struct { record, .. } | ImplKind::Trait { record, impl_for: ImplFor::T, .. } => {
+            Some(record)
+        }
+        _ => None,
+    };
+    // Trait methods must have the signature required by the trait, so string views, spans and
+    // optionals are only converted from and to Rust types in inherent methods and free
+    // functions.
+    let converts_to_rust_types =
+        matches!(impl_kind, ImplKind::None { .. } | ImplKind::Struct { .. });
+    let mut view_lifetimes = vec![];
+    for (i, (ident, type_)) in param_idents.iter().zip(param_types.iter()).enumerate() {
+        type_.check_by_value()?;
+        if converts_to_rust_types && is_cxx_string_view(type_) {
+            let lifetime = view_lifetime(db, &func.params[i].type_.rs_type)?;
+            let lifetime_tokens = lifetime.as_ref().map(Lifetime::format_for_reference);
+            view_lifetimes.extend(lifetime);
+            api_params.push(
+                quote! {#ident: & #lifetime_tokens (impl ::core::convert::AsRef<[u8]> + ?Sized)},
+            );
+            thunk_args.push(quote! {::cxx_string::CxxStringView::from(#ident.as_ref())});
+        } else if let (true, Some((element_type, mutability))) =
+            (converts_to_rust_types, absl_span_element_type(type_))
+        {
+            let lifetime = view_lifetime(db, &func.params[i].type_.rs_type)?;
+            let lifetime_tokens = lifetime.as_ref().map(Lifetime::format_for_reference);
+            view_lifetimes.extend(lifetime);
+            let element_type =
+                element_type.to_token_stream_replacing_by_self(impl_kind_record.map(|r| &**r));
+            let mutability_tokens = mutability.format_for_reference();
+            api_params.push(quote! {#ident: & #lifetime_tokens #mutability_tokens [#element_type]});
+            thunk_args.push(match mutability {
+                Mutability::Const => quote! {::absl_span::Span::from(#ident)},
+                Mutability::Mut => quote! {::absl_span::SpanMut::from(#ident)},
+            });
+        } else if let (true, Some(value_type)) =
+            (converts_to_rust_types, cxx_optional_value_type(type_))
+        {
+            let value_type =
+                value_type.to_token_stream_replacing_by_self(impl_kind_record.map(|r| &**r));
+            api_params.push(quote! {#ident: ::core::option::Option<#value_type>});
+            thunk_args.push(quote! {&mut ::cxx_optional::CxxOptional::from(#ident)});
+        } else if !type_.is_unpin() {
+            // `impl Ctor` will fail to compile in a trait.
+            // This will only be hit if there was a bug in api_func_shape.
+            if let ImplKind::Trait { .. } = &impl_kind {
+                panic!(
+                    "non-Unpin types cannot work by value in traits; this should have instead \
+                        become an rvalue reference to force the caller to materialize the Ctor."
+                );
+            }
+            // The generated bindings require a move constructor.
+            if !type_.is_move_constructible() {
+                bail!("Non-movable, non-trivial_abi type '{type}' is not supported by value as parameter #{i}", type=quote!{#type_});
+            }
+            let quoted_type_or_self = if let Some(impl_record) = impl_kind_record {
+                type_.to_token_stream_replacing_by_self(Some(impl_record))
+            } else {
+                quote! {#type_}
+            };
+            features.insert(make_rs_ident("impl_trait_in_assoc_type"));
+            api_params.push(quote! {#ident: impl ::ctor::Ctor<Output=#quoted_type_or_self>});
+            thunk_args
+                .push(quote! {::core::pin::Pin::into_inner_unchecked(::ctor::emplace!(#ident))});
+        } else {
+            let quoted_type_or_self = if let Some(impl_record) = impl_kind_record {
+                type_.to_token_stream_replacing_by_self(Some(impl_record))
+            } else {
+                quote! {#type_}
+            };
+            if is_passed_to_thunk_by_value(db, func, type_) {
+                api_params.push(quote! {#ident: #quoted_type_or_self});
+                thunk_args.push(quote! {#ident});
+            } else {
+                api_params.push(quote! {mut #ident: #quoted_type_or_self});
+                thunk_args.push(quote! {&mut #ident});
+            }
+        }
+    }
+
+    let mut lifetimes: Vec<Lifetime> = unique_lifetimes(&*param_types).collect();
+    for lifetime in view_lifetimes {
+        if !lifetimes.contains(&lifetime) {
+            lifetimes.push(lifetime);
+        }
+    }
+
+    let mut quoted_return_type = None;
+    let mut return_conversion = None;
+    if converts_to_rust_types && is_cxx_string_view(return_type) {
+        // Without a lifetime, the returned string view can only be read unsafely.
+        if let Some(lifetime) = view_lifetime(db, &func.return_type.rs_type)? {
+            let lifetime = lifetime.format_for_reference();
+            quoted_return_type = Some(quote! {& #lifetime [u8]});
+            return_conversion = Some(quote! {::cxx_string::CxxStringView::as_bytes});
+        }
+    } else if let (true, Some((element_type, mutability))) =
+        (converts_to_rust_types, absl_span_element_type(return_type))
+    {
+        // Without a lifetime, the returned span can only be read unsafely.
+        if let Some(lifetime) = view_lifetime(db, &func.return_type.rs_type)? {
+            let lifetime = lifetime.format_for_reference();
+            let element_type =
+                element_type.to_token_stream_replacing_by_self(impl_kind_record.map(|r| &**r));
+            let mutability_tokens = mutability.format_for_reference();
+            quoted_return_type = Some(quote! {& #lifetime #mutability_tokens [#element_type]});
+            return_conversion = Some(match mutability {
+                Mutability::Const => quote! {::absl_span::Span::as_slice},
+                Mutability::Mut => quote! {::absl_span::SpanMut::as_mut_slice},
+            });
+        }
+    } else if let (true, Some(value_type)) =
+        (converts_to_rust_types, cxx_optional_value_type(return_type))
+    {
+        let value_type =
+            value_type.to_token_stream_replacing_by_self(impl_kind_record.map(|r| &**r));
+        quoted_return_type = Some(quote! {::core::option::Option<#value_type>});
+        return_conversion = Some(quote! {::cxx_optional::CxxOptional::into_option});
+    } else if converts_to_rust_types {
+        match return_type {
+            RsTypeKind::Other { name, .. } if is_absl_status(name) => {
+                quoted_return_type =
+                    Some(quote! {::core::result::Result<(), ::absl_status::StatusError>});
+                return_conversion = Some(quote! {::absl_status::Status::into_result});
+            }
+            RsTypeKind::Other { name, type_args, .. } if is_absl_status_or(name) => {
+                let value_type = type_args
+                    .first()
+                    .ok_or_else(|| anyhow!("`absl::StatusOr` without a value type"))?
+                    .to_token_stream_replacing_by_self(impl_kind_record.map(|r| &**r));
+                quoted_return_type =
+                    Some(quote! {::core::result::Result<#value_type, ::absl_status::StatusError>});
+                return_conversion = Some(quote! {::absl_status::StatusOr::into_result});
+            }
+            _ => {}
+        }
+    }
+    if let ImplKind::Trait {
+        trait_name: trait_name @ (TraitName::UnpinConstructor { .. } | TraitName::CtorNew(..)),
+        ..
+    } = &impl_kind
+    {
+        // For constructors, we move the output parameter to be the return value.
+        // The return value is "really" void.
+        ensure!(
+            func.return_type.rs_type.is_unit_type(),
+            "Unexpectedly non-void return type of a constructor"
+        );
+
+        //  Presence of element #0 is indirectly verified by a `Constructor`-related
+        // `match` branch a little bit above.
+        *return_type = param_types[0]
+            .referent()
+            .ok_or_else(|| anyhow!("Expected pointer/reference for `__this` parameter"))?
+            .clone();
+        quoted_return_type = Some(quote! {Self});
+
+        // Grab the `__this` lifetime to remove it from the lifetime parameters.
         let this_lifetime = param_types[0]
             .lifetime()
             .ok_or_else(|| anyhow!("Missing lifetime for `__this` parameter"))?;
@@ -1800,6 +3412,9 @@ fn function_signature(
                     api_params[0] = rs_snippet.tokens;
                     features.extend(rs_snippet.features.into_iter());
                     thunk_args[0] = quote! { self };
+                } else if is_passed_to_thunk_by_value(db, func, first_api_param) {
+                    api_params[0] = quote! { self };
+                    thunk_args[0] = quote! { self };
                 } else {
                     api_params[0] = quote! { mut self };
                     thunk_args[0] = quote! { &mut self };
@@ -1821,47 +3436,204 @@ fn function_signature(
         return_type_fragment,
         thunk_prepare,
         thunk_args,
+        return_conversion,
     })
 }
 
-fn generate_func_thunk(
+/// Returns the lifetime of a string view type (see `is_cxx_string_view`) or
+/// `absl::Span` type (see `absl_span_element_type`), if it has one.
+fn view_lifetime(db: &dyn BindingsGenerator, ty: &RsType) -> Result<Option<Lifetime>> {
+    match *ty.lifetime_args {
+        [] => Ok(None),
+        [lifetime_id] => db
+            .ir()
+            .get_lifetime(lifetime_id)
+            .ok_or_else(|| anyhow!("no known lifetime with id {lifetime_id:?}"))
+            .map(|lifetime| Some(Lifetime::from(lifetime))),
+        _ => bail!("String views and spans have at most one lifetime: {ty:?}"),
+    }
+}
+
+/// Generates bindings for a C variadic function (e.g. `int Log(const char* fmt,
+/// ...)`).
+///
+/// Rust can only declare (and not define) variadic functions, and a C++ thunk
+/// can't forward the variadic arguments, so the C++ function is declared
+/// directly in an `extern "C"` block. Calling it is therefore `unsafe`.
+fn generate_variadic_func(
     db: &dyn BindingsGenerator,
     func: &Func,
+    func_name: Ident,
+    impl_kind: &ImplKind,
     param_idents: &[Ident],
     param_types: &[RsTypeKind],
     return_type: &RsTypeKind,
-) -> Result<TokenStream> {
-    let thunk_attr = if can_skip_cc_thunk(db, func) {
-        let mangled_name = func.mangled_name.as_ref();
-        quote! {#[link_name = #mangled_name]}
-    } else {
-        quote! {}
-    };
+) -> Result<(Rc<GeneratedItem>, Rc<FunctionId>)> {
+    if !matches!(impl_kind, ImplKind::None { .. }) {
+        bail!("Variadic member functions and operators are not supported");
+    }
+    if func.params.is_empty() {
+        bail!("Variadic functions without named parameters are not supported");
+    }
+    if func.sets_errno {
+        bail!("`--errno_functions` doesn't support variadic functions");
+    }
+    if !can_skip_cc_thunk(db, func) {
+        bail!(
+            "Variadic functions that require a C++ thunk (e.g. inline functions, or functions \
+             taking or returning structs by value) are not supported"
+        );
+    }
+    let ir = db.ir();
+    let namespace_qualifier = namespace_qualifier_of_item(func.id, &ir)?.format_for_rs();
+    let doc_comment = generate_doc_comment(
+        func.doc_comment.as_deref(),
+        Some(&func.source_loc),
+        db.generate_source_loc_doc_comment(),
+    );
+    let deprecated = generate_deprecated_attr(func.deprecated.as_deref());
+    let mangled_name = func.mangled_name.as_ref();
     let lifetimes: Vec<_> = unique_lifetimes(param_types).collect();
-
-    // The first parameter is the output parameter, if any.
+    let generic_params = format_generic_params(&lifetimes, std::iter::empty::<syn::Ident>());
+    let return_type_fragment = return_type.format_as_return_type_fragment(None);
+    let item = quote! {
+        extern "C" {
+            #doc_comment
+            #deprecated
+            #[link_name = #mangled_name]
+            pub fn #func_name #generic_params(
+                #( #param_idents: #param_types, )* ...
+            ) #return_type_fragment;
+        }
+    };
+    let function_id = FunctionId {
+        self_type: None,
+        function_path: syn::parse2(quote! { #namespace_qualifier #func_name }).unwrap(),
+    };
+    Ok((Rc::new(GeneratedItem { item, ..Default::default() }), Rc::new(function_id)))
+}
+
+/// Formats the argument that passes the `MaybeUninit` local variable `ident` as
+/// the out parameter of a thunk.
+///
+/// Miri rejects references to uninitialized memory that are passed across
+/// function boundaries (the reference is retagged, and protected, for the
+/// whole call), so `--generate_miri_compatible_bindings` passes a raw pointer
+/// that is created without an intermediate reference instead.
+fn format_thunk_out_arg(db: &dyn BindingsGenerator, ident: &Ident) -> TokenStream {
+    if db.generate_miri_compatible_bindings() {
+        quote! { ::core::ptr::addr_of_mut!(#ident) }
+    } else {
+        quote! { &mut #ident }
+    }
+}
+
+/// Returns the type that a C++ function returning a pointer annotated as
+/// `_Nonnull` is bound as (`NonNull<T>`, or a reference if the pointer has a
+/// lifetime), and the pointee type, if `return_type` is such a type.
+///
+/// C++ doesn't enforce the annotation, so the thunk returns a raw `*mut T`,
+/// and the Rust function checks that it isn't null (see
+/// `format_nonnull_return_conversion`).
+fn nonnull_return_pointee<'a>(func: &Func, return_type: &'a RsTypeKind) -> Option<&'a RsTypeKind> {
+    if func.return_type.cc_type.name.as_deref() != Some("*") {
+        return None;
+    }
+    match return_type {
+        RsTypeKind::Other { name, type_args, .. } if is_non_null(name) => type_args.first(),
+        RsTypeKind::Reference { referent, .. } => Some(referent),
+        _ => None,
+    }
+}
+
+/// Converts `value`, the `*mut T` returned by the thunk of a function that
+/// returns a pointer annotated as `_Nonnull`, into the `NonNull<T>` or
+/// reference that the function is bound as. Panics if the pointer is null,
+/// rather than creating an invalid `NonNull<T>` or reference.
+///
+/// Other values are returned unchanged.
+fn format_nonnull_return_conversion(
+    func: &Func,
+    return_type: &RsTypeKind,
+    value: TokenStream,
+) -> TokenStream {
+    if nonnull_return_pointee(func, return_type).is_none() {
+        return value;
+    }
+    let non_null = quote! {
+        ::core::ptr::NonNull::new(#value)
+            .expect("C++ function annotated as returning `_Nonnull` returned a null pointer")
+    };
+    match return_type {
+        RsTypeKind::Reference { mutability: Mutability::Mut, .. } => quote! { #non_null.as_mut() },
+        RsTypeKind::Reference { mutability: Mutability::Const, .. } => {
+            quote! { #non_null.as_ref() }
+        }
+        _ => non_null,
+    }
+}
+
+/// The signature of the `extern "C"` declaration of the thunk of a function,
+/// which `mod raw` wraps (see `generate_raw_module_tokens`).
+#[derive(Clone, Debug)]
+struct ThunkSignature {
+    generic_params: TokenStream,
+    param_idents: Vec<Ident>,
+    param_types: Vec<TokenStream>,
+    return_type_fragment: TokenStream,
+}
+
+/// Returns the `extern "C"` declaration of the thunk of `func`, and its
+/// signature.
+fn generate_func_thunk(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    param_idents: &[Ident],
+    param_types: &[RsTypeKind],
+    return_type: &RsTypeKind,
+    catches_exceptions: bool,
+) -> Result<(TokenStream, ThunkSignature)> {
+    let thunk_attr = if can_skip_cc_thunk(db, func) {
+        let mangled_name = func.mangled_name.as_ref();
+        quote! {#[link_name = #mangled_name]}
+    } else {
+        quote! {}
+    };
+    let lifetimes: Vec<_> = unique_lifetimes(param_types).collect();
+
+    // The first parameter is the output parameter, if any.
     let mut param_types = param_types.iter();
     let mut param_idents = param_idents.iter();
     let mut out_param = None;
     let mut out_param_ident = None;
     let mut return_type_fragment = return_type.format_as_return_type_fragment(None);
+    if let Some(pointee) = nonnull_return_pointee(func, return_type) {
+        return_type_fragment = quote! { -> *mut #pointee };
+    }
     if func.name == UnqualifiedIdentifier::Constructor {
         // For constructors, inject MaybeUninit into the type of `__this_` parameter.
         let first_param = param_types
             .next()
             .ok_or_else(|| anyhow!("Constructors should have at least one parameter (__this)"))?;
-        out_param = Some(first_param.format_mut_ref_as_uninitialized().with_context(|| {
+        let format_uninitialized = if db.generate_miri_compatible_bindings() {
+            RsTypeKind::format_mut_ref_as_uninitialized_ptr
+        } else {
+            RsTypeKind::format_mut_ref_as_uninitialized
+        };
+        out_param = Some(format_uninitialized(first_param).with_context(|| {
             format!(
                 "Failed to format `__this` param for a constructor thunk: {:?}",
                 func.params.get(0)
             )
         })?);
         out_param_ident = Some(param_idents.next().unwrap().clone());
-    } else if !return_type.is_c_abi_compatible_by_value() {
+    } else if !is_returned_from_thunk_by_value(db, func, return_type, catches_exceptions) {
         // For return types that can't be passed by value, create a new out parameter.
         // The lifetime doesn't matter, so we can insert a new anonymous lifetime here.
-        out_param = Some(quote! {
-            &mut ::core::mem::MaybeUninit< #return_type >
+        out_param = Some(if db.generate_miri_compatible_bindings() {
+            quote! { *mut ::core::mem::MaybeUninit< #return_type > }
+        } else {
+            quote! { &mut ::core::mem::MaybeUninit< #return_type > }
         });
         out_param_ident = Some(make_rs_ident("__return"));
         return_type_fragment = quote! {};
@@ -1870,20 +3642,36 @@ fn generate_func_thunk(
     let thunk_ident = thunk_ident(func);
 
     let generic_params = format_generic_params(&lifetimes, std::iter::empty::<syn::Ident>());
-    let param_idents = out_param_ident.as_ref().into_iter().chain(param_idents);
-    let param_types = out_param.into_iter().chain(param_types.map(|t| {
-        if !t.is_c_abi_compatible_by_value() {
-            quote! {&mut #t}
-        } else {
-            quote! {#t}
-        }
-    }));
+    // The exception thrown by the function, if any, is stored in front of all
+    // other parameters.
+    let (exception_param_ident, exception_param) = if catches_exceptions {
+        (Some(make_rs_ident("__exception")), Some(quote! { &mut ::cxx_exception::ExceptionPtr }))
+    } else {
+        (None, None)
+    };
+    let param_idents = exception_param_ident
+        .into_iter()
+        .chain(out_param_ident)
+        .chain(param_idents.cloned())
+        .collect_vec();
+    let param_types = exception_param
+        .into_iter()
+        .chain(out_param)
+        .chain(param_types.map(|t| {
+            if !is_passed_to_thunk_by_value(db, func, t) {
+                quote! {&mut #t}
+            } else {
+                quote! {#t}
+            }
+        }))
+        .collect_vec();
 
-    Ok(quote! {
+    let thunk = quote! {
         #thunk_attr
         pub(crate) fn #thunk_ident #generic_params( #( #param_idents: #param_types ),*
         ) #return_type_fragment ;
-    })
+    };
+    Ok((thunk, ThunkSignature { generic_params, param_idents, param_types, return_type_fragment }))
 }
 fn generate_doc_comment(
     comment: Option<&str>,
@@ -1906,6 +3694,116 @@ fn generate_doc_comment(
     quote! {#[doc = #doc_comment]}
 }
 
+/// Generates the `#[deprecated]` attribute of a `[[deprecated]]` C++
+/// declaration, given the message of the C++ attribute.
+fn generate_deprecated_attr(deprecated: Option<&str>) -> TokenStream {
+    match deprecated {
+        None => quote! {},
+        Some("") => quote! { #[deprecated] },
+        Some(note) => quote! { #[deprecated(note = #note)] },
+    }
+}
+
+/// Returns whether the bindings of `item` refer to a `[[deprecated]]` C++
+/// declaration, other than by defining it. For example, the layout assertions
+/// of a deprecated struct refer to it, and so does a function that takes it as
+/// a parameter.
+fn refers_to_deprecated(db: &Database, item: &Item) -> bool {
+    let refers_to_deprecated_type =
+        |ty: &RsType| db.rs_type_kind(ty.clone()).is_ok_and(|ty| ty.refers_to_deprecated());
+    let is_member_of_deprecated_record = |record_id: Option<ItemId>| {
+        record_id.is_some_and(|record_id| {
+            db.ir()
+                .find_decl::<Rc<Record>>(record_id)
+                .is_ok_and(|record| record.deprecated.is_some())
+        })
+    };
+    match item {
+        Item::Func(func) => {
+            let record_id = func.member_func_metadata.as_ref().map(|meta| meta.record_id);
+            is_member_of_deprecated_record(record_id)
+                || refers_to_deprecated_type(&func.return_type.rs_type)
+                || func.params.iter().any(|param| refers_to_deprecated_type(&param.type_.rs_type))
+        }
+        Item::Record(record) => {
+            record.deprecated.is_some()
+                || record.fields.iter().any(|field| {
+                    field.deprecated.is_some()
+                        || matches!(&field.type_, Ok(ty) if refers_to_deprecated_type(&ty.rs_type))
+                })
+        }
+        Item::Enum(enum_) => enum_.deprecated.is_some(),
+        Item::TypeAlias(type_alias) => {
+            refers_to_deprecated_type(&type_alias.underlying_type.rs_type)
+        }
+        Item::Constant(constant) => refers_to_deprecated_type(&constant.type_.rs_type),
+        Item::GlobalVar(global_var) => {
+            is_member_of_deprecated_record(global_var.enclosing_record_id)
+                || refers_to_deprecated_type(&global_var.type_.rs_type)
+        }
+        _ => false,
+    }
+}
+
+/// Adds `#[allow(deprecated)]` to each of the Rust items in `items`, unless
+/// they already have it.
+///
+/// Lint attributes on macro invocations are ignored, so `items` must not
+/// contain any (see `generate_record`).
+fn allow_deprecated(items: TokenStream) -> TokenStream {
+    let allow_deprecated = quote! { #[allow(deprecated)] };
+    let is_allow_deprecated = |token: Option<&TokenTree>| {
+        matches!(token, Some(TokenTree::Group(group))
+            if group.stream().to_string() == quote! { allow(deprecated) }.to_string())
+    };
+    let is_semicolon = |tt: &TokenTree| matches!(tt, TokenTree::Punct(p) if p.as_char() == ';');
+    let mut result = TokenStream::new();
+    let mut at_item_start = true;
+    let mut tokens = items.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match &token {
+            // Newlines and comments are not part of the items.
+            TokenTree::Ident(ident) if ident == "__NEWLINE__" => {}
+            TokenTree::Ident(ident) if ident == "__COMMENT__" => {
+                result.extend([token]);
+                result.extend(tokens.next());
+                continue;
+            }
+            _ if at_item_start => {
+                at_item_start = false;
+                let is_hash = matches!(&token, TokenTree::Punct(p) if p.as_char() == '#');
+                if !(is_hash && is_allow_deprecated(tokens.peek())) {
+                    result.extend(allow_deprecated.clone());
+                }
+            }
+            // Items end with a `;`, or with a `{ ... }` block that isn't followed by
+            // one (unlike e.g. `const _: () = { ... };`).
+            _ if is_semicolon(&token) => at_item_start = true,
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace
+                    && !tokens.peek().is_some_and(is_semicolon) =>
+            {
+                at_item_start = true
+            }
+            _ => {}
+        }
+        result.extend([token]);
+    }
+    result
+}
+
+/// Disables the Clang warnings about uses of deprecated declarations in
+/// `thunk_impls`, which refer to a `[[deprecated]]` C++ declaration (e.g. a
+/// thunk that calls a deprecated function).
+fn ignore_deprecated_declarations(thunk_impls: TokenStream) -> TokenStream {
+    quote! {
+        __HASH_TOKEN__ pragma clang diagnostic push __NEWLINE__
+        __HASH_TOKEN__ pragma clang diagnostic ignored "-Wdeprecated-declarations" __NEWLINE__
+        #thunk_impls __NEWLINE__
+        __HASH_TOKEN__ pragma clang diagnostic pop __NEWLINE__
+    }
+}
+
 fn format_generic_params<'a, T: ToTokens>(
     lifetimes: impl IntoIterator<Item = &'a Lifetime>,
     types: impl IntoIterator<Item = T>,
@@ -2073,6 +3971,18 @@ fn bit_padding(padding_size_in_bits: usize) -> TokenStream {
 /// a tuple.
 fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem> {
     let ir = db.ir();
+    ensure_nested_record_name_is_unique(&ir, record)?;
+    ensure_renamed_keyword_is_unique(&record.rs_name, |rs_name| {
+        is_type_name_taken(&ir, record.enclosing_namespace_id, rs_name, record.id)
+    })?;
+    for field in &record.fields {
+        let Some(identifier) = &field.identifier else { continue };
+        ensure_renamed_keyword_is_unique(&identifier.identifier, |rs_name| {
+            record.fields.iter().any(|field| {
+                field.identifier.as_ref().is_some_and(|other| &*other.identifier == rs_name)
+            })
+        })?;
+    }
     let crate_root_path = crate_root_path_tokens(&ir);
     let ident = make_rs_ident(record.rs_name.as_ref());
     let namespace_qualifier = namespace_qualifier_of_item(record.id, &ir)?.format_for_rs();
@@ -2084,6 +3994,7 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
         Some(&record.source_loc),
         db.generate_source_loc_doc_comment(),
     );
+    let deprecated = generate_deprecated_attr(record.deprecated.as_deref());
     let mut field_copy_trait_assertions: Vec<TokenStream> = vec![];
 
     let fields_with_bounds = (record.fields.iter())
@@ -2222,6 +4133,7 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
                     )
                 }
             };
+            let deprecated = generate_deprecated_attr(field.deprecated.as_deref());
             let access = if field.access == AccessSpecifier::Public && field_rs_type_kind.is_ok() {
                 quote! { pub }
             } else {
@@ -2251,7 +4163,7 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
                 }
             };
 
-            Ok(quote! { #padding #doc_comment #access #ident: #field_type })
+            Ok(quote! { #padding #doc_comment #deprecated #access #ident: #field_type })
         })
         .collect::<Result<Vec<_>>>()?;
 
@@ -2272,8 +4184,12 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
                     assert_eq!(field.offset % 8, 0);
                     let expected_offset = Literal::usize_unsuffixed(field.offset / 8);
 
-                    let actual_offset_expr = quote! {
-                        memoffset::offset_of!(#qualified_ident, #field_ident)
+                    // `memoffset::offset_of!` may compute the offset by casting pointers to
+                    // integers, which Miri rejects.
+                    let actual_offset_expr = if db.generate_miri_compatible_bindings() {
+                        quote! { ::core::mem::offset_of!(#qualified_ident, #field_ident) }
+                    } else {
+                        quote! { memoffset::offset_of!(#qualified_ident, #field_ident) }
                     };
                     quote! {
                         const _: () = assert!(#actual_offset_expr == #expected_offset);
@@ -2288,6 +4204,7 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
     // assertions about the `Copy` trait - this trait should be implemented
     // iff `should_implement_drop(record)` is false.
     let mut features = BTreeSet::new();
+    let mut extern_crates = BTreeSet::new();
 
     let derives = generate_derives(record);
     let derives = if derives.is_empty() {
@@ -2355,9 +4272,14 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
     // TODO(b/227442773): After namespace support is added, use the fully-namespaced
     // name.
     let incomplete_symbol = record.cc_name.as_ref();
-    let incomplete_definition = quote! {
+    let mut incomplete_definition = quote! {
         forward_declare::unsafe_define!(forward_declare::symbol!(#incomplete_symbol), #qualified_ident);
     };
+    if refers_to_deprecated(db, &Item::Record(record.clone())) {
+        // Lint attributes on macro invocations are ignored, so `allow_deprecated`
+        // needs an item to put `#[allow(deprecated)]` on.
+        incomplete_definition = quote! { const _: () = { #incomplete_definition }; };
+    }
 
     let no_unique_address_accessors = cc_struct_no_unique_address_impl(db, record)?;
     let mut record_generated_items = record
@@ -2371,7 +4293,35 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
         })
         .collect::<Result<Vec<_>>>()?;
 
-    record_generated_items.push(cc_struct_upcast_impl(record, &ir)?);
+    record_generated_items.push(cc_struct_upcast_impl(db, record, &ir)?);
+    record_generated_items.push(cc_struct_inherited_methods_impl(db, record, &ir)?);
+    record_generated_items.push(cc_struct_three_way_comparison_impl(db, record, &ir)?);
+    record_generated_items.push(cc_struct_conversion_impls(db, record, &ir)?);
+    if record.has_stream_insertion_operator {
+        record_generated_items.push(cc_struct_display_impl(db, record, &ir)?);
+    }
+    if db.vector_element_records().contains(&record.id) {
+        record_generated_items.push(cc_struct_vector_element_impl(db, record, &ir)?);
+    }
+    if db.unique_ptr_target_records().contains(&record.id) {
+        record_generated_items.push(cc_struct_unique_ptr_target_impl(db, record, &ir)?);
+    }
+    if db.shared_ptr_target_records().contains(&record.id) {
+        record_generated_items.push(cc_struct_shared_ptr_target_impl(db, record, &ir)?);
+    }
+    if let Some(types) = db.member_function_pointer_types().get(&record.id) {
+        record_generated_items
+            .push(cc_struct_member_function_signature_impls(db, record, types, &ir)?);
+    }
+    if record.is_extensible {
+        record_generated_items.push(cc_struct_extension_impl(db, record, &ir)?);
+    } else if is_interface(record, &ir) {
+        // Interfaces are implicitly extensible, as long as all of their member
+        // functions can be implemented in Rust.
+        if let Ok(generated) = cc_struct_extension_impl(db, record, &ir) {
+            record_generated_items.push(generated);
+        }
+    }
 
     let mut items = vec![];
     let mut thunks_from_record_items = vec![];
@@ -2390,10 +4340,25 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
             thunk_impls_from_record_items.push(generated.thunk_impls);
         }
         features.extend(generated.features.clone());
+        extern_crates.extend(generated.extern_crates.clone());
+    }
+
+    let mut auto_trait_impls = vec![];
+    for (is_impl, trait_name) in [(record.is_send, quote! {Send}), (record.is_sync, quote! {Sync})]
+    {
+        match is_impl {
+            Some(true) => auto_trait_impls.push(quote! { unsafe impl #trait_name for #ident {} }),
+            Some(false) => {
+                features.insert(make_rs_ident("negative_impls"));
+                auto_trait_impls.push(quote! { impl !#trait_name for #ident {} });
+            }
+            None => {}
+        }
     }
 
     let record_tokens = quote! {
         #doc_comment
+        #deprecated
         #derives
         #recursively_pinned_attribute
         #[repr(#( #repr_attributes ),*)]
@@ -2402,6 +4367,8 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
             #( #field_definitions, )*
         }
 
+        #( #auto_trait_impls __NEWLINE__ )*
+
         #incomplete_definition
 
         #no_unique_address_accessors
@@ -2434,6 +4401,12 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
         };
         add_conditional_assertion(should_derive_copy(record), quote! { Copy });
         add_conditional_assertion(should_implement_drop(record), quote! { Drop });
+        if let Some(is_send) = record.is_send {
+            add_conditional_assertion(is_send, quote! { Send });
+        }
+        if let Some(is_sync) = record.is_sync {
+            add_conditional_assertion(is_sync, quote! { Sync });
+        }
         assertions
     };
     let size_align_assertions = rs_size_align_assertions(qualified_ident, &record.size_align);
@@ -2452,6 +4425,7 @@ fn generate_record(db: &Database, record: &Rc<Record>) -> Result<GeneratedItem>
     Ok(GeneratedItem {
         item: record_tokens,
         features,
+        extern_crates,
         assertions: assertion_tokens,
         thunks: thunk_tokens,
         thunk_impls: quote! {#(#thunk_impls_from_record_items __NEWLINE__ __NEWLINE__)*},
@@ -2516,32 +4490,48 @@ fn generate_derives(record: &Record) -> Vec<Ident> {
 }
 
 fn generate_enum(db: &Database, enum_: &Enum) -> Result<GeneratedItem> {
+    let ir = db.ir();
+    ensure_renamed_keyword_is_unique(&enum_.identifier.identifier, |rs_name| {
+        is_type_name_taken(&ir, enum_.enclosing_namespace_id, rs_name, enum_.id)
+    })?;
+    for enumerator in &enum_.enumerators {
+        ensure_renamed_keyword_is_unique(&enumerator.identifier.identifier, |rs_name| {
+            enum_.enumerators.iter().any(|other| &*other.identifier.identifier == rs_name)
+        })?;
+    }
     let name = make_rs_ident(&enum_.identifier.identifier);
     let underlying_type = db.rs_type_kind(enum_.underlying_type.rs_type.clone())?;
+    let doc_comment = generate_doc_comment(
+        enum_.doc_comment.as_deref(),
+        Some(&enum_.source_loc),
+        db.generate_source_loc_doc_comment(),
+    );
+    let enumerator_doc_comments = enum_.enumerators.iter().map(|enumerator| {
+        generate_doc_comment(
+            enumerator.doc_comment.as_deref(),
+            None,
+            db.generate_source_loc_doc_comment(),
+        )
+    });
     let enumerator_names =
         enum_.enumerators.iter().map(|enumerator| make_rs_ident(&enumerator.identifier.identifier));
-    let enumerator_values = enum_.enumerators.iter().map(|enumerator| {
-        if underlying_type.is_bool() {
-            if enumerator.value.wrapped_value == 0 {
-                quote! {false}
-            } else {
-                quote! {true}
-            }
-        } else {
-            if enumerator.value.is_negative {
-                Literal::i64_unsuffixed(enumerator.value.wrapped_value as i64).into_token_stream()
-            } else {
-                Literal::u64_unsuffixed(enumerator.value.wrapped_value).into_token_stream()
-            }
-        }
-    });
+    let enumerator_values = enum_
+        .enumerators
+        .iter()
+        .map(|enumerator| format_integer_constant(&enumerator.value, underlying_type.is_bool()));
+    let deprecated = generate_deprecated_attr(enum_.deprecated.as_deref());
 
     Ok(quote! {
+        #doc_comment
+        #deprecated
         #[repr(transparent)]
         #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
         pub struct #name(#underlying_type);
         impl #name {
-            #(pub const #enumerator_names: #name = #name(#enumerator_values);)*
+            #(
+                #enumerator_doc_comments
+                pub const #enumerator_names: #name = #name(#enumerator_values);
+            )*
         }
         impl From<#underlying_type> for #name {
             fn from(value: #underlying_type) -> #name {
@@ -2557,7 +4547,232 @@ fn generate_enum(db: &Database, enum_: &Enum) -> Result<GeneratedItem> {
     .into())
 }
 
+/// Formats an integer constant as a literal of `bool` type (if `is_bool`) or of
+/// an integer type.
+fn format_integer_constant(value: &IntegerConstant, is_bool: bool) -> TokenStream {
+    if is_bool {
+        if value.wrapped_value == 0 {
+            quote! {false}
+        } else {
+            quote! {true}
+        }
+    } else if value.is_negative {
+        Literal::i64_unsuffixed(value.wrapped_value as i64).into_token_stream()
+    } else {
+        Literal::u64_unsuffixed(value.wrapped_value).into_token_stream()
+    }
+}
+
+fn generate_constant(db: &Database, constant: &Constant) -> Result<GeneratedItem> {
+    let ident = make_rs_ident(&constant.identifier.identifier);
+    if constant.enclosing_record_id.is_none() {
+        if let Some(other) = value_name_collision(db, constant, &ident) {
+            bail!("The constant would collide with {other}, which is also named `{ident}` in Rust");
+        }
+    }
+    let doc_comment = generate_doc_comment(
+        constant.doc_comment.as_deref(),
+        Some(&constant.source_loc),
+        db.generate_source_loc_doc_comment(),
+    );
+    let type_ = db.rs_type_kind(constant.type_.rs_type.clone())?;
+    let mut unaliased_type = &type_;
+    while let RsTypeKind::TypeAlias { underlying_type, .. } = unaliased_type {
+        unaliased_type = &**underlying_type;
+    }
+    let value = match (&constant.value, unaliased_type) {
+        (ConstantValue::Integer(value), RsTypeKind::Enum { enum_, .. }) => {
+            // The value of an enum can only be spelled as one of its enumerators.
+            let enumerator =
+                match enum_.enumerators.iter().find(|enumerator| enumerator.value == *value) {
+                    Some(enumerator) => make_rs_ident(&enumerator.identifier.identifier),
+                    None => bail!(
+                        "The value of the constant is not an enumerator of `{}`",
+                        enum_.identifier.identifier
+                    ),
+                };
+            quote! { #unaliased_type::#enumerator }
+        }
+        (ConstantValue::Integer(value), _) => {
+            format_integer_constant(value, unaliased_type.is_bool())
+        }
+        (ConstantValue::Float(literal), _) => {
+            let value = literal
+                .parse::<f64>()
+                .with_context(|| format!("Invalid floating point constant: {literal}"))?;
+            Literal::f64_unsuffixed(value).into_token_stream()
+        }
+    };
+    let const_item = quote! {
+        #doc_comment
+        pub const #ident: #type_ = #value;
+    };
+    match constant.enclosing_record_id {
+        None => Ok(const_item.into()),
+        Some(record_id) => {
+            // Static data members are bound as associated constants.
+            let ir = db.ir();
+            let record = ir.find_decl::<Rc<Record>>(record_id)?;
+            let record_type = RsTypeKind::new_record(record.clone(), &ir)?;
+            Ok(quote! { impl #record_type { #const_item } }.into())
+        }
+    }
+}
+
+/// Returns a description of the other item whose Rust name in the value
+/// namespace is `ident`, in the scope of the namespace-scope `constant`, if
+/// any.
+///
+/// Object-like macros are imported as constants without taking C++ scopes into
+/// account (e.g. `#define Color 1` after `enum Color {};` is fine in C++), so
+/// their names may collide with functions, enums (which are bound as tuple
+/// structs) and other constants. Both colliding constants are skipped, as it
+/// isn't clear which one the user expects.
+fn value_name_collision(db: &Database, constant: &Constant, ident: &Ident) -> Option<String> {
+    let ir = db.ir();
+    let name = ident.to_string();
+    let collision = ir
+        .items()
+        .filter(|item| {
+            item.id() != constant.id
+                && item.enclosing_namespace_id() == constant.enclosing_namespace_id
+                && item.owning_target().is_some_and(|target| ir.is_current_target(target))
+        })
+        .find_map(|item| match item {
+            Item::Enum(enum_) if make_rs_ident(&enum_.identifier.identifier) == name => {
+                Some(format!("the enum `{}`", enum_.identifier.identifier))
+            }
+            Item::Constant(other)
+                if other.enclosing_record_id.is_none()
+                    && make_rs_ident(&other.identifier.identifier) == name =>
+            {
+                Some(format!("the constant `{}`", other.identifier.identifier))
+            }
+            Item::Func(func)
+                if func.member_func_metadata.is_none()
+                    && bound_method_name(db, func).is_some_and(|bound_name| bound_name == name) =>
+            {
+                func.name.identifier_as_str().map(|cc_name| format!("the function `{cc_name}`"))
+            }
+            _ => None,
+        });
+    collision
+}
+
+/// Returns a description of the other associated item of the record with the
+/// id `record_id` whose Rust name is `ident`, if any, for the accessor of the
+/// static data member `global_var`.
+///
+/// The names of the methods and of the static data members of a class can't
+/// collide in C++, but they can in Rust (e.g. after renaming a method with
+/// `CRUBIT_RUST_NAME`, or a Rust keyword with an underscore).
+fn associated_item_name_collision(
+    db: &Database,
+    global_var: &GlobalVar,
+    record_id: ItemId,
+    ident: &Ident,
+) -> Option<String> {
+    let ir = db.ir();
+    let name = ident.to_string();
+    let collision =
+        ir.items().filter(|item| item.id() != global_var.id).find_map(|item| match item {
+            Item::Func(func)
+                if func
+                    .member_func_metadata
+                    .as_ref()
+                    .is_some_and(|meta| meta.record_id == record_id)
+                    && bound_method_name(db, func).is_some_and(|bound_name| bound_name == name) =>
+            {
+                func.name.identifier_as_str().map(|cc_name| format!("the method `{cc_name}`"))
+            }
+            Item::Constant(constant)
+                if constant.enclosing_record_id == Some(record_id)
+                    && make_rs_ident(&constant.identifier.identifier) == name =>
+            {
+                Some(format!("the constant `{}`", constant.identifier.identifier))
+            }
+            Item::GlobalVar(other)
+                if other.enclosing_record_id == Some(record_id)
+                    && make_rs_ident(&other.identifier.identifier) == name =>
+            {
+                Some(format!("the static data member `{}`", other.identifier.identifier))
+            }
+            _ => None,
+        });
+    collision
+}
+
+/// Generates an associated function that returns a pointer to a static data
+/// member, which it gets from a thunk.
+fn generate_global_var(db: &Database, global_var: &GlobalVar) -> Result<GeneratedItem> {
+    let ir = db.ir();
+    let record_id = match global_var.enclosing_record_id {
+        Some(record_id) => record_id,
+        None => bail!("Variables outside of classes are not supported yet"),
+    };
+    let record = ir.find_decl::<Rc<Record>>(record_id)?;
+    let record_type = RsTypeKind::new_record(record.clone(), &ir)?;
+    let cc_record_name = cc_tagless_type_name_for_record(record, &ir)?;
+    let type_ = db.rs_type_kind(global_var.type_.rs_type.clone())?;
+    let mut unaliased_type = &type_;
+    while let RsTypeKind::TypeAlias { underlying_type, .. } = unaliased_type {
+        unaliased_type = &**underlying_type;
+    }
+    match unaliased_type {
+        RsTypeKind::Reference { .. } | RsTypeKind::RvalueReference { .. } => {
+            bail!("Static data members of reference type are not supported yet")
+        }
+        RsTypeKind::Array { .. } => {
+            bail!("Static data members of array type are not supported yet")
+        }
+        _ => {}
+    }
+    let mutability = if global_var.type_.cc_type.is_const {
+        quote! {const}
+    } else {
+        quote! {mut}
+    };
+    let cc_type = format_cc_type(&global_var.type_.cc_type, &ir)?;
+    let ident = make_rs_ident(&global_var.identifier.identifier);
+    if let Some(other) = associated_item_name_collision(db, global_var, record.id, &ident) {
+        bail!("The accessor would collide with {other}, which is also named `{ident}` in Rust");
+    }
+    let cc_ident = format_cc_ident(&global_var.identifier.identifier);
+    let doc_comment = generate_doc_comment(
+        global_var.doc_comment.as_deref(),
+        Some(&global_var.source_loc),
+        db.generate_source_loc_doc_comment(),
+    );
+    let thunk_ident = format_ident!("__rust_thunk__{}", global_var.mangled_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(&ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+    Ok(GeneratedItem {
+        item: quote! {
+            impl #record_type {
+                #doc_comment
+                #[inline(always)]
+                pub fn #ident() -> *#mutability #type_ {
+                    unsafe { #crate_root_path::detail::#thunk_ident() }
+                }
+            }
+        },
+        thunks: quote! {
+            pub(crate) fn #thunk_ident() -> *#mutability #type_;
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers #cc_type* #thunk_ident() {
+                return &#cc_record_name::#cc_ident;
+            }
+        },
+        ..Default::default()
+    })
+}
+
 fn generate_type_alias(db: &Database, type_alias: &TypeAlias) -> Result<GeneratedItem> {
+    let ir = db.ir();
+    ensure_renamed_keyword_is_unique(&type_alias.identifier.identifier, |rs_name| {
+        is_type_name_taken(&ir, type_alias.enclosing_namespace_id, rs_name, type_alias.id)
+    })?;
     let ident = make_rs_ident(&type_alias.identifier.identifier);
     let doc_comment = generate_doc_comment(
         type_alias.doc_comment.as_deref(),
@@ -2567,8 +4782,10 @@ fn generate_type_alias(db: &Database, type_alias: &TypeAlias) -> Result<Generate
     let underlying_type = db
         .rs_type_kind(type_alias.underlying_type.rs_type.clone())
         .with_context(|| format!("Failed to format underlying type for {:?}", type_alias))?;
+    let deprecated = generate_deprecated_attr(type_alias.deprecated.as_deref());
     Ok(quote! {
         #doc_comment
+        #deprecated
         pub type #ident = #underlying_type;
     }
     .into())
@@ -2603,11 +4820,18 @@ fn generate_comment(comment: &Comment) -> Result<GeneratedItem> {
 
 fn generate_namespace(db: &Database, namespace: &Namespace) -> Result<GeneratedItem> {
     let ir = db.ir();
+    ensure_renamed_keyword_is_unique(&namespace.name.identifier, |rs_name| {
+        ir.namespaces().any(|other| {
+            other.enclosing_namespace_id == namespace.enclosing_namespace_id
+                && &*other.name.identifier == rs_name
+        })
+    })?;
     let mut items = vec![];
     let mut thunks = vec![];
     let mut thunk_impls = vec![];
     let mut assertions = vec![];
     let mut features = BTreeSet::new();
+    let mut extern_crates = BTreeSet::new();
 
     for item_id in namespace.child_item_ids.iter() {
         let item = ir.find_decl(*item_id).with_context(|| {
@@ -2625,6 +4849,7 @@ fn generate_namespace(db: &Database, namespace: &Namespace) -> Result<GeneratedI
             assertions.push(generated.assertions);
         }
         features.extend(generated.features);
+        extern_crates.extend(generated.extern_crates);
     }
 
     let reopened_namespace_idx = ir.get_reopened_namespace_idx(namespace.id)?;
@@ -2669,6 +4894,7 @@ fn generate_namespace(db: &Database, namespace: &Namespace) -> Result<GeneratedI
     Ok(GeneratedItem {
         item: namespace_tokens,
         features,
+        extern_crates,
         thunks: quote! { #( #thunks )* },
         thunk_impls: quote! { #( #thunk_impls )* },
         assertions: quote! { #( #assertions )* },
@@ -2676,6 +4902,52 @@ fn generate_namespace(db: &Database, namespace: &Namespace) -> Result<GeneratedI
     })
 }
 
+/// How a top-level item is handled when namespaces of the current target are
+/// split into separate crates.
+enum SplitNamespaceItem {
+    /// The item belongs to the crate being generated.
+    Generate,
+    /// The item belongs to a different crate.
+    Skip,
+    /// The item is a namespace that belongs to a different crate, and needs to
+    /// be re-exported by the crate being generated (using the given tokens).
+    Reexport(TokenStream),
+}
+
+/// Returns whether the bindings for the top-level `item` should be generated,
+/// skipped or re-exported from a split namespace crate.
+///
+/// When generating the bindings of the target itself, the items of split
+/// namespaces are skipped, and the (canonical) module of each split namespace
+/// is re-exported from the crate of the namespace instead.  When generating the
+/// bindings of a split namespace, all other items are skipped.
+fn generate_split_namespace_reexport(db: &Database, item: &Item) -> Result<SplitNamespaceItem> {
+    let ir = db.ir();
+    let namespace = match item {
+        Item::Namespace(namespace) => Some(namespace),
+        _ => None,
+    };
+    let split_crate =
+        namespace.and_then(|namespace| ir.split_namespace_crate(&namespace.name.identifier));
+    match (ir.split_namespace_to_generate(), namespace, split_crate) {
+        (Some(namespace_to_generate), Some(namespace), Some(_))
+            if namespace.name.identifier == namespace_to_generate =>
+        {
+            Ok(SplitNamespaceItem::Generate)
+        }
+        (Some(_), _, _) => Ok(SplitNamespaceItem::Skip),
+        (None, Some(namespace), Some(split_crate)) => {
+            if !ir.is_last_reopened_namespace(namespace.id, namespace.canonical_namespace_id)? {
+                return Ok(SplitNamespaceItem::Skip);
+            }
+            let split_crate = make_rs_ident(&split_crate.replace('-', "_"));
+            let name = make_rs_ident(&namespace.name.identifier);
+            Ok(SplitNamespaceItem::Reexport(quote! { pub use ::#split_crate::#name; }))
+        }
+        (None, _, _) => Ok(SplitNamespaceItem::Generate),
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 struct GeneratedItem {
     item: TokenStream,
@@ -2684,6 +4956,22 @@ struct GeneratedItem {
     thunk_impls: TokenStream,
     assertions: TokenStream,
     features: BTreeSet<Ident>,
+    // Crates of the standard library (e.g. `alloc`) used by the item, which the
+    // `no_std` bindings must declare with `extern crate`.
+    extern_crates: BTreeSet<Ident>,
+    // The signature of the thunk of a function, if the item is a function.
+    thunk_signature: Option<Rc<ThunkSignature>>,
+}
+
+impl GeneratedItem {
+    fn append(&mut self, other: &GeneratedItem) {
+        self.item.extend(other.item.clone());
+        self.thunks.extend(other.thunks.clone());
+        self.thunk_impls.extend(other.thunk_impls.clone());
+        self.assertions.extend(other.assertions.clone());
+        self.features.extend(other.features.iter().cloned());
+        self.extern_crates.extend(other.extern_crates.iter().cloned());
+    }
 }
 
 impl From<TokenStream> for GeneratedItem {
@@ -2760,9 +5048,22 @@ fn generate_item_impl(db: &Database, item: &Item) -> Result<GeneratedItem> {
             Some((item, function_id)) => {
                 if overloaded_funcs.contains(&function_id) {
                     bail!("Cannot generate bindings for overloaded function")
-                } else {
-                    (*item).clone()
                 }
+                let mut generated_item = (*item).clone();
+                let binds_default_args_as_options = binds_default_args_as_options(db, func);
+                for func in funcs_without_default_args(db, func) {
+                    if let Some((item, function_id)) = db.generate_func(Rc::new(func))? {
+                        if binds_default_args_as_options {
+                            // Only the thunks are used, by the function with
+                            // `Option` parameters.
+                            generated_item
+                                .append(&GeneratedItem { item: quote! {}, ..(*item).clone() });
+                        } else if !overloaded_funcs.contains(&function_id) {
+                            generated_item.append(&item);
+                        }
+                    }
+                }
+                generated_item
             }
         },
         Item::IncompleteRecord(incomplete_record) => generate_incomplete_record(incomplete_record)?,
@@ -2783,6 +5084,8 @@ fn generate_item_impl(db: &Database, item: &Item) -> Result<GeneratedItem> {
                 generate_type_alias(db, type_alias)?
             }
         }
+        Item::Constant(constant) => generate_constant(db, constant)?,
+        Item::GlobalVar(global_var) => generate_global_var(db, global_var)?,
         Item::UnsupportedItem(unsupported) => generate_unsupported(db, unsupported)?,
         Item::Comment(comment) => generate_comment(comment)?,
         Item::Namespace(namespace) => generate_namespace(db, namespace)?,
@@ -2817,6 +5120,18 @@ fn generate_item_impl(db: &Database, item: &Item) -> Result<GeneratedItem> {
         }
     };
 
+    let mut generated_item = generated_item;
+    let refers_to_deprecated = refers_to_deprecated(db, item);
+    if refers_to_deprecated {
+        generated_item.item = allow_deprecated(generated_item.item);
+        generated_item.thunks = allow_deprecated(generated_item.thunks);
+        generated_item.assertions = allow_deprecated(generated_item.assertions);
+    }
+    let calls_deprecated_func = matches!(item, Item::Func(func) if func.deprecated.is_some());
+    if (refers_to_deprecated || calls_deprecated_func) && !generated_item.thunk_impls.is_empty() {
+        generated_item.thunk_impls = ignore_deprecated_declarations(generated_item.thunk_impls);
+    }
+
     // Suppress bindings at the last minute, to collect other errors first.
     if let HasBindings::No(reason) = has_bindings(db, item) {
         return Err(reason.into());
@@ -2851,6 +5166,12 @@ enum NoBindingsReason {
         context: Rc<str>,
         error: Error,
     },
+    /// The item is not in the split namespace whose crate is being generated
+    /// (see `--split_namespaces`).
+    OutsideSplitNamespace {
+        context: Rc<str>,
+        namespace: Rc<str>,
+    },
 }
 
 #[must_use]
@@ -2869,10 +5190,25 @@ fn has_bindings(db: &dyn BindingsGenerator, item: &Item) -> HasBindings {
         }
     }
 
+    // The crate of a split namespace doesn't depend on the crate of the rest of the
+    // target (which re-exports it), nor on the crates of other split namespaces.
+    if let Some(namespace_to_generate) = ir.split_namespace_to_generate() {
+        if item.owning_target().is_some_and(|target| ir.is_current_target(target))
+            && top_level_namespace_name(&ir, item).as_ref() != Some(&namespace_to_generate)
+        {
+            return HasBindings::No(NoBindingsReason::OutsideSplitNamespace {
+                context: item.debug_name(&ir),
+                namespace: namespace_to_generate,
+            });
+        }
+    }
+
     match item {
         // Function bindings aren't guaranteed, because they don't _need_ to be guaranteed. We
         // choose not to generate code which relies on functions existing in other TUs.
         Item::Func(..) => HasBindings::Maybe,
+        // Likewise, no generated code relies on constants or variables.
+        Item::Constant(..) | Item::GlobalVar(..) => HasBindings::Maybe,
         Item::TypeAlias(alias) => match db.rs_type_kind(alias.underlying_type.rs_type.clone()) {
             Ok(_) => HasBindings::Yes,
             Err(error) => HasBindings::No(NoBindingsReason::DependencyFailed {
@@ -2895,6 +5231,22 @@ impl From<NoBindingsReason> for Error {
             NoBindingsReason::DependencyFailed { context, error } => error.context(format!(
                 "Can't generate bindings for {context} due to missing bindings for its dependency"
             )),
+            NoBindingsReason::OutsideSplitNamespace { context, namespace } => anyhow!(
+                "{context} is not in the namespace `{namespace}`, whose bindings are generated \
+                 into a separate crate (see --split_namespaces)"
+            ),
+        }
+    }
+}
+
+/// Returns the name of the top-level namespace that contains `item`, if any.
+fn top_level_namespace_name(ir: &IR, item: &Item) -> Option<Rc<str>> {
+    let mut namespace_id = item.enclosing_namespace_id()?;
+    loop {
+        let namespace = ir.find_decl::<Rc<Namespace>>(namespace_id).ok()?;
+        match namespace.enclosing_namespace_id {
+            Some(enclosing_namespace_id) => namespace_id = enclosing_namespace_id,
+            None => return Some(namespace.name.identifier.clone()),
         }
     }
 }
@@ -2920,39 +5272,231 @@ fn overloaded_funcs(db: &dyn BindingsGenerator) -> Rc<HashSet<Rc<FunctionId>>> {
     let mut seen_funcs = HashSet::new();
     let mut overloaded_funcs = HashSet::new();
     for func in db.ir().functions() {
-        if let Ok(Some(f)) = db.generate_func(func.clone()) {
-            let (.., function_id) = &f;
-            if !seen_funcs.insert(function_id.clone()) {
-                overloaded_funcs.insert(function_id.clone());
+        // The functions without their default arguments (see `generate_item_impl`)
+        // can collide with other functions too, unless they are only called by the
+        // function with `Option` parameters.
+        let funcs_without_default_args = if binds_default_args_as_options(db, &func) {
+            vec![]
+        } else {
+            funcs_without_default_args(db, &func)
+        };
+        let funcs = std::iter::once(func.clone())
+            .chain(funcs_without_default_args.into_iter().map(Rc::new));
+        for func in funcs {
+            if let Ok(Some(f)) = db.generate_func(func) {
+                let (.., function_id) = &f;
+                if !seen_funcs.insert(function_id.clone()) {
+                    overloaded_funcs.insert(function_id.clone());
+                }
             }
         }
     }
     Rc::new(overloaded_funcs)
 }
 
-// Returns the Rust code implementing bindings, plus any auxiliary C++ code
-// needed to support it.
-fn generate_bindings_tokens(
-    ir: Rc<IR>,
-    crubit_support_path: &str,
-    errors: Rc<dyn ErrorReporting>,
-    generate_source_loc_doc_comment: SourceLocationDocComment,
-) -> Result<BindingsTokens> {
-    let mut db = Database::default();
-    db.set_ir(ir.clone());
-    db.set_generate_source_loc_doc_comment(generate_source_loc_doc_comment);
-    db.set_errors(errors);
-    let mut items = vec![];
-    let mut thunks = vec![];
-    let mut thunk_impls = vec![
-        generate_rs_api_impl_includes(&mut db, crubit_support_path)?,
+/// The Rust name of a function renamed by `disambiguated_func_names`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DisambiguatedFuncName {
+    name: Rc<str>,
+    /// Whether the function was renamed because it is overloaded in C++ (as
+    /// opposed to only because its Rust name collides with another C++ name).
+    is_overload: bool,
+    /// Whether `name` is also the Rust name of another C++ function in the same
+    /// scope, in which case the function doesn't get bindings.
+    collides: bool,
+}
+
+/// Renames functions whose Rust names collide:
+///
+/// * If the Rust names of functions collide even though their C++ names are
+///   different (e.g. `FooBar` and `foo_bar` with `--snake_case_function_names`,
+///   or `self` and `self_`), the function whose C++ name is spelled like the
+///   colliding Rust name keeps it, and the other functions get numeric
+///   suffixes (`foo_bar_2`, `foo_bar_3`, ...), in the alphabetical order of
+///   their C++ names. Suffixes that would give a function the Rust name of
+///   another function (e.g. a C++ function named `foo_bar_2`) are skipped.
+/// * Overloads of a C++ function get suffixes derived from their parameter
+///   types (see `overload_suffix`). The suffix of an overload doesn't depend on
+///   the other overloads, but a function that becomes overloaded is renamed
+///   (unless it has no parameters), and so is a function that stops being
+///   overloaded.
+///
+/// Functions that are not renamed are absent from the returned map, and so are
+/// the functions named with the `crubit_rust_name` attribute. A new name that is
+/// also the Rust name of another function in the same scope (e.g. `f_int` for
+/// `f(int)`, next to a C++ function named `f_int`) is marked as `collides`.
+/// Overloads that still collide (e.g. `f(int)` and `f(int) &&`) are left to
+/// `overloaded_funcs`.
+fn disambiguated_func_names(
+    db: &dyn BindingsGenerator,
+) -> Rc<HashMap<ItemId, DisambiguatedFuncName>> {
+    let ir = db.ir();
+    // Functions grouped by their scope and Rust name, and then by their C++ name.
+    let mut colliding_funcs = HashMap::<_, BTreeMap<(bool, Rc<str>), Vec<Rc<Func>>>>::new();
+    // The C++ names of the functions with a `crubit_rust_name`, by their scope and
+    // Rust name.
+    let mut explicitly_named_funcs = HashMap::<_, HashSet<Rc<str>>>::new();
+    for func in ir.functions() {
+        let id = match &func.name {
+            UnqualifiedIdentifier::Identifier(id) => id,
+            _ => continue,
+        };
+        let namespace_qualifier = match namespace_qualifier_of_item(func.id, &ir) {
+            Ok(namespace_qualifier) => namespace_qualifier,
+            Err(_) => continue,
+        };
+        let record_id = func.member_func_metadata.as_ref().map(|meta| meta.record_id);
+        if let Some(rust_name) = &func.rust_name {
+            explicitly_named_funcs
+                .entry((record_id, namespace_qualifier, rust_name.to_string()))
+                .or_default()
+                .insert(id.identifier.clone());
+            continue;
+        }
+        let base_name = func_base_name(func, &id.identifier);
+        let rs_name = make_rs_ident(&rs_func_name(db, &base_name)).to_string();
+        let is_renamed = make_rs_ident(&base_name).to_string() != rs_name;
+        colliding_funcs
+            .entry((record_id, namespace_qualifier, rs_name))
+            .or_default()
+            .entry((is_renamed, Rc::<str>::from(base_name)))
+            .or_default()
+            .push(func.clone());
+    }
+
+    let mut disambiguated_names = HashMap::new();
+    // The C++ names of all functions, by their scope and final Rust name.
+    let mut funcs_by_final_name = explicitly_named_funcs;
+    // The Rust names of all functions before disambiguation, which numeric
+    // suffixes skip.
+    let taken_names: HashSet<_> = colliding_funcs
+        .keys()
+        .map(|(record_id, namespace_qualifier, rs_name)| {
+            (*record_id, namespace_qualifier.clone(), rs_name.trim_start_matches("r#").to_string())
+        })
+        .chain(funcs_by_final_name.keys().cloned())
+        .collect();
+    let mut renamed_funcs = vec![];
+    for ((record_id, namespace_qualifier, rs_name), funcs_by_cc_name) in colliding_funcs {
+        let rs_name = rs_name.trim_start_matches("r#");
+        let mut next_suffix = 2;
+        for (i, ((_, cc_name), funcs)) in funcs_by_cc_name.into_iter().enumerate() {
+            let name = if i == 0 {
+                rs_name.to_string()
+            } else {
+                loop {
+                    let name = format!("{rs_name}_{next_suffix}");
+                    next_suffix += 1;
+                    if !taken_names.contains(&(
+                        record_id,
+                        namespace_qualifier.clone(),
+                        name.clone(),
+                    )) {
+                        break name;
+                    }
+                }
+            };
+            let is_overload = funcs.len() > 1;
+            for func in funcs {
+                let suffix = match overload_suffix(&func, &ir) {
+                    Ok(suffix) if is_overload => suffix,
+                    _ => String::new(),
+                };
+                let name =
+                    if suffix.is_empty() { name.clone() } else { format!("{name}_{suffix}") };
+                let scope_and_name = (record_id, namespace_qualifier.clone(), name.clone());
+                funcs_by_final_name
+                    .entry(scope_and_name.clone())
+                    .or_default()
+                    .insert(cc_name.clone());
+                if name != rs_name {
+                    renamed_funcs.push((func.id, scope_and_name, !suffix.is_empty()));
+                }
+            }
+        }
+    }
+    for (func_id, scope_and_name, is_overload) in renamed_funcs {
+        // Overloads with the same C++ name that still collide are left to
+        // `overloaded_funcs`.
+        let collides = funcs_by_final_name[&scope_and_name].len() > 1;
+        disambiguated_names.insert(
+            func_id,
+            DisambiguatedFuncName { name: scope_and_name.2.into(), is_overload, collides },
+        );
+    }
+    Rc::new(disambiguated_names)
+}
+
+/// Returns the suffix that distinguishes the Rust name of an overloaded C++
+/// function from the other overloads: the words of its parameter types,
+/// followed by `const` for `const` methods (e.g. `int_ptr` for `f(int*)`, or
+/// `const` for `f() const`). The suffix is empty for an overload without
+/// parameters, which keeps the name of the C++ function.
+fn overload_suffix(func: &Func, ir: &IR) -> Result<String> {
+    let mut words = vec![];
+    let params = if func.is_instance_method() {
+        func.params.get(1..).unwrap_or(&[])
+    } else {
+        &func.params[..]
+    };
+    for param in params {
+        let cc_type =
+            format_cc_type_inner(&param.type_.cc_type, ir, /* references_ok= */ true)?;
+        words.extend(cc_spelling_words(&cc_type.to_string()));
+    }
+    let is_const_method = func
+        .member_func_metadata
+        .as_ref()
+        .and_then(|meta| meta.instance_method_metadata.as_ref())
+        .map_or(false, |instance_method| instance_method.is_const);
+    if is_const_method {
+        words.push("const".to_string());
+    }
+    Ok(words.join("_"))
+}
+
+fn new_database(ir: Rc<IR>, errors: Rc<dyn ErrorReporting>, options: CodegenOptions) -> Database {
+    let mut db = Database::default();
+    db.set_ir(ir);
+    db.set_generate_source_loc_doc_comment(options.generate_source_loc_doc_comment);
+    db.set_errors(errors);
+    db.set_generate_miri_compatible_bindings(options.generate_miri_compatible_bindings);
+    db.set_thunk_visibility(options.thunk_visibility);
+    db.set_generate_header_only_thunks(options.generate_header_only_thunks);
+    db.set_snake_case_function_names(options.snake_case_function_names);
+    db.set_constructor_builder_min_params(options.constructor_builder_min_params);
+    db.set_generate_raw_module(options.generate_raw_module);
+    db.set_pointer_unsafety(options.pointer_unsafety);
+    db.set_exception_policy(options.exception_policy);
+    db.set_errno_std_io_error(options.errno_std_io_error);
+    db.set_default_arguments(options.default_arguments);
+    db
+}
+
+// Returns the Rust code implementing bindings, plus any auxiliary C++ code
+// needed to support it.
+fn generate_bindings_tokens(
+    ir: Rc<IR>,
+    crubit_support_path: &str,
+    errors: Rc<dyn ErrorReporting>,
+    options: CodegenOptions,
+) -> Result<BindingsTokens> {
+    let mut db = new_database(ir.clone(), errors, options);
+    let mut items = vec![];
+    let mut thunks = vec![];
+    let mut thunk_impls = vec![];
+    if db.generate_header_only_thunks() {
+        thunk_impls.push(quote! { __HASH_TOKEN__ pragma once __NEWLINE__ });
+    }
+    thunk_impls.extend([
+        generate_rs_api_impl_includes(&mut db, crubit_support_path)?,
         quote! {
             __HASH_TOKEN__ pragma clang diagnostic push __NEWLINE__
             // Disable Clang thread-safety-analysis warnings that would otherwise
             // complain about thunks that call mutex locking functions in an unpaired way.
             __HASH_TOKEN__ pragma clang diagnostic ignored "-Wthread-safety-analysis" __NEWLINE__
         },
-    ];
+    ]);
     let mut assertions = vec![];
 
     // We import nullable pointers as an Option<&T> and assume that at the ABI
@@ -2968,11 +5512,18 @@ fn generate_bindings_tokens(
 
     // For #![rustfmt::skip].
     features.insert(make_rs_ident("custom_inner_attributes"));
+    let mut extern_crates = BTreeSet::new();
 
     for top_level_item_id in ir.top_level_item_ids() {
         let item =
             ir.find_decl(*top_level_item_id).context("Failed to look up ir.top_level_item_ids")?;
-        let generated = generate_item(&db, item)?;
+        let generated = match generate_split_namespace_reexport(&db, item)? {
+            SplitNamespaceItem::Generate => generate_item(&db, item)?,
+            SplitNamespaceItem::Skip => continue,
+            SplitNamespaceItem::Reexport(reexport) => {
+                GeneratedItem { item: reexport, ..Default::default() }
+            }
+        };
         items.push(generated.item);
         if !generated.thunks.is_empty() {
             thunks.push(generated.thunks);
@@ -2984,6 +5535,7 @@ fn generate_bindings_tokens(
             thunk_impls.push(generated.thunk_impls);
         }
         features.extend(generated.features);
+        extern_crates.extend(generated.extern_crates);
     }
 
     thunk_impls.push(quote! {
@@ -3007,6 +5559,12 @@ fn generate_bindings_tokens(
         }
     };
 
+    let mod_raw = if db.generate_raw_module() {
+        generate_raw_module_tokens(&db)
+    } else {
+        quote! {}
+    };
+
     let features = if features.is_empty() {
         quote! {}
     } else {
@@ -3033,16 +5591,507 @@ fn generate_bindings_tokens(
 
             #![deny(warnings)] __NEWLINE__ __NEWLINE__
 
+            #( extern crate #extern_crates; __NEWLINE__ )*
+
             #( #items __NEWLINE__ __NEWLINE__ )*
 
             #mod_detail __NEWLINE__ __NEWLINE__
 
+            #mod_raw __NEWLINE__ __NEWLINE__
+
             #( #assertions __NEWLINE__ __NEWLINE__ )*
         },
         rs_api_impl: quote! {#(#thunk_impls  __NEWLINE__ __NEWLINE__ )*},
     })
 }
 
+/// Returns the type to use for a harness-generated value of type `ty`, or
+/// `None` if `ty` is not a primitive type (which implements both
+/// `arbitrary::Arbitrary` and `Default`).
+///
+/// Type aliases are looked through, because the fuzz and benchmark harnesses
+/// live outside of the bindings crate and can't use `crate::`-relative paths.
+fn format_primitive_type(ty: &RsTypeKind) -> Option<TokenStream> {
+    match ty {
+        RsTypeKind::TypeAlias { underlying_type, .. } => format_primitive_type(underlying_type),
+        RsTypeKind::Other { name, type_args, .. } if type_args.is_empty() => {
+            let is_primitive = matches!(
+                name.as_ref(),
+                "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "isize"
+                    | "usize" | "f32" | "f64"
+            ) || matches!(
+                name.strip_prefix("::core::ffi::c_"),
+                Some(
+                    "char"
+                        | "schar"
+                        | "uchar"
+                        | "short"
+                        | "ushort"
+                        | "int"
+                        | "uint"
+                        | "long"
+                        | "ulong"
+                        | "longlong"
+                        | "ulonglong"
+                )
+            );
+            if is_primitive {
+                Some(ty.to_token_stream())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Generates `mod raw`, which exposes the `extern "C"` thunks of all the
+/// functions of the current target under readable names, as `unsafe fn`s that
+/// call them.
+///
+/// The thunks have the faithful signatures of the C++ functions (e.g. with raw
+/// pointers instead of references), which the rest of the crate wraps in a safe
+/// API. `mod raw` gives access to these signatures, e.g. to hand-write safe
+/// wrappers that the bindings don't provide. The thunks themselves stay private
+/// to the crate.
+///
+/// Free functions and inherent methods are named after their Rust path, with
+/// `::` replaced by `__` (e.g. `SomeStruct__method`). Trait impls are
+/// additionally prefixed with the `Self` type (e.g.
+/// `SomeStruct__Default__default`).
+fn generate_raw_module_tokens(db: &Database) -> TokenStream {
+    let ir = db.ir();
+    let crate_root_path = crate_root_path_tokens(&ir);
+    let raw_fns = raw_module_names(db)
+        .into_iter()
+        .map(|(func, thunk_ident, name, signature)| {
+            let ThunkSignature { generic_params, param_idents, param_types, return_type_fragment } =
+                &*signature;
+            let raw_fn = quote! {
+                #[inline(always)]
+                pub unsafe fn #name #generic_params( #( #param_idents: #param_types ),*
+                ) #return_type_fragment {
+                    #crate_root_path::detail::#thunk_ident( #( #param_idents ),* )
+                }
+            };
+            if refers_to_deprecated(db, &Item::Func(func)) {
+                allow_deprecated(raw_fn)
+            } else {
+                raw_fn
+            }
+        })
+        .collect_vec();
+    if raw_fns.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        pub mod raw {
+            #( #raw_fns )*
+        }
+    }
+}
+
+/// Returns the thunks of the current target that are exposed by `mod raw`,
+/// together with their functions, their names in `mod raw` and their
+/// signatures.
+fn raw_module_names(db: &Database) -> Vec<(Rc<Func>, Ident, Ident, Rc<ThunkSignature>)> {
+    let ir = db.ir();
+    let overloaded_funcs = db.overloaded_funcs();
+    let mut used_names = HashSet::new();
+    let mut names = vec![];
+    for func in ir.functions() {
+        if !ir.is_current_target(&func.owning_target) {
+            continue;
+        }
+        let (signature, function_id) = match db.generate_func(func.clone()) {
+            Ok(Some((generated, function_id))) if !overloaded_funcs.contains(&function_id) => {
+                match &generated.thunk_signature {
+                    Some(signature) => (signature.clone(), function_id),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        let thunk_ident = thunk_ident(func);
+        let name = function_id
+            .self_type
+            .iter()
+            .flat_map(|self_type| self_type.segments.iter())
+            .chain(function_id.function_path.segments.iter())
+            .map(|segment| segment.ident.to_string().trim_start_matches("r#").to_string())
+            .join("__");
+        // Distinct trait impls can share a path (e.g. `From::from`), in which case
+        // only the first one gets the readable name.
+        let name = if used_names.insert(name.clone()) {
+            format_ident!("{name}")
+        } else {
+            thunk_ident.clone()
+        };
+        names.push((func.clone(), thunk_ident, name, signature));
+    }
+    names
+}
+
+/// A free function of the current target that the fuzz harness and the
+/// benchmark call.
+struct HarnessFunc {
+    func: Rc<Func>,
+    function_id: Rc<FunctionId>,
+    /// The types of the parameters, which are all primitive types (see
+    /// `format_primitive_type`).
+    param_types: Vec<TokenStream>,
+}
+
+/// Returns the free functions of the current target that have safe bindings and
+/// only take primitive types, for which any input can be marshalled without
+/// knowing about the invariants of the C++ library. Functions that take other
+/// types (including records, pointers and references) are not supported by the
+/// fuzz harness and the benchmark.
+fn harness_funcs(db: &Database) -> Vec<HarnessFunc> {
+    let ir = db.ir();
+    let mut harness_funcs = vec![];
+    for func in ir.functions() {
+        if !ir.is_current_target(&func.owning_target)
+            || func.member_func_metadata.is_some()
+            || func.is_variadic
+        {
+            continue;
+        }
+        let function_id = match db.generate_func(func.clone()) {
+            Ok(Some((_, function_id))) if function_id.self_type.is_none() => function_id,
+            _ => continue,
+        };
+        let param_types = match func
+            .params
+            .iter()
+            .map(|p| db.rs_type_kind(p.type_.rs_type.clone()).ok())
+            .map(|t| t.as_ref().and_then(format_primitive_type))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(param_types) => param_types,
+            None => continue,
+        };
+        harness_funcs.push(HarnessFunc { func: func.clone(), function_id, param_types });
+    }
+    harness_funcs
+}
+
+/// Generates a cargo-fuzz / libFuzzer harness that calls the free functions of
+/// the current target with `arbitrary`-derived arguments.
+///
+/// Only functions that have safe bindings and that take nothing but primitive
+/// types are exercised (see `harness_funcs`). It is an error if there are no
+/// such functions.
+fn generate_fuzz_harness_tokens(db: &Database) -> Result<TokenStream> {
+    let ir = db.ir();
+    let crate_ident = make_rs_ident(&ir.current_target().target_name().replace('-', "_"));
+    let mut variants = vec![];
+    let mut arms = vec![];
+    for HarnessFunc { function_id, param_types, .. } in harness_funcs(db) {
+        let variant = format_ident!(
+            "{}",
+            function_id
+                .function_path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string().trim_start_matches("r#").to_string())
+                .join("__")
+        );
+        let args = (0..param_types.len()).map(|i| format_ident!("arg{i}")).collect_vec();
+        let function_path = &function_id.function_path;
+        variants.push(quote! { #variant( #( #param_types ),* ) });
+        arms.push(quote! {
+            FuzzCall::#variant( #( #args ),* ) => {
+                let _ = #crate_ident :: #function_path( #( #args ),* );
+            }
+        });
+    }
+    ensure!(
+        !variants.is_empty(),
+        "The fuzz harness only supports free functions with safe bindings that take primitive \
+         types, and `{}` has none",
+        ir.current_target()
+    );
+
+    Ok(quote! {
+        #![no_main] __NEWLINE__
+        #![allow(non_camel_case_types)] __NEWLINE__ __NEWLINE__
+
+        #[derive(Debug, ::arbitrary::Arbitrary)]
+        enum FuzzCall {
+            #( #variants ),*
+        }
+        __NEWLINE__
+
+        ::libfuzzer_sys::fuzz_target!(|calls: Vec<FuzzCall>| {
+            for call in calls {
+                match call {
+                    #( #arms )*
+                }
+            }
+        });
+    })
+}
+
+/// Generates a Criterion benchmark that measures the call overhead of the free
+/// functions of the current target.
+///
+/// Each function that has safe bindings and takes and returns nothing but
+/// primitive types (see `harness_funcs`) gets a benchmark group with a
+/// `binding` benchmark, which calls the safe Rust function (and so the C++
+/// thunk, if there is one). If the C++ function can be called without a thunk
+/// (see `can_call_cc_function_directly`, which e.g. excludes `inline`
+/// functions, which might not be emitted by the C++ library), the group also
+/// has a `direct` benchmark, which calls the mangled C++ symbol directly. A
+/// large difference between the two indicates that the function would benefit
+/// from thunk elision. With `--generate_raw_module`, the group also has a `raw`
+/// benchmark, which calls the `extern "C"` declaration of the bindings in
+/// `mod raw`, to tell the overhead of the Rust side of the bindings apart.
+fn generate_benchmark_tokens(db: &Database) -> Result<TokenStream> {
+    let ir = db.ir();
+    let crate_ident = make_rs_ident(&ir.current_target().target_name().replace('-', "_"));
+    let mut direct_decls = vec![];
+    let raw_names: HashMap<Ident, Ident> = if db.generate_raw_module() {
+        raw_module_names(db)
+            .into_iter()
+            .map(|(_, thunk_ident, name, _)| (thunk_ident, name))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let mut benches = vec![];
+    for HarnessFunc { func, function_id, param_types } in harness_funcs(db) {
+        let return_type = match db.rs_type_kind(func.return_type.rs_type.clone()) {
+            Ok(RsTypeKind::Unit) => quote! {},
+            Ok(return_type) => match format_primitive_type(&return_type) {
+                Some(return_type) => quote! { -> #return_type },
+                None => continue,
+            },
+            Err(_) => continue,
+        };
+        let group_name = function_id
+            .function_path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string().trim_start_matches("r#").to_string())
+            .join("::");
+        let args =
+            param_types.iter().map(|_| quote! { black_box(Default::default()) }).collect_vec();
+        let function_path = &function_id.function_path;
+        let direct_bench = if can_call_cc_function_directly(db, &func) {
+            let direct_ident = format_ident!("direct_{}", direct_decls.len());
+            let mangled_name = func.mangled_name.as_ref();
+            let params = (0..param_types.len()).map(|i| format_ident!("arg{i}")).collect_vec();
+            direct_decls.push(quote! {
+                #[link_name = #mangled_name]
+                pub(crate) fn #direct_ident( #( #params: #param_types ),* ) #return_type;
+            });
+            quote! {
+                group.bench_function("direct", |b| {
+                    b.iter(|| unsafe { direct::#direct_ident( #( #args ),* ) })
+                });
+            }
+        } else {
+            quote! {}
+        };
+        let raw_bench = match raw_names.get(&thunk_ident(&func)) {
+            Some(raw_name) => quote! {
+                group.bench_function("raw", |b| {
+                    b.iter(|| unsafe { #crate_ident::raw::#raw_name( #( #args ),* ) })
+                });
+            },
+            None => quote! {},
+        };
+        benches.push(quote! {
+            let mut group = c.benchmark_group(#group_name);
+            group.bench_function("binding", |b| {
+                b.iter(|| #crate_ident :: #function_path( #( #args ),* ))
+            });
+            #direct_bench
+            #raw_bench
+            group.finish();
+        });
+    }
+
+    Ok(quote! {
+        #![allow(clippy::unit_arg)] __NEWLINE__ __NEWLINE__
+
+        use ::criterion::black_box;
+        __NEWLINE__
+
+        mod direct {
+            extern "C" {
+                #( #direct_decls )*
+            }
+        }
+        __NEWLINE__
+
+        fn bench_bindings(c: &mut ::criterion::Criterion) {
+            #( #benches )*
+        }
+        __NEWLINE__
+
+        ::criterion::criterion_group!(benches, bench_bindings);
+        ::criterion::criterion_main!(benches);
+    })
+}
+
+/// Returns the file and line of a source location recorded by the importer,
+/// e.g. `Generated from: google3/foo/bar.h;l=12`.
+fn parse_source_loc(source_loc: &str) -> Option<(&str, u32)> {
+    let location = source_loc.lines().next()?.strip_prefix("Generated from: ")?;
+    let location = location.strip_prefix("google3/").unwrap_or(location);
+    let (file, line) = location.rsplit_once(";l=")?;
+    Some((file, line.parse().ok()?))
+}
+
+/// Returns statistics about the bindings as JSON: the number of items of the
+/// current target that were imported and bound (by kind), the number of items
+/// skipped for each reason, and the number of C++ thunks.
+fn generate_stats_json(db: &Database) -> Result<String> {
+    let ir = db.ir();
+    let mut imported: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut bound: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut skipped: BTreeMap<Rc<str>, u64> = BTreeMap::new();
+    let mut thunks = 0;
+    for item in ir.items() {
+        let kind = match item {
+            Item::Func(_) => "functions",
+            Item::IncompleteRecord(_) => "incomplete_records",
+            Item::Record(_) => "records",
+            Item::Enum(_) => "enums",
+            Item::TypeAlias(_) => "type_aliases",
+            Item::Constant(_) => "constants",
+            Item::GlobalVar(_) => "global_vars",
+            Item::UnsupportedItem(unsupported) => {
+                *skipped.entry(error_category(unsupported.cause()).into()).or_default() += 1;
+                continue;
+            }
+            Item::Comment(_) | Item::Namespace(_) | Item::UseMod(_) | Item::TypeMapOverride(_) => {
+                continue;
+            }
+        };
+        if !item.owning_target().map_or(false, |target| ir.is_current_target(target)) {
+            continue;
+        }
+        *imported.entry(kind).or_default() += 1;
+        if let Item::Func(func) = item {
+            // Functions that are imported as "nothing" (e.g. trivial destructors) are
+            // neither bound nor skipped.
+            if let Ok(None) = db.generate_func(func.clone()) {
+                continue;
+            }
+        }
+        match generate_item_impl(db, item) {
+            Ok(_) => {
+                *bound.entry(kind).or_default() += 1;
+                if let Item::Func(func) = item {
+                    if !can_skip_cc_thunk(db, func) {
+                        thunks += 1;
+                    }
+                }
+            }
+            Err(err) => *skipped.entry(error_category(&err).into()).or_default() += 1,
+        }
+    }
+    let stats = serde_json::json!({
+        "target": ir.current_target().0.as_ref(),
+        "imported_items": imported,
+        "bound_items": bound,
+        "skipped_items": skipped,
+        "thunks": thunks,
+    });
+    Ok(serde_json::to_string_pretty(&stats)?)
+}
+
+/// Formats a Rust path without the spaces that `TokenStream::to_string` adds,
+/// e.g. `foo::Bar`.
+fn format_rs_path(path: impl ToTokens) -> String {
+    path.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Returns a JSON source map of the bindings, which maps the Rust path of each
+/// generated item (and the C++ thunk of each generated function) to the C++
+/// declaration it was generated from.
+fn generate_source_map_json(db: &Database) -> Result<String> {
+    let ir = db.ir();
+    let mut items = vec![];
+    for item in ir.items() {
+        let (rs_path, thunk) = match item {
+            Item::Func(func) if ir.is_current_target(&func.owning_target) => {
+                let function_id = match db.generate_func(func.clone()) {
+                    Ok(Some((_, function_id))) => function_id,
+                    _ => continue,
+                };
+                let function_path = format_rs_path(&function_id.function_path);
+                let rs_path = match &function_id.self_type {
+                    None => function_path,
+                    // `function_path` is the path of the trait method, e.g.
+                    // `Default::default`.
+                    Some(self_type) => match function_path.rsplit_once("::") {
+                        Some((trait_path, method)) => {
+                            format!("<{} as {trait_path}>::{method}", format_rs_path(self_type))
+                        }
+                        None => function_path,
+                    },
+                };
+                let thunk =
+                    if can_skip_cc_thunk(db, func) { None } else { Some(thunk_ident(func)) };
+                (rs_path, thunk)
+            }
+            Item::Record(record) if ir.is_current_target(&record.owning_target) => {
+                let namespace_qualifier =
+                    namespace_qualifier_of_item(record.id, &ir)?.format_for_rs();
+                let ident = make_rs_ident(&record.rs_name);
+                (format_rs_path(quote! { #namespace_qualifier #ident }), None)
+            }
+            Item::Enum(enum_) if ir.is_current_target(&enum_.owning_target) => {
+                let namespace_qualifier =
+                    namespace_qualifier_of_item(enum_.id, &ir)?.format_for_rs();
+                let ident = make_rs_ident(&enum_.identifier.identifier);
+                (format_rs_path(quote! { #namespace_qualifier #ident }), None)
+            }
+            Item::TypeAlias(type_alias)
+                if ir.is_current_target(&type_alias.owning_target)
+                    && type_alias.enclosing_record_id.is_none() =>
+            {
+                let namespace_qualifier =
+                    namespace_qualifier_of_item(type_alias.id, &ir)?.format_for_rs();
+                let ident = make_rs_ident(&type_alias.identifier.identifier);
+                (format_rs_path(quote! { #namespace_qualifier #ident }), None)
+            }
+            Item::Constant(constant)
+                if ir.is_current_target(&constant.owning_target)
+                    && constant.enclosing_record_id.is_none() =>
+            {
+                let namespace_qualifier =
+                    namespace_qualifier_of_item(constant.id, &ir)?.format_for_rs();
+                let ident = make_rs_ident(&constant.identifier.identifier);
+                (format_rs_path(quote! { #namespace_qualifier #ident }), None)
+            }
+            _ => continue,
+        };
+        let mut entry = serde_json::Map::new();
+        entry.insert("rs_path".into(), rs_path.into());
+        let namespace_qualifier = namespace_qualifier_of_item(item.id(), &ir)?.format_for_cc()?;
+        let cc_name = format!("{}{}", format_rs_path(namespace_qualifier), item.debug_name(&ir));
+        entry.insert("cc_name".into(), cc_name.into());
+        if let Some((file, line)) = item.source_loc().as_deref().and_then(parse_source_loc) {
+            entry.insert("file".into(), file.into());
+            entry.insert("line".into(), line.into());
+        }
+        if let Some(thunk) = thunk {
+            entry.insert("thunk".into(), thunk.to_string().into());
+        }
+        items.push(serde_json::Value::Object(entry));
+    }
+    let source_map = serde_json::json!({
+        "target": ir.current_target().0.as_ref(),
+        "items": items,
+    });
+    Ok(serde_json::to_string_pretty(&source_map)?)
+}
+
 /// Formats a C++ identifier.  Panics if `ident` is a C++ reserved keyword.
 fn format_cc_ident(ident: &str) -> TokenStream {
     code_gen_utils::format_cc_ident(ident).expect("IR should only contain valid C++ identifiers")
@@ -3197,6 +6246,11 @@ enum RsTypeKind {
         return_type: Rc<RsTypeKind>,
         param_types: Rc<[RsTypeKind]>,
     },
+    /// A C++ array, e.g. `[i32; 16]` for `int[16]`.
+    Array {
+        element_type: Rc<RsTypeKind>,
+        size: usize,
+    },
     /// An incomplete record type.
     IncompleteRecord {
         incomplete_record: Rc<IncompleteRecord>,
@@ -3207,6 +6261,11 @@ enum RsTypeKind {
         record: Rc<Record>,
         crate_path: Rc<CratePath>,
     },
+    /// An enum type (scoped or unscoped).
+    Enum {
+        enum_: Rc<Enum>,
+        crate_path: Rc<CratePath>,
+    },
     TypeAlias {
         type_alias: Rc<TypeAlias>,
         underlying_type: Rc<RsTypeKind>,
@@ -3244,6 +6303,7 @@ impl RsTypeKind {
             RsTypeKind::IncompleteRecord { .. } => false,
             RsTypeKind::Record { record, .. } => record.is_unpin(),
             RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_unpin(),
+            RsTypeKind::Array { element_type, .. } => element_type.is_unpin(),
             _ => true,
         }
     }
@@ -3255,9 +6315,8 @@ impl RsTypeKind {
             RsTypeKind::TypeAlias { underlying_type, .. } => {
                 underlying_type.is_c_abi_compatible_by_value()
             }
-            RsTypeKind::IncompleteRecord { .. } => {
-                panic!("IncompleteRecord is unexpected as a parameter type or return type")
-            }
+            // Values of incomplete types can't exist (see `check_by_value`).
+            RsTypeKind::IncompleteRecord { .. } => false,
             // `rs_bindings_from_cc` can change the type of fields (e.g. using a blob of bytes for
             // unsupported field types, or for no_unique_address fields).  Changing the type
             // of fields may change the ABI, which means that we can no longer assume
@@ -3266,6 +6325,8 @@ impl RsTypeKind {
             // TODO(b/274177296): Return `true` for structs where bindings replicate the type of
             // all the fields.
             RsTypeKind::Record { .. } => false,
+            // C and C++ don't pass arrays by value.
+            RsTypeKind::Array { .. } => false,
             RsTypeKind::Other { is_same_abi, .. } => *is_same_abi,
             _ => true,
         }
@@ -3285,6 +6346,7 @@ impl RsTypeKind {
             RsTypeKind::TypeAlias { underlying_type, .. } => {
                 underlying_type.is_move_constructible()
             }
+            RsTypeKind::Array { element_type, .. } => element_type.is_move_constructible(),
             _ => true,
         }
     }
@@ -3294,7 +6356,38 @@ impl RsTypeKind {
     pub fn check_by_value(&self) -> Result<()> {
         match self {
             RsTypeKind::Record { record, .. } => check_by_value(record),
+            // Forward-declared types are opaque: their size isn't known, so they are only
+            // supported behind pointers and references.
+            RsTypeKind::IncompleteRecord { incomplete_record, .. } => bail!(
+                "Can't directly construct values of type `{}` as it is only forward-declared",
+                incomplete_record.cc_name.as_ref()
+            ),
+            // Vectors are owned by C++, and only accessed through references from Rust.
+            RsTypeKind::Other { name, .. } if is_cxx_vector(name) => {
+                bail!("`std::vector` is only supported behind references")
+            }
+            // Rust moves optionals (and their values) with `memcpy`.
+            RsTypeKind::Other { name, type_args, .. } if is_cxx_optional(name) => {
+                for value_type in type_args.iter() {
+                    value_type.check_by_value()?;
+                    if !value_type.is_unpin() {
+                        bail!("`std::optional` is only supported for Unpin value types")
+                    }
+                }
+                Ok(())
+            }
+            // Rust moves the values out of `StatusOr`s with `memcpy`.
+            RsTypeKind::Other { name, type_args, .. } if is_absl_status_or(name) => {
+                for value_type in type_args.iter() {
+                    value_type.check_by_value()?;
+                    if !value_type.is_unpin() {
+                        bail!("`absl::StatusOr` is only supported for Unpin value types")
+                    }
+                }
+                Ok(())
+            }
             RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.check_by_value(),
+            RsTypeKind::Array { element_type, .. } => element_type.check_by_value(),
             _ => Ok(()),
         }
     }
@@ -3321,6 +6414,18 @@ impl RsTypeKind {
         }
     }
 
+    /// Formats this RsTypeKind as `*mut MaybeUninit<SomeStruct>`. This is used
+    /// instead of `format_mut_ref_as_uninitialized` with
+    /// `--generate_miri_compatible_bindings`.
+    pub fn format_mut_ref_as_uninitialized_ptr(&self) -> Result<TokenStream> {
+        match self {
+            RsTypeKind::Reference { referent, mutability: Mutability::Mut, .. } => {
+                Ok(quote! { *mut ::core::mem::MaybeUninit< #referent > })
+            }
+            _ => bail!("Expected reference to format as MaybeUninit, got: {:?}", self),
+        }
+    }
+
     /// Formats this RsTypeKind as the `self` parameter: usually, `&'a self` or
     /// `&'a mut self`.
     ///
@@ -3387,7 +6492,25 @@ impl RsTypeKind {
             RsTypeKind::RvalueReference { .. } => false,
             RsTypeKind::IncompleteRecord { .. } => false,
             RsTypeKind::Record { record, .. } => should_derive_copy(record),
+            RsTypeKind::Enum { .. } => true,
             RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.implements_copy(),
+            RsTypeKind::Array { element_type, .. } => element_type.implements_copy(),
+            // `NonNull<T>` is `Copy` like `*mut T`, regardless of `T`.
+            RsTypeKind::Other { name, .. } if is_non_null(name) => true,
+            // `CxxString` owns the C++ `std::string`, and destroys it on drop.
+            RsTypeKind::Other { name, .. } if &**name == "::cxx_string::CxxString" => false,
+            // `CxxVector` owns the elements of the C++ `std::vector`.
+            RsTypeKind::Other { name, .. } if is_cxx_vector(name) => false,
+            // `CxxOptional` destroys its value on drop.
+            RsTypeKind::Other { name, .. } if is_cxx_optional(name) => false,
+            // `UniquePtr` deletes the C++ object on drop.
+            RsTypeKind::Other { name, .. } if is_cxx_unique_ptr(name) => false,
+            // Copying a `SharedPtr` updates the reference count in C++.
+            RsTypeKind::Other { name, .. } if is_cxx_shared_ptr(name) => false,
+            // Statuses are reference counted in C++.
+            RsTypeKind::Other { name, .. } if is_absl_status(name) || is_absl_status_or(name) => {
+                false
+            }
             RsTypeKind::Other { type_args, .. } => {
                 // All types that may appear here without `type_args` (e.g.
                 // primitive types like `i32`) implement `Copy`. Generic types
@@ -3398,47 +6521,132 @@ impl RsTypeKind {
         }
     }
 
-    pub fn is_ref_to(&self, expected_record: &Record) -> bool {
+    /// Returns whether the type represented by `self` is a signed integer
+    /// (looking through type aliases).
+    pub fn is_signed_integer(&self) -> bool {
         match self {
-            RsTypeKind::Reference { referent, .. } => referent.is_record(expected_record),
-            RsTypeKind::RvalueReference { referent, .. } => referent.is_record(expected_record),
+            RsTypeKind::Other { name, type_args, .. } if type_args.is_empty() => matches!(
+                name.as_ref(),
+                "i8" | "i16"
+                    | "i32"
+                    | "i64"
+                    | "isize"
+                    | "::core::ffi::c_schar"
+                    | "::core::ffi::c_short"
+                    | "::core::ffi::c_int"
+                    | "::core::ffi::c_long"
+                    | "::core::ffi::c_longlong"
+            ),
+            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_signed_integer(),
             _ => false,
         }
     }
 
-    pub fn is_shared_ref_to(&self, expected_record: &Record) -> bool {
+    /// Returns whether the type represented by `self` is a raw pointer (looking
+    /// through type aliases).
+    ///
+    /// `NonNull<T>` and `Option<NonNull<T>>`, which annotated pointers without
+    /// a lifetime are bound as, count as raw pointers.
+    pub fn is_raw_pointer(&self) -> bool {
         match self {
-            RsTypeKind::Reference { referent, mutability: Mutability::Const, .. } => {
-                referent.is_record(expected_record)
+            RsTypeKind::Pointer { .. } => true,
+            RsTypeKind::Other { name, .. } if is_non_null(name) => true,
+            RsTypeKind::Other { name, type_args, .. } if &**name == "Option" => {
+                type_args.iter().any(|t| t.is_raw_pointer())
             }
+            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_raw_pointer(),
             _ => false,
         }
     }
 
-    pub fn is_record(&self, expected_record: &Record) -> bool {
+    /// Returns whether the type represented by `self` is one of the types that
+    /// `gsl::span` is bound as (looking through type aliases).
+    pub fn is_span(&self) -> bool {
         match self {
-            RsTypeKind::Record { record: actual_record, .. } => {
-                actual_record.id == expected_record.id
-            }
+            RsTypeKind::Other { name, .. } => is_gsl_span(name),
+            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_span(),
             _ => false,
         }
     }
 
-    pub fn is_bool(&self) -> bool {
+    /// Returns whether the type represented by `self` contains a raw pointer
+    /// anywhere, e.g. `*mut T`, `&mut *mut T` or `Option<&*const T>`. The
+    /// pointers in `gsl::span` types count, too.
+    ///
+    /// Function pointer types and records are not looked into: the pointers
+    /// they contain aren't dereferenced just because the value is passed on.
+    pub fn contains_raw_pointer(&self) -> bool {
         match self {
-            RsTypeKind::Other { name, .. } => &**name == "bool",
-            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_bool(),
-            _ => false,
+            RsTypeKind::Pointer { .. } => true,
+            RsTypeKind::Other { name, .. } if is_gsl_span(name) || is_non_null(name) => true,
+            RsTypeKind::Unit
+            | RsTypeKind::FuncPtr { .. }
+            | RsTypeKind::IncompleteRecord { .. }
+            | RsTypeKind::Record { .. }
+            | RsTypeKind::Enum { .. } => false,
+            RsTypeKind::Reference { referent, .. }
+            | RsTypeKind::RvalueReference { referent, .. } => referent.contains_raw_pointer(),
+            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.contains_raw_pointer(),
+            RsTypeKind::Array { element_type, .. } => element_type.contains_raw_pointer(),
+            RsTypeKind::Other { type_args, .. } => {
+                type_args.iter().any(|t| t.contains_raw_pointer())
+            }
         }
     }
 
-    /// Iterates over `self` and all the nested types (e.g. pointees, generic
-    /// type args, etc.) in DFS order.
-    pub fn dfs_iter(&self) -> impl Iterator<Item = &RsTypeKind> + '_ {
-        RsTypeKindIter::new(self)
+    /// Returns whether the type represented by `self` refers to a
+    /// `[[deprecated]]` C++ declaration anywhere, e.g. `*mut SomeStruct`
+    /// where `SomeStruct` is deprecated.
+    pub fn refers_to_deprecated(&self) -> bool {
+        self.dfs_iter().any(|ty| match ty {
+            RsTypeKind::Record { record, .. } => record.deprecated.is_some(),
+            RsTypeKind::Enum { enum_, .. } => enum_.deprecated.is_some(),
+            RsTypeKind::TypeAlias { type_alias, .. } => type_alias.deprecated.is_some(),
+            _ => false,
+        })
     }
 
-    /// Iterates over all `LifetimeId`s in `self` and in all the nested types.
+    pub fn is_ref_to(&self, expected_record: &Record) -> bool {
+        match self {
+            RsTypeKind::Reference { referent, .. } => referent.is_record(expected_record),
+            RsTypeKind::RvalueReference { referent, .. } => referent.is_record(expected_record),
+            _ => false,
+        }
+    }
+
+    pub fn is_shared_ref_to(&self, expected_record: &Record) -> bool {
+        match self {
+            RsTypeKind::Reference { referent, mutability: Mutability::Const, .. } => {
+                referent.is_record(expected_record)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_record(&self, expected_record: &Record) -> bool {
+        match self {
+            RsTypeKind::Record { record: actual_record, .. } => {
+                actual_record.id == expected_record.id
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_bool(&self) -> bool {
+        match self {
+            RsTypeKind::Other { name, .. } => &**name == "bool",
+            RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.is_bool(),
+            _ => false,
+        }
+    }
+
+    /// Iterates over `self` and all the nested types (e.g. pointees, generic
+    /// type args, etc.) in DFS order.
+    pub fn dfs_iter(&self) -> impl Iterator<Item = &RsTypeKind> + '_ {
+        RsTypeKindIter::new(self)
+    }
+
+    /// Iterates over all `LifetimeId`s in `self` and in all the nested types.
     /// Note that the results might contain duplicate LifetimeId values (e.g.
     /// if the same LifetimeId is used in two `type_args`).
     pub fn lifetimes(&self) -> impl Iterator<Item = Lifetime> + '_ {
@@ -3505,6 +6713,11 @@ impl RsTypeKind {
                 let return_frag = return_type.format_as_return_type_fragment(self_record);
                 quote! { extern #abi fn( #( #param_types_ ),* ) #return_frag }
             }
+            RsTypeKind::Array { element_type, size } => {
+                let element_type_ = element_type.to_token_stream_replacing_by_self(self_record);
+                let size = Literal::usize_unsuffixed(*size);
+                quote! { [#element_type_; #size] }
+            }
             RsTypeKind::Record { record, crate_path } => {
                 if self_record == Some(record) {
                     quote! { Self }
@@ -3576,6 +6789,10 @@ impl ToTokens for RsTypeKind {
                 let return_frag = return_type.format_as_return_type_fragment(None);
                 quote! { extern #abi fn( #( #param_types ),* ) #return_frag }
             }
+            RsTypeKind::Array { element_type, size } => {
+                let size = Literal::usize_unsuffixed(*size);
+                quote! { [#element_type; #size] }
+            }
             RsTypeKind::IncompleteRecord { incomplete_record, crate_path } => {
                 let record_ident = make_rs_ident(incomplete_record.rs_name.as_ref());
                 quote! { #crate_path #record_ident }
@@ -3584,6 +6801,10 @@ impl ToTokens for RsTypeKind {
                 let ident = make_rs_ident(record.rs_name.as_ref());
                 quote! { #crate_path #ident }
             }
+            RsTypeKind::Enum { enum_, crate_path } => {
+                let ident = make_rs_ident(&enum_.identifier.identifier);
+                quote! { #crate_path #ident }
+            }
             RsTypeKind::TypeAlias { type_alias, crate_path, .. } => {
                 let ident = make_rs_ident(&type_alias.identifier.identifier);
                 quote! { #crate_path #ident }
@@ -3601,6 +6822,140 @@ impl ToTokens for RsTypeKind {
     }
 }
 
+/// Returns whether `name` is one of the types that `gsl::span` is mapped to
+/// (see `support/gsl.rs`).
+fn is_gsl_span(name: &str) -> bool {
+    matches!(name, "::gsl::Span" | "::gsl::SpanMut")
+}
+
+/// Returns the C++ types of the `gsl::span`s in the signature of `func`.
+fn gsl_span_cc_types(func: &Func) -> Vec<&CcType> {
+    fn collect<'a>(rs_type: &RsType, cc_type: &'a CcType, spans: &mut Vec<&'a CcType>) {
+        if rs_type.name.as_deref().is_some_and(is_gsl_span) {
+            spans.push(cc_type);
+        } else {
+            for (rs_type_arg, cc_type_arg) in rs_type.type_args.iter().zip(&cc_type.type_args) {
+                collect(rs_type_arg, cc_type_arg, spans);
+            }
+        }
+    }
+    let mut spans = vec![];
+    for mapped_type in func.params.iter().map(|p| &p.type_).chain([&func.return_type]) {
+        collect(&mapped_type.rs_type, &mapped_type.cc_type, &mut spans);
+    }
+    spans
+}
+
+/// Returns `static_assert`s that the `gsl::span` types in the signature of
+/// `func` have the layout of `Span` and `SpanMut` (see `support/gsl.rs`): a
+/// size and a pointer, which can be passed by value across the FFI boundary.
+///
+/// The order of the size and the pointer isn't checked, because they are
+/// private members of `gsl::span`, which `offsetof` can't be used with.
+fn gsl_span_layout_assertions(func: &Func, ir: &IR) -> Result<TokenStream> {
+    let mut assertions = vec![];
+    let mut seen = HashSet::new();
+    for cc_type in gsl_span_cc_types(func) {
+        let cc_type = format_cc_type(cc_type, ir)?;
+        if !seen.insert(cc_type.to_string()) {
+            continue;
+        }
+        assertions.push(quote! {
+            static_assert(sizeof(#cc_type) == 2 * sizeof(void*));
+            static_assert(alignof(#cc_type) == alignof(void*));
+            static_assert(std::is_trivially_copyable_v<#cc_type>);
+        });
+    }
+    Ok(quote! { #( #assertions )* })
+}
+
+/// Returns whether `name` is the type that `std::vector` is mapped to (see
+/// `support/cxx_vector.rs`).
+fn is_cxx_vector(name: &str) -> bool {
+    name == "::cxx_vector::CxxVector"
+}
+
+/// Returns whether `name` is the type that `std::unique_ptr` is mapped to (see
+/// `support/cxx_unique_ptr.rs`).
+fn is_cxx_unique_ptr(name: &str) -> bool {
+    name == "::cxx_unique_ptr::UniquePtr"
+}
+
+/// Returns whether `name` is the type that `std::shared_ptr` is mapped to (see
+/// `support/cxx_shared_ptr.rs`).
+fn is_cxx_shared_ptr(name: &str) -> bool {
+    name == "::cxx_shared_ptr::SharedPtr"
+}
+
+/// Returns whether `name` is the type that `absl::Status` is mapped to (see
+/// `support/absl_status.rs`).
+fn is_absl_status(name: &str) -> bool {
+    name == "::absl_status::Status"
+}
+
+/// Returns whether `name` is the type that `absl::StatusOr` is mapped to (see
+/// `support/absl_status.rs`).
+fn is_absl_status_or(name: &str) -> bool {
+    name == "::absl_status::StatusOr"
+}
+
+/// Returns whether `name` is the type that `std::optional` is mapped to (see
+/// `support/cxx_optional.rs`).
+fn is_cxx_optional(name: &str) -> bool {
+    name == "::cxx_optional::CxxOptional"
+}
+
+/// Returns the value type `T` if `ty` is the `CxxOptional<T>` that
+/// `std::optional<T>` is mapped to.
+fn cxx_optional_value_type(ty: &RsTypeKind) -> Option<&RsTypeKind> {
+    match ty {
+        RsTypeKind::Other { name, type_args, .. } if is_cxx_optional(name) => type_args.first(),
+        _ => None,
+    }
+}
+
+/// Returns the element type `T` and the mutability of the elements if `ty` is
+/// one of the types that `absl::Span<T>` is mapped to (see
+/// `support/absl_span.rs`).
+fn absl_span_element_type(ty: &RsTypeKind) -> Option<(&RsTypeKind, Mutability)> {
+    match ty {
+        RsTypeKind::Other { name, type_args, .. } => {
+            let mutability = match &**name {
+                "::absl_span::Span" => Mutability::Const,
+                "::absl_span::SpanMut" => Mutability::Mut,
+                _ => return None,
+            };
+            type_args.first().map(|element_type| (element_type, mutability))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `ty` is the type that `std::string_view` and
+/// `absl::string_view` are mapped to (see `support/cxx_string.rs`).
+fn is_cxx_string_view(ty: &RsTypeKind) -> bool {
+    matches!(ty, RsTypeKind::Other { name, .. } if &**name == "::cxx_string::CxxStringView")
+}
+
+/// Returns whether `name` is one of the `core::arch` SIMD types that vector
+/// types (e.g. `__m128`) are mapped to.
+fn is_core_arch_vector(name: &str) -> bool {
+    name.starts_with("::core::arch::")
+}
+
+/// Returns whether `name` is `NonNull`, which pointers without a lifetime that are
+/// annotated as `_Nonnull` or `_Nullable` are bound as (the latter wrapped in an
+/// `Option`).
+fn is_non_null(name: &str) -> bool {
+    name == "::core::ptr::NonNull"
+}
+
+/// Returns whether `name` is one of the types that half-precision floats are
+/// mapped to (see `support/half_float.rs`).
+fn is_half_float(name: &str) -> bool {
+    matches!(name, "::half_float::F16" | "::half_float::Bf16")
+}
+
 struct RsTypeKindIter<'ty> {
     todo: Vec<&'ty RsTypeKind>,
 }
@@ -3621,11 +6976,13 @@ impl<'ty> Iterator for RsTypeKindIter<'ty> {
                 match curr {
                     RsTypeKind::Unit
                     | RsTypeKind::IncompleteRecord { .. }
-                    | RsTypeKind::Record { .. } => {}
+                    | RsTypeKind::Record { .. }
+                    | RsTypeKind::Enum { .. } => {}
                     RsTypeKind::Pointer { pointee, .. } => self.todo.push(pointee),
                     RsTypeKind::Reference { referent, .. } => self.todo.push(referent),
                     RsTypeKind::RvalueReference { referent, .. } => self.todo.push(referent),
                     RsTypeKind::TypeAlias { underlying_type: t, .. } => self.todo.push(t),
+                    RsTypeKind::Array { element_type, .. } => self.todo.push(element_type),
                     RsTypeKind::FuncPtr { return_type, param_types, .. } => {
                         self.todo.push(return_type);
                         self.todo.extend(param_types.iter().rev());
@@ -3699,7 +7056,18 @@ fn rs_type_kind(db: &dyn BindingsGenerator, ty: ir::RsType) -> Result<RsTypeKind
                         rs_imported_crate_name(&incomplete_record.owning_target, &ir),
                     )),
                 },
-                Item::Record(record) => RsTypeKind::new_record(record.clone(), &ir)?,
+                Item::Record(record) => {
+                    ensure_nested_record_name_is_unique(&ir, record)?;
+                    RsTypeKind::new_record(record.clone(), &ir)?
+                }
+                Item::Enum(enum_) => RsTypeKind::Enum {
+                    enum_: enum_.clone(),
+                    crate_path: Rc::new(CratePath::new(
+                        &ir,
+                        namespace_qualifier_of_item(enum_.id, &ir)?,
+                        rs_imported_crate_name(&enum_.owning_target, &ir),
+                    )),
+                },
                 Item::TypeAlias(type_alias) => {
                     // TODO(b/200067824): support nested type aliases.
                     if type_alias.enclosing_record_id.is_some() {
@@ -3756,13 +7124,75 @@ fn rs_type_kind(db: &dyn BindingsGenerator, ty: ir::RsType) -> Result<RsTypeKind
                 mutability: Mutability::Const,
                 lifetime: get_lifetime()?,
             },
+            name if name.starts_with("#array ") => {
+                let size = name["#array ".len()..]
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid array size in {:?}", ty))?;
+                RsTypeKind::Array { element_type: get_pointee()?, size }
+            }
             name => {
-                let mut type_args = get_type_args()?;
+                let mut type_args = match (name, &ty.type_args[..]) {
+                    // Pointers to member functions whose signature isn't supported by function
+                    // pointers are bound with the `()` signature, and can't be called.
+                    ("::member_pointer::MemberFunctionPointer", [class, signature]) => vec![
+                        db.rs_type_kind(class.clone())?,
+                        db.rs_type_kind(signature.clone()).unwrap_or(RsTypeKind::Unit),
+                    ],
+                    _ => get_type_args()?,
+                };
+                // `UniquePtr<T>` requires `T: UniquePtrTarget` (and `SharedPtr<T>` requires
+                // `T: SharedPtrTarget`), which is implemented for the primitive types, and for the
+                // `unique_ptr_target_records` (or `shared_ptr_target_records`) of each target.
+                if is_cxx_unique_ptr(name) || is_cxx_shared_ptr(name) {
+                    let target_records = if is_cxx_unique_ptr(name) {
+                        db.unique_ptr_target_records()
+                    } else {
+                        db.shared_ptr_target_records()
+                    };
+                    let is_pointer_target = match (ty.type_args.first(), type_args.first()) {
+                        (Some(RsType { name: None, decl_id: Some(id), .. }), _) => {
+                            target_records.contains(id)
+                        }
+                        (_, Some(pointee)) => format_primitive_type(pointee).is_some(),
+                        _ => false,
+                    };
+                    ensure!(
+                        is_pointer_target,
+                        "Smart pointers are only supported for primitive types, and for \
+                         (destructible) structs of the same target"
+                    );
+                }
                 match name.strip_prefix("#funcPtr ") {
                     None => RsTypeKind::Other {
                         name: name.into(),
                         type_args: Rc::from(type_args),
-                        is_same_abi: true,
+                        // Whether SIMD types are passed in vector registers depends on the
+                        // target features that the C++ and Rust code are compiled with.
+                        // Half-precision floats are passed in floating point registers,
+                        // unlike the integers that they are represented as in Rust.
+                        // Complex numbers are passed in floating point registers on some
+                        // platforms, where structs would be passed in memory.
+                        // `long double` is passed in memory or in x87 registers, unlike the
+                        // struct that represents it in Rust.
+                        // Pointers to member functions aren't guaranteed to be passed like
+                        // the struct that represents them in Rust.
+                        // `std::string` is not trivially copyable, so C++ passes it by
+                        // pointer. Neither is `std::optional<T>` for most `T`, and when it
+                        // is, the `MaybeUninit<T>` in `CxxOptional` isn't guaranteed to be
+                        // passed like `T`. `std::unique_ptr`, `std::shared_ptr`,
+                        // `absl::Status` and `absl::StatusOr` have non-trivial destructors,
+                        // so C++ passes them by pointer too.
+                        is_same_abi: !is_core_arch_vector(name)
+                            && !is_half_float(name)
+                            && name != "::long_double::LongDouble"
+                            && name != "::complex::Complex"
+                            && name != "::member_pointer::MemberFunctionPointer"
+                            && name != "::cxx_string::CxxString"
+                            && !is_cxx_optional(name)
+                            && !is_cxx_unique_ptr(name)
+                            && !is_cxx_shared_ptr(name)
+                            && !is_absl_status(name)
+                            && !is_absl_status_or(name),
                     },
                     Some(abi) => {
                         // Assert that function pointers in the IR either have static lifetime or
@@ -3801,11 +7231,55 @@ fn cc_type_name_for_record(record: &Record, ir: &IR) -> Result<TokenStream> {
 }
 
 fn cc_tagless_type_name_for_record(record: &Record, ir: &IR) -> Result<TokenStream> {
+    if record.is_anon_nested_record {
+        // The type of the field `s` in `struct Outer { struct { ... } s; }` is
+        // `decltype(Outer::s)`, but anonymous struct and union members have no name.
+        let (enclosing_record, field) = enclosing_field_of_anon_nested_record(record, ir)?;
+        let Some(field_name) = &field.identifier else {
+            bail!("The type of the anonymous record `{}` can't be spelled in C++", record.cc_name);
+        };
+        let (enclosing_record, _) = closest_named_record(enclosing_record, ir)?;
+        let enclosing_record_name = cc_tagless_type_name_for_record(enclosing_record, ir)?;
+        let field_ident = format_cc_ident(&field_name.identifier);
+        return Ok(quote! { decltype(#enclosing_record_name :: #field_ident) });
+    }
     let ident = format_cc_ident(record.cc_name.as_ref());
     let namespace_qualifier = namespace_qualifier_of_item(record.id, ir)?.format_for_cc()?;
     Ok(quote! { #namespace_qualifier #ident })
 }
 
+/// Returns the record that the anonymous nested record `record` is declared in
+/// (see `Record::is_anon_nested_record`), and its field of type `record`.
+fn enclosing_field_of_anon_nested_record<'a>(
+    record: &Record,
+    ir: &'a IR,
+) -> Result<(&'a Rc<Record>, &'a Field)> {
+    ir.records()
+        .find_map(|enclosing_record| {
+            let field = enclosing_record.fields.iter().find(|field| {
+                field.type_.as_ref().is_ok_and(|type_| type_.cc_type.decl_id == Some(record.id))
+            })?;
+            Some((enclosing_record, field))
+        })
+        .ok_or_else(|| anyhow!("Missing the field of the anonymous record `{}`", record.cc_name))
+}
+
+/// Returns `record`, or if it is an anonymous struct or union member, the
+/// closest enclosing record that isn't, together with the offset of `record` in
+/// it, in bits. The members of an anonymous struct or union are also members of
+/// the enclosing record in C++.
+fn closest_named_record<'a>(record: &'a Record, ir: &'a IR) -> Result<(&'a Record, usize)> {
+    if !record.is_anon_nested_record {
+        return Ok((record, 0));
+    }
+    let (enclosing_record, field) = enclosing_field_of_anon_nested_record(record, ir)?;
+    if field.identifier.is_some() {
+        return Ok((record, 0));
+    }
+    let (named_record, offset) = closest_named_record(enclosing_record, ir)?;
+    Ok((named_record, offset + field.offset))
+}
+
 fn cc_type_name_for_item(item: &ir::Item, ir: &IR) -> Result<TokenStream> {
     match item {
         Item::IncompleteRecord(incomplete_record) => {
@@ -3816,6 +7290,11 @@ fn cc_type_name_for_item(item: &ir::Item, ir: &IR) -> Result<TokenStream> {
             Ok(quote! { #tag_kind #namespace_qualifier #ident })
         }
         Item::Record(record) => cc_type_name_for_record(record, ir),
+        Item::Enum(enum_) => {
+            let ident = format_cc_ident(&enum_.identifier.identifier);
+            let namespace_qualifier = namespace_qualifier_of_item(enum_.id, ir)?.format_for_cc()?;
+            Ok(quote! { #namespace_qualifier #ident })
+        }
         Item::TypeAlias(type_alias) => {
             let ident = format_cc_ident(&type_alias.identifier.identifier);
             if let Some(record_id) = type_alias.enclosing_record_id {
@@ -3837,7 +7316,7 @@ fn cc_type_name_for_item(item: &ir::Item, ir: &IR) -> Result<TokenStream> {
 }
 
 fn cc_tag_kind(record: &ir::Record) -> TokenStream {
-    if record.is_anon_record_with_typedef {
+    if record.is_anon_record_with_typedef || record.is_anon_nested_record {
         quote! {}
     } else {
         record.record_type.into_token_stream()
@@ -3867,10 +7346,11 @@ fn format_cc_type(ty: &ir::CcType, ir: &IR) -> Result<TokenStream> {
     format_cc_type_inner(ty, ir, /* references_ok= */ false)
 }
 fn format_cc_type_inner(ty: &ir::CcType, ir: &IR, references_ok: bool) -> Result<TokenStream> {
-    let const_fragment = if ty.is_const {
-        quote! {const}
-    } else {
-        quote! {}
+    let const_fragment = match (ty.is_const, ty.is_volatile) {
+        (false, false) => quote! {},
+        (true, false) => quote! {const},
+        (false, true) => quote! {volatile},
+        (true, true) => quote! {const volatile},
     };
     if let Some(ref name) = ty.name {
         match name.as_ref() {
@@ -3890,6 +7370,18 @@ fn format_cc_type_inner(ty: &ir::CcType, ir: &IR, references_ok: bool) -> Result
                 };
                 Ok(quote! {#nested_type #ptr #const_fragment})
             }
+            cc_type_name if cc_type_name.starts_with("#array ") => {
+                if ty.type_args.len() != 1 {
+                    bail!("Invalid array type (need exactly 1 type argument): {:?}", ty);
+                }
+                let size = cc_type_name["#array ".len()..]
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid array size in {:?}", ty))?;
+                let size = Literal::usize_unsuffixed(size);
+                let element_type = format_cc_type_inner(&ty.type_args[0], ir, references_ok)?;
+                // See the comment about `type_identity_t` for function types below.
+                Ok(quote! { crubit::type_identity_t< #element_type [#size] > #const_fragment })
+            }
             cc_type_name => match cc_type_name.strip_prefix("#funcValue ") {
                 None => {
                     if !ty.type_args.is_empty() {
@@ -3934,9 +7426,12 @@ fn format_cc_type_inner(ty: &ir::CcType, ir: &IR, references_ok: bool) -> Result
     }
 }
 fn cc_struct_layout_assertion(db: &Database, record: &Record) -> Result<TokenStream> {
-    let record_ident = format_cc_ident(record.cc_name.as_ref());
-    let namespace_qualifier = namespace_qualifier_of_item(record.id, &db.ir())?.format_for_cc()?;
-    let tag_kind = cc_tag_kind(record);
+    let ir = db.ir();
+    let (named_record, offset) = closest_named_record(record, &ir)?;
+    if named_record.id != record.id {
+        return cc_unnamed_member_layout_assertion(&ir, record, named_record, offset);
+    }
+    let cc_name = cc_type_name_for_record(record, &ir)?;
     let field_assertions = record
         .fields
         .iter()
@@ -3954,9 +7449,7 @@ fn cc_struct_layout_assertion(db: &Database, record: &Record) -> Result<TokenStr
             let expected_offset = Literal::usize_unsuffixed(field.offset / 8);
 
             let field_ident = format_cc_ident(&field.identifier.as_ref().unwrap().identifier);
-            let actual_offset = quote! {
-                CRUBIT_OFFSET_OF(#field_ident, #tag_kind #namespace_qualifier #record_ident)
-            };
+            let actual_offset = quote! { CRUBIT_OFFSET_OF(#field_ident, #cc_name) };
 
             quote! { static_assert( #actual_offset == #expected_offset); }
         });
@@ -3969,13 +7462,54 @@ fn cc_struct_layout_assertion(db: &Database, record: &Record) -> Result<TokenStr
     } else {
         quote! {CRUBIT_SIZEOF}
     };
+    // Rust moves `CRUBIT_RUST_MOVABLE` types with `memcpy`, which stands in for
+    // a C++ move followed by destruction of the source.
+    let rust_movable_assertion = if record.is_rust_movable {
+        quote! {
+            static_assert(
+                std::is_move_constructible_v<#cc_name> && std::is_destructible_v<#cc_name>,
+                "CRUBIT_RUST_MOVABLE types must be move-constructible and destructible");
+        }
+    } else {
+        quote! {}
+    };
     Ok(quote! {
-        static_assert(#sizeof(#tag_kind #namespace_qualifier #record_ident) == #size);
-        static_assert(alignof(#tag_kind #namespace_qualifier #record_ident) == #alignment);
+        static_assert(#sizeof(#cc_name) == #size);
+        static_assert(alignof(#cc_name) == #alignment);
         #( #field_assertions )*
+        #rust_movable_assertion
     })
 }
 
+/// Returns the layout assertions of an anonymous struct or union member (e.g.
+/// `struct Outer { union { int i; float f; }; };`), whose type can't be spelled
+/// in C++.
+///
+/// Its fields are also members of the closest enclosing `named_record`, in
+/// which the anonymous member is at `offset` bits, so their offsets are checked
+/// relative to that record instead. The size and alignment of the anonymous
+/// member itself are only checked on the Rust side.
+fn cc_unnamed_member_layout_assertion(
+    ir: &IR,
+    record: &Record,
+    named_record: &Record,
+    offset: usize,
+) -> Result<TokenStream> {
+    let cc_name = cc_type_name_for_record(named_record, ir)?;
+    let field_assertions = record
+        .fields
+        .iter()
+        .filter(|f| f.access == AccessSpecifier::Public && f.identifier.is_some())
+        .filter(|f| !f.is_bitfield)
+        .map(|field| {
+            assert_eq!((offset + field.offset) % 8, 0);
+            let expected_offset = Literal::usize_unsuffixed((offset + field.offset) / 8);
+            let field_ident = format_cc_ident(&field.identifier.as_ref().unwrap().identifier);
+            quote! { static_assert(CRUBIT_OFFSET_OF(#field_ident, #cc_name) == #expected_offset); }
+        });
+    Ok(quote! { #( #field_assertions )* })
+}
+
 // Returns the accessor functions for no_unique_address member variables.
 fn cc_struct_no_unique_address_impl(db: &Database, record: &Record) -> Result<TokenStream> {
     let mut fields = vec![];
@@ -4024,12 +7558,226 @@ fn crate_root_path_tokens(ir: &IR) -> TokenStream {
     }
 }
 
+/// Returns the direct base classes of `record` among its unambiguous public
+/// base classes.
+///
+/// The IR lists direct and indirect base classes alike, so the direct ones are
+/// those that aren't themselves base classes of another listed base class.
+fn direct_bases<'a>(
+    record: &'a Record,
+    ir: &'a IR,
+) -> Result<Vec<(&'a BaseClass, &'a Rc<Record>)>> {
+    let bases = record
+        .unambiguous_public_bases
+        .iter()
+        .map(|base| {
+            let base_record: &Rc<Record> = ir
+                .find_decl(base.base_record_id)
+                .with_context(|| format!("Can't find a base record of {:?}", record))?;
+            Ok((base, base_record))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(bases
+        .iter()
+        .filter(|(base, _)| {
+            !bases.iter().any(|(_, other)| {
+                other
+                    .unambiguous_public_bases
+                    .iter()
+                    .any(|indirect| indirect.base_record_id == base.base_record_id)
+            })
+        })
+        .copied()
+        .collect())
+}
+
+/// Returns the only direct base class of `record` among its unambiguous public
+/// base classes, if there is exactly one and it isn't a virtual base class.
+fn single_direct_nonvirtual_base<'a>(
+    record: &'a Record,
+    ir: &'a IR,
+) -> Result<Option<&'a Rc<Record>>> {
+    match &direct_bases(record, ir)?[..] {
+        [(base, base_record)] if base.offset.is_some() => Ok(Some(*base_record)),
+        _ => Ok(None),
+    }
+}
+
+/// Returns the Rust name of the inherent method that `func` is bound as, if
+/// any.
+fn bound_method_name(db: &dyn BindingsGenerator, func: &Rc<Func>) -> Option<Ident> {
+    match db.generate_func(func.clone()) {
+        Ok(Some((_, function_id))) if function_id.self_type.is_none() => {
+            function_id.function_path.segments.last().map(|segment| segment.ident.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Returns methods that forward to the `const` methods of the direct base
+/// classes of a class with multiple base classes, so that they can be called on
+/// the derived class without an explicit upcast. (A class with a single base
+/// class derefs to it instead, see `cc_struct_upcast_impl`.)
+///
+/// Methods that are declared in the derived class hide the inherited methods
+/// with the same C++ name, like in C++. A method that is inherited from several
+/// base classes is bound once per base class, with the name of the base class
+/// as a suffix (e.g. `Name_Base1` and `Name_Base2`), and forwarders whose names
+/// collide with a method of the derived class aren't generated.
+fn cc_struct_inherited_methods_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let bases = direct_bases(record, ir)?;
+    if bases.len() < 2 {
+        return Ok(GeneratedItem::default());
+    }
+    let mut own_cc_names = HashSet::new();
+    let mut own_rs_names = HashSet::new();
+    for id in &record.child_item_ids {
+        let Ok(func) = ir.find_decl::<Rc<Func>>(*id) else { continue };
+        if let UnqualifiedIdentifier::Identifier(id) = &func.name {
+            own_cc_names.insert(id.identifier.clone());
+        }
+        own_rs_names.extend(bound_method_name(db, func));
+    }
+
+    // The inherited methods, by their Rust name.
+    let mut inherited_methods = BTreeMap::<String, Vec<(&Rc<Record>, Rc<Func>, Ident)>>::new();
+    for (_, base_record) in &bases {
+        for id in &base_record.child_item_ids {
+            let Ok(func) = ir.find_decl::<Rc<Func>>(*id) else { continue };
+            let is_const_method = func
+                .member_func_metadata
+                .as_ref()
+                .and_then(|meta| meta.instance_method_metadata.as_ref())
+                .is_some_and(|meta| meta.is_const);
+            match &func.name {
+                UnqualifiedIdentifier::Identifier(id)
+                    if is_const_method && !own_cc_names.contains(&id.identifier) => {}
+                _ => continue,
+            }
+            // Methods that don't get bindings on the base class aren't forwarded either.
+            let Some(name) = bound_method_name(db, func) else { continue };
+            inherited_methods.entry(name.to_string()).or_default().push((
+                base_record,
+                func.clone(),
+                name,
+            ));
+        }
+    }
+
+    let mut methods = vec![];
+    let mut features = BTreeSet::new();
+    for methods_with_name in inherited_methods.into_values() {
+        let is_ambiguous = methods_with_name.len() > 1;
+        for (base_record, func, base_method_name) in methods_with_name {
+            let (name, doc_comment) = if is_ambiguous {
+                let name = make_rs_ident(&format!(
+                    "{}_{}",
+                    base_method_name.to_string().trim_start_matches("r#"),
+                    base_record.rs_name
+                ));
+                let doc_comment = format!(
+                    " Calls `{}::{base_method_name}`. Bound as `{name}`, because `{base_method_name}` \
+                     is inherited from multiple base classes.",
+                    base_record.rs_name
+                );
+                (name, doc_comment)
+            } else {
+                let doc_comment = format!(" Calls `{}::{base_method_name}`.", base_record.rs_name);
+                (base_method_name.clone(), doc_comment)
+            };
+            if own_rs_names.contains(&name) {
+                continue;
+            }
+
+            let mut param_types = func
+                .params
+                .iter()
+                .map(|p| db.rs_type_kind(p.type_.rs_type.clone()))
+                .collect::<Result<Vec<_>>>()?;
+            let Some((_, impl_kind)) = api_func_shape(db, &func, &mut param_types)? else {
+                continue;
+            };
+            let mut return_type = db.rs_type_kind(func.return_type.rs_type.clone())?;
+            let param_idents =
+                func.params.iter().map(|p| make_rs_ident(&p.identifier.identifier)).collect_vec();
+            let BindingsSignature { lifetimes, params: api_params, return_type_fragment, .. } =
+                function_signature(
+                    db,
+                    &mut features,
+                    &func,
+                    &impl_kind,
+                    &param_idents,
+                    &mut param_types,
+                    &mut return_type,
+                )?;
+            let return_type_fragment = if catches_exceptions(db, &func, &impl_kind, &return_type) {
+                let value_type = if return_type_fragment.is_empty() {
+                    quote! {()}
+                } else {
+                    return_type_fragment
+                };
+                quote! { ::core::result::Result<#value_type, ::cxx_exception::CxxException> }
+            } else {
+                return_type_fragment
+            };
+            let arrow = if return_type_fragment.is_empty() {
+                quote! {}
+            } else {
+                quote! {->}
+            };
+            let generic_params =
+                format_generic_params(&lifetimes, std::iter::empty::<syn::Ident>());
+            let base_name = RsTypeKind::new_record(base_record.clone(), ir)?;
+            let args = &param_idents[1..];
+            let mut body = quote! {
+                oops::Upcast::<&#base_name>::upcast(self).#base_method_name( #( #args ),* )
+            };
+            let unsafe_ = if impl_kind.is_unsafe() {
+                body = quote! { unsafe { #body } };
+                quote! { unsafe }
+            } else {
+                quote! {}
+            };
+            methods.push(quote! {
+                #[doc = #doc_comment]
+                #[inline(always)]
+                pub #unsafe_ fn #name #generic_params( #( #api_params ),* )
+                    #arrow #return_type_fragment {
+                    #body
+                }
+            });
+        }
+    }
+    if methods.is_empty() {
+        return Ok(GeneratedItem::default());
+    }
+    let record_name = RsTypeKind::new_record(record.clone(), ir)?;
+    Ok(GeneratedItem {
+        item: quote! {
+            impl #record_name {
+                #( #methods )*
+            }
+        },
+        features,
+        ..Default::default()
+    })
+}
+
 /// Returns the implementation of base class conversions, for converting a type
 /// to its unambiguous public base classes.
-fn cc_struct_upcast_impl(record: &Rc<Record>, ir: &IR) -> Result<GeneratedItem> {
+fn cc_struct_upcast_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
     let mut impls = Vec::with_capacity(record.unambiguous_public_bases.len());
     let mut thunks = vec![];
     let mut cc_impls = vec![];
+    let mut assertions = vec![];
     for base in &record.unambiguous_public_bases {
         let base_record: &Rc<Record> = ir
             .find_decl(base.base_record_id)
@@ -4040,6 +7788,16 @@ fn cc_struct_upcast_impl(record: &Rc<Record>, ir: &IR) -> Result<GeneratedItem>
         if let Some(offset) = base.offset {
             let offset = Literal::i64_unsuffixed(offset);
             body = quote! {(derived as *const _ as *const u8).offset(#offset) as *const #base_name};
+            // The base class subobject must be suitably aligned, and fit in the derived
+            // class. (Its tail padding may be reused by the derived class, but not beyond
+            // the end of the derived class, since the derived class is at least as aligned.)
+            assertions.push(quote! {
+                const _: () = assert!(#offset % ::core::mem::align_of::<#base_name>() == 0);
+                const _: () = assert!(
+                    #offset + ::core::mem::size_of::<#base_name>()
+                        <= ::core::mem::size_of::<#derived_name>()
+                );
+            });
         } else {
             let cast_fn_name = make_rs_ident(&format!(
                 "__crubit_dynamic_upcast__{}__to__{}",
@@ -4047,8 +7805,11 @@ fn cc_struct_upcast_impl(record: &Rc<Record>, ir: &IR) -> Result<GeneratedItem>
             ));
             let base_cc_name = cc_type_name_for_record(base_record.as_ref(), ir)?;
             let derived_cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+            let thunk_specifiers = format_cc_thunk_specifiers(db);
             cc_impls.push(quote! {
-                extern "C" const #base_cc_name& #cast_fn_name(const #derived_cc_name& from) {
+                extern "C" #thunk_specifiers const #base_cc_name& #cast_fn_name(
+                    const #derived_cc_name& from
+                ) {
                     return from;
                 }
             });
@@ -4069,1206 +7830,6532 @@ fn cc_struct_upcast_impl(record: &Rc<Record>, ir: &IR) -> Result<GeneratedItem>
         });
     }
 
+    // A class with a single direct, non-virtual public base class derefs to it, so
+    // that inherited `&self` methods (and base class fields) can be used on the
+    // derived class without an explicit upcast. `DerefMut` isn't implemented, since
+    // base classes are `!Unpin`.
+    if let Some(base_record) = single_direct_nonvirtual_base(record, ir)? {
+        let base_name = RsTypeKind::new_record(base_record.clone(), ir)?.into_token_stream();
+        let derived_name = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+        impls.push(quote! {
+            impl ::core::ops::Deref for #derived_name {
+                type Target = #base_name;
+                #[inline(always)]
+                fn deref(&self) -> &#base_name {
+                    oops::Upcast::<&#base_name>::upcast(self)
+                }
+            }
+        });
+    }
+
     Ok(GeneratedItem {
         item: quote! {#(#impls)*},
         thunks: quote! {#(#thunks)*},
         thunk_impls: quote! {#(#cc_impls)*},
+        assertions: quote! {#(#assertions)*},
         ..Default::default()
     })
 }
 
-fn thunk_ident(func: &Func) -> Ident {
-    format_ident!("__rust_thunk__{}", func.mangled_name.as_ref())
-}
-
-fn generate_func_thunk_impl(db: &dyn BindingsGenerator, func: &Func) -> Result<TokenStream> {
-    if can_skip_cc_thunk(db, func) {
-        return Ok(quote! {});
+/// Returns the implementation of `PartialOrd`, and `Ord` if possible, for a
+/// record with an `operator<=>`.
+fn cc_struct_three_way_comparison_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let func = match find_three_way_comparison(db, record) {
+        Some(func) if func.owning_target == record.owning_target => func,
+        _ => return Ok(GeneratedItem::default()),
+    };
+    // Otherwise, `api_func_shape` reports why `operator<=>` can't be bound.
+    if get_binding(
+        db,
+        UnqualifiedIdentifier::Operator(Operator { name: Rc::from("==") }),
+        func.params
+            .iter()
+            .map(|param| db.rs_type_kind(param.type_.rs_type.clone()))
+            .collect::<Result<Vec<_>>>()?,
+    )
+    .is_none()
+    {
+        return Ok(GeneratedItem::default());
     }
-    let ir = db.ir();
-    let thunk_ident = thunk_ident(func);
-    let implementation_function = match &func.name {
-        UnqualifiedIdentifier::Operator(op) => {
-            let name = syn::parse_str::<TokenStream>(&op.name)?;
-            quote! { operator #name }
+    let category = match db.rs_type_kind(func.return_type.rs_type.clone())? {
+        RsTypeKind::Record { record, .. } => record.cc_name.clone(),
+        _ => bail!("operator<=> must return a comparison category type"),
+    };
+
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let thunk_ident =
+        format_ident!("__crubit_three_way_comparison__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+
+    // Only `std::strong_ordering` is consistent with `Eq`, as `Ord` requires:
+    // `std::weak_ordering` allows equivalent values that aren't equal.
+    let ord_impl = if category.as_ref() == "strong_ordering" && implements_eq(db, record) {
+        quote! {
+            impl Ord for #qualified_ident {
+                #[inline(always)]
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    self.partial_cmp(other).unwrap()
+                }
+            }
         }
-        UnqualifiedIdentifier::Identifier(id) => {
-            let fn_ident = format_cc_ident(&id.identifier);
-            match func.member_func_metadata.as_ref() {
-                Some(meta) => {
-                    if meta.instance_method_metadata.is_some() {
-                        quote! { #fn_ident }
-                    } else {
-                        let record: &Rc<Record> = ir.find_decl(meta.record_id)?;
-                        let record_ident = format_cc_ident(record.cc_name.as_ref());
-                        let namespace_qualifier =
-                            namespace_qualifier_of_item(record.id, &ir)?.format_for_cc()?;
-                        quote! { #namespace_qualifier #record_ident :: #fn_ident }
-                    }
-                }
-                None => {
-                    let namespace_qualifier =
-                        namespace_qualifier_of_item(func.id, &ir)?.format_for_cc()?;
-                    quote! { #namespace_qualifier #fn_ident }
-                }
-            }
-        }
-        // Use `destroy_at` to avoid needing to spell out the class name. Destructor identiifers
-        // use the name of the type itself, without namespace qualification, template
-        // parameters, or aliases. We do not need to use that naming scheme anywhere else in
-        // the bindings, and it can be difficult (impossible?) to spell in the general case. By
-        // using destroy_at, we avoid needing to determine or remember what the correct spelling
-        // is. Similar arguments apply to `construct_at`.
-        UnqualifiedIdentifier::Constructor => {
-            quote! { crubit::construct_at }
-        }
-        UnqualifiedIdentifier::Destructor => quote! {std::destroy_at},
+    } else {
+        quote! {}
     };
 
-    let mut param_idents =
-        func.params.iter().map(|p| format_cc_ident(&p.identifier.identifier)).collect_vec();
-
-    let mut param_types = func
-        .params
-        .iter()
-        .map(|p| {
-            let formatted = format_cc_type(&p.type_.cc_type, &ir)?;
-            if !db.rs_type_kind(p.type_.rs_type.clone())?.is_c_abi_compatible_by_value() {
-                // non-Unpin types are wrapped by a pointer in the thunk.
-                Ok(quote! {#formatted *})
-            } else {
-                Ok(formatted)
-            }
-        })
-        .collect::<Result<Vec<_>>>()?;
+    let GeneratedItem {
+        item: overrides,
+        thunks: override_thunks,
+        thunk_impls: override_thunk_impls,
+        ..
+    } = relational_operator_overrides(db, record)?;
 
-    let arg_expressions = func
-        .params
-        .iter()
-        .map(|p| {
-            let ident = format_cc_ident(&p.identifier.identifier);
-            match p.type_.cc_type.name.as_deref() {
-                Some("&") => Ok(quote! { * #ident }),
-                Some("&&") => Ok(quote! { std::move(* #ident) }),
-                _ => {
-                    // non-Unpin types are wrapped by a pointer in the thunk.
-                    if !db.rs_type_kind(p.type_.rs_type.clone())?.is_c_abi_compatible_by_value() {
-                        Ok(quote! { std::move(* #ident) })
-                    } else {
-                        Ok(quote! { #ident })
+    Ok(GeneratedItem {
+        item: quote! {
+            impl PartialOrd for #qualified_ident {
+                #[inline(always)]
+                fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                    match unsafe { #crate_root_path::detail::#thunk_ident(self, other) } {
+                        -1 => Some(::core::cmp::Ordering::Less),
+                        0 => Some(::core::cmp::Ordering::Equal),
+                        1 => Some(::core::cmp::Ordering::Greater),
+                        _ => None,
                     }
                 }
+                #overrides
             }
-        })
-        .collect::<Result<Vec<_>>>()?;
-
-    // Here, we add a `__return` parameter if the return type can't be passed by
-    // value across `extern "C"` ABI.  (We do this after the arg_expressions
-    // computation, so that it's only in the parameter list, not the argument
-    // list.)
-    let is_return_value_c_abi_compatible =
-        db.rs_type_kind(func.return_type.rs_type.clone())?.is_c_abi_compatible_by_value();
+            #ord_impl
+        },
+        thunks: quote! {
+            pub(crate) fn #thunk_ident(lhs: *const #qualified_ident, rhs: *const #qualified_ident) -> i8;
+            #override_thunks
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers signed char #thunk_ident(
+                const #cc_name* lhs, const #cc_name* rhs
+            ) {
+                const auto result = *lhs <=> *rhs;
+                return result < 0 ? -1 : result > 0 ? 1 : result == 0 ? 0 : 2;
+            }
+            #override_thunk_impls
+        },
+        ..Default::default()
+    })
+}
 
-    let return_type_name = if !is_return_value_c_abi_compatible {
-        param_idents.insert(0, format_cc_ident("__return"));
-        // In order to be modified, the return type can't be const.
-        let mut cc_return_type = func.return_type.cc_type.clone();
-        cc_return_type.is_const = false;
-        let return_type_name = format_cc_type(&cc_return_type, &ir)?;
-        param_types.insert(0, quote! {#return_type_name *});
-        quote! {void}
-    } else {
-        format_cc_type(&func.return_type.cc_type, &ir)?
-    };
+/// Returns the target type of a conversion function, if it can be bound as
+/// `impl From<&Record> for Target`.
+fn conversion_target(db: &dyn BindingsGenerator, func: &Func) -> Result<RsTypeKind> {
+    let is_const = func
+        .member_func_metadata
+        .as_ref()
+        .and_then(|meta| meta.instance_method_metadata.as_ref())
+        .map_or(false, |meta| meta.is_const);
+    ensure!(is_const, "Non-const conversion functions are not supported");
+    ensure!(
+        !matches!(func.return_type.cc_type.name.as_deref(), Some("&") | Some("&&")),
+        "Conversion functions to reference types are not supported"
+    );
+    let target = db.rs_type_kind(func.return_type.rs_type.clone())?;
+    ensure!(
+        target.lifetimes().next().is_none(),
+        "Conversion functions to types with lifetimes are not supported"
+    );
+    ensure!(target.is_unpin(), "Conversion functions to non-Unpin types are not supported");
+    Ok(target)
+}
 
-    let this_ref_qualification =
-        func.member_func_metadata.as_ref().and_then(|meta| match &func.name {
-            UnqualifiedIdentifier::Constructor | UnqualifiedIdentifier::Destructor => None,
-            UnqualifiedIdentifier::Identifier(_) | UnqualifiedIdentifier::Operator(_) => meta
-                .instance_method_metadata
-                .as_ref()
-                .map(|instance_method| instance_method.reference),
-        });
-    let (implementation_function, arg_expressions) =
-        if let Some(this_ref_qualification) = this_ref_qualification {
-            let this_param = func
-                .params
-                .first()
-                .ok_or_else(|| anyhow!("Instance methods must have `__this` param."))?;
+/// Returns the `From` implementations for the conversion functions of
+/// `record`: `operator T() const` is bound as `impl From<&Record> for T`.
+fn cc_struct_conversion_impls(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
 
-            let this_arg = format_cc_ident(&this_param.identifier.identifier);
-            let this_dot = if this_ref_qualification == ir::ReferenceQualification::RValue {
-                quote! {std::move(*#this_arg).}
-            } else {
-                quote! {#this_arg->}
-            };
-            (
-                quote! { #this_dot #implementation_function},
-                arg_expressions.iter().skip(1).cloned().collect_vec(),
-            )
-        } else {
-            (implementation_function, arg_expressions)
+    let mut items = vec![];
+    let mut thunks = vec![];
+    let mut thunk_impls = vec![];
+    for id in &record.child_item_ids {
+        let func = match ir.find_decl::<Rc<Func>>(*id) {
+            Ok(func) if func.name == UnqualifiedIdentifier::Conversion => func,
+            _ => continue,
         };
-
-    let return_expr = quote! {#implementation_function( #( #arg_expressions ),* )};
-    let return_stmt = if !is_return_value_c_abi_compatible {
-        // Explicitly use placement `new` so that we get guaranteed copy elision in
-        // C++17.
-        let out_param = &param_idents[0];
-        quote! {new(#out_param) auto(#return_expr)}
-    } else {
-        match func.return_type.cc_type.name.as_deref() {
-            Some("void") => return_expr,
-            Some("&") => quote! { return & #return_expr },
-            Some("&&") => {
-                // The code below replicates bits of `format_cc_type`, but formats an rvalue
-                // reference (which `format_cc_type` would format as a pointer).
-                // `const_fragment` from `format_cc_type` is ignored - it is not applicable for
-                // references.
-                let ty = &func.return_type.cc_type;
-                if ty.type_args.len() != 1 {
-                    bail!("Invalid reference type (need exactly 1 type argument): {:?}", ty);
+        // Otherwise, `api_func_shape` reports why the conversion function can't be
+        // bound.
+        let target = match conversion_target(db, func) {
+            Ok(target) => target,
+            Err(_) => continue,
+        };
+        let cc_target = format_cc_type(&func.return_type.cc_type, ir)?;
+        let thunk_ident = format_ident!("__crubit_conversion__{}", func.mangled_name.as_ref());
+        if target.is_c_abi_compatible_by_value() {
+            items.push(quote! {
+                impl<'a> From<&'a #qualified_ident> for #target {
+                    #[inline(always)]
+                    fn from(value: &'a #qualified_ident) -> Self {
+                        unsafe { #crate_root_path::detail::#thunk_ident(value) }
+                    }
                 }
-                let nested_type = format_cc_type(&ty.type_args[0], &ir)?;
-                quote! {
-                    #nested_type && lvalue = #return_expr;
-                    return &lvalue
+            });
+            thunks.push(quote! {
+                pub(crate) fn #thunk_ident(source: *const #qualified_ident) -> #target;
+            });
+            thunk_impls.push(quote! {
+                extern "C" #thunk_specifiers #cc_target #thunk_ident(const #cc_name* source) {
+                    return source->operator #cc_target();
                 }
-            }
-            _ => quote! { return #return_expr },
+            });
+        } else {
+            items.push(quote! {
+                impl<'a> From<&'a #qualified_ident> for #target {
+                    #[inline(always)]
+                    fn from(value: &'a #qualified_ident) -> Self {
+                        let mut __return = ::core::mem::MaybeUninit::<Self>::uninit();
+                        unsafe {
+                            #crate_root_path::detail::#thunk_ident(__return.as_mut_ptr(), value);
+                            __return.assume_init()
+                        }
+                    }
+                }
+            });
+            thunks.push(quote! {
+                pub(crate) fn #thunk_ident(
+                    __return: *mut #target, source: *const #qualified_ident
+                );
+            });
+            thunk_impls.push(quote! {
+                extern "C" #thunk_specifiers void #thunk_ident(
+                    #cc_target* __return, const #cc_name* source
+                ) {
+                    new (__return) auto(source->operator #cc_target());
+                }
+            });
         }
-    };
+    }
 
-    Ok(quote! {
-        extern "C" #return_type_name #thunk_ident( #( #param_types #param_idents ),* ) {
-            #return_stmt;
-        }
+    Ok(GeneratedItem {
+        item: quote! { #( #items )* },
+        thunks: quote! { #( #thunks )* },
+        thunk_impls: quote! { #( #thunk_impls )* },
+        ..Default::default()
     })
 }
 
-fn generate_rs_api_impl_includes(
-    db: &mut Database,
-    crubit_support_path: &str,
-) -> Result<TokenStream> {
-    let ir = db.ir();
+/// Returns the `Display` implementation of a record with a stream insertion
+/// operator (`operator<<(std::ostream&, const T&)`), which formats the record
+/// into a `std::ostringstream` and copies the result into a `String`.
+fn cc_struct_display_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let thunk_ident = format_ident!("__crubit_display__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
 
-    let mut internal_includes = BTreeSet::new();
-    internal_includes.insert(CcInclude::memory()); // ubiquitous.
-    if ir.records().next().is_some() {
-        internal_includes.insert(CcInclude::cstddef());
-        internal_includes.insert(CcInclude::user_header(
-            format!("{crubit_support_path}/internal/sizeof.h").into(),
-        ));
-    };
-    for crubit_header in ["internal/cxx20_backports.h", "internal/offsetof.h"] {
-        internal_includes.insert(CcInclude::user_header(
-            format!("{crubit_support_path}/{crubit_header}").into(),
-        ));
-    }
-    let internal_includes = format_cc_includes(&internal_includes);
+    Ok(GeneratedItem {
+        item: quote! {
+            impl ::core::fmt::Display for #qualified_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    // Writes the output of the C++ stream to the formatter, replacing
+                    // invalid UTF-8 with U+FFFD.
+                    unsafe extern "C" fn append(
+                        output: *mut ::core::ffi::c_void,
+                        data: *const ::core::ffi::c_char,
+                        size: usize,
+                    ) {
+                        let (f, result) = &mut *(output
+                            as *mut (&mut ::core::fmt::Formatter<'_>, ::core::fmt::Result));
+                        let data = ::core::slice::from_raw_parts(data as *const u8, size);
+                        for chunk in data.utf8_chunks() {
+                            *result = result.and_then(|()| f.write_str(chunk.valid()));
+                            if !chunk.invalid().is_empty() {
+                                *result = result.and_then(|()| f.write_str("\u{FFFD}"));
+                            }
+                        }
+                    }
+                    let mut output = (f, Ok(()));
+                    unsafe {
+                        #crate_root_path::detail::#thunk_ident(
+                            self,
+                            &mut output as *mut _ as *mut ::core::ffi::c_void,
+                            append,
+                        );
+                    }
+                    output.1
+                }
+            }
+        },
+        thunks: quote! {
+            pub(crate) fn #thunk_ident(
+                value: *const #qualified_ident,
+                output: *mut ::core::ffi::c_void,
+                append: unsafe extern "C" fn(*mut ::core::ffi::c_void, *const ::core::ffi::c_char, usize),
+            );
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers void #thunk_ident(
+                const #cc_name* value, void* output, void (*append)(void*, const char*, size_t)
+            ) {
+                std::ostringstream stream;
+                stream << *value;
+                const std::string str = stream.str();
+                append(output, str.data(), str.size());
+            }
+        },
+        ..Default::default()
+    })
+}
 
-    // In order to generate C++ thunk in all the cases Clang needs to be able to
-    // access declarations from public headers of the C++ library.  We don't
-    // process these includes via `format_cc_includes` to preserve their
-    // original order (some libraries require certain headers to be included
-    // first - e.g. `config.h`).
-    let ir_includes =
-        ir.public_headers().map(|hdr| CcInclude::user_header(hdr.name.clone())).collect_vec();
+/// Returns the records of the current target that are the element type of a
+/// `std::vector` in the API of the current target (i.e. in the type of a
+/// function parameter, return value or struct field), and can be moved into
+/// the vector.
+///
+/// `::cxx_vector::VectorElement` is implemented for these records, so that
+/// elements can be pushed onto the vectors from Rust.
+fn vector_element_records(db: &dyn BindingsGenerator) -> Rc<HashSet<ItemId>> {
+    let ir = db.ir();
+    let mut element_ids = support_type_arg_records(&ir, is_cxx_vector);
+    element_ids.retain(|id| match ir.find_decl::<Rc<Record>>(*id) {
+        Ok(record) => {
+            ir.is_current_target(&record.owning_target)
+                && record.is_unpin()
+                && record.move_constructor != SpecialMemberFunc::Unavailable
+                && check_by_value(record).is_ok()
+        }
+        Err(_) => false,
+    });
+    Rc::new(element_ids)
+}
 
-    Ok(quote! {
-        #internal_includes
-        __NEWLINE__
-        __COMMENT__ "Public headers of the C++ library being wrapped."
-        #( #ir_includes )* __NEWLINE__
-    })
+/// Returns the records of the current target that are the `std::unique_ptr`
+/// pointee type in the parameter, return or field types of the current target.
+///
+/// `::cxx_unique_ptr::UniquePtrTarget` is implemented for these records, so that
+/// the `UniquePtr`s can delete them.
+fn unique_ptr_target_records(db: &dyn BindingsGenerator) -> Rc<HashSet<ItemId>> {
+    let ir = db.ir();
+    let mut pointee_ids = support_type_arg_records(&ir, is_cxx_unique_ptr);
+    pointee_ids.retain(|id| match ir.find_decl::<Rc<Record>>(*id) {
+        Ok(record) => {
+            ir.is_current_target(&record.owning_target)
+                && record.destructor != SpecialMemberFunc::Unavailable
+        }
+        Err(_) => false,
+    });
+    Rc::new(pointee_ids)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ir_testing::{make_ir_from_items, retrieve_func, with_lifetime_macros};
-    use static_assertions::{assert_impl_all, assert_not_impl_any};
-    use token_stream_matchers::{
-        assert_cc_matches, assert_cc_not_matches, assert_rs_matches, assert_rs_not_matches,
-    };
-    use token_stream_printer::rs_tokens_to_formatted_string_for_tests;
+/// Returns the records of the current target that are the `std::shared_ptr`
+/// pointee type in the parameter, return or field types of the current target.
+///
+/// `::cxx_shared_ptr::SharedPtrTarget` is implemented for these records, so that
+/// the `SharedPtr`s can be cloned and dropped.
+fn shared_ptr_target_records(db: &dyn BindingsGenerator) -> Rc<HashSet<ItemId>> {
+    let ir = db.ir();
+    let mut pointee_ids = support_type_arg_records(&ir, is_cxx_shared_ptr);
+    pointee_ids.retain(|id| match ir.find_decl::<Rc<Record>>(*id) {
+        Ok(record) => ir.is_current_target(&record.owning_target),
+        Err(_) => false,
+    });
+    Rc::new(pointee_ids)
+}
 
-    fn ir_from_cc(header: &str) -> Result<IR> {
-        ir_testing::ir_from_cc(multiplatform_testing::test_platform(), header)
+/// Returns the pointer-to-member-function types in the parameter, return or
+/// field types of the current target that can be called from Rust, grouped by
+/// the record of the current target that they point into.
+///
+/// `::member_pointer::MemberFunctionSignature` is implemented for the
+/// signatures of these types. Types with the same signature in Rust (e.g. ones
+/// that only differ in `noexcept`) are only listed once.
+fn member_function_pointer_types(
+    db: &dyn BindingsGenerator,
+) -> Rc<HashMap<ItemId, Vec<MappedType>>> {
+    let ir = db.ir();
+    let mut types = vec![];
+    for func in ir.functions().filter(|func| ir.is_current_target(&func.owning_target)) {
+        types.extend(func.params.iter().map(|param| &param.type_));
+        types.push(&func.return_type);
     }
-    fn ir_from_cc_dependency(header: &str, dep_header: &str) -> Result<IR> {
-        ir_testing::ir_from_cc_dependency(
-            multiplatform_testing::test_platform(),
-            header,
-            dep_header,
-        )
-    }
-    fn ir_record(name: &str) -> Record {
-        ir_testing::ir_record(multiplatform_testing::test_platform(), name)
+    for record in ir.records().filter(|record| ir.is_current_target(&record.owning_target)) {
+        types.extend(record.fields.iter().filter_map(|field| field.type_.as_ref().ok()));
     }
 
-    fn generate_bindings_tokens(ir: IR) -> Result<BindingsTokens> {
-        super::generate_bindings_tokens(
-            Rc::new(ir),
-            "crubit/rs_bindings_support",
-            Rc::new(IgnoreErrors),
-            SourceLocationDocComment::Enabled,
-        )
+    let mut types_by_record: HashMap<ItemId, Vec<MappedType>> = HashMap::new();
+    for type_ in types {
+        if type_.rs_type.name.as_deref() != Some("::member_pointer::MemberFunctionPointer") {
+            continue;
+        }
+        let Some(RsType { name: None, decl_id: Some(id), .. }) = type_.rs_type.type_args.first()
+        else {
+            continue;
+        };
+        let is_current_target_record = match ir.find_decl::<Rc<Record>>(*id) {
+            Ok(record) => ir.is_current_target(&record.owning_target),
+            Err(_) => false,
+        };
+        let is_callable = match db.rs_type_kind(type_.rs_type.clone()) {
+            Ok(RsTypeKind::Other { type_args, .. }) => {
+                matches!(type_args.get(1), Some(RsTypeKind::FuncPtr { .. }))
+            }
+            _ => false,
+        };
+        if !is_current_target_record || !is_callable {
+            continue;
+        }
+        let record_types = types_by_record.entry(*id).or_default();
+        if !record_types.iter().any(|t| t.rs_type == type_.rs_type) {
+            record_types.push(type_.clone());
+        }
     }
+    Rc::new(types_by_record)
+}
 
-    fn db_from_cc(cc_src: &str) -> Result<Database> {
-        let mut db = Database::default();
-        db.set_ir(Rc::new(ir_from_cc(cc_src)?));
-        Ok(db)
+/// Returns the records that are the first type argument of a support type
+/// (e.g. the element type of a `CxxVector`) in the parameter, return or field
+/// types of the current target.
+fn support_type_arg_records(ir: &IR, is_support_type: fn(&str) -> bool) -> HashSet<ItemId> {
+    fn collect(rs_type: &RsType, is_support_type: fn(&str) -> bool, ids: &mut HashSet<ItemId>) {
+        if rs_type.name.as_deref().map_or(false, is_support_type) {
+            if let Some(RsType { name: None, decl_id: Some(id), .. }) = rs_type.type_args.first() {
+                ids.insert(*id);
+            }
+        }
+        for type_arg in rs_type.type_args.iter() {
+            collect(type_arg, is_support_type, ids);
+        }
     }
 
-    #[test]
-    fn test_disable_thread_safety_warnings() -> Result<()> {
-        let ir = ir_from_cc("inline void foo() {}")?;
-        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                ...
-                __HASH_TOKEN__ pragma clang diagnostic push
-                __HASH_TOKEN__ pragma clang diagnostic ignored "-Wthread-safety-analysis"
-                ...
-
-                __HASH_TOKEN__ pragma clang diagnostic pop
-                ...
+    let mut ids = HashSet::new();
+    for func in ir.functions().filter(|func| ir.is_current_target(&func.owning_target)) {
+        for param in &func.params {
+            collect(&param.type_.rs_type, is_support_type, &mut ids);
+        }
+        collect(&func.return_type.rs_type, is_support_type, &mut ids);
+    }
+    for record in ir.records().filter(|record| ir.is_current_target(&record.owning_target)) {
+        for field in &record.fields {
+            if let Ok(type_) = &field.type_ {
+                collect(&type_.rs_type, is_support_type, &mut ids);
             }
-        );
-        Ok(())
+        }
     }
+    ids
+}
 
-    #[test]
-    fn test_simple_function() -> Result<()> {
-        let ir = ir_from_cc("int Add(int a, int b);")?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[inline(always)]
-                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
-                    unsafe { crate::detail::__rust_thunk___Z3Addii(a, b) }
+/// Implements `::cxx_unique_ptr::UniquePtrTarget` for `record`, using a thunk
+/// that calls `delete`.
+fn cc_struct_unique_ptr_target_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let thunk_ident =
+        format_ident!("__crubit_unique_ptr_delete__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+
+    Ok(GeneratedItem {
+        item: quote! {
+            unsafe impl ::cxx_unique_ptr::UniquePtrTarget for #qualified_ident {
+                unsafe fn delete(ptr: *mut Self) {
+                    #crate_root_path::detail::#thunk_ident(ptr)
                 }
             }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                mod detail {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    extern "C" {
-                        #[link_name = "_Z3Addii"]
-                        pub(crate) fn __rust_thunk___Z3Addii(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int;
-                    }
-                }
+        },
+        thunks: quote! {
+            pub(crate) fn #thunk_ident(ptr: *mut #qualified_ident);
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers void #thunk_ident(#cc_name* ptr) {
+                delete ptr;
             }
-        );
-
-        assert_cc_not_matches!(rs_api_impl, quote! {__rust_thunk___Z3Addii});
+        },
+        ..Default::default()
+    })
+}
 
-        Ok(())
-    }
+/// Implements `::cxx_shared_ptr::SharedPtrTarget` for `record`, using thunks
+/// that call the copy constructor and the destructor of `std::shared_ptr`.
+fn cc_struct_shared_ptr_target_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let copy_thunk_ident =
+        format_ident!("__crubit_shared_ptr_copy__{}", record.mangled_cc_name.as_ref());
+    let destroy_thunk_ident =
+        format_ident!("__crubit_shared_ptr_destroy__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
 
-    #[test]
-    fn test_inline_function() -> Result<()> {
-        let ir = ir_from_cc("inline int Add(int a, int b);")?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[inline(always)]
-                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
-                    unsafe { crate::detail::__rust_thunk___Z3Addii(a, b) }
+    Ok(GeneratedItem {
+        item: quote! {
+            unsafe impl ::cxx_shared_ptr::SharedPtrTarget for #qualified_ident {
+                unsafe fn copy_shared_ptr(
+                    dest: *mut ::cxx_shared_ptr::SharedPtr<Self>,
+                    src: &::cxx_shared_ptr::SharedPtr<Self>,
+                ) {
+                    #crate_root_path::detail::#copy_thunk_ident(dest, src)
+                }
+                unsafe fn destroy_shared_ptr(ptr: *mut ::cxx_shared_ptr::SharedPtr<Self>) {
+                    #crate_root_path::detail::#destroy_thunk_ident(ptr)
                 }
             }
+        },
+        thunks: quote! {
+            pub(crate) fn #copy_thunk_ident(
+                dest: *mut ::cxx_shared_ptr::SharedPtr<#qualified_ident>,
+                src: *const ::cxx_shared_ptr::SharedPtr<#qualified_ident>,
+            );
+            pub(crate) fn #destroy_thunk_ident(
+                ptr: *mut ::cxx_shared_ptr::SharedPtr<#qualified_ident>,
+            );
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers void #copy_thunk_ident(
+                std::shared_ptr<#cc_name>* dest, const std::shared_ptr<#cc_name>* src
+            ) {
+                crubit::construct_at(dest, *src);
+            }
+            extern "C" #thunk_specifiers void #destroy_thunk_ident(
+                std::shared_ptr<#cc_name>* ptr
+            ) {
+                std::destroy_at(ptr);
+            }
+        },
+        ..Default::default()
+    })
+}
+
+/// Implements `::member_pointer::MemberFunctionSignature` for the signatures
+/// of the pointer-to-member-function `types` of `record`, using thunks that
+/// call the member functions.
+fn cc_struct_member_function_signature_impls(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    types: &[MappedType],
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+    let mut generated_item = GeneratedItem::default();
+    for (index, type_) in types.iter().enumerate() {
+        let RsTypeKind::Other { type_args, .. } = db.rs_type_kind(type_.rs_type.clone())? else {
+            bail!("Not a pointer to a member function: {:?}", type_);
+        };
+        let [class, signature] = &type_args[..] else {
+            bail!("Invalid pointer to a member function: {:?}", type_);
+        };
+        let RsTypeKind::FuncPtr { return_type, param_types, .. } = signature else {
+            bail!("Pointers to member functions with the `()` signature can't be called");
+        };
+        let thunk_ident = format_ident!(
+            "__crubit_call_member_function__{}_{}",
+            record.mangled_cc_name.as_ref(),
+            index
         );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                mod detail {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    extern "C" {
-                        pub(crate) fn __rust_thunk___Z3Addii(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int;
+        let param_idents =
+            (0..param_types.len()).map(|i| format_ident!("__param_{}", i)).collect_vec();
+        let param_indices = (0..param_types.len()).map(Literal::usize_unsuffixed).collect_vec();
+        let param_types = param_types.iter().collect_vec();
+        let return_frag = return_type.format_as_return_type_fragment(None);
+        let cc_type = format_cc_type(&type_.cc_type, ir)?;
+        generated_item.append(&GeneratedItem {
+            item: quote! {
+                unsafe impl ::member_pointer::MemberFunctionSignature<#class> for #signature {
+                    type Args = ( #( #param_types, )* );
+                    type Output = #return_type;
+                    unsafe fn call(
+                        f: &::member_pointer::MemberFunctionPointer<#class, Self>,
+                        ( #( #param_idents, )* ): Self::Args,
+                    ) -> Self::Output {
+                        #crate_root_path::detail::#thunk_ident(f, #( #param_idents ),*)
                     }
                 }
-            }
-        );
-
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" int __rust_thunk___Z3Addii(int a, int b) {
-                    return Add(a, b);
+            },
+            thunks: quote! {
+                pub(crate) fn #thunk_ident(
+                    f: &::member_pointer::MemberFunctionPointer<#class, #signature>,
+                    #( #param_idents: #param_types ),*
+                ) #return_frag;
+            },
+            thunk_impls: quote! {
+                extern "C" #thunk_specifiers crubit::MemberFunctionResult<#cc_type> #thunk_ident(
+                    const #cc_type* f,
+                    #( crubit::MemberFunctionParam<#cc_type, #param_indices> #param_idents ),*
+                ) {
+                    return crubit::CallMemberFunction(*f, #( #param_idents ),*);
                 }
-            }
-        );
-        Ok(())
+            },
+            ..Default::default()
+        });
     }
+    Ok(generated_item)
+}
 
-    #[test]
-    fn test_simple_function_with_types_from_other_target() -> Result<()> {
-        let ir = ir_from_cc_dependency(
-            "inline ReturnStruct DoSomething(ParamStruct param);",
-            "struct ReturnStruct final {}; struct ParamStruct final {};",
-        )?;
+/// Implements `::cxx_vector::VectorElement` for `record`, using a thunk that
+/// calls `std::vector::push_back`.
+fn cc_struct_vector_element_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_name = cc_type_name_for_record(record.as_ref(), ir)?;
+    let thunk_ident =
+        format_ident!("__crubit_vector_push_back__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
 
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[inline(always)]
-                pub fn DoSomething(mut param: dependency::ParamStruct)
-                    -> dependency::ReturnStruct {
-                     unsafe {
-                         let mut __return =
-                             ::core::mem::MaybeUninit::<dependency::ReturnStruct>::uninit();
-                         crate::detail::__rust_thunk___Z11DoSomething11ParamStruct(
-                             &mut __return, &mut param);
-                         __return.assume_init()
-                     }
+    Ok(GeneratedItem {
+        item: quote! {
+            unsafe impl ::cxx_vector::VectorElement for #qualified_ident {
+                unsafe fn push_back(v: &mut ::cxx_vector::CxxVector<Self>, value: &mut Self) {
+                    #crate_root_path::detail::#thunk_ident(v, value)
                 }
             }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-            mod detail {
-                #[allow(unused_imports)]
-                use super::*;
-                extern "C" {
-                    pub(crate) fn __rust_thunk___Z11DoSomething11ParamStruct(
-                        __return: &mut ::core::mem::MaybeUninit<dependency::ReturnStruct>,
-                        param: &mut dependency::ParamStruct
-                    );
-                }
-            }}
-        );
+        },
+        thunks: quote! {
+            pub(crate) fn #thunk_ident(
+                v: *mut ::cxx_vector::CxxVector<#qualified_ident>,
+                value: *mut #qualified_ident,
+            );
+        },
+        thunk_impls: quote! {
+            extern "C" #thunk_specifiers void #thunk_ident(
+                std::vector<#cc_name>* v, #cc_name* value
+            ) {
+                v->push_back(std::move(*value));
+            }
+        },
+        ..Default::default()
+    })
+}
 
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___Z11DoSomething11ParamStruct(
-                        struct ReturnStruct* __return, struct ParamStruct* param) {
-                    new (__return) auto(DoSomething(std::move(*param)));
-                }
+/// Returns whether `record` is an interface: an abstract class without base
+/// classes or data members, whose member functions (other than constructors
+/// and destructors) are all pure virtual.
+fn is_interface(record: &Record, ir: &IR) -> bool {
+    record.is_abstract
+        && !record.is_derived_class
+        && record.fields.is_empty()
+        && record.child_item_ids.iter().all(|id| match ir.find_decl::<Rc<Func>>(*id) {
+            Ok(func) => match &func.name {
+                UnqualifiedIdentifier::Constructor | UnqualifiedIdentifier::Destructor => true,
+                _ => func
+                    .member_func_metadata
+                    .as_ref()
+                    .and_then(|meta| meta.instance_method_metadata.as_ref())
+                    .map_or(false, |meta| meta.is_pure_virtual),
+            },
+            // Unsupported member functions might be pure virtual.
+            Err(_) => ir.find_decl::<Rc<UnsupportedItem>>(*id).is_err(),
+        })
+}
+
+/// Returns the implementation of Rust subclasses of a `CRUBIT_EXTENSIBLE`
+/// record: a trait with the virtual member functions of the record, and a C++
+/// derived class that forwards its virtual member functions to an
+/// implementation of that trait.
+fn cc_struct_extension_impl(
+    db: &dyn BindingsGenerator,
+    record: &Rc<Record>,
+    ir: &IR,
+) -> Result<GeneratedItem> {
+    // Returns the Rust and C++ spelling of a type passed between the C++ derived
+    // class and the Rust implementation, which must be a C ABI compatible type
+    // without lifetimes.
+    let format_override_type = |ty: &MappedType, method: &Identifier| -> Result<_> {
+        let rs_type_kind = db.rs_type_kind(ty.rs_type.clone())?;
+        if !rs_type_kind.is_c_abi_compatible_by_value()
+            || rs_type_kind.lifetimes().next().is_some()
+            || matches!(ty.cc_type.name.as_deref(), Some("&" | "&&"))
+        {
+            bail!("Type `{}` in `{}` is not supported in Rust overrides", rs_type_kind, method);
+        }
+        Ok((rs_type_kind, format_cc_type(&ty.cc_type, ir)?))
+    };
+
+    if record.has_non_public_pure_virtual {
+        bail!("Extensible records must not have non-public pure virtual member functions");
+    }
+
+    let trait_name = format_ident!("{}Overrides", record.rs_name.as_ref());
+    let subclass_name = format_ident!("{}RustSubclass", record.rs_name.as_ref());
+    let qualified_ident = RsTypeKind::new_record(record.clone(), ir)?.into_token_stream();
+    let cc_base_name = cc_tagless_type_name_for_record(record.as_ref(), ir)?;
+    let cc_subclass_name =
+        format_cc_ident(&format!("__crubit_rust_subclass__{}", record.mangled_cc_name.as_ref()));
+    let new_fn_name =
+        format_ident!("__crubit_new_rust_subclass__{}", record.mangled_cc_name.as_ref());
+    let delete_fn_name =
+        format_ident!("__crubit_delete_rust_subclass__{}", record.mangled_cc_name.as_ref());
+    let crate_root_path = crate_root_path_tokens(ir);
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+
+    // The member functions of the record, followed by the pure virtual member
+    // functions of its base classes, which the C++ derived class must override
+    // too. The flag is true for the latter.
+    let mut funcs = vec![];
+    for id in &record.child_item_ids {
+        if let Ok(func) = ir.find_decl::<Rc<Func>>(*id) {
+            funcs.push((func.clone(), false));
+        }
+    }
+    for base in &record.unambiguous_public_bases {
+        let base_record = match ir.find_decl::<Rc<Record>>(base.base_record_id) {
+            Ok(base_record) => base_record,
+            Err(_) if record.is_abstract => {
+                bail!("Extensible records must not derive from abstract classes without bindings")
             }
-        );
-        Ok(())
+            Err(_) => continue,
+        };
+        for id in &base_record.child_item_ids {
+            if let Ok(func) = ir.find_decl::<Rc<Func>>(*id) {
+                funcs.push((func.clone(), true));
+            }
+        }
     }
 
-    #[test]
-    fn test_template_in_dependency_and_alias_in_current_target() -> Result<()> {
-        // See also the test with the same name in `ir_from_cc_test.rs`.
-        let ir = {
-            let dependency_src = r#" #pragma clang lifetime_elision
-                    template <typename T>
-                    struct MyTemplate {
-                        T GetValue() { return field; }
-                        T field;
-                    }; "#;
-            let current_target_src = r#" #pragma clang lifetime_elision
-                    using MyAliasOfTemplate = MyTemplate<int>; "#;
-            ir_from_cc_dependency(current_target_src, dependency_src)?
+    let mut has_default_constructor = false;
+    let mut has_virtual_destructor = false;
+    let mut has_const_methods = false;
+    let mut method_names = HashSet::new();
+    let mut trait_methods = vec![];
+    let mut trampolines = vec![];
+    let mut rs_fn_ptr_params = vec![];
+    let mut rs_fn_ptr_args = vec![];
+    let mut cc_fn_ptr_params = vec![];
+    let mut cc_fn_ptr_args = vec![];
+    let mut cc_fn_ptr_fields = vec![];
+    let mut cc_fn_ptr_inits = vec![];
+    let mut cc_overrides = vec![];
+    let mut dyn_methods = vec![];
+    let mut dyn_thunks = vec![];
+    let mut dyn_thunk_impls = vec![];
+    for (func, is_inherited) in funcs {
+        let meta = match func
+            .member_func_metadata
+            .as_ref()
+            .and_then(|meta| meta.instance_method_metadata.as_ref())
+        {
+            Some(meta) => meta,
+            None => continue,
         };
-
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[repr(C)]
-                pub struct __CcTemplateInst10MyTemplateIiE {
-                    pub field: ::core::ffi::c_int,
-                }
+        let method = match &func.name {
+            UnqualifiedIdentifier::Constructor | UnqualifiedIdentifier::Destructor
+                if is_inherited =>
+            {
+                continue
             }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                impl __CcTemplateInst10MyTemplateIiE {
-                    #[doc = " Generated from: google3/test/dependency_header.h;l=4"]
-                    #[inline(always)]
-                    pub fn GetValue<'a>(self: ... Pin<&'a mut Self>) -> ::core::ffi::c_int { unsafe {
-                        crate::detail::__rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(
-                            self)
-                    }}
-                }
+            UnqualifiedIdentifier::Constructor => {
+                has_default_constructor |= func.params.len() == 1;
+                continue;
             }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                pub type MyAliasOfTemplate = crate::__CcTemplateInst10MyTemplateIiE;
+            UnqualifiedIdentifier::Destructor => {
+                has_virtual_destructor = meta.is_virtual;
+                continue;
             }
-        );
+            _ if !meta.is_virtual => continue,
+            // Other inherited virtual member functions keep their base class
+            // implementation.
+            _ if is_inherited && !meta.is_pure_virtual => continue,
+            UnqualifiedIdentifier::Identifier(method) => method,
+            _ => bail!("Overriding virtual operators is not supported: {:?}", func.name),
+        };
+        if !method_names.insert(method.identifier.clone()) {
+            if is_inherited {
+                // Overridden by the record, or by another base class.
+                continue;
+            }
+            bail!("Overriding overloaded virtual member functions is not supported: {}", method);
+        }
+
+        let mut rs_params = vec![];
+        let mut rs_param_types = vec![];
+        let mut cc_params = vec![];
+        let mut cc_param_types = vec![];
+        // The first parameter is `__this`.
+        for param in func.params.iter().skip(1) {
+            let (rs_type_kind, cc_type) = format_override_type(&param.type_, method)?;
+            rs_params.push(make_rs_ident(&param.identifier.identifier));
+            rs_param_types.push(rs_type_kind.into_token_stream());
+            cc_params.push(format_cc_ident(&param.identifier.identifier));
+            cc_param_types.push(cc_type);
+        }
+        let (rs_return_type, cc_return_type) = format_override_type(&func.return_type, method)?;
+        let rs_return_type = match rs_return_type {
+            RsTypeKind::Unit => quote! {},
+            rs_return_type => quote! { -> #rs_return_type },
+        };
+        has_const_methods |= meta.is_const;
+        // The implementation is borrowed for the duration of each call, because
+        // C++ may call back into a member function while another one is running
+        // (or call member functions from several threads), which must not alias
+        // `&mut self`.
+        let (self_param, rs_imp_type, borrow, cc_imp_type, cc_const) = if meta.is_const {
+            (
+                quote! { &self },
+                quote! { *const ::core::ffi::c_void },
+                quote! { borrow },
+                quote! { const void* },
+                quote! { const },
+            )
+        } else {
+            (
+                quote! { &mut self },
+                quote! { *mut ::core::ffi::c_void },
+                quote! { borrow_mut },
+                quote! { void* },
+                quote! {},
+            )
+        };
+        let (rs_this_type, rs_this_arg) = if meta.is_const {
+            (quote! { *const #qualified_ident }, quote! { &**self })
+        } else {
+            (quote! { *mut #qualified_ident }, quote! { self.as_mut().get_unchecked_mut() })
+        };
+
+        let method_ident = make_rs_ident(&method.identifier);
+        let trampoline_ident = format_ident!("__{}", method.identifier.as_ref());
+        trait_methods.push(quote! {
+            fn #method_ident(#self_param #(, #rs_params: #rs_param_types)*) #rs_return_type;
+        });
+        trampolines.push(quote! {
+            unsafe extern "C" fn #trampoline_ident<T: #trait_name>(
+                imp: #rs_imp_type #(, #rs_params: #rs_param_types)*
+            ) #rs_return_type {
+                (*(imp as *const __Imp<T>)).#borrow(|imp| imp.#method_ident(#(#rs_params),*))
+            }
+        });
+        rs_fn_ptr_params.push(quote! {
+            #method_ident: unsafe extern "C" fn(#rs_imp_type #(, #rs_param_types)*) #rs_return_type
+        });
+        rs_fn_ptr_args.push(quote! { #trampoline_ident::<T> });
+
+        let cc_method_ident = format_cc_ident(&method.identifier);
+        let cc_field = format_cc_ident(&format!("{}_", method.identifier));
+        cc_fn_ptr_params.push(quote! {
+            #cc_return_type (*#cc_method_ident)(#cc_imp_type #(, #cc_param_types)*)
+        });
+        cc_fn_ptr_args.push(quote! { #cc_method_ident });
+        cc_fn_ptr_fields.push(quote! {
+            #cc_return_type (*#cc_field)(#cc_imp_type #(, #cc_param_types)*);
+        });
+        cc_fn_ptr_inits.push(quote! { #cc_field(#cc_method_ident) });
+        cc_overrides.push(quote! {
+            #cc_return_type #cc_method_ident(#(#cc_param_types #cc_params),*) #cc_const override {
+                return #cc_field(imp_ #(, #cc_params)*);
+            }
+        });
+
+        // The existing thunk of the member function can't be reused, because its
+        // signature depends on lifetime elision. The thunk of an inherited member
+        // function is specific to the record, since the base class may also get
+        // one.
+        let dyn_thunk_ident = if is_inherited {
+            format_ident!(
+                "__crubit_dynamic_call__{}__{}",
+                record.mangled_cc_name.as_ref(),
+                func.mangled_name.as_ref()
+            )
+        } else {
+            format_ident!("__crubit_dynamic_call__{}", func.mangled_name.as_ref())
+        };
+        dyn_methods.push(quote! {
+            fn #method_ident(#self_param #(, #rs_params: #rs_param_types)*) #rs_return_type {
+                unsafe {
+                    #crate_root_path::detail::#dyn_thunk_ident(#rs_this_arg #(, #rs_params)*)
+                }
+            }
+        });
+        dyn_thunks.push(quote! {
+            pub(crate) fn #dyn_thunk_ident(
+                __this: #rs_this_type #(, #rs_params: #rs_param_types)*
+            ) #rs_return_type;
+        });
+        dyn_thunk_impls.push(quote! {
+            extern "C" #thunk_specifiers #cc_return_type #dyn_thunk_ident(
+                #cc_const #cc_base_name* __this #(, #cc_param_types #cc_params)*
+            ) {
+                return __this->#cc_method_ident(#(#cc_params),*);
+            }
+        });
+    }
+    if !has_default_constructor {
+        bail!("Extensible records must have a public default constructor");
+    }
+    if !has_virtual_destructor {
+        bail!("Extensible records must have a public virtual destructor");
+    }
+
+    let doc_comment = format!(
+        "Creates a C++ object deriving from `{0}`, whose virtual member functions \
+         call into `imp`.\n\nA call that would alias `&mut self` (e.g. a call of \
+         a non-const member function from within another member function of the \
+         same object, or from another thread) panics, which aborts the program.",
+        record.cc_name
+    );
+    // `const` member functions may run concurrently on several threads.
+    let sync_bound = if has_const_methods {
+        quote! { + Sync }
+    } else {
+        quote! {}
+    };
+    let subclass_doc_comment = format!(
+        "An owned C++ object deriving from `{0}`, created by `{1}::new_rust_subclass`.\n\n\
+         Dropping it destroys the C++ object, which drops the Rust implementation.",
+        record.cc_name, record.rs_name
+    );
+    let item = quote! {
+        pub trait #trait_name {
+            #(#trait_methods)*
+        }
+
+        impl<'a> #trait_name for ::core::pin::Pin<&'a mut #qualified_ident> {
+            #(#dyn_methods)*
+        }
+
+        #[doc = #subclass_doc_comment]
+        pub struct #subclass_name(::core::ptr::NonNull<#qualified_ident>);
+
+        impl #subclass_name {
+            /// Returns a pointer to the C++ object, which remains owned by `self`.
+            pub fn as_ptr(&self) -> *mut #qualified_ident {
+                self.0.as_ptr()
+            }
+
+            /// Returns the C++ object.
+            pub fn as_mut(&mut self) -> ::core::pin::Pin<&mut #qualified_ident> {
+                unsafe { ::core::pin::Pin::new_unchecked(self.0.as_mut()) }
+            }
+
+            /// Releases the ownership of the C++ object, e.g. to hand it over to C++ as
+            /// a `std::unique_ptr`. Destroying it (e.g. with `delete`) drops the Rust
+            /// implementation.
+            pub fn into_raw(self) -> *mut #qualified_ident {
+                let ptr = self.0.as_ptr();
+                ::core::mem::forget(self);
+                ptr
+            }
+        }
+
+        impl Drop for #subclass_name {
+            fn drop(&mut self) {
+                unsafe { #crate_root_path::detail::#delete_fn_name(self.0.as_ptr()) }
+            }
+        }
+
+        impl #qualified_ident {
+            #[doc = #doc_comment]
+            pub fn new_rust_subclass<T: #trait_name + Send #sync_bound + 'static>(
+                imp: T
+            ) -> #subclass_name {
+                struct __Imp<T> {
+                    borrows: ::core::sync::atomic::AtomicIsize,
+                    imp: ::core::cell::UnsafeCell<T>,
+                }
+                impl<T> __Imp<T> {
+                    unsafe fn borrow<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+                        use ::core::sync::atomic::Ordering;
+                        if self.borrows.fetch_add(1, Ordering::Acquire) < 0 {
+                            panic!("already mutably borrowed");
+                        }
+                        let result = f(&*self.imp.get());
+                        self.borrows.fetch_sub(1, Ordering::Release);
+                        result
+                    }
+                    unsafe fn borrow_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+                        use ::core::sync::atomic::Ordering;
+                        if self
+                            .borrows
+                            .compare_exchange(0, -1, Ordering::Acquire, Ordering::Relaxed)
+                            .is_err()
+                        {
+                            panic!("already borrowed");
+                        }
+                        let result = f(&mut *self.imp.get());
+                        self.borrows.store(0, Ordering::Release);
+                        result
+                    }
+                }
+                unsafe extern "C" fn __drop<T>(imp: *mut ::core::ffi::c_void) {
+                    ::core::mem::drop(::alloc::boxed::Box::from_raw(imp as *mut __Imp<T>));
+                }
+                #(#trampolines)*
+                let ptr = unsafe {
+                    #crate_root_path::detail::#new_fn_name(
+                        ::alloc::boxed::Box::into_raw(::alloc::boxed::Box::new(__Imp {
+                            borrows: ::core::sync::atomic::AtomicIsize::new(0),
+                            imp: ::core::cell::UnsafeCell::new(imp),
+                        })) as *mut ::core::ffi::c_void,
+                        __drop::<T>
+                        #(, #rs_fn_ptr_args)*
+                    )
+                };
+                #subclass_name(::core::ptr::NonNull::new(ptr).expect("`new` never returns null"))
+            }
+        }
+    };
+    let thunks = quote! {
+        pub(crate) fn #new_fn_name(
+            imp: *mut ::core::ffi::c_void,
+            drop: unsafe extern "C" fn(*mut ::core::ffi::c_void)
+            #(, #rs_fn_ptr_params)*
+        ) -> *mut #qualified_ident;
+        pub(crate) fn #delete_fn_name(ptr: *mut #qualified_ident);
+        #(#dyn_thunks)*
+    };
+    let thunk_impls = quote! {
+        class #cc_subclass_name final : public #cc_base_name {
+          public:
+            #cc_subclass_name(void* imp, void (*drop)(void*) #(, #cc_fn_ptr_params)*)
+                : imp_(imp), drop_(drop) #(, #cc_fn_ptr_inits)* {}
+            ~#cc_subclass_name() override { drop_(imp_); }
+            #(#cc_overrides)*
+
+          private:
+            void* imp_;
+            void (*drop_)(void*);
+            #(#cc_fn_ptr_fields)*
+        };
+        extern "C" #thunk_specifiers #cc_base_name* #new_fn_name(
+            void* imp, void (*drop)(void*) #(, #cc_fn_ptr_params)*
+        ) {
+            return new #cc_subclass_name(imp, drop #(, #cc_fn_ptr_args)*);
+        }
+        extern "C" #thunk_specifiers void #delete_fn_name(#cc_base_name* ptr) {
+            delete ptr;
+        }
+        #(#dyn_thunk_impls)*
+    };
+    Ok(GeneratedItem {
+        item,
+        thunks,
+        thunk_impls,
+        extern_crates: [make_rs_ident("alloc")].into_iter().collect(),
+        ..Default::default()
+    })
+}
+
+fn thunk_ident(func: &Func) -> Ident {
+    match func.omitted_default_params.first() {
+        None => format_ident!("__rust_thunk__{}", func.mangled_name.as_ref()),
+        Some(param) => format_ident!(
+            "__rust_thunk__{}__without_{}",
+            func.mangled_name.as_ref(),
+            omitted_param_name(param)
+        ),
+    }
+}
+
+/// Returns the copies of `func` without some of its trailing parameters with
+/// default arguments that get bindings (see `Func::with_omitted_default_args`).
+///
+/// A constructor without some of its arguments is bound like the constructor
+/// with the remaining parameters would be (e.g. as `Default` or `From<T>`),
+/// except as a builder, which would collide with the builder of `func`.
+fn funcs_without_default_args(db: &dyn BindingsGenerator, func: &Func) -> Vec<Func> {
+    match &func.name {
+        UnqualifiedIdentifier::Identifier(_) => func.with_omitted_default_args(),
+        UnqualifiedIdentifier::Constructor => func
+            .with_omitted_default_args()
+            .into_iter()
+            .filter(|func| !is_builder_ctor(db, func))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns how the omitted parameter `param` is referred to in the names of
+/// the functions without it (see `Func::with_omitted_default_args`): by its
+/// C++ name, or as `arg_<N>` if it is the unnamed `N`th parameter (counting
+/// from 1, and without `this`).
+fn omitted_param_name(param: &FuncParam) -> String {
+    match param.identifier.identifier.strip_prefix("__param_").map(str::parse::<usize>) {
+        Some(Ok(position)) => format!("arg_{}", position + 1),
+        _ => param.identifier.identifier.to_string(),
+    }
+}
+
+/// Returns whether the parameters with default arguments of `func` are bound
+/// as `Option<T>` parameters (see `--default_arguments`), instead of with
+/// additional `_without_` functions.
+///
+/// Constructors are bound as traits, whose signatures can't have `Option`s.
+/// Only parameters that are passed to the thunk as they are can be wrapped in
+/// an `Option`, so functions with other parameters with default arguments
+/// (e.g. string views, or non-`Unpin` types) are still bound with `_without_`
+/// functions.
+fn binds_default_args_as_options(db: &dyn BindingsGenerator, func: &Func) -> bool {
+    if db.default_arguments() != DefaultArguments::Option
+        || !matches!(func.name, UnqualifiedIdentifier::Identifier(_))
+        || !func.omitted_default_params.is_empty()
+        || func.sets_errno
+    {
+        return false;
+    }
+    let funcs_without_default_args = func.with_omitted_default_args();
+    let Some(func_without_default_args) = funcs_without_default_args.last() else {
+        return false;
+    };
+    func_without_default_args.omitted_default_params.iter().all(|param| {
+        db.rs_type_kind(param.type_.rs_type.clone()).is_ok_and(|type_| {
+            type_.is_unpin()
+                && is_passed_to_thunk_by_value(db, func, &type_)
+                && !is_cxx_string_view(&type_)
+                && absl_span_element_type(&type_).is_none()
+                && cxx_optional_value_type(&type_).is_none()
+        })
+    })
+}
+
+fn generate_func_thunk_impl(
+    db: &dyn BindingsGenerator,
+    func: &Func,
+    catches_exceptions: bool,
+) -> Result<TokenStream> {
+    if can_skip_cc_thunk(db, func) {
+        return Ok(quote! {});
+    }
+    let ir = db.ir();
+    let thunk_ident = thunk_ident(func);
+    let implementation_function = match &func.name {
+        UnqualifiedIdentifier::Operator(op) => {
+            let name = syn::parse_str::<TokenStream>(&op.name)?;
+            quote! { operator #name }
+        }
+        UnqualifiedIdentifier::Identifier(id) => {
+            let mut fn_ident = format_cc_ident(&id.identifier);
+            if let Some(template_args) = &func.template_args {
+                let template_args = syn::parse_str::<TokenStream>(template_args)?;
+                fn_ident = quote! { #fn_ident #template_args };
+            }
+            match func.member_func_metadata.as_ref() {
+                Some(meta) => {
+                    if meta.instance_method_metadata.is_some() {
+                        quote! { #fn_ident }
+                    } else {
+                        let record: &Rc<Record> = ir.find_decl(meta.record_id)?;
+                        let record_ident = format_cc_ident(record.cc_name.as_ref());
+                        let namespace_qualifier =
+                            namespace_qualifier_of_item(record.id, &ir)?.format_for_cc()?;
+                        quote! { #namespace_qualifier #record_ident :: #fn_ident }
+                    }
+                }
+                // Hidden friends can't be found by qualified lookup, only by ADL.
+                None if func.adl_enclosing_record.is_some() => quote! { #fn_ident },
+                None => {
+                    let namespace_qualifier =
+                        namespace_qualifier_of_item(func.id, &ir)?.format_for_cc()?;
+                    quote! { #namespace_qualifier #fn_ident }
+                }
+            }
+        }
+        // Use `destroy_at` to avoid needing to spell out the class name. Destructor identiifers
+        // use the name of the type itself, without namespace qualification, template
+        // parameters, or aliases. We do not need to use that naming scheme anywhere else in
+        // the bindings, and it can be difficult (impossible?) to spell in the general case. By
+        // using destroy_at, we avoid needing to determine or remember what the correct spelling
+        // is. Similar arguments apply to `construct_at`.
+        UnqualifiedIdentifier::Constructor => {
+            quote! { crubit::construct_at }
+        }
+        UnqualifiedIdentifier::Destructor => quote! {std::destroy_at},
+        UnqualifiedIdentifier::Conversion => {
+            let target = format_cc_type(&func.return_type.cc_type, &ir)?;
+            quote! { operator #target }
+        }
+    };
+
+    let mut param_idents =
+        func.params.iter().map(|p| format_cc_ident(&p.identifier.identifier)).collect_vec();
+
+    let mut param_types = func
+        .params
+        .iter()
+        .map(|p| {
+            let formatted = format_cc_type(&p.type_.cc_type, &ir)?;
+            if !db.rs_type_kind(p.type_.rs_type.clone())?.is_c_abi_compatible_by_value() {
+                // non-Unpin types are wrapped by a pointer in the thunk.
+                Ok(quote! {#formatted *})
+            } else {
+                Ok(formatted)
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let arg_expressions = func
+        .params
+        .iter()
+        .map(|p| {
+            let ident = format_cc_ident(&p.identifier.identifier);
+            match p.type_.cc_type.name.as_deref() {
+                Some("&") => Ok(quote! { * #ident }),
+                Some("&&") => Ok(quote! { std::move(* #ident) }),
+                _ => {
+                    // non-Unpin types are wrapped by a pointer in the thunk.
+                    if !db.rs_type_kind(p.type_.rs_type.clone())?.is_c_abi_compatible_by_value() {
+                        Ok(quote! { std::move(* #ident) })
+                    } else {
+                        Ok(quote! { #ident })
+                    }
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Here, we add a `__return` parameter if the return type can't be passed by
+    // value across `extern "C"` ABI.  (We do this after the arg_expressions
+    // computation, so that it's only in the parameter list, not the argument
+    // list.)
+    let is_return_value_c_abi_compatible =
+        db.rs_type_kind(func.return_type.rs_type.clone())?.is_c_abi_compatible_by_value()
+            && (!catches_exceptions || is_value_initializable(&ir, &func.return_type.cc_type));
+
+    let return_type_name = if !is_return_value_c_abi_compatible {
+        param_idents.insert(0, format_cc_ident("__return"));
+        // In order to be modified, the return type can't be const.
+        let mut cc_return_type = func.return_type.cc_type.clone();
+        cc_return_type.is_const = false;
+        let return_type_name = format_cc_type(&cc_return_type, &ir)?;
+        param_types.insert(0, quote! {#return_type_name *});
+        quote! {void}
+    } else {
+        format_cc_type(&func.return_type.cc_type, &ir)?
+    };
+
+    let this_ref_qualification =
+        func.member_func_metadata.as_ref().and_then(|meta| match &func.name {
+            UnqualifiedIdentifier::Constructor | UnqualifiedIdentifier::Destructor => None,
+            UnqualifiedIdentifier::Identifier(_)
+            | UnqualifiedIdentifier::Operator(_)
+            | UnqualifiedIdentifier::Conversion => meta
+                .instance_method_metadata
+                .as_ref()
+                .map(|instance_method| instance_method.reference),
+        });
+    let (implementation_function, arg_expressions) =
+        if let Some(this_ref_qualification) = this_ref_qualification {
+            let this_param = func
+                .params
+                .first()
+                .ok_or_else(|| anyhow!("Instance methods must have `__this` param."))?;
+
+            let this_arg = format_cc_ident(&this_param.identifier.identifier);
+            let this_dot = if this_ref_qualification == ir::ReferenceQualification::RValue {
+                quote! {std::move(*#this_arg).}
+            } else {
+                quote! {#this_arg->}
+            };
+            (
+                quote! { #this_dot #implementation_function},
+                arg_expressions.iter().skip(1).cloned().collect_vec(),
+            )
+        } else {
+            (implementation_function, arg_expressions)
+        };
+
+    // The placement storage that objects are constructed into (`__this` of
+    // constructors, `__return`) may have been allocated by Rust code that isn't
+    // instrumented by MSan, so we unpoison it first. Other memory handed over
+    // from Rust (e.g. `__this` of destructors, or objects passed by value) holds
+    // live objects, and is left alone. See `support/internal/sanitizers.h`.
+    let mut unpoisoned_idents = vec![];
+    if !is_return_value_c_abi_compatible {
+        unpoisoned_idents.push(param_idents[0].clone());
+    }
+    if func.name == UnqualifiedIdentifier::Constructor {
+        if let Some(this) = func.params.first() {
+            unpoisoned_idents.push(format_cc_ident(&this.identifier.identifier));
+        }
+    }
+    let unpoison_stmts = unpoisoned_idents
+        .iter()
+        .map(|ident| quote! { crubit::UnpoisonPlacementStorage(#ident); })
+        .collect_vec();
+
+    let return_expr = quote! {#implementation_function( #( #arg_expressions ),* )};
+    let return_stmt = if !is_return_value_c_abi_compatible {
+        // Explicitly use placement `new` so that we get guaranteed copy elision in
+        // C++17.
+        let out_param = &param_idents[0];
+        quote! {new(#out_param) auto(#return_expr)}
+    } else {
+        match func.return_type.cc_type.name.as_deref() {
+            Some("void") => return_expr,
+            Some("&") => quote! { return & #return_expr },
+            Some("&&") => {
+                // The code below replicates bits of `format_cc_type`, but formats an rvalue
+                // reference (which `format_cc_type` would format as a pointer).
+                // `const_fragment` from `format_cc_type` is ignored - it is not applicable for
+                // references.
+                let ty = &func.return_type.cc_type;
+                if ty.type_args.len() != 1 {
+                    bail!("Invalid reference type (need exactly 1 type argument): {:?}", ty);
+                }
+                let nested_type = format_cc_type(&ty.type_args[0], &ir)?;
+                quote! {
+                    #nested_type && lvalue = #return_expr;
+                    return &lvalue
+                }
+            }
+            _ => quote! { return #return_expr },
+        }
+    };
+
+    let thunk_specifiers = format_cc_thunk_specifiers(db);
+    if catches_exceptions {
+        // The thunk returns a dummy value if the function throws, which the Rust
+        // side ignores. Types that may not be value-initializable are returned
+        // through `__return` instead (see `is_returned_from_thunk_by_value`).
+        let returns_void = !is_return_value_c_abi_compatible
+            || func.return_type.cc_type.name.as_deref() == Some("void");
+        let return_dummy = if returns_void {
+            quote! {}
+        } else {
+            quote! { return {}; }
+        };
+        return Ok(quote! {
+            extern "C" #thunk_specifiers #return_type_name #thunk_ident(
+                std::exception_ptr* __exception #( , #param_types #param_idents )*
+            ) {
+                #( #unpoison_stmts )*
+                try {
+                    #return_stmt;
+                } catch (...) {
+                    *__exception = std::current_exception();
+                }
+                #return_dummy
+            }
+        });
+    }
+    let noexcept = format_cc_thunk_noexcept(db, func);
+    Ok(quote! {
+        extern "C" #thunk_specifiers #return_type_name #thunk_ident(
+            #( #param_types #param_idents ),*
+        ) #noexcept {
+            #( #unpoison_stmts )*
+            #return_stmt;
+        }
+    })
+}
+
+/// Returns `noexcept` if exceptions thrown by `func` should terminate the
+/// program when it is called from a thunk (see `--exception_policy`).
+fn format_cc_thunk_noexcept(db: &dyn BindingsGenerator, func: &Func) -> TokenStream {
+    match db.exception_policy() {
+        ExceptionPolicy::Abort | ExceptionPolicy::Catch if func.may_throw => quote! { noexcept },
+        _ => quote! {},
+    }
+}
+
+/// Returns the specifiers (e.g. `inline`, `__attribute__((visibility(...)))`)
+/// that go between `extern "C"` and the return type of a C++ thunk definition.
+fn format_cc_thunk_specifiers(db: &dyn BindingsGenerator) -> TokenStream {
+    // In header-only mode the thunks may be defined in multiple translation
+    // units, so they need to be `inline`.  `used` makes sure that they are
+    // still emitted even though no C++ code calls them.
+    let inline = if db.generate_header_only_thunks() {
+        quote! { inline __attribute__((used)) }
+    } else {
+        quote! {}
+    };
+    let visibility = match db.thunk_visibility() {
+        ThunkVisibility::Default => quote! {},
+        ThunkVisibility::Hidden => quote! { __attribute__((visibility("hidden"))) },
+        ThunkVisibility::Internal => quote! { __attribute__((visibility("internal"))) },
+    };
+    quote! { #inline #visibility }
+}
+
+fn generate_rs_api_impl_includes(
+    db: &mut Database,
+    crubit_support_path: &str,
+) -> Result<TokenStream> {
+    let ir = db.ir();
+
+    let mut internal_includes = BTreeSet::new();
+    internal_includes.insert(CcInclude::memory()); // ubiquitous.
+    if ir.records().next().is_some() {
+        internal_includes.insert(CcInclude::cstddef());
+        internal_includes.insert(CcInclude::user_header(
+            format!("{crubit_support_path}/internal/sizeof.h").into(),
+        ));
+    };
+    if ir.records().any(|record| record.is_rust_movable)
+        || ir.functions().any(|func| !gsl_span_cc_types(func).is_empty())
+    {
+        internal_includes.insert(CcInclude::type_traits());
+    }
+    if ir.functions().any(|func| func.sets_errno) {
+        internal_includes.insert(CcInclude::cerrno());
+    }
+    if db.exception_policy() == ExceptionPolicy::Catch {
+        internal_includes.insert(CcInclude::exception());
+    }
+    if ir.records().any(|record| record.has_stream_insertion_operator) {
+        internal_includes.insert(CcInclude::sstream());
+    }
+    if !db.member_function_pointer_types().is_empty() {
+        internal_includes.insert(CcInclude::user_header(
+            format!("{crubit_support_path}/internal/member_function_pointer.h").into(),
+        ));
+    }
+    for crubit_header in
+        ["internal/cxx20_backports.h", "internal/offsetof.h", "internal/sanitizers.h"]
+    {
+        internal_includes.insert(CcInclude::user_header(
+            format!("{crubit_support_path}/{crubit_header}").into(),
+        ));
+    }
+    let internal_includes = format_cc_includes(&internal_includes);
+
+    // In order to generate C++ thunk in all the cases Clang needs to be able to
+    // access declarations from public headers of the C++ library.  We don't
+    // process these includes via `format_cc_includes` to preserve their
+    // original order (some libraries require certain headers to be included
+    // first - e.g. `config.h`).
+    let ir_includes =
+        ir.public_headers().map(|hdr| CcInclude::user_header(hdr.name.clone())).collect_vec();
+    // Forced includes come first, like when the headers were imported (see
+    // `--forced_includes`).
+    let forced_includes =
+        ir.forced_includes().map(|hdr| CcInclude::user_header(hdr.name.clone())).collect_vec();
+    let forced_includes = if forced_includes.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            __COMMENT__ "Headers included before the public headers of the C++ library."
+            #( #forced_includes )* __NEWLINE__
+        }
+    };
+
+    Ok(quote! {
+        #internal_includes
+        __NEWLINE__
+        #forced_includes
+        __COMMENT__ "Public headers of the C++ library being wrapped."
+        #( #ir_includes )* __NEWLINE__
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir_testing::{make_ir_from_items, retrieve_func, with_lifetime_macros};
+    use static_assertions::{assert_impl_all, assert_not_impl_any};
+    use token_stream_matchers::{
+        assert_cc_matches, assert_cc_not_matches, assert_rs_matches, assert_rs_not_matches,
+    };
+    use token_stream_printer::rs_tokens_to_formatted_string_for_tests;
+
+    fn ir_from_cc(header: &str) -> Result<IR> {
+        ir_testing::ir_from_cc(multiplatform_testing::test_platform(), header)
+    }
+    fn ir_from_cc_dependency(header: &str, dep_header: &str) -> Result<IR> {
+        ir_testing::ir_from_cc_dependency(
+            multiplatform_testing::test_platform(),
+            header,
+            dep_header,
+        )
+    }
+    fn ir_record(name: &str) -> Record {
+        ir_testing::ir_record(multiplatform_testing::test_platform(), name)
+    }
+
+    fn generate_bindings_tokens(ir: IR) -> Result<BindingsTokens> {
+        super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions::default(),
+        )
+    }
+
+    fn db_from_cc(cc_src: &str) -> Result<Database> {
+        let mut db = Database::default();
+        db.set_ir(Rc::new(ir_from_cc(cc_src)?));
+        Ok(db)
+    }
+
+    #[test]
+    fn test_disable_thread_safety_warnings() -> Result<()> {
+        let ir = ir_from_cc("inline void foo() {}")?;
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                ...
+                __HASH_TOKEN__ pragma clang diagnostic push
+                __HASH_TOKEN__ pragma clang diagnostic ignored "-Wthread-safety-analysis"
+                ...
+
+                __HASH_TOKEN__ pragma clang diagnostic pop
+                ...
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_function() -> Result<()> {
+        let ir = ir_from_cc("int Add(int a, int b);")?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addii(a, b) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                mod detail {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    extern "C" {
+                        #[link_name = "_Z3Addii"]
+                        pub(crate) fn __rust_thunk___Z3Addii(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int;
+                    }
+                }
+            }
+        );
+
+        assert_cc_not_matches!(rs_api_impl, quote! {__rust_thunk___Z3Addii});
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_function() -> Result<()> {
+        let ir = ir_from_cc("inline int Add(int a, int b);")?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addii(a, b) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                mod detail {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    extern "C" {
+                        pub(crate) fn __rust_thunk___Z3Addii(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int;
+                    }
+                }
+            }
+        );
+
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addii(int a, int b) {
+                    return Add(a, b);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_variadic_functions() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            extern "C" int Log(const char* fmt, ...);
+            int Sum(int count, ...);
+            inline void InlineLog(const char* fmt, ...) {}
+            void NoNamedParams(...);
+            struct S final {
+              void Method(int x, ...);
+            };
+            void SetLogger(int (*logger)(const char* fmt, ...));
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                extern "C" {
+                    ...
+                    #[link_name = "Log"]
+                    pub fn Log(fmt: *const ::core::ffi::c_char, ...) -> ::core::ffi::c_int;
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                extern "C" {
+                    ...
+                    #[link_name = "_Z3Sumiz"]
+                    pub fn Sum(count: ::core::ffi::c_int, ...) -> ::core::ffi::c_int;
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=6\n\
+                           Error while generating bindings for item 'InlineLog':\n\
+                           Variadic functions that require a C++ thunk (e.g. inline functions, \
+                           or functions taking or returning structs by value) are not supported";
+            quote! { __COMMENT__ #txt }
+        });
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=7\n\
+                           Error while generating bindings for item 'NoNamedParams':\n\
+                           Variadic functions without named parameters are not supported";
+            quote! { __COMMENT__ #txt }
+        });
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=9\n\
+                           Error while generating bindings for item 'S::Method':\n\
+                           Variadic member functions and operators are not supported";
+            quote! { __COMMENT__ #txt }
+        });
+        // `...` is a wildcard in the patterns above.
+        let rs_api_str = rs_tokens_to_formatted_string_for_tests(rs_api.clone())?;
+        assert!(rs_api_str
+            .contains("pub fn Sum(count: ::core::ffi::c_int, ...) -> ::core::ffi::c_int;"));
+
+        // Pointers to variadic functions are not supported yet.
+        assert!(
+            rs_api_str.contains("Pointers and references to variadic functions are not supported")
+        );
+        assert_rs_not_matches!(rs_api, quote! { pub fn SetLogger });
+
+        // The C++ functions are called directly, so there are no thunks.
+        assert_rs_not_matches!(rs_api, quote! { __rust_thunk___Z3Sumiz });
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z3Sumiz });
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_with_default_arguments() -> Result<()> {
+        let ir = ir_from_cc("int Add(int a, int b = 1, int c = 2);")?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn Add(
+                    a: ::core::ffi::c_int, b: ::core::ffi::c_int, c: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addiii(a, b, c) }
+                }
+                ...
+                #[inline(always)]
+                pub fn Add_without_b(a: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addiii__without_b(a) }
+                }
+                ...
+                #[inline(always)]
+                pub fn Add_without_c(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addiii__without_c(a, b) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[link_name = "_Z3Addiii"]
+                pub(crate) fn __rust_thunk___Z3Addiii(
+                    a: ::core::ffi::c_int, b: ::core::ffi::c_int, c: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int;
+                pub(crate) fn __rust_thunk___Z3Addiii__without_b(
+                    a: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int;
+                pub(crate) fn __rust_thunk___Z3Addiii__without_c(
+                    a: ::core::ffi::c_int, b: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int;
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addiii__without_b(int a) {
+                    return Add(a);
+                }
+                ...
+                extern "C" int __rust_thunk___Z3Addiii__without_c(int a, int b) {
+                    return Add(a, b);
+                }
+            }
+        );
+        assert_cc_not_matches!(rs_api_impl, quote! {__rust_thunk___Z3Addiii});
+        Ok(())
+    }
+
+    #[test]
+    fn test_method_with_default_arguments() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                void Method(int x = 0);
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn Method<'a>(&'a mut self, x: ::core::ffi::c_int) {
+                        unsafe { crate::detail::__rust_thunk___ZN10SomeStruct6MethodEi(self, x) }
+                    }
+                }
+                ...
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn Method_without_x<'a>(&'a mut self) {
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN10SomeStruct6MethodEi__without_x(self)
+                        }
+                    }
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unnamed_params_with_default_arguments() -> Result<()> {
+        let ir = ir_from_cc("int Add(int a, int = 1);")?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Add_without_arg_2(a: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addii__without_arg_2(a) }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addii__without_arg_2(int a) {
+                    return Add(a);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_constructor_with_default_arguments() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                SomeStruct(int i = 0);
+                int i;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl From<::core::ffi::c_int> for SomeStruct {
+                    #[inline(always)]
+                    fn from(i: ::core::ffi::c_int) -> Self {
+                        ...
+                        crate::detail::__rust_thunk___ZN10SomeStructC1Ei(&mut tmp, i);
+                        ...
+                    }
+                }
+                ...
+                impl Default for SomeStruct {
+                    #[inline(always)]
+                    fn default() -> Self {
+                        ...
+                        crate::detail::__rust_thunk___ZN10SomeStructC1Ei__without_i(&mut tmp);
+                        ...
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN10SomeStructC1Ei__without_i(
+                    struct SomeStruct* __this) {
+                    ...
+                    crubit::construct_at(__this);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_arguments_as_options() -> Result<()> {
+        let ir = ir_from_cc("int Add(int a, int b = 1, int c = 2);")?;
+        let BindingsTokens { rs_api, rs_api_impl } = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions { default_arguments: DefaultArguments::Option, ..Default::default() },
+        )?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn Add(
+                    a: ::core::ffi::c_int,
+                    b: ::core::option::Option<::core::ffi::c_int>,
+                    c: ::core::option::Option<::core::ffi::c_int>
+                ) -> ::core::ffi::c_int {
+                    unsafe {
+                        match (b, c,) {
+                            (::core::option::Option::Some(b), ::core::option::Option::Some(c),) =>
+                                crate::detail::__rust_thunk___Z3Addiii(a, b, c),
+                            (::core::option::Option::Some(b), ::core::option::Option::None,) =>
+                                crate::detail::__rust_thunk___Z3Addiii__without_c(a, b),
+                            (::core::option::Option::None, ::core::option::Option::None,) =>
+                                crate::detail::__rust_thunk___Z3Addiii__without_b(a),
+                            _ => ::core::panic!("Only trailing default arguments can be `None`"),
+                        }
+                    }
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { Add_without_b });
+        assert_rs_not_matches!(rs_api, quote! { Add_without_c });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addiii__without_b(int a) {
+                    return Add(a);
+                }
+                ...
+                extern "C" int __rust_thunk___Z3Addiii__without_c(int a, int b) {
+                    return Add(a, b);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_function_with_types_from_other_target() -> Result<()> {
+        let ir = ir_from_cc_dependency(
+            "inline ReturnStruct DoSomething(ParamStruct param);",
+            "struct ReturnStruct final {}; struct ParamStruct final {};",
+        )?;
+
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn DoSomething(mut param: dependency::ParamStruct)
+                    -> dependency::ReturnStruct {
+                     unsafe {
+                         let mut __return =
+                             ::core::mem::MaybeUninit::<dependency::ReturnStruct>::uninit();
+                         crate::detail::__rust_thunk___Z11DoSomething11ParamStruct(
+                             &mut __return, &mut param);
+                         __return.assume_init()
+                     }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+            mod detail {
+                #[allow(unused_imports)]
+                use super::*;
+                extern "C" {
+                    pub(crate) fn __rust_thunk___Z11DoSomething11ParamStruct(
+                        __return: &mut ::core::mem::MaybeUninit<dependency::ReturnStruct>,
+                        param: &mut dependency::ParamStruct
+                    );
+                }
+            }}
+        );
+
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z11DoSomething11ParamStruct(
+                        struct ReturnStruct* __return, struct ParamStruct* param) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    new (__return) auto(DoSomething(std::move(*param)));
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_in_dependency_and_alias_in_current_target() -> Result<()> {
+        // See also the test with the same name in `ir_from_cc_test.rs`.
+        let ir = {
+            let dependency_src = r#" #pragma clang lifetime_elision
+                    template <typename T>
+                    struct MyTemplate {
+                        T GetValue() { return field; }
+                        T field;
+                    }; "#;
+            let current_target_src = r#" #pragma clang lifetime_elision
+                    using MyAliasOfTemplate = MyTemplate<int>; "#;
+            ir_from_cc_dependency(current_target_src, dependency_src)?
+        };
+
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C)]
+                pub struct __CcTemplateInst10MyTemplateIiE {
+                    pub field: ::core::ffi::c_int,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl __CcTemplateInst10MyTemplateIiE {
+                    #[doc = " Generated from: google3/test/dependency_header.h;l=4"]
+                    #[inline(always)]
+                    pub fn GetValue<'a>(self: ... Pin<&'a mut Self>) -> ::core::ffi::c_int { unsafe {
+                        crate::detail::__rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(
+                            self)
+                    }}
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub type MyAliasOfTemplate = crate::__CcTemplateInst10MyTemplateIiE;
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                mod detail { ...  extern "C" {
+                    ...
+                    pub(crate) fn
+                    __rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget<'a>(
+                        __this: ... Pin<&'a mut crate::__CcTemplateInst10MyTemplateIiE>
+                    ) -> ::core::ffi::c_int;
+                    ...
+                } }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C"
+                int __rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(
+                        struct MyTemplate<int>* __this) {
+                    return __this->GetValue();
+                }
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_with_out_of_line_definition() -> Result<()> {
+        // See also an end-to-end test in the `test/templates/out_of_line_definition`
+        // directory.
+        let ir = ir_from_cc(
+            r#" #pragma clang lifetime_elision
+                template <typename T>
+                class MyTemplate final {
+                 public:
+                  static MyTemplate Create(T value);
+                  const T& value() const;
+
+                 private:
+                  T value_;
+                };
+
+                using MyTypeAlias = MyTemplate<int>; "#,
+        )?;
+
+        let BindingsTokens { rs_api_impl, .. } = generate_bindings_tokens(ir)?;
+
+        // Even though the member functions above are *not* defined inline (e.g.
+        // IR::Func::is_inline is false), they still need to have thunks generated for
+        // them (to force/guarantee that the class template and its members get
+        // instantiated).  This is also covered in the following end-to-end
+        // tests:
+        // - test/templates/out_of_line_definition/ - without a thunk, the template
+        //   won't be instantiated and Rust bindings won't be able to call the member
+        //   function (there will be no instantiation of the member function in the C++
+        //   object files)
+        // - test/templates/definition_in_cc/ - the instantiation happens in the .cc
+        //   file and therefore the thunk is not *required* (but it doesn't hurt to have
+        //   the thunk)
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void
+                __rust_thunk___ZN10MyTemplateIiE6CreateEi__2f_2ftest_3atesting_5ftarget(
+                    class MyTemplate<int>* __return, int value) {
+                  crubit::UnpoisonPlacementStorage(__return);
+                  new (__return) auto(MyTemplate<int>::Create(value));
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int const*
+                __rust_thunk___ZNK10MyTemplateIiE5valueEv__2f_2ftest_3atesting_5ftarget(
+                        const class MyTemplate<int>*__this) {
+                    return &__this->value();
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_miri_compatible_layout_assertions() -> Result<()> {
+        let ir = ir_from_cc("struct SomeStruct final { int first; int second; };")?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions { generate_miri_compatible_bindings: true, ..Default::default() },
+        )?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(::core::mem::offset_of!(crate::SomeStruct, first) == 0);
+                const _: () = assert!(::core::mem::offset_of!(crate::SomeStruct, second) == 4);
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { memoffset::offset_of! });
+        Ok(())
+    }
+
+    #[test]
+    fn test_miri_compatible_out_params() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+              SomeStruct();
+              int field;
+            };
+            inline SomeStruct MakeStruct() { return SomeStruct(); }"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions { generate_miri_compatible_bindings: true, ..Default::default() },
+        )?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                unsafe {
+                    crate::detail::__rust_thunk___ZN10SomeStructC1Ev(
+                        ::core::ptr::addr_of_mut!(tmp));
+                    tmp.assume_init()
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___ZN10SomeStructC1Ev<'a>(
+                    __this: *mut ::core::mem::MaybeUninit<crate::SomeStruct>
+                );
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                let mut __return = ::core::mem::MaybeUninit::<crate::SomeStruct>::uninit();
+                crate::detail::__rust_thunk___Z10MakeStructv(::core::ptr::addr_of_mut!(__return));
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___Z10MakeStructv(
+                    __return: *mut ::core::mem::MaybeUninit<crate::SomeStruct>
+                );
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { &mut tmp });
+        assert_rs_not_matches!(rs_api, quote! { &mut __return });
+        Ok(())
+    }
+
+    #[test]
+    fn test_thunk_visibility() -> Result<()> {
+        let ir = ir_from_cc("inline int Add(int a, int b) { return a + b; }")?;
+        let rs_api_impl = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions { thunk_visibility: ThunkVisibility::Hidden, ..Default::default() },
+        )?
+        .rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" __attribute__((visibility("hidden"))) int __rust_thunk___Z3Addii(
+                    int a, int b) {
+                    return Add(a, b);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_snake_case_function_names() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            int GetValue(int x);
+            struct SomeStruct final {
+              void DoThing();
+              void already_snake();
+            };"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                snake_case_function_names: true,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn get_value(x: ::core::ffi::c_int) -> ::core::ffi::c_int { ... }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                #[inline(always)]
+                pub fn GetValue(x: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    get_value(x)
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn do_thing<'a>(&'a mut self) { ... }
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    pub fn DoThing<'a>(&'a mut self) {
+                        Self::do_thing(self)
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub fn already_snake<'a>(&'a mut self) { ... } });
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguated_func_names() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            void FooBar();
+            void foo_bar();
+            void fooBar();
+            void self();
+            void self_();"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                snake_case_function_names: true,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Bound as `foo_bar_2`, because the Rust name of the C++ function `FooBar` collides with another function."]
+                #[inline(always)]
+                pub fn foo_bar_2() { ... }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Bound as `foo_bar_3`, because the Rust name of the C++ function `fooBar` collides with another function."]
+                #[inline(always)]
+                pub fn foo_bar_3() { ... }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { #[inline(always)] pub fn foo_bar() { ... } });
+        assert_rs_matches!(rs_api, quote! { #[inline(always)] pub fn self_() { ... } });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Bound as `self_2`, because the Rust name of the C++ function `self` collides with another function."]
+                #[inline(always)]
+                pub fn self_2() { ... }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguated_func_names_skip_taken_suffixes() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            void FooBar();
+            void foo_bar();
+            void foo_bar_2();"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                snake_case_function_names: true,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(rs_api, quote! { #[inline(always)] pub fn foo_bar_2() { ... } });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Bound as `foo_bar_3`, because the Rust name of the C++ function `FooBar` collides with another function."]
+                #[inline(always)]
+                pub fn foo_bar_3() { ... }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_methods_inherited_from_multiple_bases() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Base1 {
+              int Name() const;
+              int Size() const;
+            };
+            struct Base2 {
+              int Name() const;
+              void Hidden() const;
+            };
+            struct Derived final : Base1, Base2 {
+              void Hidden() const;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl crate::Derived {
+                    #[doc = " Calls `Base1::Name`. Bound as `Name_Base1`, because `Name` is inherited from multiple base classes."]
+                    #[inline(always)]
+                    pub fn Name_Base1<'a>(&'a self) -> ::core::ffi::c_int {
+                        oops::Upcast::<&crate::Base1>::upcast(self).Name()
+                    }
+                    #[doc = " Calls `Base2::Name`. Bound as `Name_Base2`, because `Name` is inherited from multiple base classes."]
+                    #[inline(always)]
+                    pub fn Name_Base2<'a>(&'a self) -> ::core::ffi::c_int {
+                        oops::Upcast::<&crate::Base2>::upcast(self).Name()
+                    }
+                    #[doc = " Calls `Base1::Size`."]
+                    #[inline(always)]
+                    pub fn Size<'a>(&'a self) -> ::core::ffi::c_int {
+                        oops::Upcast::<&crate::Base1>::upcast(self).Size()
+                    }
+                }
+            }
+        );
+        // `Derived::Hidden` hides `Base2::Hidden`.
+        assert_rs_not_matches!(rs_api, quote! { upcast(self).Hidden() });
+        Ok(())
+    }
+
+    #[test]
+    fn test_constructor_builder() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Config final {
+              Config(int width, int height, double scale);
+              Config(int width, int height);
+            };"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                constructor_builder_min_params: 3,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Builder for the `Config` constructor."]
+                #[derive(Default)]
+                pub struct ConfigBuilder {
+                    width: ::core::option::Option<::core::ffi::c_int>,
+                    height: ::core::option::Option<::core::ffi::c_int>,
+                    scale: ::core::option::Option<f64>,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn width(mut self, width: ::core::ffi::c_int) -> Self {
+                    self.width = ::core::option::Option::Some(width);
+                    self
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn build(self) -> Config {
+                    let width = self.width.expect(
+                        "`ConfigBuilder::width` must be set before calling `build`");
+                    ...
+                    let mut tmp = ::core::mem::MaybeUninit::<Config>::zeroed();
+                    unsafe {
+                        crate::detail::__rust_thunk___ZN6ConfigC1Eiid(
+                            &mut tmp, width, height, scale);
+                        tmp.assume_init()
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl Config {
+                    #[inline(always)]
+                    pub fn builder() -> ConfigBuilder {
+                        ::core::default::Default::default()
+                    }
+                }
+            }
+        );
+        // Constructors with fewer parameters aren't bound as builders, and
+        // constructors with more than one parameter aren't supported otherwise.
+        assert_rs_not_matches!(rs_api, quote! { __rust_thunk___ZN6ConfigC1Eii });
+        Ok(())
+    }
+
+    fn generate_bindings_tokens_with_builders(
+        ir: IR,
+        constructor_builder_min_params: i32,
+    ) -> Result<BindingsTokens> {
+        super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                constructor_builder_min_params,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_constructor_builder_options_struct() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct ConfigOptions final {
+              int width = 640;
+              int height = 480;
+            };
+            struct Config final {
+              explicit Config(const ConfigOptions& options);
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens_with_builders(ir, 3)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Builder for the `Config` constructor that takes a `ConfigOptions`."]
+                #[derive(Default)]
+                pub struct ConfigBuilder {
+                    options: crate::ConfigOptions,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn height(mut self, height: ::core::ffi::c_int) -> Self {
+                    self.options.height = height;
+                    self
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn build(self) -> Config {
+                    let options = self.options;
+                    let mut tmp = ::core::mem::MaybeUninit::<Config>::zeroed();
+                    unsafe {
+                        crate::detail::__rust_thunk___ZN6ConfigC1ERK13ConfigOptions(
+                            &mut tmp, &options);
+                        tmp.assume_init()
+                    }
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { impl From<...> for Config });
+        // The copy constructor of the options struct isn't a builder.
+        assert_rs_not_matches!(rs_api, quote! { ConfigOptionsBuilder });
+        Ok(())
+    }
+
+    #[test]
+    fn test_constructor_builder_collisions() -> Result<()> {
+        // Two constructors that would be bound as the same builder type.
+        let ir = ir_from_cc(
+            r#"struct Config final {
+              Config(int width, int height, double scale);
+              Config(int width, int height, int depth);
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens_with_builders(ir, 3)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { ConfigBuilder });
+
+        // A builder type that would collide with a C++ type.
+        let ir = ir_from_cc(
+            r#"struct ConfigBuilder final {};
+            struct Config final {
+              Config(int width, int height, double scale);
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens_with_builders(ir, 3)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { fn builder });
+
+        // A setter that would collide with `build`.
+        let ir = ir_from_cc(
+            r#"struct Config final {
+              Config(int width, int height, bool build);
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens_with_builders(ir, 3)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { ConfigBuilder });
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_module() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"int Add(int a, int b);
+            struct SomeStruct final {
+              void Method();
+            };"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                generate_raw_module: true,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        // The thunks themselves stay private to the crate.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[link_name = "_Z3Addii"]
+                pub(crate) fn __rust_thunk___Z3Addii(a: ::core::ffi::c_int, b: ::core::ffi::c_int)
+                    -> ::core::ffi::c_int;
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub mod raw {
+                    ...
+                    #[inline(always)]
+                    pub unsafe fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int)
+                        -> ::core::ffi::c_int {
+                        crate::detail::__rust_thunk___Z3Addii(a, b)
+                    }
+                    ...
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn SomeStruct__Method<'a>(__this: ...) {
+                    crate::detail::__rust_thunk___ZN10SomeStruct6MethodEv(__this)
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { pub fn __rust_thunk___Z3Addii });
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_raw_module_by_default() -> Result<()> {
+        let ir = ir_from_cc("int Add(int a, int b);")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { pub mod raw });
+        assert_rs_matches!(rs_api, quote! { pub(crate) fn __rust_thunk___Z3Addii });
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_only_thunks() -> Result<()> {
+        let ir = ir_from_cc("inline int Add(int a, int b) { return a + b; }")?;
+        let rs_api_impl = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions { generate_header_only_thunks: true, ..Default::default() },
+        )?
+        .rs_api_impl;
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ pragma once });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" inline __attribute__((used)) int __rust_thunk___Z3Addii(int a, int b) {
+                    return Add(a, b);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_namespaces() -> Result<()> {
+        let cc_src = r#"
+            struct Outer final {};
+            namespace split { struct Inner final {}; }
+            namespace split { inline void Func() {} }
+            namespace other { struct Other final {}; }
+        "#;
+
+        let mut ir = ir_from_cc(cc_src)?;
+        ir.split_namespaces_mut().0.insert("split".into(), "split-crate".into());
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(rs_api, quote! { pub struct Outer });
+        assert_rs_matches!(rs_api, quote! { pub mod other });
+        assert_rs_matches!(rs_api, quote! { pub use ::split_crate::split; });
+        assert_rs_not_matches!(rs_api, quote! { Inner });
+        assert_rs_not_matches!(rs_api, quote! { pub mod split });
+        assert_cc_not_matches!(rs_api_impl, quote! { Func });
+
+        let mut ir = ir_from_cc(cc_src)?;
+        let (split_namespaces, split_namespace_to_generate) = ir.split_namespaces_mut();
+        split_namespaces.insert("split".into(), "split-crate".into());
+        *split_namespace_to_generate = Some("split".into());
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(rs_api, quote! { pub mod split_0 { ... pub struct Inner ... } });
+        assert_rs_matches!(rs_api, quote! { pub mod split { ... pub fn Func() ... } });
+        assert_cc_matches!(rs_api_impl, quote! { split::Func() });
+        assert_rs_not_matches!(rs_api, quote! { Outer });
+        assert_rs_not_matches!(rs_api, quote! { other });
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_namespaces_reference_outside_namespace() -> Result<()> {
+        let mut ir = ir_from_cc(
+            r#"
+            struct Outer final {};
+            namespace other { struct Other final {}; }
+            namespace split {
+            struct Inner final {};
+            inline void TakesInner(Inner inner) {}
+            inline void TakesOuter(Outer outer) {}
+            inline void TakesOther(other::Other other) {}
+            }
+            "#,
+        )?;
+        let (split_namespaces, split_namespace_to_generate) = ir.split_namespaces_mut();
+        split_namespaces.insert("split".into(), "split-crate".into());
+        split_namespaces.insert("other".into(), "other-crate".into());
+        *split_namespace_to_generate = Some("split".into());
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains("pub fn TakesInner("));
+        assert!(!rs_api.contains("pub fn TakesOuter("));
+        assert!(!rs_api.contains("pub fn TakesOther("));
+        assert_eq!(
+            rs_api
+                .matches(
+                    "is not in the namespace `split`, whose bindings are generated into a \
+                     separate crate (see --split_namespaces)"
+                )
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_forced_includes_and_macro_definitions() -> Result<()> {
+        let mut ir = ir_from_cc("inline void Func() {}")?;
+        let (forced_includes, macro_definitions) = ir.preprocessor_options_mut();
+        forced_includes.push(HeaderName { name: "shims.h".into() });
+        macro_definitions.push("MY_EXPORT=".into());
+        macro_definitions.push("MY_FEATURE".into());
+        let ir = Rc::new(ir);
+        assert_eq!(
+            format_macro_definitions(&ir),
+            "// Macros predefined for the C++ library being wrapped.\n\
+            #define MY_EXPORT \n\
+            #define MY_FEATURE 1\n\n"
+        );
+        let BindingsTokens { rs_api_impl, .. } = super::generate_bindings_tokens(
+            ir,
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions::default(),
+        )?;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                __HASH_TOKEN__ include "shims.h"
+                ...
+                __HASH_TOKEN__ include "ir_from_cc_virtual_header.h"
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzz_harness() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            int Add(int a, int b);
+            namespace ns { bool IsEven(long long x); }
+            void TakesPointer(int* p);
+            struct S final {};
+            void TakesStruct(S s);
+            "#,
+        )?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let harness = generate_fuzz_harness_tokens(&db)?;
+        assert_rs_matches!(
+            harness,
+            quote! {
+                enum FuzzCall {
+                    Add(::core::ffi::c_int, ::core::ffi::c_int),
+                    ns__IsEven(::core::ffi::c_longlong)
+                }
+            }
+        );
+        assert_rs_matches!(
+            harness,
+            quote! {
+                ::libfuzzer_sys::fuzz_target!(|calls: Vec<FuzzCall>| {
+                    for call in calls {
+                        match call {
+                            FuzzCall::Add(arg0, arg1) => {
+                                let _ = testing_target::Add(arg0, arg1);
+                            }
+                            FuzzCall::ns__IsEven(arg0) => {
+                                let _ = testing_target::ns::IsEven(arg0);
+                            }
+                        }
+                    }
+                });
+            }
+        );
+        assert_rs_not_matches!(harness, quote! { TakesPointer });
+        assert_rs_not_matches!(harness, quote! { TakesStruct });
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzz_harness_without_supported_functions() -> Result<()> {
+        let ir = ir_from_cc("void TakesPointer(int* p);")?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let err = generate_fuzz_harness_tokens(&db).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The fuzz harness only supports free functions with safe bindings that take \
+             primitive types, and `//test:testing_target` has none"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_benchmark() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            int Add(int a, int b);
+            namespace ns { inline void Noop() {} }
+            void TakesPointer(int* p);
+            "#,
+        )?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let benchmark = generate_benchmark_tokens(&db)?;
+        assert_rs_matches!(
+            benchmark,
+            quote! {
+                let mut group = c.benchmark_group("Add");
+                group.bench_function("binding", |b| {
+                    b.iter(|| testing_target::Add(
+                        black_box(Default::default()),
+                        black_box(Default::default())
+                    ))
+                });
+                group.bench_function("direct", |b| {
+                    b.iter(|| unsafe { direct::direct_0(
+                        black_box(Default::default()),
+                        black_box(Default::default())
+                    ) })
+                });
+                group.finish();
+            }
+        );
+        assert_rs_matches!(
+            benchmark,
+            quote! {
+                mod direct {
+                    extern "C" {
+                        #[link_name = "_Z3Addii"]
+                        pub(crate) fn direct_0(arg0: ::core::ffi::c_int, arg1: ::core::ffi::c_int)
+                            -> ::core::ffi::c_int;
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            benchmark,
+            quote! {
+                let mut group = c.benchmark_group("ns::Noop");
+                group.bench_function("binding", |b| {
+                    b.iter(|| testing_target::ns::Noop())
+                });
+                group.finish();
+            }
+        );
+        assert_rs_not_matches!(benchmark, quote! { TakesPointer });
+        // Inline functions might not be emitted by the C++ library.
+        assert_rs_not_matches!(benchmark, quote! { direct_1 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_benchmark_raw_module() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            int Add(int a, int b);
+            namespace ns { inline void Noop() {} }
+            "#,
+        )?;
+        let db = new_database(
+            Rc::new(ir),
+            Rc::new(IgnoreErrors),
+            CodegenOptions { generate_raw_module: true, ..CodegenOptions::default() },
+        );
+        let benchmark = generate_benchmark_tokens(&db)?;
+        assert_rs_matches!(
+            benchmark,
+            quote! {
+                let mut group = c.benchmark_group("Add");
+                group.bench_function("binding", |b| { ... });
+                group.bench_function("direct", |b| { ... });
+                group.bench_function("raw", |b| {
+                    b.iter(|| unsafe { testing_target::raw::Add(
+                        black_box(Default::default()),
+                        black_box(Default::default())
+                    ) })
+                });
+                group.finish();
+            }
+        );
+        assert_rs_matches!(
+            benchmark,
+            quote! {
+                group.bench_function("raw", |b| {
+                    b.iter(|| unsafe { testing_target::raw::ns__Noop() })
+                });
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_map() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                int field;
+            };
+            namespace ns {
+            int Add(int a, int b);
+            }"#,
+        )?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let source_map: serde_json::Value = serde_json::from_str(&generate_source_map_json(&db)?)?;
+        assert_eq!(source_map["target"], "//test:testing_target");
+        let items = source_map["items"].as_array().unwrap();
+        let find_item = |rs_path: &str| {
+            items.iter().find(|item| item["rs_path"] == rs_path).unwrap_or_else(|| {
+                panic!("No source map entry for {rs_path} in {source_map:#}");
+            })
+        };
+        assert_eq!(
+            find_item("SomeStruct"),
+            &serde_json::json!({
+                "rs_path": "SomeStruct",
+                "cc_name": "SomeStruct",
+                "file": "ir_from_cc_virtual_header.h",
+                "line": 2,
+            })
+        );
+        assert_eq!(
+            find_item("ns::Add"),
+            &serde_json::json!({
+                "rs_path": "ns::Add",
+                "cc_name": "ns::Add",
+                "file": "ir_from_cc_virtual_header.h",
+                "line": 6,
+            })
+        );
+        let default = find_item("<SomeStruct as Default>::default");
+        assert_eq!(default["thunk"], "__rust_thunk___ZN10SomeStructC1Ev");
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                int field;
+            };
+            inline int Add(int a, int b) { return a + b; }
+            void Overloaded(int);
+            void Overloaded(float);
+            "#,
+        )?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let stats: serde_json::Value = serde_json::from_str(&generate_stats_json(&db)?)?;
+        assert_eq!(stats["target"], "//test:testing_target");
+        assert_eq!(stats["imported_items"]["records"], 1);
+        assert_eq!(stats["bound_items"]["records"], 1);
+        // Overloads are bound under disambiguated names.
+        assert!(stats["skipped_items"]
+            .get("Cannot generate bindings for overloaded function")
+            .is_none());
+        // `Add` is inline, so it's called through a thunk (as are the special member
+        // functions of `SomeStruct`).
+        assert!(stats["thunks"].as_u64().unwrap() >= 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_colliding_functions() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            void Overloaded(int);
+            [[clang::annotate("crubit_rust_name", "Overloaded")]] void AlsoOverloaded(float);
+            "#,
+        )?;
+        let db = new_database(Rc::new(ir), Rc::new(IgnoreErrors), CodegenOptions::default());
+        let stats: serde_json::Value = serde_json::from_str(&generate_stats_json(&db)?)?;
+        assert_eq!(stats["skipped_items"]["Cannot generate bindings for overloaded function"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_struct() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            struct SomeStruct final {
+                ~SomeStruct() {}
+                int public_int;
+              protected:
+                int protected_int;
+              private:
+               int private_int;
+            };
+        "#,
+        )?;
+
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[::ctor::recursively_pinned(PinnedDrop)]
+                #[repr(C, align(4))]
+                pub struct SomeStruct {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 0],
+                    pub public_int: ::core::ffi::c_int,
+                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
+                    pub(crate) protected_int: [::core::mem::MaybeUninit<u8>; 4],
+                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
+                    pub(crate) private_int: [::core::mem::MaybeUninit<u8>; 4],
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(::core::mem::size_of::<crate::SomeStruct>() == 12);
+                const _: () = assert!(::core::mem::align_of::<crate::SomeStruct>() == 4);
+                const _: () = { static_assertions::assert_not_impl_any!(crate::SomeStruct: Copy); };
+                const _: () = { static_assertions::assert_impl_all!(crate::SomeStruct: Drop); };
+                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, public_int) == 0);
+                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, protected_int) == 4);
+                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, private_int) == 8);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN10SomeStructD1Ev(struct SomeStruct * __this) {
+                    std::destroy_at(__this);
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 12);
+                static_assert(alignof(struct SomeStruct) == 4);
+                static_assert(CRUBIT_OFFSET_OF(public_int, struct SomeStruct) == 0);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_struct() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            namespace ns {
+            struct Outer final {
+                struct Inner final {
+                    inline int GetField() const { return inner_field; }
+                    int inner_field;
+                };
+                Inner outer_field;
+            };
+            }  // namespace ns
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Outer {
+                    pub outer_field: crate::ns::Outer_Inner,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Outer_Inner {
+                    pub inner_field: ::core::ffi::c_int,
+                }
+                forward_declare::unsafe_define!(
+                    forward_declare::symbol!("Outer::Inner"),
+                    crate::ns::Outer_Inner
+                );
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl Outer_Inner {
+                    #[inline(always)]
+                    pub fn GetField<'a>(&'a self) -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZNK2ns5Outer5Inner8GetFieldEv(self) }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(::core::mem::size_of::<crate::ns::Outer_Inner>() == 4);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZNK2ns5Outer5Inner8GetFieldEv(
+                        const struct ns::Outer::Inner* __this) {
+                    return __this->GetField();
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_SIZEOF(struct ns::Outer::Inner) == 4);
+                static_assert(alignof(struct ns::Outer::Inner) == 4);
+                static_assert(CRUBIT_OFFSET_OF(inner_field, struct ns::Outer::Inner) == 0);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_struct_name_collision() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct Outer final {
+                struct Inner final {};
+                struct Other final {};
+                Inner inner_field;
+            };
+            struct Outer_Inner final {};
+            inline void TakeInner(Outer::Inner inner) {}
+            inline void TakeOuterInner(Outer_Inner outer_inner) {}
+        "#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert_eq!(rs_api.matches("pub struct Outer_Inner {").count(), 1);
+        assert!(rs_api.contains("pub struct Outer_Other {"));
+        assert!(rs_api.contains("pub fn TakeOuterInner("));
+        assert!(!rs_api.contains("pub fn TakeInner("));
+        assert!(!rs_api.contains("pub inner_field: crate::Outer_Inner"));
+        assert!(rs_api.contains(
+            "The nested record `Outer::Inner` would be bound as `Outer_Inner`, which is already \
+             the name of another declaration"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_vs_class() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            struct SomeStruct final {
+                SomeStruct() {}
+                int field;
+            };
+            class SomeClass final {
+              public:
+                SomeClass() {}
+                int field;
+            };
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+
+        // A Rust `struct` is generated for both `SomeStruct` and `SomeClass`.
+        assert_rs_matches!(rs_api, quote! { pub struct SomeStruct },);
+        assert_rs_matches!(rs_api, quote! { pub struct SomeClass },);
+
+        // But in C++ we still should refer to `struct SomeStruct` and `class
+        // SomeClass`. See also b/238212337.
+        assert_cc_matches!(rs_api_impl, quote! { struct SomeStruct * __this });
+        assert_cc_matches!(rs_api_impl, quote! { class SomeClass * __this });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 4); }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_SIZEOF(class SomeClass) == 4); }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_vs_typedefed_struct() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            struct SomeStruct final {
+              int x;
+            } __attribute__((aligned(16)));
+            typedef struct {
+              int x;
+            } SomeAnonStruct __attribute__((aligned(16)));
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+
+        // A `struct` is generated for both `SomeStruct` and `SomeAnonStruct`, both
+        // in Rust and in C++.
+        assert_rs_matches!(rs_api, quote! { pub struct SomeStruct },);
+        assert_rs_matches!(rs_api, quote! { pub struct SomeAnonStruct },);
+        assert_rs_matches!(rs_api_impl, quote! { struct SomeStruct * __this },);
+        assert_rs_matches!(rs_api_impl, quote! { SomeAnonStruct * __this },);
+
+        // In C++, both have align == 16, but size for `SomeAnonStruct` is not aligned.
+        // `SomeAnonStruct` won't have `struct` in the assert.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(alignof(struct SomeStruct) == 16); }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { static_assert(alignof(SomeAnonStruct) == 16); });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 16); }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_SIZEOF(SomeAnonStruct) == 16); }
+        );
+
+        // In Rust, both have align == 16 and size == 16.
+        assert_rs_matches!(
+            rs_api,
+            quote! { assert!(::core::mem::size_of::<crate::SomeStruct>() == 16); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { assert!(::core::mem::align_of::<crate::SomeStruct>() == 16); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { assert!(::core::mem::size_of::<crate::SomeAnonStruct>() == 16); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { assert!(::core::mem::align_of::<crate::SomeAnonStruct>() == 16); }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typedef_member() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct SomeStruct final {
+              typedef int Type;
+            };
+            inline SomeStruct::Type Function() {return 0;}
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        // TODO(b/200067824): This should use the alias's real name in Rust, as well.
+        assert_rs_matches!(rs_api, quote! { pub fn Function() -> ::core::ffi::c_int { ... } },);
+
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" SomeStruct::Type __rust_thunk___Z8Functionv(){ return Function(); }
+            },
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ref_to_struct_in_thunk_impls() -> Result<()> {
+        let ir = ir_from_cc("struct S{}; inline void foo(S& s) {} ")?;
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z3fooR1S(struct S* s) {
+                    foo(*s);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_ref_to_struct_in_thunk_impls() -> Result<()> {
+        let ir = ir_from_cc("struct S{}; inline void foo(const S& s) {} ")?;
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z3fooRK1S(const struct S* s) {
+                    foo(*s);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_volatile_pointee_in_thunk_impls() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            inline volatile int* Register(const volatile int& r) { return nullptr; }"#,
+        )?;
+        let bindings = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            bindings.rs_api,
+            quote! {
+                pub unsafe fn Register(r: *const ::core::ffi::c_int) -> *mut ::core::ffi::c_int
+            }
+        );
+        assert_cc_matches!(
+            bindings.rs_api_impl,
+            quote! {
+                extern "C" int volatile* __rust_thunk___Z8RegisterRVKi(int const volatile* r) {
+                    return Register(*r);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_references_returned_without_lifetimes() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            inline int& GetRef() { static int i; return i; }
+            inline int&& GetRvalueRef() { static int i; return static_cast<int&&>(i); }"#,
+        )?;
+        let bindings = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            bindings.rs_api,
+            quote! {
+                pub fn GetRef() -> *mut ::core::ffi::c_int
+            }
+        );
+        assert_rs_matches!(
+            bindings.rs_api,
+            quote! {
+                pub fn GetRvalueRef() -> *mut ::core::ffi::c_int
+            }
+        );
+        assert_cc_matches!(
+            bindings.rs_api_impl,
+            quote! {
+                extern "C" int* __rust_thunk___Z12GetRvalueRefv() {
+                    int&& lvalue = GetRvalueRef();
+                    return &lvalue;
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_int_in_thunk_impls() -> Result<()> {
+        let ir = ir_from_cc("inline void foo(unsigned int i) {} ")?;
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z3fooj(unsigned int i) {
+                    foo(i);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_static_methods_qualify_call_in_thunk() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct SomeStruct {
+                static inline int some_func() { return 42; }
+            }; "#,
+        )?;
+
+        assert_cc_matches!(
+            generate_bindings_tokens(ir)?.rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZN10SomeStruct9some_funcEv() {
+                    return SomeStruct::some_func();
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hidden_friend_is_called_via_adl_in_thunk() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            namespace ns {
+              struct SomeStruct final {
+                friend int get(const SomeStruct& s) { return 42; }
+              };
+            }  // namespace ns"#,
+        )?;
+        let bindings = generate_bindings_tokens(ir)?;
+
+        assert_rs_matches!(
+            bindings.rs_api,
+            quote! {
+                pub mod ns {
+                    ...
+                    pub fn get<'a>(s: &'a crate::ns::SomeStruct) -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN2ns3getERKNS_10SomeStructE(s) }
+                    }
+                    ...
+                }
+            }
+        );
+        // `ns::get` would not compile, because hidden friends are only visible to ADL.
+        assert_cc_matches!(
+            bindings.rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZN2ns3getERKNS_10SomeStructE(
+                    const struct ns::SomeStruct* s) {
+                    return get(*s);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_static_methods_are_associated_functions() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            namespace ns {
+              struct SomeStruct final {
+                static int Create();
+                static int Create(int x);
+                int Create(float f) const;
+              };
+            }  // namespace ns
+            void Create(); "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
+        // Static methods don't take `self`, and the overloads (including the
+        // overloads that are instance methods) get suffixes derived from their
+        // parameter types.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn Create() -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN2ns10SomeStruct6CreateEv() }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn Create_int(x: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN2ns10SomeStruct6CreateEi(x) }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn Create_float_const(__this: *const Self, f: f32) -> ::core::ffi::c_int
+            }
+        );
+
+        // The free function of the same name is not an overload of the methods.
+        assert_rs_matches!(rs_api, quote! { pub fn Create() { ... } });
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_instance_methods_deref_this_in_thunk() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct SomeStruct {
+                inline int some_func(int arg) const { return 42 + arg; }
+            }; "#,
+        )?;
+
+        assert_cc_matches!(
+            generate_bindings_tokens(ir)?.rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZNK10SomeStruct9some_funcEi(
+                        const struct SomeStruct* __this, int arg) {
+                    return __this->some_func(arg);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_with_unsupported_field_type() -> Result<()> {
+        // Using `volatile` because it's currently not supported.
+        // But... any other unsupported type would also work for this test.
+        let ir = ir_from_cc(
+            r#"
+            struct StructWithUnsupportedField {
+              // Doc comment for `my_field`.
+              volatile int my_field;
+            };
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C, align(4))]
+                pub struct StructWithUnsupportedField {
+                    #[doc = " Doc comment for `my_field`.\n \n Reason for representing this field as a blob of bytes:\n Unsupported `volatile` qualifier: volatile int"]
+                    pub(crate) my_field: [::core::mem::MaybeUninit<u8>; 4],
+                }
+                ...
+                const _: () = assert!(
+                    memoffset::offset_of!(crate::StructWithUnsupportedField, my_field) == 0);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_with_array_fields() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct Inner final {
+              int value;
+            };
+            struct StructWithArrays final {
+              int buffer[16];
+              float matrix[2][3];
+              Inner inners[4];
+            };
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C)]
+                pub struct StructWithArrays {
+                    pub buffer: [::core::ffi::c_int; 16],
+                    pub matrix: [[f32; 3]; 2],
+                    pub inners: [crate::Inner; 4],
+                }
+                ...
+                const _: () = assert!(memoffset::offset_of!(crate::StructWithArrays, buffer) == 0);
+                const _: () = assert!(memoffset::offset_of!(crate::StructWithArrays, matrix) == 64);
+                const _: () = assert!(memoffset::offset_of!(crate::StructWithArrays, inners) == 88);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_OFFSET_OF(buffer, struct StructWithArrays) == 0);
+                static_assert(CRUBIT_OFFSET_OF(matrix, struct StructWithArrays) == 64);
+                static_assert(CRUBIT_OFFSET_OF(inners, struct StructWithArrays) == 88);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pointer_to_array() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            using Pair = int[2];
+            inline void TakesPointerToArray(int (*pair)[2]) {}
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(rs_api, quote! { pub type Pair = [::core::ffi::c_int; 2]; });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn TakesPointerToArray(pair: *mut [::core::ffi::c_int; 2]) { ... }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z19TakesPointerToArrayPA2_i(
+                    crubit::type_identity_t<int[2]>* pair) {
+                    TakesPointerToArray(pair);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    /// This is a regression test for b/283835873 where the alignment of the
+    /// generated struct was wrong/missing.
+    #[test]
+    fn test_struct_with_only_bitfields() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                struct SomeStruct {
+                  char32_t code_point : 31;
+                  enum : char32_t {
+                    ok = 0,
+                    error = 1
+                  } status : 1;
+                };
+                static_assert(sizeof(SomeStruct) == 4);
+                static_assert(alignof(SomeStruct) == 4);
+            "#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               #[repr(C, align(4))]
+               pub struct SomeStruct { ...  }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { const _: () = assert!(::core::mem::size_of::<crate::SomeStruct>() == 4); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {  const _: () = assert!(::core::mem::align_of::<crate::SomeStruct>() == 4); }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_with_unnamed_bitfield_member() -> Result<()> {
+        // This test input causes `field_decl->getName()` to return an empty string.
+        // This example is based on `struct timex` from
+        // /usr/grte/v5/include/bits/timex.h
+        let ir = ir_from_cc(
+            r#"
+            struct SomeStruct {
+                int first_field;
+                int :32;
+                int last_field;
+            }; "#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               #[repr(C, align(4))]
+               pub struct SomeStruct {
+                   pub first_field: ::core::ffi::c_int, ...
+                   __bitfields1: [::core::mem::MaybeUninit<u8>; 4],
+                   pub last_field: ::core::ffi::c_int,
+               }
+               ...
+               const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, first_field) == 0);
+               const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, last_field) == 8);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_with_inheritable_field() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct TrivialButInheritable {
+              int x;
+            };
+            struct StructWithInheritable final {
+              TrivialButInheritable t;
+            };
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_with_inheritable_field() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct TrivialButInheritable {
+              int x;
+            };
+            union UnionWithInheritable {
+              TrivialButInheritable t;
+            };
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        Ok(())
+    }
+
+    /// Classes with a non-public destructor shouldn't be constructible, not
+    /// even via Copy/Clone.
+    #[test]
+    fn test_trivial_nonpublic_destructor() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Indestructible final {
+              Indestructible() = default;
+              Indestructible(int);
+              Indestructible(const Indestructible&) = default;
+              void Foo() const;
+             private:
+              ~Indestructible() = default;
+            };
+
+            Indestructible ReturnsValue();
+            void TakesValue(Indestructible);
+            void TakesReference(const Indestructible& x);
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // It isn't available by value:
+        assert_rs_not_matches!(rs_api, quote! {Default});
+        assert_rs_not_matches!(rs_api, quote! {From});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        assert_rs_not_matches!(rs_api, quote! {ReturnsValue});
+        assert_rs_not_matches!(rs_api, quote! {TakesValue});
+        // ... but it is otherwise available:
+        assert_rs_matches!(rs_api, quote! {struct Indestructible});
+        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn TakesReference<'a>(x: &'a crate::Indestructible)});
+        Ok(())
+    }
+
+    #[test]
+    fn test_nontrivial_nonpublic_destructor() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Indestructible final {
+              Indestructible() = default;
+              Indestructible(int);
+              Indestructible(const Indestructible&) = default;
+              void Foo() const;
+             private:
+              ~Indestructible() {}
+            };
+
+            Indestructible ReturnsValue();
+            void TakesValue(Indestructible);
+            void TakesReference(const Indestructible& x);
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // It isn't available by value:
+        assert_rs_not_matches!(rs_api, quote! {CtorNew});
+        assert_rs_not_matches!(rs_api, quote! {ReturnsValue});
+        assert_rs_not_matches!(rs_api, quote! {TakesValue});
+        // ... but it is otherwise available:
+        assert_rs_matches!(rs_api, quote! {struct Indestructible});
+        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn TakesReference<'a>(x: &'a crate::Indestructible)});
+        Ok(())
+    }
+
+    /// trivial abstract structs shouldn't be constructible, not even via
+    /// Copy/Clone.
+    ///
+    /// Right now, a struct can only be Copy/Clone if it's final, but that
+    /// restriction will likely be lifted later.
+    #[test]
+    fn test_trivial_abstract_by_value() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Abstract final {
+              Abstract() = default;
+              Abstract(int);
+              Abstract(const Abstract&) = default;
+              virtual void Foo() const = 0;
+              void Nonvirtual() const;
+            };
+            void TakesAbstract(const Abstract& a);
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // It isn't available by value:
+        assert_rs_not_matches!(rs_api, quote! {Default});
+        assert_rs_not_matches!(rs_api, quote! {From});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        // ... but it is otherwise available:
+        assert_rs_matches!(rs_api, quote! {struct Abstract});
+        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn Nonvirtual<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn TakesAbstract<'a>(a: &'a crate::Abstract)});
+        Ok(())
+    }
+
+    #[test]
+    fn test_nontrivial_abstract_by_value() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Abstract final {
+              Abstract() {};
+              Abstract(int);
+              Abstract(const Abstract&) {}
+              virtual void Foo() const = 0;
+              void Nonvirtual() const;
+            };
+            void TakesAbstract(const Abstract& a);
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {CtorNew});
+        // ... but it is otherwise available:
+        assert_rs_matches!(rs_api, quote! {struct Abstract});
+        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn Nonvirtual<'a>(&'a self)});
+        assert_rs_matches!(rs_api, quote! {fn TakesAbstract<'a>(a: &'a crate::Abstract)});
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_with_unnamed_struct_and_union_members() -> Result<()> {
+        // This test input causes `field_decl->getName()` to return an empty string.
+        // See also:
+        // - https://en.cppreference.com/w/c/language/struct: "[...] an unnamed member
+        //   of a struct whose type is a struct without name is known as anonymous
+        //   struct."
+        // - https://rust-lang.github.io/rfcs/2102-unnamed-fields.html
+        let ir = ir_from_cc(
+            r#"
+            struct StructWithUnnamedMembers {
+              int first_field;
+
+              struct {
+                int anonymous_struct_field_1;
+                int anonymous_struct_field_2;
+              };
+              union {
+                int anonymous_union_field_1;
+                int anonymous_union_field_2;
+              };
+
+              int last_field;
+            }; "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               #[repr(C)]
+               pub struct StructWithUnnamedMembers {
+                   pub first_field: ::core::ffi::c_int,
+                   pub __unnamed_field1: crate::StructWithUnnamedMembers__unnamed_field1,
+                   pub __unnamed_field2: crate::StructWithUnnamedMembers__unnamed_field2,
+                   pub last_field: ::core::ffi::c_int,
+               }
+               ...
+               const _: () = assert!(memoffset::offset_of!(
+                       crate::StructWithUnnamedMembers, first_field) == 0);
+               const _: () = assert!(memoffset::offset_of!(
+                       crate::StructWithUnnamedMembers, __unnamed_field1) == 4);
+               const _: () = assert!(memoffset::offset_of!(
+                       crate::StructWithUnnamedMembers, __unnamed_field2) == 12);
+               const _: () = assert!(memoffset::offset_of!(
+                       crate::StructWithUnnamedMembers, last_field) == 16);
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               #[repr(C)]
+               pub struct StructWithUnnamedMembers__unnamed_field1 {
+                   pub anonymous_struct_field_1: ::core::ffi::c_int,
+                   pub anonymous_struct_field_2: ::core::ffi::c_int,
+               }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               #[repr(C)]
+               pub union StructWithUnnamedMembers__unnamed_field2 {
+                   pub anonymous_union_field_1: ::core::ffi::c_int,
+                   pub anonymous_union_field_2: ::core::ffi::c_int,
+               }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(
+                    ::core::mem::size_of::<crate::StructWithUnnamedMembers__unnamed_field1>() == 8
+                );
+            }
+        );
+        // The anonymous records can't be named in C++, so the offsets of their fields
+        // are checked in the enclosing record.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_OFFSET_OF(anonymous_struct_field_1,
+                    struct StructWithUnnamedMembers) == 4);
+                static_assert(CRUBIT_OFFSET_OF(anonymous_struct_field_2,
+                    struct StructWithUnnamedMembers) == 8);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_OFFSET_OF(anonymous_union_field_1,
+                    struct StructWithUnnamedMembers) == 12);
+                static_assert(CRUBIT_OFFSET_OF(anonymous_union_field_2,
+                    struct StructWithUnnamedMembers) == 12);
+            }
+        );
+        assert_cc_not_matches!(rs_api_impl, quote! { StructWithUnnamedMembers__unnamed_field1 });
+        assert_cc_not_matches!(rs_api_impl, quote! { StructWithUnnamedMembers__unnamed_field2 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_with_fields_of_anonymous_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct Outer {
+              struct {
+                union {
+                  int i;
+                  float f;
+                } value;
+              } named_field;
+            }; "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               pub struct Outer {
+                   pub named_field: crate::Outer__named_field,
+               }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               pub struct Outer__named_field {
+                   pub value: crate::Outer__named_field__value,
+               }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+               pub union Outer__named_field__value {
+                   pub i: ::core::ffi::c_int,
+                   pub f: f32,
+               }
+            }
+        );
+        // The types of named fields are spelled with `decltype` in C++.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_SIZEOF(decltype(Outer::named_field)) == 4);
+                static_assert(alignof(decltype(Outer::named_field)) == 4);
+                static_assert(CRUBIT_OFFSET_OF(value, decltype(Outer::named_field)) == 0);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(CRUBIT_SIZEOF(decltype(decltype(Outer::named_field)::value)) == 4);
+                static_assert(alignof(decltype(decltype(Outer::named_field)::value)) == 4);
+                static_assert(
+                    CRUBIT_OFFSET_OF(i, decltype(decltype(Outer::named_field)::value)) == 0);
+                static_assert(
+                    CRUBIT_OFFSET_OF(f, decltype(decltype(Outer::named_field)::value)) == 0);
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_from_other_target() -> Result<()> {
+        let ir = ir_from_cc_dependency("// intentionally empty", "struct SomeStruct {};")?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_not_matches!(rs_api, quote! { SomeStruct });
+        assert_cc_not_matches!(rs_api_impl, quote! { SomeStruct });
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_derives() {
+        let record = ir_record("S");
+        assert_eq!(generate_derives(&record), &["Clone", "Copy"]);
+    }
+
+    #[test]
+    fn test_copy_derives_not_is_trivial_abi() {
+        let mut record = ir_record("S");
+        record.is_trivial_abi = false;
+        assert_eq!(generate_derives(&record), &[""; 0]);
+    }
+
+    /// Even if it's trivially relocatable, !Unpin C++ type cannot be
+    /// cloned/copied or otherwise used by value, because values would allow
+    /// assignment into the Pin.
+    ///
+    /// All !Unpin C++ types, not just non trivially relocatable ones, are
+    /// unsafe to assign in the Rust sense.
+    #[test]
+    fn test_copy_derives_not_final() {
+        let mut record = ir_record("S");
+        record.is_inheritable = true;
+        assert_eq!(generate_derives(&record), &[""; 0]);
+    }
+
+    #[test]
+    fn test_copy_derives_ctor_deleted() {
+        let mut record = ir_record("S");
+        record.copy_constructor = ir::SpecialMemberFunc::Unavailable;
+        assert_eq!(generate_derives(&record), &[""; 0]);
+    }
+
+    #[test]
+    fn test_copy_derives_ctor_nontrivial_members() {
+        let mut record = ir_record("S");
+        record.copy_constructor = ir::SpecialMemberFunc::NontrivialMembers;
+        assert_eq!(generate_derives(&record), &[""; 0]);
+    }
+
+    #[test]
+    fn test_copy_derives_ctor_nontrivial_self() {
+        let mut record = ir_record("S");
+        record.copy_constructor = ir::SpecialMemberFunc::NontrivialUserDefined;
+        assert_eq!(generate_derives(&record), &[""; 0]);
+    }
+
+    /// In Rust, a Drop type cannot be Copy.
+    #[test]
+    fn test_copy_derives_dtor_nontrivial_self() {
+        let mut record = ir_record("S");
+        for definition in
+            [ir::SpecialMemberFunc::NontrivialUserDefined, ir::SpecialMemberFunc::NontrivialMembers]
+        {
+            record.destructor = definition;
+            assert_eq!(generate_derives(&record), &["Clone"]);
+        }
+    }
+
+    #[test]
+    fn test_ptr_func() -> Result<()> {
+        let ir = ir_from_cc(r#" inline int* Deref(int*const* p); "#)?;
+
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub unsafe fn Deref(p: *const *mut ::core::ffi::c_int) -> *mut ::core::ffi::c_int {
+                    crate::detail::__rust_thunk___Z5DerefPKPi(p)
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                mod detail {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    extern "C" {
+                        pub(crate) fn __rust_thunk___Z5DerefPKPi(p: *const *mut ::core::ffi::c_int) -> *mut ::core::ffi::c_int;
+                    }
+                }
+            }
+        );
+
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int* __rust_thunk___Z5DerefPKPi(int* const * p) {
+                    return Deref(p);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_char_ptr_func() -> Result<()> {
+        // This is a regression test: We used to include the "const" in the name
+        // of the CcType, which caused a panic in the code generator
+        // ('"const char" is not a valid Ident').
+        // It's therefore important that f() is inline so that we need to
+        // generate a thunk for it (where we then process the CcType).
+        let ir = ir_from_cc(r#" inline void f(const signed char *str); "#)?;
+
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub unsafe fn f(str: *const ::core::ffi::c_schar) {
+                    crate::detail::__rust_thunk___Z1fPKa(str)
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                extern "C" {
+                    pub(crate) fn __rust_thunk___Z1fPKa(str: *const ::core::ffi::c_schar);
+                }
+            }
+        );
+
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z1fPKa(signed char const * str){ f(str); }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pointer_unsafety_any_pointer() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            using IntPtr = int*;
+            void TakesAlias(IntPtr p);
+            void TakesRefToAlias(IntPtr& p);
+            void TakesRef(int& p);"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { pub unsafe fn TakesAlias(p: crate::IntPtr) });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesRefToAlias<'a>(p: &'a mut crate::IntPtr) }
+        );
+        assert_rs_matches!(rs_api, quote! { pub fn TakesRef<'a>(p: &'a mut ::core::ffi::c_int) });
+        Ok(())
+    }
+
+    #[test]
+    fn test_pointer_unsafety_top_level_pointer() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            using IntPtr = int*;
+            void TakesAlias(IntPtr p);
+            void TakesRefToAlias(IntPtr& p);"#,
+        )?;
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                pointer_unsafety: PointerUnsafety::TopLevelPointer,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(rs_api, quote! { pub unsafe fn TakesAlias(p: crate::IntPtr) });
+        assert_rs_matches!(rs_api, quote! { pub fn TakesRefToAlias<'a>(p: &'a mut crate::IntPtr) });
+        Ok(())
+    }
+
+    fn generate_bindings_tokens_with_exception_policy(
+        ir: IR,
+        exception_policy: ExceptionPolicy,
+    ) -> Result<BindingsTokens> {
+        super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                exception_policy,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_exception_policy_abort() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"int Add(int a, int b);
+            int AddNoexcept(int a, int b) noexcept;"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } =
+            generate_bindings_tokens_with_exception_policy(ir, ExceptionPolicy::Abort)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z3Addii(a, b) }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addii(int a, int b) noexcept {
+                    return Add(a, b);
+                }
+            }
+        );
+        // `noexcept` functions are called directly.
+        assert_rs_matches!(rs_api, quote! { #[link_name = "_Z11AddNoexceptii"] });
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z11AddNoexceptii });
+        Ok(())
+    }
+
+    #[test]
+    fn test_exception_policy_functions_that_cant_throw() -> Result<()> {
+        for exception_policy in [ExceptionPolicy::Abort, ExceptionPolicy::Catch] {
+            let mut ir = ir_from_cc(
+                r#"inline int AddInline(int a, int b) noexcept { return a + b; }
+                int AddWithoutExceptions(int a, int b);"#,
+            )?;
+            // As if compiled with `-fno-exceptions`.
+            for item in ir.items_mut() {
+                if let Item::Func(func) = item {
+                    if func.name
+                        == UnqualifiedIdentifier::Identifier(ir_testing::ir_id(
+                            "AddWithoutExceptions",
+                        ))
+                    {
+                        Rc::make_mut(func).may_throw = false;
+                    }
+                }
+            }
+            let BindingsTokens { rs_api, rs_api_impl } =
+                generate_bindings_tokens_with_exception_policy(ir, exception_policy)?;
+            // The thunk of an inline function doesn't need to be `noexcept`.
+            assert_cc_matches!(
+                rs_api_impl,
+                quote! {
+                    extern "C" int __rust_thunk___Z9AddInlineii(int a, int b) {
+                        return AddInline(a, b);
+                    }
+                }
+            );
+            // Other functions are called directly.
+            assert_rs_matches!(rs_api, quote! { #[link_name = "_Z20AddWithoutExceptionsii"] });
+            assert_cc_not_matches!(
+                rs_api_impl,
+                quote! { __rust_thunk___Z20AddWithoutExceptionsii }
+            );
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    pub fn AddWithoutExceptions(a: ::core::ffi::c_int, b: ::core::ffi::c_int)
+                        -> ::core::ffi::c_int { ... }
+                }
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exception_policy_catch() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"struct S final { int x; };
+            int Add(int a, int b);
+            void Log(int a);
+            S Make();
+            int AddNoexcept(int a, int b) noexcept;
+            struct [[clang::annotate("crubit_internal_rust_type", "i32")]]
+                [[clang::annotate("crubit_internal_same_abi")]] Id final {
+              explicit Id(int value);
+              int value;
+            };
+            Id MakeId();"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } =
+            generate_bindings_tokens_with_exception_policy(ir, ExceptionPolicy::Catch)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Add(a: ::core::ffi::c_int, b: ::core::ffi::c_int)
+                    -> ::core::result::Result<::core::ffi::c_int, ::cxx_exception::CxxException> {
+                    unsafe {
+                        let mut __exception = ::cxx_exception::ExceptionPtr::null();
+                        let __return_value =
+                            crate::detail::__rust_thunk___Z3Addii(&mut __exception, a, b);
+                        __exception.into_result()?;
+                        ::core::result::Result::Ok(__return_value)
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___Z3Addii(
+                    __exception: &mut ::cxx_exception::ExceptionPtr,
+                    a: ::core::ffi::c_int,
+                    b: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int;
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Log(a: ::core::ffi::c_int)
+                    -> ::core::result::Result<(), ::cxx_exception::CxxException> { ... }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Make()
+                    -> ::core::result::Result<crate::S, ::cxx_exception::CxxException> {
+                    unsafe {
+                        let mut __exception = ::cxx_exception::ExceptionPtr::null();
+                        let mut __return = ::core::mem::MaybeUninit::<crate::S>::uninit();
+                        crate::detail::__rust_thunk___Z4Makev(&mut __exception, &mut __return);
+                        __exception.into_result()?;
+                        ::core::result::Result::Ok(__return.assume_init())
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ include <exception> });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___Z3Addii(
+                    std::exception_ptr* __exception, int a, int b) {
+                    try {
+                        return Add(a, b);
+                    } catch (...) {
+                        *__exception = std::current_exception();
+                    }
+                    return {};
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z4Makev(
+                    std::exception_ptr* __exception, struct S* __return) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    try {
+                        new (__return) auto(Make());
+                    } catch (...) {
+                        *__exception = std::current_exception();
+                    }
+                }
+            }
+        );
+        // `Id` may not be value-initializable, so it is returned through `__return`
+        // rather than by value, which would need a dummy value to return when
+        // catching an exception.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___Z6MakeIdv(
+                    __exception: &mut ::cxx_exception::ExceptionPtr,
+                    __return: &mut ::core::mem::MaybeUninit<i32>
+                );
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z6MakeIdv(
+                    std::exception_ptr* __exception, ... __return) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    try {
+                        new (__return) auto(MakeId());
+                    } catch (...) {
+                        *__exception = std::current_exception();
+                    }
+                }
+            }
+        );
+        // `noexcept` functions can't throw, so their bindings don't return a `Result`.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn AddNoexcept(a: ::core::ffi::c_int, b: ::core::ffi::c_int)
+                    -> ::core::ffi::c_int { ... }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_gsl_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace gsl {
+            constexpr unsigned long dynamic_extent = static_cast<unsigned long>(-1);
+            template <typename T, unsigned long Extent = dynamic_extent>
+            class span {
+             private:
+              unsigned long size_;
+              T* data_;
+            };
+            template <typename T>
+            using owner = T;
+            using czstring = const char*;
+            }  // namespace gsl
+            inline void TakesSpan(gsl::span<int> s) {}
+            inline void TakesConstSpan(gsl::span<const int> s) {}
+            gsl::owner<int*> ReturnsOwner();
+            void TakesCzstring(gsl::czstring s);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesSpan(s: ::gsl::SpanMut<::core::ffi::c_int>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesConstSpan(s: ::gsl::Span<::core::ffi::c_int>) }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ include <type_traits> });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(sizeof(gsl::span<int, 18446744073709551615UL>) == 2 * sizeof(void*));
+                static_assert(alignof(gsl::span<int, 18446744073709551615UL>) == alignof(void*));
+                static_assert(std::is_trivially_copyable_v<gsl::span<int, 18446744073709551615UL>>);
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z9TakesSpanN3gsl4spanIiLm18446744073709551615EEE(
+                    gsl::span<int, 18446744073709551615UL> s) {
+                    TakesSpan(s);
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub fn ReturnsOwner() -> *mut ::core::ffi::c_int });
+        assert_rs_matches!(rs_api, quote! { pub unsafe fn TakesCzstring(s: crate::gsl::czstring) });
+        Ok(())
+    }
+
+    #[test]
+    fn test_simd_types() -> Result<()> {
+        if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
+            return Ok(());
+        }
+        let ir = ir_from_cc(
+            r#"typedef float __m128 __attribute__((__vector_size__(16), __aligned__(16)));
+            typedef long long __m128i __attribute__((__vector_size__(16), __aligned__(16)));
+            struct Vec4 final {
+              __m128 v;
+              __m128i mask;
+            };
+            __m128 Scale(__m128 v, float factor);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Vec4 {
+                    pub v: ::core::arch::x86_64::__m128,
+                    pub mask: ::core::arch::x86_64::__m128i,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(::core::mem::size_of::<crate::Vec4>() == 32);
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Scale(mut v: ::core::arch::x86_64::__m128, factor: f32)
+                    -> ::core::arch::x86_64::__m128
+            }
+        );
+        // Vectors are not passed by value to the thunks, because their calling convention
+        // depends on the enabled target features.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z5ScaleDv4_ff(
+                    __m128* __return, __m128* v, float factor) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    new (__return) auto(Scale(std::move(*v), factor));
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_half_float_types() -> Result<()> {
+        // `__bf16` is only available on Arm with the `bf16` target feature.
+        if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
+            return Ok(());
+        }
+        let ir = ir_from_cc(
+            r#"struct Activation final {
+              _Float16 f16;
+              __bf16 bf16;
+            };
+            _Float16 Halve(_Float16 x);
+            void Store(__fp16* out, float value);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Activation {
+                    pub f16: ::half_float::F16,
+                    pub bf16: ::half_float::Bf16,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn Halve(mut x: ::half_float::F16) -> ::half_float::F16 }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn Store(out: *mut ::half_float::F16, value: f32) }
+        );
+        // Half-precision floats are not passed by value to the thunks, because
+        // C++ passes them in floating point registers.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z5HalveDF16_(
+                    _Float16* __return, _Float16* x) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    new (__return) auto(Halve(std::move(*x)));
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_double_converted_to_f64() -> Result<()> {
+        // `--long_double=f64` binds `long double Scale(long double x)` as if it
+        // took and returned `double`, but calls it through a thunk.
+        let mut ir = ir_from_cc("double Scale(double x);")?;
+        for item in ir.items_mut() {
+            if let Item::Func(func) = item {
+                Rc::make_mut(func).converts_long_double = true;
+            }
+        }
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn Scale(x: f64) -> f64 {
+                    unsafe { crate::detail::__rust_thunk___Z5Scaled(x) }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" double __rust_thunk___Z5Scaled(double x) {
+                    return Scale(x);
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_string() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            template <typename T> struct char_traits {};
+            template <typename T> struct allocator {};
+            template <typename CharT, typename Traits = char_traits<CharT>,
+                      typename Alloc = allocator<CharT>>
+            class basic_string {
+             public:
+              basic_string(const basic_string&);
+              ~basic_string();
+             private:
+              CharT* data_;
+              unsigned long size_;
+              unsigned long capacity_;
+            };
+            using string = basic_string<char>;
+            }  // namespace std
+            std::string Greet(const std::string& name);
+            void Consume(std::string s);
+            struct Person final {
+              std::string name;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Greet<'a>(name: &'a ::cxx_string::CxxString) -> ::cxx_string::CxxString {
+                    unsafe {
+                        let mut __return =
+                            ::core::mem::MaybeUninit::<::cxx_string::CxxString>::uninit();
+                        ...
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub fn Consume(mut s: ::cxx_string::CxxString) });
+        assert_cc_matches!(rs_api_impl, quote! { new (__return) auto(Greet(*name)); });
+        assert_cc_matches!(rs_api_impl, quote! { Consume(std::move(*s)); });
+
+        // `CxxString` destroys the C++ string on drop, so it isn't destroyed a second
+        // time by the destructor of `Person`.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Person {
+                    pub name: ::core::mem::ManuallyDrop<::cxx_string::CxxString>,
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub type string = ::cxx_string::CxxString; });
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_view() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"namespace std {
+            template <typename T> struct char_traits {};
+            template <typename CharT, typename Traits = char_traits<CharT>>
+            class basic_string_view {
+             private:
+              const CharT* data_;
+              unsigned long size_;
+            };
+            using string_view = basic_string_view<char>;
+            }  // namespace std
+            std::string_view Trim(std::string_view s);
+            struct Flag final {
+              std::string_view name;
+              void SetName(std::string_view new_name);
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        // Without lifetimes, the returned view can only be read unsafely.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Trim(
+                    s: &(impl ::core::convert::AsRef<[u8]> + ?Sized)
+                ) -> ::cxx_string::CxxStringView {
+                    unsafe {
+                        crate::detail::...(::cxx_string::CxxStringView::from(s.as_ref()))
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn SetName(
+                    __this: *mut Self,
+                    new_name: &(impl ::core::convert::AsRef<[u8]> + ?Sized)
+                ) {
+                    crate::detail::...(
+                        __this,
+                        ::cxx_string::CxxStringView::from(new_name.as_ref())
+                    )
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Flag {
+                    pub name: ::cxx_string::CxxStringView,
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub type string_view = ::cxx_string::CxxStringView; });
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_view_with_lifetimes() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace absl {
+            class [[clang::annotate("lifetime_params", "s")]] string_view {
+             private:
+              const char* data_;
+              unsigned long size_;
+            };
+            }  // namespace absl
+            absl::string_view Trim(absl::string_view s);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Trim<'a>(s: &'a (impl ::core::convert::AsRef<[u8]> + ?Sized)) -> &'a [u8] {
+                    unsafe {
+                        ::cxx_string::CxxStringView::as_bytes(crate::detail::...(
+                            ::cxx_string::CxxStringView::from(s.as_ref())
+                        ))
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn ...(s: ::cxx_string::CxxStringView) -> ::cxx_string::CxxStringView;
+            }
+        );
+        assert_cc_not_matches!(rs_api_impl, quote! { Trim });
+        Ok(())
+    }
+
+    #[test]
+    fn test_absl_span() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"namespace absl {
+            template <typename T>
+            class Span {
+             private:
+              T* ptr_;
+              unsigned long len_;
+            };
+            }  // namespace absl
+            struct Point final {
+              int x;
+              int y;
+            };
+            int Sum(absl::Span<const int> values);
+            void Fill(absl::Span<Point> points);
+            absl::Span<const int> Evens(absl::Span<const int> values);
+            struct Buffer final {
+              absl::Span<const char> data;
+              void Append(absl::Span<const char> bytes);
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Sum(values: &[::core::ffi::c_int]) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::...(::absl_span::Span::from(values)) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Fill(points: &mut [crate::Point]) {
+                    unsafe { crate::detail::...(::absl_span::SpanMut::from(points)) }
+                }
+            }
+        );
+        // Without lifetimes, the returned span can only be read unsafely.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Evens(values: &[::core::ffi::c_int]) -> ::absl_span::Span<::core::ffi::c_int>
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn Append(__this: *mut Self, bytes: &[::core::ffi::c_char]) {
+                    crate::detail::...(__this, ::absl_span::Span::from(bytes))
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Buffer {
+                    pub data: ::absl_span::Span<::core::ffi::c_char>,
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_absl_span_with_lifetimes() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace absl {
+            template <typename T>
+            class [[clang::annotate("lifetime_params", "s")]] Span {
+             private:
+              T* ptr_;
+              unsigned long len_;
+            };
+            }  // namespace absl
+            absl::Span<const int> Evens(absl::Span<const int> values);
+            absl::Span<int> Tail(absl::Span<int> values);"#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Evens<'a>(values: &'a [::core::ffi::c_int]) -> &'a [::core::ffi::c_int] {
+                    unsafe {
+                        ::absl_span::Span::as_slice(crate::detail::...(
+                            ::absl_span::Span::from(values)
+                        ))
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Tail<'a>(values: &'a mut [::core::ffi::c_int])
+                    -> &'a mut [::core::ffi::c_int] {
+                    unsafe {
+                        ::absl_span::SpanMut::as_mut_slice(crate::detail::...(
+                            ::absl_span::SpanMut::from(values)
+                        ))
+                    }
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_vector() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            template <typename T> struct allocator {};
+            template <typename T, typename Alloc = allocator<T>>
+            class vector {
+             public:
+              vector(const vector&);
+              ~vector();
+             private:
+              T* begin_;
+              T* end_;
+              T* end_cap_;
+            };
+            }  // namespace std
+            struct Point final {
+              int x;
+              int y;
+            };
+            struct Polygon final {
+              std::vector<Point> points;
+            };
+            int Sum(const std::vector<int>& values);
+            void AddOrigin(std::vector<Point>& points);
+            std::vector<int> Copy(const std::vector<int>& values);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Sum<'a>(values: &'a ::cxx_vector::CxxVector<::core::ffi::c_int>)
+                    -> ::core::ffi::c_int
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn AddOrigin<'a>(points: &'a mut ::cxx_vector::CxxVector<crate::Point>)
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Polygon {
+                    pub points: ::cxx_vector::CxxVector<crate::Point>,
+                }
+            }
+        );
+        // Vectors are owned by C++, so they can't be returned by value.
+        assert_rs_not_matches!(rs_api, quote! { pub fn Copy });
+
+        // `Point` is used as the element type of vectors, so elements can be pushed onto them.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                unsafe impl ::cxx_vector::VectorElement for crate::Point {
+                    unsafe fn push_back(
+                        v: &mut ::cxx_vector::CxxVector<Self>,
+                        value: &mut Self
+                    ) {
+                        crate::detail::__crubit_vector_push_back__5Point(v, value)
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_vector_push_back__5Point(
+                    std::vector<struct Point>* v, struct Point* value
+                ) {
+                    v->push_back(std::move(*value));
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { VectorElement for crate::Polygon });
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_optional() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            template <typename T>
+            class optional {
+             public:
+              optional(const optional&);
+              ~optional();
+             private:
+              union { char null_state_; T value_; };
+              bool engaged_;
+            };
+            }  // namespace std
+            struct Point final {
+              int x;
+              int y;
+            };
+            struct Pinned {
+              Pinned(Pinned&&);
+              ~Pinned();
+            };
+            struct Config final {
+              std::optional<int> limit;
+              void SetOrigin(std::optional<Point> origin);
+            };
+            std::optional<int> Find(int key);
+            int ValueOr(const std::optional<int>& value, int fallback);
+            void TakePinned(std::optional<Pinned> pinned);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Find(key: ::core::ffi::c_int) -> ::core::option::Option<::core::ffi::c_int> {
+                    unsafe {
+                        let mut __return = ::core::mem::MaybeUninit::<
+                            ::cxx_optional::CxxOptional<::core::ffi::c_int>
+                        >::uninit();
+                        crate::detail::...(&mut __return, key);
+                        ::cxx_optional::CxxOptional::into_option(__return.assume_init())
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn SetOrigin<'a>(
+                    &'a mut self,
+                    origin: ::core::option::Option<crate::Point>
+                ) {
+                    unsafe {
+                        crate::detail::...(self, &mut ::cxx_optional::CxxOptional::from(origin))
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { new (__return) auto(Find(key)); });
+        assert_cc_matches!(rs_api_impl, quote! { __this->SetOrigin(std::move(*origin)); });
+
+        // References to optionals, and fields, keep the `CxxOptional` type.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn ValueOr<'a>(
+                    value: &'a ::cxx_optional::CxxOptional<::core::ffi::c_int>,
+                    fallback: ::core::ffi::c_int
+                ) -> ::core::ffi::c_int
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Config {
+                    pub limit: ::core::mem::ManuallyDrop<
+                        ::cxx_optional::CxxOptional<::core::ffi::c_int>
+                    >,
+                }
+            }
+        );
+
+        // Rust can't move the values of optionals of non-Unpin types.
+        assert_rs_not_matches!(rs_api, quote! { pub fn TakePinned });
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_unique_ptr() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"namespace std {
+            template <typename T> struct default_delete {};
+            template <typename T, typename Deleter = default_delete<T>>
+            class unique_ptr {
+             public:
+              unique_ptr(unique_ptr&&);
+              ~unique_ptr();
+             private:
+              T* ptr_;
+            };
+            }  // namespace std
+            struct Widget final {
+              int id;
+            };
+            struct Node final {
+              std::unique_ptr<Node> next;
+            };
+            struct Undeletable final {
+              ~Undeletable() = delete;
+            };
+            std::unique_ptr<Widget> MakeWidget(int id);
+            void Consume(std::unique_ptr<Widget> widget);
+            std::unique_ptr<int> MakeInt();
+            std::unique_ptr<Undeletable> MakeUndeletable();"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn MakeWidget(id: ::core::ffi::c_int)
+                    -> ::cxx_unique_ptr::UniquePtr<crate::Widget> {
+                    unsafe {
+                        let mut __return = ::core::mem::MaybeUninit::<
+                            ::cxx_unique_ptr::UniquePtr<crate::Widget>
+                        >::uninit();
+                        ...
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn Consume(mut widget: ::cxx_unique_ptr::UniquePtr<crate::Widget>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn MakeInt() -> ::cxx_unique_ptr::UniquePtr<::core::ffi::c_int> }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { new (__return) auto(MakeWidget(id)); });
+        assert_cc_matches!(rs_api_impl, quote! { Consume(std::move(*widget)); });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Node {
+                    pub next: ::core::mem::ManuallyDrop<::cxx_unique_ptr::UniquePtr<crate::Node>>,
+                }
+            }
+        );
+
+        // `Widget` and `Node` are the pointee type of `std::unique_ptr`s, so they can be deleted.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                unsafe impl ::cxx_unique_ptr::UniquePtrTarget for crate::Widget {
+                    unsafe fn delete(ptr: *mut Self) {
+                        crate::detail::__crubit_unique_ptr_delete__6Widget(ptr)
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_unique_ptr_delete__6Widget(struct Widget* ptr) {
+                    delete ptr;
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { UniquePtrTarget for crate::Node });
+
+        // `Undeletable` can't be deleted, so pointers to it are not supported.
+        assert_rs_not_matches!(rs_api, quote! { UniquePtrTarget for crate::Undeletable });
+        assert_rs_not_matches!(rs_api, quote! { pub fn MakeUndeletable });
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_shared_ptr() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            template <typename T>
+            class shared_ptr {
+             public:
+              shared_ptr(const shared_ptr&);
+              ~shared_ptr();
+             private:
+              T* ptr_;
+              void* control_block_;
+            };
+            }  // namespace std
+            struct Config final {
+              int verbosity;
+            };
+            struct Service final {
+              std::shared_ptr<Config> config;
+            };
+            std::shared_ptr<Config> LoadConfig();
+            void Share(const std::shared_ptr<Config>& config);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn LoadConfig() -> ::cxx_shared_ptr::SharedPtr<crate::Config> {
+                    unsafe {
+                        let mut __return = ::core::mem::MaybeUninit::<
+                            ::cxx_shared_ptr::SharedPtr<crate::Config>
+                        >::uninit();
+                        ...
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn Share<'a>(config: &'a ::cxx_shared_ptr::SharedPtr<crate::Config>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Service {
+                    pub config: ::core::mem::ManuallyDrop<
+                        ::cxx_shared_ptr::SharedPtr<crate::Config>
+                    >,
+                }
+            }
+        );
+
+        // `Config` is the pointee type of `std::shared_ptr`s, so they can be cloned and dropped.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                unsafe impl ::cxx_shared_ptr::SharedPtrTarget for crate::Config {
+                    unsafe fn copy_shared_ptr(
+                        dest: *mut ::cxx_shared_ptr::SharedPtr<Self>,
+                        src: &::cxx_shared_ptr::SharedPtr<Self>,
+                    ) {
+                        crate::detail::__crubit_shared_ptr_copy__6Config(dest, src)
+                    }
+                    unsafe fn destroy_shared_ptr(ptr: *mut ::cxx_shared_ptr::SharedPtr<Self>) {
+                        crate::detail::__crubit_shared_ptr_destroy__6Config(ptr)
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_shared_ptr_copy__6Config(
+                    std::shared_ptr<struct Config>* dest,
+                    const std::shared_ptr<struct Config>* src
+                ) {
+                    crubit::construct_at(dest, *src);
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_shared_ptr_destroy__6Config(
+                    std::shared_ptr<struct Config>* ptr
+                ) {
+                    std::destroy_at(ptr);
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { SharedPtrTarget for crate::Service });
+        Ok(())
+    }
+
+    #[test]
+    fn test_absl_status() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace absl {
+            class Status {
+             public:
+              Status(const Status&);
+              ~Status();
+             private:
+              unsigned long rep_;
+            };
+            template <typename T>
+            class StatusOr {
+             public:
+              StatusOr(const StatusOr&);
+              ~StatusOr();
+             private:
+              Status status_;
+              union { T data_; };
+            };
+            }  // namespace absl
+            struct Config final {
+              int verbosity;
+            };
+            absl::Status Save(int value);
+            absl::StatusOr<int> Parse(int value);
+            absl::StatusOr<Config> Load();
+            void Report(const absl::Status& status);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Save(value: ::core::ffi::c_int)
+                    -> ::core::result::Result<(), ::absl_status::StatusError> {
+                    unsafe {
+                        let mut __return = ::core::mem::MaybeUninit::<::absl_status::Status>::uninit();
+                        crate::detail::...(&mut __return, value);
+                        ::absl_status::Status::into_result(__return.assume_init())
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Parse(value: ::core::ffi::c_int)
+                    -> ::core::result::Result<::core::ffi::c_int, ::absl_status::StatusError> {
+                    unsafe {
+                        let mut __return = ::core::mem::MaybeUninit::<
+                            ::absl_status::StatusOr<::core::ffi::c_int>
+                        >::uninit();
+                        crate::detail::...(&mut __return, value);
+                        ::absl_status::StatusOr::into_result(__return.assume_init())
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Load() -> ::core::result::Result<crate::Config, ::absl_status::StatusError>
+            }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { new (__return) auto(Save(value)); });
+
+        // Statuses that aren't returned by value keep the `Status` type.
+        assert_rs_matches!(rs_api, quote! { pub fn Report<'a>(status: &'a ::absl_status::Status) });
+        Ok(())
+    }
+
+    #[test]
+    fn test_complex_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"namespace std {
+            template <typename T>
+            class complex {
+             private:
+              T re_, im_;
+            };
+            }  // namespace std
+            struct Signal final {
+              _Complex double sample;
+              std::complex<float> gain;
+            };
+            std::complex<double> Conjugate(std::complex<double> c);
+            void Fft(_Complex float* data, int size);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Signal {
+                    pub sample: ::complex::Complex<f64>,
+                    pub gain: ::complex::Complex<f32>,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Conjugate(mut c: ::complex::Complex<f64>) -> ::complex::Complex<f64>
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn Fft(data: *mut ::complex::Complex<f32>, size: ::core::ffi::c_int) }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z9ConjugateSt7complexIdE(
+                    std::complex<double>* __return, std::complex<double>* c) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    new (__return) auto(Conjugate(std::move(*c)));
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errno_wrapper() -> Result<()> {
+        let mut ir = ir_from_cc(
+            r#"namespace posix {
+            int close(int fd);
+            void* mmap(void* addr, unsigned long length);
+            void* _Nonnull sbrk(long increment);
+            }  // namespace posix"#,
+        )?;
+        for item in ir.items_mut() {
+            if let Item::Func(func) = item {
+                Rc::make_mut(func).sets_errno = true;
+            }
+        }
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " Calls `close`, and returns the `errno` that it sets if it returns -1."]
+                #[inline(always)]
+                pub fn try_close(fd: ::core::ffi::c_int)
+                    -> ::core::result::Result<::core::ffi::c_int, ::core::ffi::c_int> {
+                    let mut __errno: ::core::ffi::c_int = 0;
+                    let __result = unsafe {
+                        crate::detail::__rust_thunk___ZN5posix5closeEi__errno(fd, &mut __errno)
+                    };
+                    if __result == -1 {
+                        ::core::result::Result::Err(__errno)
+                    } else {
+                        ::core::result::Result::Ok(__result)
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn try_mmap(addr: *mut ::core::ffi::c_void, length: ::core::ffi::c_ulong)
+                    -> ::core::result::Result<*mut ::core::ffi::c_void, ::core::ffi::c_int> {
+                    let mut __errno: ::core::ffi::c_int = 0;
+                    let __result = crate::detail::__rust_thunk___ZN5posix4mmapEPvm__errno(
+                        addr, length, &mut __errno);
+                    if __result.is_null() { ... } else { ... }
+                }
+            }
+        );
+        // The thunk returns a raw pointer, which is only converted into a `NonNull`
+        // if it isn't null.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn try_sbrk(increment: ::core::ffi::c_long)
+                    -> ::core::result::Result<::core::ptr::NonNull<::core::ffi::c_void>,
+                                              ::core::ffi::c_int> {
+                    ...
+                    if __result.is_null() {
+                        ::core::result::Result::Err(__errno)
+                    } else {
+                        ::core::result::Result::Ok(::core::ptr::NonNull::new(__result).expect(...))
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___ZN5posix4sbrkEl__errno(
+                    increment: ::core::ffi::c_long, __errno: &mut ::core::ffi::c_int
+                ) -> *mut ::core::ffi::c_void;
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___ZN5posix5closeEi__errno(
+                    fd: ::core::ffi::c_int, __errno: &mut ::core::ffi::c_int
+                ) -> ::core::ffi::c_int;
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { extern crate std; });
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ include <cerrno> });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZN5posix5closeEi__errno(int fd, int* __errno) {
+                    errno = 0;
+                    int __result = posix::close(fd);
+                    *__errno = errno;
+                    return __result;
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errno_wrapper_std_io_error() -> Result<()> {
+        let mut ir = ir_from_cc("int close(int fd);")?;
+        for item in ir.items_mut() {
+            if let Item::Func(func) = item {
+                Rc::make_mut(func).sets_errno = true;
+            }
+        }
+        let rs_api = super::generate_bindings_tokens(
+            Rc::new(ir),
+            "crubit/rs_bindings_support",
+            Rc::new(IgnoreErrors),
+            CodegenOptions {
+                generate_source_loc_doc_comment: SourceLocationDocComment::Disabled,
+                errno_std_io_error: true,
+                ..Default::default()
+            },
+        )?
+        .rs_api;
+        assert_rs_matches!(rs_api, quote! { extern crate std; });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn try_close(fd: ::core::ffi::c_int)
+                    -> ::core::result::Result<::core::ffi::c_int, ::std::io::Error> {
+                    ...
+                    if __result == -1 {
+                        ::core::result::Result::Err(::std::io::Error::from_raw_os_error(__errno))
+                    } else {
+                        ::core::result::Result::Ok(__result)
+                    }
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errno_wrapper_unsupported_return_type() -> Result<()> {
+        let mut ir = ir_from_cc("unsigned int Read(int fd);")?;
+        for item in ir.items_mut() {
+            if let Item::Func(func) = item {
+                Rc::make_mut(func).sets_errno = true;
+            }
+        }
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { fn Read });
+        assert_rs_not_matches!(rs_api, quote! { fn try_Read });
+        Ok(())
+    }
+
+    #[test]
+    fn test_pointer_nullability_inference() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace gsl {
+            template <typename T>
+            class not_null {
+             public:
+              not_null(T ptr) : ptr_(ptr) {}
+             private:
+              T ptr_;
+            };
+            }  // namespace gsl
+            void TakesNotNull(gsl::not_null<int*> p);
+            void TakesNonnull(int* _Nonnull p);
+            void TakesNullDefault(int* _Nonnull p = nullptr);
+            void TakesPointer(int* p);"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn TakesNotNull<'a>(p: &'a mut ::core::ffi::c_int) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn TakesNonnull<'a>(p: &'a mut ::core::ffi::c_int) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn TakesNullDefault<'a>(p: Option<&'a mut ::core::ffi::c_int>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn TakesPointer<'a>(p: Option<&'a mut ::core::ffi::c_int>) }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nullability_annotations() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"namespace absl {
+            template <typename T>
+            using Nonnull [[clang::annotate("Nonnull")]] = T;
+            template <typename T>
+            using Nullable [[clang::annotate("Nullable")]] = T;
+            }  // namespace absl
+            void TakesNonnull(int* _Nonnull p);
+            void TakesNullable(const int* _Nullable p);
+            void TakesAbslNonnull(absl::Nonnull<int*> p);
+            void TakesAbslNullable(absl::Nullable<int*> p);
+            void TakesPointer(int* p);
+            int* _Nonnull ReturnsNonnull();
+
+            #pragma clang lifetime_elision
+            int* _Nonnull ReturnsNonnullRef(int& x);
+            int* ReturnsRef(int& x);"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // Without a lifetime, annotated pointers are bound as `NonNull<T>` or
+        // `Option<NonNull<T>>`, which are still unsafe to dereference.
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesNonnull(p: ::core::ptr::NonNull<::core::ffi::c_int>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn TakesNullable(p: Option<::core::ptr::NonNull<::core::ffi::c_int>>)
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesAbslNonnull(p: ::core::ptr::NonNull<::core::ffi::c_int>) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn TakesAbslNullable(
+                    p: Option<::core::ptr::NonNull<::core::ffi::c_int>>)
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn TakesPointer(p: *mut ::core::ffi::c_int) }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn ReturnsNonnull() -> ::core::ptr::NonNull<::core::ffi::c_int> }
+        );
+        // With a lifetime, they are bound as references.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn ReturnsNonnullRef<'a>(x: &'a mut ::core::ffi::c_int)
+                    -> &'a mut ::core::ffi::c_int
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn ReturnsRef<'a>(x: &'a mut ::core::ffi::c_int)
+                    -> Option<&'a mut ::core::ffi::c_int>
+            }
+        );
+        // C++ doesn't enforce `_Nonnull`, so returned pointers are checked.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                ::core::ptr::NonNull::new(crate::detail::__rust_thunk___Z14ReturnsNonnullv())
+                    .expect("C++ function annotated as returning `_Nonnull` returned a null pointer")
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                ::core::ptr::NonNull::new(crate::detail::__rust_thunk___Z17ReturnsNonnullRefRi(x))
+                    .expect("C++ function annotated as returning `_Nonnull` returned a null pointer")
+                    .as_mut()
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { fn __rust_thunk___Z14ReturnsNonnullv() -> *mut ::core::ffi::c_int; }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                fn __rust_thunk___Z17ReturnsNonnullRefRi<'a>(x: &'a mut ::core::ffi::c_int)
+                    -> *mut ::core::ffi::c_int;
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_ptr_where_params_are_primitive_types() -> Result<()> {
+        let ir = ir_from_cc(r#" int (*get_ptr_to_func())(float, double); "#)?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn get_ptr_to_func() -> Option<extern "C" fn (f32, f64) -> ::core::ffi::c_int> {
+                    unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                mod detail {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    extern "C" {
+                        #[link_name = "_Z15get_ptr_to_funcv"]
+                        pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
+                        -> Option<extern "C" fn(f32, f64) -> ::core::ffi::c_int>;
+                    }
+                }
+            }
+        );
+        // Verify that no C++ thunk got generated.
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
+
+        // TODO(b/217419782): Add another test for more exotic calling conventions /
+        // abis.
+
+        // TODO(b/276461979): Add another test for pointer to a function that requires
+        // thunks - e.g. because it takes/returns structs value. See also
+        // b/276461979 and <internal link>
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_ptr_fields() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            typedef void (*Callback)(int);
+            struct WithCallbacks final {
+              void (*callback)(int);
+              Callback typedefed_callback;
+            };
+            void SetCallback(Callback callback);
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub type Callback = Option<extern "C" fn(::core::ffi::c_int)>; }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct WithCallbacks {
+                    pub callback: Option<extern "C" fn(::core::ffi::c_int)>,
+                    // Typedefs of pointer types are expanded.
+                    pub typedefed_callback: Option<extern "C" fn(::core::ffi::c_int)>,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(memoffset::offset_of!(crate::WithCallbacks, callback) == 0);
+                const _: () = assert!(
+                    memoffset::offset_of!(crate::WithCallbacks, typedefed_callback) == 8
+                );
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn SetCallback(callback: Option<extern "C" fn(::core::ffi::c_int)>) { ... }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_member_pointer_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct S final {
+              int x;
+              void Method(int);
+            };
+            struct WithMemberPointers final {
+              int S::* field;
+              void (S::* method)(int);
+            };
+            int Read(const S& s, int S::* field);
+            void SetMethod(void (S::* method)(int));
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct WithMemberPointers {
+                    pub field: ::member_pointer::DataMemberPointer<crate::S, ::core::ffi::c_int>,
+                    pub method: ::member_pointer::MemberFunctionPointer<
+                        crate::S,
+                        extern "C" fn(*mut crate::S, ::core::ffi::c_int)
+                    >,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn Read<'a>(
+                    s: &'a crate::S,
+                    field: ::member_pointer::DataMemberPointer<crate::S, ::core::ffi::c_int>
+                ) -> ::core::ffi::c_int { ... }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn SetMethod(
+                    mut method: ::member_pointer::MemberFunctionPointer<
+                        crate::S,
+                        extern "C" fn(*mut crate::S, ::core::ffi::c_int)
+                    >
+                ) {
+                    ...
+                }
+            }
+        );
+        // Pointers to member functions are passed to C++ by pointer.
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z9SetMethodM1SFviE(
+                    crubit::type_identity_t<void (S::*)(int)>* method) {
+                    ...
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_member_function_pointer_call() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct NonTrivial final {
+              NonTrivial(const NonTrivial&);
+            };
+            struct S final {
+              int Get(int) const;
+              void Take(NonTrivial);
+            };
+            int Call(const S& s, int (S::* getter)(int) const);
+            int CallNoexcept(const S& s, int (S::* getter)(int) const noexcept);
+            void SetTaker(void (S::* taker)(NonTrivial));
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        // Pointers to member functions that only differ in `noexcept` share the
+        // implementation of `MemberFunctionSignature`.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                unsafe impl ::member_pointer::MemberFunctionSignature<crate::S>
+                    for extern "C" fn(*const crate::S, ::core::ffi::c_int) -> ::core::ffi::c_int
+                {
+                    type Args = (*const crate::S, ::core::ffi::c_int,);
+                    type Output = ::core::ffi::c_int;
+                    unsafe fn call(
+                        f: &::member_pointer::MemberFunctionPointer<crate::S, Self>,
+                        (__param_0, __param_1,): Self::Args,
+                    ) -> Self::Output {
+                        crate::detail::__crubit_call_member_function__1S_0(f, __param_0, __param_1)
+                    }
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { __crubit_call_member_function__1S_1 });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" crubit::MemberFunctionResult<
+                    crubit::type_identity_t<int (S::*)(int) const>
+                > __crubit_call_member_function__1S_0(
+                    const crubit::type_identity_t<int (S::*)(int) const>* f,
+                    crubit::MemberFunctionParam<
+                        crubit::type_identity_t<int (S::*)(int) const>, 0
+                    > __param_0,
+                    crubit::MemberFunctionParam<
+                        crubit::type_identity_t<int (S::*)(int) const>, 1
+                    > __param_1
+                ) {
+                    return crubit::CallMemberFunction(*f, __param_0, __param_1);
+                }
+            }
+        );
+        // Member functions that take a struct that isn't passed like in C can't be
+        // called.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn SetTaker(
+                    mut taker: ::member_pointer::MemberFunctionPointer<crate::S, ()>
+                ) {
+                    ...
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_ref() -> Result<()> {
+        let ir = ir_from_cc(r#" int (&get_ref_to_func())(float, double); "#)?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn get_ref_to_func() -> extern "C" fn (f32, f64) -> ::core::ffi::c_int {
+                    unsafe { crate::detail::__rust_thunk___Z15get_ref_to_funcv() }
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_ptr_with_non_static_lifetime() -> Result<()> {
+        let ir = ir_from_cc(&with_lifetime_macros(
+            r#"
+            int (* $a get_ptr_to_func())(float, double); "#,
+        ))?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_cc_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=33\n\
+                           Error while generating bindings for item 'get_ptr_to_func':\n\
+                           Type may not be annotated with lifetimes";
+            quote! { __COMMENT__ #txt }
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_ptr_where_params_are_raw_ptrs() -> Result<()> {
+        let ir = ir_from_cc(r#" const int* (*get_ptr_to_func())(const int*); "#)?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                mod detail { ...  extern "C" {
-                    ...
-                    pub(crate) fn
-                    __rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget<'a>(
-                        __this: ... Pin<&'a mut crate::__CcTemplateInst10MyTemplateIiE>
-                    ) -> ::core::ffi::c_int;
-                    ...
-                } }
+                #[inline(always)]
+                pub fn get_ptr_to_func() -> Option<extern "C" fn (*const ::core::ffi::c_int) -> *const ::core::ffi::c_int> {
+                    unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
+                }
             }
         );
-        assert_cc_matches!(
-            rs_api_impl,
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C"
-                int __rust_thunk___ZN10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(
-                        struct MyTemplate<int>* __this) {
-                    return __this->GetValue();
+                mod detail {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    extern "C" {
+                        #[link_name = "_Z15get_ptr_to_funcv"]
+                        pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
+                        -> Option<extern "C" fn(*const ::core::ffi::c_int) -> *const ::core::ffi::c_int>;
+                    }
                 }
             }
         );
+        // Verify that no C++ thunk got generated.
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
+
+        // TODO(b/217419782): Add another test where params (and the return
+        // type) are references with lifetimes.  Something like this:
+        //     #pragma clang lifetime_elision
+        //     const int& (*get_ptr_to_func())(const int&, const int&); "#)?;
+        // 1) Need to investigate why this fails - seeing raw pointers in Rust
+        //    seems to indicate that no lifetimes are present at the `importer.cc`
+        //    level. Maybe lifetime elision doesn't support this scenario? Unclear
+        //    how to explicitly apply [[clang::annotate("lifetimes", "a, b -> a")]]
+        //    to the _inner_ function.
+        // 2) It is important to have 2 reference parameters, so see if the problem
+        //    of passing `lifetimes` by value would have been caught - see:
+        //    cl/428079010/depot/rs_bindings_from_cc/
+        // importer.cc?version=s6#823
+
+        // TODO(b/217419782): Decide what to do if the C++ pointer is *not*
+        // annotated with a lifetime - emit `unsafe fn(...) -> ...` in that
+        // case?
 
         Ok(())
     }
 
-    #[test]
-    fn test_template_with_out_of_line_definition() -> Result<()> {
-        // See also an end-to-end test in the `test/templates/out_of_line_definition`
-        // directory.
-        let ir = ir_from_cc(
-            r#" #pragma clang lifetime_elision
-                template <typename T>
-                class MyTemplate final {
-                 public:
-                  static MyTemplate Create(T value);
-                  const T& value() const;
+    mod custom_abi_tests {
+        use super::*;
+        use ir_matchers::assert_ir_matches;
+        #[test]
+        fn test_func_ptr_with_custom_abi() -> Result<()> {
+            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
+                return Ok(());
+            }
+            let ir =
+                ir_from_cc(r#" int (*get_ptr_to_func())(float, double) [[clang::vectorcall]]; "#)?;
 
-                 private:
-                  T value_;
-                };
+            // Verify that the test input correctly represents what we intend to
+            // test - we want [[clang::vectorcall]] to apply to the returned
+            // function pointer, but *not* apply to the `get_ptr_to_func` function.
+            assert_ir_matches!(
+                ir,
+                quote! {
+                    Func(Func {
+                        name: "get_ptr_to_func", ...
+                        return_type: MappedType {
+                            rs_type: RsType {
+                                name: Some("Option"), ...
+                                type_args: [RsType { name: Some("#funcPtr vectorcall"), ... }], ...
+                            },
+                            cc_type: CcType {
+                                name: Some("*"), ...
+                                type_args: [CcType { name: Some("#funcValue vectorcall"), ... }], ...
+                            },
+                        }, ...
+                        has_c_calling_convention: true, ...
+                    }),
+                }
+            );
 
-                using MyTypeAlias = MyTemplate<int>; "#,
-        )?;
+            let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+            // Check that the custom "vectorcall" ABI gets propagated into the
+            // return type (i.e. into `extern "vectorcall" fn`).
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    #[inline(always)]
+                    pub fn get_ptr_to_func() -> Option<extern "vectorcall" fn (f32, f64) -> ::core::ffi::c_int> {
+                        unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
+                    }
+                }
+            );
 
-        let BindingsTokens { rs_api_impl, .. } = generate_bindings_tokens(ir)?;
+            // The usual `extern "C"` ABI should be used for "get_ptr_to_func".
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    mod detail {
+                        #[allow(unused_imports)]
+                        use super::*;
+                        extern "C" {
+                            #[link_name = "_Z15get_ptr_to_funcv"]
+                            pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
+                            -> Option<extern "vectorcall" fn(f32, f64) -> ::core::ffi::c_int>;
+                        }
+                    }
+                }
+            );
 
-        // Even though the member functions above are *not* defined inline (e.g.
-        // IR::Func::is_inline is false), they still need to have thunks generated for
-        // them (to force/guarantee that the class template and its members get
-        // instantiated).  This is also covered in the following end-to-end
-        // tests:
-        // - test/templates/out_of_line_definition/ - without a thunk, the template
-        //   won't be instantiated and Rust bindings won't be able to call the member
-        //   function (there will be no instantiation of the member function in the C++
-        //   object files)
-        // - test/templates/definition_in_cc/ - the instantiation happens in the .cc
-        //   file and therefore the thunk is not *required* (but it doesn't hurt to have
-        //   the thunk)
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void
-                __rust_thunk___ZN10MyTemplateIiE6CreateEi__2f_2ftest_3atesting_5ftarget(
-                    class MyTemplate<int>* __return, int value) {
-                  new (__return) auto(MyTemplate<int>::Create(value));
+            // Verify that no C++ thunk got generated.
+            assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
+            Ok(())
+        }
+
+        #[test]
+        fn test_func_ptr_with_custom_abi_thunk() -> Result<()> {
+            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
+                return Ok(());
+            }
+            // Using an `inline` keyword forces generation of a C++ thunk in
+            // `rs_api_impl` (i.e. exercises `format_cc_type`,
+            // `format_cc_call_conv_as_clang_attribute` and similar code).
+            let ir = ir_from_cc(
+                r#"
+                inline int (*inline_get_ptr_to_func())(float, double) [[clang::vectorcall]];
+            "#,
+            )?;
+
+            // Verify that the test input correctly represents what we intend to
+            // test - we want [[clang::vectorcall]] to apply to the returned
+            // function pointer, but *not* apply to the `get_ptr_to_func` function.
+            assert_ir_matches!(
+                ir,
+                quote! {
+                    Func(Func {
+                        name: "inline_get_ptr_to_func", ...
+                        return_type: MappedType {
+                            rs_type: RsType {
+                                name: Some("Option"), ...
+                                type_args: [RsType { name: Some("#funcPtr vectorcall"), ... }], ...
+                            },
+                            cc_type: CcType {
+                                name: Some("*"), ...
+                                type_args: [CcType { name: Some("#funcValue vectorcall"), ... }], ...
+                            },
+                        }, ...
+                        has_c_calling_convention: true, ...
+                    }),
+                }
+            );
+
+            // This test is quite similar to `test_func_ptr_thunk` - the main
+            // difference is verification of the `__attribute__((vectorcall))` in
+            // the expected signature of the generated thunk below.
+            let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+            assert_cc_matches!(
+                rs_api_impl,
+                quote! {
+                    extern "C" crubit::type_identity_t<
+                            int(float , double) __attribute__((vectorcall))
+                        >* __rust_thunk___Z22inline_get_ptr_to_funcv() {
+                        return inline_get_ptr_to_func();
+                    }
+                }
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn test_custom_abi_thunk() -> Result<()> {
+            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
+                return Ok(());
+            }
+            let ir = ir_from_cc(
+                r#"
+                float f_vectorcall_calling_convention(float p1, float p2) [[clang::vectorcall]];
+                double f_c_calling_convention(double p1, double p2);
+            "#,
+            )?;
+            let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    #[inline(always)]
+                    pub fn f_vectorcall_calling_convention(p1: f32, p2: f32) -> f32 {
+                        unsafe {
+                            crate::detail::__rust_thunk___Z31f_vectorcall_calling_conventionff(p1, p2)
+                        }
+                    }
+                }
+            );
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    #[inline(always)]
+                    pub fn f_c_calling_convention(p1: f64, p2: f64) -> f64 {
+                        unsafe { crate::detail::__rust_thunk___Z22f_c_calling_conventiondd(p1, p2) }
+                    }
+                }
+            );
+            // `link_name` (i.e. no thunk) for `f_c_calling_convention`. No
+            // `link_name` (i.e. indicates presence of a thunk) for
+            // `f_vectorcall_calling_convention`.
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    mod detail {
+                        #[allow(unused_imports)]
+                        use super::*;
+                        extern "C" {
+                            pub(crate) fn __rust_thunk___Z31f_vectorcall_calling_conventionff(
+                                p1: f32, p2: f32) -> f32;
+                            #[link_name = "_Z22f_c_calling_conventiondd"]
+                            pub(crate) fn __rust_thunk___Z22f_c_calling_conventiondd(
+                                p1: f64, p2: f64) -> f64;
+                        }
+                    }
                 }
-            }
-        );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" int const*
-                __rust_thunk___ZNK10MyTemplateIiE5valueEv__2f_2ftest_3atesting_5ftarget(
-                        const class MyTemplate<int>*__this) {
-                    return &__this->value();
+            );
+            // C++ thunk needed for `f_vectorcall_calling_convention`.
+            assert_cc_matches!(
+                rs_api_impl,
+                quote! {
+                    extern "C" float __rust_thunk___Z31f_vectorcall_calling_conventionff(
+                        float p1, float p2) {
+                            return f_vectorcall_calling_convention(p1, p2);
+                    }
                 }
-            }
-        );
-        Ok(())
+            );
+            // No C++ thunk expected for `f_c_calling_convention`.
+            assert_cc_not_matches!(rs_api_impl, quote! { f_c_calling_convention });
+            Ok(())
+        }
     }
 
     #[test]
-    fn test_simple_struct() -> Result<()> {
+    fn test_func_ptr_thunk() -> Result<()> {
+        // Using an `inline` keyword forces generation of a C++ thunk in
+        // `rs_api_impl` (i.e. exercises `format_cc_type` and similar code).
         let ir = ir_from_cc(
             r#"
-            #pragma clang lifetime_elision
-            struct SomeStruct final {
-                ~SomeStruct() {}
-                int public_int;
-              protected:
-                int protected_int;
-              private:
-               int private_int;
-            };
+            int multiply(int x, int y);
+            inline int (*inline_get_pointer_to_function())(int, int) {
+                return multiply;
+            }
         "#,
         )?;
-
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[::ctor::recursively_pinned(PinnedDrop)]
-                #[repr(C, align(4))]
-                pub struct SomeStruct {
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 0],
-                    pub public_int: ::core::ffi::c_int,
-                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
-                    pub(crate) protected_int: [::core::mem::MaybeUninit<u8>; 4],
-                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
-                    pub(crate) private_int: [::core::mem::MaybeUninit<u8>; 4],
-                }
-            }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                const _: () = assert!(::core::mem::size_of::<crate::SomeStruct>() == 12);
-                const _: () = assert!(::core::mem::align_of::<crate::SomeStruct>() == 4);
-                const _: () = { static_assertions::assert_not_impl_any!(crate::SomeStruct: Copy); };
-                const _: () = { static_assertions::assert_impl_all!(crate::SomeStruct: Drop); };
-                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, public_int) == 0);
-                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, protected_int) == 4);
-                const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, private_int) == 8);
-            }
-        );
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
         assert_cc_matches!(
             rs_api_impl,
             quote! {
-                extern "C" void __rust_thunk___ZN10SomeStructD1Ev(struct SomeStruct * __this) {
-                    std::destroy_at(__this);
+                extern "C" crubit::type_identity_t<int(int , int)>*
+                __rust_thunk___Z30inline_get_pointer_to_functionv() {
+                    return inline_get_pointer_to_function();
                 }
             }
         );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 12);
-                static_assert(alignof(struct SomeStruct) == 4);
-                static_assert(CRUBIT_OFFSET_OF(public_int, struct SomeStruct) == 0);
-            }
-        );
-        Ok(())
-    }
-
-    #[test]
-    fn test_struct_vs_class() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"
-            #pragma clang lifetime_elision
-            struct SomeStruct final {
-                SomeStruct() {}
-                int field;
-            };
-            class SomeClass final {
-              public:
-                SomeClass() {}
-                int field;
-            };
-        "#,
-        )?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-
-        // A Rust `struct` is generated for both `SomeStruct` and `SomeClass`.
-        assert_rs_matches!(rs_api, quote! { pub struct SomeStruct },);
-        assert_rs_matches!(rs_api, quote! { pub struct SomeClass },);
-
-        // But in C++ we still should refer to `struct SomeStruct` and `class
-        // SomeClass`. See also b/238212337.
-        assert_cc_matches!(rs_api_impl, quote! { struct SomeStruct * __this });
-        assert_cc_matches!(rs_api_impl, quote! { class SomeClass * __this });
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 4); }
-        );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_SIZEOF(class SomeClass) == 4); }
-        );
-        Ok(())
-    }
-
-    #[test]
-    fn test_struct_vs_typedefed_struct() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"
-            #pragma clang lifetime_elision
-            struct SomeStruct final {
-              int x;
-            } __attribute__((aligned(16)));
-            typedef struct {
-              int x;
-            } SomeAnonStruct __attribute__((aligned(16)));
-        "#,
-        )?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-
-        // A `struct` is generated for both `SomeStruct` and `SomeAnonStruct`, both
-        // in Rust and in C++.
-        assert_rs_matches!(rs_api, quote! { pub struct SomeStruct },);
-        assert_rs_matches!(rs_api, quote! { pub struct SomeAnonStruct },);
-        assert_rs_matches!(rs_api_impl, quote! { struct SomeStruct * __this },);
-        assert_rs_matches!(rs_api_impl, quote! { SomeAnonStruct * __this },);
-
-        // In C++, both have align == 16, but size for `SomeAnonStruct` is not aligned.
-        // `SomeAnonStruct` won't have `struct` in the assert.
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(alignof(struct SomeStruct) == 16); }
-        );
-        assert_cc_matches!(rs_api_impl, quote! { static_assert(alignof(SomeAnonStruct) == 16); });
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_SIZEOF(struct SomeStruct) == 16); }
-        );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_SIZEOF(SomeAnonStruct) == 16); }
-        );
-
-        // In Rust, both have align == 16 and size == 16.
-        assert_rs_matches!(
-            rs_api,
-            quote! { assert!(::core::mem::size_of::<crate::SomeStruct>() == 16); }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! { assert!(::core::mem::align_of::<crate::SomeStruct>() == 16); }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! { assert!(::core::mem::size_of::<crate::SomeAnonStruct>() == 16); }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! { assert!(::core::mem::align_of::<crate::SomeAnonStruct>() == 16); }
-        );
-
         Ok(())
     }
 
     #[test]
-    fn test_typedef_member() -> Result<()> {
+    fn test_item_order() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            struct SomeStruct final {
-              typedef int Type;
-            };
-            inline SomeStruct::Type Function() {return 0;}
-        "#,
+            "int first_func();
+             struct FirstStruct {};
+             int second_func();
+             struct SecondStruct {};",
         )?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        // TODO(b/200067824): This should use the alias's real name in Rust, as well.
-        assert_rs_matches!(rs_api, quote! { pub fn Function() -> ::core::ffi::c_int { ... } },);
 
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" SomeStruct::Type __rust_thunk___Z8Functionv(){ return Function(); }
-            },
-        );
-        Ok(())
-    }
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
 
-    #[test]
-    fn test_ref_to_struct_in_thunk_impls() -> Result<()> {
-        let ir = ir_from_cc("struct S{}; inline void foo(S& s) {} ")?;
-        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___Z3fooR1S(struct S* s) {
-                    foo(*s);
-                }
-            }
-        );
-        Ok(())
-    }
+        let idx = |s: &str| rs_api.find(s).ok_or_else(|| anyhow!("'{}' missing", s));
 
-    #[test]
-    fn test_const_ref_to_struct_in_thunk_impls() -> Result<()> {
-        let ir = ir_from_cc("struct S{}; inline void foo(const S& s) {} ")?;
-        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___Z3fooRK1S(const struct S* s) {
-                    foo(*s);
-                }
-            }
-        );
-        Ok(())
-    }
+        let f1 = idx("fn first_func")?;
+        let f2 = idx("fn second_func")?;
+        let s1 = idx("struct FirstStruct")?;
+        let s2 = idx("struct SecondStruct")?;
+        let t1 = idx("fn __rust_thunk___Z10first_funcv")?;
+        let t2 = idx("fn __rust_thunk___Z11second_funcv")?;
+
+        assert!(f1 < s1);
+        assert!(s1 < f2);
+        assert!(f2 < s2);
+        assert!(s2 < t1);
+        assert!(t1 < t2);
 
-    #[test]
-    fn test_unsigned_int_in_thunk_impls() -> Result<()> {
-        let ir = ir_from_cc("inline void foo(unsigned int i) {} ")?;
-        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___Z3fooj(unsigned int i) {
-                    foo(i);
-                }
-            }
-        );
         Ok(())
     }
 
     #[test]
-    fn test_record_static_methods_qualify_call_in_thunk() -> Result<()> {
+    fn test_base_class_subobject_layout() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct SomeStruct {
-                static inline int some_func() { return 42; }
-            }; "#,
+            // We use a class here to force `Derived::z` to live inside the tail padding of `Base`.
+            // On the Itanium ABI, this would not happen if `Base` were a POD type.
+            class Base {__INT64_TYPE__ x; char y;};
+            struct Derived final : Base {__INT16_TYPE__ z;};
+        "#,
         )?;
-
-        assert_cc_matches!(
-            generate_bindings_tokens(ir)?.rs_api_impl,
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C" int __rust_thunk___ZN10SomeStruct9some_funcEv() {
-                    return SomeStruct::some_func();
+                #[repr(C, align(8))]
+                pub struct Derived {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
+                    pub z: ::core::ffi::c_short,
                 }
             }
         );
         Ok(())
     }
 
+    /// The size and alignment assertions of a derived class account for its
+    /// base class subobjects, and the fields of the derived class that live in
+    /// their tail padding.
     #[test]
-    fn test_record_instance_methods_deref_this_in_thunk() -> Result<()> {
+    fn test_base_class_subobject_layout_assertions() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct SomeStruct {
-                inline int some_func(int arg) const { return 42 + arg; }
-            }; "#,
+            class Base {__INT64_TYPE__ x; char y;};
+            struct Derived final : Base {__INT16_TYPE__ z;};
+        "#,
         )?;
-
-        assert_cc_matches!(
-            generate_bindings_tokens(ir)?.rs_api_impl,
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C" int __rust_thunk___ZNK10SomeStruct9some_funcEi(
-                        const struct SomeStruct* __this, int arg) {
-                    return __this->some_func(arg);
-                }
+                const _: () = assert!(::core::mem::size_of::<crate::Derived>() == 16);
+                const _: () = assert!(::core::mem::align_of::<crate::Derived>() == 8);
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(memoffset::offset_of!(crate::Derived, z) == 10);
             }
         );
         Ok(())
     }
 
+    /// The same as test_base_class_subobject_layout, but with multiple
+    /// inheritance.
     #[test]
-    fn test_record_with_unsupported_field_type() -> Result<()> {
-        // Using a nested struct because it's currently not supported.
-        // But... any other unsupported type would also work for this test.
+    fn test_base_class_multiple_inheritance_subobject_layout() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct StructWithUnsupportedField {
-              struct NestedStruct {
-                int nested_field;
-              };
-
-              // Doc comment for `my_field`.
-              NestedStruct my_field;
-            };
+            class Base1 {__INT64_TYPE__ x;};
+            class Base2 {char y;};
+            struct Derived final : Base1, Base2 {__INT16_TYPE__ z;};
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(4))]
-                pub struct StructWithUnsupportedField {
-                    #[doc = " Doc comment for `my_field`.\n \n Reason for representing this field as a blob of bytes:\n Unsupported type 'struct StructWithUnsupportedField::NestedStruct': No generated bindings found for 'NestedStruct'"]
-                    pub(crate) my_field: [::core::mem::MaybeUninit<u8>; 4],
+                #[repr(C, align(8))]
+                pub struct Derived {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
+                    pub z: ::core::ffi::c_short,
                 }
-                ...
-                const _: () = assert!(
-                    memoffset::offset_of!(crate::StructWithUnsupportedField, my_field) == 0);
             }
         );
         Ok(())
     }
 
-    /// This is a regression test for b/283835873 where the alignment of the
-    /// generated struct was wrong/missing.
+    /// The same as test_base_class_subobject_layout, but with a chain of
+    /// inheritance.
     #[test]
-    fn test_struct_with_only_bitfields() -> Result<()> {
+    fn test_base_class_deep_inheritance_subobject_layout() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-                struct SomeStruct {
-                  char32_t code_point : 31;
-                  enum : char32_t {
-                    ok = 0,
-                    error = 1
-                  } status : 1;
-                };
-                static_assert(sizeof(SomeStruct) == 4);
-                static_assert(alignof(SomeStruct) == 4);
-            "#,
+            class Base1 {__INT64_TYPE__ x;};
+            class Base2 : Base1 {char y;};
+            struct Derived final : Base2 {__INT16_TYPE__ z;};
+        "#,
         )?;
-        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-               #[repr(C, align(4))]
-               pub struct SomeStruct { ...  }
+                #[repr(C, align(8))]
+                pub struct Derived {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
+                    pub z: ::core::ffi::c_short,
+                }
             }
         );
-        assert_rs_matches!(
-            rs_api,
-            quote! { const _: () = assert!(::core::mem::size_of::<crate::SomeStruct>() == 4); }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! {  const _: () = assert!(::core::mem::align_of::<crate::SomeStruct>() == 4); }
-        );
         Ok(())
     }
 
+    /// For derived classes with no data members, we can't use the offset of the
+    /// first member to determine the size of the base class subobjects.
     #[test]
-    fn test_struct_with_unnamed_bitfield_member() -> Result<()> {
-        // This test input causes `field_decl->getName()` to return an empty string.
-        // This example is based on `struct timex` from
-        // /usr/grte/v5/include/bits/timex.h
+    fn test_base_class_subobject_fieldless_layout() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct SomeStruct {
-                int first_field;
-                int :32;
-                int last_field;
-            }; "#,
+            class Base {__INT64_TYPE__ x; char y;};
+            struct Derived final : Base {};
+        "#,
         )?;
-        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-               #[repr(C, align(4))]
-               pub struct SomeStruct {
-                   pub first_field: ::core::ffi::c_int, ...
-                   __bitfields1: [::core::mem::MaybeUninit<u8>; 4],
-                   pub last_field: ::core::ffi::c_int,
-               }
-               ...
-               const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, first_field) == 0);
-               const _: () = assert!(memoffset::offset_of!(crate::SomeStruct, last_field) == 8);
+                #[repr(C, align(8))]
+                pub struct Derived {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 16],
+                }
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_struct_with_inheritable_field() -> Result<()> {
+    fn test_base_class_subobject_empty_fieldless() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct TrivialButInheritable {
-              int x;
-            };
-            struct StructWithInheritable final {
-              TrivialButInheritable t;
-            };
+            class Base {};
+            struct Derived final : Base {};
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C)]
+                pub struct Derived {
+                    ...
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_union_with_inheritable_field() -> Result<()> {
+    fn test_base_class_subobject_empty() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct TrivialButInheritable {
-              int x;
-            };
-            union UnionWithInheritable {
-              TrivialButInheritable t;
+            class Base {};
+            struct Derived final : Base {
+                __INT16_TYPE__ x;
             };
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct Derived {
+                    pub x: ::core::ffi::c_short,
+                }
+            }
+        );
         Ok(())
     }
 
-    /// Classes with a non-public destructor shouldn't be constructible, not
-    /// even via Copy/Clone.
+    /// Non-aggregate structs can't be directly initialized, because we add
+    /// a zero-sized private field to the bindings.
     #[test]
-    fn test_trivial_nonpublic_destructor() -> Result<()> {
+    fn test_non_aggregate_struct_private_field() -> Result<()> {
         let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct Indestructible final {
-              Indestructible() = default;
-              Indestructible(int);
-              Indestructible(const Indestructible&) = default;
-              void Foo() const;
-             private:
-              ~Indestructible() = default;
-            };
+            r#"
+            struct NonAggregate {
+                NonAggregate() {}
 
-            Indestructible ReturnsValue();
-            void TakesValue(Indestructible);
-            void TakesReference(const Indestructible& x);
+                __INT16_TYPE__ x = 0;
+            };
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        // It isn't available by value:
-        assert_rs_not_matches!(rs_api, quote! {Default});
-        assert_rs_not_matches!(rs_api, quote! {From});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
-        assert_rs_not_matches!(rs_api, quote! {ReturnsValue});
-        assert_rs_not_matches!(rs_api, quote! {TakesValue});
-        // ... but it is otherwise available:
-        assert_rs_matches!(rs_api, quote! {struct Indestructible});
-        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn TakesReference<'a>(x: &'a crate::Indestructible)});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct NonAggregate {
+                    __non_field_data:  [::core::mem::MaybeUninit<u8>; 0],
+                    pub x: ::core::ffi::c_short,
+                }
+            }
+        );
         Ok(())
     }
 
+    /// When a field is [[no_unique_address]], it occupies the space up to the
+    /// next field.
     #[test]
-    fn test_nontrivial_nonpublic_destructor() -> Result<()> {
+    fn test_no_unique_address() -> Result<()> {
         let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct Indestructible final {
-              Indestructible() = default;
-              Indestructible(int);
-              Indestructible(const Indestructible&) = default;
-              void Foo() const;
-             private:
-              ~Indestructible() {}
+            r#"
+            class Field1 {__INT64_TYPE__ x;};
+            class Field2 {char y;};
+            struct Struct final {
+                [[no_unique_address]] Field1 field1;
+                [[no_unique_address]] Field2 field2;
+                __INT16_TYPE__ z;
             };
-
-            Indestructible ReturnsValue();
-            void TakesValue(Indestructible);
-            void TakesReference(const Indestructible& x);
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        // It isn't available by value:
-        assert_rs_not_matches!(rs_api, quote! {CtorNew});
-        assert_rs_not_matches!(rs_api, quote! {ReturnsValue});
-        assert_rs_not_matches!(rs_api, quote! {TakesValue});
-        // ... but it is otherwise available:
-        assert_rs_matches!(rs_api, quote! {struct Indestructible});
-        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn TakesReference<'a>(x: &'a crate::Indestructible)});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C, align(8))]
+                pub struct Struct {
+                    ...
+                    pub(crate) field1: [::core::mem::MaybeUninit<u8>; 8],
+                    ...
+                    pub(crate) field2: [::core::mem::MaybeUninit<u8>; 2],
+                    pub z: ::core::ffi::c_short,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl Struct {
+                    pub fn field1(&self) -> &crate::Field1 {
+                        unsafe {&* (&self.field1 as *const _ as *const crate::Field1)}
+                    }
+                    pub fn field2(&self) -> &crate::Field2 {
+                        unsafe {&* (&self.field2 as *const _ as *const crate::Field2)}
+                    }
+                }
+            }
+        );
         Ok(())
     }
 
-    /// trivial abstract structs shouldn't be constructible, not even via
-    /// Copy/Clone.
-    ///
-    /// Right now, a struct can only be Copy/Clone if it's final, but that
-    /// restriction will likely be lifted later.
+    /// When a [[no_unique_address]] field is the last one, it occupies the rest
+    /// of the object.
     #[test]
-    fn test_trivial_abstract_by_value() -> Result<()> {
+    fn test_no_unique_address_last_field() -> Result<()> {
         let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct Abstract final {
-              Abstract() = default;
-              Abstract(int);
-              Abstract(const Abstract&) = default;
-              virtual void Foo() const = 0;
-              void Nonvirtual() const;
+            r#"
+            class Field1 {__INT64_TYPE__ x;};
+            class Field2 {char y;};
+            struct Struct final {
+                [[no_unique_address]] Field1 field1;
+                [[no_unique_address]] Field2 field2;
             };
-            void TakesAbstract(const Abstract& a);
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        // It isn't available by value:
-        assert_rs_not_matches!(rs_api, quote! {Default});
-        assert_rs_not_matches!(rs_api, quote! {From});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
-        // ... but it is otherwise available:
-        assert_rs_matches!(rs_api, quote! {struct Abstract});
-        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn Nonvirtual<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn TakesAbstract<'a>(a: &'a crate::Abstract)});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C, align(8))]
+                pub struct Struct {
+                    ...
+                    pub(crate) field1: [::core::mem::MaybeUninit<u8>; 8],
+                    ...
+                    pub(crate) field2: [::core::mem::MaybeUninit<u8>; 8],
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_nontrivial_abstract_by_value() -> Result<()> {
+    fn test_no_unique_address_empty() -> Result<()> {
         let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct Abstract final {
-              Abstract() {};
-              Abstract(int);
-              Abstract(const Abstract&) {}
-              virtual void Foo() const = 0;
-              void Nonvirtual() const;
+            r#"
+            class Field {};
+            struct Struct final {
+                [[no_unique_address]] Field field;
+                int x;
             };
-            void TakesAbstract(const Abstract& a);
         "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {CtorNew});
-        // ... but it is otherwise available:
-        assert_rs_matches!(rs_api, quote! {struct Abstract});
-        assert_rs_matches!(rs_api, quote! {fn Foo<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn Nonvirtual<'a>(&'a self)});
-        assert_rs_matches!(rs_api, quote! {fn TakesAbstract<'a>(a: &'a crate::Abstract)});
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C, align(4))]
+                pub struct Struct {
+                    ...
+                    pub(crate) field: [::core::mem::MaybeUninit<u8>; 0],
+                    pub x: ::core::ffi::c_int,
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_struct_with_unnamed_struct_and_union_members() -> Result<()> {
-        // This test input causes `field_decl->getName()` to return an empty string.
-        // See also:
-        // - https://en.cppreference.com/w/c/language/struct: "[...] an unnamed member
-        //   of a struct whose type is a struct without name is known as anonymous
-        //   struct."
-        // - https://rust-lang.github.io/rfcs/2102-unnamed-fields.html
+    fn test_base_class_subobject_empty_last_field() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            struct StructWithUnnamedMembers {
-              int first_field;
-
-              struct {
-                int anonymous_struct_field_1;
-                int anonymous_struct_field_2;
-              };
-              union {
-                int anonymous_union_field_1;
-                int anonymous_union_field_2;
-              };
-
-              int last_field;
-            }; "#,
+            class Field {};
+            struct Struct final {
+                [[no_unique_address]] Field field;
+            };
+        "#,
         )?;
-        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
-        // TODO(b/200067824): Once nested structs anhd unions are supported,
-        // `__unnamed_field1` and `__unnamed_field2` should have a real, usable
-        // type.
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-               #[repr(C, align(4))]
-               pub struct StructWithUnnamedMembers {
-                   pub first_field: ::core::ffi::c_int,
-                   #[doc =" Reason for representing this field as a blob of bytes:\n Unsupported type 'struct StructWithUnnamedMembers::(anonymous at ./ir_from_cc_virtual_header.h:7:15)': No generated bindings found for ''"]
-                   pub(crate) __unnamed_field1: [::core::mem::MaybeUninit<u8>; 8],
-                   #[doc =" Reason for representing this field as a blob of bytes:\n Unsupported type 'union StructWithUnnamedMembers::(anonymous at ./ir_from_cc_virtual_header.h:11:15)': No generated bindings found for ''"]
-                   pub(crate) __unnamed_field2: [::core::mem::MaybeUninit<u8>; 4],
-                   pub last_field: ::core::ffi::c_int,
-               }
-               ...
-               const _: () = assert!(memoffset::offset_of!(
-                       crate::StructWithUnnamedMembers, first_field) == 0);
-               const _: () = assert!(memoffset::offset_of!(
-                       crate::StructWithUnnamedMembers, __unnamed_field1) == 4);
-               const _: () = assert!(memoffset::offset_of!(
-                       crate::StructWithUnnamedMembers, __unnamed_field2) == 12);
-               const _: () = assert!(memoffset::offset_of!(
-                       crate::StructWithUnnamedMembers, last_field) == 16);
+                #[repr(C)]
+                pub struct Struct {
+                    ...
+                    pub(crate) field: [::core::mem::MaybeUninit<u8>; 1],
+                }
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_struct_from_other_target() -> Result<()> {
-        let ir = ir_from_cc_dependency("// intentionally empty", "struct SomeStruct {};")?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_not_matches!(rs_api, quote! { SomeStruct });
-        assert_cc_not_matches!(rs_api_impl, quote! { SomeStruct });
+    fn test_generate_enum_basic() -> Result<()> {
+        let ir = ir_from_cc("enum Color { kRed = 5, kBlue };")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_uint);
+                impl Color {
+                    pub const kRed: Color = Color(5);
+                    pub const kBlue: Color = Color(6);
+                }
+                impl From<::core::ffi::c_uint> for Color {
+                    fn from(value: ::core::ffi::c_uint) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_uint {
+                    fn from(value: Color) -> ::core::ffi::c_uint {
+                        value.0
+                    }
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_copy_derives() {
-        let record = ir_record("S");
-        assert_eq!(generate_derives(&record), &["Clone", "Copy"]);
-    }
-
-    #[test]
-    fn test_copy_derives_not_is_trivial_abi() {
-        let mut record = ir_record("S");
-        record.is_trivial_abi = false;
-        assert_eq!(generate_derives(&record), &[""; 0]);
-    }
-
-    /// Even if it's trivially relocatable, !Unpin C++ type cannot be
-    /// cloned/copied or otherwise used by value, because values would allow
-    /// assignment into the Pin.
-    ///
-    /// All !Unpin C++ types, not just non trivially relocatable ones, are
-    /// unsafe to assign in the Rust sense.
-    #[test]
-    fn test_copy_derives_not_final() {
-        let mut record = ir_record("S");
-        record.is_inheritable = true;
-        assert_eq!(generate_derives(&record), &[""; 0]);
-    }
-
-    #[test]
-    fn test_copy_derives_ctor_deleted() {
-        let mut record = ir_record("S");
-        record.copy_constructor = ir::SpecialMemberFunc::Unavailable;
-        assert_eq!(generate_derives(&record), &[""; 0]);
-    }
-
-    #[test]
-    fn test_copy_derives_ctor_nontrivial_members() {
-        let mut record = ir_record("S");
-        record.copy_constructor = ir::SpecialMemberFunc::NontrivialMembers;
-        assert_eq!(generate_derives(&record), &[""; 0]);
-    }
-
-    #[test]
-    fn test_copy_derives_ctor_nontrivial_self() {
-        let mut record = ir_record("S");
-        record.copy_constructor = ir::SpecialMemberFunc::NontrivialUserDefined;
-        assert_eq!(generate_derives(&record), &[""; 0]);
-    }
-
-    /// In Rust, a Drop type cannot be Copy.
-    #[test]
-    fn test_copy_derives_dtor_nontrivial_self() {
-        let mut record = ir_record("S");
-        for definition in
-            [ir::SpecialMemberFunc::NontrivialUserDefined, ir::SpecialMemberFunc::NontrivialMembers]
-        {
-            record.destructor = definition;
-            assert_eq!(generate_derives(&record), &["Clone"]);
-        }
+    fn test_generate_scoped_enum_basic() -> Result<()> {
+        let ir = ir_from_cc("enum class Color { kRed = -5, kBlue };")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_int);
+                impl Color {
+                    pub const kRed: Color = Color(-5);
+                    pub const kBlue: Color = Color(-4);
+                }
+                impl From<::core::ffi::c_int> for Color {
+                    fn from(value: ::core::ffi::c_int) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_int {
+                    fn from(value: Color) -> ::core::ffi::c_int {
+                        value.0
+                    }
+                }
+            }
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_ptr_func() -> Result<()> {
-        let ir = ir_from_cc(r#" inline int* Deref(int*const* p); "#)?;
-
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+    fn test_generate_enum_with_64_bit_signed_vals() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"enum Color : long {
+                    kViolet = -9223372036854775807 - 1LL,
+                    kRed = -5,
+                    kBlue,
+                    kGreen = 3,
+                    kMagenta = 9223372036854775807
+                };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[inline(always)]
-                pub unsafe fn Deref(p: *const *mut ::core::ffi::c_int) -> *mut ::core::ffi::c_int {
-                    crate::detail::__rust_thunk___Z5DerefPKPi(p)
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_long);
+                impl Color {
+                    pub const kViolet: Color = Color(-9223372036854775808);
+                    pub const kRed: Color = Color(-5);
+                    pub const kBlue: Color = Color(-4);
+                    pub const kGreen: Color = Color(3);
+                    pub const kMagenta: Color = Color(9223372036854775807);
+                }
+                impl From<::core::ffi::c_long> for Color {
+                    fn from(value: ::core::ffi::c_long) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_long {
+                    fn from(value: Color) -> ::core::ffi::c_long {
+                        value.0
+                    }
                 }
             }
         );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_enum_with_64_bit_unsigned_vals() -> Result<()> {
+        let ir = ir_from_cc(
+            r#" enum Color: unsigned long {
+                    kRed,
+                    kBlue,
+                    kLimeGreen = 18446744073709551615
+                }; "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                mod detail {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    extern "C" {
-                        pub(crate) fn __rust_thunk___Z5DerefPKPi(p: *const *mut ::core::ffi::c_int) -> *mut ::core::ffi::c_int;
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_ulong);
+                impl Color {
+                    pub const kRed: Color = Color(0);
+                    pub const kBlue: Color = Color(1);
+                    pub const kLimeGreen: Color = Color(18446744073709551615);
+                }
+                impl From<::core::ffi::c_ulong> for Color {
+                    fn from(value: ::core::ffi::c_ulong) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_ulong {
+                    fn from(value: Color) -> ::core::ffi::c_ulong {
+                        value.0
                     }
                 }
             }
         );
+        Ok(())
+    }
 
-        assert_cc_matches!(
-            rs_api_impl,
+    #[test]
+    fn test_generate_enum_with_32_bit_signed_vals() -> Result<()> {
+        let ir = ir_from_cc(
+            "enum Color { kViolet = -2147483647 - 1, kRed = -5, kBlue, kGreen = 3, kMagenta = 2147483647 };",
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C" int* __rust_thunk___Z5DerefPKPi(int* const * p) {
-                    return Deref(p);
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_int);
+                impl Color {
+                    pub const kViolet: Color = Color(-2147483648);
+                    pub const kRed: Color = Color(-5);
+                    pub const kBlue: Color = Color(-4);
+                    pub const kGreen: Color = Color(3);
+                    pub const kMagenta: Color = Color(2147483647);
+                }
+                impl From<::core::ffi::c_int> for Color {
+                    fn from(value: ::core::ffi::c_int) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_int {
+                    fn from(value: Color) -> ::core::ffi::c_int {
+                        value.0
+                    }
                 }
             }
         );
@@ -5276,92 +14363,172 @@ mod tests {
     }
 
     #[test]
-    fn test_const_char_ptr_func() -> Result<()> {
-        // This is a regression test: We used to include the "const" in the name
-        // of the CcType, which caused a panic in the code generator
-        // ('"const char" is not a valid Ident').
-        // It's therefore important that f() is inline so that we need to
-        // generate a thunk for it (where we then process the CcType).
-        let ir = ir_from_cc(r#" inline void f(const signed char *str); "#)?;
-
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+    fn test_generate_enum_with_32_bit_unsigned_vals() -> Result<()> {
+        let ir = ir_from_cc("enum Color: unsigned int { kRed, kBlue, kLimeGreen = 4294967295 };")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[inline(always)]
-                pub unsafe fn f(str: *const ::core::ffi::c_schar) {
-                    crate::detail::__rust_thunk___Z1fPKa(str)
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_uint);
+                impl Color {
+                    pub const kRed: Color = Color(0);
+                    pub const kBlue: Color = Color(1);
+                    pub const kLimeGreen: Color = Color(4294967295);
+                }
+                impl From<::core::ffi::c_uint> for Color {
+                    fn from(value: ::core::ffi::c_uint) -> Color {
+                        Color(value)
+                    }
+                }
+                impl From<Color> for ::core::ffi::c_uint {
+                    fn from(value: Color) -> ::core::ffi::c_uint {
+                        value.0
+                    }
                 }
             }
         );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_enum_bool() -> Result<()> {
+        let ir = ir_from_cc("enum Bool : bool { kFalse, kTrue };")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                extern "C" {
-                    pub(crate) fn __rust_thunk___Z1fPKa(str: *const ::core::ffi::c_schar);
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Bool(bool);
+                impl Bool {
+                    pub const kFalse: Bool = Bool(false);
+                    pub const kTrue: Bool = Bool(true);
+                }
+                impl From<bool> for Bool {
+                    fn from(value: bool) -> Bool {
+                        Bool(value)
+                    }
+                }
+                impl From<Bool> for bool {
+                    fn from(value: Bool) -> bool {
+                        value.0
+                    }
                 }
             }
         );
+        Ok(())
+    }
 
-        assert_cc_matches!(
-            rs_api_impl,
+    #[test]
+    fn test_generate_enum_bool_alias() -> Result<()> {
+        let ir = ir_from_cc("using MyBool = bool; enum Bool : MyBool { kFalse, kTrue };")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C" void __rust_thunk___Z1fPKa(signed char const * str){ f(str); }
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Bool(crate::MyBool);
+                impl Bool {
+                    pub const kFalse: Bool = Bool(false);
+                    pub const kTrue: Bool = Bool(true);
+                }
+                impl From<crate::MyBool> for Bool {
+                    fn from(value: crate::MyBool) -> Bool {
+                        Bool(value)
+                    }
+                }
+                impl From<Bool> for crate::MyBool {
+                    fn from(value: Bool) -> crate::MyBool {
+                        value.0
+                    }
+                }
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_func_ptr_where_params_are_primitive_types() -> Result<()> {
-        let ir = ir_from_cc(r#" int (*get_ptr_to_func())(float, double); "#)?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+    fn test_generate_typedefed_anonymous_enum() -> Result<()> {
+        let ir = ir_from_cc("typedef enum { kRead = 1, kWrite = 2 } Flags;")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[inline(always)]
-                pub fn get_ptr_to_func() -> Option<extern "C" fn (f32, f64) -> ::core::ffi::c_int> {
-                    unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Flags(::core::ffi::c_uint);
+                impl Flags {
+                    pub const kRead: Flags = Flags(1);
+                    pub const kWrite: Flags = Flags(2);
                 }
             }
         );
+        assert_rs_not_matches!(rs_api, quote! { pub type Flags });
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_enum_doc_comments() -> Result<()> {
+        let ir = ir_from_cc(
+            "/// Doc comment for Color.\n\
+            enum Color {\n\
+                /// Doc comment for kRed.\n\
+                kRed,\n\
+                kBlue,\n\
+            };",
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                mod detail {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    extern "C" {
-                        #[link_name = "_Z15get_ptr_to_funcv"]
-                        pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
-                        -> Option<extern "C" fn(f32, f64) -> ::core::ffi::c_int>;
-                    }
+                #[doc = " Doc comment for Color.\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=4"]
+                #[repr(transparent)]
+                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+                pub struct Color(::core::ffi::c_uint);
+                impl Color {
+                    #[doc = " Doc comment for kRed."]
+                    pub const kRed: Color = Color(0);
+                    pub const kBlue: Color = Color(1);
                 }
             }
         );
-        // Verify that no C++ thunk got generated.
-        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
-
-        // TODO(b/217419782): Add another test for more exotic calling conventions /
-        // abis.
-
-        // TODO(b/276461979): Add another test for pointer to a function that requires
-        // thunks - e.g. because it takes/returns structs value. See also
-        // b/276461979 and <internal link>
-
         Ok(())
     }
 
     #[test]
-    fn test_func_ref() -> Result<()> {
-        let ir = ir_from_cc(r#" int (&get_ref_to_func())(float, double); "#)?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+    fn test_enum_in_function_signature() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            namespace ns { enum Color { kRed, kBlue }; }
+            typedef enum { kRead = 1, kWrite = 2 } Flags;
+            inline Flags ToFlags(ns::Color color);
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
                 #[inline(always)]
-                pub fn get_ref_to_func() -> extern "C" fn (f32, f64) -> ::core::ffi::c_int {
-                    unsafe { crate::detail::__rust_thunk___Z15get_ref_to_funcv() }
+                pub fn ToFlags(color: crate::ns::Color) -> crate::Flags {
+                    unsafe { crate::detail::__rust_thunk___Z7ToFlagsN2ns5ColorE(color) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___Z7ToFlagsN2ns5ColorE(
+                    color: crate::ns::Color) -> crate::Flags;
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" Flags __rust_thunk___Z7ToFlagsN2ns5ColorE(ns::Color color) {
+                    return ToFlags(color);
                 }
             }
         );
@@ -5369,280 +14536,97 @@ mod tests {
     }
 
     #[test]
-    fn test_func_ptr_with_non_static_lifetime() -> Result<()> {
-        let ir = ir_from_cc(&with_lifetime_macros(
+    fn test_function_template_specialization() -> Result<()> {
+        let ir = ir_from_cc(
             r#"
-            int (* $a get_ptr_to_func())(float, double); "#,
-        ))?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_cc_matches!(rs_api, {
-            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=33\n\
-                           Error while generating bindings for item 'get_ptr_to_func':\n\
-                           Type may not be annotated with lifetimes";
-            quote! { __COMMENT__ #txt }
-        });
-        Ok(())
-    }
-
-    #[test]
-    fn test_func_ptr_where_params_are_raw_ptrs() -> Result<()> {
-        let ir = ir_from_cc(r#" const int* (*get_ptr_to_func())(const int*); "#)?;
+            template <typename T> T Add(T a, T b);
+            template <> int Add<int>(int a, int b);
+            template <> int* Add<int*>(int* a, int* b);
+        "#,
+        )?;
         let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[inline(always)]
-                pub fn get_ptr_to_func() -> Option<extern "C" fn (*const ::core::ffi::c_int) -> *const ::core::ffi::c_int> {
-                    unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
-                }
+                pub fn Add_int(a: ::core::ffi::c_int, b: ::core::ffi::c_int) -> ::core::ffi::c_int
             }
         );
         assert_rs_matches!(
             rs_api,
             quote! {
-                mod detail {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    extern "C" {
-                        #[link_name = "_Z15get_ptr_to_funcv"]
-                        pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
-                        -> Option<extern "C" fn(*const ::core::ffi::c_int) -> *const ::core::ffi::c_int>;
-                    }
-                }
+                pub unsafe fn Add_int_ptr(a: *mut ::core::ffi::c_int, b: *mut ::core::ffi::c_int)
+                    -> *mut ::core::ffi::c_int
             }
         );
-        // Verify that no C++ thunk got generated.
-        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
-
-        // TODO(b/217419782): Add another test where params (and the return
-        // type) are references with lifetimes.  Something like this:
-        //     #pragma clang lifetime_elision
-        //     const int& (*get_ptr_to_func())(const int&, const int&); "#)?;
-        // 1) Need to investigate why this fails - seeing raw pointers in Rust
-        //    seems to indicate that no lifetimes are present at the `importer.cc`
-        //    level. Maybe lifetime elision doesn't support this scenario? Unclear
-        //    how to explicitly apply [[clang::annotate("lifetimes", "a, b -> a")]]
-        //    to the _inner_ function.
-        // 2) It is important to have 2 reference parameters, so see if the problem
-        //    of passing `lifetimes` by value would have been caught - see:
-        //    cl/428079010/depot/rs_bindings_from_cc/
-        // importer.cc?version=s6#823
-
-        // TODO(b/217419782): Decide what to do if the C++ pointer is *not*
-        // annotated with a lifetime - emit `unsafe fn(...) -> ...` in that
-        // case?
-
+        assert_cc_matches!(rs_api_impl, quote! { return Add<int>(a, b); });
+        assert_cc_matches!(rs_api_impl, quote! { return Add<int *>(a, b); });
         Ok(())
     }
 
-    mod custom_abi_tests {
-        use super::*;
-        use ir_matchers::assert_ir_matches;
-        #[test]
-        fn test_func_ptr_with_custom_abi() -> Result<()> {
-            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
-                return Ok(());
-            }
-            let ir =
-                ir_from_cc(r#" int (*get_ptr_to_func())(float, double) [[clang::vectorcall]]; "#)?;
-
-            // Verify that the test input correctly represents what we intend to
-            // test - we want [[clang::vectorcall]] to apply to the returned
-            // function pointer, but *not* apply to the `get_ptr_to_func` function.
-            assert_ir_matches!(
-                ir,
-                quote! {
-                    Func(Func {
-                        name: "get_ptr_to_func", ...
-                        return_type: MappedType {
-                            rs_type: RsType {
-                                name: Some("Option"), ...
-                                type_args: [RsType { name: Some("#funcPtr vectorcall"), ... }], ...
-                            },
-                            cc_type: CcType {
-                                name: Some("*"), ...
-                                type_args: [CcType { name: Some("#funcValue vectorcall"), ... }], ...
-                            },
-                        }, ...
-                        has_c_calling_convention: true, ...
-                    }),
-                }
-            );
-
-            let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-            // Check that the custom "vectorcall" ABI gets propagated into the
-            // return type (i.e. into `extern "vectorcall" fn`).
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    #[inline(always)]
-                    pub fn get_ptr_to_func() -> Option<extern "vectorcall" fn (f32, f64) -> ::core::ffi::c_int> {
-                        unsafe { crate::detail::__rust_thunk___Z15get_ptr_to_funcv() }
-                    }
-                }
-            );
-
-            // The usual `extern "C"` ABI should be used for "get_ptr_to_func".
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    mod detail {
-                        #[allow(unused_imports)]
-                        use super::*;
-                        extern "C" {
-                            #[link_name = "_Z15get_ptr_to_funcv"]
-                            pub(crate) fn __rust_thunk___Z15get_ptr_to_funcv()
-                            -> Option<extern "vectorcall" fn(f32, f64) -> ::core::ffi::c_int>;
-                        }
-                    }
-                }
-            );
-
-            // Verify that no C++ thunk got generated.
-            assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z15get_ptr_to_funcv });
-            Ok(())
-        }
-
-        #[test]
-        fn test_func_ptr_with_custom_abi_thunk() -> Result<()> {
-            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
-                return Ok(());
-            }
-            // Using an `inline` keyword forces generation of a C++ thunk in
-            // `rs_api_impl` (i.e. exercises `format_cc_type`,
-            // `format_cc_call_conv_as_clang_attribute` and similar code).
-            let ir = ir_from_cc(
-                r#"
-                inline int (*inline_get_ptr_to_func())(float, double) [[clang::vectorcall]];
-            "#,
-            )?;
-
-            // Verify that the test input correctly represents what we intend to
-            // test - we want [[clang::vectorcall]] to apply to the returned
-            // function pointer, but *not* apply to the `get_ptr_to_func` function.
-            assert_ir_matches!(
-                ir,
-                quote! {
-                    Func(Func {
-                        name: "inline_get_ptr_to_func", ...
-                        return_type: MappedType {
-                            rs_type: RsType {
-                                name: Some("Option"), ...
-                                type_args: [RsType { name: Some("#funcPtr vectorcall"), ... }], ...
-                            },
-                            cc_type: CcType {
-                                name: Some("*"), ...
-                                type_args: [CcType { name: Some("#funcValue vectorcall"), ... }], ...
-                            },
-                        }, ...
-                        has_c_calling_convention: true, ...
-                    }),
-                }
-            );
-
-            // This test is quite similar to `test_func_ptr_thunk` - the main
-            // difference is verification of the `__attribute__((vectorcall))` in
-            // the expected signature of the generated thunk below.
-            let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-            assert_cc_matches!(
-                rs_api_impl,
-                quote! {
-                    extern "C" crubit::type_identity_t<
-                            int(float , double) __attribute__((vectorcall))
-                        >* __rust_thunk___Z22inline_get_ptr_to_funcv() {
-                        return inline_get_ptr_to_func();
-                    }
-                }
-            );
-            Ok(())
-        }
+    #[test]
+    fn test_variadic_function_template_specialization() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            template <typename... Args> int Count(Args... args);
+            template <> int Count<>();
+            template <> int Count<int, float>(int i, float f);
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(rs_api, quote! { pub fn Count() -> ::core::ffi::c_int });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn Count_int_float(i: ::core::ffi::c_int, f: f32) -> ::core::ffi::c_int }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { return Count<>(); });
+        assert_cc_matches!(rs_api_impl, quote! { return Count<int, float>(i, f); });
+        Ok(())
+    }
 
-        #[test]
-        fn test_custom_abi_thunk() -> Result<()> {
-            if multiplatform_testing::test_platform() != multiplatform_testing::Platform::X86Linux {
-                return Ok(());
-            }
-            let ir = ir_from_cc(
-                r#"
-                float f_vectorcall_calling_convention(float p1, float p2) [[clang::vectorcall]];
-                double f_c_calling_convention(double p1, double p2);
-            "#,
-            )?;
-            let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    #[inline(always)]
-                    pub fn f_vectorcall_calling_convention(p1: f32, p2: f32) -> f32 {
-                        unsafe {
-                            crate::detail::__rust_thunk___Z31f_vectorcall_calling_conventionff(p1, p2)
-                        }
-                    }
-                }
-            );
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    #[inline(always)]
-                    pub fn f_c_calling_convention(p1: f64, p2: f64) -> f64 {
-                        unsafe { crate::detail::__rust_thunk___Z22f_c_calling_conventiondd(p1, p2) }
-                    }
-                }
-            );
-            // `link_name` (i.e. no thunk) for `f_c_calling_convention`. No
-            // `link_name` (i.e. indicates presence of a thunk) for
-            // `f_vectorcall_calling_convention`.
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    mod detail {
-                        #[allow(unused_imports)]
-                        use super::*;
-                        extern "C" {
-                            pub(crate) fn __rust_thunk___Z31f_vectorcall_calling_conventionff(
-                                p1: f32, p2: f32) -> f32;
-                            #[link_name = "_Z22f_c_calling_conventiondd"]
-                            pub(crate) fn __rust_thunk___Z22f_c_calling_conventiondd(
-                                p1: f64, p2: f64) -> f64;
-                        }
-                    }
-                }
-            );
-            // C++ thunk needed for `f_vectorcall_calling_convention`.
-            assert_cc_matches!(
-                rs_api_impl,
-                quote! {
-                    extern "C" float __rust_thunk___Z31f_vectorcall_calling_conventionff(
-                        float p1, float p2) {
-                            return f_vectorcall_calling_convention(p1, p2);
-                    }
-                }
-            );
-            // No C++ thunk expected for `f_c_calling_convention`.
-            assert_cc_not_matches!(rs_api_impl, quote! { f_c_calling_convention });
-            Ok(())
-        }
+    #[test]
+    fn test_doc_comment_func() -> Result<()> {
+        let ir = ir_from_cc(
+            "
+        // Doc Comment
+        // with two lines
+        int func();",
+        )?;
+
+        assert_rs_matches!(
+            generate_bindings_tokens(ir)?.rs_api,
+            // leading space is intentional so there is a space between /// and the text of the
+            // comment
+            quote! {
+                #[doc = " Doc Comment\n with two lines\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=6"]
+                #[inline(always)]
+                pub fn func
+            }
+        );
+
+        Ok(())
     }
 
     #[test]
-    fn test_func_ptr_thunk() -> Result<()> {
-        // Using an `inline` keyword forces generation of a C++ thunk in
-        // `rs_api_impl` (i.e. exercises `format_cc_type` and similar code).
+    fn test_doc_comment_record() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            int multiply(int x, int y);
-            inline int (*inline_get_pointer_to_function())(int, int) {
-                return multiply;
-            }
-        "#,
+            "// Doc Comment\n\
+            //\n\
+            //  * with bullet\n\
+            struct SomeStruct final {\n\
+                // Field doc\n\
+                int field;\
+            };",
         )?;
-        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
-        assert_cc_matches!(
-            rs_api_impl,
+
+        assert_rs_matches!(
+            generate_bindings_tokens(ir)?.rs_api,
             quote! {
-                extern "C" crubit::type_identity_t<int(int , int)>*
-                __rust_thunk___Z30inline_get_pointer_to_functionv() {
-                    return inline_get_pointer_to_function();
+                #[doc = " Doc Comment\n \n  * with bullet\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=6"]
+                #[derive(Clone, Copy)]
+                #[repr(C)]
+                pub struct SomeStruct {
+                    # [doc = " Field doc"]
+                    pub field: ::core::ffi::c_int,
                 }
             }
         );
@@ -5650,547 +14634,678 @@ mod tests {
     }
 
     #[test]
-    fn test_item_order() -> Result<()> {
+    fn test_deprecated() -> Result<()> {
         let ir = ir_from_cc(
-            "int first_func();
-             struct FirstStruct {};
-             int second_func();
-             struct SecondStruct {};",
-        )?;
-
-        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
-
-        let idx = |s: &str| rs_api.find(s).ok_or_else(|| anyhow!("'{}' missing", s));
+            r#"
+            [[deprecated("Use g() instead.")]] void f();
 
-        let f1 = idx("fn first_func")?;
-        let f2 = idx("fn second_func")?;
-        let s1 = idx("struct FirstStruct")?;
-        let s2 = idx("struct SecondStruct")?;
-        let t1 = idx("fn __rust_thunk___Z10first_funcv")?;
-        let t2 = idx("fn __rust_thunk___Z11second_funcv")?;
+            struct [[deprecated]] SomeStruct final {
+                [[deprecated("Use y.")]] int x;
+                int y;
+                [[deprecated("Use f().")]] static void Method();
+            };
 
-        assert!(f1 < s1);
-        assert!(s1 < f2);
-        assert!(f2 < s2);
-        assert!(s2 < t1);
-        assert!(t1 < t2);
+            using MyInt [[deprecated("Use int.")]] = int;
+            enum [[deprecated("Use a bool.")]] Color { kRed };
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[deprecated(note = "Use g() instead.")]
+                #[inline(always)]
+                pub fn f() {...}
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[deprecated]
+                #[derive(Clone, Copy)]
+                #[repr(C)]
+                pub struct SomeStruct {
+                    #[deprecated(note = "Use y.")]
+                    pub x: ::core::ffi::c_int,
+                    pub y: ::core::ffi::c_int,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[allow(deprecated)]
+                impl SomeStruct {
+                    ...
+                    #[deprecated(note = "Use f().")]
+                    #[inline(always)]
+                    pub fn Method() {...}
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[deprecated(note = "Use int.")]
+                pub type MyInt = ::core::ffi::c_int;
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[deprecated(note = "Use a bool.")]
+                #[repr(transparent)]
+                ...
+                pub struct Color(...);
+            }
+        );
+        Ok(())
+    }
 
+    #[test]
+    fn test_deprecated_uses_in_bindings() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            [[deprecated]] inline void f() {}
+            struct [[deprecated]] SomeStruct final { int x; };
+            void TakesStruct(SomeStruct s);
+            inline void NotDeprecated() {}
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_not_matches!(rs_api, quote! { #![allow(deprecated)] });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[allow(deprecated)]
+                const _: () = assert!(::core::mem::size_of::<crate::SomeStruct>() == 4);
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[allow(deprecated)]
+                #[inline(always)]
+                pub fn TakesStruct(...) {...}
+            }
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! {
+                #[allow(deprecated)]
+                #[inline(always)]
+                pub fn NotDeprecated
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                __HASH_TOKEN__ pragma clang diagnostic push
+                __HASH_TOKEN__ pragma clang diagnostic ignored "-Wdeprecated-declarations"
+                extern "C" void __rust_thunk___Z1fv() { f(); }
+                __HASH_TOKEN__ pragma clang diagnostic pop
+            }
+        );
+        assert_cc_not_matches!(
+            rs_api_impl,
+            quote! {
+                __HASH_TOKEN__ pragma clang diagnostic ignored "-Wdeprecated-declarations"
+                extern "C" void __rust_thunk___Z13NotDeprecatedv
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_base_class_subobject_layout() -> Result<()> {
+    fn test_basic_union() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            // We use a class here to force `Derived::z` to live inside the tail padding of `Base`.
-            // On the Itanium ABI, this would not happen if `Base` were a POD type.
-            class Base {__INT64_TYPE__ x; char y;};
-            struct Derived final : Base {__INT16_TYPE__ z;};
-        "#,
+            #pragma clang lifetime_elision
+            union SomeUnion {
+                int some_field;
+                long long some_bigger_field;
+            };
+            "#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(8))]
-                pub struct Derived {
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
-                    pub z: ::core::ffi::c_short,
+                #[derive(Clone, Copy)]
+                #[repr(C)]
+                pub union SomeUnion {
+                    pub some_field: ::core::ffi::c_int,
+                    pub some_bigger_field: ::core::ffi::c_longlong,
                 }
             }
         );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN9SomeUnionC1Ev(union SomeUnion*__this) {...}
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_SIZEOF(union SomeUnion)==8) }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { static_assert(alignof(union SomeUnion)==8) });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_OFFSET_OF(some_field, union SomeUnion)==0) }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_OFFSET_OF(some_bigger_field, union SomeUnion)==0) }
+        );
         Ok(())
     }
 
-    /// The same as test_base_class_subobject_layout, but with multiple
-    /// inheritance.
     #[test]
-    fn test_base_class_multiple_inheritance_subobject_layout() -> Result<()> {
+    fn test_union_with_opaque_field() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Base1 {__INT64_TYPE__ x;};
-            class Base2 {char y;};
-            struct Derived final : Base1, Base2 {__INT16_TYPE__ z;};
-        "#,
+            union MyUnion {
+                char first_field[56];
+                int second_field;
+              };
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(8))]
-                pub struct Derived {
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
-                    pub z: ::core::ffi::c_short,
+                #[repr(C, align(4))]
+                pub union MyUnion { ...
+                    first_field: [::core::mem::MaybeUninit<u8>; 56],
+                    pub second_field: ::core::ffi::c_int,
                 }
             }
         );
+
+        assert_rs_matches!(
+            rs_api,
+            quote! { const _: () = assert!(::core::mem::size_of::<crate::MyUnion>() == 56); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {  const _: () = assert!(::core::mem::align_of::<crate::MyUnion>() == 4); }
+        );
         Ok(())
     }
 
-    /// The same as test_base_class_subobject_layout, but with a chain of
-    /// inheritance.
     #[test]
-    fn test_base_class_deep_inheritance_subobject_layout() -> Result<()> {
+    // TODO(https://github.com/Gilnaa/memoffset/issues/66): generate assertions for unions once
+    // offsetof supports them.
+    fn test_currently_no_offset_assertions_for_unions() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Base1 {__INT64_TYPE__ x;};
-            class Base2 : Base1 {char y;};
-            struct Derived final : Base2 {__INT16_TYPE__ z;};
-        "#,
+            union SomeUnion {
+                int some_field;
+                long long some_bigger_field;
+            };
+            "#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
+
+        assert_rs_not_matches!(rs_api, quote! { offset_of! });
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_with_private_fields() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            union SomeUnionWithPrivateFields {
+              public:
+                int public_field;
+              private:
+                long long private_field;
+            };
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
+                #[derive(Clone, Copy)]
                 #[repr(C, align(8))]
-                pub struct Derived {
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 10],
-                    pub z: ::core::ffi::c_short,
+                pub union SomeUnionWithPrivateFields {
+                    pub public_field: ::core::ffi::c_int,
+                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
+                    pub(crate) private_field: [::core::mem::MaybeUninit<u8>; 8],
                 }
             }
         );
-        Ok(())
-    }
 
-    /// For derived classes with no data members, we can't use the offset of the
-    /// first member to determine the size of the base class subobjects.
-    #[test]
-    fn test_base_class_subobject_fieldless_layout() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"
-            class Base {__INT64_TYPE__ x; char y;};
-            struct Derived final : Base {};
-        "#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(8))]
-                pub struct Derived {
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 16],
-                }
+                const _: () = assert!(::core::mem::size_of::<crate::SomeUnionWithPrivateFields>() == 8);
+                const _: () = assert!(::core::mem::align_of::<crate::SomeUnionWithPrivateFields>() == 8);
+                const _: () = {
+                  static_assertions::assert_impl_all!(crate::SomeUnionWithPrivateFields: Clone);
+                };
+                const _: () = {
+                  static_assertions::assert_impl_all!(crate::SomeUnionWithPrivateFields: Copy);
+                };
+                const _: () = {
+                  static_assertions::assert_not_impl_any!(crate::SomeUnionWithPrivateFields: Drop);
+                };
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_base_class_subobject_empty_fieldless() -> Result<()> {
-        let ir = ir_from_cc(
+    fn test_nontrivial_unions() -> Result<()> {
+        let ir = ir_from_cc_dependency(
             r#"
-            class Base {};
-            struct Derived final : Base {};
-        "#,
+            union UnionWithNontrivialField {
+                NonTrivialStruct my_field;
+            };
+            "#,
+            r#"
+            struct NonTrivialStruct {
+                NonTrivialStruct(NonTrivialStruct&&);
+            };
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
+        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
         assert_rs_matches!(
             rs_api,
             quote! {
+                #[::ctor::recursively_pinned]
                 #[repr(C)]
-                pub struct Derived {
-                    ...
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
-                }
+                pub union UnionWithNontrivialField { ... }
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_base_class_subobject_empty() -> Result<()> {
+    fn test_empty_struct() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Base {};
-            struct Derived final : Base {
-                __INT16_TYPE__ x;
-            };
-        "#,
+            struct EmptyStruct final {};
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                pub struct Derived {
-                    pub x: ::core::ffi::c_short,
+                #[derive(Clone, Copy)]
+                #[repr(C)]
+                pub struct EmptyStruct {
+                    ...
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
                 }
             }
         );
-        Ok(())
-    }
 
-    /// Non-aggregate structs can't be directly initialized, because we add
-    /// a zero-sized private field to the bindings.
-    #[test]
-    fn test_non_aggregate_struct_private_field() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"
-            struct NonAggregate {
-                NonAggregate() {}
-
-                __INT16_TYPE__ x = 0;
-            };
-        "#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                pub struct NonAggregate {
-                    __non_field_data:  [::core::mem::MaybeUninit<u8>; 0],
-                    pub x: ::core::ffi::c_short,
-                }
+                const _: () = assert!(::core::mem::size_of::<crate::EmptyStruct>() == 1);
+                const _: () = assert!(::core::mem::align_of::<crate::EmptyStruct>() == 1);
             }
         );
+
         Ok(())
     }
 
-    /// When a field is [[no_unique_address]], it occupies the space up to the
-    /// next field.
     #[test]
-    fn test_no_unique_address() -> Result<()> {
+    fn test_empty_union() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Field1 {__INT64_TYPE__ x;};
-            class Field2 {char y;};
-            struct Struct final {
-                [[no_unique_address]] Field1 field1;
-                [[no_unique_address]] Field2 field2;
-                __INT16_TYPE__ z;
-            };
-        "#,
+            union EmptyUnion {};
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(8))]
-                pub struct Struct {
-                    ...
-                    pub(crate) field1: [::core::mem::MaybeUninit<u8>; 8],
+                #[derive(Clone, Copy)]
+                #[repr(C)]
+                pub union EmptyUnion {
                     ...
-                    pub(crate) field2: [::core::mem::MaybeUninit<u8>; 2],
-                    pub z: ::core::ffi::c_short,
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
                 }
             }
         );
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl Struct {
-                    pub fn field1(&self) -> &crate::Field1 {
-                        unsafe {&* (&self.field1 as *const _ as *const crate::Field1)}
-                    }
-                    pub fn field2(&self) -> &crate::Field2 {
-                        unsafe {&* (&self.field2 as *const _ as *const crate::Field2)}
-                    }
-                }
+                const _: () = assert!(::core::mem::size_of::<crate::EmptyUnion>() == 1);
+                const _: () = assert!(::core::mem::align_of::<crate::EmptyUnion>() == 1);
             }
         );
+
         Ok(())
     }
 
-    /// When a [[no_unique_address]] field is the last one, it occupies the rest
-    /// of the object.
     #[test]
-    fn test_no_unique_address_last_field() -> Result<()> {
+    fn test_union_field_with_nontrivial_destructor() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Field1 {__INT64_TYPE__ x;};
-            class Field2 {char y;};
-            struct Struct final {
-                [[no_unique_address]] Field1 field1;
-                [[no_unique_address]] Field2 field2;
+            struct NontrivialStruct { ~NontrivialStruct(); };
+            union UnionWithNontrivialField {
+                int trivial_field;
+                NontrivialStruct nontrivial_field;
             };
-        "#,
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(8))]
-                pub struct Struct {
-                    ...
-                    pub(crate) field1: [::core::mem::MaybeUninit<u8>; 8],
-                    ...
-                    pub(crate) field2: [::core::mem::MaybeUninit<u8>; 8],
+                #[repr(C)]
+                pub union UnionWithNontrivialField {
+                    pub trivial_field: ::core::ffi::c_int,
+                    pub nontrivial_field: ::core::mem::ManuallyDrop<crate::NontrivialStruct>,
                 }
             }
         );
-        Ok(())
-    }
 
-    #[test]
-    fn test_no_unique_address_empty() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"
-            class Field {};
-            struct Struct final {
-                [[no_unique_address]] Field field;
-                int x;
-            };
-        "#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(4))]
-                pub struct Struct {
-                    ...
-                    pub(crate) field: [::core::mem::MaybeUninit<u8>; 0],
-                    pub x: ::core::ffi::c_int,
-                }
+                const _: () = assert!(::core::mem::size_of::<crate::UnionWithNontrivialField>() == 4);
+                const _: () = assert!(::core::mem::align_of::<crate::UnionWithNontrivialField>() == 4);
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_base_class_subobject_empty_last_field() -> Result<()> {
+    fn test_union_with_constructors() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            class Field {};
-            struct Struct final {
-                [[no_unique_address]] Field field;
+            #pragma clang lifetime_elision
+            union UnionWithDefaultConstructors {
+                int a;
             };
-        "#,
+            "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+
         assert_rs_matches!(
             rs_api,
             quote! {
+                #[derive(Clone, Copy)]
                 #[repr(C)]
-                pub struct Struct {
-                    ...
-                    pub(crate) field: [::core::mem::MaybeUninit<u8>; 1],
+                pub union UnionWithDefaultConstructors {
+                    pub a: ::core::ffi::c_int,
                 }
             }
         );
-        Ok(())
-    }
 
-    #[test]
-    fn test_generate_enum_basic() -> Result<()> {
-        let ir = ir_from_cc("enum Color { kRed = 5, kBlue };")?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_uint);
-                impl Color {
-                    pub const kRed: Color = Color(5);
-                    pub const kBlue: Color = Color(6);
-                }
-                impl From<::core::ffi::c_uint> for Color {
-                    fn from(value: ::core::ffi::c_uint) -> Color {
-                        Color(value)
-                    }
-                }
-                impl From<Color> for ::core::ffi::c_uint {
-                    fn from(value: Color) -> ::core::ffi::c_uint {
-                        value.0
+                impl Default for UnionWithDefaultConstructors {
+                    #[inline(always)]
+                    fn default() -> Self {
+                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN28UnionWithDefaultConstructorsC1Ev(&mut tmp);
+                            tmp.assume_init()
+                        }
                     }
                 }
             }
         );
-        Ok(())
-    }
 
-    #[test]
-    fn test_generate_scoped_enum_basic() -> Result<()> {
-        let ir = ir_from_cc("enum class Color { kRed = -5, kBlue };")?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_int);
-                impl Color {
-                    pub const kRed: Color = Color(-5);
-                    pub const kBlue: Color = Color(-4);
-                }
-                impl From<::core::ffi::c_int> for Color {
-                    fn from(value: ::core::ffi::c_int) -> Color {
-                        Color(value)
-                    }
-                }
-                impl From<Color> for ::core::ffi::c_int {
-                    fn from(value: Color) -> ::core::ffi::c_int {
-                        value.0
+                impl<'b> From<::ctor::RvalueReference<'b, Self>> for UnionWithDefaultConstructors {
+                    #[inline(always)]
+                    fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
+                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN28UnionWithDefaultConstructorsC1EOS_(&mut tmp, __param_0);
+                            tmp.assume_init()
+                        }
                     }
                 }
             }
         );
+
         Ok(())
     }
 
     #[test]
-    fn test_generate_enum_with_64_bit_signed_vals() -> Result<()> {
+    fn test_multiple_inheritance_upcast_offsets() -> Result<()> {
         let ir = ir_from_cc(
-            r#"enum Color : long {
-                    kViolet = -9223372036854775807 - 1LL,
-                    kRed = -5,
-                    kBlue,
-                    kGreen = 3,
-                    kMagenta = 9223372036854775807
-                };"#,
+            "
+            struct Base1 { long long x; };
+            struct Base2 { int y; };
+            struct Derived : Base1, Base2 { int z; };
+        ",
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_long);
-                impl Color {
-                    pub const kViolet: Color = Color(-9223372036854775808);
-                    pub const kRed: Color = Color(-5);
-                    pub const kBlue: Color = Color(-4);
-                    pub const kGreen: Color = Color(3);
-                    pub const kMagenta: Color = Color(9223372036854775807);
-                }
-                impl From<::core::ffi::c_long> for Color {
-                    fn from(value: ::core::ffi::c_long) -> Color {
-                        Color(value)
-                    }
-                }
-                impl From<Color> for ::core::ffi::c_long {
-                    fn from(value: Color) -> ::core::ffi::c_long {
-                        value.0
+                unsafe impl oops::Inherits<crate::Base2> for crate::Derived {
+                    unsafe fn upcast_ptr(derived: *const Self) -> *const crate::Base2 {
+                        (derived as *const _ as *const u8).offset(8) as *const crate::Base2
                     }
                 }
             }
         );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(8 % ::core::mem::align_of::<crate::Base2>() == 0);
+                const _: () = assert!(
+                    8 + ::core::mem::size_of::<crate::Base2>()
+                        <= ::core::mem::size_of::<crate::Derived>()
+                );
+            }
+        );
         Ok(())
     }
 
+    /// Classes with a single direct, non-virtual base class deref to it, so
+    /// that its methods can be called on the derived class.
     #[test]
-    fn test_generate_enum_with_64_bit_unsigned_vals() -> Result<()> {
+    fn test_single_base_deref() -> Result<()> {
         let ir = ir_from_cc(
-            r#" enum Color: unsigned long {
-                    kRed,
-                    kBlue,
-                    kLimeGreen = 18446744073709551615
-                }; "#,
+            "
+            struct Base { int x; };
+            struct Mid : Base {};
+            struct Derived final : Mid {};
+            struct Other { int y; };
+            struct MultipleInheritance : Base, Other {};
+            struct VirtualInheritance : virtual Base {};
+        ",
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_ulong);
-                impl Color {
-                    pub const kRed: Color = Color(0);
-                    pub const kBlue: Color = Color(1);
-                    pub const kLimeGreen: Color = Color(18446744073709551615);
-                }
-                impl From<::core::ffi::c_ulong> for Color {
-                    fn from(value: ::core::ffi::c_ulong) -> Color {
-                        Color(value)
+                impl ::core::ops::Deref for crate::Mid {
+                    type Target = crate::Base;
+                    #[inline(always)]
+                    fn deref(&self) -> &crate::Base {
+                        oops::Upcast::<&crate::Base>::upcast(self)
                     }
                 }
-                impl From<Color> for ::core::ffi::c_ulong {
-                    fn from(value: Color) -> ::core::ffi::c_ulong {
-                        value.0
-                    }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl ::core::ops::Deref for crate::Derived {
+                    type Target = crate::Mid;
+                    ...
+                }
+            }
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! {
+                impl ::core::ops::Deref for crate::Derived {
+                    type Target = crate::Base;
+                    ...
                 }
             }
         );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! { impl ::core::ops::Deref for crate::MultipleInheritance }
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! { impl ::core::ops::Deref for crate::VirtualInheritance }
+        );
+        assert_rs_not_matches!(rs_api, quote! { DerefMut });
         Ok(())
     }
 
     #[test]
-    fn test_generate_enum_with_32_bit_signed_vals() -> Result<()> {
-        let ir = ir_from_cc(
-            "enum Color { kViolet = -2147483647 - 1, kRed = -5, kBlue, kGreen = 3, kMagenta = 2147483647 };",
+    fn test_unambiguous_public_bases() -> Result<()> {
+        let ir = ir_from_cc_dependency(
+            "
+            struct VirtualBase {};
+            struct PrivateBase {};
+            struct ProtectedBase {};
+            struct UnambiguousPublicBase {};
+            struct AmbiguousPublicBase {};
+            struct MultipleInheritance : UnambiguousPublicBase, AmbiguousPublicBase {};
+            struct Derived : private PrivateBase, protected ProtectedBase, MultipleInheritance, AmbiguousPublicBase, virtual VirtualBase {};
+        ",
+            "",
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_int);
-                impl Color {
-                    pub const kViolet: Color = Color(-2147483648);
-                    pub const kRed: Color = Color(-5);
-                    pub const kBlue: Color = Color(-4);
-                    pub const kGreen: Color = Color(3);
-                    pub const kMagenta: Color = Color(2147483647);
-                }
-                impl From<::core::ffi::c_int> for Color {
-                    fn from(value: ::core::ffi::c_int) -> Color {
-                        Color(value)
-                    }
-                }
-                impl From<Color> for ::core::ffi::c_int {
-                    fn from(value: Color) -> ::core::ffi::c_int {
-                        value.0
+                unsafe impl oops::Inherits<crate::VirtualBase> for crate::Derived {
+                    unsafe fn upcast_ptr(derived: *const Self) -> *const crate::VirtualBase {
+                        crate::detail::__crubit_dynamic_upcast__7Derived__to__11VirtualBase(derived)
                     }
                 }
             }
         );
+        assert_rs_matches!(
+            rs_api,
+            quote! { unsafe impl oops::Inherits<crate::UnambiguousPublicBase> for crate::Derived }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { unsafe impl oops::Inherits<crate::MultipleInheritance> for crate::Derived }
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! {unsafe impl oops::Inherits<crate::PrivateBase> for crate::Derived}
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! {unsafe impl oops::Inherits<crate::ProtectedBase> for crate::Derived}
+        );
+        assert_rs_not_matches!(
+            rs_api,
+            quote! {unsafe impl oops::Inherits<crate::AmbiguousPublicBase> for crate::Derived}
+        );
         Ok(())
     }
 
+    /// Contrary to intuitions: a base class conversion is ambiguous even if the
+    /// ambiguity is from a private base class cast that you can't even
+    /// perform.
+    ///
+    /// Explanation (courtesy James Dennett):
+    ///
+    /// > Once upon a time, there was a rule in C++ that changing all access
+    /// > specifiers to "public" would not change the meaning of code.
+    /// > That's no longer true, but some of its effects can still be seen.
+    ///
+    /// So, we need to be sure to not allow casting to privately-ambiguous
+    /// bases.
     #[test]
-    fn test_generate_enum_with_32_bit_unsigned_vals() -> Result<()> {
-        let ir = ir_from_cc("enum Color: unsigned int { kRed, kBlue, kLimeGreen = 4294967295 };")?;
+    fn test_unambiguous_public_bases_private_ambiguity() -> Result<()> {
+        let ir = ir_from_cc_dependency(
+            "
+            struct Base {};
+            struct Intermediate : public Base {};
+            struct Derived : Base, private Intermediate {};
+        ",
+            "",
+        )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_matches!(
+        assert_rs_not_matches!(
             rs_api,
+            quote! { unsafe impl oops::Inherits<crate::Base> for Derived }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_virtual_thunk() -> Result<()> {
+        let ir = ir_from_cc("struct Polymorphic { virtual void Foo(); };")?;
+
+        assert_cc_matches!(
+            generate_bindings_tokens(ir)?.rs_api_impl,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Color(::core::ffi::c_uint);
-                impl Color {
-                    pub const kRed: Color = Color(0);
-                    pub const kBlue: Color = Color(1);
-                    pub const kLimeGreen: Color = Color(4294967295);
-                }
-                impl From<::core::ffi::c_uint> for Color {
-                    fn from(value: ::core::ffi::c_uint) -> Color {
-                        Color(value)
-                    }
-                }
-                impl From<Color> for ::core::ffi::c_uint {
-                    fn from(value: Color) -> ::core::ffi::c_uint {
-                        value.0
-                    }
-                }
+                extern "C" void __rust_thunk___ZN11Polymorphic3FooEv(struct Polymorphic * __this)
             }
         );
         Ok(())
     }
 
+    /// Calls to virtual member functions (including overrides and pure virtual
+    /// functions) go through the vtable, like they would in C++.
     #[test]
-    fn test_generate_enum_bool() -> Result<()> {
-        let ir = ir_from_cc("enum Bool : bool { kFalse, kTrue };")?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+    fn test_virtual_thunk_dispatches_dynamically() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Interface {
+              virtual int Get() const = 0;
+            };
+            struct Impl final : Interface {
+              int Get() const override;
+            };
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Bool(bool);
-                impl Bool {
-                    pub const kFalse: Bool = Bool(false);
-                    pub const kTrue: Bool = Bool(true);
-                }
-                impl From<bool> for Bool {
-                    fn from(value: bool) -> Bool {
-                        Bool(value)
+                impl Interface {
+                    #[inline(always)]
+                    pub fn Get<'a>(&'a self) -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZNK9Interface3GetEv(self) }
                     }
                 }
-                impl From<Bool> for bool {
-                    fn from(value: Bool) -> bool {
-                        value.0
-                    }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZNK9Interface3GetEv(
+                    const struct Interface* __this) {
+                    return __this->Get();
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int __rust_thunk___ZNK4Impl3GetEv(const struct Impl* __this) {
+                    return __this->Get();
                 }
             }
         );
@@ -6198,28 +15313,104 @@ mod tests {
     }
 
     #[test]
-    fn test_generate_enum_bool_alias() -> Result<()> {
-        let ir = ir_from_cc("using MyBool = bool; enum Bool : MyBool { kFalse, kTrue };")?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+    fn test_extensible_record_rust_subclass() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct [[clang::annotate("crubit_extensible")]] Listener {
+              virtual ~Listener();
+              virtual void OnEvent(int event) = 0;
+              virtual int Count() const;
+            };
+        "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub trait ListenerOverrides {
+                    fn OnEvent(&mut self, event: ::core::ffi::c_int);
+                    fn Count(&self) -> ::core::ffi::c_int;
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn new_rust_subclass<T: ListenerOverrides + Send + Sync + 'static>(imp: T)
+                    -> ListenerRustSubclass
+                {
+                    struct __Imp<T> {
+                        borrows: ::core::sync::atomic::AtomicIsize,
+                        imp: ::core::cell::UnsafeCell<T>,
+                    }
+                    ...
+                    unsafe extern "C" fn __drop<T>(imp: *mut ::core::ffi::c_void) {
+                        ::core::mem::drop(::alloc::boxed::Box::from_raw(imp as *mut __Imp<T>));
+                    }
+                    unsafe extern "C" fn __OnEvent<T: ListenerOverrides>(
+                        imp: *mut ::core::ffi::c_void, event: ::core::ffi::c_int
+                    ) {
+                        (*(imp as *const __Imp<T>)).borrow_mut(|imp| imp.OnEvent(event))
+                    }
+                    unsafe extern "C" fn __Count<T: ListenerOverrides>(
+                        imp: *const ::core::ffi::c_void
+                    ) -> ::core::ffi::c_int {
+                        (*(imp as *const __Imp<T>)).borrow(|imp| imp.Count())
+                    }
+                    let ptr = unsafe {
+                        crate::detail::__crubit_new_rust_subclass__8Listener(
+                            ::alloc::boxed::Box::into_raw(::alloc::boxed::Box::new(__Imp {
+                                borrows: ::core::sync::atomic::AtomicIsize::new(0),
+                                imp: ::core::cell::UnsafeCell::new(imp),
+                            })) as *mut ::core::ffi::c_void,
+                            __drop::<T>,
+                            __OnEvent::<T>,
+                            __Count::<T>
+                        )
+                    };
+                    ListenerRustSubclass(::core::ptr::NonNull::new(ptr).expect(...))
+                }
+            }
+        );
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(transparent)]
-                #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
-                pub struct Bool(crate::MyBool);
-                impl Bool {
-                    pub const kFalse: Bool = Bool(false);
-                    pub const kTrue: Bool = Bool(true);
-                }
-                impl From<crate::MyBool> for Bool {
-                    fn from(value: crate::MyBool) -> Bool {
-                        Bool(value)
+                impl Drop for ListenerRustSubclass {
+                    fn drop(&mut self) {
+                        unsafe { crate::detail::__crubit_delete_rust_subclass__8Listener(self.0.as_ptr()) }
                     }
                 }
-                impl From<Bool> for crate::MyBool {
-                    fn from(value: Bool) -> crate::MyBool {
-                        value.0
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { extern crate alloc; });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                class __crubit_rust_subclass__8Listener final : public Listener {
+                  public:
+                    ...
+                    ~__crubit_rust_subclass__8Listener() override { drop_(imp_); }
+                    void OnEvent(int event) override {
+                        return OnEvent_(imp_, event);
+                    }
+                    int Count() const override {
+                        return Count_(imp_);
                     }
+                    ...
+                };
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" Listener* __crubit_new_rust_subclass__8Listener(
+                    void* imp, void (*drop)(void*), void (*OnEvent)(void*, int),
+                    int (*Count)(const void*)
+                ) {
+                    return new __crubit_rust_subclass__8Listener(imp, drop, OnEvent, Count);
+                }
+                extern "C" void __crubit_delete_rust_subclass__8Listener(Listener* ptr) {
+                    delete ptr;
                 }
             }
         );
@@ -6227,615 +15418,840 @@ mod tests {
     }
 
     #[test]
-    fn test_doc_comment_func() -> Result<()> {
+    fn test_extensible_record_inherited_pure_virtual() -> Result<()> {
         let ir = ir_from_cc(
-            "
-        // Doc Comment
-        // with two lines
-        int func();",
+            r#"
+            struct Base {
+              virtual ~Base();
+              virtual void Start() = 0;
+              virtual void Stop() = 0;
+              virtual void Pause();
+            };
+            struct [[clang::annotate("crubit_extensible")]] Derived : Base {
+              void Stop() override;
+              virtual int Count() const;
+            };
+        "#,
         )?;
-
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        // `Start` must be overridden, since it is pure virtual in the base class.
         assert_rs_matches!(
-            generate_bindings_tokens(ir)?.rs_api,
-            // leading space is intentional so there is a space between /// and the text of the
-            // comment
+            rs_api,
             quote! {
-                #[doc = " Doc Comment\n with two lines\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=6"]
-                #[inline(always)]
-                pub fn func
+                pub trait DerivedOverrides {
+                    fn Stop(&mut self);
+                    fn Count(&self) -> ::core::ffi::c_int;
+                    fn Start(&mut self);
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { fn Pause(&mut self); });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_dynamic_call__7Derived___ZN4Base5StartEv(Derived* __this) {
+                    return __this->Start();
+                }
             }
         );
-
         Ok(())
     }
 
     #[test]
-    fn test_doc_comment_record() -> Result<()> {
+    fn test_extensible_record_non_public_pure_virtual() -> Result<()> {
         let ir = ir_from_cc(
-            "// Doc Comment\n\
-            //\n\
-            //  * with bullet\n\
-            struct SomeStruct final {\n\
-                // Field doc\n\
-                int field;\
-            };",
-        )?;
+            r#"
+            class [[clang::annotate("crubit_extensible")]] Listener {
+             public:
+              virtual ~Listener();
+              virtual void OnEvent(int event) = 0;
 
-        assert_rs_matches!(
-            generate_bindings_tokens(ir)?.rs_api,
-            quote! {
-                #[doc = " Doc Comment\n \n  * with bullet\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=6"]
-                #[derive(Clone, Copy)]
-                #[repr(C)]
-                pub struct SomeStruct {
-                    # [doc = " Field doc"]
-                    pub field: ::core::ffi::c_int,
-                }
-            }
-        );
+             private:
+              virtual void OnPrivateEvent() = 0;
+            };
+        "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { pub trait ListenerOverrides });
+        assert_rs_not_matches!(rs_api, quote! { extern crate alloc; });
         Ok(())
     }
 
     #[test]
-    fn test_basic_union() -> Result<()> {
+    fn test_interface_record_trait() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            #pragma clang lifetime_elision
-            union SomeUnion {
-                int some_field;
-                long long some_bigger_field;
+            class Shape {
+             public:
+              virtual ~Shape() = default;
+              virtual double Area() const = 0;
+              virtual void Scale(double factor) = 0;
             };
-            "#,
+        "#,
         )?;
         let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[derive(Clone, Copy)]
-                #[repr(C)]
-                pub union SomeUnion {
-                    pub some_field: ::core::ffi::c_int,
-                    pub some_bigger_field: ::core::ffi::c_longlong,
+                pub trait ShapeOverrides {
+                    fn Area(&self) -> f64;
+                    fn Scale(&mut self, factor: f64);
                 }
             }
         );
-        assert_cc_matches!(
-            rs_api_impl,
+        assert_rs_matches!(
+            rs_api,
             quote! {
-                extern "C" void __rust_thunk___ZN9SomeUnionC1Ev(union SomeUnion*__this) {...}
+                impl<'a> ShapeOverrides for ::core::pin::Pin<&'a mut crate::Shape> {
+                    fn Area(&self) -> f64 {
+                        unsafe { crate::detail::__crubit_dynamic_call___ZNK5Shape4AreaEv(&**self) }
+                    }
+                    fn Scale(&mut self, factor: f64) {
+                        unsafe {
+                            crate::detail::__crubit_dynamic_call___ZN5Shape5ScaleEd(
+                                self.as_mut().get_unchecked_mut(), factor
+                            )
+                        }
+                    }
+                }
             }
         );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_SIZEOF(union SomeUnion)==8) }
-        );
-        assert_cc_matches!(rs_api_impl, quote! { static_assert(alignof(union SomeUnion)==8) });
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! { static_assert(CRUBIT_OFFSET_OF(some_field, union SomeUnion)==0) }
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn new_rust_subclass<T: ShapeOverrides + Send + Sync + 'static>(imp: T)
+                    -> ShapeRustSubclass
+            }
         );
         assert_cc_matches!(
             rs_api_impl,
-            quote! { static_assert(CRUBIT_OFFSET_OF(some_bigger_field, union SomeUnion)==0) }
+            quote! {
+                extern "C" double __crubit_dynamic_call___ZNK5Shape4AreaEv(const Shape* __this) {
+                    return __this->Area();
+                }
+            }
         );
         Ok(())
     }
 
     #[test]
-    fn test_union_with_opaque_field() -> Result<()> {
+    fn test_non_interface_record_has_no_trait() -> Result<()> {
         let ir = ir_from_cc(
             r#"
-            union MyUnion {
-                char first_field[56];
-                int second_field;
-              };
-            "#,
+            class Shape {
+             public:
+              virtual ~Shape() = default;
+              virtual double Area() const = 0;
+              void Scale(double factor);
+            };
+        "#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { ShapeOverrides });
+        Ok(())
+    }
+
+    /// A trivially relocatable final struct is safe to use in Rust as normal,
+    /// and is Unpin.
+    #[test]
+    fn test_no_negative_impl_unpin() -> Result<()> {
+        let ir = ir_from_cc("struct Trivial final {};")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {#[::ctor::recursively_pinned]});
+        Ok(())
+    }
+
+    /// A non-final struct, even if it's trivial, is not usable by mut
+    /// reference, and so is !Unpin.
+    #[test]
+    fn test_negative_impl_unpin_nonfinal() -> Result<()> {
+        let ir = ir_from_cc("struct Nonfinal {};")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! {#[::ctor::recursively_pinned]});
+        Ok(())
+    }
+
+    /// At the least, a trivial type should have no drop impl if or until we add
+    /// empty drop impls.
+    #[test]
+    fn test_no_impl_drop() -> Result<()> {
+        let ir = ir_from_cc("struct Trivial {};")?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {impl Drop});
+        assert_rs_not_matches!(rs_api, quote! {impl ::ctor::PinnedDrop});
+        Ok(())
+    }
 
+    /// User-defined destructors *must* become Drop impls with ManuallyDrop
+    /// fields
+    #[test]
+    fn test_impl_drop_user_defined_destructor() -> Result<()> {
+        let ir = ir_from_cc(
+            r#" struct NontrivialStruct { ~NontrivialStruct(); };
+            struct UserDefinedDestructor {
+                ~UserDefinedDestructor();
+                int x;
+                NontrivialStruct nts;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C, align(4))]
-                pub union MyUnion { ...
-                    first_field: [::core::mem::MaybeUninit<u8>; 56],
-                    pub second_field: ::core::ffi::c_int,
+                impl ::ctor::PinnedDrop for UserDefinedDestructor {
+                    #[inline(always)]
+                    unsafe fn pinned_drop<'a>(self: ::core::pin::Pin<&'a mut Self>) {
+                        crate::detail::__rust_thunk___ZN21UserDefinedDestructorD1Ev(self)
+                    }
                 }
             }
         );
-
+        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int,});
         assert_rs_matches!(
             rs_api,
-            quote! { const _: () = assert!(::core::mem::size_of::<crate::MyUnion>() == 56); }
+            quote! {pub nts: ::core::mem::ManuallyDrop<crate::NontrivialStruct>,}
         );
+        Ok(())
+    }
+
+    /// `Unpin` types with a non-trivial copy constructor implement `Clone` by
+    /// calling it, instead of deriving `Clone`.
+    #[test]
+    fn test_impl_clone_nontrivial_copy_constructor() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"struct [[clang::annotate("crubit_rust_movable")]] SomeStruct final {
+                SomeStruct(const SomeStruct&);
+                int x;
+            };
+            struct [[clang::annotate("crubit_rust_movable")]] Uncopyable final {
+                Uncopyable(const Uncopyable&) = delete;
+                int x;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
-            quote! {  const _: () = assert!(::core::mem::align_of::<crate::MyUnion>() == 4); }
+            quote! {
+                impl Clone for SomeStruct {
+                    #[inline(always)]
+                    fn clone<'b>(&'b self) -> Self {
+                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN10SomeStructC1ERKS_(&mut tmp, self);
+                            tmp.assume_init()
+                        }
+                    }
+                }
+            }
         );
+        assert_rs_not_matches!(rs_api, quote! { #[derive(Clone)] });
+        assert_rs_not_matches!(rs_api, quote! { impl Clone for Uncopyable });
         Ok(())
     }
 
+    /// `Unpin` types with user-defined destructors implement `Drop`, and are
+    /// never `Copy`, even if their copy constructor is trivial.
     #[test]
-    // TODO(https://github.com/Gilnaa/memoffset/issues/66): generate assertions for unions once
-    // offsetof supports them.
-    fn test_currently_no_offset_assertions_for_unions() -> Result<()> {
+    fn test_impl_drop_unpin_user_defined_destructor() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            union SomeUnion {
-                int some_field;
-                long long some_bigger_field;
-            };
-            "#,
+            r#"struct [[clang::annotate("crubit_rust_movable")]] UserDefinedDestructor final {
+                ~UserDefinedDestructor();
+                int x;
+            };"#,
         )?;
         let BindingsTokens { rs_api, .. } = generate_bindings_tokens(ir)?;
-
-        assert_rs_not_matches!(rs_api, quote! { offset_of! });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl Drop for UserDefinedDestructor {
+                    #[inline(always)]
+                    fn drop<'a>(&'a mut self) {
+                        unsafe { crate::detail::__rust_thunk___ZN21UserDefinedDestructorD1Ev(self) }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { #[derive(Clone)] });
+        assert_rs_not_matches!(rs_api, quote! { #[derive(Clone, Copy)] });
+        assert_rs_matches!(
+            rs_api,
+            quote! { static_assertions::assert_not_impl_any!(crate::UserDefinedDestructor: Copy); }
+        );
         Ok(())
     }
 
+    /// nontrivial types without user-defined destructors should invoke
+    /// the C++ destructor to preserve the order of field destructions.
     #[test]
-    fn test_union_with_private_fields() -> Result<()> {
+    fn test_impl_drop_nontrivial_member_destructor() -> Result<()> {
+        // TODO(jeanpierreda): This would be cleaner if the UserDefinedDestructor code were
+        // omitted. For example, we simulate it so that UserDefinedDestructor
+        // comes from another library.
         let ir = ir_from_cc(
-            r#"
-            union SomeUnionWithPrivateFields {
-              public:
-                int public_field;
-              private:
-                long long private_field;
+            r#"struct UserDefinedDestructor final {
+                ~UserDefinedDestructor();
             };
-            "#,
+            struct TrivialStruct final { int i; };
+            struct NontrivialMembers final {
+                UserDefinedDestructor udd;
+                TrivialStruct ts;
+                int x;
+            };"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[derive(Clone, Copy)]
-                #[repr(C, align(8))]
-                pub union SomeUnionWithPrivateFields {
-                    pub public_field: ::core::ffi::c_int,
-                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
-                    pub(crate) private_field: [::core::mem::MaybeUninit<u8>; 8],
+                impl ::ctor::PinnedDrop for NontrivialMembers {
+                    #[inline(always)]
+                    unsafe fn pinned_drop<'a>(self: ::core::pin::Pin<&'a mut Self>) {
+                        crate::detail::__rust_thunk___ZN17NontrivialMembersD1Ev(self)
+                    }
                 }
             }
         );
-
+        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int,});
+        assert_rs_matches!(rs_api, quote! {pub ts: crate::TrivialStruct,});
         assert_rs_matches!(
             rs_api,
-            quote! {
-                const _: () = assert!(::core::mem::size_of::<crate::SomeUnionWithPrivateFields>() == 8);
-                const _: () = assert!(::core::mem::align_of::<crate::SomeUnionWithPrivateFields>() == 8);
-                const _: () = {
-                  static_assertions::assert_impl_all!(crate::SomeUnionWithPrivateFields: Clone);
-                };
-                const _: () = {
-                  static_assertions::assert_impl_all!(crate::SomeUnionWithPrivateFields: Copy);
-                };
-                const _: () = {
-                  static_assertions::assert_not_impl_any!(crate::SomeUnionWithPrivateFields: Drop);
-                };
-            }
+            quote! {pub udd: ::core::mem::ManuallyDrop<crate::UserDefinedDestructor>,}
         );
         Ok(())
     }
 
+    /// Trivial types (at least those that are mapped to Copy rust types) do not
+    /// get a Drop impl.
     #[test]
-    fn test_nontrivial_unions() -> Result<()> {
-        let ir = ir_from_cc_dependency(
-            r#"
-            union UnionWithNontrivialField {
-                NonTrivialStruct my_field;
-            };
-            "#,
-            r#"
-            struct NonTrivialStruct {
-                NonTrivialStruct(NonTrivialStruct&&);
-            };
-            "#,
+    fn test_impl_drop_trivial() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"struct Trivial final {
+                ~Trivial() = default;
+                int x;
+            };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Copy ... )});
-        assert_rs_not_matches!(rs_api, quote! {derive ( ... Clone ... )});
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[::ctor::recursively_pinned]
-                #[repr(C)]
-                pub union UnionWithNontrivialField { ... }
-            }
-        );
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_not_matches!(rs_api, quote! {impl Drop});
+        assert_rs_not_matches!(rs_api, quote! {impl ::ctor::PinnedDrop});
+        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int});
+        assert_cc_not_matches!(rs_api_impl, quote! { std::destroy_at });
         Ok(())
     }
 
     #[test]
-    fn test_empty_struct() -> Result<()> {
+    fn test_impl_default_explicitly_defaulted_constructor() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            struct EmptyStruct final {};
-            "#,
+            r#"#pragma clang lifetime_elision
+            struct DefaultedConstructor final {
+                DefaultedConstructor() = default;
+            };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[derive(Clone, Copy)]
-                #[repr(C)]
-                pub struct EmptyStruct {
-                    ...
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
+                impl Default for DefaultedConstructor {
+                    #[inline(always)]
+                    fn default() -> Self {
+                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN20DefaultedConstructorC1Ev(&mut tmp);
+                            tmp.assume_init()
+                        }
+                    }
                 }
             }
         );
-
         assert_rs_matches!(
             rs_api,
             quote! {
-                const _: () = assert!(::core::mem::size_of::<crate::EmptyStruct>() == 1);
-                const _: () = assert!(::core::mem::align_of::<crate::EmptyStruct>() == 1);
+                impl DefaultedConstructor {
+                    #[inline(always)]
+                    pub fn new() -> Self {
+                        Default::default()
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN20DefaultedConstructorC1Ev(
+                        struct DefaultedConstructor* __this) {
+                    crubit::UnpoisonPlacementStorage(__this);
+                    crubit::construct_at(__this);
+                }
             }
         );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_clone_that_propagates_lifetime() -> Result<()> {
+        // This test covers the case where a single lifetime applies to 1)
+        // the `__this` parameter and 2) other constructor parameters. For
+        // example, maybe the newly constructed object needs to have the
+        // same lifetime as the constructor's parameter. (This might require
+        // annotating the whole C++ struct with a lifetime, so maybe the
+        // example below is not fully realistic/accurate...).
+        let ir = ir_from_cc(&with_lifetime_macros(
+            r#"#pragma clang lifetime_elision
+            struct Foo final {
+                Foo(const int& $a i) $a;
+            };"#,
+        ))?;
+        let ctor: &Func = ir
+            .items()
+            .filter_map(|item| match item {
+                Item::Func(func) => Some(&**func),
+                _ => None,
+            })
+            .find(|f| {
+                matches!(&f.name, UnqualifiedIdentifier::Constructor)
+                    && f.params
+                        .get(1)
+                        .map(|p| p.identifier.identifier.as_ref() == "i")
+                        .unwrap_or_default()
+            })
+            .unwrap();
+        {
+            // Double-check that the test scenario set up above uses the same lifetime
+            // for both of the constructor's parameters: `__this` and `i`.
+            assert_eq!(ctor.params.len(), 2);
+            let this_lifetime: LifetimeId =
+                *ctor.params[0].type_.rs_type.lifetime_args.first().unwrap();
+            let i_lifetime: LifetimeId =
+                *ctor.params[1].type_.rs_type.lifetime_args.first().unwrap();
+            assert_eq!(i_lifetime, this_lifetime);
+        }
 
+        // Before cl/423346348 the generated Rust code would incorrectly look
+        // like this (note the mismatched 'a and 'b lifetimes):
+        //     fn from<'b>(i: &'a i32) -> Self
+        // After this CL, this scenario will result in an explicit error.
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {impl From});
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=34\n\
+                           Error while generating bindings for item 'Foo::Foo':\n\
+                           The lifetime of `__this` is \
+                               unexpectedly also used by another parameter: Lifetime(\"a\")";
+            quote! { __COMMENT__ #txt }
+        });
         Ok(())
     }
 
     #[test]
-    fn test_empty_union() -> Result<()> {
+    fn test_impl_default_non_trivial_struct() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            union EmptyUnion {};
-            "#,
+            r#"#pragma clang lifetime_elision
+            struct NonTrivialStructWithConstructors final {
+                NonTrivialStructWithConstructors();
+                ~NonTrivialStructWithConstructors();  // Non-trivial
+            };"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {impl Default});
+        assert_rs_not_matches!(rs_api, quote! {pub fn new});
+        Ok(())
+    }
 
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[derive(Clone, Copy)]
-                #[repr(C)]
-                pub union EmptyUnion {
-                    ...
-                    __non_field_data: [::core::mem::MaybeUninit<u8>; 1],
+    #[test]
+    fn test_impl_from_for_1_arg_constructor() -> Result<()> {
+        for explicit_qualifier in ["", "explicit"] {
+            let ir = ir_from_cc(&format!(
+                r#"#pragma clang lifetime_elision
+                struct SomeStruct final {{
+                    {explicit_qualifier} SomeStruct(int i);  // implicit - no `explicit` keyword
+                }};"#,
+            ))?;
+            let rs_api = generate_bindings_tokens(ir)?.rs_api;
+            assert_rs_matches!(
+                rs_api,
+                quote! {
+                    impl From<::core::ffi::c_int> for SomeStruct {
+                        #[inline(always)]
+                        fn from(i: ::core::ffi::c_int) -> Self {
+                            let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                            unsafe {
+                                crate::detail::__rust_thunk___ZN10SomeStructC1Ei(&mut tmp, i);
+                                tmp.assume_init()
+                            }
+                        }
+                    }
                 }
-            }
-        );
+            );
+        }
+        Ok(())
+    }
 
+    #[test]
+    fn test_impl_from_for_implicit_conversion_from_reference() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeOtherStruct final { int i; };
+            struct StructUnderTest final {
+                StructUnderTest(const SomeOtherStruct& other);  // implicit - no `explicit` keyword
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // This is a regression test for b/223800038: We want to ensure that the
+        // code says `impl<'b>` (instead of incorrectly declaring that lifetime
+        // in `fn from<'b>`).
         assert_rs_matches!(
             rs_api,
             quote! {
-                const _: () = assert!(::core::mem::size_of::<crate::EmptyUnion>() == 1);
-                const _: () = assert!(::core::mem::align_of::<crate::EmptyUnion>() == 1);
-            }
+                impl<'b> From<&'b crate::SomeOtherStruct> for StructUnderTest {
+                    #[inline(always)]
+                    fn from(other: &'b crate::SomeOtherStruct) -> Self {
+                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
+                        unsafe {
+                            crate::detail::__rust_thunk___ZN15StructUnderTestC1ERK15SomeOtherStruct(
+                                &mut tmp, other);
+                            tmp.assume_init()
+                        }
+                    }
+                }
+            },
         );
+        Ok(())
+    }
+
+    /// Methods with missing lifetimes for `self` should give a useful error
+    /// message.
+    #[test]
+    fn test_eq_nolifetime() -> Result<()> {
+        // Missing lifetimes currently only causes hard errors for trait impls,
+        // not For inherent methods.
+        let ir = ir_from_cc("struct SomeStruct{SomeStruct& operator=(const SomeStruct&);};")?;
 
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'SomeStruct::operator=':\n\
+             // `self` has no lifetime. Use lifetime annotations or \
+                `#pragma clang lifetime_elision` to create bindings for this function."
+        ));
         Ok(())
     }
 
     #[test]
-    fn test_union_field_with_nontrivial_destructor() -> Result<()> {
+    fn test_impl_eq_for_member_function() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            struct NontrivialStruct { ~NontrivialStruct(); };
-            union UnionWithNontrivialField {
-                int trivial_field;
-                NontrivialStruct nontrivial_field;
-            };
-            "#,
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const {
+                    return i == other.i;
+                }
+                int i;
+            };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                #[repr(C)]
-                pub union UnionWithNontrivialField {
-                    pub trivial_field: ::core::ffi::c_int,
-                    pub nontrivial_field: ::core::mem::ManuallyDrop<crate::NontrivialStruct>,
+                impl PartialEq for SomeStruct {
+                    #[inline(always)]
+                    fn eq<'a, 'b>(&'a self, other: &'b Self) -> bool {
+                        unsafe { crate::detail::__rust_thunk___ZNK10SomeStructeqERKS_(self, other) }
+                    }
                 }
             }
         );
-
-        assert_rs_matches!(
-            rs_api,
+        assert_cc_matches!(
+            rs_api_impl,
             quote! {
-                const _: () = assert!(::core::mem::size_of::<crate::UnionWithNontrivialField>() == 4);
-                const _: () = assert!(::core::mem::align_of::<crate::UnionWithNontrivialField>() == 4);
+                extern "C" bool __rust_thunk___ZNK10SomeStructeqERKS_(
+                        const struct SomeStruct* __this, const struct SomeStruct* other) {
+                    return __this->operator==(*other);
+                }
             }
         );
         Ok(())
     }
 
     #[test]
-    fn test_union_with_constructors() -> Result<()> {
+    fn test_impl_eq_for_free_function() -> Result<()> {
         let ir = ir_from_cc(
-            r#"
-            #pragma clang lifetime_elision
-            union UnionWithDefaultConstructors {
-                int a;
-            };
-            "#,
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final { int i; };
+            bool operator==(const SomeStruct& lhs, const SomeStruct& rhs) {
+                return lhs.i == rhs.i;
+            }"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                #[derive(Clone, Copy)]
-                #[repr(C)]
-                pub union UnionWithDefaultConstructors {
-                    pub a: ::core::ffi::c_int,
-                }
-            }
-        );
-
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl Default for UnionWithDefaultConstructors {
+                impl PartialEq for SomeStruct {
                     #[inline(always)]
-                    fn default() -> Self {
-                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
-                        unsafe {
-                            crate::detail::__rust_thunk___ZN28UnionWithDefaultConstructorsC1Ev(&mut tmp);
-                            tmp.assume_init()
-                        }
+                    fn eq<'a, 'b>(&'a self, rhs: &'b Self) -> bool {
+                        unsafe { crate::detail::__rust_thunk___ZeqRK10SomeStructS1_(self, rhs) }
                     }
                 }
             }
         );
+        Ok(())
+    }
 
+    #[test]
+    fn test_impl_eq_for_free_function_different_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final { int i; };
+            struct SomeOtherStruct final { int i; };
+            bool operator==(const SomeStruct& lhs, const SomeOtherStruct& rhs) {
+                return lhs.i == rhs.i;
+            }"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl<'b> From<::ctor::RvalueReference<'b, Self>> for UnionWithDefaultConstructors {
+                impl PartialEq<crate::SomeOtherStruct> for SomeStruct {
                     #[inline(always)]
-                    fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
-                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
-                        unsafe {
-                            crate::detail::__rust_thunk___ZN28UnionWithDefaultConstructorsC1EOS_(&mut tmp, __param_0);
-                            tmp.assume_init()
-                        }
+                    fn eq<'a, 'b>(&'a self, rhs: &'b crate::SomeOtherStruct) -> bool {
+                        unsafe { crate::detail::__rust_thunk___ZeqRK10SomeStructRK15SomeOtherStruct(self, rhs) }
                     }
                 }
             }
         );
-
         Ok(())
     }
 
     #[test]
-    fn test_unambiguous_public_bases() -> Result<()> {
-        let ir = ir_from_cc_dependency(
-            "
-            struct VirtualBase {};
-            struct PrivateBase {};
-            struct ProtectedBase {};
-            struct UnambiguousPublicBase {};
-            struct AmbiguousPublicBase {};
-            struct MultipleInheritance : UnambiguousPublicBase, AmbiguousPublicBase {};
-            struct Derived : private PrivateBase, protected ProtectedBase, MultipleInheritance, AmbiguousPublicBase, virtual VirtualBase {};
-        ",
-            "",
+    fn test_impl_eq_for_free_function_by_value() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final { int i; };
+            bool operator==(SomeStruct lhs, SomeStruct rhs) {
+                return lhs.i == rhs.i;
+            }"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
         assert_rs_matches!(
             rs_api,
             quote! {
-                unsafe impl oops::Inherits<crate::VirtualBase> for crate::Derived {
-                    unsafe fn upcast_ptr(derived: *const Self) -> *const crate::VirtualBase {
-                        crate::detail::__crubit_dynamic_upcast__7Derived__to__11VirtualBase(derived)
+                impl PartialEq for SomeStruct {
+                    #[inline(always)]
+                    fn eq(& self, rhs: & Self) -> bool {
+                        unsafe { crate::detail::__rust_thunk___Zeq10SomeStructS_(
+                                self.clone(), rhs.clone()) }
                     }
                 }
             }
         );
-        assert_rs_matches!(
-            rs_api,
-            quote! { unsafe impl oops::Inherits<crate::UnambiguousPublicBase> for crate::Derived }
-        );
-        assert_rs_matches!(
-            rs_api,
-            quote! { unsafe impl oops::Inherits<crate::MultipleInheritance> for crate::Derived }
-        );
-        assert_rs_not_matches!(
-            rs_api,
-            quote! {unsafe impl oops::Inherits<crate::PrivateBase> for crate::Derived}
-        );
-        assert_rs_not_matches!(
-            rs_api,
-            quote! {unsafe impl oops::Inherits<crate::ProtectedBase> for crate::Derived}
-        );
-        assert_rs_not_matches!(
-            rs_api,
-            quote! {unsafe impl oops::Inherits<crate::AmbiguousPublicBase> for crate::Derived}
-        );
         Ok(())
     }
 
-    /// Contrary to intuitions: a base class conversion is ambiguous even if the
-    /// ambiguity is from a private base class cast that you can't even
-    /// perform.
-    ///
-    /// Explanation (courtesy James Dennett):
-    ///
-    /// > Once upon a time, there was a rule in C++ that changing all access
-    /// > specifiers to "public" would not change the meaning of code.
-    /// > That's no longer true, but some of its effects can still be seen.
-    ///
-    /// So, we need to be sure to not allow casting to privately-ambiguous
-    /// bases.
     #[test]
-    fn test_unambiguous_public_bases_private_ambiguity() -> Result<()> {
-        let ir = ir_from_cc_dependency(
-            "
-            struct Base {};
-            struct Intermediate : public Base {};
-            struct Derived : Base, private Intermediate {};
-        ",
-            "",
+    fn test_impl_eq_noexcept() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const noexcept {
+                    return i == other.i;
+                }
+                int i;
+            };"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(
-            rs_api,
-            quote! { unsafe impl oops::Inherits<crate::Base> for Derived }
-        );
-        Ok(())
-    }
-
-    #[test]
-    fn test_virtual_thunk() -> Result<()> {
-        let ir = ir_from_cc("struct Polymorphic { virtual void Foo(); };")?;
-
-        assert_cc_matches!(
-            generate_bindings_tokens(ir)?.rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___ZN11Polymorphic3FooEv(struct Polymorphic * __this)
-            }
-        );
+        assert_rs_matches!(rs_api, quote! { impl PartialEq for SomeStruct });
+        assert_rs_matches!(rs_api, quote! { impl Eq for SomeStruct {} });
         Ok(())
     }
 
-    /// A trivially relocatable final struct is safe to use in Rust as normal,
-    /// and is Unpin.
     #[test]
-    fn test_no_negative_impl_unpin() -> Result<()> {
-        let ir = ir_from_cc("struct Trivial final {};")?;
+    fn test_impl_eq_not_noexcept() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const {
+                    return i == other.i;
+                }
+                int i;
+            };"#,
+        )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {#[::ctor::recursively_pinned]});
+        assert_rs_matches!(rs_api, quote! { impl PartialEq for SomeStruct });
+        assert_rs_not_matches!(rs_api, quote! { impl Eq for SomeStruct });
         Ok(())
     }
 
-    /// A non-final struct, even if it's trivial, is not usable by mut
-    /// reference, and so is !Unpin.
     #[test]
-    fn test_negative_impl_unpin_nonfinal() -> Result<()> {
-        let ir = ir_from_cc("struct Nonfinal {};")?;
+    fn test_impl_eq_noexcept_different_types() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final { int i; };
+            struct SomeOtherStruct final { int i; };
+            bool operator==(const SomeStruct& lhs, const SomeOtherStruct& rhs) noexcept {
+                return lhs.i == rhs.i;
+            }"#,
+        )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_matches!(rs_api, quote! {#[::ctor::recursively_pinned]});
+        assert_rs_matches!(
+            rs_api,
+            quote! { impl PartialEq<crate::SomeOtherStruct> for SomeStruct }
+        );
+        assert_rs_not_matches!(rs_api, quote! { impl Eq });
         Ok(())
     }
 
-    /// At the least, a trivial type should have no drop impl if or until we add
-    /// empty drop impls.
     #[test]
-    fn test_no_impl_drop() -> Result<()> {
-        let ir = ir_from_cc("struct Trivial {};")?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {impl Drop});
-        assert_rs_not_matches!(rs_api, quote! {impl ::ctor::PinnedDrop});
+    fn test_impl_ne_with_eq() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const {
+                    return i == other.i;
+                }
+                inline bool operator!=(const SomeStruct& other) const {
+                    return i != other.i;
+                }
+                int i;
+            };"#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains("impl PartialEq for SomeStruct"));
+        assert!(!rs_api.contains("operator!="));
+        assert!(!rs_api.contains("__rust_thunk___ZNK10SomeStructneERKS_"));
         Ok(())
     }
 
-    /// User-defined destructors *must* become Drop impls with ManuallyDrop
-    /// fields
     #[test]
-    fn test_impl_drop_user_defined_destructor() -> Result<()> {
+    fn test_impl_ne_missing_eq() -> Result<()> {
         let ir = ir_from_cc(
-            r#" struct NontrivialStruct { ~NontrivialStruct(); };
-            struct UserDefinedDestructor {
-                ~UserDefinedDestructor();
-                int x;
-                NontrivialStruct nts;
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator!=(const SomeStruct& other) const {
+                    return i != other.i;
+                }
+                int i;
+            };"#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'SomeStruct::operator!=':\n\
+             // operator!= where operator== is missing."
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_index_and_index_mut() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            typedef decltype(sizeof(0)) size_t;
+            struct SomeStruct final {
+                inline const int& operator[](size_t i) const { return values[i]; }
+                inline int& operator[](size_t i) { return values[i]; }
+                int values[4];
             };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl ::ctor::PinnedDrop for UserDefinedDestructor {
+                impl ::core::ops::Index<usize> for crate::SomeStruct {
+                    type Output = ::core::ffi::c_int;
                     #[inline(always)]
-                    unsafe fn pinned_drop<'a>(self: ::core::pin::Pin<&'a mut Self>) {
-                        crate::detail::__rust_thunk___ZN21UserDefinedDestructorD1Ev(self)
+                    fn index<'a>(&'a self, i: usize) -> &'a ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZNK10SomeStructixEm(self, i) }
                     }
                 }
             }
         );
-        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int,});
         assert_rs_matches!(
             rs_api,
-            quote! {pub nts: ::core::mem::ManuallyDrop<crate::NontrivialStruct>,}
+            quote! {
+                impl ::core::ops::IndexMut<usize> for crate::SomeStruct {
+                    #[inline(always)]
+                    fn index_mut<'a>(&'a mut self, i: usize) -> &'a mut ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN10SomeStructixEm(self, i) }
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int* __rust_thunk___ZN10SomeStructixEm(
+                        struct SomeStruct* __this, size_t i) {
+                    return &__this->operator[](i);
+                }
+            }
         );
         Ok(())
     }
 
-    /// nontrivial types without user-defined destructors should invoke
-    /// the C++ destructor to preserve the order of field destructions.
     #[test]
-    fn test_impl_drop_nontrivial_member_destructor() -> Result<()> {
-        // TODO(jeanpierreda): This would be cleaner if the UserDefinedDestructor code were
-        // omitted. For example, we simulate it so that UserDefinedDestructor
-        // comes from another library.
+    fn test_impl_index_mut_without_const_overload() -> Result<()> {
         let ir = ir_from_cc(
-            r#"struct UserDefinedDestructor final {
-                ~UserDefinedDestructor();
-            };
-            struct TrivialStruct final { int i; };
-            struct NontrivialMembers final {
-                UserDefinedDestructor udd;
-                TrivialStruct ts;
-                int x;
+            r#"#pragma clang lifetime_elision
+            typedef decltype(sizeof(0)) size_t;
+            struct SomeStruct final {
+                int& operator[](size_t i);
+                int values[4];
             };"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { IndexMut });
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl ::ctor::PinnedDrop for NontrivialMembers {
+                impl SomeStruct {
                     #[inline(always)]
-                    unsafe fn pinned_drop<'a>(self: ::core::pin::Pin<&'a mut Self>) {
-                        crate::detail::__rust_thunk___ZN17NontrivialMembersD1Ev(self)
+                    pub fn get_mut<'a>(&'a mut self, i: usize) -> &'a mut ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN10SomeStructixEm(self, i) }
                     }
                 }
             }
         );
-        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int,});
-        assert_rs_matches!(rs_api, quote! {pub ts: crate::TrivialStruct,});
-        assert_rs_matches!(
-            rs_api,
-            quote! {pub udd: ::core::mem::ManuallyDrop<crate::UserDefinedDestructor>,}
-        );
         Ok(())
     }
 
-    /// Trivial types (at least those that are mapped to Copy rust types) do not
-    /// get a Drop impl.
     #[test]
-    fn test_impl_drop_trivial() -> Result<()> {
+    fn test_impl_index_returning_value() -> Result<()> {
         let ir = ir_from_cc(
-            r#"struct Trivial final {
-                ~Trivial() = default;
-                int x;
+            r#"#pragma clang lifetime_elision
+            typedef decltype(sizeof(0)) size_t;
+            struct SomeStruct final {
+                int operator[](size_t i) const;
+                int values[4];
             };"#,
         )?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-        assert_rs_not_matches!(rs_api, quote! {impl Drop});
-        assert_rs_not_matches!(rs_api, quote! {impl ::ctor::PinnedDrop});
-        assert_rs_matches!(rs_api, quote! {pub x: ::core::ffi::c_int});
-        assert_cc_not_matches!(rs_api_impl, quote! { std::destroy_at });
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { Index });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl SomeStruct {
+                    #[inline(always)]
+                    pub fn get<'a>(&'a self, i: usize) -> ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZNK10SomeStructixEm(self, i) }
+                    }
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_impl_default_explicitly_defaulted_constructor() -> Result<()> {
+    fn test_call_operator() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
-            struct DefaultedConstructor final {
-                DefaultedConstructor() = default;
+            struct SomeStruct final {
+                inline bool operator()(int lhs, int rhs) const { return lhs < rhs; }
             };"#,
         )?;
         let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl Default for DefaultedConstructor {
+                impl SomeStruct {
                     #[inline(always)]
-                    fn default() -> Self {
-                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
-                        unsafe {
-                            crate::detail::__rust_thunk___ZN20DefaultedConstructorC1Ev(&mut tmp);
-                            tmp.assume_init()
-                        }
+                    pub fn call<'a>(
+                        &'a self, lhs: ::core::ffi::c_int, rhs: ::core::ffi::c_int
+                    ) -> bool {
+                        unsafe { crate::detail::__rust_thunk___ZNK10SomeStructclEii(self, lhs, rhs) }
                     }
                 }
             }
@@ -6843,9 +16259,9 @@ mod tests {
         assert_cc_matches!(
             rs_api_impl,
             quote! {
-                extern "C" void __rust_thunk___ZN20DefaultedConstructorC1Ev(
-                        struct DefaultedConstructor* __this) {
-                    crubit::construct_at(__this);
+                extern "C" bool __rust_thunk___ZNK10SomeStructclEii(
+                        const struct SomeStruct* __this, int lhs, int rhs) {
+                    return __this->operator()(lhs, rhs);
                 }
             }
         );
@@ -6853,160 +16269,35 @@ mod tests {
     }
 
     #[test]
-    fn test_impl_clone_that_propagates_lifetime() -> Result<()> {
-        // This test covers the case where a single lifetime applies to 1)
-        // the `__this` parameter and 2) other constructor parameters. For
-        // example, maybe the newly constructed object needs to have the
-        // same lifetime as the constructor's parameter. (This might require
-        // annotating the whole C++ struct with a lifetime, so maybe the
-        // example below is not fully realistic/accurate...).
-        let ir = ir_from_cc(&with_lifetime_macros(
-            r#"#pragma clang lifetime_elision
-            struct Foo final {
-                Foo(const int& $a i) $a;
-            };"#,
-        ))?;
-        let ctor: &Func = ir
-            .items()
-            .filter_map(|item| match item {
-                Item::Func(func) => Some(&**func),
-                _ => None,
-            })
-            .find(|f| {
-                matches!(&f.name, UnqualifiedIdentifier::Constructor)
-                    && f.params
-                        .get(1)
-                        .map(|p| p.identifier.identifier.as_ref() == "i")
-                        .unwrap_or_default()
-            })
-            .unwrap();
-        {
-            // Double-check that the test scenario set up above uses the same lifetime
-            // for both of the constructor's parameters: `__this` and `i`.
-            assert_eq!(ctor.params.len(), 2);
-            let this_lifetime: LifetimeId =
-                *ctor.params[0].type_.rs_type.lifetime_args.first().unwrap();
-            let i_lifetime: LifetimeId =
-                *ctor.params[1].type_.rs_type.lifetime_args.first().unwrap();
-            assert_eq!(i_lifetime, this_lifetime);
-        }
-
-        // Before cl/423346348 the generated Rust code would incorrectly look
-        // like this (note the mismatched 'a and 'b lifetimes):
-        //     fn from<'b>(i: &'a i32) -> Self
-        // After this CL, this scenario will result in an explicit error.
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {impl From});
-        assert_rs_matches!(rs_api, {
-            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=34\n\
-                           Error while generating bindings for item 'Foo::Foo':\n\
-                           The lifetime of `__this` is \
-                               unexpectedly also used by another parameter: Lifetime(\"a\")";
-            quote! { __COMMENT__ #txt }
-        });
-        Ok(())
-    }
-
-    #[test]
-    fn test_impl_default_non_trivial_struct() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct NonTrivialStructWithConstructors final {
-                NonTrivialStructWithConstructors();
-                ~NonTrivialStructWithConstructors();  // Non-trivial
-            };"#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {impl Default});
-        Ok(())
-    }
-
-    #[test]
-    fn test_impl_from_for_1_arg_constructor() -> Result<()> {
-        for explicit_qualifier in ["", "explicit"] {
-            let ir = ir_from_cc(&format!(
-                r#"#pragma clang lifetime_elision
-                struct SomeStruct final {{
-                    {explicit_qualifier} SomeStruct(int i);  // implicit - no `explicit` keyword
-                }};"#,
-            ))?;
-            let rs_api = generate_bindings_tokens(ir)?.rs_api;
-            assert_rs_matches!(
-                rs_api,
-                quote! {
-                    impl From<::core::ffi::c_int> for SomeStruct {
-                        #[inline(always)]
-                        fn from(i: ::core::ffi::c_int) -> Self {
-                            let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
-                            unsafe {
-                                crate::detail::__rust_thunk___ZN10SomeStructC1Ei(&mut tmp, i);
-                                tmp.assume_init()
-                            }
-                        }
-                    }
-                }
-            );
-        }
-        Ok(())
-    }
-
-    #[test]
-    fn test_impl_from_for_implicit_conversion_from_reference() -> Result<()> {
+    fn test_call_operator_non_const() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
-            struct SomeOtherStruct final { int i; };
-            struct StructUnderTest final {
-                StructUnderTest(const SomeOtherStruct& other);  // implicit - no `explicit` keyword
+            struct SomeStruct final {
+                void operator()(int value);
+                int sum;
             };"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        // This is a regression test for b/223800038: We want to ensure that the
-        // code says `impl<'b>` (instead of incorrectly declaring that lifetime
-        // in `fn from<'b>`).
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl<'b> From<&'b crate::SomeOtherStruct> for StructUnderTest {
+                impl SomeStruct {
                     #[inline(always)]
-                    fn from(other: &'b crate::SomeOtherStruct) -> Self {
-                        let mut tmp = ::core::mem::MaybeUninit::<Self>::zeroed();
-                        unsafe {
-                            crate::detail::__rust_thunk___ZN15StructUnderTestC1ERK15SomeOtherStruct(
-                                &mut tmp, other);
-                            tmp.assume_init()
-                        }
+                    pub fn call_mut<'a>(&'a mut self, value: ::core::ffi::c_int) {
+                        unsafe { crate::detail::__rust_thunk___ZN10SomeStructclEi(self, value) }
                     }
                 }
-            },
+            }
         );
         Ok(())
     }
 
-    /// Methods with missing lifetimes for `self` should give a useful error
-    /// message.
-    #[test]
-    fn test_eq_nolifetime() -> Result<()> {
-        // Missing lifetimes currently only causes hard errors for trait impls,
-        // not For inherent methods.
-        let ir = ir_from_cc("struct SomeStruct{SomeStruct& operator=(const SomeStruct&);};")?;
-
-        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
-        assert!(rs_api.contains(
-            "// Error while generating bindings for item 'SomeStruct::operator=':\n\
-             // `self` has no lifetime. Use lifetime annotations or \
-                `#pragma clang lifetime_elision` to create bindings for this function."
-        ));
-        Ok(())
-    }
-
     #[test]
-    fn test_impl_eq_for_member_function() -> Result<()> {
+    fn test_conversion_function_to_primitive() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
             struct SomeStruct final {
-                inline bool operator==(const SomeStruct& other) const {
-                    return i == other.i;
-                }
+                explicit operator int() const { return i; }
                 int i;
             };"#,
         )?;
@@ -7014,20 +16305,28 @@ mod tests {
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl PartialEq for SomeStruct {
+                impl<'a> From<&'a crate::SomeStruct> for ::core::ffi::c_int {
                     #[inline(always)]
-                    fn eq<'a, 'b>(&'a self, other: &'b Self) -> bool {
-                        unsafe { crate::detail::__rust_thunk___ZNK10SomeStructeqERKS_(self, other) }
+                    fn from(value: &'a crate::SomeStruct) -> Self {
+                        unsafe { crate::detail::__crubit_conversion___ZNK10SomeStructcviEv(value) }
                     }
                 }
             }
         );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __crubit_conversion___ZNK10SomeStructcviEv(
+                    source: *const crate::SomeStruct
+                ) -> ::core::ffi::c_int;
+            }
+        );
         assert_cc_matches!(
             rs_api_impl,
             quote! {
-                extern "C" bool __rust_thunk___ZNK10SomeStructeqERKS_(
-                        const struct SomeStruct* __this, const struct SomeStruct* other) {
-                    return __this->operator==(*other);
+                extern "C" int __crubit_conversion___ZNK10SomeStructcviEv(
+                        const struct SomeStruct* source) {
+                    return source->operator int();
                 }
             }
         );
@@ -7035,76 +16334,133 @@ mod tests {
     }
 
     #[test]
-    fn test_impl_eq_for_free_function() -> Result<()> {
+    fn test_conversion_function_to_record() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
-            struct SomeStruct final { int i; };
-            bool operator==(const SomeStruct& lhs, const SomeStruct& rhs) {
-                return lhs.i == rhs.i;
-            }"#,
+            struct Target final {
+                int i;
+            };
+            struct SomeStruct final {
+                operator Target() const { return Target{i}; }
+                int i;
+            };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl PartialEq for SomeStruct {
+                impl<'a> From<&'a crate::SomeStruct> for crate::Target {
                     #[inline(always)]
-                    fn eq<'a, 'b>(&'a self, rhs: &'b Self) -> bool {
-                        unsafe { crate::detail::__rust_thunk___ZeqRK10SomeStructS1_(self, rhs) }
+                    fn from(value: &'a crate::SomeStruct) -> Self {
+                        let mut __return = ::core::mem::MaybeUninit::<Self>::uninit();
+                        unsafe {
+                            crate::detail::__crubit_conversion___ZNK10SomeStructcv6TargetEv(
+                                __return.as_mut_ptr(),
+                                value
+                            );
+                            __return.assume_init()
+                        }
                     }
                 }
             }
         );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_conversion___ZNK10SomeStructcv6TargetEv(
+                        struct Target* __return, const struct SomeStruct* source) {
+                    new (__return) auto(source->operator struct Target());
+                }
+            }
+        );
         Ok(())
     }
 
     #[test]
-    fn test_impl_eq_for_free_function_different_types() -> Result<()> {
+    fn test_conversion_function_non_const() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
-            struct SomeStruct final { int i; };
-            struct SomeOtherStruct final { int i; };
-            bool operator==(const SomeStruct& lhs, const SomeOtherStruct& rhs) {
-                return lhs.i == rhs.i;
-            }"#,
+            struct SomeStruct final {
+                operator int();
+            };"#,
         )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'SomeStruct::operator int':\n\
+             // Non-const conversion functions are not supported"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_display_for_stream_insertion_operator() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+              template <typename CharT> class basic_ostream {};
+              using ostream = basic_ostream<char>;
+            }
+            struct SomeStruct final {
+                int i;
+            };
+            std::ostream& operator<<(std::ostream& os, const SomeStruct& value);"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
         assert_rs_matches!(
             rs_api,
             quote! {
-                impl PartialEq<crate::SomeOtherStruct> for SomeStruct {
-                    #[inline(always)]
-                    fn eq<'a, 'b>(&'a self, rhs: &'b crate::SomeOtherStruct) -> bool {
-                        unsafe { crate::detail::__rust_thunk___ZeqRK10SomeStructRK15SomeOtherStruct(self, rhs) }
+                impl ::core::fmt::Display for crate::SomeStruct {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        ...
+                        unsafe {
+                            crate::detail::__crubit_display__10SomeStruct(
+                                self,
+                                &mut output as *mut _ as *mut ::core::ffi::c_void,
+                                append,
+                            );
+                        }
+                        output.1
                     }
                 }
             }
         );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __crubit_display__10SomeStruct(
+                        const struct SomeStruct* value,
+                        void* output,
+                        void (*append)(void*, const char*, size_t)) {
+                    std::ostringstream stream;
+                    stream << *value;
+                    const std::string str = stream.str();
+                    append(output, str.data(), str.size());
+                }
+            }
+        );
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ include <sstream> });
         Ok(())
     }
 
     #[test]
-    fn test_impl_eq_for_free_function_by_value() -> Result<()> {
+    fn test_no_display_without_stream_insertion_operator() -> Result<()> {
         let ir = ir_from_cc(
             r#"#pragma clang lifetime_elision
-            struct SomeStruct final { int i; };
-            bool operator==(SomeStruct lhs, SomeStruct rhs) {
-                return lhs.i == rhs.i;
-            }"#,
+            namespace std {
+              template <typename CharT> class basic_ostream {};
+              using ostream = basic_ostream<char>;
+            }
+            struct SomeStruct final {
+                int i;
+            };
+            struct OtherStruct final {
+                int i;
+            };
+            std::ostream& operator<<(std::ostream& os, const OtherStruct& value);"#,
         )?;
         let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_matches!(
-            rs_api,
-            quote! {
-                impl PartialEq for SomeStruct {
-                    #[inline(always)]
-                    fn eq(& self, rhs: & Self) -> bool {
-                        unsafe { crate::detail::__rust_thunk___Zeq10SomeStructS_(
-                                &mut self.clone(), &mut rhs.clone()) }
-                    }
-                }
-            }
-        );
+        assert_rs_not_matches!(rs_api, quote! { impl ::core::fmt::Display for crate::SomeStruct });
+        assert_rs_matches!(rs_api, quote! { impl ::core::fmt::Display for crate::OtherStruct });
         Ok(())
     }
 
@@ -7234,7 +16590,7 @@ mod tests {
                     #[inline(always)]
                     fn lt(& self, rhs: &Self) -> bool {
                         unsafe { crate::detail::__rust_thunk___Zlt10SomeStructS_(
-                                &mut self.clone(), &mut rhs.clone()) }
+                                self.clone(), rhs.clone()) }
                     }
                 }
             }
@@ -7380,28 +16736,305 @@ mod tests {
                 inline bool operator==(const SomeStruct& other) const {
                     return i == other.i;
                 }
-                int i;
-                bool operator<(SomeStruct other) const;
-            };"#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {impl PartialOrd});
-        Ok(())
-    }
-
-    #[test]
-    fn test_impl_lt_missing_eq_impl() -> Result<()> {
-        let ir = ir_from_cc(
-            r#"#pragma clang lifetime_elision
-            struct SomeStruct final {
-                inline bool operator<(const SomeStruct& other) const {
-                    return i < other.i;
+                int i;
+                bool operator<(SomeStruct other) const;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {impl PartialOrd});
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_lt_missing_eq_impl() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator<(const SomeStruct& other) const {
+                    return i < other.i;
+                }
+                int i;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! {impl PartialOrd});
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_le_gt_ge_with_lt() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const {
+                    return i == other.i;
+                }
+                inline bool operator<(const SomeStruct& other) const {
+                    return i < other.i;
+                }
+                inline bool operator<=(const SomeStruct& other) const {
+                    return i <= other.i;
+                }
+                inline bool operator>(const SomeStruct& other) const {
+                    return i > other.i;
+                }
+                inline bool operator>=(const SomeStruct& other) const {
+                    return i >= other.i;
+                }
+                int i;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        // The user-declared operators take precedence over the ones implemented in
+        // terms of `partial_cmp`.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl PartialOrd for SomeStruct {
+                    ...
+                    #[inline(always)]
+                    fn le(&self, other: &Self) -> bool {
+                        unsafe { crate::detail::__crubit_le__10SomeStruct(self, other) }
+                    }
+                    #[inline(always)]
+                    fn gt(&self, other: &Self) -> bool {
+                        unsafe { crate::detail::__crubit_gt__10SomeStruct(self, other) }
+                    }
+                    #[inline(always)]
+                    fn ge(&self, other: &Self) -> bool {
+                        unsafe { crate::detail::__crubit_ge__10SomeStruct(self, other) }
+                    }
+                    ...
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" bool __crubit_le__10SomeStruct(
+                    const struct SomeStruct* lhs, const struct SomeStruct* rhs
+                ) {
+                    return *lhs <= *rhs;
+                }
+            }
+        );
+        let rs_api = rs_tokens_to_formatted_string_for_tests(rs_api)?;
+        assert!(rs_api.contains("impl PartialOrd for SomeStruct"));
+        assert!(!rs_api.contains("Error while generating bindings"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_gt_missing_lt() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                inline bool operator==(const SomeStruct& other) const {
+                    return i == other.i;
+                }
+                inline bool operator>(const SomeStruct& other) const {
+                    return i > other.i;
+                }
+                int i;
+            };"#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'SomeStruct::operator>':\n\
+             // operator> where operator< is missing."
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_three_way_comparison() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            struct strong_ordering final { signed char value; };
+            }
+            struct SomeStruct final {
+                bool operator==(const SomeStruct& other) const noexcept;
+                std::strong_ordering operator<=>(const SomeStruct& other) const noexcept;
+                bool operator<(const SomeStruct& other) const;
+                int i;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl PartialOrd for crate::SomeStruct {
+                    #[inline(always)]
+                    fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                        match unsafe {
+                            crate::detail::__crubit_three_way_comparison__10SomeStruct(self, other)
+                        } {
+                            -1 => Some(::core::cmp::Ordering::Less),
+                            0 => Some(::core::cmp::Ordering::Equal),
+                            1 => Some(::core::cmp::Ordering::Greater),
+                            _ => None,
+                        }
+                    }
+                }
+                impl Ord for crate::SomeStruct {
+                    #[inline(always)]
+                    fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                        self.partial_cmp(other).unwrap()
+                    }
+                }
+            }
+        );
+        // `operator<` doesn't implement `PartialOrd` a second time.
+        assert_rs_not_matches!(rs_api, quote! { impl PartialOrd for SomeStruct });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" signed char __crubit_three_way_comparison__10SomeStruct(
+                    const struct SomeStruct* lhs, const struct SomeStruct* rhs
+                ) {
+                    const auto result = *lhs <=> *rhs;
+                    return result < 0 ? -1 : result > 0 ? 1 : result == 0 ? 0 : 2;
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_three_way_comparison_partial_ordering() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            struct partial_ordering final { signed char value; };
+            }
+            struct SomeStruct final {
+                bool operator==(const SomeStruct& other) const noexcept;
+                std::partial_ordering operator<=>(const SomeStruct& other) const;
+                float f;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { impl PartialOrd for crate::SomeStruct });
+        assert_rs_not_matches!(rs_api, quote! { impl Ord });
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_three_way_comparison_weak_ordering() -> Result<()> {
+        // Equivalent values under `std::weak_ordering` aren't necessarily equal, so
+        // `Ord` would be inconsistent with `Eq`.
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            struct weak_ordering final { signed char value; };
+            }
+            struct SomeStruct final {
+                bool operator==(const SomeStruct& other) const noexcept;
+                std::weak_ordering operator<=>(const SomeStruct& other) const noexcept;
+                int i;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { impl PartialOrd for crate::SomeStruct });
+        assert_rs_not_matches!(rs_api, quote! { impl Ord });
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_three_way_comparison_with_gt() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            namespace std {
+            struct strong_ordering final { signed char value; };
+            }
+            struct SomeStruct final {
+                bool operator==(const SomeStruct& other) const noexcept;
+                std::strong_ordering operator<=>(const SomeStruct& other) const noexcept;
+                bool operator>(const SomeStruct& other) const;
+                int i;
+            };"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl PartialOrd for crate::SomeStruct {
+                    #[inline(always)]
+                    fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> { ... }
+                    #[inline(always)]
+                    fn gt(&self, other: &Self) -> bool {
+                        unsafe { crate::detail::__crubit_gt__10SomeStruct(self, other) }
+                    }
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { fn le });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" bool __crubit_gt__10SomeStruct(
+                    const struct SomeStruct* lhs, const struct SomeStruct* rhs
+                ) {
+                    return *lhs > *rhs;
+                }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_thunk_unpoisons_storage_for_sanitizers() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Nontrivial {
+              Nontrivial();
+              ~Nontrivial();
+            };
+
+            Nontrivial TakesByValue(Nontrivial x, const Nontrivial& y, int z);
+            "#,
+        )?;
+        let rs_api_impl = generate_bindings_tokens(ir)?.rs_api_impl;
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { __HASH_TOKEN__ include "crubit/rs_bindings_support/internal/sanitizers.h" }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN10NontrivialC1Ev(struct Nontrivial* __this) {
+                    crubit::UnpoisonPlacementStorage(__this);
+                    crubit::construct_at(__this);
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___ZN10NontrivialD1Ev(struct Nontrivial* __this) {
+                    std::destroy_at(__this);
+                }
+            }
+        );
+        // Only the placement storage is unpoisoned: live objects (`__this` of the
+        // destructor, `x` passed by value) keep their shadow.
+        assert_cc_not_matches!(
+            rs_api_impl,
+            quote! { crubit::UnpoisonPlacementStorage(__this); std::destroy_at(__this); }
+        );
+        assert_cc_not_matches!(rs_api_impl, quote! { crubit::UnpoisonPlacementStorage(x) });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z12TakesByValue10NontrivialRKS_i(
+                        struct Nontrivial* __return, struct Nontrivial* x,
+                        const struct Nontrivial* y, int z) {
+                    crubit::UnpoisonPlacementStorage(__return);
+                    new(__return) auto(TakesByValue(std::move(*x), *y, z));
                 }
-                int i;
-            };"#,
-        )?;
-        let rs_api = generate_bindings_tokens(ir)?.rs_api;
-        assert_rs_not_matches!(rs_api, quote! {impl PartialOrd});
+            }
+        );
         Ok(())
     }
 
@@ -7479,10 +17112,8 @@ mod tests {
 
     #[test]
     fn test_format_generic_params() -> Result<()> {
-        assert!(
-            format_generic_params(/* lifetimes= */ &[], std::iter::empty::<syn::Ident>())
-                .is_empty(),
-        );
+        assert!(format_generic_params(/* lifetimes= */ &[], std::iter::empty::<syn::Ident>())
+            .is_empty(),);
 
         let idents = ["T1", "T2"].iter().map(|s| make_rs_ident(s));
         assert_rs_matches!(
@@ -7511,9 +17142,6 @@ mod tests {
 
     #[test]
     fn test_overloaded_functions() -> Result<()> {
-        // TODO(b/213280424): We don't support creating bindings for overloaded
-        // functions yet, except in the case of overloaded constructors with a
-        // single parameter.
         let ir = ir_from_cc(
             r#" #pragma clang lifetime_elision
                 void f() {}
@@ -7534,40 +17162,127 @@ mod tests {
                 namespace bar { void not_overloaded(); }
             "#,
         )?;
-        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
-
-        // Cannot overload free functions.
-        assert_cc_matches!(rs_api, {
-            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=4\n\
-                           Error while generating bindings for item 'f':\n\
-                           Cannot generate bindings for overloaded function";
-            quote! { __COMMENT__ #txt }
-        });
-        assert_rs_not_matches!(rs_api, quote! {pub fn f()});
-        assert_rs_not_matches!(rs_api, quote! {pub fn f(i: ::core::ffi::c_int)});
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
 
-        assert_cc_matches!(rs_api, {
-            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=7\n\
-                           Error while generating bindings for item 'S1::f':\n\
-                           Cannot generate bindings for overloaded function";
-            quote! { __COMMENT__ #txt }
-        });
-        assert_rs_not_matches!(rs_api, quote! {pub fn f(... S1 ...)});
+        // The overload without parameters keeps the name of the C++ function, and the
+        // other overloads get suffixes derived from their parameter types.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn f() {
+                    unsafe { crate::detail::__rust_thunk___Z1fv() }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn f_int(i: ::core::ffi::c_int) {
+                    unsafe { crate::detail::__rust_thunk___Z1fi(i) }
+                }
+            }
+        );
 
-        // And thunks aren't generated for either.
-        assert_cc_not_matches!(rs_api_impl, quote! {f});
+        // The same applies to methods.
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl S1 {
+                    ...
+                    pub fn f_int<'a>(&'a mut self, i: ::core::ffi::c_int) { ... }
+                }
+            }
+        );
 
-        // But we can import member functions that have the same name as a free
-        // function.
-        assert_rs_matches!(rs_api, quote! {pub fn f<'a>(&'a mut self)});
+        // Methods that have the same name as a free function are not overloads.
+        assert_rs_matches!(rs_api, quote! { impl S2 { ... pub fn f<'a>(&'a mut self) { ... } } });
 
-        // We can also import overloaded single-parameter constructors.
+        // Overloaded single-parameter constructors are bound as `From` impls.
         assert_rs_matches!(rs_api, quote! {impl From<::core::ffi::c_int> for S3});
         assert_rs_matches!(rs_api, quote! {impl From<f64> for S3});
 
-        // And we can import functions that have the same name + signature, but that are
-        // in 2 different namespaces.
+        // And functions that have the same name + signature, but that are in 2
+        // different namespaces are not overloads either.
         assert_rs_matches!(rs_api, quote! { pub fn not_overloaded() });
+
+        let rs_api_str = rs_tokens_to_formatted_string_for_tests(rs_api)?;
+        assert!(
+            rs_api_str.contains("Bound as `f_int`, because the C++ function `f` is overloaded.")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overloaded_functions_suffixes() -> Result<()> {
+        let ir = ir_from_cc(
+            r#" #pragma clang lifetime_elision
+                struct S final {
+                  int Get(const int* p, int& r);
+                  int Get() const;
+                  int Get();
+                };
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { pub fn Get<'a>(&'a mut self) -> ::core::ffi::c_int });
+        assert_rs_matches!(rs_api, quote! { pub fn Get_const<'a>(&'a self) -> ::core::ffi::c_int });
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub unsafe fn Get_int_const_ptr_int_ref<'a, 'b>(
+                    &'a mut self, p: *const ::core::ffi::c_int, r: &'b mut ::core::ffi::c_int
+                ) -> ::core::ffi::c_int
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overloaded_functions_with_rust_name_attribute() -> Result<()> {
+        let ir = ir_from_cc(
+            r#" void f(int i);
+                [[clang::annotate("crubit_rust_name", "f_from_float")]] void f(float x);
+                [[clang::annotate("crubit_rust_name", "g")]] void collides_with_g();
+                void g();
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // The other overload is no longer overloaded on the Rust side.
+        assert_rs_matches!(rs_api, quote! { pub fn f(i: ::core::ffi::c_int) });
+        assert_rs_matches!(rs_api, quote! { pub fn f_from_float(x: f32) });
+        let rs_api_str = rs_tokens_to_formatted_string_for_tests(rs_api)?;
+        assert!(rs_api_str.contains(
+            "// Error while generating bindings for item 'g':\n\
+             // Cannot generate bindings for overloaded function"
+        ));
+        assert!(rs_api_str.contains(
+            "// Error while generating bindings for item 'collides_with_g':\n\
+             // Cannot generate bindings for overloaded function"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overloaded_functions_suffix_collision() -> Result<()> {
+        let ir = ir_from_cc(
+            r#" void f(int i);
+                void f(float x);
+                void f_int(int i);
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        // The function whose C++ name is `f_int` keeps its name, and the overload
+        // that would be renamed to `f_int` doesn't get bindings.
+        assert_rs_matches!(rs_api, quote! { pub fn f_int(i: ::core::ffi::c_int) { ... } });
+        assert_rs_matches!(rs_api, quote! { pub fn f_float(x: f32) });
+        assert_rs_not_matches!(rs_api, quote! { __rust_thunk___Z1fi });
+        let rs_api_str = rs_tokens_to_formatted_string_for_tests(rs_api)?;
+        assert!(rs_api_str.contains(
+            "// Error while generating bindings for item 'f':\n\
+             // The function would be bound as `f_int`, which is the Rust name of another function"
+        ));
         Ok(())
     }
 
@@ -7613,6 +17328,291 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_c_style_struct_typedef_in_signature() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                struct Foo final { int i; };
+                typedef struct Foo Foo;
+                Foo* MakeFoo();
+                void DestroyFoo(Foo* foo);
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_not_matches!(rs_api, quote! { pub type Foo });
+        assert_rs_matches!(rs_api, quote! { pub fn MakeFoo() -> *mut crate::Foo { ... } });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn DestroyFoo(foo: *mut crate::Foo) { ... } }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_constants() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                // kMaxSize doc comment
+                inline constexpr int kMaxSize = 42;
+                constexpr long kNegative = -kMaxSize;
+                constexpr bool kEnabled = true;
+                constexpr double kScale = 2.5;
+                constexpr float kHalf = 1.0f / 2;
+
+                using MyInt = int;
+                constexpr MyInt kAliased = 2 * 3;
+
+                enum class Color { kRed, kGreen };
+                constexpr Color kDefaultColor = Color::kGreen;
+                constexpr Color kInvalidColor = static_cast<Color>(7);
+
+                extern const int kDefinedElsewhere;
+                inline int kNotConst = 1;
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[doc = " kMaxSize doc comment\n \n Generated from: google3/ir_from_cc_virtual_header.h;l=5"]
+                pub const kMaxSize: ::core::ffi::c_int = 42;
+            }
+        );
+        assert_rs_matches!(rs_api, quote! { pub const kNegative: ::core::ffi::c_long = -42; });
+        assert_rs_matches!(rs_api, quote! { pub const kEnabled: bool = true; });
+        assert_rs_matches!(rs_api, quote! { pub const kScale: f64 = 2.5; });
+        assert_rs_matches!(rs_api, quote! { pub const kHalf: f32 = 0.5; });
+        assert_rs_matches!(rs_api, quote! { pub const kAliased: crate::MyInt = 6; });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub const kDefaultColor: crate::Color = crate::Color::kGreen; }
+        );
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=16\n\
+                           Error while generating bindings for item 'kInvalidColor':\n\
+                           The value of the constant is not an enumerator of `Color`";
+            quote! { __COMMENT__ #txt }
+        });
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=18\n\
+                           Error while generating bindings for item 'kDefinedElsewhere':\n\
+                           The value of the constant is not known at compile time";
+            quote! { __COMMENT__ #txt }
+        });
+        assert_rs_not_matches!(rs_api, quote! { kNotConst });
+        Ok(())
+    }
+
+    #[test]
+    fn test_macro_constants() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                typedef unsigned int MyU32;
+                #define MAX_PATH 4096
+                #define MAX_PATH_BYTES (MAX_PATH * 2ull)
+                #define NEGATIVE -1L
+                #define MASK ((MyU32)0xff)
+                #define SIGNED_CHAR ((signed char)'a')
+                #define HALF 0.5f
+                #define ENABLED (MAX_PATH > 0)
+                #define CALL_DEPTH (RECURSIVE + 1)
+                #define RECURSIVE CALL_DEPTH
+                #define INVALID (1.5 % 2)
+                #define VERSION "1.0"
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { pub const MAX_PATH: ::core::ffi::c_int = 4096; });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub const MAX_PATH_BYTES: ::core::ffi::c_ulonglong = 8192; }
+        );
+        assert_rs_matches!(rs_api, quote! { pub const NEGATIVE: ::core::ffi::c_long = -1; });
+        assert_rs_matches!(rs_api, quote! { pub const MASK: crate::MyU32 = 255; });
+        assert_rs_matches!(rs_api, quote! { pub const SIGNED_CHAR: ::core::ffi::c_schar = 97; });
+        assert_rs_matches!(rs_api, quote! { pub const HALF: f32 = 0.5; });
+        assert_rs_matches!(rs_api, quote! { pub const ENABLED: bool = true; });
+        assert_rs_not_matches!(rs_api, quote! { CALL_DEPTH });
+        assert_rs_not_matches!(rs_api, quote! { RECURSIVE });
+        assert_rs_not_matches!(rs_api, quote! { INVALID });
+        assert_rs_not_matches!(rs_api, quote! { VERSION });
+        Ok(())
+    }
+
+    #[test]
+    fn test_macro_constant_name_collisions() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                enum Color { kRed };
+                [[clang::annotate("crubit_rust_name", "kTimeout")]] int GetTimeout();
+                const int kLimit = 1;
+                #define Color 1
+                #define kTimeout 2
+                #define kLimit 3
+                #define kOther 4
+            "#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains("pub struct Color"));
+        assert!(rs_api.contains("pub fn kTimeout()"));
+        assert!(rs_api.contains("pub const kOther: ::core::ffi::c_int = 4;"));
+        assert!(!rs_api.contains("pub const Color"));
+        assert!(!rs_api.contains("pub const kTimeout"));
+        assert!(!rs_api.contains("pub const kLimit"));
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'Color':\n\
+             // The constant would collide with the enum `Color`, which is also named `Color` in \
+             Rust"
+        ));
+        assert!(rs_api.contains(
+            "// Error while generating bindings for item 'kTimeout':\n\
+             // The constant would collide with the function `GetTimeout`, which is also named \
+             `kTimeout` in Rust"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_like_macros() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                #define htons(x) __builtin_bswap16(x)
+                #define TO_UCHAR(x) ((unsigned char)(x))
+                #define SQUARE(x) ((x) * (x))
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn htons(x: ::core::ffi::c_ushort) -> ::core::ffi::c_ushort {
+                    unsafe {
+                        crate::detail::__rust_thunk____crubit_macro_htons__2f_2ftest_3atesting_5ftarget(x)
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn TO_UCHAR(x: ::core::ffi::c_uchar) -> ::core::ffi::c_uchar { ... } }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" unsigned short
+                __rust_thunk____crubit_macro_htons__2f_2ftest_3atesting_5ftarget(
+                    unsigned short x) ... {
+                    return htons(x);
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" unsigned char
+                __rust_thunk____crubit_macro_TO_UCHAR__2f_2ftest_3atesting_5ftarget(
+                    unsigned char x) ... {
+                    return TO_UCHAR(x);
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { SQUARE });
+        Ok(())
+    }
+
+    #[test]
+    fn test_static_data_members() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                struct S final {
+                  static constexpr int kMaxSize = 42;
+                  static int count;
+                  static const int kDefinedElsewhere;
+                 private:
+                  static int private_count;
+                };
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl crate::S {
+                    pub const kMaxSize: ::core::ffi::c_int = 42;
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl crate::S {
+                    #[inline(always)]
+                    pub fn count() -> *mut ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN1S5countE() }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl crate::S {
+                    #[inline(always)]
+                    pub fn kDefinedElsewhere() -> *const ::core::ffi::c_int {
+                        unsafe { crate::detail::__rust_thunk___ZN1S17kDefinedElsewhereE() }
+                    }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub(crate) fn __rust_thunk___ZN1S5countE() -> *mut ::core::ffi::c_int;
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int* __rust_thunk___ZN1S5countE() {
+                    return &S::count;
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" const int* __rust_thunk___ZN1S17kDefinedElsewhereE() {
+                    return &S::kDefinedElsewhere;
+                }
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { private_count });
+        Ok(())
+    }
+
+    #[test]
+    fn test_static_data_members_unsupported() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+                struct S final {
+                  static int values[3];
+                  static int count;
+                  [[clang::annotate("crubit_rust_name", "count")]] static int GetCount();
+                };
+            "#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert!(rs_api.contains("Static data members of array type are not supported yet"));
+        assert!(rs_api.contains(
+            "The accessor would collide with the method `GetCount`, which is also named `count` \
+             in Rust"
+        ));
+        assert!(!rs_api.contains("__rust_thunk___ZN1S6valuesE"));
+        assert!(!rs_api.contains("__rust_thunk___ZN1S5countE"));
+        Ok(())
+    }
+
     #[test]
     fn test_rs_type_kind_implements_copy() -> Result<()> {
         let template = r#" LIFETIMES
@@ -7966,6 +17966,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_unescapable_rust_keywords_are_renamed_in_rs_api_file() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct Self { int self; };
+            namespace super { void crate(); }"#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(rs_api, quote! { struct Self_ { ... self_: ::core::ffi::c_int ... } });
+        assert_rs_matches!(rs_api, quote! { pub mod super_ { ... pub fn crate_() { ... } ... } });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! { static_assert(CRUBIT_OFFSET_OF(self, struct Self) ... ) }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_renamed_rust_keywords_collisions() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct Self final { int field; };
+            struct Self_ final { int field; };
+            struct SomeStruct final { int self; int self_; };
+            inline void Function(int self, int self_) {}"#,
+        )?;
+        let rs_api = rs_tokens_to_formatted_string_for_tests(generate_bindings_tokens(ir)?.rs_api)?;
+        assert_eq!(rs_api.matches("pub struct Self_ {").count(), 1);
+        assert!(!rs_api.contains("pub struct SomeStruct {"));
+        assert!(!rs_api.contains("pub fn Function("));
+        let message = |keyword: &str| {
+            format!(
+                "`{keyword}` is a Rust keyword, so it would be renamed to `{keyword}_`, which is \
+                 already the name of another declaration"
+            )
+        };
+        assert_eq!(rs_api.matches(&message("Self")).count(), 1);
+        assert_eq!(rs_api.matches(&message("self")).count(), 2);
+        Ok(())
+    }
+
     #[test]
     fn test_rust_keywords_are_not_escaped_in_rs_api_impl_file() -> Result<()> {
         let ir = ir_from_cc("struct type { int dyn; };")?;
@@ -8040,6 +18081,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_self_param_reflects_constness_of_method() -> Result<()> {
+        let rs_api = generate_bindings_tokens(ir_from_cc(
+            r#"
+            #pragma clang lifetime_elision
+            struct S final {
+              int Get() const;
+              void Set(int value);
+            };
+        "#,
+        )?)?
+        .rs_api;
+        assert_rs_matches!(rs_api, quote! { pub fn Get<'a>(&'a self) -> ::core::ffi::c_int });
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub fn Set<'a>(&'a mut self, value: ::core::ffi::c_int) }
+        );
+
+        // Without lifetimes, `this` is a raw pointer of the same constness.
+        let rs_api = generate_bindings_tokens(ir_from_cc(
+            r#"
+            struct S final {
+              int Get() const;
+              void Set(int value);
+            };
+        "#,
+        )?)?
+        .rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn Get(__this: *const Self) -> ::core::ffi::c_int }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { pub unsafe fn Set(__this: *mut Self, value: ::core::ffi::c_int) }
+        );
+        Ok(())
+    }
+
     /// !Unpin &self should not be pinned.
     #[test]
     fn test_nonunpin_ref_self() -> Result<()> {
@@ -8278,6 +18358,7 @@ mod tests {
             quote! {
                 extern "C" void __rust_thunk___Z14ReturnsByValueRKiS0_(
                         struct Nontrivial* __return, int const* x, int const* y) {
+                    crubit::UnpoisonPlacementStorage(__return);
                     new(__return) auto(ReturnsByValue(*x, *y));
                 }
             }
@@ -8318,6 +18399,7 @@ mod tests {
             quote! {
                 extern "C" void __rust_thunk___Z14ReturnsByValueRKiS0_(
                         struct Nontrivial* __return, int const* x, int const* y) {
+                    crubit::UnpoisonPlacementStorage(__return);
                     new(__return) auto(ReturnsByValue(*x, *y));
                 }
             }
@@ -8341,25 +18423,19 @@ mod tests {
             rs_api,
             quote! {
                 #[inline(always)]
-                pub fn foo(mut param: crate::Trivial) {
-                    unsafe { crate::detail::__rust_thunk___Z3foo7Trivial(&mut param) }
+                pub fn foo(param: crate::Trivial) {
+                    unsafe { crate::detail::__rust_thunk___Z3foo7Trivial(param) }
                 }
             }
         );
         assert_rs_matches!(
             rs_api,
             quote! {
-                pub(crate) fn __rust_thunk___Z3foo7Trivial(param: &mut crate::Trivial);
-            }
-        );
-        assert_cc_matches!(
-            rs_api_impl,
-            quote! {
-                extern "C" void __rust_thunk___Z3foo7Trivial(struct Trivial* param) {
-                    foo(std::move(*param));
-                }
+                #[link_name = "_Z3foo7Trivial"]
+                pub(crate) fn __rust_thunk___Z3foo7Trivial(param: crate::Trivial);
             }
         );
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z3foo7Trivial });
         Ok(())
     }
 
@@ -8380,9 +18456,48 @@ mod tests {
             quote! {
                 #[inline(always)]
                 pub fn foo() -> crate::Trivial {
+                    unsafe { crate::detail::__rust_thunk___Z3foov() }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[link_name = "_Z3foov"]
+                pub(crate) fn __rust_thunk___Z3foov() -> crate::Trivial;
+            }
+        );
+        assert_cc_not_matches!(rs_api_impl, quote! { __rust_thunk___Z3foov });
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_by_value_that_is_not_c_like() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct Base {};
+            struct Derived final : Base {
+              int field;
+            };
+            struct WithPrivateField final {
+             private:
+              int field;
+            };
+            struct Empty final {};
+
+            Derived MakeDerived();
+            void TakeWithPrivateField(WithPrivateField param);
+            Empty MakeEmpty();
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn MakeDerived() -> crate::Derived {
                     unsafe {
-                        let mut __return = ::core::mem::MaybeUninit::<crate::Trivial>::uninit();
-                        crate::detail::__rust_thunk___Z3foov(&mut __return);
+                        let mut __return = ::core::mem::MaybeUninit::<crate::Derived>::uninit();
+                        crate::detail::__rust_thunk___Z11MakeDerivedv(&mut __return);
                         __return.assume_init()
                     }
                 }
@@ -8391,22 +18506,95 @@ mod tests {
         assert_rs_matches!(
             rs_api,
             quote! {
-                pub(crate) fn __rust_thunk___Z3foov(
-                    __return: &mut ::core::mem::MaybeUninit<crate::Trivial>
-                );
+                pub fn TakeWithPrivateField(mut param: crate::WithPrivateField) {
+                    unsafe {
+                        crate::detail::__rust_thunk___Z20TakeWithPrivateField16WithPrivateField(
+                            &mut param
+                        )
+                    }
+                }
             }
         );
         assert_cc_matches!(
             rs_api_impl,
             quote! {
-                extern "C" void __rust_thunk___Z3foov(struct Trivial* __return) {
-                    new (__return) auto(foo());
-                }
+                extern "C" void __rust_thunk___Z11MakeDerivedv(struct Derived* __return) { ... }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z20TakeWithPrivateField16WithPrivateField(
+                    struct WithPrivateField* param) { ... }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z9MakeEmptyv(struct Empty* __return) { ... }
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rust_movable() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct [[clang::annotate("crubit_rust_movable")]] Movable final {
+              Movable(Movable&&);
+              ~Movable();
+              int field;
+            };
+
+            Movable foo();
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_not_matches!(rs_api, quote! {#[::ctor::recursively_pinned]});
+        assert_rs_matches!(rs_api, quote! { pub fn foo() -> crate::Movable { ... } });
+        assert_cc_matches!(rs_api_impl, quote! { __HASH_TOKEN__ include <type_traits> });
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                static_assert(
+                    std::is_move_constructible_v<struct Movable> &&
+                    std::is_destructible_v<struct Movable>,
+                    "CRUBIT_RUST_MOVABLE types must be move-constructible and destructible");
             }
         );
         Ok(())
     }
 
+    #[test]
+    fn test_send_sync() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            struct [[clang::annotate("crubit_send")]] [[clang::annotate("crubit_sync")]]
+            ThreadSafe final {
+              int* ptr;
+            };
+            struct [[clang::annotate("crubit_not_send")]] ThreadAffine final {
+              int field;
+            };
+            "#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(rs_api, quote! { unsafe impl Send for ThreadSafe {} });
+        assert_rs_matches!(rs_api, quote! { unsafe impl Sync for ThreadSafe {} });
+        assert_rs_matches!(rs_api, quote! { impl !Send for ThreadAffine {} });
+        assert_rs_not_matches!(rs_api, quote! { impl !Sync for ThreadAffine {} });
+        assert_rs_matches!(
+            rs_api,
+            quote! { static_assertions::assert_impl_all!(crate::ThreadSafe: Send); }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! { static_assertions::assert_not_impl_any!(crate::ThreadAffine: Send); }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_unpin_rvalue_ref_qualified_method() -> Result<()> {
         let ir = ir_from_cc(
@@ -8514,6 +18702,7 @@ mod tests {
                     struct Nontrivial* __return, struct Nontrivial* __this,
                     const struct Nontrivial* other
                 ) {
+                    crubit::UnpoisonPlacementStorage(__return);
                     new(__return) auto(__this->operator=(*other));
                 }
             }
@@ -8614,6 +18803,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_function_taking_rvalue_references() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            struct SomeStruct final {
+                int field;
+            };
+            // Inline to force generation (and test coverage) of C++ thunks.
+            inline void TakeRValueReference(SomeStruct&& value) {}
+            inline void TakeConstRValueReference(const SomeStruct&& value) {}
+            "#,
+        )?;
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir)?;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn TakeRValueReference<'a>(value: ::ctor::RvalueReference<'a, crate::SomeStruct>) {
+                    unsafe { crate::detail::__rust_thunk___Z19TakeRValueReferenceO10SomeStruct(value) }
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[inline(always)]
+                pub fn TakeConstRValueReference<'a>(
+                    value: ::ctor::ConstRvalueReference<'a, crate::SomeStruct>
+                ) {
+                    unsafe {
+                        crate::detail::__rust_thunk___Z24TakeConstRValueReferenceOK10SomeStruct(value)
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" void __rust_thunk___Z19TakeRValueReferenceO10SomeStruct(
+                        struct SomeStruct* value) {
+                    TakeRValueReference(std::move(*value));
+                }
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_function_returning_rvalue_reference() -> Result<()> {
         let ir = ir_from_cc(
@@ -8697,6 +18933,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_forward_declared_in_signatures() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"#pragma clang lifetime_elision
+            class Widget;
+            Widget* NewWidget();
+            void UseWidget(const Widget& widget);
+            Widget MakeWidget();
+            void TakeWidget(Widget widget);"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn NewWidget() -> *mut crate::Widget { ... }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub fn UseWidget<'a>(widget: &'a crate::Widget) { ... }
+            }
+        );
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=7\n\
+                           Error while generating bindings for item 'MakeWidget':\n\
+                           Can't directly construct values of type `Widget` as it is only \
+                           forward-declared";
+            quote! { __COMMENT__ #txt }
+        });
+        assert_rs_matches!(rs_api, {
+            let txt = "Generated from: google3/ir_from_cc_virtual_header.h;l=8\n\
+                           Error while generating bindings for item 'TakeWidget':\n\
+                           Can't directly construct values of type `Widget` as it is only \
+                           forward-declared";
+            quote! { __COMMENT__ #txt }
+        });
+        Ok(())
+    }
+
     #[test]
     fn test_namespace_module_items() -> Result<()> {
         let rs_api = generate_bindings_tokens(ir_from_cc(
@@ -8959,6 +19235,51 @@ mod tests {
         Ok(())
     }
 
+    /// A private field whose type has no bindings (here, a private nested
+    /// struct) is still represented by an opaque blob of the right size, so
+    /// that the record itself has the same layout as in C++.
+    #[test]
+    fn test_private_field_of_private_type() -> Result<()> {
+        let ir = ir_from_cc(
+            r#"
+            class HasPrivateStorage final {
+             public:
+              int Get() const;
+             private:
+              struct Impl {
+                __INT64_TYPE__ a;
+                char b;
+              };
+              Impl impl_;
+              char c_;
+            };"#,
+        )?;
+        let rs_api = generate_bindings_tokens(ir)?.rs_api;
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                #[repr(C, align(8))]
+                pub struct HasPrivateStorage {
+                    __non_field_data: [::core::mem::MaybeUninit<u8>; 0],
+                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
+                    pub(crate) impl_: [::core::mem::MaybeUninit<u8>; 16],
+                    #[doc = " Reason for representing this field as a blob of bytes:\n Types of non-public C++ fields can be elided away"]
+                    pub(crate) c_: [::core::mem::MaybeUninit<u8>; 8],
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                const _: () = assert!(::core::mem::size_of::<crate::HasPrivateStorage>() == 24);
+                const _: () = assert!(::core::mem::align_of::<crate::HasPrivateStorage>() == 8);
+            }
+        );
+        assert_rs_not_matches!(rs_api, quote! { pub struct Impl });
+        assert_rs_not_matches!(rs_api, quote! { pub struct HasPrivateStorage_Impl });
+        Ok(())
+    }
+
     #[test]
     fn test_implicit_template_specializations_are_sorted_by_mangled_name() -> Result<()> {
         let bindings = generate_bindings_tokens(ir_from_cc(
@@ -9085,6 +19406,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_explicit_class_template_instantiation_definition() -> Result<()> {
+        let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens(ir_from_cc(
+            r#" #pragma clang lifetime_elision
+                template <typename T>
+                struct MyTemplate final {
+                    T GetValue() const { return value; }
+                    T value;
+                };
+
+                template struct MyTemplate<int>;"#,
+        )?)?;
+
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                pub struct __CcTemplateInst10MyTemplateIiE {
+                    pub value: ::core::ffi::c_int,
+                }
+            }
+        );
+        assert_rs_matches!(
+            rs_api,
+            quote! {
+                impl __CcTemplateInst10MyTemplateIiE {
+                    #[inline(always)]
+                    pub fn GetValue<'a>(&'a self) -> ::core::ffi::c_int {
+                        unsafe {
+                            crate::detail::__rust_thunk___ZNK10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(self)
+                        }
+                    }
+                }
+            }
+        );
+        assert_cc_matches!(
+            rs_api_impl,
+            quote! {
+                extern "C" int
+                __rust_thunk___ZNK10MyTemplateIiE8GetValueEv__2f_2ftest_3atesting_5ftarget(
+                        const struct MyTemplate<int>*__this) {
+                    return __this->GetValue();
+                }
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_lifetime_elision_for_references() {
         let type_args: &[RsTypeKind] = &[];
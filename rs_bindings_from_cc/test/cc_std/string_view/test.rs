@@ -7,13 +7,15 @@ use string_view_apis::crubit_string_view::{GetHelloWorld, GetInvalidUtf8};
 
 #[test]
 fn test_valid_utf8_str() {
-    let hello_str: &'static str = GetHelloWorld().try_into().unwrap();
+    // The string views don't have lifetime annotations, so they are bound as raw
+    // `CxxStringView`s, which can only be read unsafely.
+    let hello_str = core::str::from_utf8(unsafe { GetHelloWorld().as_bytes() }).unwrap();
     assert_eq!(hello_str, "Hello, world!");
 }
 
 #[test]
 fn test_invalid_utf8_str() {
-    let not_a_str: Result<&'static str, _> = GetInvalidUtf8().try_into();
+    let not_a_str = core::str::from_utf8(unsafe { GetInvalidUtf8().as_bytes() });
     let _ = not_a_str.unwrap_err();
 }
 
@@ -21,6 +23,6 @@ fn test_invalid_utf8_str() {
 fn test_round_trip_empty_str() {
     let original: &[u8] = &[];
     let sv: std::string_view = original.into();
-    let round_tripped: &[u8] = sv.into();
+    let round_tripped: &[u8] = unsafe { sv.as_bytes() };
     assert_eq!(original, round_tripped);
 }
@@ -43,6 +43,6 @@ mod tests {
     fn test_string_view() {
         let x = "this is a string";
         let x_sv = std::string_view::from(x);
-        assert_eq!(x, <&str>::try_from(x_sv).unwrap());
+        assert_eq!(x.as_bytes(), unsafe { x_sv.as_bytes() });
     }
 }
@@ -0,0 +1,22 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "rs_bindings_from_cc/test/function/lifetimes/lifetimes.h"
+
+const int& Min(const int& x, const int& y) { return y < x ? y : x; }
+
+const int& First(const int& values, const int& unused) { return values; }
+
+const int& GetDefault() {
+  static const int kDefault = 42;
+  return kDefault;
+}
+
+int& Identity(int& x) { return x; }
+
+void Swap(int& x, int& y) {
+  int tmp = x;
+  x = y;
+  y = tmp;
+}
@@ -0,0 +1,26 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_RS_BINDINGS_FROM_CC_TEST_FUNCTION_LIFETIMES_LIFETIMES_H_
+#define CRUBIT_RS_BINDINGS_FROM_CC_TEST_FUNCTION_LIFETIMES_LIFETIMES_H_
+
+#include "support/lifetime_annotations.h"
+
+#pragma clang lifetime_elision
+
+// Explicitly annotated: the result borrows from both parameters.
+const int& $a Min(const int& $a x, const int& $a y);
+
+// Explicitly annotated: the result borrows from `values` only.
+const int& $a First(const int& $a values, const int& $b unused);
+
+const int& $static GetDefault();
+
+// Elided: the result borrows from the only reference parameter.
+int& Identity(int& x);
+
+// Elided: parameters that are not returned get distinct lifetimes.
+void Swap(int& x, int& y);
+
+#endif  // CRUBIT_RS_BINDINGS_FROM_CC_TEST_FUNCTION_LIFETIMES_LIFETIMES_H_
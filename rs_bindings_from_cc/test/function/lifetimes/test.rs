@@ -0,0 +1,48 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#[cfg(test)]
+mod tests {
+    use lifetimes::*;
+
+    // Functions with lifetimes are bound as safe functions that take and return
+    // references, so none of the calls below need an `unsafe` block.
+
+    #[test]
+    fn test_explicit_lifetimes() {
+        let x = 1;
+        let y = 2;
+        let min: &i32 = Min(&x, &y);
+        assert_eq!(*min, 1);
+        assert!(core::ptr::eq(min, &x));
+    }
+
+    #[test]
+    fn test_unrelated_lifetime() {
+        let values = 1;
+        let first = {
+            // `unused` doesn't need to outlive the result.
+            let unused = 2;
+            First(&values, &unused)
+        };
+        assert_eq!(*first, 1);
+    }
+
+    #[test]
+    fn test_static_lifetime() {
+        let default: &'static i32 = GetDefault();
+        assert_eq!(*default, 42);
+    }
+
+    #[test]
+    fn test_elided_lifetimes() {
+        let mut x = 1;
+        *Identity(&mut x) += 1;
+        assert_eq!(x, 2);
+
+        let mut y = 3;
+        Swap(&mut x, &mut y);
+        assert_eq!((x, y), (3, 2));
+    }
+}
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -27,16 +28,19 @@ static_assert(CRUBIT_OFFSET_OF(f7, struct WithBitfields) == 27);
 
 extern "C" void __rust_thunk___ZN13WithBitfieldsC1Ev(
     struct WithBitfields* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13WithBitfieldsC1ERKS_(
     struct WithBitfields* __this, const struct WithBitfields* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN13WithBitfieldsC1EOS_(
     struct WithBitfields* __this, struct WithBitfields* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -55,18 +59,21 @@ static_assert(alignof(struct AlignmentRegressionTest) == 4);
 
 extern "C" void __rust_thunk___ZN23AlignmentRegressionTestC1Ev(
     struct AlignmentRegressionTest* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN23AlignmentRegressionTestC1ERKS_(
     struct AlignmentRegressionTest* __this,
     const struct AlignmentRegressionTest* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN23AlignmentRegressionTestC1EOS_(
     struct AlignmentRegressionTest* __this,
     struct AlignmentRegressionTest* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -340,6 +340,13 @@ unsafe impl oops::Inherits<crate::HasCustomAlignment>
         (derived as *const _ as *const u8).offset(0) as *const crate::HasCustomAlignment
     }
 }
+impl ::core::ops::Deref for crate::InheritsFromBaseWithCustomAlignment {
+    type Target = crate::HasCustomAlignment;
+    #[inline(always)]
+    fn deref(&self) -> &crate::HasCustomAlignment {
+        oops::Upcast::<&crate::HasCustomAlignment>::upcast(self)
+    }
+}
 
 #[::ctor::recursively_pinned]
 #[repr(C, align(64))]
@@ -746,6 +753,11 @@ const _: () = {
 const _: () = {
     static_assertions::assert_not_impl_any!(crate::InheritsFromBaseWithCustomAlignment:Drop);
 };
+const _: () = assert!(0 % ::core::mem::align_of::<crate::HasCustomAlignment>() == 0);
+const _: () = assert!(
+    0 + ::core::mem::size_of::<crate::HasCustomAlignment>()
+        <= ::core::mem::size_of::<crate::InheritsFromBaseWithCustomAlignment>()
+);
 
 const _: () = assert!(::core::mem::size_of::<crate::HasCustomAlignmentWithGnuAttr>() == 64);
 const _: () = assert!(::core::mem::align_of::<crate::HasCustomAlignmentWithGnuAttr>() == 64);
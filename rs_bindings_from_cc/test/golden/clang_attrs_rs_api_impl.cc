@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,17 +25,20 @@ static_assert(alignof(struct HasCustomAlignment) == 64);
 
 extern "C" void __rust_thunk___ZN18HasCustomAlignmentC1Ev(
     struct HasCustomAlignment* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18HasCustomAlignmentC1ERKS_(
     struct HasCustomAlignment* __this,
     const struct HasCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN18HasCustomAlignmentC1EOS_(
     struct HasCustomAlignment* __this, struct HasCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -57,18 +61,21 @@ static_assert(CRUBIT_OFFSET_OF(field, struct HasFieldWithCustomAlignment) == 0);
 
 extern "C" void __rust_thunk___ZN27HasFieldWithCustomAlignmentC1Ev(
     struct HasFieldWithCustomAlignment* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN27HasFieldWithCustomAlignmentC1ERKS_(
     struct HasFieldWithCustomAlignment* __this,
     const struct HasFieldWithCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN27HasFieldWithCustomAlignmentC1EOS_(
     struct HasFieldWithCustomAlignment* __this,
     struct HasFieldWithCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -91,18 +98,21 @@ static_assert(alignof(struct InheritsFromBaseWithCustomAlignment) == 64);
 
 extern "C" void __rust_thunk___ZN35InheritsFromBaseWithCustomAlignmentC1Ev(
     struct InheritsFromBaseWithCustomAlignment* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN35InheritsFromBaseWithCustomAlignmentC1ERKS_(
     struct InheritsFromBaseWithCustomAlignment* __this,
     const struct InheritsFromBaseWithCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN35InheritsFromBaseWithCustomAlignmentC1EOS_(
     struct InheritsFromBaseWithCustomAlignment* __this,
     struct InheritsFromBaseWithCustomAlignment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -125,18 +135,21 @@ static_assert(alignof(struct HasCustomAlignmentWithGnuAttr) == 64);
 
 extern "C" void __rust_thunk___ZN29HasCustomAlignmentWithGnuAttrC1Ev(
     struct HasCustomAlignmentWithGnuAttr* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN29HasCustomAlignmentWithGnuAttrC1ERKS_(
     struct HasCustomAlignmentWithGnuAttr* __this,
     const struct HasCustomAlignmentWithGnuAttr* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN29HasCustomAlignmentWithGnuAttrC1EOS_(
     struct HasCustomAlignmentWithGnuAttr* __this,
     struct HasCustomAlignmentWithGnuAttr* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -162,6 +175,7 @@ static_assert(alignof(struct template_with_preferred_name::SomeTemplate<int>) ==
 extern "C" void
 __rust_thunk___ZN28template_with_preferred_name12SomeTemplateIiEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3aclang_5fattrs_5fcc(
     struct template_with_preferred_name::SomeTemplate<int>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -169,6 +183,7 @@ extern "C" void
 __rust_thunk___ZN28template_with_preferred_name12SomeTemplateIiEC1ERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3aclang_5fattrs_5fcc(
     struct template_with_preferred_name::SomeTemplate<int>* __this,
     const struct template_with_preferred_name::SomeTemplate<int>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -176,6 +191,7 @@ extern "C" void
 __rust_thunk___ZN28template_with_preferred_name12SomeTemplateIiEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3aclang_5fattrs_5fcc(
     struct template_with_preferred_name::SomeTemplate<int>* __this,
     struct template_with_preferred_name::SomeTemplate<int>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -48,6 +48,13 @@ impl Default for Foo {
     }
 }
 
+impl Foo {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for Foo {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -117,6 +124,13 @@ impl Default for Bar {
     }
 }
 
+impl Bar {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for Bar {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -165,6 +179,13 @@ impl Default for HasNoComments {
     }
 }
 
+impl HasNoComments {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for HasNoComments {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
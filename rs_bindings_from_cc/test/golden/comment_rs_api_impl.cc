@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -25,11 +26,13 @@ static_assert(CRUBIT_OFFSET_OF(i, struct Foo) == 0);
 static_assert(CRUBIT_OFFSET_OF(j, struct Foo) == 4);
 
 extern "C" void __rust_thunk___ZN3FooC1Ev(struct Foo* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN3FooC1EOS_(struct Foo* __this,
                                             struct Foo* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -50,11 +53,13 @@ static_assert(alignof(struct Bar) == 4);
 static_assert(CRUBIT_OFFSET_OF(i, struct Bar) == 0);
 
 extern "C" void __rust_thunk___ZN3BarC1Ev(struct Bar* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN3BarC1EOS_(struct Bar* __this,
                                             struct Bar* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -74,11 +79,13 @@ static_assert(CRUBIT_OFFSET_OF(i, struct HasNoComments) == 0);
 
 extern "C" void __rust_thunk___ZN13HasNoCommentsC1Ev(
     struct HasNoComments* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13HasNoCommentsC1EOS_(
     struct HasNoComments* __this, struct HasNoComments* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
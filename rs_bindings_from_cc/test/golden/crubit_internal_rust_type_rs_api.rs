@@ -78,6 +78,13 @@ impl Default for TypeMapOverrideFieldTypes {
     }
 }
 
+impl TypeMapOverrideFieldTypes {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for TypeMapOverrideFieldTypes {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -33,12 +34,14 @@ static_assert(CRUBIT_OFFSET_OF(error, struct TypeMapOverrideFieldTypes) == 4);
 
 extern "C" void __rust_thunk___ZN25TypeMapOverrideFieldTypesC1Ev(
     struct TypeMapOverrideFieldTypes* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN25TypeMapOverrideFieldTypesC1EOS_(
     struct TypeMapOverrideFieldTypes* __this,
     struct TypeMapOverrideFieldTypes* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
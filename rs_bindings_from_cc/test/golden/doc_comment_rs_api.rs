@@ -77,6 +77,13 @@ impl Default for DocCommentSlashes {
     }
 }
 
+impl DocCommentSlashes {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 /// An implicit conversion constructor which will get translated into `impl
 /// From<int> for DocCommentSlashes`.
 impl From<::core::ffi::c_int> for DocCommentSlashes {
@@ -138,6 +145,13 @@ impl Default for DocCommentBang {
     }
 }
 
+impl DocCommentBang {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for DocCommentBang {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -192,6 +206,13 @@ impl Default for MultilineCommentTwoStars {
     }
 }
 
+impl MultilineCommentTwoStars {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for MultilineCommentTwoStars {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -243,6 +264,13 @@ impl Default for LineComment {
     }
 }
 
+impl LineComment {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for LineComment {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -297,6 +325,13 @@ impl Default for MultilineOneStar {
     }
 }
 
+impl MultilineOneStar {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for MultilineOneStar {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -381,6 +416,13 @@ impl Default for __CcTemplateInst10MyTemplateIiE {
     }
 }
 
+impl __CcTemplateInst10MyTemplateIiE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for __CcTemplateInst10MyTemplateIiE {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -447,6 +489,13 @@ impl Default for __CcTemplateInst10MyTemplateIfE {
     }
 }
 
+impl __CcTemplateInst10MyTemplateIfE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for __CcTemplateInst10MyTemplateIfE {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
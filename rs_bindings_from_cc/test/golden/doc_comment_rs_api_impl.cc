@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -25,6 +26,7 @@ static_assert(CRUBIT_OFFSET_OF(i, struct DocCommentSlashes) == 0);
 
 extern "C" void __rust_thunk___ZN17DocCommentSlashesC1EOS_(
     struct DocCommentSlashes* __this, struct DocCommentSlashes* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -46,11 +48,13 @@ static_assert(CRUBIT_OFFSET_OF(i, struct DocCommentBang) == 0);
 
 extern "C" void __rust_thunk___ZN14DocCommentBangC1Ev(
     struct DocCommentBang* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN14DocCommentBangC1EOS_(
     struct DocCommentBang* __this, struct DocCommentBang* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -70,12 +74,14 @@ static_assert(CRUBIT_OFFSET_OF(i, struct MultilineCommentTwoStars) == 0);
 
 extern "C" void __rust_thunk___ZN24MultilineCommentTwoStarsC1Ev(
     struct MultilineCommentTwoStars* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN24MultilineCommentTwoStarsC1EOS_(
     struct MultilineCommentTwoStars* __this,
     struct MultilineCommentTwoStars* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -98,11 +104,13 @@ static_assert(alignof(struct LineComment) == 4);
 static_assert(CRUBIT_OFFSET_OF(i, struct LineComment) == 0);
 
 extern "C" void __rust_thunk___ZN11LineCommentC1Ev(struct LineComment* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN11LineCommentC1EOS_(
     struct LineComment* __this, struct LineComment* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -122,11 +130,13 @@ static_assert(CRUBIT_OFFSET_OF(i, struct MultilineOneStar) == 0);
 
 extern "C" void __rust_thunk___ZN16MultilineOneStarC1Ev(
     struct MultilineOneStar* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN16MultilineOneStarC1EOS_(
     struct MultilineOneStar* __this, struct MultilineOneStar* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -149,12 +159,14 @@ static_assert(CRUBIT_OFFSET_OF(value, struct MyTemplate<int>) == 0);
 extern "C" void
 __rust_thunk___ZN10MyTemplateIiEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3adoc_5fcomment_5fcc(
     struct MyTemplate<int>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void
 __rust_thunk___ZN10MyTemplateIiEC1EOS0___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3adoc_5fcomment_5fcc(
     struct MyTemplate<int>* __this, struct MyTemplate<int>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -183,12 +195,14 @@ static_assert(CRUBIT_OFFSET_OF(value, struct MyTemplate<float>) == 0);
 extern "C" void
 __rust_thunk___ZN10MyTemplateIfEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3adoc_5fcomment_5fcc(
     struct MyTemplate<float>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void
 __rust_thunk___ZN10MyTemplateIfEC1EOS0___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3adoc_5fcomment_5fcc(
     struct MyTemplate<float>* __this, struct MyTemplate<float>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -38,6 +38,13 @@ impl Default for r#type {
     }
 }
 
+impl r#type {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for r#type {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
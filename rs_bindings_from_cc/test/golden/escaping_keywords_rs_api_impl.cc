@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(struct type) == 4);
 static_assert(CRUBIT_OFFSET_OF(dyn, struct type) == 0);
 
 extern "C" void __rust_thunk___ZN4typeC1Ev(struct type* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN4typeC1EOS_(struct type* __this,
                                              struct type* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
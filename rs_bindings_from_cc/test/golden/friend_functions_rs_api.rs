@@ -38,6 +38,13 @@ impl Default for SomeClass {
     }
 }
 
+impl SomeClass {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for SomeClass {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -0,0 +1,288 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Summarizes the differences between two versions of a generated bindings
+//! file as added, removed and changed items, so that changes to large golden
+//! files (e.g. `types_rs_api.rs`) can be reviewed without reading a raw text
+//! diff.
+//!
+//! Usage: `golden_diff EXPECTED ACTUAL`
+//!
+//! `.rs` files are compared item by item (recursing into modules). `.cc` files
+//! are compared by top-level declarations (e.g. thunks and `static_assert`s).
+//! Items are matched by their signature (e.g. `impl Default for SomeStruct`),
+//! so an item whose body changed is reported as changed, while an item whose
+//! signature changed is reported as removed and added.
+
+use anyhow::{bail, Context, Result};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Maps the key of each item to its (normalized) text.
+type Items = BTreeMap<String, String>;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        bail!("Usage: {} EXPECTED ACTUAL", args[0]);
+    }
+    let expected_path = Path::new(&args[1]);
+    let expected = std::fs::read_to_string(expected_path)
+        .with_context(|| format!("Failed to read {}", args[1]))?;
+    let actual =
+        std::fs::read_to_string(&args[2]).with_context(|| format!("Failed to read {}", args[2]))?;
+    let (expected_items, actual_items) =
+        match expected_path.extension().and_then(|ext| ext.to_str()) {
+            Some("rs") => (rs_items(&expected)?, rs_items(&actual)?),
+            _ => (cc_items(&expected), cc_items(&actual)),
+        };
+    print!("{}", format_summary(&expected_items, &actual_items));
+    Ok(())
+}
+
+/// Returns the lines of the summary of the differences between `expected` and
+/// `actual`, e.g. `+ pub fn foo`.
+fn format_summary(expected: &Items, actual: &Items) -> String {
+    let mut summary = String::new();
+    for (key, text) in expected {
+        match actual.get(key) {
+            None => summary.push_str(&format!("- {key}\n")),
+            Some(actual_text) if actual_text != text => summary.push_str(&format!("~ {key}\n")),
+            Some(_) => {}
+        }
+    }
+    for key in actual.keys() {
+        if !expected.contains_key(key) {
+            summary.push_str(&format!("+ {key}\n"));
+        }
+    }
+    summary
+}
+
+/// Returns the items of a generated `.rs` file, including the items that
+/// couldn't be generated (which are only present as comments).
+fn rs_items(source: &str) -> Result<Items> {
+    let mut items = Items::new();
+    for line in source.lines() {
+        if let Some(name) = line
+            .trim_start()
+            .strip_prefix("// Error while generating bindings for item '")
+            .and_then(|rest| rest.strip_suffix("':"))
+        {
+            items.insert(format!("unsupported item '{name}'"), String::new());
+        }
+    }
+    let tokens: TokenStream =
+        source.parse().map_err(|err| anyhow::anyhow!("Failed to tokenize: {err:?}"))?;
+    collect_rs_items("", tokens, &mut items);
+    Ok(items)
+}
+
+fn collect_rs_items(prefix: &str, tokens: TokenStream, items: &mut Items) {
+    let mut item: Vec<TokenTree> = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        // Attributes (including doc comments) belong to the item that follows them.
+        if let TokenTree::Punct(punct) = &token {
+            if punct.as_char() == '#' {
+                item.push(token);
+                if let Some(TokenTree::Punct(bang)) = tokens.peek() {
+                    if bang.as_char() == '!' {
+                        item.push(tokens.next().unwrap());
+                    }
+                }
+                if let Some(attr) = tokens.next() {
+                    item.push(attr);
+                }
+                continue;
+            }
+        }
+        let ends_item = match &token {
+            TokenTree::Punct(punct) => punct.as_char() == ';',
+            TokenTree::Group(group) => {
+                group.delimiter() == Delimiter::Brace
+                    && !matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ';')
+            }
+            _ => false,
+        };
+        item.push(token);
+        if ends_item {
+            add_rs_item(prefix, std::mem::take(&mut item), items);
+        }
+    }
+    if !item.is_empty() {
+        add_rs_item(prefix, item, items);
+    }
+}
+
+fn add_rs_item(prefix: &str, item: Vec<TokenTree>, items: &mut Items) {
+    let text = TokenStream::from_iter(item.iter().cloned()).to_string();
+    // The signature of the item: everything but the attributes, up to the body.
+    let mut signature = vec![];
+    let mut body = None;
+    let mut tokens = item.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' && signature.is_empty() => {
+                if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!') {
+                    tokens.next();
+                }
+                tokens.next();
+                continue;
+            }
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                body = Some(group.stream());
+                break;
+            }
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => break,
+            TokenTree::Punct(punct) if matches!(punct.as_char(), '=' | ';') => break,
+            _ => {}
+        }
+        signature.push(token);
+    }
+    if signature.is_empty() {
+        return;
+    }
+    let signature = TokenStream::from_iter(signature).to_string();
+    let is_module = signature.starts_with("mod ")
+        || signature.starts_with("pub mod ")
+        || signature.starts_with("extern ");
+    match body {
+        Some(body) if is_module => {
+            collect_rs_items(&format!("{prefix}{signature} :: "), body, items);
+        }
+        _ => {
+            // Anonymous items (e.g. `const _: () = assert!(...)`) are identified by
+            // their whole text.
+            let key = if signature.ends_with(" _ :") || signature.ends_with(" _") {
+                text.clone()
+            } else {
+                signature
+            };
+            items.insert(format!("{prefix}{key}"), text);
+        }
+    }
+}
+
+/// Returns the top-level declarations of a generated `.cc` file.
+///
+/// The files are formatted by clang-format, so every declaration starts in the
+/// first column, and continues with indented lines (or a closing brace).
+fn cc_items(source: &str) -> Items {
+    let mut items = Items::new();
+    let mut current: Vec<&str> = vec![];
+    let mut flush = |current: &mut Vec<&str>| {
+        if current.is_empty() {
+            return;
+        }
+        let text = current.iter().map(|line| line.trim()).collect::<Vec<_>>().join(" ");
+        let key = match text.find('(') {
+            // Thunks are identified by their name.
+            Some(paren) if text.starts_with("extern \"C\"") => {
+                text[..paren].rsplit(' ').next().unwrap_or_default().to_string()
+            }
+            _ => text.clone(),
+        };
+        items.insert(key, text);
+        current.clear();
+    };
+    for line in source.lines() {
+        let starts_declaration = !line.is_empty()
+            && !line.starts_with(char::is_whitespace)
+            && !line.starts_with('}')
+            && !line.starts_with(')');
+        if starts_declaration {
+            flush(&mut current);
+            if line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+        }
+        if !line.trim().is_empty() && (starts_declaration || !current.is_empty()) {
+            current.push(line);
+        }
+    }
+    flush(&mut current);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rs_summary() {
+        let expected = rs_items(
+            r#"
+            // Error while generating bindings for item 'Unsupported':
+            // Unsupported type
+            pub struct Removed {}
+            impl Default for Changed {
+                fn default() -> Self { 1 }
+            }
+            pub mod ns {
+                pub fn unchanged() {}
+            }
+            const _: () = assert!(::core::mem::size_of::<crate::Removed>() == 1);
+            "#,
+        )
+        .unwrap();
+        let actual = rs_items(
+            r#"
+            /// Now with docs.
+            impl Default for Changed {
+                fn default() -> Self { 2 }
+            }
+            pub mod ns {
+                pub fn unchanged() {}
+                pub fn added() {}
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            format_summary(&expected, &actual),
+            "- const _ : () = assert ! (:: core :: mem :: size_of :: < crate :: Removed > () == 1) ;\n\
+             ~ impl Default for Changed\n\
+             - pub struct Removed\n\
+             - unsupported item 'Unsupported'\n\
+             + pub mod ns :: pub fn added\n"
+        );
+    }
+
+    #[test]
+    fn test_cc_summary() {
+        let expected = cc_items(
+            r#"#include <memory>
+
+static_assert(sizeof(struct S) == 1);
+
+extern "C" void __rust_thunk___ZN1SC1Ev(struct S* __this) {
+  crubit::construct_at(__this);
+}
+
+extern "C" void __rust_thunk___Z3foov() { foo(); }
+"#,
+        );
+        let actual = cc_items(
+            r#"#include <memory>
+
+static_assert(sizeof(struct S) == 2);
+
+extern "C" void __rust_thunk___ZN1SC1Ev(
+    struct S* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
+  crubit::construct_at(__this);
+}
+"#,
+        );
+        assert_eq!(
+            format_summary(&expected, &actual),
+            "- __rust_thunk___Z3foov\n\
+             ~ __rust_thunk___ZN1SC1Ev\n\
+             - static_assert(sizeof(struct S) == 1);\n\
+             + static_assert(sizeof(struct S) == 2);\n"
+        );
+    }
+}
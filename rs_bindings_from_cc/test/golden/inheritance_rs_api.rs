@@ -347,6 +347,13 @@ impl Default for Derived {
     }
 }
 
+impl Derived {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for Derived {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -984,6 +991,13 @@ impl Default for MethodDerived {
     }
 }
 
+impl MethodDerived {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for MethodDerived {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1298,6 +1312,16 @@ const _: () = {
     static_assertions::assert_not_impl_any!(crate::Derived:Drop);
 };
 const _: () = assert!(memoffset::offset_of!(crate::Derived, derived_1) == 12);
+const _: () = assert!(0 % ::core::mem::align_of::<crate::Base0>() == 0);
+const _: () =
+    assert!(0 + ::core::mem::size_of::<crate::Base0>() <= ::core::mem::size_of::<crate::Derived>());
+const _: () = assert!(0 % ::core::mem::align_of::<crate::Base1>() == 0);
+const _: () =
+    assert!(0 + ::core::mem::size_of::<crate::Base1>() <= ::core::mem::size_of::<crate::Derived>());
+const _: () = assert!(10 % ::core::mem::align_of::<crate::Base2>() == 0);
+const _: () = assert!(
+    10 + ::core::mem::size_of::<crate::Base2>() <= ::core::mem::size_of::<crate::Derived>()
+);
 
 const _: () = assert!(::core::mem::size_of::<crate::VirtualBase1>() == 24);
 const _: () = assert!(::core::mem::align_of::<crate::VirtualBase1>() == 8);
@@ -1364,3 +1388,13 @@ const _: () = {
 const _: () = {
     static_assertions::assert_not_impl_any!(crate::MethodDerived:Drop);
 };
+const _: () = assert!(0 % ::core::mem::align_of::<crate::MethodBase1>() == 0);
+const _: () = assert!(
+    0 + ::core::mem::size_of::<crate::MethodBase1>()
+        <= ::core::mem::size_of::<crate::MethodDerived>()
+);
+const _: () = assert!(0 % ::core::mem::align_of::<crate::MethodBase2>() == 0);
+const _: () = assert!(
+    0 + ::core::mem::size_of::<crate::MethodBase2>()
+        <= ::core::mem::size_of::<crate::MethodDerived>()
+);
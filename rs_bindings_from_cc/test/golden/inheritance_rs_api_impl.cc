@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -23,16 +24,19 @@ static_assert(sizeof(class Base0) == 1);
 static_assert(alignof(class Base0) == 1);
 
 extern "C" void __rust_thunk___ZN5Base0C1Ev(class Base0* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN5Base0C1ERKS_(class Base0* __this,
                                                const class Base0* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN5Base0C1EOS_(class Base0* __this,
                                               class Base0* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -50,16 +54,19 @@ static_assert(CRUBIT_SIZEOF(class Base1) == 16);
 static_assert(alignof(class Base1) == 8);
 
 extern "C" void __rust_thunk___ZN5Base1C1Ev(class Base1* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN5Base1C1ERKS_(class Base1* __this,
                                                const class Base1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN5Base1C1EOS_(class Base1* __this,
                                               class Base1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -77,16 +84,19 @@ static_assert(CRUBIT_SIZEOF(class Base2) == 2);
 static_assert(alignof(class Base2) == 2);
 
 extern "C" void __rust_thunk___ZN5Base2C1Ev(class Base2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN5Base2C1ERKS_(class Base2* __this,
                                                const class Base2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN5Base2C1EOS_(class Base2* __this,
                                               class Base2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -105,11 +115,13 @@ static_assert(alignof(struct Derived) == 8);
 static_assert(CRUBIT_OFFSET_OF(derived_1, struct Derived) == 12);
 
 extern "C" void __rust_thunk___ZN7DerivedC1Ev(struct Derived* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN7DerivedC1EOS_(struct Derived* __this,
                                                 struct Derived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -128,16 +140,19 @@ static_assert(alignof(class VirtualBase1) == 8);
 
 extern "C" void __rust_thunk___ZN12VirtualBase1C1Ev(
     class VirtualBase1* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN12VirtualBase1C1ERKS_(
     class VirtualBase1* __this, const class VirtualBase1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN12VirtualBase1C1EOS_(
     class VirtualBase1* __this, class VirtualBase1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -162,16 +177,19 @@ static_assert(alignof(class VirtualBase2) == 8);
 
 extern "C" void __rust_thunk___ZN12VirtualBase2C1Ev(
     class VirtualBase2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN12VirtualBase2C1ERKS_(
     class VirtualBase2* __this, const class VirtualBase2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN12VirtualBase2C1EOS_(
     class VirtualBase2* __this, class VirtualBase2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -196,16 +214,19 @@ static_assert(alignof(class VirtualDerived) == 8);
 
 extern "C" void __rust_thunk___ZN14VirtualDerivedC1Ev(
     class VirtualDerived* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN14VirtualDerivedC1ERKS_(
     class VirtualDerived* __this, const class VirtualDerived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN14VirtualDerivedC1EOS_(
     class VirtualDerived* __this, class VirtualDerived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -247,16 +268,19 @@ static_assert(sizeof(class MethodBase1) == 1);
 static_assert(alignof(class MethodBase1) == 1);
 
 extern "C" void __rust_thunk___ZN11MethodBase1C1Ev(class MethodBase1* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN11MethodBase1C1ERKS_(
     class MethodBase1* __this, const class MethodBase1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN11MethodBase1C1EOS_(
     class MethodBase1* __this, class MethodBase1* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -274,16 +298,19 @@ static_assert(sizeof(class MethodBase2) == 1);
 static_assert(alignof(class MethodBase2) == 1);
 
 extern "C" void __rust_thunk___ZN11MethodBase2C1Ev(class MethodBase2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN11MethodBase2C1ERKS_(
     class MethodBase2* __this, const class MethodBase2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN11MethodBase2C1EOS_(
     class MethodBase2* __this, class MethodBase2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -302,11 +329,13 @@ static_assert(alignof(class MethodDerived) == 1);
 
 extern "C" void __rust_thunk___ZN13MethodDerivedC1Ev(
     class MethodDerived* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13MethodDerivedC1EOS_(
     class MethodDerived* __this, class MethodDerived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
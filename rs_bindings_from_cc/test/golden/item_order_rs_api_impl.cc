@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(struct FirstStruct) == 4);
 static_assert(CRUBIT_OFFSET_OF(field, struct FirstStruct) == 0);
 
 extern "C" void __rust_thunk___ZN11FirstStructC1Ev(struct FirstStruct* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN11FirstStructC1EOS_(
     struct FirstStruct* __this, struct FirstStruct* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -50,11 +53,13 @@ static_assert(CRUBIT_OFFSET_OF(field, struct SecondStruct) == 0);
 
 extern "C" void __rust_thunk___ZN12SecondStructC1Ev(
     struct SecondStruct* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN12SecondStructC1EOS_(
     struct SecondStruct* __this, struct SecondStruct* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
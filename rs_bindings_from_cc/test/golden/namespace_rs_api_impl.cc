@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -25,12 +26,14 @@ static_assert(CRUBIT_OFFSET_OF(i, struct test_namespace_bindings::S) == 0);
 
 extern "C" void __rust_thunk___ZN23test_namespace_bindings1SC1Ev(
     struct test_namespace_bindings::S* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN23test_namespace_bindings1SC1EOS0_(
     struct test_namespace_bindings::S* __this,
     struct test_namespace_bindings::S* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -48,26 +51,17 @@ __rust_thunk___ZN23test_namespace_bindings1SaSEOS0_(
   return &__this->operator=(std::move(*__param_0));
 }
 
-extern "C" int __rust_thunk___ZN23test_namespace_bindings1fENS_1SE(
-    struct test_namespace_bindings::S* s) {
-  return test_namespace_bindings::f(std::move(*s));
-}
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings15inline_functionEv() {
   test_namespace_bindings::inline_function();
 }
 
-extern "C" void __rust_thunk___Z8identityN23test_namespace_bindings1SE(
-    struct test_namespace_bindings::S* __return,
-    struct test_namespace_bindings::S* s) {
-  new (__return) auto(identity(std::move(*s)));
-}
-
 static_assert(sizeof(struct test_namespace_bindings_reopened::inner::S) == 1);
 static_assert(alignof(struct test_namespace_bindings_reopened::inner::S) == 1);
 
 extern "C" void __rust_thunk___ZN32test_namespace_bindings_reopened5inner1SC1Ev(
     struct test_namespace_bindings_reopened::inner::S* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -75,6 +69,7 @@ extern "C" void
 __rust_thunk___ZN32test_namespace_bindings_reopened5inner1SC1EOS1_(
     struct test_namespace_bindings_reopened::inner::S* __this,
     struct test_namespace_bindings_reopened::inner::S* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -107,6 +102,7 @@ extern "C" void
 __rust_thunk___ZN30test_namespace_bindings_inline5inner23StructInInlineNamespaceC1Ev(
     struct test_namespace_bindings_inline::inner::StructInInlineNamespace*
         __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -116,6 +112,7 @@ __rust_thunk___ZN30test_namespace_bindings_inline5inner23StructInInlineNamespace
         __this,
     struct test_namespace_bindings_inline::inner::StructInInlineNamespace*
         __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
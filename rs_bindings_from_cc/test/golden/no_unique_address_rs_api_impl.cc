@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -25,11 +26,13 @@ static_assert(CRUBIT_OFFSET_OF(field1, struct Struct) == 0);
 static_assert(CRUBIT_OFFSET_OF(field2, struct Struct) == 4);
 
 extern "C" void __rust_thunk___ZN6StructC1Ev(struct Struct* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN6StructC1EOS_(struct Struct* __this,
                                                struct Struct* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -45,6 +48,7 @@ extern "C" struct Struct* __rust_thunk___ZN6StructaSEOS_(
 
 extern "C" void __rust_thunk___ZN6Struct4MakeEic(struct Struct* __return,
                                                  int f1, char f2) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(Struct::Make(f1, f2));
 }
 
@@ -55,12 +59,14 @@ static_assert(CRUBIT_OFFSET_OF(field2, struct PaddingBetweenFields) == 4);
 
 extern "C" void __rust_thunk___ZN20PaddingBetweenFieldsC1Ev(
     struct PaddingBetweenFields* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN20PaddingBetweenFieldsC1EOS_(
     struct PaddingBetweenFields* __this,
     struct PaddingBetweenFields* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -80,6 +86,7 @@ __rust_thunk___ZN20PaddingBetweenFieldsaSEOS_(
 
 extern "C" void __rust_thunk___ZN20PaddingBetweenFields4MakeEci(
     struct PaddingBetweenFields* __return, char f1, int f2) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(PaddingBetweenFields::Make(f1, f2));
 }
 
@@ -92,12 +99,14 @@ static_assert(CRUBIT_OFFSET_OF(inner_char_field,
 
 extern "C" void __rust_thunk___ZN30FieldInTailPadding_InnerStructC1Ev(
     struct FieldInTailPadding_InnerStruct* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN30FieldInTailPadding_InnerStructC1ERKS_(
     struct FieldInTailPadding_InnerStruct* __this,
     const struct FieldInTailPadding_InnerStruct* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -122,11 +131,13 @@ static_assert(CRUBIT_OFFSET_OF(char_in_tail_padding_of_prev_field,
 extern "C" void __rust_thunk___ZN18FieldInTailPaddingC1ERKS_(
     struct FieldInTailPadding* __this,
     const struct FieldInTailPadding* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN18FieldInTailPaddingC1EOS_(
     struct FieldInTailPadding* __this, struct FieldInTailPadding* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -151,6 +162,7 @@ __rust_thunk___ZN18FieldInTailPaddingaSEOS_(
 extern "C" void __rust_thunk___ZN18FieldInTailPaddingC1Eicc(
     struct FieldInTailPadding* __this, int inner_int, char inner_char,
     char outer_char) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, inner_int, inner_char, outer_char);
 }
 
@@ -256,9 +256,6 @@ impl PartialEq for Nontrivial {
     }
 }
 
-// Error while generating bindings for item 'Nontrivial::operator!=':
-// Bindings for this kind of operator (operator != with 2 parameter(s)) are not supported
-
 impl PartialOrd for Nontrivial {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
@@ -608,6 +605,13 @@ impl Default for NontrivialUnpin {
     }
 }
 
+impl NontrivialUnpin {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl From<::core::ffi::c_int> for NontrivialUnpin {
     #[inline(always)]
     fn from(field: ::core::ffi::c_int) -> Self {
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -26,12 +27,14 @@ static_assert(CRUBIT_OFFSET_OF(field, struct Nontrivial) == 0);
 extern "C" void __rust_thunk___ZN10NontrivialaSEf(struct Nontrivial* __return,
                                                   struct Nontrivial* __this,
                                                   float __param_0) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator=(__param_0));
 }
 
 extern "C" void __rust_thunk___ZNK10NontrivialplERKS_(
     struct Nontrivial* __return, const struct Nontrivial* __this,
     const struct Nontrivial* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator+(*rhs));
 }
 
@@ -41,26 +44,31 @@ static_assert(CRUBIT_OFFSET_OF(field, struct NontrivialInline) == 0);
 
 extern "C" void __rust_thunk___ZN16NontrivialInlineC1Ev(
     struct NontrivialInline* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN16NontrivialInlineC1Ei(
     struct NontrivialInline* __this, int field) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, field);
 }
 
 extern "C" void __rust_thunk___ZN16NontrivialInlineC1Eii(
     struct NontrivialInline* __this, int field, int unused) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, field, unused);
 }
 
 extern "C" void __rust_thunk___ZN16NontrivialInlineC1ERKS_(
     struct NontrivialInline* __this, const struct NontrivialInline* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN16NontrivialInlineC1EOS_(
     struct NontrivialInline* __this, struct NontrivialInline* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -96,17 +104,20 @@ static_assert(CRUBIT_OFFSET_OF(nontrivial_member, struct NontrivialMembers) ==
 
 extern "C" void __rust_thunk___ZN17NontrivialMembersC1Ev(
     struct NontrivialMembers* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN17NontrivialMembersC1ERKS_(
     struct NontrivialMembers* __this,
     const struct NontrivialMembers* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN17NontrivialMembersC1EOS_(
     struct NontrivialMembers* __this, struct NontrivialMembers* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -133,16 +144,19 @@ static_assert(CRUBIT_OFFSET_OF(field, struct NontrivialUnpin) == 0);
 
 extern "C" void __rust_thunk___Z12TakesByValue10Nontrivial(
     struct Nontrivial* __return, struct Nontrivial* nontrivial) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(TakesByValue(std::move(*nontrivial)));
 }
 
 extern "C" void __rust_thunk___Z18TakesByValueInline16NontrivialInline(
     struct NontrivialInline* __return, struct NontrivialInline* nontrivial) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(TakesByValueInline(std::move(*nontrivial)));
 }
 
 extern "C" void __rust_thunk___Z17TakesByValueUnpin15NontrivialUnpin(
     struct NontrivialUnpin* __return, struct NontrivialUnpin* nontrivial) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(TakesByValueUnpin(std::move(*nontrivial)));
 }
 
@@ -151,11 +165,13 @@ static_assert(alignof(struct NontrivialByValue) == 1);
 
 extern "C" void __rust_thunk___ZN17NontrivialByValueC1ERKS_(
     struct NontrivialByValue* __this, const struct NontrivialByValue* other) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *other);
 }
 
 extern "C" void __rust_thunk___ZN17NontrivialByValueC1EOS_(
     struct NontrivialByValue* __this, struct NontrivialByValue* other) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*other));
 }
 
@@ -173,6 +189,7 @@ extern "C" struct NontrivialByValue* __rust_thunk___ZN17NontrivialByValueaSEOS_(
 extern "C" void __rust_thunk___ZN17NontrivialByValueaSE10Nontrivial(
     struct NontrivialByValue* __return, struct NontrivialByValue* __this,
     struct Nontrivial* other) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator=(std::move(*other)));
 }
 
@@ -181,6 +198,7 @@ static_assert(alignof(struct Nonmovable) == 1);
 
 extern "C" void __rust_thunk___Z24ReturnsNonmovableByValuev(
     struct Nonmovable* __return) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(ReturnsNonmovableByValue());
 }
 
@@ -49,6 +49,13 @@ impl Default for AddableConstMember {
     }
 }
 
+impl AddableConstMember {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableConstMember {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -114,6 +121,13 @@ impl Default for AddableNonConstMember {
     }
 }
 
+impl AddableNonConstMember {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableNonConstMember {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -182,6 +196,13 @@ impl Default for AddableFriend {
     }
 }
 
+impl AddableFriend {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableFriend {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -244,6 +265,13 @@ impl Default for AddableFreeByConstRef {
     }
 }
 
+impl AddableFreeByConstRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableFreeByConstRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -294,6 +322,13 @@ impl Default for AddableFreeByMutRef {
     }
 }
 
+impl AddableFreeByMutRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableFreeByMutRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -344,6 +379,13 @@ impl Default for AddableFreeByValue {
     }
 }
 
+impl AddableFreeByValue {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableFreeByValue {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -394,6 +436,13 @@ impl Default for AddableFreeByRValueRef {
     }
 }
 
+impl AddableFreeByRValueRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableFreeByRValueRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -488,6 +537,13 @@ impl Default for Overloaded {
     }
 }
 
+impl Overloaded {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for Overloaded {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -554,6 +610,13 @@ impl Default for IncompatibleLHS {
     }
 }
 
+impl IncompatibleLHS {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for IncompatibleLHS {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -583,8 +646,17 @@ impl<'b> ::ctor::UnpinAssign<::ctor::RvalueReference<'b, Self>> for Incompatible
     }
 }
 
-// Error while generating bindings for item 'operator+':
-// Expected first parameter to be a record or reference
+impl<'a> ::core::ops::Add<&'a crate::IncompatibleLHS> for ::core::ffi::c_int {
+    type Output = crate::IncompatibleLHS;
+    #[inline(always)]
+    fn add(self, rhs: &'a crate::IncompatibleLHS) -> Self::Output {
+        unsafe {
+            let mut __return = ::core::mem::MaybeUninit::<crate::IncompatibleLHS>::uninit();
+            crate::detail::__rust_thunk___ZpliRK15IncompatibleLHS(&mut __return, self, rhs);
+            __return.assume_init()
+        }
+    }
+}
 
 // Error while generating bindings for item 'operator+':
 // Expected first parameter referent to be a record
@@ -613,6 +685,13 @@ impl Default for AddableReturnsVoid {
     }
 }
 
+impl AddableReturnsVoid {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddableReturnsVoid {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -767,6 +846,13 @@ impl Default for AddAssignMemberInt {
     }
 }
 
+impl AddAssignMemberInt {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignMemberInt {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -826,6 +912,13 @@ impl Default for AddAssignMemberByConstRef {
     }
 }
 
+impl AddAssignMemberByConstRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignMemberByConstRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -885,6 +978,13 @@ impl Default for AddAssignFreeByConstRef {
     }
 }
 
+impl AddAssignFreeByConstRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignFreeByConstRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -938,6 +1038,13 @@ impl Default for AddAssignFreeByValue {
     }
 }
 
+impl AddAssignFreeByValue {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignFreeByValue {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -997,6 +1104,13 @@ impl Default for AddAssignFriendByConstRef {
     }
 }
 
+impl AddAssignFriendByConstRef {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignFriendByConstRef {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1047,6 +1161,13 @@ impl Default for AddAssignFriendByValue {
     }
 }
 
+impl AddAssignFriendByValue {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignFriendByValue {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1106,6 +1227,13 @@ impl Default for AddAssignProhibitedConstMember {
     }
 }
 
+impl AddAssignProhibitedConstMember {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignProhibitedConstMember {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1163,6 +1291,13 @@ impl Default for AddAssignProhibitedFriendConstLhs {
     }
 }
 
+impl AddAssignProhibitedFriendConstLhs {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for AddAssignProhibitedFriendConstLhs {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1221,6 +1356,13 @@ impl Default for ManyOperators {
     }
 }
 
+impl ManyOperators {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for ManyOperators {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -1671,6 +1813,11 @@ mod detail {
             __this: &'a mut crate::IncompatibleLHS,
             __param_0: ::ctor::RvalueReference<'b, crate::IncompatibleLHS>,
         ) -> &'a mut crate::IncompatibleLHS;
+        pub(crate) fn __rust_thunk___ZpliRK15IncompatibleLHS<'a>(
+            __return: &mut ::core::mem::MaybeUninit<crate::IncompatibleLHS>,
+            lhs: ::core::ffi::c_int,
+            rhs: &'a crate::IncompatibleLHS,
+        );
         pub(crate) fn __rust_thunk___ZN18AddableReturnsVoidC1Ev<'a>(
             __this: &'a mut ::core::mem::MaybeUninit<crate::AddableReturnsVoid>,
         );
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(class AddableConstMember) == 4);
 
 extern "C" void __rust_thunk___ZN18AddableConstMemberC1Ev(
     class AddableConstMember* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18AddableConstMemberC1EOS_(
     class AddableConstMember* __this, class AddableConstMember* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -48,6 +51,7 @@ __rust_thunk___ZN18AddableConstMemberaSEOS_(
 extern "C" void __rust_thunk___ZNK18AddableConstMemberplERKS_(
     class AddableConstMember* __return, const class AddableConstMember* __this,
     const class AddableConstMember* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator+(*rhs));
 }
 
@@ -56,12 +60,14 @@ static_assert(alignof(class AddableNonConstMember) == 4);
 
 extern "C" void __rust_thunk___ZN21AddableNonConstMemberC1Ev(
     class AddableNonConstMember* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN21AddableNonConstMemberC1EOS_(
     class AddableNonConstMember* __this,
     class AddableNonConstMember* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -82,6 +88,7 @@ __rust_thunk___ZN21AddableNonConstMemberaSEOS_(
 extern "C" void __rust_thunk___ZN21AddableNonConstMemberplERKS_(
     class AddableNonConstMember* __return, class AddableNonConstMember* __this,
     const class AddableNonConstMember* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator+(*rhs));
 }
 
@@ -90,11 +97,13 @@ static_assert(alignof(class AddableFriend) == 4);
 
 extern "C" void __rust_thunk___ZN13AddableFriendC1Ev(
     class AddableFriend* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13AddableFriendC1EOS_(
     class AddableFriend* __this, class AddableFriend* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -111,6 +120,7 @@ extern "C" class AddableFriend* __rust_thunk___ZN13AddableFriendaSEOS_(
 extern "C" void __rust_thunk___ZplRK13AddableFriendS1_(
     class AddableFriend* __return, const class AddableFriend* lhs,
     const class AddableFriend* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(operator+(*lhs, *rhs));
 }
 
@@ -119,12 +129,14 @@ static_assert(alignof(class AddableFreeByConstRef) == 1);
 
 extern "C" void __rust_thunk___ZN21AddableFreeByConstRefC1Ev(
     class AddableFreeByConstRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN21AddableFreeByConstRefC1EOS_(
     class AddableFreeByConstRef* __this,
     class AddableFreeByConstRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -147,11 +159,13 @@ static_assert(alignof(class AddableFreeByMutRef) == 1);
 
 extern "C" void __rust_thunk___ZN19AddableFreeByMutRefC1Ev(
     class AddableFreeByMutRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN19AddableFreeByMutRefC1EOS_(
     class AddableFreeByMutRef* __this, class AddableFreeByMutRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -173,11 +187,13 @@ static_assert(alignof(class AddableFreeByValue) == 1);
 
 extern "C" void __rust_thunk___ZN18AddableFreeByValueC1Ev(
     class AddableFreeByValue* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18AddableFreeByValueC1EOS_(
     class AddableFreeByValue* __this, class AddableFreeByValue* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -199,12 +215,14 @@ static_assert(alignof(class AddableFreeByRValueRef) == 1);
 
 extern "C" void __rust_thunk___ZN22AddableFreeByRValueRefC1Ev(
     class AddableFreeByRValueRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN22AddableFreeByRValueRefC1EOS_(
     class AddableFreeByRValueRef* __this,
     class AddableFreeByRValueRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -226,18 +244,21 @@ extern "C" void __rust_thunk___ZplRK21AddableFreeByConstRefS1_(
     class AddableFreeByConstRef* __return,
     const class AddableFreeByConstRef* lhs,
     const class AddableFreeByConstRef* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(operator+(*lhs, *rhs));
 }
 
 extern "C" void __rust_thunk___ZplR19AddableFreeByMutRefS0_(
     class AddableFreeByMutRef* __return, class AddableFreeByMutRef* lhs,
     class AddableFreeByMutRef* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(operator+(*lhs, *rhs));
 }
 
 extern "C" void __rust_thunk___Zpl18AddableFreeByValueS_(
     class AddableFreeByValue* __return, class AddableFreeByValue* lhs,
     class AddableFreeByValue* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(operator+(std::move(*lhs), std::move(*rhs)));
 }
 
@@ -245,11 +266,13 @@ static_assert(sizeof(class Overloaded) == 1);
 static_assert(alignof(class Overloaded) == 1);
 
 extern "C" void __rust_thunk___ZN10OverloadedC1Ev(class Overloaded* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN10OverloadedC1EOS_(
     class Overloaded* __this, class Overloaded* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -268,11 +291,13 @@ static_assert(alignof(class IncompatibleLHS) == 1);
 
 extern "C" void __rust_thunk___ZN15IncompatibleLHSC1Ev(
     class IncompatibleLHS* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN15IncompatibleLHSC1EOS_(
     class IncompatibleLHS* __this, class IncompatibleLHS* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -286,16 +311,25 @@ extern "C" class IncompatibleLHS* __rust_thunk___ZN15IncompatibleLHSaSEOS_(
   return &__this->operator=(std::move(*__param_0));
 }
 
+extern "C" void __rust_thunk___ZpliRK15IncompatibleLHS(
+    class IncompatibleLHS* __return, int lhs,
+    const class IncompatibleLHS* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
+  new (__return) auto(operator+(lhs, *rhs));
+}
+
 static_assert(CRUBIT_SIZEOF(class AddableReturnsVoid) == 4);
 static_assert(alignof(class AddableReturnsVoid) == 4);
 
 extern "C" void __rust_thunk___ZN18AddableReturnsVoidC1Ev(
     class AddableReturnsVoid* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18AddableReturnsVoidC1EOS_(
     class AddableReturnsVoid* __this, class AddableReturnsVoid* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -317,12 +351,14 @@ static_assert(alignof(class AddableConstMemberNonunpin) == 4);
 
 extern "C" void __rust_thunk___ZN26AddableConstMemberNonunpinC1Ev(
     class AddableConstMemberNonunpin* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN26AddableConstMemberNonunpinC1ERKS_(
     class AddableConstMemberNonunpin* __this,
     const class AddableConstMemberNonunpin* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -337,6 +373,7 @@ extern "C" void __rust_thunk___ZNK26AddableConstMemberNonunpinplERKS_(
     class AddableConstMemberNonunpin* __return,
     const class AddableConstMemberNonunpin* __this,
     const class AddableConstMemberNonunpin* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator+(*rhs));
 }
 
@@ -350,11 +387,13 @@ static_assert(alignof(struct AddAssignMemberInt) == 1);
 
 extern "C" void __rust_thunk___ZN18AddAssignMemberIntC1Ev(
     struct AddAssignMemberInt* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18AddAssignMemberIntC1EOS_(
     struct AddAssignMemberInt* __this, struct AddAssignMemberInt* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -376,12 +415,14 @@ static_assert(alignof(struct AddAssignMemberByConstRef) == 1);
 
 extern "C" void __rust_thunk___ZN25AddAssignMemberByConstRefC1Ev(
     struct AddAssignMemberByConstRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN25AddAssignMemberByConstRefC1EOS_(
     struct AddAssignMemberByConstRef* __this,
     struct AddAssignMemberByConstRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -404,12 +445,14 @@ static_assert(alignof(struct AddAssignFreeByConstRef) == 1);
 
 extern "C" void __rust_thunk___ZN23AddAssignFreeByConstRefC1Ev(
     struct AddAssignFreeByConstRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN23AddAssignFreeByConstRefC1EOS_(
     struct AddAssignFreeByConstRef* __this,
     struct AddAssignFreeByConstRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -432,12 +475,14 @@ static_assert(alignof(struct AddAssignFreeByValue) == 1);
 
 extern "C" void __rust_thunk___ZN20AddAssignFreeByValueC1Ev(
     struct AddAssignFreeByValue* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN20AddAssignFreeByValueC1EOS_(
     struct AddAssignFreeByValue* __this,
     struct AddAssignFreeByValue* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -466,12 +511,14 @@ static_assert(alignof(struct AddAssignFriendByConstRef) == 1);
 
 extern "C" void __rust_thunk___ZN25AddAssignFriendByConstRefC1Ev(
     struct AddAssignFriendByConstRef* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN25AddAssignFriendByConstRefC1EOS_(
     struct AddAssignFriendByConstRef* __this,
     struct AddAssignFriendByConstRef* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -494,12 +541,14 @@ static_assert(alignof(struct AddAssignFriendByValue) == 1);
 
 extern "C" void __rust_thunk___ZN22AddAssignFriendByValueC1Ev(
     struct AddAssignFriendByValue* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN22AddAssignFriendByValueC1EOS_(
     struct AddAssignFriendByValue* __this,
     struct AddAssignFriendByValue* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -528,12 +577,14 @@ static_assert(alignof(struct AddAssignProhibitedConstMember) == 1);
 
 extern "C" void __rust_thunk___ZN30AddAssignProhibitedConstMemberC1Ev(
     struct AddAssignProhibitedConstMember* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN30AddAssignProhibitedConstMemberC1EOS_(
     struct AddAssignProhibitedConstMember* __this,
     struct AddAssignProhibitedConstMember* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -556,12 +607,14 @@ static_assert(alignof(struct AddAssignProhibitedFriendConstLhs) == 1);
 
 extern "C" void __rust_thunk___ZN33AddAssignProhibitedFriendConstLhsC1Ev(
     struct AddAssignProhibitedFriendConstLhs* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN33AddAssignProhibitedFriendConstLhsC1EOS_(
     struct AddAssignProhibitedFriendConstLhs* __this,
     struct AddAssignProhibitedFriendConstLhs* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -584,11 +637,13 @@ static_assert(alignof(struct ManyOperators) == 1);
 
 extern "C" void __rust_thunk___ZN13ManyOperatorsC1Ev(
     struct ManyOperators* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13ManyOperatorsC1EOS_(
     struct ManyOperators* __this, struct ManyOperators* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -604,71 +659,83 @@ extern "C" struct ManyOperators* __rust_thunk___ZN13ManyOperatorsaSEOS_(
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsngEv(
     struct ManyOperators* __return, const struct ManyOperators* __this) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator-());
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsntEv(
     struct ManyOperators* __return, const struct ManyOperators* __this) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator!());
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsplERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator+(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsmiERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator-(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsmlERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator*(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsdvERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator/(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsrmERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator%(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsanERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator&(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsorERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator|(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorseoERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator^(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorslsERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator<<(*rhs));
 }
 
 extern "C" void __rust_thunk___ZNK13ManyOperatorsrsERKS_(
     struct ManyOperators* __return, const struct ManyOperators* __this,
     const struct ManyOperators* rhs) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(__this->operator>>(*rhs));
 }
 
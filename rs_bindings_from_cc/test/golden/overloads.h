@@ -11,7 +11,11 @@ void Overload(int);
 // An overload where at least one of the functions is uncallable.
 // This can happen in real code, one example is the `void*` overload of
 // absl::flags_internal::FlagImpl::Read().
-inline void UncallableOverload(void* x) {}
+//
+// The thunk calling the `void*` overload wouldn't compile, so it has to be
+// excluded from the bindings.
+[[clang::annotate("crubit_do_not_bind")]] inline void UncallableOverload(
+    void* x) {}
 
 // TODO(b/251045039): delete this overload
 inline void UncallableOverload(int* x) {}
@@ -20,17 +20,22 @@
 // Exceptions. See /LICENSE for license information.
 // SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
-// Error while generating bindings for item 'Overload':
-// Cannot generate bindings for overloaded function
-
-// Error while generating bindings for item 'Overload':
-// Cannot generate bindings for overloaded function
+#[inline(always)]
+pub fn Overload() {
+    unsafe { crate::detail::__rust_thunk___Z8Overloadv() }
+}
 
-// Error while generating bindings for item 'UncallableOverload':
-// Cannot generate bindings for overloaded function
+/// Bound as `Overload_int`, because the C++ function `Overload` is overloaded.
+#[inline(always)]
+pub fn Overload_int(__param_0: ::core::ffi::c_int) {
+    unsafe { crate::detail::__rust_thunk___Z8Overloadi(__param_0) }
+}
 
-// Error while generating bindings for item 'UncallableOverload':
-// Cannot generate bindings for overloaded function
+/// TODO(b/251045039): delete this overload
+#[inline(always)]
+pub unsafe fn UncallableOverload(x: *mut ::core::ffi::c_int) {
+    crate::detail::__rust_thunk___Z18UncallableOverloadPi(x)
+}
 
 // Error while generating bindings for item 'Sizeof':
 // Class templates are not supported yet
@@ -52,6 +57,11 @@ mod detail {
     #[allow(unused_imports)]
     use super::*;
     extern "C" {
+        #[link_name = "_Z8Overloadv"]
+        pub(crate) fn __rust_thunk___Z8Overloadv();
+        #[link_name = "_Z8Overloadi"]
+        pub(crate) fn __rust_thunk___Z8Overloadi(__param_0: ::core::ffi::c_int);
+        pub(crate) fn __rust_thunk___Z18UncallableOverloadPi(x: *mut ::core::ffi::c_int);
         pub(crate) fn __rust_thunk___Z20AlsoTemplateOverloadv();
     }
 }
@@ -10,6 +10,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 
 // Public headers of the C++ library being wrapped.
 #include "rs_bindings_from_cc/test/golden/overloads.h"
@@ -17,6 +18,10 @@
 #pragma clang diagnostic push
 #pragma clang diagnostic ignored "-Wthread-safety-analysis"
 
+extern "C" void __rust_thunk___Z18UncallableOverloadPi(int* x) {
+  UncallableOverload(x);
+}
+
 extern "C" void __rust_thunk___Z20AlsoTemplateOverloadv() {
   AlsoTemplateOverload();
 }
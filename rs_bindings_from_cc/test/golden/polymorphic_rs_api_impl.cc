@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(class PolymorphicBase) == 8);
 
 extern "C" void __rust_thunk___ZN15PolymorphicBaseC1Ev(
     class PolymorphicBase* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN15PolymorphicBaseC1ERKS_(
     class PolymorphicBase* __this, const class PolymorphicBase* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -47,11 +50,13 @@ static_assert(alignof(class PolymorphicBase2) == 8);
 
 extern "C" void __rust_thunk___ZN16PolymorphicBase2C1Ev(
     class PolymorphicBase2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN16PolymorphicBase2C1ERKS_(
     class PolymorphicBase2* __this, const class PolymorphicBase2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -75,17 +80,20 @@ static_assert(alignof(class PolymorphicDerived) == 8);
 
 extern "C" void __rust_thunk___ZN18PolymorphicDerivedC1Ev(
     class PolymorphicDerived* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18PolymorphicDerivedC1ERKS_(
     class PolymorphicDerived* __this,
     const class PolymorphicDerived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN18PolymorphicDerivedC1EOS_(
     class PolymorphicDerived* __this, class PolymorphicDerived* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
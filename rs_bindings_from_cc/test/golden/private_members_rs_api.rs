@@ -46,6 +46,13 @@ pub mod test_namespace_bindings {
         }
     }
 
+    impl SomeClass {
+        #[inline(always)]
+        pub fn new() -> Self {
+            Default::default()
+        }
+    }
+
     impl<'b> From<::ctor::RvalueReference<'b, Self>> for SomeClass {
         #[inline(always)]
         fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -26,12 +27,14 @@ static_assert(CRUBIT_OFFSET_OF(public_member_variable_,
 
 extern "C" void __rust_thunk___ZN23test_namespace_bindings9SomeClassC1Ev(
     class test_namespace_bindings::SomeClass* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN23test_namespace_bindings9SomeClassC1EOS0_(
     class test_namespace_bindings::SomeClass* __this,
     class test_namespace_bindings::SomeClass* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -23,11 +24,13 @@ static_assert(CRUBIT_SIZEOF(class SomeClass) == 4);
 static_assert(alignof(class SomeClass) == 4);
 
 extern "C" void __rust_thunk___ZN9SomeClassC1Ev(class SomeClass* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN9SomeClassC1EOS_(class SomeClass* __this,
                                                   class SomeClass* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -43,6 +46,7 @@ extern "C" class SomeClass* __rust_thunk___ZN9SomeClassaSEOS_(
 
 extern "C" void __rust_thunk___ZN9SomeClass21static_factory_methodEi(
     class SomeClass* __return, int initial_value_of_field) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(SomeClass::static_factory_method(initial_value_of_field));
 }
 
@@ -43,6 +43,13 @@ impl Default for DifferentScope {
     }
 }
 
+impl DifferentScope {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for DifferentScope {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -104,6 +111,13 @@ pub mod test_namespace_bindings {
         }
     }
 
+    impl TemplateParam {
+        #[inline(always)]
+        pub fn new() -> Self {
+            Default::default()
+        }
+    }
+
     impl<'b> From<::ctor::RvalueReference<'b, Self>> for TemplateParam {
         #[inline(always)]
         fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -333,6 +347,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings10MyTemplateI14Differ
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings10MyTemplateI14DifferentScopeEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings10MyTemplateI14DifferentScopeEE
 {
@@ -416,6 +437,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings10MyTemplateINS_13Tem
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings10MyTemplateINS_13TemplateParamEEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings10MyTemplateINS_13TemplateParamEEE
 {
@@ -497,6 +525,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings10MyTemplateIiEE {
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings10MyTemplateIiEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings10MyTemplateIiEE
 {
@@ -578,6 +613,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoPara
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsINS0_IiiEEiEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsINS0_IiiEEiEE
 {
@@ -635,6 +677,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoPara
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsIifEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsIifEE
 {
@@ -692,6 +741,13 @@ impl Default for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoPara
     }
 }
 
+impl __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsIiiEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInstN23test_namespace_bindings21TemplateWithTwoParamsIiiEE
 {
@@ -827,6 +883,106 @@ impl<'b> ::ctor::Assign<::ctor::RvalueReference<'b, Self>>
     }
 }
 
+/// Explicit class template instantiation definition is imported similarly to
+/// how implicit typedeffed instantiations are.
+#[::ctor::recursively_pinned]
+#[repr(C)]
+pub struct __CcTemplateInstN23test_namespace_bindings8MyStructIfEE {
+    pub t: f32,
+}
+forward_declare::unsafe_define!(
+    forward_declare::symbol!("test_namespace_bindings::MyStruct<float>"),
+    crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE
+);
+
+impl ::ctor::CtorNew<()> for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE {
+    type CtorType = impl ::ctor::Ctor<Output = Self>;
+    #[inline(always)]
+    fn ctor_new(args: ()) -> Self::CtorType {
+        let () = args;
+        unsafe {
+            ::ctor::FnCtor::new(
+                move |dest: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<Self>>| {
+                    crate::detail::__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(::core::pin::Pin::into_inner_unchecked(dest));
+                },
+            )
+        }
+    }
+}
+
+impl<'b> ::ctor::CtorNew<&'b Self> for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE {
+    type CtorType = impl ::ctor::Ctor<Output = Self> + ::ctor::Captures<'b>;
+    #[inline(always)]
+    fn ctor_new(args: &'b Self) -> Self::CtorType {
+        let __param_0 = args;
+        unsafe {
+            ::ctor::FnCtor::new(
+                move |dest: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<Self>>| {
+                    crate::detail::__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1ERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(::core::pin::Pin::into_inner_unchecked(dest),__param_0);
+                },
+            )
+        }
+    }
+}
+impl<'b> ::ctor::CtorNew<(&'b Self,)> for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE {
+    type CtorType = impl ::ctor::Ctor<Output = Self> + ::ctor::Captures<'b>;
+    #[inline(always)]
+    fn ctor_new(args: (&'b Self,)) -> Self::CtorType {
+        let (arg,) = args;
+        <Self as ::ctor::CtorNew<&'b Self>>::ctor_new(arg)
+    }
+}
+
+impl<'b> ::ctor::CtorNew<::ctor::RvalueReference<'b, Self>>
+    for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE
+{
+    type CtorType = impl ::ctor::Ctor<Output = Self> + ::ctor::Captures<'b>;
+    #[inline(always)]
+    fn ctor_new(args: ::ctor::RvalueReference<'b, Self>) -> Self::CtorType {
+        let __param_0 = args;
+        unsafe {
+            ::ctor::FnCtor::new(
+                move |dest: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<Self>>| {
+                    crate::detail::__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(::core::pin::Pin::into_inner_unchecked(dest),__param_0);
+                },
+            )
+        }
+    }
+}
+impl<'b> ::ctor::CtorNew<(::ctor::RvalueReference<'b, Self>,)>
+    for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE
+{
+    type CtorType = impl ::ctor::Ctor<Output = Self> + ::ctor::Captures<'b>;
+    #[inline(always)]
+    fn ctor_new(args: (::ctor::RvalueReference<'b, Self>,)) -> Self::CtorType {
+        let (arg,) = args;
+        <Self as ::ctor::CtorNew<::ctor::RvalueReference<'b, Self>>>::ctor_new(arg)
+    }
+}
+
+impl<'b> ::ctor::Assign<&'b Self> for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE {
+    #[inline(always)]
+    fn assign<'a>(self: ::core::pin::Pin<&'a mut Self>, __param_0: &'b Self) {
+        unsafe {
+            crate::detail::__rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(self,__param_0);
+        }
+    }
+}
+
+impl<'b> ::ctor::Assign<::ctor::RvalueReference<'b, Self>>
+    for __CcTemplateInstN23test_namespace_bindings8MyStructIfEE
+{
+    #[inline(always)]
+    fn assign<'a>(
+        self: ::core::pin::Pin<&'a mut Self>,
+        __param_0: ::ctor::RvalueReference<'b, Self>,
+    ) {
+        unsafe {
+            crate::detail::__rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSEOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(self,__param_0);
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct __CcTemplateInst18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEE {
@@ -848,6 +1004,13 @@ impl Default for __CcTemplateInst18MyTopLevelTemplateIN23test_namespace_bindings
     }
 }
 
+impl __CcTemplateInst18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEE {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>>
     for __CcTemplateInst18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEE
 {
@@ -1319,6 +1482,55 @@ mod detail {
                 crate::__CcTemplateInstN23test_namespace_bindings8MyStructIcEE,
             >,
         ) -> ::core::pin::Pin<&'a mut crate::__CcTemplateInstN23test_namespace_bindings8MyStructIcEE>;
+        pub(crate) fn __rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
+            'a,
+        >(
+            __this: &'a mut ::core::mem::MaybeUninit<
+                crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+        );
+        pub(crate) fn __rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1ERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
+            'a,
+            'b,
+        >(
+            __this: &'a mut ::core::mem::MaybeUninit<
+                crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+            __param_0: &'b crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+        );
+        pub(crate) fn __rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
+            'a,
+            'b,
+        >(
+            __this: &'a mut ::core::mem::MaybeUninit<
+                crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+            __param_0: ::ctor::RvalueReference<
+                'b,
+                crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+        );
+        pub(crate) fn __rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
+            'a,
+            'b,
+        >(
+            __this: ::core::pin::Pin<
+                &'a mut crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+            __param_0: &'b crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+        ) -> ::core::pin::Pin<&'a mut crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE>;
+        pub(crate) fn __rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSEOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
+            'a,
+            'b,
+        >(
+            __this: ::core::pin::Pin<
+                &'a mut crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+            __param_0: ::ctor::RvalueReference<
+                'b,
+                crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE,
+            >,
+        ) -> ::core::pin::Pin<&'a mut crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE>;
         pub(crate) fn __rust_thunk___ZN18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc<
             'a,
         >(
@@ -1608,6 +1820,22 @@ const _: () = {
     static_assertions::assert_not_impl_any!(crate::__CcTemplateInstN23test_namespace_bindings8MyStructIcEE:Drop);
 };
 
+const _: () = assert!(
+    ::core::mem::size_of::<crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE>() == 4
+);
+const _: () = assert!(
+    ::core::mem::align_of::<crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE>() == 4
+);
+const _: () = {
+    static_assertions::assert_not_impl_any!(crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE:Copy);
+};
+const _: () = {
+    static_assertions::assert_not_impl_any!(crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE:Drop);
+};
+const _: () = assert!(
+    memoffset::offset_of!(crate::__CcTemplateInstN23test_namespace_bindings8MyStructIfEE, t) == 0
+);
+
 const _: () = assert!(
     ::core::mem::size_of::<
         crate::__CcTemplateInst18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEE,
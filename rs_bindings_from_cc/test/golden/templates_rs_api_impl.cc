@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(struct DifferentScope) == 1);
 
 extern "C" void __rust_thunk___ZN14DifferentScopeC1Ev(
     struct DifferentScope* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN14DifferentScopeC1EOS_(
     struct DifferentScope* __this, struct DifferentScope* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -47,6 +50,7 @@ static_assert(alignof(struct test_namespace_bindings::TemplateParam) == 1);
 
 extern "C" void __rust_thunk___ZN23test_namespace_bindings13TemplateParamC1Ev(
     struct test_namespace_bindings::TemplateParam* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -54,6 +58,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings13TemplateParamC1EOS0_(
     struct test_namespace_bindings::TemplateParam* __this,
     struct test_namespace_bindings::TemplateParam* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -77,12 +82,14 @@ static_assert(alignof(class private_classes::HasPrivateType) == 1);
 extern "C" void __rust_thunk___ZN15private_classes14HasPrivateTypeC1ERKS0_(
     class private_classes::HasPrivateType* __this,
     const class private_classes::HasPrivateType* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN15private_classes14HasPrivateTypeC1EOS0_(
     class private_classes::HasPrivateType* __this,
     class private_classes::HasPrivateType* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -108,6 +115,7 @@ static_assert(
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateI14DifferentScopeEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<DifferentScope>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -115,6 +123,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateI14DifferentScopeEC1EOS2___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<DifferentScope>* __this,
     class test_namespace_bindings::MyTemplate<DifferentScope>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -137,6 +146,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateI14DifferentScopeE6CreateES1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<DifferentScope>* __return,
     struct DifferentScope* value) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(
       test_namespace_bindings::MyTemplate<DifferentScope>::Create(
           std::move(*value)));
@@ -157,6 +167,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateINS_13TemplateParamEEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<
         test_namespace_bindings::TemplateParam>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -166,6 +177,7 @@ __rust_thunk___ZN23test_namespace_bindings10MyTemplateINS_13TemplateParamEEC1EOS
         test_namespace_bindings::TemplateParam>* __this,
     class test_namespace_bindings::MyTemplate<
         test_namespace_bindings::TemplateParam>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -194,6 +206,7 @@ __rust_thunk___ZN23test_namespace_bindings10MyTemplateINS_13TemplateParamEE6Crea
     class test_namespace_bindings::MyTemplate<
         test_namespace_bindings::TemplateParam>* __return,
     struct test_namespace_bindings::TemplateParam* value) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(
       test_namespace_bindings::MyTemplate<
           test_namespace_bindings::TemplateParam>::Create(std::move(*value)));
@@ -213,6 +226,7 @@ static_assert(alignof(class test_namespace_bindings::MyTemplate<int>) == 4);
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateIiEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<int>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -220,6 +234,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateIiEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<int>* __this,
     class test_namespace_bindings::MyTemplate<int>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -240,6 +255,7 @@ __rust_thunk___ZN23test_namespace_bindings10MyTemplateIiEaSEOS1___2f_2fthird_5fp
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings10MyTemplateIiE6CreateEi__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class test_namespace_bindings::MyTemplate<int>* __return, int value) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(test_namespace_bindings::MyTemplate<int>::Create(value));
 }
 
@@ -276,6 +292,7 @@ __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsINS0_IiiEEiEC1E
     struct test_namespace_bindings::TemplateWithTwoParams<
         test_namespace_bindings::TemplateWithTwoParams<int, int>, int>*
         __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -286,6 +303,7 @@ __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsINS0_IiiEEiEC1E
     struct test_namespace_bindings::TemplateWithTwoParams<
         test_namespace_bindings::TemplateWithTwoParams<int, int>, int>*
         __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -333,6 +351,7 @@ static_assert(
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsIifEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct test_namespace_bindings::TemplateWithTwoParams<int, float>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -341,6 +360,7 @@ __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsIifEC1EOS1___2f
     struct test_namespace_bindings::TemplateWithTwoParams<int, float>* __this,
     struct test_namespace_bindings::TemplateWithTwoParams<int, float>*
         __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -378,6 +398,7 @@ static_assert(
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsIiiEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct test_namespace_bindings::TemplateWithTwoParams<int, int>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -386,6 +407,7 @@ __rust_thunk___ZN23test_namespace_bindings21TemplateWithTwoParamsIiiEC1EOS1___2f
     struct test_namespace_bindings::TemplateWithTwoParams<int, int>* __this,
     struct test_namespace_bindings::TemplateWithTwoParams<int, int>*
         __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -411,6 +433,7 @@ static_assert(alignof(struct test_namespace_bindings::MyStruct<char>) == 1);
 extern "C" void
 __rust_thunk___ZN23test_namespace_bindings8MyStructIcEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct test_namespace_bindings::MyStruct<char>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -418,6 +441,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings8MyStructIcEC1ERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct test_namespace_bindings::MyStruct<char>* __this,
     const struct test_namespace_bindings::MyStruct<char>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -425,6 +449,7 @@ extern "C" void
 __rust_thunk___ZN23test_namespace_bindings8MyStructIcEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct test_namespace_bindings::MyStruct<char>* __this,
     struct test_namespace_bindings::MyStruct<char>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -442,6 +467,49 @@ __rust_thunk___ZN23test_namespace_bindings8MyStructIcEaSEOS1___2f_2fthird_5fpart
   return &__this->operator=(std::move(*__param_0));
 }
 
+static_assert(CRUBIT_SIZEOF(struct test_namespace_bindings::MyStruct<float>) ==
+              4);
+static_assert(alignof(struct test_namespace_bindings::MyStruct<float>) == 4);
+static_assert(
+    CRUBIT_OFFSET_OF(t, struct test_namespace_bindings::MyStruct<float>) == 0);
+
+extern "C" void
+__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
+    struct test_namespace_bindings::MyStruct<float>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
+  crubit::construct_at(__this);
+}
+
+extern "C" void
+__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1ERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
+    struct test_namespace_bindings::MyStruct<float>* __this,
+    const struct test_namespace_bindings::MyStruct<float>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
+  crubit::construct_at(__this, *__param_0);
+}
+
+extern "C" void
+__rust_thunk___ZN23test_namespace_bindings8MyStructIfEC1EOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
+    struct test_namespace_bindings::MyStruct<float>* __this,
+    struct test_namespace_bindings::MyStruct<float>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
+  crubit::construct_at(__this, std::move(*__param_0));
+}
+
+extern "C" struct test_namespace_bindings::MyStruct<float>*
+__rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSERKS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
+    struct test_namespace_bindings::MyStruct<float>* __this,
+    const struct test_namespace_bindings::MyStruct<float>* __param_0) {
+  return &__this->operator=(*__param_0);
+}
+
+extern "C" struct test_namespace_bindings::MyStruct<float>*
+__rust_thunk___ZN23test_namespace_bindings8MyStructIfEaSEOS1___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
+    struct test_namespace_bindings::MyStruct<float>* __this,
+    struct test_namespace_bindings::MyStruct<float>* __param_0) {
+  return &__this->operator=(std::move(*__param_0));
+}
+
 static_assert(
     sizeof(struct MyTopLevelTemplate<test_namespace_bindings::TemplateParam>) ==
     1);
@@ -458,6 +526,7 @@ static_assert(
 extern "C" void
 __rust_thunk___ZN18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamEEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     struct MyTopLevelTemplate<test_namespace_bindings::TemplateParam>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -466,6 +535,7 @@ __rust_thunk___ZN18MyTopLevelTemplateIN23test_namespace_bindings13TemplateParamE
     struct MyTopLevelTemplate<test_namespace_bindings::TemplateParam>* __this,
     struct MyTopLevelTemplate<test_namespace_bindings::TemplateParam>*
         __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -494,6 +564,7 @@ extern "C" void
 __rust_thunk___ZN24template_template_params10MyTemplateINS_6PolicyEEC1Ev__2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3atemplates_5fcc(
     class template_template_params::MyTemplate<
         template_template_params::Policy>* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
@@ -503,6 +574,7 @@ __rust_thunk___ZN24template_template_params10MyTemplateINS_6PolicyEEC1ERKS2___2f
         template_template_params::Policy>* __this,
     const class template_template_params::MyTemplate<
         template_template_params::Policy>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -512,6 +584,7 @@ __rust_thunk___ZN24template_template_params10MyTemplateINS_6PolicyEEC1EOS2___2f_
         template_template_params::Policy>* __this,
     class template_template_params::MyTemplate<
         template_template_params::Policy>* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -3,20 +3,57 @@
 # Exceptions. See /LICENSE for license information.
 # SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
+# Usage: test.sh EXPECTED ACTUAL [EXPECTED ACTUAL] [--update-goldens]
+#
+# Compares each pair of files (ignoring the license header of the expected
+# file). With `--update-goldens` (i.e. `bazel run :foo_test -- --update-goldens`),
+# overwrites the expected files in the workspace with the actual ones instead.
+
+UPDATE_GOLDENS=0
+FILES=()
+for arg in "$@"; do
+  if [[ "${arg}" == "--update-goldens" ]]; then
+    UPDATE_GOLDENS=1
+  else
+    FILES+=("${arg}")
+  fi
+done
+
 function prepend_license() {
   cat rs_bindings_from_cc/test/golden/LICENSE_HEADER "$1"
 }
 
-diff -u "$1" <(prepend_license "$2")
-STATUS1=$?
+# Compares the golden file "$1" with the generated file "$2".
+function check_golden() {
+  if ((UPDATE_GOLDENS)); then
+    if [[ -z "${BUILD_WORKSPACE_DIRECTORY:-}" ]]; then
+      echo "--update-goldens requires running the test with 'bazel run'."
+      return 1
+    fi
+    prepend_license "$2" > "${BUILD_WORKSPACE_DIRECTORY}/$1"
+    echo "Updated $1"
+    return 0
+  fi
 
-if [ "$#" == 2 ]; then
-  STATUS2=0
-else
-  diff -u "$3" <(prepend_license "$4")
-  STATUS2=$?
-fi
+  if diff -q "$1" <(prepend_license "$2") > /dev/null; then
+    return 0
+  fi
+  if [[ -n "${GOLDEN_DIFF:-}" ]]; then
+    echo "Changed items in $1 (- removed, + added, ~ changed):"
+    "${GOLDEN_DIFF}" "$1" <(prepend_license "$2")
+    echo
+  fi
+  diff -u "$1" <(prepend_license "$2")
+  return 1
+}
+
+STATUS=0
+for ((i = 0; i < ${#FILES[@]}; i += 2)); do
+  check_golden "${FILES[i]}" "${FILES[i + 1]}" || STATUS=1
+done
 
-if (($STATUS1 != 0 || $STATUS2 != 0)); then
-  exit 1
+if ((STATUS != 0)); then
+  echo "To update the golden files, run:"
+  echo "  bazel run ${TEST_TARGET:-<this test>} -- --update-goldens"
 fi
+exit "${STATUS}"
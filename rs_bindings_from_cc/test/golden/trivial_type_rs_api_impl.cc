@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,11 +25,13 @@ static_assert(alignof(struct ns::Trivial) == 4);
 static_assert(CRUBIT_OFFSET_OF(trivial_field, struct ns::Trivial) == 0);
 
 extern "C" void __rust_thunk___ZN2ns7TrivialC1Ev(struct ns::Trivial* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN2ns7TrivialC1EOS0_(
     struct ns::Trivial* __this, struct ns::Trivial* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -48,17 +51,20 @@ static_assert(CRUBIT_OFFSET_OF(trivial_field, struct ns::TrivialNonfinal) == 0);
 
 extern "C" void __rust_thunk___ZN2ns15TrivialNonfinalC1Ev(
     struct ns::TrivialNonfinal* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN2ns15TrivialNonfinalC1ERKS0_(
     struct ns::TrivialNonfinal* __this,
     const struct ns::TrivialNonfinal* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN2ns15TrivialNonfinalC1EOS0_(
     struct ns::TrivialNonfinal* __this, struct ns::TrivialNonfinal* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -75,13 +81,10 @@ __rust_thunk___ZN2ns15TrivialNonfinalaSEOS0_(
   return &__this->operator=(std::move(*__param_0));
 }
 
-extern "C" void __rust_thunk___ZN2ns12TakesByValueENS_7TrivialE(
-    struct ns::Trivial* __return, struct ns::Trivial* trivial) {
-  new (__return) auto(ns::TakesByValue(std::move(*trivial)));
-}
 extern "C" void
 __rust_thunk___ZN2ns27TakesTrivialNonfinalByValueENS_15TrivialNonfinalE(
     struct ns::TrivialNonfinal* __return, struct ns::TrivialNonfinal* trivial) {
+  crubit::UnpoisonPlacementStorage(__return);
   new (__return) auto(ns::TakesTrivialNonfinalByValue(std::move(*trivial)));
 }
 
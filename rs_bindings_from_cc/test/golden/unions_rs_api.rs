@@ -43,6 +43,13 @@ impl Default for EmptyUnion {
     }
 }
 
+impl EmptyUnion {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for EmptyUnion {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -195,6 +202,13 @@ impl Default for NonEmptyUnion {
     }
 }
 
+impl NonEmptyUnion {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for NonEmptyUnion {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -271,9 +285,7 @@ impl<'b> ::ctor::Assign<::ctor::RvalueReference<'b, Self>> for NonCopyUnion2 {
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub union UnionWithOpaqueField {
-    /// Reason for representing this field as a blob of bytes:
-    /// Unsupported type 'char[42]': Unsupported clang::Type class 'ConstantArray'
-    pub(crate) constant_array_field_not_yet_supported: [::core::mem::MaybeUninit<u8>; 42],
+    pub constant_array_field_not_yet_supported: [u8; 42],
 }
 forward_declare::unsafe_define!(
     forward_declare::symbol!("UnionWithOpaqueField"),
@@ -291,6 +303,13 @@ impl Default for UnionWithOpaqueField {
     }
 }
 
+impl UnionWithOpaqueField {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for UnionWithOpaqueField {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -538,6 +557,13 @@ impl Default for TypedefUnion {
     }
 }
 
+impl TypedefUnion {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for TypedefUnion {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -923,6 +949,9 @@ const _: () = {
 const _: () = {
     static_assertions::assert_not_impl_any!(crate::UnionWithOpaqueField:Drop);
 };
+const _: () = {
+    static_assertions::assert_impl_all!([u8; 42]:Copy);
+};
 
 const _: () = assert!(::core::mem::size_of::<crate::TrivialButInheritable>() == 4);
 const _: () = assert!(::core::mem::align_of::<crate::TrivialButInheritable>() == 4);
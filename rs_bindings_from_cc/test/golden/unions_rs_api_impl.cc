@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -23,11 +24,13 @@ static_assert(sizeof(union EmptyUnion) == 1);
 static_assert(alignof(union EmptyUnion) == 1);
 
 extern "C" void __rust_thunk___ZN10EmptyUnionC1Ev(union EmptyUnion* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN10EmptyUnionC1EOS_(
     union EmptyUnion* __this, union EmptyUnion* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -60,6 +63,7 @@ extern "C" void
 __rust_thunk___ZN44TriviallyCopyableButNontriviallyDestructibleC1ERKS_(
     struct TriviallyCopyableButNontriviallyDestructible* __this,
     const struct TriviallyCopyableButNontriviallyDestructible* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
@@ -78,11 +82,13 @@ static_assert(CRUBIT_OFFSET_OF(long_long_field, union NonEmptyUnion) == 0);
 
 extern "C" void __rust_thunk___ZN13NonEmptyUnionC1Ev(
     union NonEmptyUnion* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN13NonEmptyUnionC1EOS_(
     union NonEmptyUnion* __this, union NonEmptyUnion* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -123,11 +129,13 @@ static_assert(CRUBIT_OFFSET_OF(constant_array_field_not_yet_supported,
 
 extern "C" void __rust_thunk___ZN20UnionWithOpaqueFieldC1Ev(
     union UnionWithOpaqueField* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN20UnionWithOpaqueFieldC1EOS_(
     union UnionWithOpaqueField* __this, union UnionWithOpaqueField* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -150,18 +158,21 @@ static_assert(CRUBIT_OFFSET_OF(x, struct TrivialButInheritable) == 0);
 
 extern "C" void __rust_thunk___ZN21TrivialButInheritableC1Ev(
     struct TrivialButInheritable* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN21TrivialButInheritableC1ERKS_(
     struct TrivialButInheritable* __this,
     const struct TrivialButInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN21TrivialButInheritableC1EOS_(
     struct TrivialButInheritable* __this,
     struct TrivialButInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -185,17 +196,20 @@ static_assert(CRUBIT_OFFSET_OF(t, union UnionWithInheritable) == 0);
 
 extern "C" void __rust_thunk___ZN20UnionWithInheritableC1Ev(
     union UnionWithInheritable* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN20UnionWithInheritableC1ERKS_(
     union UnionWithInheritable* __this,
     const union UnionWithInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN20UnionWithInheritableC1EOS_(
     union UnionWithInheritable* __this, union UnionWithInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -217,11 +231,13 @@ static_assert(alignof(TypedefUnion) == 1);
 static_assert(CRUBIT_OFFSET_OF(trivial_member, TypedefUnion) == 0);
 
 extern "C" void __rust_thunk___ZN12TypedefUnionC1Ev(TypedefUnion* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN12TypedefUnionC1EOS_(TypedefUnion* __this,
                                                       TypedefUnion* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -241,18 +257,21 @@ static_assert(CRUBIT_OFFSET_OF(t, TypedefUnionWithInheritable) == 0);
 
 extern "C" void __rust_thunk___ZN27TypedefUnionWithInheritableC1Ev(
     TypedefUnionWithInheritable* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN27TypedefUnionWithInheritableC1ERKS_(
     TypedefUnionWithInheritable* __this,
     const TypedefUnionWithInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN27TypedefUnionWithInheritableC1EOS_(
     TypedefUnionWithInheritable* __this,
     TypedefUnionWithInheritable* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
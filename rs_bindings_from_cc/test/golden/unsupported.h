@@ -12,9 +12,6 @@ struct TrivialCustomType final {
   // added.
   bool operator||(const TrivialCustomType&) const;
 
-  // clang::DeclarationName::NameKind::CXXConversionFunctionName
-  explicit operator int() const { return i; }
-
   int i;
 };
 
@@ -39,16 +36,11 @@ struct PackedLayout final {
 // TODO(jeanpierreda): Use a dedicated unsupported type or attribute so that this
 // test is more stable and doesn't depend on which exact types/features are not
 // currently supported.
-volatile int* MultipleReasons(volatile int* n);
+volatile int&& MultipleReasons(volatile int&& n);
 
 struct ContainingStruct final {
-  struct NestedStruct final {
-    void NonStaticMemberFunction();
-    void StaticMemberFunction();
-  };
-
   // Doc comment for an unsupported field.
-  NestedStruct nested_struct;
+  volatile char volatile_field_not_yet_supported;
 };
 
 #endif  // CRUBIT_RS_BINDINGS_FROM_CC_TEST_GOLDEN_UNSUPPORTED_H_
@@ -46,6 +46,13 @@ impl Default for TrivialCustomType {
     }
 }
 
+impl TrivialCustomType {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for TrivialCustomType {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -78,9 +85,6 @@ impl<'b> ::ctor::UnpinAssign<::ctor::RvalueReference<'b, Self>> for TrivialCusto
 // Error while generating bindings for item 'TrivialCustomType::operator||':
 // Bindings for this kind of operator (operator || with 2 parameter(s)) are not supported
 
-// Error while generating bindings for item 'TrivialCustomType::operator int':
-// Function name is not supported: Unsupported name: operator int
-
 #[::ctor::recursively_pinned]
 #[repr(C)]
 pub struct NontrivialCustomType {
@@ -125,9 +129,9 @@ impl<'b> ::ctor::CtorNew<(::ctor::RvalueReference<'b, Self>,)> for NontrivialCus
 // Records with packed layout are not supported
 
 // Error while generating bindings for item 'MultipleReasons':
-// Parameter #0 is not supported: Unsupported type 'volatile int *': Unsupported `volatile` qualifier: volatile int
+// Parameter #0 is not supported: Unsupported type 'volatile int &&': Rvalue references to `volatile` types are not supported
 //
-// Return type is not supported: Unsupported type 'volatile int *': Unsupported `volatile` qualifier: volatile int
+// Return type is not supported: Unsupported type 'volatile int &&': Rvalue references to `volatile` types are not supported
 
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -135,8 +139,8 @@ pub struct ContainingStruct {
     /// Doc comment for an unsupported field.
     ///
     /// Reason for representing this field as a blob of bytes:
-    /// Unsupported type 'struct ContainingStruct::NestedStruct': No generated bindings found for 'NestedStruct'
-    pub(crate) nested_struct: [::core::mem::MaybeUninit<u8>; 1],
+    /// Unsupported `volatile` qualifier: volatile char
+    pub(crate) volatile_field_not_yet_supported: [::core::mem::MaybeUninit<u8>; 1],
 }
 forward_declare::unsafe_define!(
     forward_declare::symbol!("ContainingStruct"),
@@ -154,6 +158,13 @@ impl Default for ContainingStruct {
     }
 }
 
+impl ContainingStruct {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 impl<'b> From<::ctor::RvalueReference<'b, Self>> for ContainingStruct {
     #[inline(always)]
     fn from(__param_0: ::ctor::RvalueReference<'b, Self>) -> Self {
@@ -183,9 +194,6 @@ impl<'b> ::ctor::UnpinAssign<::ctor::RvalueReference<'b, Self>> for ContainingSt
     }
 }
 
-// Error while generating bindings for item 'ContainingStruct::NestedStruct':
-// Nested classes are not supported yet
-
 // CRUBIT_RS_BINDINGS_FROM_CC_TEST_GOLDEN_UNSUPPORTED_H_
 
 mod detail {
@@ -266,4 +274,5 @@ const _: () = {
 const _: () = {
     static_assertions::assert_not_impl_any!(crate::ContainingStruct:Drop);
 };
-const _: () = assert!(memoffset::offset_of!(crate::ContainingStruct, nested_struct) == 0);
+const _: () =
+    assert!(memoffset::offset_of!(crate::ContainingStruct, volatile_field_not_yet_supported) == 0);
@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -25,11 +26,13 @@ static_assert(CRUBIT_OFFSET_OF(i, struct TrivialCustomType) == 0);
 
 extern "C" void __rust_thunk___ZN17TrivialCustomTypeC1Ev(
     struct TrivialCustomType* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN17TrivialCustomTypeC1EOS_(
     struct TrivialCustomType* __this, struct TrivialCustomType* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -51,15 +54,18 @@ static_assert(CRUBIT_OFFSET_OF(i, struct NontrivialCustomType) == 0);
 
 static_assert(sizeof(struct ContainingStruct) == 1);
 static_assert(alignof(struct ContainingStruct) == 1);
-static_assert(CRUBIT_OFFSET_OF(nested_struct, struct ContainingStruct) == 0);
+static_assert(CRUBIT_OFFSET_OF(volatile_field_not_yet_supported,
+                               struct ContainingStruct) == 0);
 
 extern "C" void __rust_thunk___ZN16ContainingStructC1Ev(
     struct ContainingStruct* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN16ContainingStructC1EOS_(
     struct ContainingStruct* __this, struct ContainingStruct* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
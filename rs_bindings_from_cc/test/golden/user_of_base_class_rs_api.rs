@@ -330,6 +330,16 @@ const _: () = {
     static_assertions::assert_not_impl_any!(crate::Derived2:Drop);
 };
 const _: () = assert!(memoffset::offset_of!(crate::Derived2, derived_1) == 20);
+const _: () = assert!(8 % ::core::mem::align_of::<inheritance_cc::Base1>() == 0);
+const _: () = assert!(
+    8 + ::core::mem::size_of::<inheritance_cc::Base1>()
+        <= ::core::mem::size_of::<crate::Derived2>()
+);
+const _: () = assert!(18 % ::core::mem::align_of::<inheritance_cc::Base2>() == 0);
+const _: () = assert!(
+    18 + ::core::mem::size_of::<inheritance_cc::Base2>()
+        <= ::core::mem::size_of::<crate::Derived2>()
+);
 
 const _: () = assert!(::core::mem::size_of::<crate::VirtualDerived2>() == 32);
 const _: () = assert!(::core::mem::align_of::<crate::VirtualDerived2>() == 8);
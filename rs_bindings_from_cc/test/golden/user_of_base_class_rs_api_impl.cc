@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -24,16 +25,19 @@ static_assert(alignof(struct Derived2) == 8);
 static_assert(CRUBIT_OFFSET_OF(derived_1, struct Derived2) == 20);
 
 extern "C" void __rust_thunk___ZN8Derived2C1Ev(struct Derived2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN8Derived2C1ERKS_(
     struct Derived2* __this, const struct Derived2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN8Derived2C1EOS_(struct Derived2* __this,
                                                  struct Derived2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -57,16 +61,19 @@ static_assert(alignof(class VirtualDerived2) == 8);
 
 extern "C" void __rust_thunk___ZN15VirtualDerived2C1Ev(
     class VirtualDerived2* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN15VirtualDerived2C1ERKS_(
     class VirtualDerived2* __this, const class VirtualDerived2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, *__param_0);
 }
 
 extern "C" void __rust_thunk___ZN15VirtualDerived2C1EOS_(
     class VirtualDerived2* __this, class VirtualDerived2* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
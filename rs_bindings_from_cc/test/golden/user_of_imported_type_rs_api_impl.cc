@@ -11,6 +11,7 @@
 
 #include "support/internal/cxx20_backports.h"
 #include "support/internal/offsetof.h"
+#include "support/internal/sanitizers.h"
 #include "support/internal/sizeof.h"
 
 // Public headers of the C++ library being wrapped.
@@ -19,22 +20,19 @@
 #pragma clang diagnostic push
 #pragma clang diagnostic ignored "-Wthread-safety-analysis"
 
-extern "C" void __rust_thunk___Z16UsesImportedTypeN2ns7TrivialE(
-    struct ns::Trivial* __return, struct ns::Trivial* t) {
-  new (__return) auto(UsesImportedType(std::move(*t)));
-}
-
 static_assert(CRUBIT_SIZEOF(struct UserOfImportedType) == 8);
 static_assert(alignof(struct UserOfImportedType) == 8);
 static_assert(CRUBIT_OFFSET_OF(trivial, struct UserOfImportedType) == 0);
 
 extern "C" void __rust_thunk___ZN18UserOfImportedTypeC1Ev(
     struct UserOfImportedType* __this) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this);
 }
 
 extern "C" void __rust_thunk___ZN18UserOfImportedTypeC1EOS_(
     struct UserOfImportedType* __this, struct UserOfImportedType* __param_0) {
+  crubit::UnpoisonPlacementStorage(__this);
   crubit::construct_at(__this, std::move(*__param_0));
 }
 
@@ -56,6 +56,28 @@ struct Derived final : Base0, Base1, Base4 {
   char private_field;  // NOLINT(clang-diagnostic-unused-private-field)
 };
 
+// Public fields of base classes are accessible on the derived class.
+class PublicFieldsBase {
+ public:
+  int base_field = 1;
+  char base_tail_field = 2;
+};
+
+class PublicFieldsMid : public PublicFieldsBase {
+ public:
+  // Lives in the tail padding of `PublicFieldsBase`.
+  char mid_field = 3;
+};
+
+struct PublicFieldsDerived final : PublicFieldsMid {
+  char derived_field = 4;
+
+  size_t base_address() const {
+    const PublicFieldsBase* base = this;
+    return reinterpret_cast<size_t>(base);
+  }
+};
+
 namespace virtual_inheritance {
 
 class VirtualBase2 : public virtual Base1 {};
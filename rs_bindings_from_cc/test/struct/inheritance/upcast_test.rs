@@ -24,6 +24,17 @@ mod tests {
         assert_eq!(base4 as *const _ as usize, derived.base4_address());
     }
 
+    #[test]
+    fn test_base_fields() {
+        let derived = PublicFieldsDerived::default();
+        assert_eq!(derived.derived_field, 4);
+        assert_eq!(derived.mid_field, 3);
+        assert_eq!(derived.base_tail_field, 2);
+        assert_eq!(derived.base_field, 1);
+        let base: &PublicFieldsBase = &derived;
+        assert_eq!(base as *const _ as usize, derived.base_address());
+    }
+
     #[test]
     fn test_virtual_upcast() {
         use upcast::virtual_inheritance::*;
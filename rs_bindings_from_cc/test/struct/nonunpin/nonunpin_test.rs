@@ -161,6 +161,19 @@ mod tests {
         assert_eq!(y.addr(), &*y as *const _ as usize);
     }
 
+    /// Any `Ctor` can be passed by value: the argument is constructed in
+    /// storage owned by the caller, moved into the C++ parameter, and destroyed
+    /// after the call.
+    #[test]
+    fn test_pass_ctor_by_value() {
+        assert_eq!(nonunpin::GetValueFromValue(Nonunpin::ctor_new(42)), 42);
+        ctor::emplace! {
+            let x = Nonunpin::ctor_new(24);
+        }
+        assert_eq!(nonunpin::GetValueFromValue(x.AsValue()), 24);
+        assert_eq!(x.value(), 24);
+    }
+
     #[test]
     fn test_nonmovable_ctor() {
         ctor::emplace! {
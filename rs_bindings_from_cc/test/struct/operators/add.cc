@@ -20,6 +20,10 @@ UnpinStructByValue operator+(UnpinStructByValue lhs, UnpinStructByValue rhs) {
   return UnpinStructByValue{lhs.i + rhs.i};
 }
 
+UnpinStructWithIntLhs operator+(int lhs, const UnpinStructWithIntLhs& rhs) {
+  return UnpinStructWithIntLhs{lhs + rhs.i};
+}
+
 char operator+(AddableOverloaded lhs, std::int16_t rhs) {
   return lhs.int16_char;
 }
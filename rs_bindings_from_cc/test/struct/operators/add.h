@@ -113,6 +113,16 @@ UnpinStructByMutRef operator+(UnpinStructByMutRef& lhs,
 // }
 UnpinStructByValue operator+(UnpinStructByValue lhs, UnpinStructByValue rhs);
 
+struct UnpinStructWithIntLhs final {
+  int i;
+};
+
+// impl Add<&UnpinStructWithIntLhs> for i32 {
+//     type Output = UnpinStructWithIntLhs;
+//     ..
+// }
+UnpinStructWithIntLhs operator+(int lhs, const UnpinStructWithIntLhs& rhs);
+
 struct AddableOverloaded final {
   char int16_char;
   char int32_char;
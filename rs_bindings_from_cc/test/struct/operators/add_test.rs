@@ -81,6 +81,12 @@ mod tests {
         assert_eq!(33, (s1 + s2).i);
     }
 
+    #[test]
+    fn test_add_free_with_int_lhs() {
+        let s = UnpinStructWithIntLhs { i: 22 };
+        assert_eq!(33, (11 + &s).i);
+    }
+
     #[test]
     fn test_add_overloaded() {
         let s = AddableOverloaded { int16_char: b'A', int32_char: b'B' };
@@ -0,0 +1,161 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of the C++ `absl::Span` type.
+//!
+//! `rs_bindings_from_cc` maps `absl::Span<const T>` into `Span<T>`, and
+//! `absl::Span<T>` into `SpanMut<T>`. Both have the layout of `absl::Span`: a
+//! pointer to the first element, followed by the number of elements.
+//!
+//! Functions that take spans accept Rust slices instead, which are converted
+//! with `From`. Spans that are returned by functions (or stored in fields) are
+//! raw views with no lifetime, which are only converted back into slices if the
+//! lifetime of the span is known.
+
+use core::fmt;
+
+/// An `absl::Span<const T>`: a pointer to `len()` contiguous `T`s.
+#[repr(C)]
+pub struct Span<T> {
+    data: *const T,
+    len: usize,
+}
+
+impl<T> Span<T> {
+    /// Returns a span of `len` `T`s starting at `data`.
+    pub fn from_raw_parts(data: *const T, len: usize) -> Self {
+        Span { data, len }
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the elements of the span as a slice.
+    ///
+    /// # Safety
+    ///
+    /// The span must point to `len()` initialized `T`s, which must not be
+    /// mutated or destroyed for `'a`.
+    pub unsafe fn as_slice<'a>(self) -> &'a [T] {
+        raw_slice::from_raw_parts(self.data, self.len)
+    }
+}
+
+impl<T> Clone for Span<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Span<T> {}
+
+impl<T> fmt::Debug for Span<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Span").field("data", &self.data).field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for Span<T> {
+    fn from(s: &'a [T]) -> Self {
+        Span::from_raw_parts(s.as_ptr(), s.len())
+    }
+}
+
+impl<T> From<SpanMut<T>> for Span<T> {
+    fn from(s: SpanMut<T>) -> Self {
+        Span::from_raw_parts(s.as_mut_ptr(), s.len())
+    }
+}
+
+/// An `absl::Span<T>`: a pointer to `len()` contiguous, mutable `T`s.
+#[repr(C)]
+pub struct SpanMut<T> {
+    data: *mut T,
+    len: usize,
+}
+
+impl<T> SpanMut<T> {
+    /// Returns a span of `len` `T`s starting at `data`.
+    pub fn from_raw_parts(data: *mut T, len: usize) -> Self {
+        SpanMut { data, len }
+    }
+
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the elements of the span as a mutable slice.
+    ///
+    /// # Safety
+    ///
+    /// The span must point to `len()` initialized `T`s, which must not be
+    /// accessed other than through the returned slice or destroyed for `'a`.
+    pub unsafe fn as_mut_slice<'a>(self) -> &'a mut [T] {
+        raw_slice::from_raw_parts_mut(self.data, self.len)
+    }
+}
+
+impl<T> Clone for SpanMut<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SpanMut<T> {}
+
+impl<T> fmt::Debug for SpanMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpanMut").field("data", &self.data).field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T> From<&'a mut [T]> for SpanMut<T> {
+    fn from(s: &'a mut [T]) -> Self {
+        SpanMut::from_raw_parts(s.as_mut_ptr(), s.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_span() {
+        let elements = [1, 2, 3];
+        let span = Span::from(&elements[..]);
+        assert_eq!(span.len(), 3);
+        assert_eq!(unsafe { span.as_slice() }, &[1, 2, 3]);
+
+        // A default-constructed `absl::Span` has a null data pointer.
+        let empty = Span::<i32>::from_raw_parts(core::ptr::null(), 0);
+        assert!(empty.is_empty());
+        assert_eq!(unsafe { empty.as_slice() }, &[] as &[i32]);
+    }
+
+    #[test]
+    fn test_span_mut() {
+        let mut elements = [1, 2, 3];
+        let span = SpanMut::from(&mut elements[..]);
+        let slice = unsafe { span.as_mut_slice() };
+        slice[1] = 5;
+        assert_eq!(unsafe { Span::from(span).as_slice() }, &[1, 5, 3]);
+    }
+}
@@ -0,0 +1,52 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `Status` and `StatusOr` (see `absl_status.rs`).
+
+#include <cstddef>
+#include <cstdint>
+#include <memory>
+#include <new>
+
+#include "absl/status/status.h"
+#include "absl/status/statusor.h"
+#include "absl/strings/string_view.h"
+
+// `Status` is a single tagged pointer, and `StatusOr<T>` is a `Status`
+// followed by the storage for a `T`.
+static_assert(sizeof(absl::Status) == sizeof(uintptr_t));
+static_assert(alignof(absl::Status) == alignof(uintptr_t));
+static_assert(sizeof(absl::StatusOr<int64_t>) ==
+              sizeof(absl::Status) + sizeof(int64_t));
+
+extern "C" void crubit_absl_status_new(absl::Status* dest, int code,
+                                       const char* message_data,
+                                       size_t message_size) {
+  new (dest) absl::Status(static_cast<absl::StatusCode>(code),
+                          absl::string_view(message_data, message_size));
+}
+
+extern "C" void crubit_absl_status_copy(absl::Status* dest,
+                                        const absl::Status* src) {
+  new (dest) absl::Status(*src);
+}
+
+extern "C" void crubit_absl_status_destroy(absl::Status* status) {
+  std::destroy_at(status);
+}
+
+extern "C" bool crubit_absl_status_ok(const absl::Status* status) {
+  return status->ok();
+}
+
+extern "C" int crubit_absl_status_code(const absl::Status* status) {
+  return static_cast<int>(status->code());
+}
+
+extern "C" void crubit_absl_status_message(const absl::Status* status,
+                                           const char** data, size_t* size) {
+  absl::string_view message = status->message();
+  *data = message.data();
+  *size = message.size();
+}
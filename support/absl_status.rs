@@ -0,0 +1,289 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of the C++ `absl::Status` and `absl::StatusOr` types.
+//!
+//! `rs_bindings_from_cc` maps `absl::Status` into `Status`, and
+//! `absl::StatusOr<T>` into `StatusOr<T>`, which have the same layout. Functions
+//! that return them return a `Result<(), StatusError>` or a
+//! `Result<T, StatusError>` instead, converted with `into_result`.
+//!
+//! Statuses are reference counted in C++, so copying and destroying them calls
+//! into C++.
+
+use core::ffi::c_char;
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+/// The code of a `Status` (`absl::StatusCode`).
+///
+/// C++ code may use codes other than the canonical ones below, so this is not
+/// an enum.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StatusCode(pub i32);
+
+impl StatusCode {
+    pub const OK: StatusCode = StatusCode(0);
+    pub const CANCELLED: StatusCode = StatusCode(1);
+    pub const UNKNOWN: StatusCode = StatusCode(2);
+    pub const INVALID_ARGUMENT: StatusCode = StatusCode(3);
+    pub const DEADLINE_EXCEEDED: StatusCode = StatusCode(4);
+    pub const NOT_FOUND: StatusCode = StatusCode(5);
+    pub const ALREADY_EXISTS: StatusCode = StatusCode(6);
+    pub const PERMISSION_DENIED: StatusCode = StatusCode(7);
+    pub const RESOURCE_EXHAUSTED: StatusCode = StatusCode(8);
+    pub const FAILED_PRECONDITION: StatusCode = StatusCode(9);
+    pub const ABORTED: StatusCode = StatusCode(10);
+    pub const OUT_OF_RANGE: StatusCode = StatusCode(11);
+    pub const UNIMPLEMENTED: StatusCode = StatusCode(12);
+    pub const INTERNAL: StatusCode = StatusCode(13);
+    pub const UNAVAILABLE: StatusCode = StatusCode(14);
+    pub const DATA_LOSS: StatusCode = StatusCode(15);
+    pub const UNAUTHENTICATED: StatusCode = StatusCode(16);
+
+    /// Returns the name of the code, as spelled by `absl::StatusCodeToString`,
+    /// or `None` for non-canonical codes.
+    pub fn name(self) -> Option<&'static str> {
+        const NAMES: [&str; 17] = [
+            "OK",
+            "CANCELLED",
+            "UNKNOWN",
+            "INVALID_ARGUMENT",
+            "DEADLINE_EXCEEDED",
+            "NOT_FOUND",
+            "ALREADY_EXISTS",
+            "PERMISSION_DENIED",
+            "RESOURCE_EXHAUSTED",
+            "FAILED_PRECONDITION",
+            "ABORTED",
+            "OUT_OF_RANGE",
+            "UNIMPLEMENTED",
+            "INTERNAL",
+            "UNAVAILABLE",
+            "DATA_LOSS",
+            "UNAUTHENTICATED",
+        ];
+        usize::try_from(self.0).ok().and_then(|index| NAMES.get(index).copied())
+    }
+}
+
+impl fmt::Debug for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "StatusCode({})", self.0),
+        }
+    }
+}
+
+/// An `absl::Status`: either OK, or an error code with a message.
+#[repr(C)]
+pub struct Status {
+    rep: usize,
+}
+
+mod detail {
+    use super::*;
+    extern "C" {
+        pub(crate) fn crubit_absl_status_new(
+            dest: *mut Status,
+            code: i32,
+            message_data: *const c_char,
+            message_size: usize,
+        );
+        pub(crate) fn crubit_absl_status_copy(dest: *mut Status, src: *const Status);
+        pub(crate) fn crubit_absl_status_destroy(status: *mut Status);
+        pub(crate) fn crubit_absl_status_ok(status: *const Status) -> bool;
+        pub(crate) fn crubit_absl_status_code(status: *const Status) -> i32;
+        pub(crate) fn crubit_absl_status_message(
+            status: *const Status,
+            data: *mut *const c_char,
+            size: *mut usize,
+        );
+    }
+}
+
+impl Status {
+    /// Returns a status with the given code and message
+    /// (`absl::Status(code, message)`). The message is ignored for
+    /// `StatusCode::OK`.
+    pub fn new(code: StatusCode, message: &[u8]) -> Self {
+        let mut status = MaybeUninit::<Self>::uninit();
+        unsafe {
+            detail::crubit_absl_status_new(
+                status.as_mut_ptr(),
+                code.0,
+                message.as_ptr().cast(),
+                message.len(),
+            );
+            status.assume_init()
+        }
+    }
+
+    pub fn ok(&self) -> bool {
+        unsafe { detail::crubit_absl_status_ok(self) }
+    }
+
+    pub fn code(&self) -> StatusCode {
+        StatusCode(unsafe { detail::crubit_absl_status_code(self) })
+    }
+
+    /// Returns the error message, which is empty for OK statuses.
+    pub fn message(&self) -> &[u8] {
+        let mut data = core::ptr::null();
+        let mut size = 0;
+        unsafe {
+            detail::crubit_absl_status_message(self, &mut data, &mut size);
+            raw_slice::from_raw_parts(data.cast(), size)
+        }
+    }
+
+    /// Returns `Ok(())` for OK statuses, and a `StatusError` otherwise.
+    pub fn into_result(self) -> Result<(), StatusError> {
+        if self.ok() {
+            Ok(())
+        } else {
+            Err(StatusError(self))
+        }
+    }
+}
+
+impl Clone for Status {
+    fn clone(&self) -> Self {
+        let mut status = MaybeUninit::<Self>::uninit();
+        unsafe {
+            detail::crubit_absl_status_copy(status.as_mut_ptr(), self);
+            status.assume_init()
+        }
+    }
+}
+
+impl Drop for Status {
+    fn drop(&mut self) {
+        unsafe { detail::crubit_absl_status_destroy(self) }
+    }
+}
+
+impl fmt::Debug for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ok() {
+            f.write_str("OK")
+        } else {
+            write_error(f, self.code(), self.message())
+        }
+    }
+}
+
+/// Writes an error like `absl::Status::ToString()` does, e.g.
+/// `NOT_FOUND: no such file`.
+fn write_error(f: &mut fmt::Formatter<'_>, code: StatusCode, message: &[u8]) -> fmt::Result {
+    write!(f, "{code:?}: ")?;
+    match core::str::from_utf8(message) {
+        Ok(message) => f.write_str(message),
+        Err(_) => message.iter().try_for_each(|&b| write!(f, "{}", core::ascii::escape_default(b))),
+    }
+}
+
+/// A non-OK `Status`, as the error of a `Result`.
+#[derive(Clone)]
+pub struct StatusError(Status);
+
+impl StatusError {
+    pub fn code(&self) -> StatusCode {
+        self.0.code()
+    }
+
+    pub fn message(&self) -> &[u8] {
+        self.0.message()
+    }
+
+    /// Returns the (non-OK) status, e.g. to pass it back to C++.
+    pub fn into_status(self) -> Status {
+        self.0
+    }
+}
+
+impl From<StatusError> for Status {
+    fn from(error: StatusError) -> Self {
+        error.into_status()
+    }
+}
+
+impl fmt::Debug for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_error(f, self.code(), self.message())
+    }
+}
+
+/// An `absl::StatusOr<T>`: either a `T`, or a non-OK `Status`.
+///
+/// The fields are in the order of the layout of `absl::StatusOr`, which holds
+/// an OK status if (and only if) it holds a value.
+#[repr(C)]
+pub struct StatusOr<T> {
+    status: ManuallyDrop<Status>,
+    value: MaybeUninit<T>,
+}
+
+impl<T> StatusOr<T> {
+    pub fn ok(&self) -> bool {
+        self.status.ok()
+    }
+
+    /// Returns the status, which is OK if there is a value.
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// Returns `Ok` with the value, or the `StatusError` if there is no value.
+    pub fn into_result(self) -> Result<T, StatusError> {
+        let mut this = ManuallyDrop::new(self);
+        let status = unsafe { ManuallyDrop::take(&mut this.status) };
+        if status.ok() {
+            Ok(unsafe { this.value.assume_init_read() })
+        } else {
+            Err(StatusError(status))
+        }
+    }
+}
+
+impl<T> Drop for StatusOr<T> {
+    fn drop(&mut self) {
+        if self.status.ok() {
+            unsafe { self.value.assume_init_drop() }
+        }
+        unsafe { ManuallyDrop::drop(&mut self.status) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StatusOr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ok() {
+            fmt::Debug::fmt(unsafe { self.value.assume_init_ref() }, f)
+        } else {
+            fmt::Debug::fmt(&*self.status, f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_status_code_names() {
+        assert_eq!(StatusCode::OK.name(), Some("OK"));
+        assert_eq!(StatusCode::NOT_FOUND.name(), Some("NOT_FOUND"));
+        assert_eq!(StatusCode::UNAUTHENTICATED.name(), Some("UNAUTHENTICATED"));
+        assert_eq!(StatusCode(-1).name(), None);
+        assert_eq!(StatusCode(17).name(), None);
+    }
+}
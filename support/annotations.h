@@ -0,0 +1,184 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_SUPPORT_ANNOTATIONS_H_
+#define CRUBIT_SUPPORT_ANNOTATIONS_H_
+
+#include "support/internal/attribute_macros.h"
+
+// Unsafe: marks a type as safe to move by `memcpy`, as Rust does.
+//
+// By default, only types which are trivially relocatable according to Clang
+// (e.g. because they are trivial for the purpose of calls, or because they use
+// `[[clang::trivial_abi]]`) are bound as ordinary Rust values. All other types
+// are pinned, and can only be constructed through the `Ctor` API.
+//
+// This annotation tells Crubit that the type does not depend on its own
+// address: moving it with `memcpy` and forgetting the source is equivalent to
+// move-constructing it and destroying the source. The type is then bound as an
+// `Unpin` Rust type, and can be passed, returned and assigned by value.
+//
+// The type must be `final` (or otherwise not inheritable), because tail
+// padding of a base class may be reused by derived classes. The generated
+// bindings also `static_assert` that the type is move-constructible and
+// destructible.
+//
+// For example, this C++ header:
+//
+// ```c++
+// struct CRUBIT_RUST_MOVABLE Buffer final {
+//   Buffer(Buffer&&);
+//   ~Buffer();
+//   char* data;
+// };
+// Buffer MakeBuffer();
+// ```
+//
+// Becomes this Rust interface:
+//
+// ```rust
+// pub struct Buffer { pub data: *mut c_char }
+// pub fn MakeBuffer() -> Buffer;
+// ```
+//
+// SAFETY:
+//   If the type stores or hands out pointers to itself (or to its subobjects),
+//   the behavior is undefined.
+#define CRUBIT_RUST_MOVABLE CRUBIT_INTERNAL_ANNOTATE("crubit_rust_movable")
+
+// Unsafe: implements `Send` for the Rust binding of a type.
+//
+// Without this annotation, the Rust binding is `Send` only if all of its fields
+// are. Types that hold raw pointers are therefore never `Send`, even if they
+// are in fact safe to transfer across threads, and users would otherwise need
+// to wrap them in a newtype with an `unsafe impl Send`.
+//
+// SAFETY:
+//   If the type can't be safely transferred to another thread (e.g. because it
+//   uses thread-local state), the behavior is undefined.
+#define CRUBIT_SEND CRUBIT_INTERNAL_ANNOTATE("crubit_send")
+
+// Unsafe: implements `Sync` for the Rust binding of a type.
+//
+// Without this annotation, the Rust binding is `Sync` only if all of its fields
+// are.
+//
+// SAFETY:
+//   If the type can't be safely accessed through `const` references from
+//   several threads at once, the behavior is undefined.
+#define CRUBIT_SYNC CRUBIT_INTERNAL_ANNOTATE("crubit_sync")
+
+// Prevents the Rust binding of a type from implementing `Send`, even if all of
+// its fields are `Send`.
+//
+// This is useful for types which own thread-affine resources through fields
+// that Crubit can't see (e.g. private fields, which are bound as opaque
+// bytes).
+#define CRUBIT_NOT_SEND CRUBIT_INTERNAL_ANNOTATE("crubit_not_send")
+
+// Prevents the Rust binding of a type from implementing `Sync`, even if all of
+// its fields are `Sync`.
+#define CRUBIT_NOT_SYNC CRUBIT_INTERNAL_ANNOTATE("crubit_not_sync")
+
+// Excludes a function, method, field, type or namespace from the bindings.
+//
+// Annotated declarations (and everything nested in them) are skipped as if
+// they were private. Annotated fields are replaced with opaque padding of the
+// same size, so the layout of the enclosing record is unaffected.
+//
+// This is useful for internal APIs that must not be reachable from Rust.
+#define CRUBIT_DO_NOT_BIND CRUBIT_INTERNAL_ANNOTATE("crubit_do_not_bind")
+
+// Exempts a function or method from lifetime elision.
+//
+// In headers that specify `#pragma clang lifetime_elision`, or in all headers
+// of a target whose bindings are generated with `--lifetime_elision`, the
+// unannotated pointers and references of a function are bound as Rust
+// references whose lifetimes follow Rust's lifetime elision rules. This is
+// wrong for functions that e.g. return a pointer to a global, or retain a
+// pointer to a parameter: with this annotation, their unannotated pointers and
+// references are bound as raw pointers instead, and they are `unsafe` to call.
+//
+// For example, this C++ header:
+//
+// ```c++
+// #pragma clang lifetime_elision
+// int& Identity(int& x);
+// CRUBIT_NO_LIFETIME_ELISION void Register(int& counter);
+// ```
+//
+// Becomes this Rust interface:
+//
+// ```rust
+// pub fn Identity<'a>(x: &'a mut c_int) -> &'a mut c_int;
+// pub unsafe fn Register(counter: *mut c_int);
+// ```
+#define CRUBIT_NO_LIFETIME_ELISION \
+  CRUBIT_INTERNAL_ANNOTATE("crubit_no_lifetime_elision")
+
+// Binds a function or method as the Rust function `name`.
+//
+// Crubit names the Rust bindings of overloaded C++ functions after their
+// parameter types (e.g. `Log_int_const_ptr` for `Log(const int*)`). This
+// annotation overrides that, and the names of non-overloaded functions too.
+//
+// For example, this C++ header:
+//
+// ```c++
+// void Log(int value);
+// CRUBIT_RUST_NAME("log_str") void Log(const char* value);
+// ```
+//
+// Becomes this Rust interface:
+//
+// ```rust
+// pub fn Log(value: c_int);
+// pub unsafe fn log_str(value: *const c_char);
+// ```
+#define CRUBIT_RUST_NAME(name) \
+  CRUBIT_INTERNAL_ANNOTATE("crubit_rust_name", name)
+
+// Allows Rust types to implement the virtual member functions of a class.
+//
+// For an annotated class `C`, Crubit generates a Rust trait `COverrides` with
+// one method per public virtual member function of `C` (and per pure virtual
+// member function that `C` inherits without overriding it), and a function
+// `C::new_rust_subclass` that wraps a Rust implementation of that trait in a
+// C++ object deriving from `C`. Calls through the vtable of that object are
+// forwarded to the Rust implementation. This is useful to implement C++
+// callback or listener interfaces in Rust.
+//
+// The class must have a public virtual destructor and a public default
+// constructor, and must not have non-public pure virtual member functions.
+// Parameter and return types of the virtual member functions must be passable
+// by value through the C ABI (e.g. primitive types and pointers).
+//
+// For example, this C++ header:
+//
+// ```c++
+// class CRUBIT_EXTENSIBLE Listener {
+//  public:
+//   virtual ~Listener();
+//   virtual void OnEvent(int event) = 0;
+// };
+// ```
+//
+// Becomes this Rust interface:
+//
+// ```rust
+// pub trait ListenerOverrides {
+//     fn OnEvent(&mut self, event: c_int);
+// }
+// impl Listener {
+//     pub fn new_rust_subclass<T: ListenerOverrides + Send + 'static>(imp: T)
+//         -> ListenerRustSubclass;
+// }
+// ```
+//
+// `ListenerRustSubclass` owns the C++ object, and dropping it (or destroying
+// the object released by its `into_raw()` method, e.g. with `delete`) drops
+// the Rust implementation.
+#define CRUBIT_EXTENSIBLE CRUBIT_INTERNAL_ANNOTATE("crubit_extensible")
+
+#endif  // CRUBIT_SUPPORT_ANNOTATIONS_H_
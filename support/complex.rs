@@ -0,0 +1,69 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C and C++ complex number types.
+//!
+//! `rs_bindings_from_cc` binds `_Complex float`, `_Complex double`,
+//! `std::complex<float>` and `std::complex<double>` as `Complex<f32>` and
+//! `Complex<f64>`. All of these are laid out like an array of two elements:
+//! the real part followed by the imaginary part.
+
+use core::fmt;
+
+/// A complex number with real part `re` and imaginary part `im`.
+#[repr(C)]
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Complex").field(&self.re).field(&self.im).finish()
+    }
+}
+
+impl<T> From<[T; 2]> for Complex<T> {
+    fn from([re, im]: [T; 2]) -> Self {
+        Complex { re, im }
+    }
+}
+
+impl<T> From<Complex<T>> for [T; 2] {
+    fn from(c: Complex<T>) -> Self {
+        [c.re, c.im]
+    }
+}
+
+impl<T> From<(T, T)> for Complex<T> {
+    fn from((re, im): (T, T)) -> Self {
+        Complex { re, im }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let c = Complex::from([1.0, 2.0]);
+        assert_eq!(c, Complex::new(1.0, 2.0));
+        assert_eq!(<[f64; 2]>::from(c), [1.0, 2.0]);
+        assert_eq!(Complex::from((3.0f32, 4.0f32)).im, 4.0);
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(core::mem::size_of::<Complex<f32>>(), core::mem::size_of::<[f32; 2]>());
+        assert_eq!(core::mem::align_of::<Complex<f64>>(), core::mem::align_of::<[f64; 2]>());
+    }
+}
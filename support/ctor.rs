@@ -369,6 +369,25 @@ macro_rules! mov {
     };
 }
 
+/// Creates an `RvalueReference<T>` for an owned value `value: T`.
+///
+/// `mov!` only accepts pointers (e.g. `Pin<Box<T>>` or `&mut T`). `move_ref!`
+/// instead takes ownership of `value` and pins it in a temporary, so that it
+/// can be passed directly to a function taking a C++ `T&&`:
+///
+/// ```
+/// TakesRvalueReference(move_ref!(value));
+/// ```
+///
+/// As with `mov!`, the resulting `RvalueReference` has the lifetime of a
+/// temporary, after which the value is destroyed.
+#[macro_export]
+macro_rules! move_ref {
+    ($value:expr) => {
+        $crate::RvalueReference(::core::pin::pin!($value))
+    };
+}
+
 #[macro_export]
 macro_rules! const_mov {
     ($p:expr) => {
@@ -1693,6 +1712,27 @@ mod test {
         takes_rvalue_reference(mov!(pinned_mut_ref));
     }
 
+    #[test]
+    fn test_move_ref_owned_value() {
+        struct S;
+        let x = S;
+        takes_rvalue_reference(move_ref!(x));
+        // let _x = x; // fails to compile: x is moved!
+    }
+
+    #[test]
+    fn test_move_ref_deref() {
+        assert_eq!(*move_ref!(42), 42);
+    }
+
+    #[test]
+    fn test_move_ref_drops_temporary() {
+        let log = RefCell::new(vec![]);
+        let log = &log;
+        takes_rvalue_reference(move_ref!(DropCtorLogger { log }));
+        assert_eq!(*log.borrow(), vec!["drop"]);
+    }
+
     #[test]
     fn test_ctor_then() {
         emplace! {
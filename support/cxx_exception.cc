@@ -0,0 +1,35 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `ExceptionPtr` and `CxxException` (see `cxx_exception.rs`).
+
+#include <exception>
+#include <memory>
+#include <new>
+
+static_assert(sizeof(std::exception_ptr) == sizeof(void*));
+static_assert(alignof(std::exception_ptr) == alignof(void*));
+
+extern "C" void crubit_exception_ptr_copy(std::exception_ptr* dest,
+                                          const std::exception_ptr* src) {
+  new (dest) std::exception_ptr(*src);
+}
+
+extern "C" void crubit_exception_ptr_destroy(std::exception_ptr* exception) {
+  std::destroy_at(exception);
+}
+
+// Returns the `what()` message of the exception, or null if it isn't a
+// `std::exception`. The exception is rethrown in place, so the message lives as
+// long as the `exception_ptr`.
+extern "C" const char* crubit_exception_ptr_what(
+    const std::exception_ptr* exception) {
+  try {
+    std::rethrow_exception(*exception);
+  } catch (const std::exception& e) {
+    return e.what();
+  } catch (...) {
+    return nullptr;
+  }
+}
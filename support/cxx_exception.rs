@@ -0,0 +1,147 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of C++ exceptions.
+//!
+//! With `--exception_policy=catch`, the thunks of functions and methods catch
+//! the C++ exceptions that they throw, and store them into an `ExceptionPtr`.
+//! The bindings then return them as the `CxxException` error of a `Result`.
+
+use core::ffi::{c_char, CStr};
+use core::fmt;
+
+/// A `std::exception_ptr`: either null, or (a reference-counted pointer to) a
+/// C++ exception.
+///
+/// Both libc++ and libstdc++ represent `std::exception_ptr` as a single
+/// pointer, which is null for the null `exception_ptr`.
+#[repr(C)]
+pub struct ExceptionPtr {
+    ptr: *mut core::ffi::c_void,
+}
+
+mod detail {
+    use super::*;
+    extern "C" {
+        pub(crate) fn crubit_exception_ptr_copy(dest: *mut ExceptionPtr, src: *const ExceptionPtr);
+        pub(crate) fn crubit_exception_ptr_destroy(exception: *mut ExceptionPtr);
+        pub(crate) fn crubit_exception_ptr_what(exception: *const ExceptionPtr) -> *const c_char;
+    }
+}
+
+impl ExceptionPtr {
+    /// Returns the null `exception_ptr`, which doesn't point to an exception.
+    pub const fn null() -> Self {
+        ExceptionPtr { ptr: core::ptr::null_mut() }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns `Ok(())` for the null `exception_ptr`, and a `CxxException`
+    /// otherwise.
+    pub fn into_result(self) -> Result<(), CxxException> {
+        if self.is_null() {
+            Ok(())
+        } else {
+            Err(CxxException(self))
+        }
+    }
+}
+
+impl Default for ExceptionPtr {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl Clone for ExceptionPtr {
+    fn clone(&self) -> Self {
+        if self.is_null() {
+            return Self::null();
+        }
+        let mut exception = core::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            detail::crubit_exception_ptr_copy(exception.as_mut_ptr(), self);
+            exception.assume_init()
+        }
+    }
+}
+
+impl Drop for ExceptionPtr {
+    fn drop(&mut self) {
+        if !self.is_null() {
+            unsafe { detail::crubit_exception_ptr_destroy(self) }
+        }
+    }
+}
+
+impl fmt::Debug for ExceptionPtr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_null() {
+            f.write_str("ExceptionPtr(null)")
+        } else {
+            f.debug_tuple("ExceptionPtr").field(&self.ptr).finish()
+        }
+    }
+}
+
+/// A C++ exception that was thrown by a function called from Rust.
+#[derive(Clone)]
+pub struct CxxException(ExceptionPtr);
+
+impl CxxException {
+    /// Returns the `what()` message of the exception if it is a
+    /// `std::exception`, and `None` otherwise.
+    pub fn what(&self) -> Option<&CStr> {
+        // The message is owned by the exception, which is kept alive by `self`.
+        let what = unsafe { detail::crubit_exception_ptr_what(&self.0) };
+        if what.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(what) })
+        }
+    }
+
+    /// Returns the (non-null) `exception_ptr`.
+    pub fn into_exception_ptr(self) -> ExceptionPtr {
+        self.0
+    }
+}
+
+impl fmt::Debug for CxxException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CxxException").field(&self.what()).finish()
+    }
+}
+
+impl fmt::Display for CxxException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.what() {
+            Some(what) => match what.to_str() {
+                Ok(what) => f.write_str(what),
+                Err(_) => what
+                    .to_bytes()
+                    .iter()
+                    .try_for_each(|&b| write!(f, "{}", core::ascii::escape_default(b))),
+            },
+            None => f.write_str("unknown C++ exception"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_null_exception_ptr() {
+        let exception = ExceptionPtr::null();
+        assert!(exception.is_null());
+        assert!(exception.clone().is_null());
+        assert!(exception.into_result().is_ok());
+        assert!(ExceptionPtr::default().is_null());
+    }
+}
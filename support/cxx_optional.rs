@@ -0,0 +1,169 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `std::optional` type.
+//!
+//! `rs_bindings_from_cc` binds `std::optional<T>` as `CxxOptional<T>`, which
+//! has the layout of `std::optional<T>`: the storage for a `T`, followed by a
+//! flag that says whether it holds a value. Functions that take or return a
+//! `std::optional<T>` by value take or return an `Option<T>` instead, which is
+//! converted from or to a `CxxOptional<T>`.
+//!
+//! Rust moves `CxxOptional`s with `memcpy`, so `T` must be a type that Rust
+//! can move (i.e. an `Unpin` type).
+
+use core::fmt;
+use core::mem::{self, MaybeUninit};
+
+/// A C++ `std::optional<T>`: either a `T`, or nothing.
+#[repr(C)]
+pub struct CxxOptional<T> {
+    value: MaybeUninit<T>,
+    has_value: bool,
+}
+
+impl<T> CxxOptional<T> {
+    /// Returns an optional that doesn't hold a value (`std::nullopt`).
+    pub fn none() -> Self {
+        CxxOptional { value: MaybeUninit::uninit(), has_value: false }
+    }
+
+    /// Returns an optional that holds `value`.
+    pub fn some(value: T) -> Self {
+        CxxOptional { value: MaybeUninit::new(value), has_value: true }
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.has_value
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.has_value
+    }
+
+    /// Returns a reference to the value, if there is one.
+    pub fn as_ref(&self) -> Option<&T> {
+        if self.has_value {
+            Some(unsafe { self.value.assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, if there is one.
+    pub fn as_mut(&mut self) -> Option<&mut T> {
+        if self.has_value {
+            Some(unsafe { self.value.assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Takes the value out of the optional, leaving it empty
+    /// (`std::nullopt`).
+    pub fn take(&mut self) -> Option<T> {
+        if mem::replace(&mut self.has_value, false) {
+            Some(unsafe { self.value.assume_init_read() })
+        } else {
+            None
+        }
+    }
+
+    /// Converts the optional into an `Option`.
+    pub fn into_option(mut self) -> Option<T> {
+        self.take()
+    }
+}
+
+impl<T> Default for CxxOptional<T> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T: Clone> Clone for CxxOptional<T> {
+    fn clone(&self) -> Self {
+        self.as_ref().cloned().into()
+    }
+}
+
+impl<T> Drop for CxxOptional<T> {
+    fn drop(&mut self) {
+        drop(self.take())
+    }
+}
+
+impl<T> From<Option<T>> for CxxOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::some(value),
+            None => Self::none(),
+        }
+    }
+}
+
+impl<T> From<CxxOptional<T>> for Option<T> {
+    fn from(value: CxxOptional<T>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T: PartialEq> PartialEq for CxxOptional<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T: Eq> Eq for CxxOptional<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for CxxOptional<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_ref(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_option_round_trip() {
+        let some = CxxOptional::from(Some(42));
+        assert!(some.is_some());
+        assert_eq!(some.as_ref(), Some(&42));
+        assert_eq!(Option::from(some), Some(42));
+
+        let none = CxxOptional::<i32>::from(None);
+        assert!(none.is_none());
+        assert_eq!(none.into_option(), None);
+    }
+
+    #[test]
+    fn test_take() {
+        let mut optional = CxxOptional::some(1.5);
+        *optional.as_mut().unwrap() *= 2.0;
+        assert_eq!(optional.take(), Some(3.0));
+        assert!(optional.is_none());
+        assert_eq!(optional.take(), None);
+    }
+
+    #[test]
+    fn test_value_is_dropped_once() {
+        struct CountDrops<'a>(&'a Cell<i32>);
+        impl Drop for CountDrops<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        drop(CxxOptional::some(CountDrops(&drops)));
+        assert_eq!(drops.get(), 1);
+        let taken = CxxOptional::some(CountDrops(&drops)).into_option();
+        assert_eq!(drops.get(), 1);
+        drop(taken);
+        assert_eq!(drops.get(), 2);
+    }
+}
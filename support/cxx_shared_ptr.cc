@@ -0,0 +1,40 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `SharedPtr` (see `cxx_shared_ptr.rs`).
+
+#include <cstddef>
+#include <cstdint>
+#include <memory>
+#include <new>
+
+// `SharedPtr` is two pointers: to the object, and to the control block.
+static_assert(sizeof(std::shared_ptr<int>) == 2 * sizeof(void*));
+static_assert(alignof(std::shared_ptr<int>) == alignof(void*));
+
+#define CRUBIT_SHARED_PTR_FUNCTIONS(rs_type, cc_type)                      \
+  extern "C" void crubit_shared_ptr_copy_##rs_type(                        \
+      std::shared_ptr<cc_type>* dest, const std::shared_ptr<cc_type>* src) { \
+    new (dest) std::shared_ptr<cc_type>(*src);                             \
+  }                                                                        \
+  extern "C" void crubit_shared_ptr_destroy_##rs_type(                     \
+      std::shared_ptr<cc_type>* ptr) {                                     \
+    std::destroy_at(ptr);                                                  \
+  }
+
+CRUBIT_SHARED_PTR_FUNCTIONS(bool, bool)
+CRUBIT_SHARED_PTR_FUNCTIONS(i8, int8_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(u8, uint8_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(i16, int16_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(u16, uint16_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(i32, int32_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(u32, uint32_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(i64, int64_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(u64, uint64_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(isize, intptr_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(usize, size_t)
+CRUBIT_SHARED_PTR_FUNCTIONS(f32, float)
+CRUBIT_SHARED_PTR_FUNCTIONS(f64, double)
+
+#undef CRUBIT_SHARED_PTR_FUNCTIONS
@@ -0,0 +1,261 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `std::shared_ptr` type.
+//!
+//! `rs_bindings_from_cc` binds `std::shared_ptr<T>` as `SharedPtr<T>`, which
+//! has the layout of `std::shared_ptr<T>`: a pointer to the object, and a
+//! pointer to the control block that holds the reference counts. Cloning a
+//! `SharedPtr` increments the reference count, and dropping it decrements the
+//! reference count (and deletes the object when it reaches zero), by calling
+//! the copy constructor and the destructor of `std::shared_ptr<T>`. It can
+//! therefore only be used with types that implement `SharedPtrTarget`.
+
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ptr;
+
+/// A C++ `std::shared_ptr<T>`: a reference-counted pointer to a C++ object,
+/// or null.
+#[repr(C)]
+pub struct SharedPtr<T: SharedPtrTarget> {
+    ptr: *mut T,
+    control_block: *mut core::ffi::c_void,
+}
+
+// The reference counts in the control block are updated atomically, so that
+// `SharedPtr`s to the same object can be cloned and dropped on different
+// threads. Like `Arc<T>`, this gives all threads access to the object.
+unsafe impl<T: SharedPtrTarget + Send + Sync> Send for SharedPtr<T> {}
+unsafe impl<T: SharedPtrTarget + Send + Sync> Sync for SharedPtr<T> {}
+
+mod detail {
+    use super::*;
+    extern "C" {
+        pub(crate) fn crubit_shared_ptr_copy_bool(
+            d: *mut SharedPtr<bool>,
+            s: *const SharedPtr<bool>,
+        );
+        pub(crate) fn crubit_shared_ptr_copy_i8(d: *mut SharedPtr<i8>, s: *const SharedPtr<i8>);
+        pub(crate) fn crubit_shared_ptr_copy_u8(d: *mut SharedPtr<u8>, s: *const SharedPtr<u8>);
+        pub(crate) fn crubit_shared_ptr_copy_i16(d: *mut SharedPtr<i16>, s: *const SharedPtr<i16>);
+        pub(crate) fn crubit_shared_ptr_copy_u16(d: *mut SharedPtr<u16>, s: *const SharedPtr<u16>);
+        pub(crate) fn crubit_shared_ptr_copy_i32(d: *mut SharedPtr<i32>, s: *const SharedPtr<i32>);
+        pub(crate) fn crubit_shared_ptr_copy_u32(d: *mut SharedPtr<u32>, s: *const SharedPtr<u32>);
+        pub(crate) fn crubit_shared_ptr_copy_i64(d: *mut SharedPtr<i64>, s: *const SharedPtr<i64>);
+        pub(crate) fn crubit_shared_ptr_copy_u64(d: *mut SharedPtr<u64>, s: *const SharedPtr<u64>);
+        pub(crate) fn crubit_shared_ptr_copy_isize(
+            d: *mut SharedPtr<isize>,
+            s: *const SharedPtr<isize>,
+        );
+        pub(crate) fn crubit_shared_ptr_copy_usize(
+            d: *mut SharedPtr<usize>,
+            s: *const SharedPtr<usize>,
+        );
+        pub(crate) fn crubit_shared_ptr_copy_f32(d: *mut SharedPtr<f32>, s: *const SharedPtr<f32>);
+        pub(crate) fn crubit_shared_ptr_copy_f64(d: *mut SharedPtr<f64>, s: *const SharedPtr<f64>);
+
+        pub(crate) fn crubit_shared_ptr_destroy_bool(p: *mut SharedPtr<bool>);
+        pub(crate) fn crubit_shared_ptr_destroy_i8(p: *mut SharedPtr<i8>);
+        pub(crate) fn crubit_shared_ptr_destroy_u8(p: *mut SharedPtr<u8>);
+        pub(crate) fn crubit_shared_ptr_destroy_i16(p: *mut SharedPtr<i16>);
+        pub(crate) fn crubit_shared_ptr_destroy_u16(p: *mut SharedPtr<u16>);
+        pub(crate) fn crubit_shared_ptr_destroy_i32(p: *mut SharedPtr<i32>);
+        pub(crate) fn crubit_shared_ptr_destroy_u32(p: *mut SharedPtr<u32>);
+        pub(crate) fn crubit_shared_ptr_destroy_i64(p: *mut SharedPtr<i64>);
+        pub(crate) fn crubit_shared_ptr_destroy_u64(p: *mut SharedPtr<u64>);
+        pub(crate) fn crubit_shared_ptr_destroy_isize(p: *mut SharedPtr<isize>);
+        pub(crate) fn crubit_shared_ptr_destroy_usize(p: *mut SharedPtr<usize>);
+        pub(crate) fn crubit_shared_ptr_destroy_f32(p: *mut SharedPtr<f32>);
+        pub(crate) fn crubit_shared_ptr_destroy_f64(p: *mut SharedPtr<f64>);
+    }
+}
+
+impl<T: SharedPtrTarget> SharedPtr<T> {
+    /// Returns a null `SharedPtr`, which doesn't have a control block.
+    pub fn null() -> Self {
+        SharedPtr { ptr: ptr::null_mut(), control_block: ptr::null_mut() }
+    }
+
+    /// Returns whether the pointer is null. A null pointer may still share
+    /// ownership of an object, if it was created with the aliasing
+    /// constructor of `std::shared_ptr`.
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns a pointer to the object (`std::shared_ptr::get()`).
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns a reference to the object, or `None` if the pointer is null.
+    ///
+    /// The object is shared, so there is no way to get a mutable reference to
+    /// it.
+    pub fn as_ref(&self) -> Option<&T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns whether both pointers point to the same object.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T: SharedPtrTarget> Default for SharedPtr<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T: SharedPtrTarget> Clone for SharedPtr<T> {
+    /// Returns a pointer to the same object, and increments the reference
+    /// count.
+    fn clone(&self) -> Self {
+        let mut copy = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::copy_shared_ptr(copy.as_mut_ptr(), self);
+            copy.assume_init()
+        }
+    }
+}
+
+impl<T: SharedPtrTarget> Drop for SharedPtr<T> {
+    fn drop(&mut self) {
+        // A null `SharedPtr` without a control block doesn't own anything.
+        if !self.control_block.is_null() {
+            unsafe { T::destroy_shared_ptr(self) }
+        }
+    }
+}
+
+impl<T: SharedPtrTarget> Deref for SharedPtr<T> {
+    type Target = T;
+
+    /// Dereferences the pointer.
+    ///
+    /// Panics if the pointer is null.
+    fn deref(&self) -> &T {
+        self.as_ref().expect("called deref on a null SharedPtr")
+    }
+}
+
+impl<T: SharedPtrTarget + fmt::Debug> fmt::Debug for SharedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_ref() {
+            Some(object) => fmt::Debug::fmt(object, f),
+            None => f.write_str("nullptr"),
+        }
+    }
+}
+
+/// A type that `SharedPtr` can point to, i.e. a type for which the copy
+/// constructor and destructor of `std::shared_ptr<T>` are available.
+///
+/// `rs_bindings_from_cc` implements this trait for the primitive types, and
+/// for the structs of a library that are used as the pointee type of a
+/// `std::shared_ptr` in the API of the library.
+///
+/// # Safety
+///
+/// The functions must call the copy constructor and the destructor of
+/// `std::shared_ptr<Self>`.
+pub unsafe trait SharedPtrTarget: Sized {
+    /// Copy-constructs a `std::shared_ptr<Self>` at `dest` from `*src`.
+    ///
+    /// # Safety
+    ///
+    /// `dest` must be valid for writes, and `src` must be a
+    /// `std::shared_ptr<Self>` created by C++.
+    unsafe fn copy_shared_ptr(dest: *mut SharedPtr<Self>, src: &SharedPtr<Self>);
+
+    /// Calls the destructor of the `std::shared_ptr<Self>` at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a `std::shared_ptr<Self>` created by C++, which isn't
+    /// used afterwards.
+    unsafe fn destroy_shared_ptr(ptr: *mut SharedPtr<Self>);
+}
+
+macro_rules! impl_shared_ptr_target {
+    ($($t:ty => ($copy:ident, $destroy:ident)),* $(,)?) => {$(
+        unsafe impl SharedPtrTarget for $t {
+            unsafe fn copy_shared_ptr(dest: *mut SharedPtr<Self>, src: &SharedPtr<Self>) {
+                detail::$copy(dest, src)
+            }
+            unsafe fn destroy_shared_ptr(ptr: *mut SharedPtr<Self>) {
+                detail::$destroy(ptr)
+            }
+        }
+    )*};
+}
+
+impl_shared_ptr_target! {
+    bool => (crubit_shared_ptr_copy_bool, crubit_shared_ptr_destroy_bool),
+    i8 => (crubit_shared_ptr_copy_i8, crubit_shared_ptr_destroy_i8),
+    u8 => (crubit_shared_ptr_copy_u8, crubit_shared_ptr_destroy_u8),
+    i16 => (crubit_shared_ptr_copy_i16, crubit_shared_ptr_destroy_i16),
+    u16 => (crubit_shared_ptr_copy_u16, crubit_shared_ptr_destroy_u16),
+    i32 => (crubit_shared_ptr_copy_i32, crubit_shared_ptr_destroy_i32),
+    u32 => (crubit_shared_ptr_copy_u32, crubit_shared_ptr_destroy_u32),
+    i64 => (crubit_shared_ptr_copy_i64, crubit_shared_ptr_destroy_i64),
+    u64 => (crubit_shared_ptr_copy_u64, crubit_shared_ptr_destroy_u64),
+    isize => (crubit_shared_ptr_copy_isize, crubit_shared_ptr_destroy_isize),
+    usize => (crubit_shared_ptr_copy_usize, crubit_shared_ptr_destroy_usize),
+    f32 => (crubit_shared_ptr_copy_f32, crubit_shared_ptr_destroy_f32),
+    f64 => (crubit_shared_ptr_copy_f64, crubit_shared_ptr_destroy_f64),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Stands in for the control block: counts the strong references.
+    struct Counted {
+        count: Cell<i32>,
+    }
+
+    unsafe impl SharedPtrTarget for Counted {
+        unsafe fn copy_shared_ptr(dest: *mut SharedPtr<Self>, src: &SharedPtr<Self>) {
+            (*src.ptr).count.set((*src.ptr).count.get() + 1);
+            dest.write(SharedPtr { ptr: src.ptr, control_block: src.control_block });
+        }
+        unsafe fn destroy_shared_ptr(ptr: *mut SharedPtr<Self>) {
+            let object = &*(*ptr).ptr;
+            object.count.set(object.count.get() - 1);
+        }
+    }
+
+    #[test]
+    fn test_null() {
+        let p = SharedPtr::<Counted>::null();
+        assert!(p.is_null());
+        assert!(p.as_ref().is_none());
+        // Neither cloning nor dropping a null pointer calls into C++.
+        let _ = SharedPtr::<Counted>::default();
+    }
+
+    #[test]
+    fn test_clone_and_drop() {
+        let mut object = Counted { count: Cell::new(1) };
+        let object_ptr: *mut Counted = &mut object;
+        let p = SharedPtr { ptr: object_ptr, control_block: object_ptr.cast() };
+        let q = p.clone();
+        assert!(p.ptr_eq(&q));
+        assert_eq!(q.count.get(), 2);
+        drop(q);
+        assert_eq!(p.count.get(), 1);
+        core::mem::forget(p);
+    }
+
+    #[test]
+    fn test_thread_safety_markers() {
+        fn is_send_and_sync<T: Send + Sync>() {}
+        is_send_and_sync::<SharedPtr<i32>>();
+    }
+}
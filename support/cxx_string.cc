@@ -0,0 +1,42 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `CxxString` and `CxxStringView` (see `cxx_string.rs`).
+
+#include <cstddef>
+#include <memory>
+#include <new>
+#include <string>
+#include <string_view>
+
+// `CxxString` moves `std::string`s with `memcpy`, and reserves the space of
+// three pointers for them. This only matches libc++, so the `cxx_string` type
+// map must only be enabled (with `--support_type_maps`) for targets built
+// against libc++. `CxxStringView` works with any standard library.
+#if defined(_LIBCPP_VERSION)
+static_assert(sizeof(std::string) == 3 * sizeof(void*));
+static_assert(alignof(std::string) == alignof(void*));
+#endif
+
+// `CxxStringView` is a pointer followed by a size.
+static_assert(sizeof(std::string_view) == 2 * sizeof(void*));
+static_assert(alignof(std::string_view) == alignof(void*));
+
+extern "C" {
+
+void crubit_cxx_string_init(std::string* s, const char* data, size_t size) {
+  new (s) std::string(data, size);
+}
+
+void crubit_cxx_string_clone(std::string* s, const std::string* source) {
+  new (s) std::string(*source);
+}
+
+void crubit_cxx_string_destroy(std::string* s) { std::destroy_at(s); }
+
+const char* crubit_cxx_string_data(const std::string* s) { return s->data(); }
+
+size_t crubit_cxx_string_size(const std::string* s) { return s->size(); }
+
+}  // extern "C"
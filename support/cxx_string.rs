@@ -0,0 +1,288 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of the C++ `std::string` and `std::string_view` types.
+//!
+//! `rs_bindings_from_cc` binds `std::string` as `CxxString`, which has the
+//! size and alignment of `std::string`, so that `std::string`s can be passed
+//! to and returned from C++ functions by value or by reference.
+//!
+//! `std::string_view` and `absl::string_view` are bound as `CxxStringView`,
+//! which has the same layout. Functions that take a string view accept any
+//! `&impl AsRef<[u8]>` (e.g. a `&str` or a `&[u8]`) instead.
+//!
+//! The contents of a `CxxString` are only accessed through the C++ functions
+//! in `cxx_string.cc`. Rust moves `CxxString`s with `memcpy`, which is only
+//! correct for standard libraries whose `std::string` is trivially relocatable
+//! (e.g. libc++, but not libstdc++), so the `cxx_string` type map is opt-in:
+//! it is only enabled for targets that ask for it, with the
+//! `support_type_maps_for_crubit_bindings` aspect hint.
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::convert::Infallible;
+use core::ffi::c_char;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::str::{FromStr, Utf8Error};
+
+/// A C++ `std::string`: an owned, growable sequence of bytes, which (unlike
+/// a Rust `String`) isn't necessarily UTF-8.
+#[repr(C)]
+pub struct CxxString {
+    repr: [MaybeUninit<usize>; 3],
+}
+
+mod detail {
+    use super::*;
+    extern "C" {
+        pub(crate) fn crubit_cxx_string_init(s: *mut CxxString, data: *const c_char, size: usize);
+        pub(crate) fn crubit_cxx_string_clone(s: *mut CxxString, source: *const CxxString);
+        pub(crate) fn crubit_cxx_string_destroy(s: *mut CxxString);
+        pub(crate) fn crubit_cxx_string_data(s: *const CxxString) -> *const c_char;
+        pub(crate) fn crubit_cxx_string_size(s: *const CxxString) -> usize;
+    }
+}
+
+impl CxxString {
+    /// Returns an empty string.
+    pub fn new() -> Self {
+        Self::from_bytes(&[])
+    }
+
+    /// Returns a string that holds a copy of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut s = MaybeUninit::<Self>::uninit();
+        unsafe {
+            detail::crubit_cxx_string_init(s.as_mut_ptr(), bytes.as_ptr().cast(), bytes.len());
+            s.assume_init()
+        }
+    }
+
+    /// Returns the number of bytes in the string (`std::string::size()`).
+    pub fn len(&self) -> usize {
+        unsafe { detail::crubit_cxx_string_size(self) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes of the string, without the trailing NUL.
+    pub fn as_bytes(&self) -> &[u8] {
+        // `std::string::data()` is never null, even for empty strings.
+        unsafe {
+            core::slice::from_raw_parts(detail::crubit_cxx_string_data(self).cast(), self.len())
+        }
+    }
+
+    /// Returns the string as a `&str`, failing if it isn't UTF-8.
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(self.as_bytes())
+    }
+
+    /// Returns the string as a `&str` if it is UTF-8, or otherwise a copy of
+    /// the string in which invalid UTF-8 sequences are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+}
+
+impl Default for CxxString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for CxxString {
+    fn clone(&self) -> Self {
+        let mut s = MaybeUninit::<Self>::uninit();
+        unsafe {
+            detail::crubit_cxx_string_clone(s.as_mut_ptr(), self);
+            s.assume_init()
+        }
+    }
+}
+
+impl Drop for CxxString {
+    fn drop(&mut self) {
+        unsafe { detail::crubit_cxx_string_destroy(self) }
+    }
+}
+
+impl FromStr for CxxString {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl From<&str> for CxxString {
+    fn from(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl From<&[u8]> for CxxString {
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl PartialEq for CxxString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for CxxString {}
+
+impl PartialEq<str> for CxxString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for CxxString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl fmt::Debug for CxxString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.to_string_lossy(), f)
+    }
+}
+
+impl fmt::Display for CxxString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.to_string_lossy(), f)
+    }
+}
+
+/// A C++ `std::string_view`: a pointer to `len()` bytes, which aren't
+/// necessarily UTF-8.
+///
+/// The view doesn't carry a lifetime, so it is as unsafe to read as a raw
+/// pointer.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CxxStringView {
+    data: *const c_char,
+    size: usize,
+}
+
+impl CxxStringView {
+    /// Returns a view of the `size` bytes starting at `data`.
+    pub fn from_raw_parts(data: *const c_char, size: usize) -> Self {
+        CxxStringView { data, size }
+    }
+
+    pub fn as_ptr(&self) -> *const c_char {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the bytes of the view.
+    ///
+    /// # Safety
+    ///
+    /// The view must point to `len()` initialized bytes, which must not be
+    /// mutated or destroyed for `'a`.
+    pub unsafe fn as_bytes<'a>(self) -> &'a [u8] {
+        raw_slice::from_raw_parts(self.data.cast(), self.size)
+    }
+}
+
+impl fmt::Debug for CxxStringView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CxxStringView").field("data", &self.data).field("size", &self.size).finish()
+    }
+}
+
+impl From<&[u8]> for CxxStringView {
+    fn from(bytes: &[u8]) -> Self {
+        CxxStringView::from_raw_parts(bytes.as_ptr().cast(), bytes.len())
+    }
+}
+
+impl From<&str> for CxxStringView {
+    fn from(s: &str) -> Self {
+        Self::from(s.as_bytes())
+    }
+}
+
+impl From<&CxxString> for CxxStringView {
+    fn from(s: &CxxString) -> Self {
+        Self::from(s.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for CxxString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let s: CxxString = "hello".parse().unwrap();
+        assert_eq!(s.as_bytes(), b"hello");
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.to_str(), Ok("hello"));
+        assert_eq!(s, "hello");
+    }
+
+    #[test]
+    fn test_empty() {
+        let s = CxxString::default();
+        assert!(s.is_empty());
+        assert_eq!(s.as_bytes(), b"");
+    }
+
+    #[test]
+    fn test_long_string_survives_moves_and_clones() {
+        let long = "a string that is too long for the small string optimization";
+        let s = CxxString::from(long);
+        let moved = [s];
+        let cloned = moved[0].clone();
+        drop(moved);
+        assert_eq!(cloned, long);
+    }
+
+    #[test]
+    fn test_to_string_lossy() {
+        let s = CxxString::from(&b"valid \xff invalid"[..]);
+        assert!(s.to_str().is_err());
+        assert_eq!(s.to_string_lossy(), "valid \u{FFFD} invalid");
+        assert_eq!(CxxString::from("utf-8").to_string_lossy(), Cow::Borrowed("utf-8"));
+    }
+
+    #[test]
+    fn test_string_view() {
+        let s = CxxString::from("hello");
+        let view = CxxStringView::from(&s);
+        assert_eq!(view.len(), 5);
+        assert_eq!(unsafe { view.as_bytes() }, b"hello");
+        assert!(unsafe { CxxStringView::from("").as_bytes() }.is_empty());
+        assert!(
+            unsafe { CxxStringView::from_raw_parts(core::ptr::null(), 0).as_bytes() }.is_empty()
+        );
+    }
+}
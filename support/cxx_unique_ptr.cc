@@ -0,0 +1,34 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `UniquePtr` (see `cxx_unique_ptr.rs`).
+
+#include <cstddef>
+#include <cstdint>
+#include <memory>
+
+// `UniquePtr` is a single pointer: the default deleter takes no space.
+static_assert(sizeof(std::unique_ptr<int>) == sizeof(void*));
+static_assert(alignof(std::unique_ptr<int>) == alignof(void*));
+
+#define CRUBIT_UNIQUE_PTR_DELETE(rs_type, cc_type)                    \
+  extern "C" void crubit_unique_ptr_delete_##rs_type(cc_type* ptr) { \
+    delete ptr;                                                       \
+  }
+
+CRUBIT_UNIQUE_PTR_DELETE(bool, bool)
+CRUBIT_UNIQUE_PTR_DELETE(i8, int8_t)
+CRUBIT_UNIQUE_PTR_DELETE(u8, uint8_t)
+CRUBIT_UNIQUE_PTR_DELETE(i16, int16_t)
+CRUBIT_UNIQUE_PTR_DELETE(u16, uint16_t)
+CRUBIT_UNIQUE_PTR_DELETE(i32, int32_t)
+CRUBIT_UNIQUE_PTR_DELETE(u32, uint32_t)
+CRUBIT_UNIQUE_PTR_DELETE(i64, int64_t)
+CRUBIT_UNIQUE_PTR_DELETE(u64, uint64_t)
+CRUBIT_UNIQUE_PTR_DELETE(isize, intptr_t)
+CRUBIT_UNIQUE_PTR_DELETE(usize, size_t)
+CRUBIT_UNIQUE_PTR_DELETE(f32, float)
+CRUBIT_UNIQUE_PTR_DELETE(f64, double)
+
+#undef CRUBIT_UNIQUE_PTR_DELETE
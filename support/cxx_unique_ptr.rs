@@ -0,0 +1,222 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `std::unique_ptr` type.
+//!
+//! `rs_bindings_from_cc` binds `std::unique_ptr<T>` (with the default deleter)
+//! as `UniquePtr<T>`, which has the layout of `std::unique_ptr<T>`: a single
+//! pointer to an object that was allocated with `new`. `UniquePtr` owns the
+//! object, and deletes it on drop, by calling into C++. It can therefore only
+//! be used with types that implement `UniquePtrTarget`.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr;
+
+/// A C++ `std::unique_ptr<T>`: an owning pointer to a C++ object, or null.
+#[repr(C)]
+pub struct UniquePtr<T: UniquePtrTarget> {
+    ptr: *mut T,
+}
+
+mod detail {
+    extern "C" {
+        pub(crate) fn crubit_unique_ptr_delete_bool(ptr: *mut bool);
+        pub(crate) fn crubit_unique_ptr_delete_i8(ptr: *mut i8);
+        pub(crate) fn crubit_unique_ptr_delete_u8(ptr: *mut u8);
+        pub(crate) fn crubit_unique_ptr_delete_i16(ptr: *mut i16);
+        pub(crate) fn crubit_unique_ptr_delete_u16(ptr: *mut u16);
+        pub(crate) fn crubit_unique_ptr_delete_i32(ptr: *mut i32);
+        pub(crate) fn crubit_unique_ptr_delete_u32(ptr: *mut u32);
+        pub(crate) fn crubit_unique_ptr_delete_i64(ptr: *mut i64);
+        pub(crate) fn crubit_unique_ptr_delete_u64(ptr: *mut u64);
+        pub(crate) fn crubit_unique_ptr_delete_isize(ptr: *mut isize);
+        pub(crate) fn crubit_unique_ptr_delete_usize(ptr: *mut usize);
+        pub(crate) fn crubit_unique_ptr_delete_f32(ptr: *mut f32);
+        pub(crate) fn crubit_unique_ptr_delete_f64(ptr: *mut f64);
+    }
+}
+
+impl<T: UniquePtrTarget> UniquePtr<T> {
+    /// Returns a null `UniquePtr`.
+    pub fn null() -> Self {
+        UniquePtr { ptr: ptr::null_mut() }
+    }
+
+    /// Takes ownership of `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null, or point to an object allocated with C++ `new`
+    /// that isn't owned by anything else.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        UniquePtr { ptr }
+    }
+
+    /// Releases ownership of the object, and returns a pointer to it
+    /// (`std::unique_ptr::release()`).
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.ptr;
+        core::mem::forget(self);
+        ptr
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns a pointer to the object, without releasing ownership
+    /// (`std::unique_ptr::get()`).
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns a reference to the object, or `None` if the pointer is null.
+    pub fn as_ref(&self) -> Option<&T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns a pinned mutable reference to the object, or `None` if the
+    /// pointer is null.
+    ///
+    /// The object is never moved by `UniquePtr`, so it can be mutated even if
+    /// it is not `Unpin`.
+    pub fn as_mut(&mut self) -> Option<Pin<&mut T>> {
+        unsafe { self.ptr.as_mut().map(|object| Pin::new_unchecked(object)) }
+    }
+}
+
+impl<T: UniquePtrTarget> Default for UniquePtr<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T: UniquePtrTarget> Drop for UniquePtr<T> {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { T::delete(self.ptr) }
+        }
+    }
+}
+
+impl<T: UniquePtrTarget> Deref for UniquePtr<T> {
+    type Target = T;
+
+    /// Dereferences the pointer.
+    ///
+    /// Panics if the pointer is null.
+    fn deref(&self) -> &T {
+        self.as_ref().expect("called deref on a null UniquePtr")
+    }
+}
+
+impl<T: UniquePtrTarget + Unpin> DerefMut for UniquePtr<T> {
+    /// Dereferences the pointer.
+    ///
+    /// Panics if the pointer is null.
+    fn deref_mut(&mut self) -> &mut T {
+        Pin::into_inner(self.as_mut().expect("called deref_mut on a null UniquePtr"))
+    }
+}
+
+impl<T: UniquePtrTarget + fmt::Debug> fmt::Debug for UniquePtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_ref() {
+            Some(object) => fmt::Debug::fmt(object, f),
+            None => f.write_str("nullptr"),
+        }
+    }
+}
+
+/// A type that `UniquePtr` can point to, i.e. a type for which
+/// `std::default_delete<T>` is available.
+///
+/// `rs_bindings_from_cc` implements this trait for the primitive types, and
+/// for the destructible structs of a library that are used as the pointee
+/// type of a `std::unique_ptr` in the API of the library.
+///
+/// # Safety
+///
+/// `delete` must destroy and deallocate `*ptr` with C++ `delete`.
+pub unsafe trait UniquePtrTarget {
+    /// Calls `delete ptr` in C++.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to an object allocated with C++ `new`, which isn't
+    /// used afterwards.
+    unsafe fn delete(ptr: *mut Self);
+}
+
+macro_rules! impl_unique_ptr_target {
+    ($($t:ty => $delete:ident),* $(,)?) => {$(
+        unsafe impl UniquePtrTarget for $t {
+            unsafe fn delete(ptr: *mut Self) {
+                detail::$delete(ptr)
+            }
+        }
+    )*};
+}
+
+impl_unique_ptr_target! {
+    bool => crubit_unique_ptr_delete_bool,
+    i8 => crubit_unique_ptr_delete_i8,
+    u8 => crubit_unique_ptr_delete_u8,
+    i16 => crubit_unique_ptr_delete_i16,
+    u16 => crubit_unique_ptr_delete_u16,
+    i32 => crubit_unique_ptr_delete_i32,
+    u32 => crubit_unique_ptr_delete_u32,
+    i64 => crubit_unique_ptr_delete_i64,
+    u64 => crubit_unique_ptr_delete_u64,
+    isize => crubit_unique_ptr_delete_isize,
+    usize => crubit_unique_ptr_delete_usize,
+    f32 => crubit_unique_ptr_delete_f32,
+    f64 => crubit_unique_ptr_delete_f64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    struct CountDeletes<'a> {
+        deletes: &'a Cell<i32>,
+    }
+
+    unsafe impl UniquePtrTarget for CountDeletes<'_> {
+        unsafe fn delete(ptr: *mut Self) {
+            let deletes = (*ptr).deletes;
+            deletes.set(deletes.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_null() {
+        let p = UniquePtr::<CountDeletes>::null();
+        assert!(p.is_null());
+        assert!(p.as_ref().is_none());
+    }
+
+    #[test]
+    fn test_delete_on_drop() {
+        let deletes = Cell::new(0);
+        let mut object = CountDeletes { deletes: &deletes };
+        let p = unsafe { UniquePtr::from_raw(&mut object) };
+        assert!(!p.is_null());
+        assert!(ptr::eq(p.deletes, &deletes));
+        drop(p);
+        assert_eq!(deletes.get(), 1);
+    }
+
+    #[test]
+    fn test_into_raw() {
+        let deletes = Cell::new(0);
+        let mut object = CountDeletes { deletes: &deletes };
+        let p = unsafe { UniquePtr::from_raw(&mut object) };
+        assert_eq!(p.into_raw(), &mut object as *mut _);
+        assert_eq!(deletes.get(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// The C++ side of `CxxVector` (see `cxx_vector.rs`).
+
+#include <cstdint>
+#include <utility>
+#include <vector>
+
+// `CxxVector` is three pointers: to the first element, past the last element,
+// and past the end of the allocated storage.
+static_assert(sizeof(std::vector<int>) == 3 * sizeof(void*));
+static_assert(alignof(std::vector<int>) == alignof(void*));
+
+#define CRUBIT_CXX_VECTOR_PUSH_BACK(rs_type, cc_type)          \
+  extern "C" void crubit_cxx_vector_push_back_##rs_type(       \
+      std::vector<cc_type>* v, cc_type* value) {               \
+    v->push_back(std::move(*value));                           \
+  }
+
+CRUBIT_CXX_VECTOR_PUSH_BACK(i8, int8_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(u8, uint8_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(i16, int16_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(u16, uint16_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(i32, int32_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(u32, uint32_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(i64, int64_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(u64, uint64_t)
+CRUBIT_CXX_VECTOR_PUSH_BACK(f32, float)
+CRUBIT_CXX_VECTOR_PUSH_BACK(f64, double)
+
+#undef CRUBIT_CXX_VECTOR_PUSH_BACK
@@ -0,0 +1,171 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `std::vector` type.
+//!
+//! `rs_bindings_from_cc` binds `std::vector<T>` as `CxxVector<T>`, which has
+//! the layout of `std::vector<T>` (a pointer to the first element, a pointer
+//! past the last element, and a pointer past the end of the allocated
+//! storage). Vectors are always owned by C++: Rust code only accesses them
+//! through references (e.g. `const std::vector<T>&` is bound as
+//! `&CxxVector<T>`) or as fields of bound structs.
+//!
+//! Reading the elements of a vector doesn't call into C++. Growing the vector
+//! does, so `push_back` is only available for element types that implement
+//! `VectorElement`.
+
+use core::fmt;
+use core::mem;
+use core::slice;
+
+/// A C++ `std::vector<T>`: a growable array of `T`s, owned by C++.
+#[repr(C)]
+pub struct CxxVector<T> {
+    begin: *mut T,
+    end: *mut T,
+    end_cap: *mut T,
+}
+
+mod detail {
+    use super::*;
+    extern "C" {
+        pub(crate) fn crubit_cxx_vector_push_back_i8(v: *mut CxxVector<i8>, value: *mut i8);
+        pub(crate) fn crubit_cxx_vector_push_back_u8(v: *mut CxxVector<u8>, value: *mut u8);
+        pub(crate) fn crubit_cxx_vector_push_back_i16(v: *mut CxxVector<i16>, value: *mut i16);
+        pub(crate) fn crubit_cxx_vector_push_back_u16(v: *mut CxxVector<u16>, value: *mut u16);
+        pub(crate) fn crubit_cxx_vector_push_back_i32(v: *mut CxxVector<i32>, value: *mut i32);
+        pub(crate) fn crubit_cxx_vector_push_back_u32(v: *mut CxxVector<u32>, value: *mut u32);
+        pub(crate) fn crubit_cxx_vector_push_back_i64(v: *mut CxxVector<i64>, value: *mut i64);
+        pub(crate) fn crubit_cxx_vector_push_back_u64(v: *mut CxxVector<u64>, value: *mut u64);
+        pub(crate) fn crubit_cxx_vector_push_back_f32(v: *mut CxxVector<f32>, value: *mut f32);
+        pub(crate) fn crubit_cxx_vector_push_back_f64(v: *mut CxxVector<f64>, value: *mut f64);
+    }
+}
+
+impl<T> CxxVector<T> {
+    /// Returns the number of elements in the vector (`std::vector::size()`).
+    pub fn len(&self) -> usize {
+        // C++ types are never zero-sized.
+        (self.end as usize - self.begin as usize) / mem::size_of::<T>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.begin == self.end
+    }
+
+    /// Returns the elements of the vector as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { raw_slice::from_raw_parts(self.begin, self.len()) }
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    /// Returns an iterator over the elements of the vector.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Appends `value` to the end of the vector, by calling
+    /// `std::vector::push_back` with an rvalue reference to `value`.
+    ///
+    /// `value` is dropped afterwards, so that the destructor of the
+    /// moved-from C++ object runs.
+    pub fn push_back(&mut self, mut value: T)
+    where
+        T: VectorElement,
+    {
+        unsafe { T::push_back(self, &mut value) }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CxxVector<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CxxVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An element type of `CxxVector`s that can grow, i.e. a type for which
+/// `std::vector<T>::push_back(T&&)` is available.
+///
+/// `rs_bindings_from_cc` implements this trait for the primitive types, and
+/// for the movable structs of a library that are used as the element type of
+/// a vector in the API of the library.
+///
+/// # Safety
+///
+/// `push_back` must call `std::vector<Self>::push_back` on `v`, with an rvalue
+/// reference to `*value`.
+pub unsafe trait VectorElement: Sized {
+    /// Calls `v.push_back(std::move(*value))` in C++.
+    ///
+    /// # Safety
+    ///
+    /// `v` must be a `std::vector<Self>` owned by C++.
+    unsafe fn push_back(v: &mut CxxVector<Self>, value: &mut Self);
+}
+
+macro_rules! impl_vector_element {
+    ($($t:ty => $push_back:ident),* $(,)?) => {$(
+        unsafe impl VectorElement for $t {
+            unsafe fn push_back(v: &mut CxxVector<Self>, value: &mut Self) {
+                detail::$push_back(v, value)
+            }
+        }
+    )*};
+}
+
+impl_vector_element! {
+    i8 => crubit_cxx_vector_push_back_i8,
+    u8 => crubit_cxx_vector_push_back_u8,
+    i16 => crubit_cxx_vector_push_back_i16,
+    u16 => crubit_cxx_vector_push_back_u16,
+    i32 => crubit_cxx_vector_push_back_i32,
+    u32 => crubit_cxx_vector_push_back_u32,
+    i64 => crubit_cxx_vector_push_back_i64,
+    u64 => crubit_cxx_vector_push_back_u64,
+    f32 => crubit_cxx_vector_push_back_f32,
+    f64 => crubit_cxx_vector_push_back_f64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty_vector() {
+        // An empty `std::vector` doesn't allocate.
+        let v = CxxVector::<i32> {
+            begin: core::ptr::null_mut(),
+            end: core::ptr::null_mut(),
+            end_cap: core::ptr::null_mut(),
+        };
+        assert_eq!(v.len(), 0);
+        assert!(v.is_empty());
+        assert_eq!(v.as_slice(), &[] as &[i32]);
+        assert_eq!(v.get(0), None);
+        assert_eq!(v.iter().next(), None);
+    }
+
+    #[test]
+    fn test_element_access() {
+        let mut elements = [1u64, 2, 3];
+        let range = elements.as_mut_ptr_range();
+        let v = CxxVector { begin: range.start, end: range.end, end_cap: range.end };
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.get(1), Some(&2));
+        assert_eq!(v.get(3), None);
+        assert_eq!((&v).into_iter().sum::<u64>(), 6);
+    }
+}
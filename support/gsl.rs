@@ -0,0 +1,176 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of the vocabulary types of the C++ Core Guidelines
+//! Support Library (GSL).
+//!
+//! `rs_bindings_from_cc` maps the GSL types as follows:
+//!
+//! - `gsl::span<const T>` (with dynamic extent) is bound as `Span<T>`.
+//! - `gsl::span<T>` (with dynamic extent) is bound as `SpanMut<T>`.
+//! - `gsl::owner<T*>`, `gsl::zstring` and `gsl::czstring` are aliases of
+//!   pointer types, and are bound as raw pointers.
+//!
+//! Spans carry no lifetime, so they are as unsafe to pass to C++ as raw
+//! pointers, and functions taking them are bound as `unsafe fn`.
+
+use core::fmt;
+
+/// A `gsl::span<const T>`: a pointer to `len()` contiguous `T`s.
+///
+/// The fields are in the order of the layout of `gsl::span` with dynamic
+/// extent, which stores the size (in a base class) before the pointer.
+#[repr(C)]
+pub struct Span<T> {
+    size: usize,
+    data: *const T,
+}
+
+impl<T> Span<T> {
+    /// Returns a span of `size` `T`s starting at `data`.
+    pub fn from_raw_parts(data: *const T, size: usize) -> Self {
+        Span { size, data }
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the elements of the span as a slice.
+    ///
+    /// # Safety
+    ///
+    /// The span must point to `len()` initialized `T`s, which must not be
+    /// mutated or destroyed for `'a`.
+    pub unsafe fn as_slice<'a>(self) -> &'a [T] {
+        raw_slice::from_raw_parts(self.data, self.size)
+    }
+}
+
+impl<T> Clone for Span<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Span<T> {}
+
+impl<T> fmt::Debug for Span<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Span").field("data", &self.data).field("size", &self.size).finish()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for Span<T> {
+    fn from(s: &'a [T]) -> Self {
+        Span::from_raw_parts(s.as_ptr(), s.len())
+    }
+}
+
+impl<T> From<SpanMut<T>> for Span<T> {
+    fn from(s: SpanMut<T>) -> Self {
+        Span::from_raw_parts(s.as_mut_ptr(), s.len())
+    }
+}
+
+/// A `gsl::span<T>`: a pointer to `len()` contiguous, mutable `T`s.
+///
+/// See `Span` for the layout.
+#[repr(C)]
+pub struct SpanMut<T> {
+    size: usize,
+    data: *mut T,
+}
+
+impl<T> SpanMut<T> {
+    /// Returns a span of `size` `T`s starting at `data`.
+    pub fn from_raw_parts(data: *mut T, size: usize) -> Self {
+        SpanMut { size, data }
+    }
+
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the elements of the span as a mutable slice.
+    ///
+    /// # Safety
+    ///
+    /// The span must point to `len()` initialized `T`s, which must not be
+    /// accessed other than through the returned slice or destroyed for `'a`.
+    pub unsafe fn as_mut_slice<'a>(self) -> &'a mut [T] {
+        raw_slice::from_raw_parts_mut(self.data, self.size)
+    }
+}
+
+impl<T> Clone for SpanMut<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SpanMut<T> {}
+
+impl<T> fmt::Debug for SpanMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpanMut").field("data", &self.data).field("size", &self.size).finish()
+    }
+}
+
+impl<'a, T> From<&'a mut [T]> for SpanMut<T> {
+    fn from(s: &'a mut [T]) -> Self {
+        SpanMut::from_raw_parts(s.as_mut_ptr(), s.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_span_round_trip() {
+        let array = [1, 2, 3];
+        let span = Span::from(&array[..]);
+        assert_eq!(span.len(), 3);
+        assert_eq!(unsafe { span.as_slice() }, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_span_mut_round_trip() {
+        let mut array = [1, 2, 3];
+        let span = SpanMut::from(&mut array[..]);
+        let slice = unsafe { span.as_mut_slice() };
+        slice[1] = 4;
+        assert_eq!(unsafe { Span::from(span).as_slice() }, &[1, 4, 3]);
+    }
+
+    #[test]
+    fn test_null_span() {
+        let span = Span::<i32>::from_raw_parts(core::ptr::null(), 0);
+        assert!(span.is_empty());
+        assert_eq!(unsafe { span.as_slice() }, &[]);
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(core::mem::size_of::<Span<i32>>(), 2 * core::mem::size_of::<usize>());
+        assert_eq!(core::mem::size_of::<SpanMut<i32>>(), 2 * core::mem::size_of::<usize>());
+    }
+}
@@ -0,0 +1,208 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of the C++ half-precision floating point types.
+//!
+//! `rs_bindings_from_cc` maps the C++ types as follows:
+//!
+//! - `_Float16` and `__fp16` (IEEE 754 binary16) are bound as `F16`.
+//! - `__bf16` (bfloat16) is bound as `Bf16`.
+//!
+//! Until `f16` is stable in Rust, these are newtypes of the bits of the value,
+//! with conversions from and to `f32`. Arithmetic has to be done on `f32`s.
+
+use core::cmp::Ordering;
+use core::fmt;
+
+/// An IEEE 754 binary16 value (`_Float16` or `__fp16`).
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct F16(u16);
+
+impl F16 {
+    pub const fn from_bits(bits: u16) -> Self {
+        F16(bits)
+    }
+
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the `F16` closest to `value` (rounding to nearest, ties to
+    /// even).
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exponent = ((bits >> 23) & 0xff) as i32;
+        let mantissa = bits & 0x7f_ffff;
+        if exponent == 0xff {
+            // Infinity or NaN. NaNs stay (quiet) NaNs.
+            let nan_bits = if mantissa != 0 { 0x200 | (mantissa >> 13) as u16 } else { 0 };
+            return F16(sign | 0x7c00 | nan_bits);
+        }
+        let half_exponent = exponent - 127 + 15;
+        if half_exponent >= 0x1f {
+            // Too large: rounds to infinity.
+            return F16(sign | 0x7c00);
+        }
+        if half_exponent <= 0 {
+            // Subnormal (or zero) as an `F16`.
+            if half_exponent < -10 {
+                return F16(sign);
+            }
+            let mantissa = mantissa | 0x80_0000;
+            let shift = (14 - half_exponent) as u32;
+            let half_mantissa = round_shift_right(mantissa, shift);
+            return F16(sign | half_mantissa as u16);
+        }
+        // Rounding up may carry into the exponent (and up to infinity), which is
+        // the correct result.
+        let half_bits = ((half_exponent as u32) << 10) + round_shift_right(mantissa, 13);
+        F16(sign | half_bits as u16)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exponent = ((self.0 >> 10) & 0x1f) as u32;
+        let mantissa = (self.0 & 0x3ff) as u32;
+        let bits = match exponent {
+            0 => {
+                // Zero or subnormal: `mantissa * 2^-24`, which is exact in `f32`.
+                let magnitude = mantissa as f32 * (1.0 / 16_777_216.0);
+                return if sign != 0 { -magnitude } else { magnitude };
+            }
+            0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+            _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+        };
+        f32::from_bits(bits)
+    }
+}
+
+/// A bfloat16 value (`__bf16`), i.e. an `f32` with a truncated mantissa.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct Bf16(u16);
+
+impl Bf16 {
+    pub const fn from_bits(bits: u16) -> Self {
+        Bf16(bits)
+    }
+
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the `Bf16` closest to `value` (rounding to nearest, ties to
+    /// even).
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        if value.is_nan() {
+            // Keep NaNs (quiet) NaNs, even if their payload is truncated.
+            return Bf16((bits >> 16) as u16 | 0x40);
+        }
+        Bf16(round_shift_right(bits, 16) as u16)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits((self.0 as u32) << 16)
+    }
+}
+
+/// Returns `value >> shift`, rounded to nearest, ties to even.
+fn round_shift_right(value: u32, shift: u32) -> u32 {
+    let truncated = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    if remainder > halfway || (remainder == halfway && truncated & 1 == 1) {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+macro_rules! impl_float_traits {
+    ($t:ident) => {
+        impl From<$t> for f32 {
+            fn from(value: $t) -> f32 {
+                value.to_f32()
+            }
+        }
+
+        impl From<$t> for f64 {
+            fn from(value: $t) -> f64 {
+                value.to_f32().into()
+            }
+        }
+
+        // Comparisons follow the floating point semantics (e.g. `-0.0 == 0.0`,
+        // and NaNs are unordered), not the bitwise representation.
+        impl PartialEq for $t {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+    };
+}
+
+impl_float_traits!(F16);
+impl_float_traits!(Bf16);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_f16_round_trip() {
+        for value in [0.0, -0.0, 1.0, -2.5, 65504.0, 6.1035156e-5, 5.9604645e-8] {
+            assert_eq!(F16::from_f32(value).to_f32().to_bits(), f32::to_bits(value));
+        }
+        assert_eq!(F16::from_f32(1.0).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(f32::INFINITY).to_bits(), 0x7c00);
+        assert!(F16::from_f32(f32::NAN).to_f32().is_nan());
+    }
+
+    #[test]
+    fn test_f16_rounding() {
+        // 65520 is halfway between the largest `F16` and the next power of two.
+        assert_eq!(F16::from_f32(65520.0).to_f32(), f32::INFINITY);
+        // 1 + 2^-11 is halfway between 1 and the next `F16`; ties go to even.
+        assert_eq!(F16::from_f32(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(1.0 + 3.0 / 2048.0).to_bits(), 0x3c02);
+        // Values below half of the smallest subnormal round to zero.
+        assert_eq!(F16::from_f32(2.0e-8).to_bits(), 0);
+    }
+
+    #[test]
+    fn test_bf16() {
+        assert_eq!(Bf16::from_f32(1.0).to_bits(), 0x3f80);
+        assert_eq!(Bf16::from_f32(-3.0).to_f32(), -3.0);
+        assert_eq!(Bf16::from_f32(f32::MAX).to_f32(), f32::INFINITY);
+        assert!(Bf16::from_f32(f32::NAN).to_f32().is_nan());
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert_eq!(F16::from_f32(0.0), F16::from_f32(-0.0));
+        assert!(F16::from_f32(1.0) < F16::from_f32(2.0));
+        assert!(Bf16::from_f32(f32::NAN) != Bf16::from_f32(f32::NAN));
+    }
+}
@@ -0,0 +1,71 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_SUPPORT_MEMBER_FUNCTION_POINTER_H_
+#define CRUBIT_SUPPORT_MEMBER_FUNCTION_POINTER_H_
+
+#include <cstddef>
+#include <tuple>
+#include <type_traits>
+#include <utility>
+
+namespace crubit {
+namespace detail {
+
+// Decomposes the type `F` of a pointer to a member function. Only the member
+// functions that the generated thunks can call (i.e. not `volatile` or
+// `&&`-qualified ones) are supported.
+template <typename F>
+struct MemberFunctionTraits;
+
+#define CRUBIT_MEMBER_FUNCTION_TRAITS(qualifiers, object_qualifiers)           \
+  template <typename R, typename C, typename... Params, bool kNoexcept>        \
+  struct MemberFunctionTraits<R (C::*)(Params...)                              \
+                                  qualifiers noexcept(kNoexcept)> {            \
+    using Object = object_qualifiers C;                                        \
+    using Result = R;                                                          \
+    using ThunkParams = std::tuple<Object*, Params...>;                        \
+  };
+
+CRUBIT_MEMBER_FUNCTION_TRAITS(, )
+CRUBIT_MEMBER_FUNCTION_TRAITS(&, )
+CRUBIT_MEMBER_FUNCTION_TRAITS(const, const)
+CRUBIT_MEMBER_FUNCTION_TRAITS(const&, const)
+
+#undef CRUBIT_MEMBER_FUNCTION_TRAITS
+
+}  // namespace detail
+
+// The return type of the thunk that calls a member function through a pointer
+// of type `F`: the return type of the member function, except that references
+// are returned as pointers (see `-Wreturn-type-c-linkage`).
+template <typename F>
+using MemberFunctionResult = std::conditional_t<
+    std::is_reference_v<typename detail::MemberFunctionTraits<F>::Result>,
+    std::remove_reference_t<typename detail::MemberFunctionTraits<F>::Result>*,
+    typename detail::MemberFunctionTraits<F>::Result>;
+
+// The type of the `I`th parameter of the thunk that calls a member function
+// through a pointer of type `F`: the object pointer (`this`) for `I == 0`,
+// and the `I - 1`th parameter of the member function otherwise.
+template <typename F, size_t I>
+using MemberFunctionParam = std::tuple_element_t<
+    I, typename detail::MemberFunctionTraits<F>::ThunkParams>;
+
+// Calls the member function that `f` points to on `*object`.
+template <typename F, typename... Args>
+MemberFunctionResult<F> CallMemberFunction(
+    F f, typename detail::MemberFunctionTraits<F>::Object* object,
+    Args&&... args) {
+  if constexpr (std::is_reference_v<
+                    typename detail::MemberFunctionTraits<F>::Result>) {
+    return &(object->*f)(std::forward<Args>(args)...);
+  } else {
+    return (object->*f)(std::forward<Args>(args)...);
+  }
+}
+
+}  // namespace crubit
+
+#endif  // CRUBIT_SUPPORT_MEMBER_FUNCTION_POINTER_H_
@@ -0,0 +1,37 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/internal/member_function_pointer.h"
+
+#include <type_traits>
+
+#include "gtest/gtest.h"
+
+namespace crubit {
+namespace {
+
+struct S {
+  int Add(int x) { return value += x; }
+  int Get() const noexcept { return value; }
+  int& Ref() & { return value; }
+
+  int value = 0;
+};
+
+static_assert(std::is_same_v<MemberFunctionParam<decltype(&S::Add), 0>, S*>);
+static_assert(std::is_same_v<MemberFunctionParam<decltype(&S::Add), 1>, int>);
+static_assert(
+    std::is_same_v<MemberFunctionParam<decltype(&S::Get), 0>, const S*>);
+static_assert(std::is_same_v<MemberFunctionResult<decltype(&S::Get)>, int>);
+static_assert(std::is_same_v<MemberFunctionResult<decltype(&S::Ref)>, int*>);
+
+TEST(MemberFunctionPointerTest, CallMemberFunction) {
+  S s;
+  EXPECT_EQ(CallMemberFunction(&S::Add, &s, 2), 2);
+  EXPECT_EQ(CallMemberFunction(&S::Get, &s), 2);
+  EXPECT_EQ(CallMemberFunction(&S::Ref, &s), &s.value);
+}
+
+}  // namespace
+}  // namespace crubit
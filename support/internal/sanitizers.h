@@ -0,0 +1,37 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_SUPPORT_INTERNAL_SANITIZERS_H_
+#define CRUBIT_SUPPORT_INTERNAL_SANITIZERS_H_
+
+#include "absl/base/config.h"
+
+#if defined(ABSL_HAVE_MEMORY_SANITIZER)
+#include <sanitizer/msan_interface.h>
+#endif
+
+namespace crubit {
+
+// Marks the `sizeof(T)` bytes at `p` as initialized for MSan.  This is a no-op
+// when building without MSan.
+//
+// Generated C++ thunks call this on the placement storage that they construct
+// objects into (`__this` of constructors, and `__return` slots), which Rust
+// may have allocated in uninstrumented code (e.g. as a `MaybeUninit<T>`).
+//
+// This must only be called on fresh storage: it must not be used on memory
+// that holds a live object (e.g. `__this` of other methods, or objects passed
+// by value), whose shadow reflects what is actually initialized. ASan is not
+// affected, since uninstrumented code doesn't poison memory for ASan.
+template <typename T>
+void UnpoisonPlacementStorage(const T* p) {
+#if defined(ABSL_HAVE_MEMORY_SANITIZER)
+  __msan_unpoison(p, sizeof(T));
+#endif
+  (void)p;
+}
+
+}  // namespace crubit
+
+#endif  // CRUBIT_SUPPORT_INTERNAL_SANITIZERS_H_
@@ -0,0 +1,50 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/internal/sanitizers.h"
+
+#include <cstdint>
+
+#include "gtest/gtest.h"
+#include "absl/base/config.h"
+
+#if defined(ABSL_HAVE_ADDRESS_SANITIZER)
+#include <sanitizer/asan_interface.h>
+#endif
+
+namespace {
+
+struct SomeStruct {
+  int32_t x;
+  int32_t y;
+};
+
+TEST(SanitizersTest, UnpoisonIsNoOpForValidMemory) {
+  SomeStruct s{1, 2};
+  crubit::UnpoisonPlacementStorage(&s);
+  EXPECT_EQ(s.x, 1);
+  EXPECT_EQ(s.y, 2);
+}
+
+#if defined(ABSL_HAVE_ADDRESS_SANITIZER)
+TEST(SanitizersTest, UnpoisonKeepsAddressSanitizerPoisoning) {
+  SomeStruct s;
+  ASAN_POISON_MEMORY_REGION(&s, sizeof(s));
+  crubit::UnpoisonPlacementStorage(&s);
+  EXPECT_TRUE(__asan_address_is_poisoned(&s));
+  ASAN_UNPOISON_MEMORY_REGION(&s, sizeof(s));
+}
+#endif
+
+#if defined(ABSL_HAVE_MEMORY_SANITIZER)
+TEST(SanitizersTest, UnpoisonMarksStorageAsInitialized) {
+  SomeStruct s;
+  __msan_poison(&s, sizeof(s));
+  ASSERT_NE(__msan_test_shadow(&s, sizeof(s)), -1);
+  crubit::UnpoisonPlacementStorage(&s);
+  EXPECT_EQ(__msan_test_shadow(&s, sizeof(s)), -1);
+}
+#endif
+
+}  // namespace
@@ -0,0 +1,61 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef CRUBIT_SUPPORT_LIFETIME_ANNOTATIONS_H_
+#define CRUBIT_SUPPORT_LIFETIME_ANNOTATIONS_H_
+
+// Lifetime annotations for pointers and references, which Crubit binds as Rust
+// references with the corresponding lifetimes.
+//
+// `$a` through `$z` annotate a pointer or reference type with the lifetime of
+// the same name, `$static` with the `'static` lifetime, and `$(name)` with a
+// lifetime of any other name. For example, this C++ header:
+//
+// ```c++
+// const int& $a Min(const int& $a x, const int& $a y);
+// const int& $static GetDefault();
+// ```
+//
+// Becomes this Rust interface:
+//
+// ```rust
+// pub fn Min<'a>(x: &'a c_int, y: &'a c_int) -> &'a c_int;
+// pub fn GetDefault() -> &'static c_int;
+// ```
+//
+// Functions with a `#pragma clang lifetime_elision` in their header don't need
+// annotations for the lifetimes that follow Rust's lifetime elision rules.
+//
+// The annotations are not checked by the C++ compiler: the generated bindings
+// are only as safe as the annotations are correct.
+#define $(l) [[clang::annotate_type("lifetime", #l)]]
+#define $a $(a)
+#define $b $(b)
+#define $c $(c)
+#define $d $(d)
+#define $e $(e)
+#define $f $(f)
+#define $g $(g)
+#define $h $(h)
+#define $i $(i)
+#define $j $(j)
+#define $k $(k)
+#define $l $(l)
+#define $m $(m)
+#define $n $(n)
+#define $o $(o)
+#define $p $(p)
+#define $q $(q)
+#define $r $(r)
+#define $s $(s)
+#define $t $(t)
+#define $u $(u)
+#define $v $(v)
+#define $w $(w)
+#define $x $(x)
+#define $y $(y)
+#define $z $(z)
+#define $static $(static)
+
+#endif  // CRUBIT_SUPPORT_LIFETIME_ANNOTATIONS_H_
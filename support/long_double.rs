@@ -0,0 +1,95 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `long double` type.
+//!
+//! With `--long_double=opaque`, `rs_bindings_from_cc` binds `long double` as
+//! `LongDouble`. Rust has no floating point type of the same precision, so
+//! `LongDouble` is an opaque blob of bytes with the size and alignment of
+//! `long double` on targets where these are 16 bytes (e.g. x86-64 and AArch64
+//! Linux). Values can be copied and passed back to C++, and their value bytes
+//! can be read, but they can't be used in arithmetic.
+//!
+//! (With `--long_double=f64`, parameters and return values are bound as `f64`
+//! instead, and this crate is not used.)
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// The number of bytes of a `long double` that hold its value. On x86, it is
+/// the 80-bit x87 extended precision format, and the remaining 6 bytes are
+/// padding, which C++ doesn't initialize.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub const VALUE_SIZE: usize = 10;
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub const VALUE_SIZE: usize = 16;
+
+/// The bytes of a C++ `long double`.
+///
+/// Invariant: the first `VALUE_SIZE` bytes are initialized.
+#[repr(C, align(16))]
+#[derive(Clone, Copy)]
+pub struct LongDouble([MaybeUninit<u8>; 16]);
+
+impl LongDouble {
+    /// Returns the `long double` with the value bytes `bytes`, and zeroed
+    /// padding.
+    pub const fn from_bytes(bytes: [u8; VALUE_SIZE]) -> Self {
+        let mut storage = [MaybeUninit::new(0); 16];
+        let mut i = 0;
+        while i < VALUE_SIZE {
+            storage[i] = MaybeUninit::new(bytes[i]);
+            i += 1;
+        }
+        LongDouble(storage)
+    }
+
+    /// Returns the value bytes, i.e. without the padding.
+    pub const fn to_bytes(self) -> [u8; VALUE_SIZE] {
+        let mut bytes = [0; VALUE_SIZE];
+        let mut i = 0;
+        while i < VALUE_SIZE {
+            // SAFETY: the value bytes are initialized (see the invariant of `LongDouble`).
+            bytes[i] = unsafe { self.0[i].assume_init() };
+            i += 1;
+        }
+        bytes
+    }
+}
+
+impl Default for LongDouble {
+    /// Returns `0.0`, which is all zeros in the formats of `long double`.
+    fn default() -> Self {
+        LongDouble::from_bytes([0; VALUE_SIZE])
+    }
+}
+
+impl fmt::Debug for LongDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LongDouble").field(&self.to_bytes()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::mem::{align_of, size_of};
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(size_of::<LongDouble>(), 16);
+        assert_eq!(align_of::<LongDouble>(), 16);
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let bytes = [7; VALUE_SIZE];
+        assert_eq!(LongDouble::from_bytes(bytes).to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_default_is_zero() {
+        assert_eq!(LongDouble::default().to_bytes(), [0; VALUE_SIZE]);
+    }
+}
@@ -0,0 +1,271 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterparts of C++ pointers to members.
+//!
+//! `rs_bindings_from_cc` maps the C++ types as follows:
+//!
+//! - A pointer to a data member (`T C::*`) is bound as
+//!   `DataMemberPointer<C, T>`.
+//! - A pointer to a member function (e.g. `int (C::*)(int)`) is bound as
+//!   `MemberFunctionPointer<C, F>`, where the signature `F` is a function
+//!   pointer type that takes the object pointer (`this`) as its first
+//!   parameter (e.g. `extern "C" fn(*mut C, c_int) -> c_int`), or `()` if the
+//!   member function can't be called from Rust.
+//!
+//! Both have the layout of the corresponding C++ types in the Itanium C++ ABI,
+//! so that structs containing them keep their layout, and they can be passed
+//! back to C++. A `DataMemberPointer` can be used to access the member of an
+//! object, and a `MemberFunctionPointer` can be called if its signature
+//! implements `MemberFunctionSignature`.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A C++ pointer to a data member of type `T` of the class `C` (`T C::*`).
+#[repr(transparent)]
+pub struct DataMemberPointer<C, T> {
+    /// The offset of the member in bytes, or -1 for a null pointer.
+    offset: isize,
+    _phantom: PhantomData<fn() -> (C, T)>,
+}
+
+impl<C, T> DataMemberPointer<C, T> {
+    /// Returns the null pointer to a member (`nullptr` in C++).
+    pub const fn null() -> Self {
+        DataMemberPointer { offset: -1, _phantom: PhantomData }
+    }
+
+    pub const fn is_null(&self) -> bool {
+        self.offset == -1
+    }
+
+    /// Returns the offset of the member in bytes, or `None` for a null pointer.
+    pub const fn offset(&self) -> Option<usize> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.offset as usize)
+        }
+    }
+
+    /// Returns a reference to the member of `object` that `self` points to.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be null, and must point to a member of `C` (rather
+    /// than, e.g., to a member of a class derived from `C`).
+    pub unsafe fn get<'a>(&self, object: &'a C) -> &'a T {
+        &*(object as *const C).cast::<u8>().offset(self.offset).cast::<T>()
+    }
+
+    /// Returns a mutable reference to the member of `object` that `self`
+    /// points to.
+    ///
+    /// # Safety
+    ///
+    /// Same as for `get`. Additionally, the member must not be moved out of
+    /// if `C` is not `Unpin`.
+    pub unsafe fn get_mut<'a>(&self, object: &'a mut C) -> &'a mut T {
+        &mut *(object as *mut C).cast::<u8>().offset(self.offset).cast::<T>()
+    }
+}
+
+impl<C, T> Clone for DataMemberPointer<C, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, T> Copy for DataMemberPointer<C, T> {}
+
+impl<C, T> Default for DataMemberPointer<C, T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<C, T> PartialEq for DataMemberPointer<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<C, T> Eq for DataMemberPointer<C, T> {}
+
+impl<C, T> fmt::Debug for DataMemberPointer<C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DataMemberPointer").field(&self.offset()).finish()
+    }
+}
+
+/// A C++ pointer to a member function of the class `C`, with the signature
+/// `F`.
+#[repr(C)]
+pub struct MemberFunctionPointer<C, F> {
+    /// The address of a non-virtual function, or the offset into the vtable of
+    /// a virtual function (marked as described by the Itanium C++ ABI).
+    ptr: usize,
+    /// The adjustment of `this` to apply before calling the function.
+    adj: isize,
+    _phantom: PhantomData<fn() -> (C, F)>,
+}
+
+impl<C, F> MemberFunctionPointer<C, F> {
+    /// Returns the null pointer to a member function (`nullptr` in C++).
+    pub const fn null() -> Self {
+        MemberFunctionPointer { ptr: 0, adj: 0, _phantom: PhantomData }
+    }
+
+    pub const fn is_null(&self) -> bool {
+        self.ptr == 0
+    }
+}
+
+impl<C, F: MemberFunctionSignature<C>> MemberFunctionPointer<C, F> {
+    /// Calls the member function that `self` points to. The first argument is
+    /// the object pointer (`this`).
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be null, the object pointer must point to a valid
+    /// object of the class `C` (or of a class derived from it), and the
+    /// preconditions of the member function must hold.
+    pub unsafe fn call(&self, args: F::Args) -> F::Output {
+        F::call(self, args)
+    }
+}
+
+/// A signature of member functions of the class `C`, i.e. a function pointer
+/// type that takes the object pointer (`this`) as its first parameter.
+///
+/// `rs_bindings_from_cc` implements this trait for the signatures of the
+/// pointers to member functions in the API of a library, if `C` is a struct of
+/// the same library, using a thunk that calls the member function in C++.
+///
+/// # Safety
+///
+/// `call` must call the member function that `f` points to, with `args`.
+pub unsafe trait MemberFunctionSignature<C>: Sized {
+    /// The parameter types of the signature, as a tuple.
+    type Args;
+    /// The return type of the signature.
+    type Output;
+
+    /// Calls the member function that `f` points to.
+    ///
+    /// # Safety
+    ///
+    /// See `MemberFunctionPointer::call`.
+    unsafe fn call(f: &MemberFunctionPointer<C, Self>, args: Self::Args) -> Self::Output;
+}
+
+impl<C, F> Clone for MemberFunctionPointer<C, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, F> Copy for MemberFunctionPointer<C, F> {}
+
+impl<C, F> Default for MemberFunctionPointer<C, F> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<C, F> PartialEq for MemberFunctionPointer<C, F> {
+    fn eq(&self, other: &Self) -> bool {
+        // All null pointers to member functions compare equal, regardless of
+        // their adjustment.
+        self.ptr == other.ptr && (self.is_null() || self.adj == other.adj)
+    }
+}
+
+impl<C, F> Eq for MemberFunctionPointer<C, F> {}
+
+impl<C, F> fmt::Debug for MemberFunctionPointer<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemberFunctionPointer")
+            .field("ptr", &self.ptr)
+            .field("adj", &self.adj)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[repr(C)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn test_data_member_pointer() {
+        let y = DataMemberPointer::<Point, i32> { offset: 4, _phantom: PhantomData };
+        let mut point = Point { x: 1, y: 2 };
+        assert_eq!(y.offset(), Some(4));
+        assert_eq!(unsafe { *y.get(&point) }, 2);
+        unsafe { *y.get_mut(&mut point) = 3 };
+        assert_eq!(point.y, 3);
+        assert_eq!(point.x, 1);
+    }
+
+    #[test]
+    fn test_null() {
+        assert!(DataMemberPointer::<Point, i32>::default().is_null());
+        assert_eq!(DataMemberPointer::<Point, i32>::null().offset(), None);
+        assert!(MemberFunctionPointer::<Point, ()>::default().is_null());
+        assert_eq!(
+            MemberFunctionPointer::<Point, ()> { ptr: 0, adj: 8, _phantom: PhantomData },
+            MemberFunctionPointer::null()
+        );
+    }
+
+    type GetY = extern "C" fn(*const Point) -> i32;
+
+    /// Calls non-virtual "member functions" that are `extern "C"` functions.
+    unsafe impl MemberFunctionSignature<Point> for GetY {
+        type Args = (*const Point,);
+        type Output = i32;
+
+        unsafe fn call(f: &MemberFunctionPointer<Point, Self>, (this,): Self::Args) -> i32 {
+            let func = core::mem::transmute::<usize, GetY>(f.ptr);
+            func(this.cast::<u8>().offset(f.adj).cast::<Point>())
+        }
+    }
+
+    #[test]
+    fn test_call() {
+        extern "C" fn get_y(point: *const Point) -> i32 {
+            unsafe { (*point).y }
+        }
+        let f = MemberFunctionPointer::<Point, GetY> {
+            ptr: get_y as GetY as usize,
+            adj: 0,
+            _phantom: PhantomData,
+        };
+        let point = Point { x: 1, y: 2 };
+        assert_eq!(unsafe { f.call((&point,)) }, 2);
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(
+            core::mem::size_of::<DataMemberPointer<Point, i32>>(),
+            core::mem::size_of::<isize>()
+        );
+        assert_eq!(
+            core::mem::size_of::<MemberFunctionPointer<Point, ()>>(),
+            2 * core::mem::size_of::<usize>()
+        );
+        assert_eq!(
+            core::mem::align_of::<MemberFunctionPointer<Point, ()>>(),
+            core::mem::align_of::<usize>()
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Conversions of the data pointer and length of C++ types (e.g.
+//! `absl::Span`, `std::string_view` or `std::vector`) into Rust slices.
+//!
+//! Unlike C++, Rust does not allow for null data pointers in slices, even
+//! empty ones, whereas the data pointer of an empty C++ span or string is
+//! often null.
+
+use core::slice;
+
+/// Returns the `len` `T`s at `data` as a slice. `data` may be null if `len`
+/// is 0.
+///
+/// # Safety
+///
+/// If `len` is not 0, `data` must point to `len` initialized `T`s, which must
+/// not be mutated or destroyed for `'a`.
+pub unsafe fn from_raw_parts<'a, T>(data: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+/// Returns the `len` `T`s at `data` as a mutable slice. `data` may be null if
+/// `len` is 0.
+///
+/// # Safety
+///
+/// If `len` is not 0, `data` must point to `len` initialized `T`s, which must
+/// not be accessed other than through the returned slice or destroyed for
+/// `'a`.
+pub unsafe fn from_raw_parts_mut<'a, T>(data: *mut T, len: usize) -> &'a mut [T] {
+    if len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(data, len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn test_null_data() {
+        assert!(unsafe { from_raw_parts(ptr::null::<i32>(), 0) }.is_empty());
+        assert!(unsafe { from_raw_parts_mut(ptr::null_mut::<i32>(), 0) }.is_empty());
+    }
+
+    #[test]
+    fn test_elements() {
+        let mut elements = [1, 2, 3];
+        assert_eq!(unsafe { from_raw_parts(elements.as_ptr(), 2) }, &[1, 2]);
+        let slice = unsafe { from_raw_parts_mut(elements.as_mut_ptr(), 3) };
+        slice[2] = 4;
+        assert_eq!(elements, [1, 2, 4]);
+    }
+}
@@ -0,0 +1,45 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#![no_std]
+//! Rust counterpart of the C++ `wchar_t` type.
+//!
+//! `rs_bindings_from_cc` binds `wchar_t` as the Rust integer type with the
+//! size and signedness that `wchar_t` has on the target that the C++ code is
+//! compiled for: `u16` on Windows, `u32` on ARM Linux, and `i32` on most
+//! other platforms. `c_wchar` is the same type on the target that the Rust
+//! code is compiled for, so that code which calls the bindings can be written
+//! portably (like `core::ffi::c_char` for `char`).
+
+/// The Rust type that `wchar_t` is bound as on the current target.
+#[cfg(windows)]
+#[allow(non_camel_case_types)]
+pub type c_wchar = u16;
+
+/// The Rust type that `wchar_t` is bound as on the current target.
+#[cfg(all(
+    not(windows),
+    not(target_vendor = "apple"),
+    any(target_arch = "aarch64", target_arch = "arm")
+))]
+#[allow(non_camel_case_types)]
+pub type c_wchar = u32;
+
+/// The Rust type that `wchar_t` is bound as on the current target.
+#[cfg(not(any(
+    windows,
+    all(not(target_vendor = "apple"), any(target_arch = "aarch64", target_arch = "arm"))
+)))]
+#[allow(non_camel_case_types)]
+pub type c_wchar = i32;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let expected_size = if cfg!(windows) { 2 } else { 4 };
+        assert_eq!(core::mem::size_of::<c_wchar>(), expected_size);
+    }
+}